@@ -1,23 +1,30 @@
 #![feature(absolute_path)]
-use anyhow::{Context, Result};
+use anyhow::{anyhow, Context, Result};
 use clap::Parser;
 use custom_rpc::RpcAsset;
 use futures::FutureExt;
 use serde::Serialize;
-use std::{io::Write, path::PathBuf, sync::Arc};
-
-use crate::settings::{
-	BrokerSubcommands, CLICommandLineOptions, CLISettings, CliCommand::*,
-	LiquidityProviderSubcommands,
+use std::{io::Write, path::PathBuf, str::FromStr, sync::Arc};
+
+use crate::{
+	output::{ErrorClass, Output},
+	settings::{
+		BrokerSubcommands, CLICommandLineOptions, CLIConnectionSettings, CLISettings,
+		CliCommand::*, LiquidityProviderSubcommands, OfflineCallSubcommands, OfflineSubcommands,
+	},
 };
 use api::{
-	lp::LpApi, primitives::RedemptionAmount, queries::QueryApi, AccountId32, BrokerApi,
-	GovernanceApi, KeyPair, OperatorApi, StateChainApi, SwapDepositAddress,
+	lp::LpApi,
+	offline::{self, OfflineCall},
+	primitives::{Asset, ForeignChain, Hash, RedemptionAmount},
+	queries::QueryApi,
+	AccountId32, BrokerApi, GovernanceApi, KeyPair, OperatorApi, StateChainApi,
 };
 use cf_chains::eth::Address as EthereumAddress;
 use chainflip_api as api;
 use utilities::{clean_hex_address, task_scope::task_scope};
 
+mod output;
 mod settings;
 
 #[tokio::main]
@@ -25,117 +32,175 @@ async fn main() {
 	// TODO: call this implicitly from within the API?
 	api::use_chainflip_account_id_encoding();
 
-	std::process::exit(match run_cli().await {
+	let command_line_opts = CLICommandLineOptions::parse();
+	let output = Output::new(command_line_opts.output, command_line_opts.assume_yes);
+
+	std::process::exit(match run_cli(command_line_opts, output).await {
 		Ok(_) => 0,
-		Err(err) => {
-			eprintln!("Error: {err:#}");
-			1
-		},
+		Err(err) => output.error(&err),
 	})
 }
 
-async fn run_cli() -> Result<()> {
-	let command_line_opts = CLICommandLineOptions::parse();
+#[derive(Serialize)]
+struct TxResult {
+	tx_hash: Hash,
+}
+
+#[derive(Serialize)]
+struct DepositAddressResult {
+	deposit_address: String,
+}
 
+#[derive(Serialize)]
+struct BoundAddressResult {
+	address: Option<EthereumAddress>,
+}
+
+async fn run_cli(command_line_opts: CLICommandLineOptions, output: Output) -> Result<()> {
 	// Generating keys does not require the settings, so run it before them
 	if let GenerateKeys { json, path, seed_phrase } = command_line_opts.cmd {
-		return generate_keys(json, path, seed_phrase)
+		return generate_keys(output, json, path, seed_phrase)
+	}
+
+	// Offline commands are run by machines that may not have any keys configured.
+	if let Offline(subcommand) = command_line_opts.cmd.clone() {
+		return offline(command_line_opts, output, subcommand).await
 	}
 
-	let cli_settings = CLISettings::new(command_line_opts.clone()).context(
-		r#"Please ensure your config file path is configured correctly and the file is valid.
+	let cli_settings = CLISettings::new(command_line_opts.clone())
+		.context(ErrorClass::Validation)
+		.context(
+			r#"Please ensure your config file path is configured correctly and the file is valid.
 			You can also just set all configurations required as command line arguments."#,
-	)?;
+		)?;
 
-	println!(
+	output.info(format_args!(
 		"Connecting to state chain node at: `{}` and using private key located at: `{}`",
 		cli_settings.state_chain.ws_endpoint,
 		cli_settings.state_chain.signing_key_file.display()
-	);
+	));
 
 	task_scope(|scope| {
 		async move {
-			let api = StateChainApi::connect(scope, cli_settings.state_chain).await?;
+			let api = StateChainApi::connect(scope, cli_settings.state_chain)
+				.await
+				.context(ErrorClass::Connection)?;
 			match command_line_opts.cmd {
 				Broker(BrokerSubcommands::RequestSwapDepositAddress(params)) => {
 					let destination_asset =
-						RpcAsset::try_from((params.destination_asset, params.destination_chain))?
-							.try_into()?;
-					let SwapDepositAddress { address, .. } = api
+						parse_asset(params.destination_asset, params.destination_chain)?;
+					let destination_address = chainflip_api::clean_foreign_chain_address(
+						destination_asset.into(),
+						&params.destination_address,
+					)
+					.context(ErrorClass::Validation)?;
+					let deposit_address = api
 						.broker_api()
 						.request_swap_deposit_address(
-							RpcAsset::try_from((params.source_asset, params.source_chain))?
-								.try_into()?,
+							parse_asset(params.source_asset, params.source_chain)?,
 							destination_asset,
-							chainflip_api::clean_foreign_chain_address(
-								destination_asset.into(),
-								&params.destination_address,
-							)?,
+							destination_address,
 							params.broker_commission,
 							None,
 						)
 						.await?;
-					println!("Deposit Address: {address}");
+					output.result(
+						&deposit_address,
+						format_args!("Deposit Address: {}", deposit_address.address),
+					)?;
 				},
 				LiquidityProvider(
 					LiquidityProviderSubcommands::RequestLiquidityDepositAddress { asset, chain },
 				) => {
-					let asset = RpcAsset::try_from((asset, chain))?;
-					let address =
-						api.lp_api().request_liquidity_deposit_address(asset.try_into()?).await?;
-					println!("Deposit Address: {address}");
+					let deposit_address = api
+						.lp_api()
+						.request_liquidity_deposit_address(parse_asset(asset, chain)?)
+						.await?
+						.to_string();
+					output.result(
+						&DepositAddressResult { deposit_address: deposit_address.clone() },
+						format_args!("Deposit Address: {deposit_address}"),
+					)?;
 				},
 				LiquidityProvider(
 					LiquidityProviderSubcommands::RegisterLiquidityRefundAddress { chain, address },
 				) => {
-					let lra_address = chainflip_api::clean_foreign_chain_address(chain, &address)?;
+					let lra_address = chainflip_api::clean_foreign_chain_address(chain, &address)
+						.context(ErrorClass::Validation)?;
 					let tx_hash =
 						api.lp_api().register_liquidity_refund_address(lra_address).await?;
-					println!("Liquidity Refund address registered. Tx hash: {tx_hash}");
+					output.result(
+						&TxResult { tx_hash },
+						format_args!("Liquidity Refund address registered. Tx hash: {tx_hash}"),
+					)?;
 				},
 				Redeem { amount, eth_address, executor_address } => {
-					request_redemption(api, amount, eth_address, executor_address).await?;
+					request_redemption(api, output, amount, eth_address, executor_address).await?;
 				},
 				BindRedeemAddress { eth_address } => {
-					bind_redeem_address(api.operator_api(), &eth_address).await?;
+					bind_redeem_address(api.operator_api(), output, &eth_address).await?;
 				},
 				BindExecutorAddress { eth_address } => {
-					bind_executor_address(api.operator_api(), &eth_address).await?;
+					bind_executor_address(api.operator_api(), output, &eth_address).await?;
 				},
 				GetBoundRedeemAddress {} => {
-					get_bound_redeem_address(api.query_api()).await?;
+					get_bound_redeem_address(api.query_api(), output).await?;
 				},
 				GetBoundExecutorAddress {} => {
-					get_bound_executor_address(api.query_api()).await?;
+					get_bound_executor_address(api.query_api(), output).await?;
 				},
 				RegisterAccountRole { role } => {
-					println!(
+					output.info(format_args!(
 					"Submitting `register-account-role` with role: {role:?}. This cannot be reversed for your account.",
-				);
-					if !confirm_submit() {
-						return Ok(())
-					}
+				));
+					output.confirm()?;
 					let tx_hash = api.operator_api().register_account_role(role).await?;
-					println!("Account role set at tx {tx_hash:#x}.");
+					output.result(
+						&TxResult { tx_hash },
+						format_args!("Account role set at tx {tx_hash:#x}."),
+					)?;
 				},
 				Rotate {} => {
 					let tx_hash = api.operator_api().rotate_session_keys().await?;
-					println!("Session key rotated at tx {tx_hash:#x}.");
+					output.result(
+						&TxResult { tx_hash },
+						format_args!("Session key rotated at tx {tx_hash:#x}."),
+					)?;
 				},
 				StopBidding {} => {
-					api.operator_api().stop_bidding().await?;
+					let tx_hash = api.operator_api().stop_bidding().await?;
+					output.result(
+						&TxResult { tx_hash },
+						format_args!("Account stopped bidding, in tx {tx_hash:#x}."),
+					)?;
 				},
 				StartBidding {} => {
-					api.operator_api().start_bidding().await?;
+					let tx_hash = api.operator_api().start_bidding().await?;
+					output.result(
+						&TxResult { tx_hash },
+						format_args!("Account started bidding at tx {tx_hash:#x}."),
+					)?;
 				},
 				VanityName { name } => {
-					api.operator_api().set_vanity_name(name).await?;
+					let tx_hash = api.operator_api().set_vanity_name(name).await?;
+					output.result(
+						&TxResult { tx_hash },
+						format_args!("Vanity name set at tx {tx_hash:#x}."),
+					)?;
 				},
-				PreUpdateCheck {} => pre_update_check(api.query_api()).await?,
+				PreUpdateCheck {} => pre_update_check(api.query_api(), output).await?,
 				ForceRotation {} => {
-					api.governance_api().force_rotation().await?;
+					output.info("Submitting governance proposal for rotation.");
+					let tx_hash = api.governance_api().force_rotation().await?;
+					output.result(
+						&TxResult { tx_hash },
+						format_args!(
+							"If you're the governance dictator, the rotation will begin soon."
+						),
+					)?;
 				},
 				GenerateKeys { .. } => unreachable!("GenerateKeys is handled above"),
+				Offline(_) => unreachable!("Offline commands are handled above"),
 			};
 			Ok(())
 		}
@@ -144,8 +209,15 @@ async fn run_cli() -> Result<()> {
 	.await
 }
 
+fn parse_asset(asset: Asset, chain: Option<ForeignChain>) -> Result<Asset> {
+	RpcAsset::try_from((asset, chain))
+		.and_then(<RpcAsset as TryInto<Asset>>::try_into)
+		.context(ErrorClass::Validation)
+}
+
 async fn request_redemption(
 	api: StateChainApi,
+	output: Output,
 	amount: Option<f64>,
 	supplied_redeem_address: String,
 	supplied_executor_address: Option<String>,
@@ -153,13 +225,16 @@ async fn request_redemption(
 	// Check validity of the redeem address
 	let redeem_address = EthereumAddress::from(
 		clean_hex_address::<[u8; 20]>(&supplied_redeem_address)
+			.context(ErrorClass::Validation)
 			.context("Invalid redeem address")?,
 	);
 
 	// Check the validity of the executor address
 	let executor_address = if let Some(address) = supplied_executor_address {
 		Some(EthereumAddress::from(
-			clean_hex_address::<[u8; 20]>(&address).context("Invalid executor address")?,
+			clean_hex_address::<[u8; 20]>(&address)
+				.context(ErrorClass::Validation)
+				.context("Invalid executor address")?,
 		))
 	} else {
 		None
@@ -170,127 +245,266 @@ async fn request_redemption(
 		Some(amount_float) => {
 			let atomic_amount = (amount_float * 10_f64.powi(18)) as u128;
 
-			println!(
+			output.info(format_args!(
 				"Submitting redemption with amount `{amount_float}` FLIP (`{atomic_amount}` Flipperinos) to ETH address `{redeem_address:?}`."
-			);
+			));
 
 			RedemptionAmount::Exact(atomic_amount)
 		},
 		None => {
-			println!("Submitting redemption with MAX amount to ETH address `{redeem_address:?}`.");
+			output.info(format_args!(
+				"Submitting redemption with MAX amount to ETH address `{redeem_address:?}`."
+			));
 
 			RedemptionAmount::Max
 		},
 	};
 
-	if !confirm_submit() {
-		return Ok(())
-	}
+	output.confirm()?;
 
 	let tx_hash = api
 		.operator_api()
 		.request_redemption(amount, redeem_address, executor_address)
 		.await?;
 
-	println!(
-		"Your redemption request has transaction hash: `{tx_hash:#x}`. View your redemption's progress on the funding app."
-	);
-
-	Ok(())
+	output.result(
+		&TxResult { tx_hash },
+		format_args!(
+			"Your redemption request has transaction hash: `{tx_hash:#x}`. View your redemption's progress on the funding app."
+		),
+	)
 }
 
-async fn bind_redeem_address(api: Arc<impl OperatorApi + Sync>, eth_address: &str) -> Result<()> {
+async fn bind_redeem_address(
+	api: Arc<impl OperatorApi + Sync>,
+	output: Output,
+	eth_address: &str,
+) -> Result<()> {
 	let eth_address = EthereumAddress::from(
-		clean_hex_address::<[u8; 20]>(eth_address).context("Invalid ETH address supplied")?,
+		clean_hex_address::<[u8; 20]>(eth_address)
+			.context(ErrorClass::Validation)
+			.context("Invalid ETH address supplied")?,
 	);
 
-	println!(
+	output.info(format_args!(
 		"Binding your account to a redemption address is irreversible. You will only ever be able to redeem to this address: {eth_address:?}.",
-	);
-	if !confirm_submit() {
-		return Ok(())
-	}
+	));
+	output.confirm()?;
 
 	let tx_hash = api.bind_redeem_address(eth_address).await?;
 
-	println!("Account bound to redeem address {eth_address}, transaction hash: `{tx_hash:#x}`.");
-
-	Ok(())
+	output.result(
+		&TxResult { tx_hash },
+		format_args!(
+			"Account bound to redeem address {eth_address}, transaction hash: `{tx_hash:#x}`."
+		),
+	)
 }
 
-async fn bind_executor_address(api: Arc<impl OperatorApi + Sync>, eth_address: &str) -> Result<()> {
+async fn bind_executor_address(
+	api: Arc<impl OperatorApi + Sync>,
+	output: Output,
+	eth_address: &str,
+) -> Result<()> {
 	let eth_address = EthereumAddress::from(
-		clean_hex_address::<[u8; 20]>(eth_address).context("Invalid ETH address supplied")?,
+		clean_hex_address::<[u8; 20]>(eth_address)
+			.context(ErrorClass::Validation)
+			.context("Invalid ETH address supplied")?,
 	);
 
-	println!(
+	output.info(format_args!(
 		"Binding your account to an executor address is irreversible. You will only ever be able to execute registered redemptions with this address: {eth_address:?}.",
-	);
-	if !confirm_submit() {
-		return Ok(())
-	}
+	));
+	output.confirm()?;
 
 	let tx_hash = api.bind_executor_address(eth_address).await?;
 
-	println!("Account bound to executor address {eth_address}, transaction hash: `{tx_hash:#x}`.");
-
-	Ok(())
+	output.result(
+		&TxResult { tx_hash },
+		format_args!(
+			"Account bound to executor address {eth_address}, transaction hash: `{tx_hash:#x}`."
+		),
+	)
 }
 
-async fn get_bound_redeem_address(api: QueryApi) -> Result<()> {
-	if let Some(bound_address) = api.get_bound_redeem_address(None, None).await? {
-		println!("Your account is bound to redeem address: {bound_address:?}");
-	} else {
-		println!("Your account is not bound to any redeem address.");
-	}
+async fn get_bound_redeem_address(api: QueryApi, output: Output) -> Result<()> {
+	let address = api.get_bound_redeem_address(None, None).await?;
 
-	Ok(())
+	output.result(
+		&BoundAddressResult { address },
+		match address {
+			Some(bound_address) =>
+				format!("Your account is bound to redeem address: {bound_address:?}"),
+			None => "Your account is not bound to any redeem address.".to_owned(),
+		},
+	)
 }
 
-async fn get_bound_executor_address(api: QueryApi) -> Result<()> {
-	if let Some(bound_address) = api.get_bound_executor_address(None, None).await? {
-		println!("Your account is bound to executor address: {bound_address:?}");
-	} else {
-		println!("Your account is not bound to any executor address.");
-	}
+async fn get_bound_executor_address(api: QueryApi, output: Output) -> Result<()> {
+	let address = api.get_bound_executor_address(None, None).await?;
 
-	Ok(())
+	output.result(
+		&BoundAddressResult { address },
+		match address {
+			Some(bound_address) =>
+				format!("Your account is bound to executor address: {bound_address:?}"),
+			None => "Your account is not bound to any executor address.".to_owned(),
+		},
+	)
 }
 
-async fn pre_update_check(api: QueryApi) -> Result<()> {
+async fn pre_update_check(api: QueryApi, output: Output) -> Result<()> {
 	let can_update = api.pre_update_check(None, None).await?;
 
-	println!("Your node is an authority: {}", can_update.is_authority);
-	println!("A rotation is occurring: {}", can_update.rotation);
+	let mut text = format!(
+		"Your node is an authority: {}\nA rotation is occurring: {}",
+		can_update.is_authority, can_update.rotation
+	);
 	if let Some(blocks) = can_update.next_block_in {
-		println!("Your validator will produce a block in {} blocks", blocks);
+		text.push_str(&format!("\nYour validator will produce a block in {} blocks", blocks));
 	}
 
-	Ok(())
+	output.result(&can_update, text)
 }
 
-fn confirm_submit() -> bool {
-	use std::{io, io::*};
-
-	loop {
-		print!("Do you wish to proceed? [y/n] > ");
-		std::io::stdout().flush().unwrap();
-		let mut input = String::new();
-		io::stdin().read_line(&mut input).expect("Error: Failed to get user input");
-
-		let input = input.trim();
-
-		match input {
-			"y" | "yes" | "1" | "true" | "ofc" => {
-				println!("Submitting...");
-				return true
-			},
-			"n" | "no" | "0" | "false" | "nah" => {
-				println!("Ok, exiting...");
-				return false
-			},
-			_ => continue,
-		}
+async fn offline(
+	command_line_opts: CLICommandLineOptions,
+	output: Output,
+	subcommand: OfflineSubcommands,
+) -> Result<()> {
+	fn parse_eth_address(address: &str) -> Result<EthereumAddress> {
+		Ok(EthereumAddress::from(
+			clean_hex_address::<[u8; 20]>(address)
+				.context(ErrorClass::Validation)
+				.context("Invalid ETH address supplied")?,
+		))
+	}
+
+	fn parse_session_key(key: &str) -> Result<[u8; 32]> {
+		clean_hex_address::<[u8; 32]>(key)
+			.context(ErrorClass::Validation)
+			.context("Invalid session key supplied")
+	}
+
+	let connection_settings = || {
+		CLIConnectionSettings::new(command_line_opts.clone())
+			.context(ErrorClass::Validation)
+			.context("Please ensure your State Chain endpoint is configured correctly.")
+	};
+
+	match subcommand {
+		OfflineSubcommands::Build { account_id, nonce, lifetime, payload_file, call } => {
+			let account_id = AccountId32::from_str(&account_id)
+				.map_err(|e| anyhow!("{e:?}"))
+				.context(ErrorClass::Validation)
+				.context("Invalid account id supplied")?;
+			let call = match call {
+				OfflineCallSubcommands::Redeem { amount, eth_address, executor_address } =>
+					OfflineCall::Redeem {
+						amount: match amount {
+							Some(amount_float) =>
+								RedemptionAmount::Exact((amount_float * 10_f64.powi(18)) as u128),
+							None => RedemptionAmount::Max,
+						},
+						address: parse_eth_address(&eth_address)?,
+						executor: executor_address
+							.map(|address| parse_eth_address(&address))
+							.transpose()?,
+					},
+				OfflineCallSubcommands::BindRedeemAddress { eth_address } =>
+					OfflineCall::BindRedeemAddress { address: parse_eth_address(&eth_address)? },
+				OfflineCallSubcommands::BindExecutorAddress { eth_address } =>
+					OfflineCall::BindExecutorAddress { address: parse_eth_address(&eth_address)? },
+				OfflineCallSubcommands::StopBidding => OfflineCall::StopBidding,
+				OfflineCallSubcommands::StartBidding => OfflineCall::StartBidding,
+				OfflineCallSubcommands::SetKeys { aura_key, grandpa_key } => OfflineCall::SetKeys {
+					aura_key: parse_session_key(&aura_key)?,
+					grandpa_key: parse_session_key(&grandpa_key)?,
+				},
+			};
+
+			let (payload, expiry_block) = offline::build_unsigned_payload(
+				&connection_settings()?.state_chain.ws_endpoint,
+				account_id,
+				call,
+				nonce,
+				lifetime,
+			)
+			.await?;
+
+			std::fs::write(&payload_file, offline::encode_hex(&payload))
+				.with_context(|| format!("Could not write to {}", payload_file.display()))?;
+
+			#[derive(Serialize)]
+			struct BuildResult {
+				payload_file: PathBuf,
+				nonce: u32,
+				expiry_block: u32,
+			}
+			output.result(
+				&BuildResult { payload_file: payload_file.clone(), nonce: payload.nonce, expiry_block },
+				format_args!(
+					"Unsigned payload with nonce {} written to {}. It must be signed and submitted before block {expiry_block}.",
+					payload.nonce,
+					payload_file.display()
+				),
+			)
+		},
+		OfflineSubcommands::Sign { payload_file, signed_file, signing_key_file, seed_phrase } => {
+			let payload: offline::UnsignedPayload = offline::decode_hex(
+				&std::fs::read_to_string(&payload_file)
+					.with_context(|| format!("Could not read {}", payload_file.display()))?,
+			)
+			.context(ErrorClass::Validation)
+			.context("Invalid payload file")?;
+
+			let signing_key = match (signing_key_file, seed_phrase) {
+				(Some(signing_key_file), _) => offline::signing_key_from_file(&signing_key_file),
+				(None, Some(seed_phrase)) => offline::signing_key_from_seed_phrase(&seed_phrase),
+				(None, None) => unreachable!("Clap requires one of the keys"),
+			}
+			.context(ErrorClass::Validation)?;
+
+			output.info(format_args!(
+				"Signing {:?} for account {} with nonce {}.",
+				payload.call, payload.signer, payload.nonce
+			));
+			output.confirm()?;
+
+			let extrinsic =
+				offline::sign_payload(payload, signing_key).context(ErrorClass::Validation)?;
+
+			std::fs::write(&signed_file, offline::encode_hex(&extrinsic))
+				.with_context(|| format!("Could not write to {}", signed_file.display()))?;
+
+			#[derive(Serialize)]
+			struct SignResult {
+				signed_file: PathBuf,
+			}
+			output.result(
+				&SignResult { signed_file: signed_file.clone() },
+				format_args!("Signed extrinsic written to {}.", signed_file.display()),
+			)
+		},
+		OfflineSubcommands::Submit { signed_file } => {
+			let extrinsic: offline::UncheckedExtrinsic = offline::decode_hex(
+				&std::fs::read_to_string(&signed_file)
+					.with_context(|| format!("Could not read {}", signed_file.display()))?,
+			)
+			.context(ErrorClass::Validation)
+			.context("Invalid signed extrinsic file")?;
+
+			let tx_hash = offline::submit_signed_extrinsic(
+				&connection_settings()?.state_chain.ws_endpoint,
+				extrinsic,
+			)
+			.await?;
+
+			output.result(
+				&TxResult { tx_hash },
+				format_args!("Submitted the signed extrinsic. Tx hash: {tx_hash:#x}"),
+			)
+		},
 	}
 }
 
@@ -304,7 +518,12 @@ const DISCLAIMER: &str = r#"
 "#;
 
 /// Entry point for the [settings::CliCommand::GenerateKeys] subcommand.
-fn generate_keys(json: bool, path: Option<PathBuf>, seed_phrase: Option<String>) -> Result<()> {
+fn generate_keys(
+	output: Output,
+	json: bool,
+	path: Option<PathBuf>,
+	seed_phrase: Option<String>,
+) -> Result<()> {
 	#[derive(Serialize)]
 	struct Keys {
 		node_key: KeyPair,
@@ -365,7 +584,9 @@ fn generate_keys(json: bool, path: Option<PathBuf>, seed_phrase: Option<String>)
 
 	let keys = Keys::new(seed_phrase)?;
 
-	if json {
+	if output.is_json() {
+		output.result(&keys, "")?;
+	} else if json {
 		println!("{}", serde_json::to_string_pretty(&keys)?);
 	} else {
 		eprintln!();
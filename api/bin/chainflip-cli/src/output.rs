@@ -0,0 +1,242 @@
+use std::{
+	fmt::Display,
+	io::{IsTerminal, Write},
+};
+
+use anyhow::{anyhow, Result};
+use chainflip_api::{DryRunError, ExtrinsicError, FinalizationError, InBlockError};
+use serde::Serialize;
+
+#[derive(clap::ValueEnum, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum OutputFormat {
+	/// Human readable output.
+	Text,
+	/// A single JSON document on stdout. Any other messages are written to stderr.
+	Json,
+}
+
+/// Broad classes of failure, each with its own exit code so that scripts can react to them
+/// without parsing error messages.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ErrorClass {
+	Other,
+	/// The input or configuration is invalid. This matches the exit code clap uses for usage
+	/// errors.
+	Validation,
+	/// The State Chain node could not be reached.
+	Connection,
+	/// The extrinsic was rejected or failed when executed on the State Chain.
+	Dispatch,
+	/// The user declined to submit the extrinsic.
+	Aborted,
+}
+
+impl ErrorClass {
+	pub fn exit_code(self) -> i32 {
+		match self {
+			ErrorClass::Other => 1,
+			ErrorClass::Validation => 2,
+			ErrorClass::Connection => 3,
+			ErrorClass::Dispatch => 4,
+			ErrorClass::Aborted => 5,
+		}
+	}
+
+	/// Errors are classified either explicitly, by adding the class as context, or by the engine
+	/// error that caused them.
+	pub fn of(error: &anyhow::Error) -> Self {
+		if let Some(class) = error.downcast_ref::<ErrorClass>() {
+			return *class
+		}
+
+		for cause in error.chain() {
+			if matches!(
+				cause.downcast_ref::<ExtrinsicError<InBlockError>>(),
+				Some(ExtrinsicError::Dispatch(_))
+			) || matches!(
+				cause.downcast_ref::<ExtrinsicError<FinalizationError>>(),
+				Some(ExtrinsicError::Dispatch(_))
+			) {
+				return ErrorClass::Dispatch
+			}
+			match cause.downcast_ref::<DryRunError>() {
+				Some(DryRunError::InvalidTransaction(_) | DryRunError::Dispatch(_)) =>
+					return ErrorClass::Dispatch,
+				Some(DryRunError::RpcCallError(_)) => return ErrorClass::Connection,
+				_ => {},
+			}
+		}
+
+		ErrorClass::Other
+	}
+}
+
+impl Display for ErrorClass {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		f.write_str(match self {
+			ErrorClass::Other => "Error",
+			ErrorClass::Validation => "Invalid input",
+			ErrorClass::Connection => "Could not connect to the State Chain",
+			ErrorClass::Dispatch => "The transaction failed",
+			ErrorClass::Aborted => "Aborted by user",
+		})
+	}
+}
+
+#[derive(Serialize)]
+#[serde(tag = "status", rename_all = "snake_case")]
+enum Document<'a, T> {
+	Success { result: &'a T },
+	Error { error: ErrorDocument },
+}
+
+#[derive(Serialize)]
+struct ErrorDocument {
+	class: ErrorClass,
+	message: String,
+}
+
+/// Writes everything the CLI outputs, so that in JSON mode stdout only ever contains the result
+/// document.
+#[derive(Clone, Copy, Debug)]
+pub struct Output {
+	format: OutputFormat,
+	assume_yes: bool,
+}
+
+impl Output {
+	pub fn new(format: OutputFormat, assume_yes: bool) -> Self {
+		Self { format, assume_yes }
+	}
+
+	pub fn is_json(&self) -> bool {
+		self.format == OutputFormat::Json
+	}
+
+	/// Progress and informational messages that are not part of the result.
+	pub fn info(&self, message: impl Display) {
+		match self.format {
+			OutputFormat::Text => println!("{message}"),
+			OutputFormat::Json => eprintln!("{message}"),
+		}
+	}
+
+	pub fn result<T: Serialize>(&self, result: &T, text: impl Display) -> Result<()> {
+		match self.format {
+			OutputFormat::Text => println!("{text}"),
+			OutputFormat::Json =>
+				println!("{}", serde_json::to_string_pretty(&Document::Success { result })?),
+		}
+		Ok(())
+	}
+
+	/// Reports the error and returns the exit code to use.
+	pub fn error(&self, error: &anyhow::Error) -> i32 {
+		let class = ErrorClass::of(error);
+		match self.format {
+			OutputFormat::Text =>
+				if class != ErrorClass::Aborted {
+					eprintln!("Error: {error:#}");
+				},
+			OutputFormat::Json => println!(
+				"{}",
+				serde_json::to_string_pretty(&Document::<()>::Error {
+					error: ErrorDocument { class, message: format!("{error:#}") },
+				})
+				.expect("Error document is always serializable")
+			),
+		}
+		class.exit_code()
+	}
+
+	/// Asks the user to confirm the submission. Returns an error classified as
+	/// [ErrorClass::Aborted] if they decline. Without `--yes`, the user can only be asked if stdin
+	/// is a terminal.
+	pub fn confirm(&self) -> Result<()> {
+		if self.assume_yes {
+			return Ok(())
+		}
+		if !std::io::stdin().is_terminal() {
+			return Err(anyhow!(
+				"Confirmation is required but stdin is not a terminal. Use --yes to confirm non-interactively."
+			)
+			.context(ErrorClass::Validation))
+		}
+
+		loop {
+			self.prompt("Do you wish to proceed? [y/n] > ");
+			let mut input = String::new();
+			if std::io::stdin().read_line(&mut input)? == 0 {
+				// EOF, there is no one left to answer.
+				return Err(anyhow!(ErrorClass::Aborted))
+			}
+
+			match input.trim() {
+				"y" | "yes" | "1" | "true" | "ofc" => {
+					self.info("Submitting...");
+					return Ok(())
+				},
+				"n" | "no" | "0" | "false" | "nah" => {
+					self.info("Ok, exiting...");
+					return Err(anyhow!(ErrorClass::Aborted))
+				},
+				_ => continue,
+			}
+		}
+	}
+
+	fn prompt(&self, prompt: &str) {
+		match self.format {
+			OutputFormat::Text => {
+				print!("{prompt}");
+				std::io::stdout().flush().unwrap();
+			},
+			OutputFormat::Json => {
+				eprint!("{prompt}");
+				std::io::stderr().flush().unwrap();
+			},
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn errors_are_classified_by_context() {
+		assert_eq!(ErrorClass::of(&anyhow!("unclassified")), ErrorClass::Other);
+		assert_eq!(
+			ErrorClass::of(
+				&anyhow!("bad address")
+					.context(ErrorClass::Validation)
+					.context("Invalid redeem address")
+			),
+			ErrorClass::Validation
+		);
+		assert_eq!(ErrorClass::of(&anyhow!(ErrorClass::Aborted)), ErrorClass::Aborted);
+	}
+
+	#[test]
+	fn json_documents_have_a_stable_shape() {
+		assert_eq!(
+			serde_json::to_value(Document::Success { result: &serde_json::json!({ "a": 1 }) })
+				.unwrap(),
+			serde_json::json!({ "status": "success", "result": { "a": 1 } })
+		);
+		assert_eq!(
+			serde_json::to_value(Document::<()>::Error {
+				error: ErrorDocument {
+					class: ErrorClass::Connection,
+					message: "connection refused".to_owned()
+				}
+			})
+			.unwrap(),
+			serde_json::json!({
+				"status": "error",
+				"error": { "class": "connection", "message": "connection refused" }
+			})
+		);
+	}
+}
@@ -1,10 +1,11 @@
+use crate::output::OutputFormat;
 use chainflip_api::primitives::{AccountRole, Asset, ForeignChain};
 pub use chainflip_engine::settings::StateChain;
 use chainflip_engine::{
 	constants::{CONFIG_ROOT, DEFAULT_CONFIG_ROOT},
 	settings::{
-		resolve_settings_path, CfSettings, Eth, EthOptions, PathResolutionExpectation,
-		StateChainOptions, DEFAULT_SETTINGS_DIR,
+		resolve_settings_path, validate_websocket_endpoint, CfSettings, Eth, EthOptions,
+		PathResolutionExpectation, StateChainOptions, DEFAULT_SETTINGS_DIR,
 	},
 };
 use clap::Parser;
@@ -27,6 +28,16 @@ pub struct CLICommandLineOptions {
 	#[clap(flatten)]
 	eth_opts: EthOptions,
 
+	/// Output format. In json mode, stdout only contains a single JSON document with the result
+	/// or the error.
+	#[clap(long = "output", global = true, value_enum, default_value = "text")]
+	pub output: OutputFormat,
+
+	/// Submit without asking for confirmation. Required to submit irreversible extrinsics when
+	/// stdin is not a terminal.
+	#[clap(short = 'y', long = "yes", global = true)]
+	pub assume_yes: bool,
+
 	#[clap(subcommand)]
 	pub cmd: CliCommand,
 }
@@ -54,6 +65,8 @@ impl Default for CLICommandLineOptions {
 			config_root: DEFAULT_CONFIG_ROOT.to_owned(),
 			state_chain_opts: StateChainOptions::default(),
 			eth_opts: EthOptions::default(),
+			output: OutputFormat::Text,
+			assume_yes: false,
 			// an arbitrary simple command
 			cmd: CliCommand::StopBidding {},
 		}
@@ -99,6 +112,81 @@ pub enum LiquidityProviderSubcommands {
 	RegisterLiquidityRefundAddress { chain: ForeignChain, address: String },
 }
 
+#[derive(clap::Subcommand, Clone, Debug)]
+pub enum OfflineCallSubcommands {
+	/// Request a redemption.
+	Redeem {
+		/// Amount to redeem in FLIP (omit this option to redeem all available FLIP)
+		#[clap(long = "exact")]
+		amount: Option<f64>,
+		/// The Ethereum address you wish to redeem your FLIP to.
+		eth_address: String,
+		/// Optional executor address. If specified, only this address will be able to execute
+		/// the redemption.
+		executor_address: Option<String>,
+	},
+	/// Irreversibly restrict the account to only be able to redeem to this address.
+	BindRedeemAddress { eth_address: String },
+	/// Irreversibly restrict the account to only be able to execute redemptions with this
+	/// address.
+	BindExecutorAddress { eth_address: String },
+	/// Stop bidding in auctions.
+	StopBidding,
+	/// Start bidding in auctions.
+	StartBidding,
+	/// Set the session keys of the validator node.
+	SetKeys {
+		/// Hex encoded Aura (sr25519) public key
+		aura_key: String,
+		/// Hex encoded Grandpa (ed25519) public key
+		grandpa_key: String,
+	},
+}
+
+#[derive(clap::Subcommand, Clone, Debug)]
+pub enum OfflineSubcommands {
+	/// Build an unsigned extrinsic for an account whose key is kept offline. This connects to the
+	/// State Chain, but doesn't need the account's key.
+	Build {
+		/// The SS58 encoded account that will sign the extrinsic.
+		#[clap(long)]
+		account_id: String,
+		/// Defaults to the next nonce of the account.
+		#[clap(long)]
+		nonce: Option<u32>,
+		/// The number of blocks the extrinsic remains valid for. This has to cover the time it
+		/// takes to sign and submit it.
+		#[clap(long, default_value = "2400")]
+		lifetime: u32,
+		/// File to write the unsigned payload to.
+		#[clap(long, parse(from_os_str))]
+		payload_file: PathBuf,
+		#[clap(subcommand)]
+		call: OfflineCallSubcommands,
+	},
+	/// Sign a payload created by `offline build`. This does not connect to the network.
+	Sign {
+		/// File containing the unsigned payload.
+		#[clap(long, parse(from_os_str))]
+		payload_file: PathBuf,
+		/// File to write the signed extrinsic to.
+		#[clap(long, parse(from_os_str))]
+		signed_file: PathBuf,
+		/// File containing the hex encoded signing key.
+		#[clap(long, parse(from_os_str), required_unless_present = "seed-phrase")]
+		signing_key_file: Option<PathBuf>,
+		/// Seed phrase to derive the signing key from, as generated by `generate-keys`.
+		#[clap(long, conflicts_with = "signing-key-file")]
+		seed_phrase: Option<String>,
+	},
+	/// Submit an extrinsic signed by `offline sign`.
+	Submit {
+		/// File containing the signed extrinsic.
+		#[clap(long, parse(from_os_str))]
+		signed_file: PathBuf,
+	},
+}
+
 #[derive(Parser, Clone, Debug)]
 pub enum CliCommand {
 	/// Broker specific commands
@@ -107,6 +195,9 @@ pub enum CliCommand {
 	/// Liquidity provider specific commands
 	#[clap(subcommand, name = "lp")]
 	LiquidityProvider(LiquidityProviderSubcommands),
+	/// Build, sign and submit extrinsics with a key that is kept on an offline machine
+	#[clap(subcommand)]
+	Offline(OfflineSubcommands),
 	#[clap(
 		about = "Request a redemption. After requesting the redemption, please proceed to the  to complete the redeeming process."
 	)]
@@ -252,6 +343,40 @@ impl CfSettings for CLISettings {
 	}
 }
 
+/// The settings for commands that connect to the State Chain but don't sign anything, so don't
+/// require any keys to be configured.
+#[derive(Deserialize, Debug)]
+pub struct CLIConnectionSettings {
+	pub state_chain: StateChainConnection,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct StateChainConnection {
+	pub ws_endpoint: String,
+}
+
+impl CfSettings for CLIConnectionSettings {
+	type CommandLineOptions = CLICommandLineOptions;
+
+	fn validate_settings(&mut self, _config_root: &Path) -> Result<(), ConfigError> {
+		validate_websocket_endpoint(self.state_chain.ws_endpoint.clone().into())
+			.map_err(|e| ConfigError::Message(e.to_string()))
+	}
+
+	fn set_defaults(
+		config_builder: ConfigBuilder<config::builder::DefaultState>,
+		_config_root: &str,
+	) -> Result<ConfigBuilder<config::builder::DefaultState>, ConfigError> {
+		config_builder.set_default("state_chain.ws_endpoint", "ws://localhost:9944")
+	}
+}
+
+impl CLIConnectionSettings {
+	pub fn new(opts: CLICommandLineOptions) -> Result<Self, ConfigError> {
+		Self::load_settings_from_all_sources(opts.config_root.clone(), DEFAULT_SETTINGS_DIR, opts)
+	}
+}
+
 impl CLISettings {
 	/// New settings loaded from "$base_config_path/config/Settings.toml",
 	/// environment and `CommandLineOptions`
@@ -295,8 +420,8 @@ mod tests {
 	fn test_all_command_line_options() {
 		// Fill the options with test values that will pass the parsing/validation.
 		// The test values need to be different from the default values set during `set_defaults()`
-		// for the test to work. `config_root` and `cmd` are not used in this test because they are
-		// not settings.
+		// for the test to work. `config_root`, `output`, `assume_yes` and `cmd` are not used in
+		// this test because they are not settings.
 		let opts = CLICommandLineOptions {
 			config_root: CLICommandLineOptions::default().config_root,
 
@@ -313,6 +438,8 @@ mod tests {
 				eth_backup_http_endpoint: Some("http://endpoint5:1234".to_owned()),
 			},
 
+			output: OutputFormat::Json,
+			assume_yes: true,
 			cmd: CliCommand::Rotate {}, // Not used in this test
 		};
 
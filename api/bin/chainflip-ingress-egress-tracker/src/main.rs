@@ -1,22 +1,15 @@
-use chainflip_engine::settings::{HttpBasicAuthEndpoint, WsHttpEndpoints};
+use cf_primitives::ForeignChain;
+use clap::Parser;
 use futures::FutureExt;
 use jsonrpsee::{core::Error, server::ServerBuilder, RpcModule};
-use std::{env, io::Write, net::SocketAddr, path::PathBuf};
+use settings::{DepositTrackerSettings, TrackerOptions};
 use tracing::log;
 use utilities::task_scope;
 
+mod settings;
+mod witness_index;
 mod witnessing;
 
-#[derive(Clone)]
-pub struct DepositTrackerSettings {
-	eth_node: WsHttpEndpoints,
-	// The key shouldn't be necessary, but the current witnesser wants this
-	eth_key_path: PathBuf,
-	dot_node: WsHttpEndpoints,
-	state_chain_ws_endpoint: String,
-	btc: HttpBasicAuthEndpoint,
-}
-
 async fn start(
 	scope: &task_scope::Scope<'_, anyhow::Error>,
 	settings: DepositTrackerSettings,
@@ -27,7 +20,8 @@ async fn start(
 		.expect("setting default subscriber failed");
 	let mut module = RpcModule::new(());
 
-	let btc_tracker = witnessing::btc_mempool::start(scope, settings.btc.clone()).await;
+	let btc_tracker =
+		witnessing::btc_mempool::start(scope, settings.btc.nodes.primary.clone()).await;
 
 	module.register_async_method("status", move |arguments, _context| {
 		let btc_tracker = btc_tracker.clone();
@@ -46,7 +40,49 @@ async fn start(
 	let (witness_sender, _) =
 		tokio::sync::broadcast::channel::<state_chain_runtime::RuntimeCall>(EVENT_BUFFER_SIZE);
 
-	witnessing::start(scope, settings, witness_sender.clone()).await?;
+	let witness_index = witnessing::start(scope, settings.clone(), witness_sender.clone()).await?;
+
+	module.register_async_method("deposit_status", {
+		let witness_index = witness_index.clone();
+		move |arguments, _context| {
+			let witness_index = witness_index.clone();
+			async move {
+				arguments.parse::<(ForeignChain, Vec<String>)>().map_err(Error::Call).and_then(
+					|(chain, addresses)| {
+						witness_index
+							.deposit_status(chain, &addresses)
+							.map_err(|err| jsonrpsee::core::Error::Custom(err.to_string()))
+					},
+				)
+			}
+		}
+	})?;
+
+	module.register_async_method("egress_status", {
+		let witness_index = witness_index.clone();
+		move |arguments, _context| {
+			let witness_index = witness_index.clone();
+			async move {
+				arguments
+					.parse::<Vec<String>>()
+					.map_err(Error::Call)
+					.map(|tx_out_ids| witness_index.egress_status(&tx_out_ids))
+			}
+		}
+	})?;
+
+	module.register_async_method("transaction_status", move |arguments, _context| {
+		let witness_index = witness_index.clone();
+		async move {
+			arguments.parse::<(ForeignChain, Vec<String>)>().map_err(Error::Call).and_then(
+				|(chain, tx_ids)| {
+					witness_index
+						.transaction_status(chain, &tx_ids)
+						.map_err(|err| jsonrpsee::core::Error::Custom(err.to_string()))
+				},
+			)
+		}
+	})?;
 
 	module.register_subscription(
 		"subscribe_witnessing",
@@ -68,8 +104,9 @@ async fn start(
 		},
 	)?;
 
-	scope.spawn(async {
-		let server = ServerBuilder::default().build("0.0.0.0:13337".parse::<SocketAddr>()?).await?;
+	let listen_address = settings.server.listen_address;
+	scope.spawn(async move {
+		let server = ServerBuilder::default().build(listen_address).await?;
 		let addr = server.local_addr()?;
 		log::info!("Listening on http://{}", addr);
 		server.start(module)?.stopped().await;
@@ -83,41 +120,7 @@ async fn start(
 
 #[tokio::main]
 async fn main() -> anyhow::Result<()> {
-	// Temporary hack: we don't actually use eth key, but the current witnesser is
-	// expecting a path with a valid key, so we create a temporary dummy key file here:
-	let mut eth_key_temp_file = tempfile::NamedTempFile::new()?;
-	eth_key_temp_file
-		.write_all(b"0123456789abcdef0123456789abcdef0123456789abcdef0123456789abcdef")
-		.unwrap();
-
-	let settings = DepositTrackerSettings {
-		eth_node: WsHttpEndpoints {
-			ws_endpoint: env::var("ETH_WS_ENDPOINT")
-				.unwrap_or("ws://localhost:8546".to_string())
-				.into(),
-			http_endpoint: env::var("ETH_HTTP_ENDPOINT")
-				.unwrap_or("http://localhost:8545".to_string())
-				.into(),
-		},
-		eth_key_path: eth_key_temp_file.path().into(),
-		dot_node: WsHttpEndpoints {
-			ws_endpoint: env::var("DOT_WS_ENDPOINT")
-				.unwrap_or("ws://localhost:9947".to_string())
-				.into(),
-			http_endpoint: env::var("DOT_HTTP_ENDPOINT")
-				.unwrap_or("http://localhost:9947".to_string())
-				.into(),
-		},
-		state_chain_ws_endpoint: env::var("SC_WS_ENDPOINT")
-			.unwrap_or("ws://localhost:9944".to_string()),
-		btc: HttpBasicAuthEndpoint {
-			http_endpoint: env::var("BTC_ENDPOINT")
-				.unwrap_or("http://127.0.0.1:8332".to_string())
-				.into(),
-			basic_auth_user: env::var("BTC_USERNAME").unwrap_or("flip".to_string()),
-			basic_auth_password: env::var("BTC_PASSWORD").unwrap_or("flip".to_string()),
-		},
-	};
+	let settings = DepositTrackerSettings::new(TrackerOptions::parse())?;
 
 	task_scope::task_scope(|scope| async move { start(scope, settings).await }.boxed()).await
 }
@@ -0,0 +1,284 @@
+use chainflip_engine::{
+	constants::{CONFIG_ROOT, DEFAULT_CONFIG_ROOT},
+	settings::{
+		insert_command_line_option, insert_command_line_option_path, resolve_settings_path, Btc,
+		BtcOptions, CfSettings, Dot, DotOptions, NodeContainer, WsHttpEndpoints,
+		DEFAULT_SETTINGS_DIR,
+	},
+};
+use clap::Parser;
+use config::{ConfigBuilder, ConfigError, Source, Value};
+use serde::Deserialize;
+use std::{
+	collections::HashMap,
+	net::SocketAddr,
+	path::{Path, PathBuf},
+};
+
+const STATE_CHAIN_WS_ENDPOINT: &str = "state_chain.ws_endpoint";
+const SERVER_LISTEN_ADDRESS: &str = "server.listen_address";
+const INDEX_FILE: &str = "index_file";
+
+#[derive(Parser, Debug, Clone, Default)]
+pub struct TrackerEthOptions {
+	#[clap(long = "eth.rpc.ws_endpoint")]
+	pub eth_ws_endpoint: Option<String>,
+	#[clap(long = "eth.rpc.http_endpoint")]
+	pub eth_http_endpoint: Option<String>,
+
+	#[clap(long = "eth.backup_rpc.ws_endpoint")]
+	pub eth_backup_ws_endpoint: Option<String>,
+	#[clap(long = "eth.backup_rpc.http_endpoint")]
+	pub eth_backup_http_endpoint: Option<String>,
+}
+
+impl TrackerEthOptions {
+	pub fn insert_all(&self, map: &mut HashMap<String, Value>) {
+		insert_command_line_option(map, "eth.rpc.ws_endpoint", &self.eth_ws_endpoint);
+		insert_command_line_option(map, "eth.rpc.http_endpoint", &self.eth_http_endpoint);
+
+		insert_command_line_option(map, "eth.backup_rpc.ws_endpoint", &self.eth_backup_ws_endpoint);
+		insert_command_line_option(
+			map,
+			"eth.backup_rpc.http_endpoint",
+			&self.eth_backup_http_endpoint,
+		);
+	}
+}
+
+#[derive(Parser, Debug, Clone)]
+#[clap(version)]
+pub struct TrackerOptions {
+	#[clap(short = 'c', long = "config-root", env = CONFIG_ROOT, default_value = DEFAULT_CONFIG_ROOT)]
+	pub config_root: String,
+
+	#[clap(long = "state_chain.ws_endpoint")]
+	pub state_chain_ws_endpoint: Option<String>,
+
+	#[clap(flatten)]
+	pub eth_opts: TrackerEthOptions,
+
+	#[clap(flatten)]
+	pub dot_opts: DotOptions,
+
+	#[clap(flatten)]
+	pub btc_opts: BtcOptions,
+
+	/// The address the tracker's RPC server listens on, e.g. 0.0.0.0:13337
+	#[clap(long = "server.listen_address")]
+	pub server_listen_address: Option<SocketAddr>,
+
+	/// If set, the deposit and egress index is persisted to this file.
+	#[clap(long = "index_file", parse(from_os_str))]
+	pub index_file: Option<PathBuf>,
+}
+
+impl Default for TrackerOptions {
+	fn default() -> Self {
+		Self {
+			config_root: DEFAULT_CONFIG_ROOT.to_owned(),
+			state_chain_ws_endpoint: None,
+			eth_opts: TrackerEthOptions::default(),
+			dot_opts: DotOptions::default(),
+			btc_opts: BtcOptions::default(),
+			server_listen_address: None,
+			index_file: None,
+		}
+	}
+}
+
+impl Source for TrackerOptions {
+	fn clone_into_box(&self) -> Box<dyn Source + Send + Sync> {
+		Box::new((*self).clone())
+	}
+
+	fn collect(&self) -> std::result::Result<config::Map<String, Value>, ConfigError> {
+		let mut map: HashMap<String, Value> = HashMap::new();
+
+		insert_command_line_option(&mut map, STATE_CHAIN_WS_ENDPOINT, &self.state_chain_ws_endpoint);
+
+		self.eth_opts.insert_all(&mut map);
+
+		self.dot_opts.insert_all(&mut map);
+
+		self.btc_opts.insert_all(&mut map);
+
+		insert_command_line_option(
+			&mut map,
+			SERVER_LISTEN_ADDRESS,
+			&self.server_listen_address.map(|address| address.to_string()),
+		);
+		insert_command_line_option_path(&mut map, INDEX_FILE, &self.index_file);
+
+		Ok(map)
+	}
+}
+
+#[derive(Debug, Deserialize, Clone, PartialEq, Eq)]
+pub struct StateChain {
+	pub ws_endpoint: String,
+}
+
+#[derive(Debug, Deserialize, Clone, PartialEq, Eq)]
+pub struct Server {
+	pub listen_address: SocketAddr,
+}
+
+#[derive(Debug, Deserialize, Clone, PartialEq, Eq)]
+pub struct DepositTrackerSettings {
+	pub state_chain: StateChain,
+	// The tracker only reads from Ethereum, so unlike the engine it doesn't need a private key.
+	pub eth: NodeContainer<WsHttpEndpoints>,
+	pub dot: Dot,
+	pub btc: Btc,
+	pub server: Server,
+	pub index_file: Option<PathBuf>,
+}
+
+impl CfSettings for DepositTrackerSettings {
+	type CommandLineOptions = TrackerOptions;
+
+	fn validate_settings(&mut self, config_root: &Path) -> Result<(), ConfigError> {
+		chainflip_engine::settings::validate_websocket_endpoint(
+			self.state_chain.ws_endpoint.clone().into(),
+		)
+		.map_err(|e| ConfigError::Message(e.to_string()))?;
+
+		self.eth.validate()?;
+
+		self.dot.validate_settings()?;
+
+		self.btc.validate_settings()?;
+
+		self.index_file = self
+			.index_file
+			.as_ref()
+			.map(|index_file| resolve_settings_path(config_root, index_file, None))
+			.transpose()?;
+
+		Ok(())
+	}
+
+	/// The defaults match the endpoints of a localnet, so no configuration is needed for
+	/// development.
+	fn set_defaults(
+		config_builder: ConfigBuilder<config::builder::DefaultState>,
+		_config_root: &str,
+	) -> Result<ConfigBuilder<config::builder::DefaultState>, ConfigError> {
+		config_builder
+			.set_default(STATE_CHAIN_WS_ENDPOINT, "ws://localhost:9944")?
+			.set_default("eth.rpc.ws_endpoint", "ws://localhost:8546")?
+			.set_default("eth.rpc.http_endpoint", "http://localhost:8545")?
+			.set_default("dot.rpc.ws_endpoint", "ws://localhost:9947")?
+			.set_default("dot.rpc.http_endpoint", "http://localhost:9947")?
+			.set_default("btc.rpc.http_endpoint", "http://127.0.0.1:8332")?
+			.set_default("btc.rpc.basic_auth_user", "flip")?
+			.set_default("btc.rpc.basic_auth_password", "flip")?
+			.set_default(SERVER_LISTEN_ADDRESS, "0.0.0.0:13337")
+	}
+}
+
+impl DepositTrackerSettings {
+	/// New settings loaded from "$base_config_path/config/Settings.toml",
+	/// environment and `TrackerOptions`
+	pub fn new(opts: TrackerOptions) -> Result<Self, ConfigError> {
+		Self::load_settings_from_all_sources(opts.config_root.clone(), DEFAULT_SETTINGS_DIR, opts)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn init_default_config() {
+		let settings = DepositTrackerSettings::new(TrackerOptions::default()).unwrap();
+
+		assert_eq!(settings.state_chain.ws_endpoint, "ws://localhost:9944");
+		assert_eq!(settings.eth.primary.ws_endpoint.as_ref(), "ws://localhost:8546");
+		assert_eq!(settings.btc.nodes.primary.basic_auth_user, "flip");
+		assert_eq!(settings.server.listen_address, "0.0.0.0:13337".parse().unwrap());
+		assert!(settings.eth.backup.is_none());
+		assert!(settings.index_file.is_none());
+	}
+
+	#[test]
+	fn test_all_command_line_options() {
+		let opts = TrackerOptions {
+			config_root: TrackerOptions::default().config_root,
+			state_chain_ws_endpoint: Some("ws://endpoint:1234".to_owned()),
+			eth_opts: TrackerEthOptions {
+				eth_ws_endpoint: Some("ws://endpoint2:1234".to_owned()),
+				eth_http_endpoint: Some("http://endpoint3:1234".to_owned()),
+				eth_backup_ws_endpoint: Some("ws://endpoint4:1234".to_owned()),
+				eth_backup_http_endpoint: Some("http://endpoint5:1234".to_owned()),
+			},
+			dot_opts: DotOptions {
+				dot_ws_endpoint: Some("ws://endpoint6:1234".to_owned()),
+				dot_http_endpoint: Some("http://endpoint7:1234".to_owned()),
+				dot_backup_ws_endpoint: None,
+				dot_backup_http_endpoint: None,
+			},
+			btc_opts: BtcOptions {
+				btc_http_endpoint: Some("http://endpoint8:1234".to_owned()),
+				btc_basic_auth_user: Some("user".to_owned()),
+				btc_basic_auth_password: Some("password".to_owned()),
+				btc_backup_http_endpoint: None,
+				btc_backup_basic_auth_user: None,
+				btc_backup_basic_auth_password: None,
+			},
+			server_listen_address: Some("127.0.0.1:8080".parse().unwrap()),
+			index_file: Some(PathBuf::from("/tmp/index.json")),
+		};
+
+		let settings = DepositTrackerSettings::new(opts.clone()).unwrap();
+
+		assert_eq!(opts.state_chain_ws_endpoint.unwrap(), settings.state_chain.ws_endpoint);
+		assert_eq!(
+			opts.eth_opts.eth_ws_endpoint.unwrap(),
+			settings.eth.primary.ws_endpoint.as_ref()
+		);
+		assert_eq!(
+			opts.eth_opts.eth_http_endpoint.unwrap(),
+			settings.eth.primary.http_endpoint.as_ref()
+		);
+		let eth_backup_node = settings.eth.backup.unwrap();
+		assert_eq!(
+			opts.eth_opts.eth_backup_ws_endpoint.unwrap(),
+			eth_backup_node.ws_endpoint.as_ref()
+		);
+		assert_eq!(
+			opts.eth_opts.eth_backup_http_endpoint.unwrap(),
+			eth_backup_node.http_endpoint.as_ref()
+		);
+		assert_eq!(
+			opts.dot_opts.dot_ws_endpoint.unwrap(),
+			settings.dot.nodes.primary.ws_endpoint.as_ref()
+		);
+		assert_eq!(
+			opts.btc_opts.btc_http_endpoint.unwrap(),
+			settings.btc.nodes.primary.http_endpoint.as_ref()
+		);
+		assert_eq!(opts.server_listen_address.unwrap(), settings.server.listen_address);
+		assert_eq!(opts.index_file, settings.index_file);
+	}
+
+	#[test]
+	fn invalid_endpoints_are_rejected() {
+		assert!(DepositTrackerSettings::new(TrackerOptions {
+			state_chain_ws_endpoint: Some("http://endpoint:1234".to_owned()),
+			..Default::default()
+		})
+		.is_err());
+
+		assert!(DepositTrackerSettings::new(TrackerOptions {
+			dot_opts: DotOptions {
+				// Polkadot endpoints must include a port.
+				dot_ws_endpoint: Some("ws://endpoint".to_owned()),
+				..Default::default()
+			},
+			..Default::default()
+		})
+		.is_err());
+	}
+}
@@ -0,0 +1,1010 @@
+use std::{
+	collections::{BTreeMap, BTreeSet},
+	path::{Path, PathBuf},
+	str::FromStr,
+	sync::{Arc, Mutex},
+	time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use anyhow::{anyhow, Context};
+use bitcoin::{hashes::Hash, Txid};
+use cf_chains::{
+	address::{ForeignChainAddressHumanreadable, ToHumanreadableAddress},
+	btc::ScriptPubkey,
+	dot::PolkadotAccountId,
+	ForeignChainAddress,
+};
+use cf_primitives::{Asset, ForeignChain, NetworkEnvironment};
+use codec::Encode;
+use serde::{Deserialize, Serialize};
+use state_chain_runtime::RuntimeCall;
+use tokio::{io::AsyncWriteExt, sync::mpsc};
+use tracing::{error, info, warn};
+use utilities::{make_periodic_tick, rpc::NumberOrHex, task_scope::Scope};
+
+/// How long witnessed deposits and egresses are kept in the index.
+const RETENTION: Duration = Duration::from_secs(30 * 24 * 60 * 60);
+/// How often the records older than the `RETENTION` are pruned and the index file is compacted.
+const PRUNE_INTERVAL: Duration = Duration::from_secs(60 * 60);
+
+/// The transaction a deposit was made in.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+enum TxId {
+	/// The hash of a Bitcoin or Ethereum transaction, as used on chain.
+	Hash([u8; 32]),
+	/// A Polkadot extrinsic, identified by its block and its index in the block.
+	Extrinsic { block_number: u32, extrinsic_index: u32 },
+}
+
+impl TxId {
+	fn parse(chain: ForeignChain, tx_id: &str) -> anyhow::Result<Self> {
+		Ok(match chain {
+			ForeignChain::Bitcoin => TxId::Hash(
+				Txid::from_str(tx_id)
+					.with_context(|| format!("Invalid Bitcoin transaction id: {tx_id}"))?
+					.to_byte_array(),
+			),
+			ForeignChain::Ethereum => TxId::Hash(
+				utilities::clean_hex_address::<[u8; 32]>(tx_id)
+					.with_context(|| format!("Invalid Ethereum transaction hash: {tx_id}"))?,
+			),
+			ForeignChain::Polkadot => {
+				let (block_number, extrinsic_index) = tx_id
+					.split_once('-')
+					.and_then(|(block_number, extrinsic_index)| {
+						Some((block_number.parse().ok()?, extrinsic_index.parse().ok()?))
+					})
+					.ok_or_else(|| {
+						anyhow!("Invalid Polkadot extrinsic id, expected <block>-<index>: {tx_id}")
+					})?;
+				TxId::Extrinsic { block_number, extrinsic_index }
+			},
+			ForeignChain::Arbitrum => return Err(anyhow!("Arbitrum is not tracked")),
+		})
+	}
+
+	/// The id as shown by the chain's explorers.
+	fn to_humanreadable(self, chain: ForeignChain) -> String {
+		match self {
+			TxId::Hash(hash) if chain == ForeignChain::Bitcoin =>
+				Txid::from_byte_array(hash).to_string(),
+			TxId::Hash(hash) => hex_encode(hash),
+			TxId::Extrinsic { block_number, extrinsic_index } =>
+				format!("{block_number}-{extrinsic_index}"),
+		}
+	}
+}
+
+/// A deposit as witnessed on the external chain, stored in its canonical (non human-readable)
+/// form so that it can be persisted and re-loaded.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+struct DepositRecord {
+	deposit_address: ForeignChainAddress,
+	asset: Asset,
+	amount: NumberOrHex,
+	block_height: u64,
+	/// The position of the deposit among the deposits of the asset to the same address in the
+	/// block. Together with the block it identifies the deposit, as the witnesses of Ethereum and
+	/// Polkadot deposits don't include the transaction they were made in.
+	position: u32,
+	/// Recorded once the witnessing has told us the transaction. Native Ether deposits are
+	/// witnessed from the balances of the deposit addresses, so they never have one.
+	tx_id: Option<TxId>,
+	/// Seconds since the Unix epoch.
+	witnessed_at: u64,
+}
+
+impl DepositRecord {
+	fn id(&self) -> (u64, Asset, u32) {
+		(self.block_height, self.asset, self.position)
+	}
+}
+
+/// A successful egress (broadcast) as witnessed on the external chain. The `tx_out_id` is the
+/// hex-encoded SCALE representation of the chain's `TransactionOutId`, which is the same id the
+/// State Chain uses to refer to the broadcast.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+struct EgressRecord {
+	chain: ForeignChain,
+	tx_out_id: String,
+	signer_id: ForeignChainAddress,
+	tx_fee: NumberOrHex,
+	/// Seconds since the Unix epoch.
+	witnessed_at: u64,
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct DepositStatus {
+	pub deposit_address: ForeignChainAddressHumanreadable,
+	pub asset: Asset,
+	pub amount: NumberOrHex,
+	pub block_height: u64,
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub tx_id: Option<String>,
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct EgressStatus {
+	pub chain: ForeignChain,
+	pub tx_out_id: String,
+	pub signer_id: ForeignChainAddressHumanreadable,
+	pub tx_fee: NumberOrHex,
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct TransactionStatus {
+	pub deposits: Vec<DepositStatus>,
+	/// Only Bitcoin egresses are identified by the hash of their transaction.
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub egress: Option<EgressStatus>,
+}
+
+/// An entry of the index file, which is a log of JSON lines. Re-loading the file replays the
+/// entries in order, a later entry for the same deposit or egress replacing the earlier one.
+#[derive(Debug, PartialEq, Eq, Serialize, Deserialize)]
+enum LogEntry {
+	Deposit(DepositRecord),
+	Egress(EgressRecord),
+}
+
+enum IndexFileWrite {
+	Append(Vec<LogEntry>),
+	/// Replaces the whole file, to drop the pruned and superseded entries.
+	Compact(Vec<LogEntry>),
+}
+
+#[derive(Default)]
+struct Index {
+	deposits: BTreeMap<ForeignChainAddress, Vec<DepositRecord>>,
+	deposit_addresses_by_tx: BTreeMap<TxId, BTreeSet<ForeignChainAddress>>,
+	egresses: BTreeMap<String, EgressRecord>,
+}
+
+impl Index {
+	fn replay(&mut self, entry: LogEntry) {
+		match entry {
+			LogEntry::Deposit(deposit) => {
+				self.upsert_deposit(deposit);
+			},
+			LogEntry::Egress(egress) => {
+				self.egresses.insert(egress.tx_out_id.clone(), egress);
+			},
+		}
+	}
+
+	fn entries(&self) -> Vec<LogEntry> {
+		self.deposits
+			.values()
+			.flatten()
+			.cloned()
+			.map(LogEntry::Deposit)
+			.chain(self.egresses.values().cloned().map(LogEntry::Egress))
+			.collect()
+	}
+
+	/// Inserts the deposit, or updates the recorded one with the same identity. Returns `false` if
+	/// nothing changed, i.e. the deposit was witnessed again.
+	fn upsert_deposit(&mut self, mut deposit: DepositRecord) -> bool {
+		let deposits = self.deposits.entry(deposit.deposit_address.clone()).or_default();
+		match deposits.iter_mut().find(|recorded| recorded.id() == deposit.id()) {
+			Some(recorded) => {
+				// Re-witnessing a block doesn't tell us the transactions again.
+				deposit.tx_id = deposit.tx_id.or(recorded.tx_id);
+				deposit.witnessed_at = recorded.witnessed_at;
+				if *recorded == deposit {
+					return false
+				}
+				*recorded = deposit.clone();
+			},
+			None => deposits.push(deposit.clone()),
+		}
+		if let Some(tx_id) = deposit.tx_id {
+			self.deposit_addresses_by_tx
+				.entry(tx_id)
+				.or_default()
+				.insert(deposit.deposit_address);
+		}
+		true
+	}
+
+	/// Records the deposits and egresses contained in a witnessed call. Returns the entries to
+	/// append to the index file, which are empty if the call isn't relevant to the index or was
+	/// already recorded.
+	fn record_call(&mut self, call: &RuntimeCall, now: u64) -> Vec<LogEntry> {
+		// The position of each deposit is assigned below.
+		let deposit = |deposit_address, asset, amount, block_height, tx_id| DepositRecord {
+			deposit_address,
+			asset,
+			amount,
+			block_height,
+			position: 0,
+			tx_id,
+			witnessed_at: now,
+		};
+		let deposits: Vec<DepositRecord> = match call {
+			RuntimeCall::EthereumIngressEgress(
+				pallet_cf_ingress_egress::Call::process_deposits {
+					deposit_witnesses,
+					block_height,
+				},
+			) => deposit_witnesses
+				.iter()
+				.map(|witness| {
+					deposit(
+						witness.deposit_address.into(),
+						witness.asset.into(),
+						witness.amount.into(),
+						*block_height,
+						None,
+					)
+				})
+				.collect(),
+			RuntimeCall::PolkadotIngressEgress(
+				pallet_cf_ingress_egress::Call::process_deposits {
+					deposit_witnesses,
+					block_height,
+				},
+			) => deposit_witnesses
+				.iter()
+				.map(|witness| {
+					deposit(
+						witness.deposit_address.into(),
+						witness.asset.into(),
+						witness.amount.into(),
+						(*block_height).into(),
+						None,
+					)
+				})
+				.collect(),
+			RuntimeCall::BitcoinIngressEgress(
+				pallet_cf_ingress_egress::Call::process_deposits {
+					deposit_witnesses,
+					block_height,
+				},
+			) => deposit_witnesses
+				.iter()
+				.map(|witness| {
+					deposit(
+						witness.deposit_address.clone().into(),
+						witness.asset.into(),
+						witness.amount.into(),
+						*block_height,
+						Some(TxId::Hash(witness.deposit_details.tx_id)),
+					)
+				})
+				.collect(),
+			_ => return self.record_egress(call, now).into_iter().collect(),
+		};
+
+		with_positions(
+			deposits
+				.into_iter()
+				.map(|deposit| ((deposit.deposit_address.clone(), deposit.asset), deposit)),
+		)
+		.filter_map(|(_, deposit, position)| {
+			let deposit = DepositRecord { position, ..deposit };
+			self.upsert_deposit(deposit.clone()).then_some(LogEntry::Deposit(deposit))
+		})
+		.collect()
+	}
+
+	fn record_egress(&mut self, call: &RuntimeCall, now: u64) -> Option<LogEntry> {
+		let egress = match call {
+			RuntimeCall::EthereumBroadcaster(
+				pallet_cf_broadcast::Call::transaction_succeeded {
+					tx_out_id,
+					signer_id,
+					tx_fee,
+					..
+				},
+			) => EgressRecord {
+				chain: ForeignChain::Ethereum,
+				tx_out_id: hex_encode(tx_out_id.encode()),
+				signer_id: (*signer_id).into(),
+				tx_fee: tx_fee.effective_gas_price.saturating_mul(tx_fee.gas_used).into(),
+				witnessed_at: now,
+			},
+			RuntimeCall::PolkadotBroadcaster(
+				pallet_cf_broadcast::Call::transaction_succeeded {
+					tx_out_id,
+					signer_id,
+					tx_fee,
+					..
+				},
+			) => EgressRecord {
+				chain: ForeignChain::Polkadot,
+				tx_out_id: hex_encode(tx_out_id.encode()),
+				signer_id: (*signer_id).into(),
+				tx_fee: (*tx_fee).into(),
+				witnessed_at: now,
+			},
+			RuntimeCall::BitcoinBroadcaster(pallet_cf_broadcast::Call::transaction_succeeded {
+				tx_out_id,
+				signer_id,
+				tx_fee,
+				..
+			}) => EgressRecord {
+				chain: ForeignChain::Bitcoin,
+				tx_out_id: hex_encode(tx_out_id.encode()),
+				signer_id: signer_id.clone().into(),
+				tx_fee: (*tx_fee).into(),
+				witnessed_at: now,
+			},
+			_ => return None,
+		};
+
+		if self.egresses.get(&egress.tx_out_id).map_or(false, |recorded| {
+			*recorded == EgressRecord { witnessed_at: recorded.witnessed_at, ..egress.clone() }
+		}) {
+			return None
+		}
+		self.egresses.insert(egress.tx_out_id.clone(), egress.clone());
+		Some(LogEntry::Egress(egress))
+	}
+
+	/// Records the transactions of the deposits of `asset` witnessed in a block, given in the
+	/// order they were witnessed.
+	fn record_deposit_transactions(
+		&mut self,
+		block_height: u64,
+		asset: Asset,
+		transactions: impl Iterator<Item = (ForeignChainAddress, TxId)>,
+	) -> Vec<LogEntry> {
+		with_positions(transactions)
+			.filter_map(|(deposit_address, tx_id, position)| {
+				let deposit = self
+					.deposits
+					.get(&deposit_address)?
+					.iter()
+					.find(|deposit| deposit.id() == (block_height, asset, position))?;
+				let deposit = DepositRecord { tx_id: Some(tx_id), ..deposit.clone() };
+				self.upsert_deposit(deposit.clone()).then_some(LogEntry::Deposit(deposit))
+			})
+			.collect()
+	}
+
+	fn prune(&mut self, witnessed_before: u64) {
+		self.deposits.retain(|_, deposits| {
+			deposits.retain(|deposit| deposit.witnessed_at >= witnessed_before);
+			!deposits.is_empty()
+		});
+		self.deposit_addresses_by_tx.retain(|tx_id, deposit_addresses| {
+			deposit_addresses.retain(|deposit_address| {
+				self.deposits.get(deposit_address).map_or(false, |deposits| {
+					deposits.iter().any(|deposit| deposit.tx_id == Some(*tx_id))
+				})
+			});
+			!deposit_addresses.is_empty()
+		});
+		self.egresses.retain(|_, egress| egress.witnessed_at >= witnessed_before);
+	}
+}
+
+/// Numbers each item by its position among the preceding items with the same key.
+fn with_positions<K: Ord + Clone, V>(
+	items: impl Iterator<Item = (K, V)>,
+) -> impl Iterator<Item = (K, V, u32)> {
+	let mut counts = BTreeMap::<K, u32>::new();
+	items.map(move |(key, value)| {
+		let count = counts.entry(key.clone()).or_default();
+		let position = *count;
+		*count += 1;
+		(key, value, position)
+	})
+}
+
+fn hex_encode(bytes: impl AsRef<[u8]>) -> String {
+	format!("0x{}", hex::encode(bytes))
+}
+
+fn now() -> u64 {
+	SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default().as_secs()
+}
+
+fn parse_address(
+	chain: ForeignChain,
+	address: &str,
+	network: NetworkEnvironment,
+) -> anyhow::Result<ForeignChainAddress> {
+	Ok(match chain {
+		ForeignChain::Ethereum => ForeignChainAddress::Eth(
+			utilities::clean_hex_address::<[u8; 20]>(address)
+				.with_context(|| format!("Invalid Ethereum address: {address}"))?
+				.into(),
+		),
+		ForeignChain::Polkadot => ForeignChainAddress::Dot(
+			PolkadotAccountId::from_str(address)
+				.with_context(|| format!("Invalid Polkadot address: {address}"))?,
+		),
+		ForeignChain::Bitcoin => ForeignChainAddress::Btc(
+			ScriptPubkey::try_from_address(address, &network.into())
+				.map_err(|_| anyhow!("Invalid Bitcoin address: {address}"))?,
+		),
+		ForeignChain::Arbitrum => ForeignChainAddress::Arb(
+			utilities::clean_hex_address::<[u8; 20]>(address)
+				.with_context(|| format!("Invalid Arbitrum address: {address}"))?
+				.into(),
+		),
+	})
+}
+
+/// An in-memory index of the deposits and egresses observed by the tracker's witnessers over the
+/// last `RETENTION`, optionally persisted to a file so that it survives restarts.
+#[derive(Clone)]
+pub struct WitnessIndex {
+	index: Arc<Mutex<Index>>,
+	network: NetworkEnvironment,
+	file_writer: Option<mpsc::UnboundedSender<IndexFileWrite>>,
+}
+
+impl WitnessIndex {
+	fn new(network: NetworkEnvironment) -> Self {
+		Self { index: Default::default(), network, file_writer: None }
+	}
+
+	/// Loads the index from the file at `persistence_path`, if any, and starts the tasks that
+	/// prune it and write it back to the file.
+	pub async fn start(
+		scope: &Scope<'_, anyhow::Error>,
+		network: NetworkEnvironment,
+		persistence_path: Option<PathBuf>,
+	) -> anyhow::Result<Self> {
+		let mut witness_index = Self::new(network);
+
+		if let Some(path) = persistence_path {
+			let mut index = load(&path).await?;
+			index.prune(now().saturating_sub(RETENTION.as_secs()));
+
+			let (file_writer, file_writes) = mpsc::unbounded_channel();
+			// Rewrite the file without the entries that have been pruned or superseded since it
+			// was last compacted.
+			let _result = file_writer.send(IndexFileWrite::Compact(index.entries()));
+			scope.spawn(write_index_file(path, file_writes));
+
+			witness_index.index = Arc::new(Mutex::new(index));
+			witness_index.file_writer = Some(file_writer);
+		}
+
+		scope.spawn({
+			let witness_index = witness_index.clone();
+			async move {
+				let mut tick = make_periodic_tick(PRUNE_INTERVAL, false);
+				loop {
+					tick.tick().await;
+					witness_index.prune();
+				}
+			}
+		});
+
+		Ok(witness_index)
+	}
+
+	/// Applies `f` to the index, and queues the entries it returns to be appended to the index
+	/// file. They are queued while the index is locked, so they are written in the same order as
+	/// the changes were made.
+	fn update(&self, f: impl FnOnce(&mut Index) -> Vec<LogEntry>) {
+		let mut index = self.index.lock().unwrap();
+		let entries = f(&mut index);
+		if let Some(file_writer) = &self.file_writer {
+			if !entries.is_empty() {
+				// The writer only stops if the tracker is shutting down.
+				let _result = file_writer.send(IndexFileWrite::Append(entries));
+			}
+		}
+	}
+
+	fn prune(&self) {
+		let mut index = self.index.lock().unwrap();
+		index.prune(now().saturating_sub(RETENTION.as_secs()));
+		if let Some(file_writer) = &self.file_writer {
+			let _result = file_writer.send(IndexFileWrite::Compact(index.entries()));
+		}
+	}
+
+	pub fn record_call(&self, call: &RuntimeCall) {
+		self.update(|index| index.record_call(call, now()));
+	}
+
+	/// Records the hashes of the transactions of the ERC20 deposits of `asset` witnessed in an
+	/// Ethereum block, given in the order they were witnessed.
+	pub fn record_erc20_deposit_transactions(
+		&self,
+		block_height: u64,
+		asset: Asset,
+		transactions: &[(sp_core::H160, sp_core::H256)],
+	) {
+		self.update(|index| {
+			index.record_deposit_transactions(
+				block_height,
+				asset,
+				transactions.iter().map(|(deposit_address, tx_hash)| {
+					(ForeignChainAddress::Eth(*deposit_address), TxId::Hash(tx_hash.0))
+				}),
+			)
+		});
+	}
+
+	/// Records the extrinsics of the deposits witnessed in a Polkadot block, given the transfers
+	/// of the block in order.
+	pub fn record_dot_deposit_extrinsics(
+		&self,
+		block_number: u32,
+		transfers: impl Iterator<Item = (u32, PolkadotAccountId)>,
+	) {
+		self.update(|index| {
+			index.record_deposit_transactions(
+				block_number.into(),
+				Asset::Dot,
+				transfers.map(|(extrinsic_index, to)| {
+					(
+						ForeignChainAddress::Dot(to),
+						TxId::Extrinsic { block_number, extrinsic_index },
+					)
+				}),
+			)
+		});
+	}
+
+	fn to_deposit_status(&self, deposit: &DepositRecord) -> DepositStatus {
+		DepositStatus {
+			deposit_address: deposit.deposit_address.to_humanreadable(self.network),
+			asset: deposit.asset,
+			amount: deposit.amount,
+			block_height: deposit.block_height,
+			tx_id: deposit
+				.tx_id
+				.map(|tx_id| tx_id.to_humanreadable(deposit.deposit_address.chain())),
+		}
+	}
+
+	fn to_egress_status(&self, egress: &EgressRecord) -> EgressStatus {
+		EgressStatus {
+			chain: egress.chain,
+			tx_out_id: egress.tx_out_id.clone(),
+			signer_id: egress.signer_id.to_humanreadable(self.network),
+			tx_fee: egress.tx_fee,
+		}
+	}
+
+	/// Returns all deposits witnessed for each of the given addresses on `chain`.
+	pub fn deposit_status(
+		&self,
+		chain: ForeignChain,
+		addresses: &[String],
+	) -> anyhow::Result<Vec<Vec<DepositStatus>>> {
+		let addresses = addresses
+			.iter()
+			.map(|address| parse_address(chain, address, self.network))
+			.collect::<anyhow::Result<Vec<_>>>()?;
+
+		let index = self.index.lock().unwrap();
+		Ok(addresses
+			.iter()
+			.map(|address| {
+				index
+					.deposits
+					.get(address)
+					.into_iter()
+					.flatten()
+					.map(|deposit| self.to_deposit_status(deposit))
+					.collect()
+			})
+			.collect())
+	}
+
+	/// Returns the witnessed egress for each of the given (hex-encoded) transaction out ids.
+	pub fn egress_status(&self, tx_out_ids: &[String]) -> Vec<Option<EgressStatus>> {
+		let index = self.index.lock().unwrap();
+		tx_out_ids
+			.iter()
+			.map(|tx_out_id| {
+				let tx_out_id = tx_out_id.to_lowercase();
+				let tx_out_id =
+					if tx_out_id.starts_with("0x") { tx_out_id } else { format!("0x{tx_out_id}") };
+				index.egresses.get(&tx_out_id).map(|egress| self.to_egress_status(egress))
+			})
+			.collect()
+	}
+
+	/// Returns the deposits and the egress made in each of the given transactions on `chain`.
+	/// Bitcoin and Ethereum transactions are given by their hash, Polkadot extrinsics as
+	/// `<block>-<index>`. Native Ether deposits can only be looked up by address.
+	pub fn transaction_status(
+		&self,
+		chain: ForeignChain,
+		tx_ids: &[String],
+	) -> anyhow::Result<Vec<TransactionStatus>> {
+		let tx_ids = tx_ids
+			.iter()
+			.map(|tx_id| TxId::parse(chain, tx_id))
+			.collect::<anyhow::Result<Vec<_>>>()?;
+
+		let index = self.index.lock().unwrap();
+		Ok(tx_ids
+			.into_iter()
+			.map(|tx_id| TransactionStatus {
+				deposits: index
+					.deposit_addresses_by_tx
+					.get(&tx_id)
+					.into_iter()
+					.flatten()
+					.filter(|deposit_address| deposit_address.chain() == chain)
+					.flat_map(|deposit_address| {
+						index.deposits.get(deposit_address).into_iter().flatten()
+					})
+					.filter(|deposit| deposit.tx_id == Some(tx_id))
+					.map(|deposit| self.to_deposit_status(deposit))
+					.collect(),
+				egress: match (chain, tx_id) {
+					(ForeignChain::Bitcoin, TxId::Hash(hash)) => index
+						.egresses
+						.get(&hex_encode(hash))
+						.map(|egress| self.to_egress_status(egress)),
+					_ => None,
+				},
+			})
+			.collect())
+	}
+}
+
+async fn load(path: &Path) -> anyhow::Result<Index> {
+	let mut index = Index::default();
+	let contents = match tokio::fs::read_to_string(path).await {
+		Ok(contents) => contents,
+		Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(index),
+		Err(err) =>
+			return Err(err).with_context(|| format!("Failed to read index file {}", path.display())),
+	};
+
+	let lines = contents.lines().filter(|line| !line.is_empty()).collect::<Vec<_>>();
+	for (i, line) in lines.iter().enumerate() {
+		match serde_json::from_str(line) {
+			Ok(entry) => index.replay(entry),
+			// The tracker may have stopped in the middle of appending the last entry.
+			Err(err) if i + 1 == lines.len() =>
+				warn!("Ignoring the incomplete last entry of index file {}: {err}", path.display()),
+			Err(err) =>
+				return Err(err)
+					.with_context(|| format!("Failed to parse index file {}", path.display())),
+		}
+	}
+	info!("Loaded {} index entries from {}", lines.len(), path.display());
+
+	Ok(index)
+}
+
+fn to_lines(entries: &[LogEntry]) -> anyhow::Result<Vec<u8>> {
+	let mut lines = Vec::new();
+	for entry in entries {
+		serde_json::to_writer(&mut lines, entry)?;
+		lines.push(b'\n');
+	}
+	Ok(lines)
+}
+
+async fn open_for_append(path: &Path) -> anyhow::Result<tokio::fs::File> {
+	tokio::fs::OpenOptions::new()
+		.create(true)
+		.append(true)
+		.open(path)
+		.await
+		.with_context(|| format!("Failed to open index file {}", path.display()))
+}
+
+/// Writes the changes to the index to the index file, outside of the witnessing tasks.
+async fn write_index_file(
+	path: PathBuf,
+	mut file_writes: mpsc::UnboundedReceiver<IndexFileWrite>,
+) -> anyhow::Result<()> {
+	let mut file = open_for_append(&path).await?;
+
+	while let Some(file_write) = file_writes.recv().await {
+		let result = match file_write {
+			IndexFileWrite::Append(entries) =>
+				async {
+					file.write_all(&to_lines(&entries)?).await?;
+					file.flush().await?;
+					Ok::<_, anyhow::Error>(())
+				}
+				.await,
+			IndexFileWrite::Compact(entries) =>
+				async {
+					// Write to a temporary file first so that a crash mid-write doesn't corrupt the
+					// index.
+					let tmp_path = path.with_extension("tmp");
+					tokio::fs::write(&tmp_path, to_lines(&entries)?).await?;
+					tokio::fs::rename(&tmp_path, &path).await?;
+					file = open_for_append(&path).await?;
+					Ok::<_, anyhow::Error>(())
+				}
+				.await,
+		};
+		if let Err(err) = result {
+			error!("Failed to persist witness index to {}: {err:#}", path.display());
+		}
+	}
+
+	Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use cf_chains::{btc::UtxoId, eth::Address as EthereumAddress};
+	use pallet_cf_ingress_egress::DepositWitness;
+
+	const BTC_ADDRESS: &str = "bc1qrtwkf6jdda74ngjv6zgmxvx4jkckxkl2dafpm3";
+
+	fn eth_deposit_call(
+		asset: cf_primitives::chains::assets::eth::Asset,
+		deposits: &[(EthereumAddress, u128)],
+		block_height: u64,
+	) -> RuntimeCall {
+		RuntimeCall::EthereumIngressEgress(pallet_cf_ingress_egress::Call::process_deposits {
+			deposit_witnesses: deposits
+				.iter()
+				.map(|(address, amount)| DepositWitness {
+					deposit_address: *address,
+					asset,
+					amount: *amount,
+					deposit_details: (),
+				})
+				.collect(),
+			block_height,
+		})
+	}
+
+	fn flip_deposit_call(address: EthereumAddress, amount: u128, block_height: u64) -> RuntimeCall {
+		eth_deposit_call(
+			cf_primitives::chains::assets::eth::Asset::Flip,
+			&[(address, amount)],
+			block_height,
+		)
+	}
+
+	fn amounts(deposits: &[DepositStatus]) -> Vec<NumberOrHex> {
+		deposits.iter().map(|deposit| deposit.amount).collect()
+	}
+
+	#[test]
+	fn indexes_eth_deposits_by_address() {
+		let index = WitnessIndex::new(NetworkEnvironment::Mainnet);
+		let address = EthereumAddress::repeat_byte(0x11);
+
+		index.record_call(&flip_deposit_call(address, 100, 10));
+		index.record_call(&flip_deposit_call(address, 200, 11));
+		// Duplicate witnesses are ignored.
+		index.record_call(&flip_deposit_call(address, 200, 11));
+
+		let status = index
+			.deposit_status(
+				ForeignChain::Ethereum,
+				&[format!("{address:?}"), format!("{:?}", EthereumAddress::repeat_byte(0x22))],
+			)
+			.unwrap();
+
+		assert_eq!(status.len(), 2);
+		assert_eq!(
+			amounts(&status[0]),
+			vec![NumberOrHex::from(100u128), NumberOrHex::from(200u128)]
+		);
+		assert!(status[1].is_empty());
+	}
+
+	#[test]
+	fn keeps_deposits_of_the_same_amount_in_one_block() {
+		let mut index = Index::default();
+		let address = EthereumAddress::repeat_byte(0x11);
+		let call = eth_deposit_call(
+			cf_primitives::chains::assets::eth::Asset::Usdc,
+			&[(address, 100), (EthereumAddress::repeat_byte(0x22), 100), (address, 100)],
+			10,
+		);
+
+		assert_eq!(index.record_call(&call, 0).len(), 3);
+		// Witnessing the block again doesn't change anything.
+		assert!(index.record_call(&call, 1).is_empty());
+		// Neither does witnessing the deposits of another asset in the same block.
+		assert_eq!(index.record_call(&flip_deposit_call(address, 100, 10), 1).len(), 1);
+
+		assert_eq!(
+			index.deposits[&ForeignChainAddress::Eth(address)]
+				.iter()
+				.map(DepositRecord::id)
+				.collect::<Vec<_>>(),
+			vec![(10, Asset::Usdc, 0), (10, Asset::Usdc, 1), (10, Asset::Flip, 0)]
+		);
+	}
+
+	#[test]
+	fn indexes_btc_deposits_by_address_and_tx_id() {
+		let index = WitnessIndex::new(NetworkEnvironment::Mainnet);
+		let script_pubkey =
+			ScriptPubkey::try_from_address(BTC_ADDRESS, &NetworkEnvironment::Mainnet.into())
+				.unwrap();
+		let tx_id = [0xab; 32];
+		let explorer_tx_id = Txid::from_byte_array(tx_id).to_string();
+
+		index.record_call(&RuntimeCall::BitcoinIngressEgress(
+			pallet_cf_ingress_egress::Call::process_deposits {
+				deposit_witnesses: vec![DepositWitness {
+					deposit_address: script_pubkey,
+					asset: cf_primitives::chains::assets::btc::Asset::Btc,
+					amount: 5000,
+					deposit_details: UtxoId { tx_id, vout: 1 },
+				}],
+				block_height: 100,
+			},
+		));
+
+		let status = index.deposit_status(ForeignChain::Bitcoin, &[BTC_ADDRESS.into()]).unwrap();
+		assert_eq!(status[0].len(), 1);
+		assert_eq!(status[0][0].block_height, 100);
+		assert_eq!(status[0][0].tx_id, Some(explorer_tx_id.clone()));
+
+		let status = index
+			.transaction_status(
+				ForeignChain::Bitcoin,
+				&[explorer_tx_id, Txid::from_byte_array([0xcd; 32]).to_string()],
+			)
+			.unwrap();
+		assert_eq!(amounts(&status[0].deposits), vec![NumberOrHex::from(5000u64)]);
+		assert!(status[1].deposits.is_empty());
+	}
+
+	#[test]
+	fn looks_up_deposits_by_the_transactions_they_were_made_in() {
+		let index = WitnessIndex::new(NetworkEnvironment::Mainnet);
+		let address = EthereumAddress::repeat_byte(0x11);
+		let tx_hashes = [sp_core::H256::repeat_byte(1), sp_core::H256::repeat_byte(2)];
+
+		index.record_call(&eth_deposit_call(
+			cf_primitives::chains::assets::eth::Asset::Flip,
+			&[(address, 100), (address, 200)],
+			10,
+		));
+		index.record_erc20_deposit_transactions(
+			10,
+			Asset::Flip,
+			&[(address, tx_hashes[0]), (address, tx_hashes[1])],
+		);
+
+		let status = index
+			.transaction_status(
+				ForeignChain::Ethereum,
+				&tx_hashes.map(|tx_hash| format!("{tx_hash:?}")),
+			)
+			.unwrap();
+		assert_eq!(amounts(&status[0].deposits), vec![NumberOrHex::from(100u128)]);
+		assert_eq!(amounts(&status[1].deposits), vec![NumberOrHex::from(200u128)]);
+
+		// The transactions are kept when the block is witnessed again.
+		index.record_call(&eth_deposit_call(
+			cf_primitives::chains::assets::eth::Asset::Flip,
+			&[(address, 100), (address, 200)],
+			10,
+		));
+		let status =
+			index.deposit_status(ForeignChain::Ethereum, &[format!("{address:?}")]).unwrap();
+		assert_eq!(status[0][1].tx_id, Some(format!("{:?}", tx_hashes[1])));
+
+		let dot_address = PolkadotAccountId::from_aliased([3; 32]);
+		index.record_call(&RuntimeCall::PolkadotIngressEgress(
+			pallet_cf_ingress_egress::Call::process_deposits {
+				deposit_witnesses: vec![DepositWitness {
+					deposit_address: dot_address,
+					asset: cf_primitives::chains::assets::dot::Asset::Dot,
+					amount: 300,
+					deposit_details: (),
+				}],
+				block_height: 20,
+			},
+		));
+		// Only the transfers to deposit addresses were witnessed.
+		index.record_dot_deposit_extrinsics(
+			20,
+			[(1, PolkadotAccountId::from_aliased([4; 32])), (2, dot_address)].into_iter(),
+		);
+		let status = index
+			.transaction_status(ForeignChain::Polkadot, &["20-2".into(), "20-1".into()])
+			.unwrap();
+		assert_eq!(amounts(&status[0].deposits), vec![NumberOrHex::from(300u128)]);
+		assert!(status[1].deposits.is_empty());
+	}
+
+	#[test]
+	fn rejects_invalid_addresses_and_tx_ids() {
+		let index = WitnessIndex::new(NetworkEnvironment::Mainnet);
+		assert!(index.deposit_status(ForeignChain::Ethereum, &["0x1234".into()]).is_err());
+		assert!(index.deposit_status(ForeignChain::Bitcoin, &["not an address".into()]).is_err());
+		assert!(index.transaction_status(ForeignChain::Ethereum, &["0x1234".into()]).is_err());
+		assert!(index.transaction_status(ForeignChain::Polkadot, &["20".into()]).is_err());
+	}
+
+	#[test]
+	fn indexes_egresses_by_tx_out_id() {
+		let index = WitnessIndex::new(NetworkEnvironment::Mainnet);
+		let tx_out_id = [0xcd; 32];
+
+		index.record_call(&RuntimeCall::BitcoinBroadcaster(
+			pallet_cf_broadcast::Call::transaction_succeeded {
+				tx_out_id,
+				signer_id: ScriptPubkey::Taproot([1; 32]),
+				tx_fee: 1000,
+				tx_metadata: (),
+			},
+		));
+
+		let status = index.egress_status(&[hex::encode(tx_out_id), hex_encode([0xef; 32])]);
+		assert_eq!(status[0].as_ref().unwrap().chain, ForeignChain::Bitcoin);
+		assert_eq!(status[0].as_ref().unwrap().tx_fee, NumberOrHex::from(1000u64));
+		assert!(status[1].is_none());
+
+		let status = index
+			.transaction_status(
+				ForeignChain::Bitcoin,
+				&[Txid::from_byte_array(tx_out_id).to_string()],
+			)
+			.unwrap();
+		assert!(status[0].egress.is_some());
+	}
+
+	#[test]
+	fn prunes_old_records() {
+		let mut index = Index::default();
+		let address = EthereumAddress::repeat_byte(0x11);
+
+		index.record_call(&flip_deposit_call(address, 100, 10), 100);
+		index.record_call(&flip_deposit_call(address, 200, 11), 200);
+		index.record_deposit_transactions(
+			10,
+			Asset::Flip,
+			[(ForeignChainAddress::Eth(address), TxId::Hash([1; 32]))].into_iter(),
+		);
+
+		index.prune(150);
+		assert_eq!(index.entries().len(), 1);
+		assert!(index.deposit_addresses_by_tx.is_empty());
+
+		index.prune(250);
+		assert!(index.entries().is_empty());
+		assert!(index.deposits.is_empty());
+	}
+
+	#[tokio::test]
+	async fn index_file_survives_restart() {
+		let dir = tempfile::tempdir().unwrap();
+		let path = dir.path().join("index.jsonl");
+		let address = EthereumAddress::repeat_byte(0x33);
+
+		let mut index = Index::default();
+		let (file_writer, file_writes) = mpsc::unbounded_channel();
+		let writer = tokio::spawn(write_index_file(path.clone(), file_writes));
+		for (amount, block_height) in [(42, 10), (43, 11)] {
+			file_writer
+				.send(IndexFileWrite::Append(
+					index.record_call(&flip_deposit_call(address, amount, block_height), 0),
+				))
+				.unwrap();
+		}
+		drop(file_writer);
+		writer.await.unwrap().unwrap();
+
+		assert_eq!(load(&path).await.unwrap().entries(), index.entries());
+
+		// An entry that was only partially written is ignored.
+		let mut file = open_for_append(&path).await.unwrap();
+		file.write_all(b"{\"Deposit\":{").await.unwrap();
+		file.flush().await.unwrap();
+		assert_eq!(load(&path).await.unwrap().entries(), index.entries());
+
+		// Compacting replaces the file.
+		index.prune(1);
+		let (file_writer, file_writes) = mpsc::unbounded_channel();
+		let writer = tokio::spawn(write_index_file(path.clone(), file_writes));
+		file_writer.send(IndexFileWrite::Compact(index.entries())).unwrap();
+		drop(file_writer);
+		writer.await.unwrap().unwrap();
+		assert!(load(&path).await.unwrap().entries().is_empty());
+	}
+}
@@ -6,7 +6,7 @@ mod eth;
 use std::collections::HashMap;
 
 use cf_chains::dot::PolkadotHash;
-use cf_primitives::chains::assets::eth::Asset;
+use cf_primitives::{chains::assets::eth::Asset, NetworkEnvironment};
 use chainflip_engine::{
 	state_chain_observer::{
 		self,
@@ -17,7 +17,7 @@ use chainflip_engine::{
 use sp_core::H160;
 use utilities::task_scope;
 
-use crate::DepositTrackerSettings;
+use crate::{settings::DepositTrackerSettings, witness_index::WitnessIndex};
 
 #[derive(Clone)]
 struct EnvironmentParameters {
@@ -28,6 +28,7 @@ struct EnvironmentParameters {
 	usdc_contract_address: H160,
 	supported_erc20_tokens: HashMap<H160, cf_primitives::Asset>,
 	dot_genesis_hash: PolkadotHash,
+	chainflip_network: NetworkEnvironment,
 	btc_network: cf_chains::btc::BitcoinNetwork,
 }
 
@@ -80,13 +81,12 @@ async fn get_env_parameters(state_chain_client: &StateChainClient<()>) -> Enviro
 		.await
 		.expect(STATE_CHAIN_CONNECTION);
 
-	let btc_network = state_chain_client
+	let chainflip_network = state_chain_client
 		.storage_value::<pallet_cf_environment::ChainflipNetworkEnvironment<state_chain_runtime::Runtime>>(
 			state_chain_client.latest_finalized_block().hash,
 		)
 		.await
-		.expect(STATE_CHAIN_CONNECTION)
-		.into();
+		.expect(STATE_CHAIN_CONNECTION);
 
 	EnvironmentParameters {
 		eth_chain_id,
@@ -96,7 +96,8 @@ async fn get_env_parameters(state_chain_client: &StateChainClient<()>) -> Enviro
 		eth_address_checker_address,
 		supported_erc20_tokens,
 		dot_genesis_hash,
-		btc_network,
+		chainflip_network,
+		btc_network: chainflip_network.into(),
 	}
 }
 
@@ -104,11 +105,11 @@ pub(super) async fn start(
 	scope: &task_scope::Scope<'_, anyhow::Error>,
 	settings: DepositTrackerSettings,
 	witness_sender: tokio::sync::broadcast::Sender<state_chain_runtime::RuntimeCall>,
-) -> anyhow::Result<()> {
+) -> anyhow::Result<WitnessIndex> {
 	let (state_chain_stream, unfinalized_chain_stream, state_chain_client) = {
 		state_chain_observer::client::StateChainClient::connect_without_account(
 			scope,
-			&settings.state_chain_ws_endpoint,
+			&settings.state_chain.ws_endpoint,
 			None,
 		)
 		.await?
@@ -116,14 +117,22 @@ pub(super) async fn start(
 
 	let env_params = get_env_parameters(&state_chain_client).await;
 
+	let witness_index =
+		WitnessIndex::start(scope, env_params.chainflip_network, settings.index_file.clone())
+			.await?;
+
 	let epoch_source =
 		EpochSource::builder(scope, state_chain_stream.clone(), state_chain_client.clone()).await;
 
 	let witness_call = {
 		let witness_sender = witness_sender.clone();
+		let witness_index = witness_index.clone();
 		move |call: state_chain_runtime::RuntimeCall, _epoch_index| {
 			let witness_sender = witness_sender.clone();
+			let witness_index = witness_index.clone();
 			async move {
+				witness_index.record_call(&call);
+
 				// Send may fail if there aren't any subscribers,
 				// but it is safe to ignore the error.
 				if let Ok(n) = witness_sender.send(call.clone()) {
@@ -141,6 +150,7 @@ pub(super) async fn start(
 		env_params.clone(),
 		epoch_source.clone(),
 		witness_call.clone(),
+		witness_index.clone(),
 	)
 	.await?;
 
@@ -163,8 +173,9 @@ pub(super) async fn start(
 		state_chain_client,
 		unfinalized_chain_stream,
 		epoch_source,
+		witness_index.clone(),
 	)
 	.await?;
 
-	Ok(())
+	Ok(witness_index)
 }
@@ -3,7 +3,6 @@ use std::sync::Arc;
 use cf_primitives::EpochIndex;
 use chainflip_engine::{
 	btc::retry_rpc::{BtcRetryRpcApi, BtcRetryRpcClient},
-	settings::NodeContainer,
 	state_chain_observer::client::{StateChainClient, StateChainStreamApi},
 	witness::{
 		btc::{btc_source::BtcSource, process_egress},
@@ -13,7 +12,7 @@ use chainflip_engine::{
 use futures::Future;
 use utilities::task_scope::Scope;
 
-use crate::DepositTrackerSettings;
+use crate::settings::DepositTrackerSettings;
 
 use super::EnvironmentParameters;
 pub(super) async fn start<ProcessCall, ProcessingFut>(
@@ -33,12 +32,8 @@ where
 		+ 'static,
 	ProcessingFut: Future<Output = ()> + Send + 'static,
 {
-	let btc_client = BtcRetryRpcClient::new(
-		scope,
-		NodeContainer { primary: settings.btc, backup: None },
-		env_params.btc_network,
-	)
-	.await?;
+	let btc_client =
+		BtcRetryRpcClient::new(scope, settings.btc.nodes, env_params.btc_network).await?;
 
 	let vaults = epoch_source.vaults().await;
 
@@ -3,7 +3,6 @@ use std::sync::Arc;
 use cf_primitives::EpochIndex;
 use chainflip_engine::{
 	dot::retry_rpc::DotRetryRpcClient,
-	settings::NodeContainer,
 	state_chain_observer::client::{
 		storage_api::StorageApi, StateChainClient, StateChainStreamApi,
 	},
@@ -12,13 +11,16 @@ use chainflip_engine::{
 			chain_source::extension::ChainSourceExt, epoch_source::EpochSourceBuilder,
 			STATE_CHAIN_CONNECTION,
 		},
-		dot::{filter_map_events, process_egress, proxy_added_witnessing, DotUnfinalisedSource},
+		dot::{
+			filter_map_events, process_egress, proxy_added_witnessing, transfers,
+			DotUnfinalisedSource,
+		},
 	},
 };
 use futures::Future;
 use utilities::task_scope::Scope;
 
-use crate::DepositTrackerSettings;
+use crate::{settings::DepositTrackerSettings, witness_index::WitnessIndex};
 
 use super::EnvironmentParameters;
 
@@ -30,6 +32,7 @@ pub(super) async fn start<ProcessCall, ProcessingFut>(
 	state_chain_client: Arc<StateChainClient<()>>,
 	state_chain_stream: impl StateChainStreamApi<false> + Clone,
 	epoch_source: EpochSourceBuilder<'_, '_, StateChainClient<()>, (), ()>,
+	witness_index: WitnessIndex,
 ) -> anyhow::Result<()>
 where
 	ProcessCall: Fn(state_chain_runtime::RuntimeCall, EpochIndex) -> ProcessingFut
@@ -39,11 +42,8 @@ where
 		+ 'static,
 	ProcessingFut: Future<Output = ()> + Send + 'static,
 {
-	let dot_client = DotRetryRpcClient::new(
-		scope,
-		NodeContainer { primary: settings.dot_node, backup: None },
-		env_params.dot_genesis_hash,
-	)?;
+	let dot_client =
+		DotRetryRpcClient::new(scope, settings.dot.nodes, env_params.dot_genesis_hash)?;
 
 	let epoch_source = epoch_source
 		.filter_map(
@@ -69,6 +69,17 @@ where
 		.await
 		// Deposit witnessing
 		.dot_deposits(witness_call.clone())
+		.then(move |_epoch, header| {
+			let witness_index = witness_index.clone();
+			async move {
+				witness_index.record_dot_deposit_extrinsics(
+					header.index,
+					transfers(&header.data)
+						.map(|(extrinsic_index, to, _amount)| (extrinsic_index, to)),
+				);
+				header.data
+			}
+		})
 		// Proxy added witnessing
 		.then(proxy_added_witnessing)
 		// Broadcast success
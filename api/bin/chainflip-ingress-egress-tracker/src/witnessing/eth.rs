@@ -5,7 +5,6 @@ use utilities::task_scope;
 
 use chainflip_engine::{
 	eth::retry_rpc::EthersRetryRpcClient,
-	settings::NodeContainer,
 	state_chain_observer::client::{StateChainClient, StateChainStreamApi},
 	witness::{
 		common::{chain_source::extension::ChainSourceExt, epoch_source::EpochSourceBuilder},
@@ -16,7 +15,7 @@ use chainflip_engine::{
 	},
 };
 
-use crate::DepositTrackerSettings;
+use crate::{settings::DepositTrackerSettings, witness_index::WitnessIndex};
 
 use super::EnvironmentParameters;
 
@@ -28,6 +27,7 @@ pub(super) async fn start<ProcessCall, ProcessingFut>(
 	env_params: EnvironmentParameters,
 	epoch_source: EpochSourceBuilder<'_, '_, StateChainClient<()>, (), ()>,
 	witness_call: ProcessCall,
+	witness_index: WitnessIndex,
 ) -> anyhow::Result<()>
 where
 	ProcessCall: Fn(state_chain_runtime::RuntimeCall, cf_primitives::EpochIndex) -> ProcessingFut
@@ -37,16 +37,9 @@ where
 		+ 'static,
 	ProcessingFut: futures::Future<Output = ()> + Send + 'static,
 {
-	let eth_client = {
-		let nodes = NodeContainer { primary: settings.eth_node.clone(), backup: None };
-
-		EthersRetryRpcClient::new(
-			scope,
-			settings.eth_key_path,
-			nodes,
-			env_params.eth_chain_id.into(),
-		)?
-	};
+	// The tracker never broadcasts, so a read-only client (without a private key) is sufficient.
+	let eth_client =
+		EthersRetryRpcClient::new(scope, None, settings.eth, env_params.eth_chain_id.into())?;
 
 	let vaults = epoch_source.vaults().await;
 	let eth_source = EthSource::new(eth_client.clone())
@@ -67,6 +60,22 @@ where
 			env_params.usdc_contract_address,
 		)
 		.await?
+		.then({
+			let witness_index = witness_index.clone();
+			move |_epoch, header| {
+				let witness_index = witness_index.clone();
+				async move {
+					if let Ok(deposit_transactions) = &header.data {
+						witness_index.record_erc20_deposit_transactions(
+							header.index,
+							cf_primitives::Asset::Usdc,
+							deposit_transactions,
+						);
+					}
+					header.data
+				}
+			}
+		})
 		.logging("witnessing USDCDeposits")
 		.spawn(scope);
 
@@ -79,6 +88,19 @@ where
 			env_params.flip_contract_address,
 		)
 		.await?
+		.then(move |_epoch, header| {
+			let witness_index = witness_index.clone();
+			async move {
+				if let Ok(deposit_transactions) = &header.data {
+					witness_index.record_erc20_deposit_transactions(
+						header.index,
+						cf_primitives::Asset::Flip,
+						deposit_transactions,
+					);
+				}
+				header.data
+			}
+		})
 		.logging("witnessing FlipDeposits")
 		.spawn(scope);
 
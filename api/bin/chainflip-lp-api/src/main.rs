@@ -13,6 +13,7 @@ use chainflip_api::{
 		chains::{Bitcoin, Ethereum, Polkadot},
 		AccountRole, Asset, ForeignChain, Hash,
 	},
+	queries::BlockQuery,
 	settings::StateChain,
 	OperatorApi, StateChainApi,
 };
@@ -155,10 +156,14 @@ pub trait Rpc {
 	#[method(name = "asset_balances")]
 	async fn asset_balances(
 		&self,
+		at: Option<BlockQuery>,
 	) -> Result<BTreeMap<ForeignChain, Vec<AssetBalance>>, AnyhowRpcError>;
 
 	#[method(name = "get_open_swap_channels")]
-	async fn get_open_swap_channels(&self) -> Result<OpenSwapChannels, AnyhowRpcError>;
+	async fn get_open_swap_channels(
+		&self,
+		at: Option<BlockQuery>,
+	) -> Result<OpenSwapChannels, AnyhowRpcError>;
 }
 
 pub struct RpcServerImpl {
@@ -220,12 +225,14 @@ impl RpcServer for RpcServerImpl {
 			.await?)
 	}
 
-	/// Returns a list of all assets and their free balance in json format
+	/// Returns a list of all assets and their free balance in json format, optionally at a given
+	/// block hash or number.
 	async fn asset_balances(
 		&self,
+		at: Option<BlockQuery>,
 	) -> Result<BTreeMap<ForeignChain, Vec<AssetBalance>>, AnyhowRpcError> {
 		let mut balances = BTreeMap::<_, Vec<_>>::new();
-		for (asset, balance) in self.api.query_api().get_balances(None).await? {
+		for (asset, balance) in self.api.query_api().get_balances(at).await? {
 			balances
 				.entry(ForeignChain::from(asset))
 				.or_default()
@@ -327,13 +334,18 @@ impl RpcServer for RpcServerImpl {
 			.await?)
 	}
 
-	async fn get_open_swap_channels(&self) -> Result<OpenSwapChannels, AnyhowRpcError> {
+	async fn get_open_swap_channels(
+		&self,
+		at: Option<BlockQuery>,
+	) -> Result<OpenSwapChannels, AnyhowRpcError> {
 		let api = self.api.query_api();
 
+		// Resolve the block once, so that all chains are queried at the same block.
+		let at = Some(BlockQuery::Hash(api.block_hash(at).await?));
 		let (ethereum, bitcoin, polkadot) = tokio::try_join!(
-			api.get_open_swap_channels::<Ethereum>(None),
-			api.get_open_swap_channels::<Bitcoin>(None),
-			api.get_open_swap_channels::<Polkadot>(None),
+			api.get_open_swap_channels::<Ethereum>(at),
+			api.get_open_swap_channels::<Bitcoin>(at),
+			api.get_open_swap_channels::<Polkadot>(at),
 		)?;
 		Ok(OpenSwapChannels { ethereum, bitcoin, polkadot })
 	}
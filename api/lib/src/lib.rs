@@ -32,10 +32,14 @@ pub mod primitives {
 }
 pub use chainflip_engine::state_chain_observer::client::{
 	base_rpc_api::{BaseRpcApi, RawRpcApi},
-	extrinsic_api::signed::{SignedExtrinsicApi, UntilFinalized},
+	extrinsic_api::signed::{
+		DryRunError, ExtrinsicError, FinalizationError, InBlockError, SignedExtrinsicApi,
+		UntilFinalized,
+	},
 };
 
 pub mod lp;
+pub mod offline;
 pub mod queries;
 
 pub use chainflip_engine::settings;
@@ -227,30 +231,29 @@ pub trait OperatorApi: SignedExtrinsicApi + RotateSessionKeysApi + AuctionPhaseA
 		Ok(tx_hash)
 	}
 
-	async fn stop_bidding(&self) -> Result<()> {
+	async fn stop_bidding(&self) -> Result<H256> {
 		let (tx_hash, ..) = self
 			.submit_signed_extrinsic(pallet_cf_funding::Call::stop_bidding {})
 			.await
 			.until_in_block()
 			.await
 			.context("Could not stop bidding")?;
-		println!("Account stopped bidding, in tx {tx_hash:#x}.");
-		Ok(())
+
+		Ok(tx_hash)
 	}
 
-	async fn start_bidding(&self) -> Result<()> {
+	async fn start_bidding(&self) -> Result<H256> {
 		let (tx_hash, ..) = self
 			.submit_signed_extrinsic(pallet_cf_funding::Call::start_bidding {})
 			.await
 			.until_in_block()
 			.await
 			.context("Could not start bidding")?;
-		println!("Account started bidding at tx {tx_hash:#x}.");
 
-		Ok(())
+		Ok(tx_hash)
 	}
 
-	async fn set_vanity_name(&self, name: String) -> Result<()> {
+	async fn set_vanity_name(&self, name: String) -> Result<H256> {
 		if name.len() > MAX_LENGTH_FOR_VANITY_NAME {
 			bail!("Name too long. Max length is {} characters.", MAX_LENGTH_FOR_VANITY_NAME,);
 		}
@@ -263,30 +266,29 @@ pub trait OperatorApi: SignedExtrinsicApi + RotateSessionKeysApi + AuctionPhaseA
 			.until_in_block()
 			.await
 			.context("Could not set vanity name for your account")?;
-		println!("Vanity name set at tx {tx_hash:#x}.");
-		Ok(())
+
+		Ok(tx_hash)
 	}
 }
 
 #[async_trait]
 pub trait GovernanceApi: SignedExtrinsicApi {
-	async fn force_rotation(&self) -> Result<()> {
-		println!("Submitting governance proposal for rotation.");
-		self.submit_signed_extrinsic(pallet_cf_governance::Call::propose_governance_extrinsic {
-			call: Box::new(pallet_cf_validator::Call::force_rotation {}.into()),
-			execution: ExecutionMode::Automatic,
-		})
-		.await
-		.until_in_block()
-		.await
-		.context("Failed to submit rotation governance proposal")?;
-
-		println!("If you're the governance dictator, the rotation will begin soon.");
+	async fn force_rotation(&self) -> Result<H256> {
+		let (tx_hash, ..) = self
+			.submit_signed_extrinsic(pallet_cf_governance::Call::propose_governance_extrinsic {
+				call: Box::new(pallet_cf_validator::Call::force_rotation {}.into()),
+				execution: ExecutionMode::Automatic,
+			})
+			.await
+			.until_in_block()
+			.await
+			.context("Failed to submit rotation governance proposal")?;
 
-		Ok(())
+		Ok(tx_hash)
 	}
 }
 
+#[derive(Debug, Clone, Serialize)]
 pub struct SwapDepositAddress {
 	pub address: String,
 	pub issued_block: state_chain_runtime::BlockNumber,
@@ -353,6 +355,7 @@ pub fn clean_foreign_chain_address(chain: ForeignChain, address: &str) -> Result
 		ForeignChain::Polkadot =>
 			EncodedAddress::Dot(PolkadotAccountId::from_str(address).map(|id| *id.aliased_ref())?),
 		ForeignChain::Bitcoin => EncodedAddress::Btc(address.as_bytes().to_vec()),
+		ForeignChain::Arbitrum => EncodedAddress::Arb(clean_hex_address(address)?),
 	})
 }
 
@@ -0,0 +1,185 @@
+//! Support for signing extrinsics with keys that are kept on a machine that is not connected to
+//! the network. The unsigned payload is built on an online machine, signed on the offline machine,
+//! and the signed extrinsic is then submitted from the online machine.
+
+use super::*;
+use chainflip_engine::state_chain_observer::client::extrinsic_api::signed::signer::PairSigner;
+use codec::{Decode, DecodeAll, Encode};
+use std::path::Path;
+
+pub use chainflip_engine::state_chain_observer::client::extrinsic_api::signed::signer::UnsignedPayload;
+pub use state_chain_runtime::{Nonce, UncheckedExtrinsic};
+
+/// The calls that can be built for offline signing.
+#[derive(Clone, Debug)]
+pub enum OfflineCall {
+	Redeem {
+		amount: primitives::RedemptionAmount,
+		address: EthereumAddress,
+		executor: Option<EthereumAddress>,
+	},
+	BindRedeemAddress {
+		address: EthereumAddress,
+	},
+	BindExecutorAddress {
+		address: EthereumAddress,
+	},
+	StopBidding,
+	StartBidding,
+	/// Unlike `rotate`, the session keys need to be provided, as the offline machine doesn't run
+	/// the node that holds them.
+	SetKeys {
+		aura_key: [u8; 32],
+		grandpa_key: [u8; 32],
+	},
+}
+
+impl From<OfflineCall> for RuntimeCall {
+	fn from(call: OfflineCall) -> Self {
+		match call {
+			OfflineCall::Redeem { amount, address, executor } =>
+				pallet_cf_funding::Call::redeem { amount, address, executor }.into(),
+			OfflineCall::BindRedeemAddress { address } =>
+				pallet_cf_funding::Call::bind_redeem_address { address }.into(),
+			OfflineCall::BindExecutorAddress { address } =>
+				pallet_cf_funding::Call::bind_executor_address { executor_address: address }.into(),
+			OfflineCall::StopBidding => pallet_cf_funding::Call::stop_bidding {}.into(),
+			OfflineCall::StartBidding => pallet_cf_funding::Call::start_bidding {}.into(),
+			OfflineCall::SetKeys { aura_key, grandpa_key } => pallet_cf_validator::Call::set_keys {
+				keys: SessionKeys {
+					aura: AuraId::from(SrPublic::from_raw(aura_key)),
+					grandpa: GrandpaId::from(EdPublic::from_raw(grandpa_key)),
+				},
+				proof: [0; 1].to_vec(),
+			}
+			.into(),
+		}
+	}
+}
+
+/// Builds a payload for `signer` to sign, valid for `lifetime` blocks from the latest finalized
+/// block. If no nonce is given, the next nonce of the account is used. Also returns the first
+/// block the signed extrinsic can no longer be included in.
+pub async fn build_unsigned_payload(
+	ws_endpoint: &str,
+	signer: AccountId32,
+	call: OfflineCall,
+	nonce: Option<Nonce>,
+	lifetime: state_chain_runtime::BlockNumber,
+) -> Result<(UnsignedPayload, state_chain_runtime::BlockNumber)> {
+	if lifetime > state_chain_runtime::BlockHashCount::get() {
+		bail!("The lifetime can be at most {} blocks.", state_chain_runtime::BlockHashCount::get());
+	}
+
+	let base_rpc_client = DefaultRpcClient::connect(ws_endpoint).await?;
+
+	let genesis_hash = base_rpc_client
+		.block_hash(0)
+		.await?
+		.ok_or_else(|| anyhow!("The State Chain has no genesis block"))?;
+	let block_hash = base_rpc_client.latest_finalized_block_hash().await?;
+	let block_number = base_rpc_client.block_header(block_hash).await?.number;
+	let runtime_version = base_rpc_client.runtime_version().await?;
+	let nonce = match nonce {
+		Some(nonce) => nonce,
+		None => base_rpc_client.next_account_nonce(signer.clone()).await?,
+	};
+
+	let (payload, valid_blocks) = UnsignedPayload::new(
+		call.into(),
+		signer,
+		&runtime_version,
+		genesis_hash,
+		block_hash,
+		block_number,
+		lifetime,
+		nonce,
+	);
+
+	Ok((payload, valid_blocks.end))
+}
+
+/// Signs the payload. This doesn't require a connection to the State Chain.
+pub fn sign_payload(
+	payload: UnsignedPayload,
+	signing_key: sp_core::sr25519::Pair,
+) -> Result<UncheckedExtrinsic> {
+	PairSigner::new(signing_key).sign(payload)
+}
+
+/// Submits an extrinsic that was signed offline, returning its hash.
+pub async fn submit_signed_extrinsic(
+	ws_endpoint: &str,
+	extrinsic: UncheckedExtrinsic,
+) -> Result<H256> {
+	DefaultRpcClient::connect(ws_endpoint)
+		.await?
+		.submit_extrinsic(extrinsic)
+		.await
+		.context("Failed to submit the signed extrinsic")
+}
+
+/// Reads a signing key in the same format as the `signing_key_file` used by the engine.
+pub fn signing_key_from_file(path: &Path) -> Result<sp_core::sr25519::Pair> {
+	Ok(sp_core::sr25519::Pair::from_seed(&utilities::read_clean_and_decode_hex_str_file(
+		path,
+		"Signing Key",
+		|str| {
+			<[u8; 32]>::try_from(hex::decode(str)?)
+				.map_err(|e| anyhow!("Failed to decode signing key: Wrong length. {e:?}"))
+		},
+	)?))
+}
+
+/// Derives the signing key from a seed phrase, in the same way as `generate_signing_key`.
+pub fn signing_key_from_seed_phrase(seed_phrase: &str) -> Result<sp_core::sr25519::Pair> {
+	use bip39::{Language, Mnemonic};
+
+	let mnemonic = Mnemonic::from_phrase(seed_phrase, Language::English)?;
+	sp_core::sr25519::Pair::from_phrase(mnemonic.phrase(), None)
+		.map(|(pair, _seed)| pair)
+		.map_err(|e| anyhow!("Invalid seed phrase. Error: {e:?}"))
+}
+
+/// Payloads and signed extrinsics are exchanged between machines as hex encoded SCALE.
+pub fn encode_hex<T: Encode>(value: &T) -> String {
+	format!("0x{}", hex::encode(value.encode()))
+}
+
+pub fn decode_hex<T: Decode>(hex_str: &str) -> Result<T> {
+	let bytes = hex::decode(hex_str.trim().trim_start_matches("0x")).context("Invalid hex")?;
+	T::decode_all(&mut &bytes[..]).context("Could not decode the SCALE encoded data")
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn seed_phrase_derives_the_generated_signing_key() {
+		let (seed_phrase, key_pair, account_id) = generate_signing_key(None).unwrap();
+
+		let signing_key = signing_key_from_seed_phrase(&seed_phrase).unwrap();
+
+		assert_eq!(signing_key.public().to_vec(), key_pair.public_key);
+		assert_eq!(AccountId32::from(signing_key.public()), account_id);
+	}
+
+	#[test]
+	fn hex_round_trip() {
+		let payload = UnsignedPayload::new(
+			OfflineCall::StopBidding.into(),
+			AccountId32::new([1; 32]),
+			&Default::default(),
+			H256::repeat_byte(1),
+			H256::repeat_byte(2),
+			100,
+			64,
+			5,
+		)
+		.0;
+
+		assert_eq!(decode_hex::<UnsignedPayload>(&encode_hex(&payload)).unwrap(), payload);
+		assert!(decode_hex::<UnsignedPayload>("0x1234").is_err());
+	}
+}
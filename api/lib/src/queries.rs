@@ -6,12 +6,17 @@ use chainflip_engine::state_chain_observer::client::{
 };
 use codec::Decode;
 use frame_support::sp_runtime::DigestItem;
+use futures::{Future, StreamExt, TryStreamExt};
 use pallet_cf_ingress_egress::DepositChannelDetails;
 use pallet_cf_validator::RotationPhase;
 use serde::Deserialize;
 use sp_consensus_aura::{Slot, AURA_ENGINE_ID};
 use state_chain_runtime::PalletInstanceAlias;
-use std::{collections::BTreeMap, ops::Deref, sync::Arc};
+use std::{
+	collections::BTreeMap,
+	ops::{Deref, RangeInclusive},
+	sync::Arc,
+};
 use tracing::log;
 use utilities::task_scope;
 
@@ -22,6 +27,39 @@ pub struct SwapChannelInfo<C: Chain> {
 	destination_asset: any::Asset,
 }
 
+/// Identifies the block at which a query should be made. Queries made without a block default to
+/// the latest finalized block.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum BlockQuery {
+	Hash(state_chain_runtime::Hash),
+	Number(state_chain_runtime::BlockNumber),
+}
+
+impl From<state_chain_runtime::Hash> for BlockQuery {
+	fn from(hash: state_chain_runtime::Hash) -> Self {
+		Self::Hash(hash)
+	}
+}
+
+impl From<state_chain_runtime::BlockNumber> for BlockQuery {
+	fn from(number: state_chain_runtime::BlockNumber) -> Self {
+		Self::Number(number)
+	}
+}
+
+/// The result of a query made at a particular block.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AtBlock<T> {
+	pub block_number: state_chain_runtime::BlockNumber,
+	pub block_hash: state_chain_runtime::Hash,
+	pub value: T,
+}
+
+// The maximum number of blocks queried concurrently by `QueryApi::query_range`.
+const MAX_CONCURRENT_RANGE_QUERIES: usize = 16;
+
+#[derive(Debug, Clone, Serialize)]
 pub struct PreUpdateStatus {
 	pub rotation: bool,
 	pub is_authority: bool,
@@ -52,16 +90,76 @@ impl QueryApi {
 		Ok(Self { state_chain_client })
 	}
 
+	/// Resolves the block hash to query at. Only finalized blocks can be queried by number, as
+	/// the number of an unfinalized block doesn't uniquely identify it.
+	pub async fn block_hash(
+		&self,
+		at: Option<BlockQuery>,
+	) -> Result<state_chain_runtime::Hash, anyhow::Error> {
+		match at {
+			None => Ok(self.state_chain_client.latest_finalized_block().hash),
+			Some(BlockQuery::Hash(hash)) => Ok(hash),
+			Some(BlockQuery::Number(number)) => {
+				let latest_finalized = self.state_chain_client.latest_finalized_block();
+				if number > latest_finalized.number {
+					bail!(
+						"Block {number} is not finalized yet (latest finalized block is {})",
+						latest_finalized.number
+					);
+				}
+				self.state_chain_client
+					.base_rpc_client
+					.block_hash(number)
+					.await?
+					.ok_or_else(|| anyhow!("Block {number} not found"))
+			},
+		}
+	}
+
+	/// Runs `query` at every `step`th block in `range` (which must be finalized), returning the
+	/// results in block order. e.g. to get the LP balances at every block in a range:
+	///
+	/// `query_api.query_range(from..=to, 1, |at| query_api.get_balances(Some(at)))`
+	pub async fn query_range<T, Fut>(
+		&self,
+		range: RangeInclusive<state_chain_runtime::BlockNumber>,
+		step: usize,
+		query: impl Fn(BlockQuery) -> Fut,
+	) -> Result<Vec<AtBlock<T>>, anyhow::Error>
+	where
+		Fut: Future<Output = Result<T, anyhow::Error>>,
+	{
+		if step == 0 {
+			bail!("Step must be greater than zero");
+		}
+
+		futures::stream::iter(range.step_by(step))
+			.map(|block_number| {
+				let query = &query;
+				async move {
+					let block_hash =
+						self.block_hash(Some(BlockQuery::Number(block_number))).await?;
+					Ok(AtBlock {
+						block_number,
+						block_hash,
+						value: query(BlockQuery::Hash(block_hash)).await?,
+					})
+				}
+			})
+			.buffered(MAX_CONCURRENT_RANGE_QUERIES)
+			.try_collect()
+			.await
+	}
+
 	pub async fn get_open_swap_channels<C: Chain + PalletInstanceAlias>(
 		&self,
-		block_hash: Option<state_chain_runtime::Hash>,
+		at: Option<BlockQuery>,
 	) -> Result<Vec<SwapChannelInfo<C>>, anyhow::Error>
 	where
 		state_chain_runtime::Runtime:
 			pallet_cf_ingress_egress::Config<C::Instance, TargetChain = C>,
 	{
-		let block_hash =
-			block_hash.unwrap_or_else(|| self.state_chain_client.latest_finalized_block().hash);
+		let block_hash = self.block_hash(at).await?;
 
 		let (channel_details, network_environment) = tokio::try_join!(
 			self.state_chain_client
@@ -96,10 +194,9 @@ impl QueryApi {
 
 	pub async fn get_balances(
 		&self,
-		block_hash: Option<state_chain_runtime::Hash>,
+		at: Option<BlockQuery>,
 	) -> Result<BTreeMap<Asset, AssetAmount>> {
-		let block_hash =
-			block_hash.unwrap_or_else(|| self.state_chain_client.latest_finalized_block().hash);
+		let block_hash = self.block_hash(at).await?;
 
 		futures::future::join_all(Asset::all().iter().map(|asset| async {
 			Ok((
@@ -121,11 +218,10 @@ impl QueryApi {
 
 	pub async fn get_bound_redeem_address(
 		&self,
-		block_hash: Option<state_chain_runtime::Hash>,
+		at: Option<BlockQuery>,
 		account_id: Option<state_chain_runtime::AccountId>,
 	) -> Result<Option<EthereumAddress>, anyhow::Error> {
-		let block_hash =
-			block_hash.unwrap_or_else(|| self.state_chain_client.latest_finalized_block().hash);
+		let block_hash = self.block_hash(at).await?;
 		let account_id = account_id.unwrap_or_else(|| self.state_chain_client.account_id());
 
 		Ok(self
@@ -139,11 +235,10 @@ impl QueryApi {
 
 	pub async fn get_bound_executor_address(
 		&self,
-		block_hash: Option<state_chain_runtime::Hash>,
+		at: Option<BlockQuery>,
 		account_id: Option<state_chain_runtime::AccountId>,
 	) -> Result<Option<EthereumAddress>, anyhow::Error> {
-		let block_hash =
-			block_hash.unwrap_or_else(|| self.state_chain_client.latest_finalized_block().hash);
+		let block_hash = self.block_hash(at).await?;
 		let account_id = account_id.unwrap_or_else(|| self.state_chain_client.account_id());
 
 		Ok(self
@@ -157,11 +252,10 @@ impl QueryApi {
 
 	pub async fn get_restricted_balances(
 		&self,
-		block_hash: Option<state_chain_runtime::Hash>,
+		at: Option<BlockQuery>,
 		account_id: Option<state_chain_runtime::AccountId>,
 	) -> Result<BTreeMap<EthereumAddress, FlipBalance>> {
-		let block_hash =
-			block_hash.unwrap_or_else(|| self.state_chain_client.latest_finalized_block().hash);
+		let block_hash = self.block_hash(at).await?;
 		let account_id = account_id.unwrap_or_else(|| self.state_chain_client.account_id());
 
 		Ok(self
@@ -175,11 +269,10 @@ impl QueryApi {
 
 	pub async fn pre_update_check(
 		&self,
-		block_hash: Option<state_chain_runtime::Hash>,
+		at: Option<BlockQuery>,
 		account_id: Option<state_chain_runtime::AccountId>,
 	) -> Result<PreUpdateStatus, anyhow::Error> {
-		let block_hash =
-			block_hash.unwrap_or_else(|| self.state_chain_client.latest_finalized_block().hash);
+		let block_hash = self.block_hash(at).await?;
 		let account_id = account_id.unwrap_or_else(|| self.state_chain_client.account_id());
 
 		let mut result =
@@ -266,6 +359,19 @@ mod test {
 		);
 	}
 
+	#[test]
+	fn test_block_query_deserialization() {
+		assert_eq!(serde_json::from_str::<BlockQuery>("42").unwrap(), BlockQuery::Number(42));
+
+		let hash = state_chain_runtime::Hash::repeat_byte(0xab);
+		assert_eq!(
+			serde_json::from_str::<BlockQuery>(&format!("\"{hash:?}\"")).unwrap(),
+			BlockQuery::Hash(hash)
+		);
+
+		assert!(serde_json::from_str::<BlockQuery>("\"not a hash\"").is_err());
+	}
+
 	#[test]
 	fn test_compute_distance() {
 		let index: usize = 5;
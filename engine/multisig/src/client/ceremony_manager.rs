@@ -17,8 +17,8 @@ use crate::{
 	client,
 	client::{
 		ceremony_id_string,
-		common::{KeygenFailureReason, SigningFailureReason},
-		signing::PayloadAndKey,
+		common::{BroadcastConflicts, KeygenFailureReason, SigningFailureReason},
+		signing::{PayloadAndKey, SharedPreprocessedNoncePool},
 		CeremonyRequestDetails,
 	},
 	crypto::{CryptoScheme, Rng},
@@ -37,7 +37,8 @@ use client::{ceremony_runner::CeremonyRunner, utils::PartyIdxMapping};
 use tokio::sync::oneshot;
 
 use client::common::{
-	broadcast::BroadcastStage, CeremonyCommon, CeremonyFailureReason, KeygenResultInfo,
+	broadcast::BroadcastStage, BroadcastSigner, CeremonyCommon, CeremonyFailureReason,
+	KeygenResultInfo,
 };
 
 use super::{
@@ -52,7 +53,7 @@ use super::{
 
 pub type CeremonyOutcome<C> = Result<
 	<C as CeremonyTrait>::Output,
-	(BTreeSet<AccountId>, <C as CeremonyTrait>::FailureReason),
+	(BTreeSet<AccountId>, <C as CeremonyTrait>::FailureReason, BroadcastConflicts),
 >;
 
 pub type CeremonyResultSender<Ceremony> = oneshot::Sender<CeremonyOutcome<Ceremony>>;
@@ -117,10 +118,12 @@ impl<C: CryptoScheme> CeremonyTrait for SigningCeremony<C> {
 /// generating signer indexes based on the list of parties
 pub struct CeremonyManager<Chain: ChainSigning> {
 	my_account_id: AccountId,
+	broadcast_signer: Arc<BroadcastSigner>,
 	outgoing_p2p_message_sender: UnboundedSender<OutgoingMultisigStageMessages>,
 	signing_states: CeremonyStates<SigningCeremony<Chain::CryptoScheme>>,
 	keygen_states: CeremonyStates<KeygenCeremony<Chain::CryptoScheme>>,
 	latest_ceremony_id: CeremonyId,
+	preprocessed_nonce_pool: SharedPreprocessedNoncePool<Chain::CryptoScheme>,
 }
 
 // A CeremonyStage for either keygen or signing
@@ -150,6 +153,8 @@ pub fn prepare_signing_request<Crypto: CryptoScheme>(
 	signers: BTreeSet<AccountId>,
 	signing_info: Vec<(KeygenResultInfo<Crypto>, Crypto::SigningPayload)>,
 	outgoing_p2p_message_sender: &UnboundedSender<OutgoingMultisigStageMessages>,
+	broadcast_signer: &Arc<BroadcastSigner>,
+	preprocessed_nonce_pool: &SharedPreprocessedNoncePool<Crypto>,
 	rng: Rng,
 ) -> Result<PreparedRequest<SigningCeremony<Crypto>>, SigningFailureReason> {
 	// Sanity check: all keys must have the same parameters
@@ -195,6 +200,7 @@ pub fn prepare_signing_request<Crypto: CryptoScheme>(
 			all_idxs: signer_idxs,
 			rng,
 			number_of_signing_payloads: Some(signing_info.len()),
+			broadcast_signer: broadcast_signer.clone(),
 		};
 
 		let processor = AwaitCommitments1::<Crypto>::new(
@@ -205,6 +211,7 @@ pub fn prepare_signing_request<Crypto: CryptoScheme>(
 					.map(|(key_info, payload)| PayloadAndKey { payload, key: key_info.key })
 					.collect(),
 			},
+			preprocessed_nonce_pool.clone(),
 		);
 
 		Box::new(BroadcastStage::new(processor, common))
@@ -218,6 +225,7 @@ pub fn prepare_key_handover_request<Crypto: CryptoScheme>(
 	own_account_id: &AccountId,
 	participants: BTreeSet<AccountId>,
 	outgoing_p2p_message_sender: &UnboundedSender<OutgoingMultisigStageMessages>,
+	broadcast_signer: &Arc<BroadcastSigner>,
 	resharing_context: ResharingContext<Crypto>,
 	rng: Rng,
 ) -> Result<PreparedRequest<KeygenCeremony<Crypto>>, KeygenFailureReason> {
@@ -242,6 +250,7 @@ pub fn prepare_key_handover_request<Crypto: CryptoScheme>(
 			all_idxs: signer_idxs,
 			rng,
 			number_of_signing_payloads: None,
+			broadcast_signer: broadcast_signer.clone(),
 		};
 
 		let processor = PubkeySharesStage0::new(
@@ -262,6 +271,7 @@ pub fn prepare_keygen_request<Crypto: CryptoScheme>(
 	own_account_id: &AccountId,
 	participants: BTreeSet<AccountId>,
 	outgoing_p2p_message_sender: &UnboundedSender<OutgoingMultisigStageMessages>,
+	broadcast_signer: &Arc<BroadcastSigner>,
 	rng: Rng,
 ) -> Result<PreparedRequest<KeygenCeremony<Crypto>>, KeygenFailureReason> {
 	let validator_mapping = Arc::new(PartyIdxMapping::from_participants(participants.clone()));
@@ -285,6 +295,7 @@ pub fn prepare_keygen_request<Crypto: CryptoScheme>(
 			all_idxs: signer_idxs,
 			rng,
 			number_of_signing_payloads: None,
+			broadcast_signer: broadcast_signer.clone(),
 		};
 
 		let keygen_common = client::keygen::KeygenCommon::new(
@@ -325,7 +336,7 @@ pub fn deserialize_for_version<C: CryptoScheme>(
 	message: VersionedCeremonyMessage,
 ) -> Result<MultisigMessage<C::Point>> {
 	match message.version {
-		1 => bincode::deserialize::<'_, MultisigMessage<C::Point>>(&message.payload).map_err(|e| {
+		2 => bincode::deserialize::<'_, MultisigMessage<C::Point>>(&message.payload).map_err(|e| {
 			anyhow!("Failed to deserialize message (version: {}): {:?}", message.version, e)
 		}),
 		_ => Err(anyhow!("Unsupported message version: {}", message.version)),
@@ -335,15 +346,18 @@ pub fn deserialize_for_version<C: CryptoScheme>(
 impl<Chain: ChainSigning> CeremonyManager<Chain> {
 	pub fn new(
 		my_account_id: AccountId,
+		broadcast_signer: Arc<BroadcastSigner>,
 		outgoing_p2p_message_sender: UnboundedSender<OutgoingMultisigStageMessages>,
 		latest_ceremony_id: CeremonyId,
 	) -> Self {
 		CeremonyManager {
 			my_account_id,
+			broadcast_signer,
 			outgoing_p2p_message_sender,
 			signing_states: CeremonyStates::new(),
 			keygen_states: CeremonyStates::new(),
 			latest_ceremony_id,
+			preprocessed_nonce_pool: Default::default(),
 		}
 	}
 
@@ -476,26 +490,30 @@ impl<Chain: ChainSigning> CeremonyManager<Chain> {
 
 		debug!("Processing a key handover request");
 
-		let request =
-			match prepare_key_handover_request(
-				ceremony_id,
-				&self.my_account_id,
-				participants,
-				&self.outgoing_p2p_message_sender,
-				resharing_context,
-				rng,
-			) {
-				Ok(request) => request,
-				Err(failed_outcome) => {
-					let _res = result_sender.send(CeremonyOutcome::<
-						KeygenCeremony<Chain::CryptoScheme>,
-					>::Err((BTreeSet::new(), failed_outcome)));
-
-					// Remove a possible unauthorised ceremony
-					self.keygen_states.cleanup_unauthorised_ceremony(&ceremony_id);
-					return
-				},
-			};
+		let request = match prepare_key_handover_request(
+			ceremony_id,
+			&self.my_account_id,
+			participants,
+			&self.outgoing_p2p_message_sender,
+			&self.broadcast_signer,
+			resharing_context,
+			rng,
+		) {
+			Ok(request) => request,
+			Err(failed_outcome) => {
+				let _res = result_sender.send(
+					CeremonyOutcome::<KeygenCeremony<Chain::CryptoScheme>>::Err((
+						BTreeSet::new(),
+						failed_outcome,
+						Default::default(),
+					)),
+				);
+
+				// Remove a possible unauthorised ceremony
+				self.keygen_states.cleanup_unauthorised_ceremony(&ceremony_id);
+				return
+			},
+		};
 
 		let ceremony_handle =
 			self.keygen_states.get_state_or_create_unauthorized::<Chain>(ceremony_id, scope);
@@ -528,25 +546,29 @@ impl<Chain: ChainSigning> CeremonyManager<Chain> {
 
 		debug!("Processing a keygen request");
 
-		let request =
-			match prepare_keygen_request(
-				ceremony_id,
-				&self.my_account_id,
-				participants,
-				&self.outgoing_p2p_message_sender,
-				rng,
-			) {
-				Ok(request) => request,
-				Err(failed_outcome) => {
-					let _res = result_sender.send(CeremonyOutcome::<
-						KeygenCeremony<Chain::CryptoScheme>,
-					>::Err((BTreeSet::new(), failed_outcome)));
-
-					// Remove a possible unauthorised ceremony
-					self.keygen_states.cleanup_unauthorised_ceremony(&ceremony_id);
-					return
-				},
-			};
+		let request = match prepare_keygen_request(
+			ceremony_id,
+			&self.my_account_id,
+			participants,
+			&self.outgoing_p2p_message_sender,
+			&self.broadcast_signer,
+			rng,
+		) {
+			Ok(request) => request,
+			Err(failed_outcome) => {
+				let _res = result_sender.send(
+					CeremonyOutcome::<KeygenCeremony<Chain::CryptoScheme>>::Err((
+						BTreeSet::new(),
+						failed_outcome,
+						Default::default(),
+					)),
+				);
+
+				// Remove a possible unauthorised ceremony
+				self.keygen_states.cleanup_unauthorised_ceremony(&ceremony_id);
+				return
+			},
+		};
 
 		let ceremony_handle =
 			self.keygen_states.get_state_or_create_unauthorized::<Chain>(ceremony_id, scope);
@@ -589,13 +611,19 @@ impl<Chain: ChainSigning> CeremonyManager<Chain> {
 			signers,
 			signing_info,
 			&self.outgoing_p2p_message_sender,
+			&self.broadcast_signer,
+			&self.preprocessed_nonce_pool,
 			rng,
 		) {
 			Ok(request) => request,
 			Err(failed_outcome) => {
 				let _res = result_sender.send(CeremonyOutcome::<
 					SigningCeremony<Chain::CryptoScheme>,
-				>::Err((BTreeSet::new(), failed_outcome)));
+				>::Err((
+					BTreeSet::new(),
+					failed_outcome,
+					Default::default(),
+				)));
 
 				// Remove a possible unauthorised ceremony
 				self.signing_states.cleanup_unauthorised_ceremony(&ceremony_id);
@@ -7,11 +7,13 @@ use crate::{
 			CeremonyHandle, CeremonyManager, CeremonyRequestState, SigningCeremony,
 		},
 		ceremony_runner::CeremonyRunner,
-		common::{BroadcastFailureReason, SigningFailureReason, SigningStageName},
+		common::{
+			BroadcastConflicts, BroadcastFailureReason, SigningFailureReason, SigningStageName,
+		},
 		gen_keygen_data_hash_comm1, get_key_data_for_test,
 		helpers::{
-			ACCOUNT_IDS, CEREMONY_TIMEOUT_DURATION, DEFAULT_KEYGEN_SEED, DEFAULT_SIGNING_SEED,
-			INITIAL_LATEST_CEREMONY_ID,
+			new_broadcast_signer, ACCOUNT_IDS, CEREMONY_TIMEOUT_DURATION, DEFAULT_KEYGEN_SEED,
+			DEFAULT_SIGNING_SEED, INITIAL_LATEST_CEREMONY_ID,
 		},
 		CeremonyRequest, CeremonyRequestDetails, KeygenRequestDetails, MultisigData,
 		SigningRequestDetails,
@@ -39,7 +41,7 @@ async fn run_on_request_to_sign<Chain: ChainSigning>(
 ) -> oneshot::Receiver<
 	Result<
 		Vec<<Chain::CryptoScheme as CryptoScheme>::Signature>,
-		(BTreeSet<AccountId32>, SigningFailureReason),
+		(BTreeSet<AccountId32>, SigningFailureReason, BroadcastConflicts),
 	>,
 > {
 	let (result_sender, result_receiver) = oneshot::channel();
@@ -74,7 +76,8 @@ fn new_ceremony_manager_for_test(
 	latest_ceremony_id: CeremonyId,
 ) -> CeremonyManager<EthSigning> {
 	CeremonyManager::<EthSigning>::new(
-		our_account_id,
+		our_account_id.clone(),
+		new_broadcast_signer(&our_account_id),
 		tokio::sync::mpsc::unbounded_channel().0,
 		latest_ceremony_id,
 	)
@@ -86,7 +89,7 @@ fn send_signing_request<C: CryptoScheme>(
 	participants: BTreeSet<AccountId32>,
 	ceremony_id: CeremonyId,
 ) -> tokio::sync::oneshot::Receiver<
-	Result<Vec<C::Signature>, (BTreeSet<AccountId32>, SigningFailureReason)>,
+	Result<Vec<C::Signature>, (BTreeSet<AccountId32>, SigningFailureReason, BroadcastConflicts)>,
 > {
 	let (result_sender, result_receiver) = oneshot::channel();
 
@@ -119,8 +122,12 @@ fn spawn_ceremony_manager<Chain: ChainSigning>(
 	let (ceremony_request_sender, ceremony_request_receiver) = mpsc::unbounded_channel();
 	let (incoming_p2p_sender, incoming_p2p_receiver) = mpsc::unbounded_channel();
 	let (outgoing_p2p_sender, outgoing_p2p_receiver) = mpsc::unbounded_channel();
-	let ceremony_manager =
-		CeremonyManager::<Chain>::new(our_account_id, outgoing_p2p_sender, latest_ceremony_id);
+	let ceremony_manager = CeremonyManager::<Chain>::new(
+		our_account_id.clone(),
+		new_broadcast_signer(&our_account_id),
+		outgoing_p2p_sender,
+		latest_ceremony_id,
+	);
 	tokio::spawn(ceremony_manager.run(ceremony_request_receiver, incoming_p2p_receiver));
 
 	(ceremony_request_sender, incoming_p2p_sender, outgoing_p2p_receiver)
@@ -194,7 +201,7 @@ async fn should_ignore_rts_with_insufficient_number_of_signers() {
 	// Receive the NotEnoughSigners error result
 	assert_eq!(
 		result_receiver.try_recv().expect("Failed to receive ceremony result"),
-		Err((BTreeSet::default(), SigningFailureReason::NotEnoughSigners,))
+		Err((BTreeSet::default(), SigningFailureReason::NotEnoughSigners, Default::default()))
 	);
 }
 
@@ -230,7 +237,7 @@ async fn should_ignore_rts_with_unknown_signer_id() {
 	// Receive the InvalidParticipants error result
 	assert_eq!(
 		result_receiver.try_recv().expect("Failed to receive ceremony result"),
-		Err((BTreeSet::default(), SigningFailureReason::InvalidParticipants,))
+		Err((BTreeSet::default(), SigningFailureReason::InvalidParticipants, Default::default()))
 	);
 }
 
@@ -248,6 +255,7 @@ async fn should_not_create_unauthorized_ceremony_with_invalid_ceremony_id() {
 	// Create a new ceremony manager and set the latest_ceremony_id
 	let mut ceremony_manager = CeremonyManager::<EthSigning>::new(
 		ACCOUNT_IDS[0].clone(),
+		new_broadcast_signer(&ACCOUNT_IDS[0]),
 		tokio::sync::mpsc::unbounded_channel().0,
 		latest_ceremony_id,
 	);
@@ -316,6 +324,7 @@ async fn should_send_outcome_of_authorised_ceremony() {
 				BroadcastFailureReason::InsufficientVerificationMessages,
 				SigningStageName::VerifyCommitmentsBroadcast2
 			),
+			Default::default(),
 		))
 	);
 }
@@ -336,6 +345,7 @@ async fn should_cleanup_unauthorised_ceremony_if_not_participating() {
 
 			let mut ceremony_manager = CeremonyManager::<EthSigning>::new(
 				our_account_id.clone(),
+				new_broadcast_signer(&our_account_id),
 				outgoing_p2p_sender,
 				INITIAL_LATEST_CEREMONY_ID,
 			);
@@ -56,6 +56,8 @@ where
 	outcome_sender: UnboundedSender<(CeremonyId, CeremonyOutcome<Ceremony>)>,
 	_phantom: std::marker::PhantomData<Chain>,
 	metrics: CeremonyMetrics,
+	/// Span covering the current stage, nested within the ceremony's span
+	stage_span: tracing::Span,
 }
 
 impl<Ceremony, Chain> CeremonyRunner<Ceremony, Chain>
@@ -111,6 +113,10 @@ where
 			}
 		};
 		if let Some(start_instant) = ceremony_start {
+			runner
+				.metrics
+				.outcome
+				.inc(&[if outcome.is_ok() { "success" } else { "failure" }]);
 			let duration = start_instant.elapsed().as_millis();
 			runner.metrics.ceremony_duration.set(duration);
 			span.in_scope(|| tracing::info!("Ceremony took {}ms to complete", duration));
@@ -134,6 +140,7 @@ where
 			outcome_sender,
 			_phantom: Default::default(),
 			metrics: CeremonyMetrics::new(ceremony_id, Chain::NAME, Ceremony::CEREMONY_TYPE),
+			stage_span: tracing::Span::none(),
 		}
 	}
 
@@ -143,7 +150,10 @@ where
 		&mut self,
 		mut initial_stage: DynStage<Ceremony>,
 	) -> OptionalCeremonyReturn<Ceremony> {
-		let single_party_result = initial_stage.init(&mut self.metrics);
+		self.stage_span =
+			tracing::info_span!("CeremonyStage", stage = %initial_stage.get_stage_name());
+		let single_party_result =
+			self.stage_span.in_scope(|| initial_stage.init(&mut self.metrics));
 
 		// This function is only ever called from a oneshot channel,
 		// so it should never get called twice.
@@ -175,12 +185,15 @@ where
 			let stage_name = stage.get_stage_name().to_string();
 			let validator_mapping = stage.ceremony_common().validator_mapping.clone();
 
-			match stage.finalize(&mut self.metrics).await {
+			match stage.finalize(&mut self.metrics).instrument(self.stage_span.clone()).await {
 				StageResult::NextStage(mut next_stage) => {
 					debug!("Ceremony transitions to {}", next_stage.get_stage_name());
 					self.metrics.stage_completing.inc(&[&stage_name]);
 
-					let single_party_result = next_stage.init(&mut self.metrics);
+					self.stage_span =
+						tracing::info_span!("CeremonyStage", stage = %next_stage.get_stage_name());
+					let single_party_result =
+						self.stage_span.in_scope(|| next_stage.init(&mut self.metrics));
 
 					self.stage = Some(next_stage);
 
@@ -201,9 +214,9 @@ where
 						self.process_delayed().await
 					}
 				},
-				StageResult::Error(bad_validators, reason) => {
+				StageResult::Error(bad_validators, reason, conflicts) => {
 					self.metrics.stage_failing.inc(&[&stage_name, &format!("{:?}", reason)]);
-					Some(Err((validator_mapping.get_ids(bad_validators), reason)))
+					Some(Err((validator_mapping.get_ids(bad_validators), reason, conflicts)))
 				},
 				StageResult::Done(result) => {
 					debug!("Ceremony reached the final stage!");
@@ -277,8 +290,9 @@ where
 					return None
 				}
 
-				if let ProcessMessageResult::Ready =
-					stage.process_message(sender_idx, data, &mut self.metrics)
+				if let ProcessMessageResult::Ready = self
+					.stage_span
+					.in_scope(|| stage.process_message(sender_idx, data, &mut self.metrics))
 				{
 					return self.finalize_current_stage().await
 				}
@@ -354,6 +368,9 @@ where
 			self.metrics
 				.missing_messages
 				.set(&[&stage_name], missing_messages_from_accounts.len());
+			for account_id in &missing_messages_from_accounts {
+				self.metrics.peer_timeout.inc(&[&stage_name, &account_id.to_string()]);
+			}
 			self.finalize_current_stage().await
 		} else {
 			panic!("Unauthorised ceremonies cannot timeout");
@@ -3,7 +3,9 @@ use crate::{
 		ceremony_manager::{prepare_signing_request, KeygenCeremony, SigningCeremony},
 		common::SigningStageName,
 		gen_keygen_data_verify_hash_comm2, get_key_data_for_test,
-		helpers::{ACCOUNT_IDS, CEREMONY_TIMEOUT_DURATION, DEFAULT_SIGNING_SEED},
+		helpers::{
+			new_broadcast_signer, ACCOUNT_IDS, CEREMONY_TIMEOUT_DURATION, DEFAULT_SIGNING_SEED,
+		},
 		signing::{
 			gen_signing_data_stage1, gen_signing_data_stage2, gen_signing_data_stage4, SigningData,
 		},
@@ -128,6 +130,8 @@ async fn should_delay_stage_1_message_while_unauthorised() {
 			EvmCryptoScheme::signing_payload_for_test(),
 		)],
 		&outgoing_p2p_sender,
+		&new_broadcast_signer(&our_account_id),
+		&Default::default(),
 		Rng::from_seed(DEFAULT_SIGNING_SEED),
 	)
 	.unwrap()
@@ -178,7 +182,8 @@ async fn should_process_delayed_messages_after_finishing_a_stage() {
 			SigningFailureReason::BroadcastFailure(
 				_,
 				SigningStageName::VerifyCommitmentsBroadcast2
-			)
+			),
+			_
 		)))
 	));
 }
@@ -223,6 +228,8 @@ async fn gen_stage_1_signing_state(
 			EvmCryptoScheme::signing_payload_for_test(),
 		)],
 		&outgoing_p2p_sender,
+		&new_broadcast_signer(&our_account_id),
+		&Default::default(),
 		Rng::from_seed(DEFAULT_SIGNING_SEED),
 	)
 	.unwrap()
@@ -377,6 +384,8 @@ async fn should_timeout_authorised_ceremony() {
 				EvmCryptoScheme::signing_payload_for_test(),
 			)],
 			&outgoing_p2p_sender,
+			&new_broadcast_signer(&ACCOUNT_IDS[0]),
+			&Default::default(),
 			Rng::from_seed(DEFAULT_SIGNING_SEED),
 		)
 		.unwrap(),
@@ -90,7 +90,7 @@ fn serialize_for_version<C: CeremonyTrait>(
 ) -> Vec<u8> {
 	let message = MultisigMessage { ceremony_id, data: data.into() };
 	match version {
-		1 => bincode::serialize(&message).unwrap(),
+		2 => bincode::serialize(&message).unwrap(),
 		_ => panic!("Unsupported protocol version"),
 	}
 }
@@ -216,9 +216,13 @@ where
 		// we insert None for any missing data
 		let stage_name = self.get_stage_name().to_string();
 		if let Some(start_instant) = self.stage_started {
+			let receiving_duration = start_instant.elapsed();
 			metrics
 				.stage_duration
-				.set(&[&stage_name, "receiving"], start_instant.elapsed().as_millis());
+				.set(&[&stage_name, "receiving"], receiving_duration.as_millis());
+			metrics
+				.stage_duration_seconds
+				.observe(&[&stage_name, "receiving"], receiving_duration.as_secs_f64());
 		}
 
 		let process_msg_instant = Instant::now();
@@ -234,9 +238,13 @@ where
 			.collect();
 
 		let result = self.processor.process(messages).await;
+		let processing_duration = process_msg_instant.elapsed();
 		metrics
 			.stage_duration
-			.set(&[&stage_name, "processing"], process_msg_instant.elapsed().as_millis());
+			.set(&[&stage_name, "processing"], processing_duration.as_millis());
+		metrics
+			.stage_duration_seconds
+			.observe(&[&stage_name, "processing"], processing_duration.as_secs_f64());
 		result
 	}
 
@@ -284,7 +292,7 @@ mod tests {
 			CURRENT_PROTOCOL_VERSION,
 		);
 
-		// Compare the serialized data with previously generated data using protocol version 1
-		assert_eq!(hex::encode(serialized_data), "010000000000000000000000010000004200000000000000307839626634396136613037353566393533383131666365313235663236383364353034323963336262343965303734313437653030383961353265616531353566");
+		// Compare the serialized data with previously generated data using protocol version 2
+		assert_eq!(hex::encode(serialized_data), "01000000000000000000000001000000420000000000000030783962663439613661303735356639353338313166636531323566323638336435303432396333626234396530373431343765303038396135326561653135356640000000000000001f9b3c04e0c7423da52e5b008952ea0f4868419f7f8ae6947c471114458f29dc6e8c3efac134e02cd1f3d98b9a53435c9fce18c75b415cd0b36245c6fe84bf08");
 	}
 }
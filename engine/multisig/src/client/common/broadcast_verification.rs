@@ -3,13 +3,18 @@ use std::{
 	marker::PhantomData,
 };
 
-use cf_primitives::AuthorityCount;
+use cf_primitives::{
+	broadcast_signing_payload, AuthorityCount, BroadcastSignature, CeremonyId, ForeignChain,
+	SignedValueHash,
+};
 use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use sp_core::{ed25519, Pair};
 use tracing::warn;
 
 use crate::client::utils::{find_frequent_element, threshold_for_broadcast_verification};
 
-use super::BroadcastFailureReason;
+use super::{BroadcastConflicts, BroadcastFailureReason};
 
 /// A wrapper around a multisig message that can be
 /// used as part of larger Serialize payloads, but prevents
@@ -39,6 +44,61 @@ impl<T: serde::de::DeserializeOwned> DelayDeserialization<T> {
 	}
 }
 
+/// Signs the values that we broadcast in the stages whose broadcasts are verified, using our peer
+/// key (registered on the state chain), so that conflicting values received from a broadcaster
+/// can be submitted as evidence and checked on chain.
+pub struct BroadcastSigner {
+	key: ed25519::Pair,
+	chain: ForeignChain,
+}
+
+impl BroadcastSigner {
+	/// The signatures are bound to the chain, since ceremony ids are only unique per chain.
+	pub fn new(key: ed25519::Pair, chain: ForeignChain) -> Self {
+		BroadcastSigner { key, chain }
+	}
+
+	/// Signs the hash of a value that is broadcast in the stage before `verification_stage`
+	pub fn sign<T: Serialize>(
+		&self,
+		ceremony_id: CeremonyId,
+		verification_stage: u8,
+		value: T,
+	) -> SignedBroadcast<T> {
+		let signature = self.key.sign(&broadcast_signing_payload(
+			self.chain,
+			ceremony_id,
+			verification_stage,
+			&value_hash(&value),
+		));
+		SignedBroadcast { value, signature: BroadcastSignature(signature.0) }
+	}
+}
+
+/// A broadcast value along with the broadcaster's signature over its hash
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord)]
+pub struct SignedBroadcast<T> {
+	pub value: T,
+	pub signature: BroadcastSignature,
+}
+
+impl<T: Serialize> SignedBroadcast<T> {
+	/// The hash of the value along with the broadcaster's signature, as submitted in evidence
+	pub fn signed_hash(&self) -> SignedValueHash {
+		SignedValueHash { hash: value_hash(&self.value), signature: self.signature }
+	}
+}
+
+impl<T: std::fmt::Display> std::fmt::Display for SignedBroadcast<T> {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		self.value.fmt(f)
+	}
+}
+
+fn value_hash<T: Serialize>(value: &T) -> [u8; 32] {
+	Sha256::digest(&bincode::serialize(value).expect("serialization can't fail")).into()
+}
+
 /// Data received by a single party for a given
 /// stage from all parties (includes our own for
 /// simplicity). Used for broadcast verification.
@@ -48,7 +108,7 @@ pub struct BroadcastVerificationMessage<T: Clone> {
 	pub data: BTreeMap<AuthorityCount, Option<T>>,
 }
 
-impl<T: Clone> BroadcastVerificationMessage<DelayDeserialization<T>> {
+impl<T: Clone> BroadcastVerificationMessage<SignedBroadcast<DelayDeserialization<T>>> {
 	/// Checks that there is the correct number of payloads and all payloads are smaller than the
 	/// given max size (without deserializing them)
 	pub fn is_data_size_valid(
@@ -60,7 +120,7 @@ impl<T: Clone> BroadcastVerificationMessage<DelayDeserialization<T>> {
 			self.data
 				.values()
 				.filter_map(|x| x.as_ref())
-				.all(|d| d.payload.len() <= max_payload_size_bytes)
+				.all(|d| d.value.payload.len() <= max_payload_size_bytes)
 	}
 }
 
@@ -81,11 +141,19 @@ where
 // If we don't, this means that either (a) the broadcaster did an inconsistent broadcast,
 // (b) that the broadcaster failed to deliver the message to large enough number of parties,
 // or (c) that ~1/2 of parties colluded to slash the broadcasting party.
+// Parties only need to agree on the values: the signatures are only kept as evidence in case
+// they don't, which is returned along with the failure (and is empty for any other failure).
 fn verify_broadcasts<T>(
-	verification_messages: BTreeMap<AuthorityCount, Option<BroadcastVerificationMessage<T>>>,
-) -> Result<BTreeMap<AuthorityCount, T>, (BTreeSet<AuthorityCount>, BroadcastFailureReason)>
+	verification_messages: BTreeMap<
+		AuthorityCount,
+		Option<BroadcastVerificationMessage<SignedBroadcast<T>>>,
+	>,
+) -> Result<
+	BTreeMap<AuthorityCount, T>,
+	(BTreeSet<AuthorityCount>, BroadcastFailureReason, BroadcastConflicts),
+>
 where
-	T: Clone + std::fmt::Debug + Ord,
+	T: Clone + std::fmt::Debug + Ord + Serialize,
 {
 	let num_parties = verification_messages.len();
 	let threshold = threshold_for_broadcast_verification(num_parties);
@@ -115,7 +183,11 @@ where
 		// TODO: consider reporting the parties that didn't send broadcast verification messages
 		// (one thing to consider is whether we are going to be in trouble if we report more parties
 		// than other nodes?)
-		return Err((BTreeSet::new(), BroadcastFailureReason::InsufficientVerificationMessages))
+		return Err((
+			BTreeSet::new(),
+			BroadcastFailureReason::InsufficientVerificationMessages,
+			BroadcastConflicts::default(),
+		))
 	}
 
 	// This should not panic due to the check above (`check_verification_message_indexes`)
@@ -142,7 +214,9 @@ where
 	// A party is reported if we can't agree on the value they broadcast
 	// or if the agreed upon value is `None` (i.e. they didn't broadcast)
 	for idx in &participating_idxs {
-		let message_iter = verification_messages.values().map(|m| m.data[idx].clone());
+		let message_iter = verification_messages
+			.values()
+			.map(|m| m.data[idx].as_ref().map(|signed| signed.value.clone()));
 		if let Some(Some(data)) = find_frequent_element(message_iter, threshold) {
 			agreed_on_values.insert(*idx, data);
 		} else {
@@ -152,25 +226,53 @@ where
 
 	if reported_parties.is_empty() {
 		Ok(agreed_on_values)
+	} else if insufficient_messages {
+		Err((reported_parties, BroadcastFailureReason::InsufficientMessages, Default::default()))
 	} else {
-		Err((
-			reported_parties,
-			if insufficient_messages {
-				BroadcastFailureReason::InsufficientMessages
-			} else {
-				// If the failure was not due to "InsufficientMessages",
-				// then it must be caused by (or at least partially caused by) inconsistency.
-				BroadcastFailureReason::Inconsistency
-			},
-		))
+		// If the failure was not due to "InsufficientMessages",
+		// then it must be caused by (or at least partially caused by) inconsistency.
+		let conflicts = broadcast_conflicts(&verification_messages, &reported_parties);
+		Err((reported_parties, BroadcastFailureReason::Inconsistency, conflicts))
 	}
 }
 
+/// Collects what each party reported receiving from the parties that we couldn't agree on
+fn broadcast_conflicts<T: Clone + Serialize>(
+	verification_messages: &BTreeMap<
+		AuthorityCount,
+		BroadcastVerificationMessage<SignedBroadcast<T>>,
+	>,
+	reported_parties: &BTreeSet<AuthorityCount>,
+) -> BroadcastConflicts {
+	BroadcastConflicts(
+		reported_parties
+			.iter()
+			.map(|idx| {
+				(
+					*idx,
+					verification_messages
+						.iter()
+						.map(|(sender, message)| {
+							(*sender, message.data[idx].as_ref().map(SignedBroadcast::signed_hash))
+						})
+						.collect(),
+				)
+			})
+			.collect(),
+	)
+}
+
 pub async fn verify_broadcasts_non_blocking<T>(
-	verification_messages: BTreeMap<AuthorityCount, Option<BroadcastVerificationMessage<T>>>,
-) -> Result<BTreeMap<AuthorityCount, T>, (BTreeSet<AuthorityCount>, BroadcastFailureReason)>
+	verification_messages: BTreeMap<
+		AuthorityCount,
+		Option<BroadcastVerificationMessage<SignedBroadcast<T>>>,
+	>,
+) -> Result<
+	BTreeMap<AuthorityCount, T>,
+	(BTreeSet<AuthorityCount>, BroadcastFailureReason, BroadcastConflicts),
+>
 where
-	T: Clone + std::fmt::Debug + Ord + Send + 'static,
+	T: Clone + std::fmt::Debug + Ord + Serialize + Send + 'static,
 {
 	utilities::task_scope::without_blocking(move || verify_broadcasts(verification_messages)).await
 }
@@ -180,10 +282,15 @@ mod tests {
 	use super::*;
 	use std::collections::BTreeSet;
 
+	fn sign(value: i32) -> SignedBroadcast<i32> {
+		BroadcastSigner::new(ed25519::Pair::from_seed(&[1; 32]), ForeignChain::Ethereum)
+			.sign(1, 2, value)
+	}
+
 	/// Transforms the (more concise) test data into the expected "shape";
 	fn to_broadcast_verification_messages(
 		test_data: Vec<(AuthorityCount, Option<Vec<Option<i32>>>)>,
-	) -> BTreeMap<AuthorityCount, Option<BroadcastVerificationMessage<i32>>> {
+	) -> BTreeMap<AuthorityCount, Option<BroadcastVerificationMessage<SignedBroadcast<i32>>>> {
 		test_data
 			.into_iter()
 			.map(|(idx, opt_values)| {
@@ -191,7 +298,7 @@ mod tests {
 					let data: BTreeMap<_, _> = values
 						.iter()
 						.enumerate()
-						.map(|(i, d)| (i as AuthorityCount + 1, *d))
+						.map(|(i, d)| (i as AuthorityCount + 1, d.map(sign)))
 						.collect();
 
 					BroadcastVerificationMessage { data }
@@ -205,10 +312,13 @@ mod tests {
 	/// check that the result matches `expected` (transforming the reported idxs Vec into a Set
 	/// to make it *NOT* sensitive to the order of elements)
 	fn check_broadcast_verification(
-		verification_messages: BTreeMap<AuthorityCount, Option<BroadcastVerificationMessage<i32>>>,
+		verification_messages: BTreeMap<
+			AuthorityCount,
+			Option<BroadcastVerificationMessage<SignedBroadcast<i32>>>,
+		>,
 		expected: Result<
 			Vec<(AuthorityCount, i32)>,
-			(BTreeSet<AuthorityCount>, BroadcastFailureReason),
+			(BTreeSet<AuthorityCount>, BroadcastFailureReason, BroadcastConflicts),
 		>,
 	) {
 		let expected = expected.map(|values| values.into_iter().collect::<BTreeMap<_, _>>());
@@ -245,13 +355,59 @@ mod tests {
 			(4, Some(vec![Some(1), Some(1), Some(1), Some(2)])),
 		]);
 
-		// Expect parties 2 and 4 to be reported
+		let hash = |value: i32| sign(value).signed_hash();
+
+		// Expect parties 2 and 4 to be reported, along with what each party received from them
 		check_broadcast_verification(
 			all_messages,
-			Err(([2, 4].iter().copied().collect(), BroadcastFailureReason::Inconsistency)),
+			Err((
+				[2, 4].iter().copied().collect(),
+				BroadcastFailureReason::Inconsistency,
+				BroadcastConflicts(BTreeMap::from([
+					(
+						2,
+						BTreeMap::from([
+							(1, None),
+							(2, Some(hash(2))),
+							(3, Some(hash(2))),
+							(4, Some(hash(1))),
+						]),
+					),
+					(
+						4,
+						BTreeMap::from([
+							(1, Some(hash(2))),
+							(2, Some(hash(1))),
+							(3, Some(hash(1))),
+							(4, Some(hash(2))),
+						]),
+					),
+				])),
+			)),
 		);
 	}
 
+	#[test]
+	fn signatures_are_not_part_of_the_agreement() {
+		let mut all_messages = to_broadcast_verification_messages(vec![
+			(1_u32, Some(vec![Some(1), Some(1), Some(1), Some(1)])),
+			(2, Some(vec![Some(1), Some(1), Some(1), Some(1)])),
+			(3, Some(vec![Some(1), Some(1), Some(1), Some(1)])),
+			(4, Some(vec![Some(1), Some(1), Some(1), Some(1)])),
+		]);
+
+		// Party 1 signed the value that it sent to party 2 differently
+		all_messages
+			.get_mut(&2)
+			.unwrap()
+			.as_mut()
+			.unwrap()
+			.data
+			.insert(1, Some(SignedBroadcast { value: 1, signature: BroadcastSignature([0; 64]) }));
+
+		check_broadcast_verification(all_messages, Ok(vec![(1, 1), (2, 1), (3, 1), (4, 1)]));
+	}
+
 	#[test]
 	fn fail_from_missing_messages() {
 		// We can't achieve consensus on values from 2
@@ -267,7 +423,11 @@ mod tests {
 		// Expect party 2 to be reported
 		check_broadcast_verification(
 			all_messages,
-			Err(([2].iter().copied().collect(), BroadcastFailureReason::InsufficientMessages)),
+			Err((
+				[2].iter().copied().collect(),
+				BroadcastFailureReason::InsufficientMessages,
+				Default::default(),
+			)),
 		);
 	}
 
@@ -285,7 +445,11 @@ mod tests {
 		// Expect no parties to be reported
 		check_broadcast_verification(
 			all_messages,
-			Err((BTreeSet::new(), BroadcastFailureReason::InsufficientVerificationMessages)),
+			Err((
+				BTreeSet::new(),
+				BroadcastFailureReason::InsufficientVerificationMessages,
+				Default::default(),
+			)),
 		);
 	}
 
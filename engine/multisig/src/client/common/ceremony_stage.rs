@@ -1,5 +1,6 @@
 use std::{collections::BTreeSet, sync::Arc};
 
+use super::{BroadcastConflicts, BroadcastSigner, SignedBroadcast};
 use crate::{
 	client::{ceremony_manager::CeremonyTrait, utils::PartyIdxMapping},
 	crypto::Rng,
@@ -8,6 +9,7 @@ use crate::{
 };
 use async_trait::async_trait;
 use cf_primitives::{AuthorityCount, CeremonyId};
+use serde::Serialize;
 use tokio::sync::mpsc::UnboundedSender;
 use utilities::metrics::CeremonyMetrics;
 
@@ -15,8 +17,9 @@ use utilities::metrics::CeremonyMetrics;
 pub enum StageResult<C: CeremonyTrait> {
 	/// Ceremony proceeds to the next stage
 	NextStage(Box<dyn CeremonyStage<C> + Send + Sync>),
-	/// Ceremony aborted (contains parties to report)
-	Error(BTreeSet<AuthorityCount>, C::FailureReason),
+	/// Ceremony aborted (contains parties to report, and the conflicting broadcasts received from
+	/// them if the failure was due to an inconsistent broadcast)
+	Error(BTreeSet<AuthorityCount>, C::FailureReason, BroadcastConflicts),
 	/// Ceremony finished and successful
 	Done(C::Output),
 }
@@ -71,12 +74,22 @@ pub struct CeremonyCommon {
 	pub validator_mapping: Arc<PartyIdxMapping>,
 	pub rng: Rng,
 	pub number_of_signing_payloads: Option<usize>,
+	pub broadcast_signer: Arc<BroadcastSigner>,
 }
 
 impl CeremonyCommon {
 	pub fn is_idx_valid(&self, idx: AuthorityCount) -> bool {
 		self.all_idxs.contains(&idx)
 	}
+
+	/// Signs a value that we broadcast for the stage that verifies the broadcast
+	pub fn sign_broadcast<T: Serialize>(
+		&self,
+		verification_stage: u8,
+		value: T,
+	) -> SignedBroadcast<T> {
+		self.broadcast_signer.sign(self.ceremony_id, verification_stage, value)
+	}
 }
 
 pub trait PreProcessStageDataCheck<CeremonyStageName> {
@@ -1,7 +1,10 @@
+use cf_primitives::{
+	AuthorityCount, BlameReason, CeremonyFailureEvidence, ConflictingBroadcast, SignedValueHash,
+};
 use state_chain_runtime::AccountId;
 use tracing::warn;
 
-use std::collections::BTreeSet;
+use std::collections::{BTreeMap, BTreeSet};
 
 use utilities::format_iterator;
 
@@ -58,6 +61,10 @@ pub enum KeygenFailureReason {
 	InvalidBlameResponse,
 	#[error("Invalid Complaint")]
 	InvalidComplaint,
+	#[error("Unknown Key")]
+	UnknownKey,
+	#[error("Refreshed shares are for a different key")]
+	RefreshedKeyMismatch,
 }
 
 #[derive(Error, Debug, PartialEq, Eq, PartialOrd, Ord)]
@@ -73,6 +80,56 @@ pub enum BroadcastFailureReason {
 	Inconsistency,
 }
 
+/// The signed hashes of the values that each party reported receiving from the broadcasters that
+/// couldn't be agreed on, by broadcaster and then by reporting party (`None` if the party
+/// didn't receive a value). This is returned alongside the failure reason as the evidence for
+/// an [BroadcastFailureReason::Inconsistency], and is empty for any other failure.
+#[derive(Clone, Debug, Default, PartialEq, Eq, PartialOrd, Ord)]
+pub struct BroadcastConflicts(
+	pub BTreeMap<AuthorityCount, BTreeMap<AuthorityCount, Option<SignedValueHash>>>,
+);
+
+impl BroadcastConflicts {
+	/// Groups the reporting parties by the value that they received
+	fn to_evidence(&self) -> Vec<ConflictingBroadcast> {
+		self.0
+			.iter()
+			.map(|(broadcaster, received_by_party)| {
+				let mut parties_by_value = BTreeMap::<_, Vec<_>>::new();
+				for (party, value) in received_by_party {
+					parties_by_value.entry(*value).or_default().push(*party);
+				}
+				ConflictingBroadcast {
+					broadcaster: *broadcaster,
+					received: parties_by_value.into_iter().collect(),
+				}
+			})
+			.collect()
+	}
+}
+
+impl BroadcastFailureReason {
+	/// The evidence against the parties reported for failing to broadcast in the stage
+	fn evidence(
+		&self,
+		stage: u8,
+		conflicts: &BroadcastConflicts,
+	) -> Option<CeremonyFailureEvidence> {
+		match self {
+			BroadcastFailureReason::InsufficientMessages => Some(CeremonyFailureEvidence {
+				reason: BlameReason::MissingBroadcast { stage },
+				conflicting_broadcasts: vec![],
+			}),
+			// No parties are reported in this case
+			BroadcastFailureReason::InsufficientVerificationMessages => None,
+			BroadcastFailureReason::Inconsistency => Some(CeremonyFailureEvidence {
+				reason: BlameReason::InconsistentBroadcast { stage },
+				conflicting_broadcasts: conflicts.to_evidence(),
+			}),
+		}
+	}
+}
+
 const SIGNING_CEREMONY_FAILED_PREFIX: &str = "Signing ceremony failed";
 const KEYGEN_CEREMONY_FAILED_PREFIX: &str = "Keygen ceremony failed";
 const REQUEST_TO_SIGN_IGNORED_PREFIX: &str = "Signing request ignored";
@@ -80,6 +137,15 @@ const KEYGEN_REQUEST_IGNORED_PREFIX: &str = "Keygen request ignored";
 
 pub trait CeremonyFailureReason {
 	fn log(&self, reported_parties: &BTreeSet<AccountId>);
+
+	/// Evidence supporting the report of the parties blamed for the failure, to be submitted to
+	/// the state chain, including the `conflicts` that were returned with the failure. `None` if
+	/// the failure doesn't blame anyone.
+	fn evidence(&self, conflicts: &BroadcastConflicts) -> Option<CeremonyFailureEvidence>;
+}
+
+fn evidence_without_conflicts(reason: BlameReason) -> Option<CeremonyFailureEvidence> {
+	Some(CeremonyFailureEvidence { reason, conflicting_broadcasts: vec![] })
 }
 
 impl CeremonyFailureReason for SigningFailureReason {
@@ -110,6 +176,23 @@ impl CeremonyFailureReason for SigningFailureReason {
 			},
 		}
 	}
+
+	fn evidence(&self, conflicts: &BroadcastConflicts) -> Option<CeremonyFailureEvidence> {
+		match self {
+			SigningFailureReason::BroadcastFailure(reason, stage) =>
+				reason.evidence(stage.stage_number(), conflicts),
+			SigningFailureReason::InvalidSigShare =>
+				evidence_without_conflicts(BlameReason::InvalidSignatureShare),
+			SigningFailureReason::DeserializationError |
+			SigningFailureReason::InvalidNumberOfPayloads =>
+				evidence_without_conflicts(BlameReason::InvalidMessage),
+			SigningFailureReason::NotParticipatingInUnauthorisedCeremony |
+			SigningFailureReason::InvalidParticipants |
+			SigningFailureReason::NotEnoughSigners |
+			SigningFailureReason::UnknownKey |
+			SigningFailureReason::DeveloperError(_) => None,
+		}
+	}
 }
 
 impl CeremonyFailureReason for KeygenFailureReason {
@@ -120,7 +203,8 @@ impl CeremonyFailureReason for KeygenFailureReason {
 			KeygenFailureReason::InvalidBlameResponse |
 			KeygenFailureReason::InvalidCommitment |
 			KeygenFailureReason::DeserializationError |
-			KeygenFailureReason::InvalidComplaint => {
+			KeygenFailureReason::InvalidComplaint |
+			KeygenFailureReason::RefreshedKeyMismatch => {
 				warn!(
 					tag = KEYGEN_CEREMONY_FAILED,
 					reported_parties = reported_parties,
@@ -130,9 +214,29 @@ impl CeremonyFailureReason for KeygenFailureReason {
 			KeygenFailureReason::NotParticipatingInUnauthorisedCeremony => {
 				warn!(tag = UNAUTHORIZED_KEYGEN_ABORTED, "{KEYGEN_CEREMONY_FAILED_PREFIX}: {self}",);
 			},
-			KeygenFailureReason::InvalidParticipants => {
+			KeygenFailureReason::InvalidParticipants | KeygenFailureReason::UnknownKey => {
 				warn!(tag = KEYGEN_REQUEST_IGNORED, "{KEYGEN_REQUEST_IGNORED_PREFIX}: {self}",);
 			},
 		}
 	}
+
+	fn evidence(&self, conflicts: &BroadcastConflicts) -> Option<CeremonyFailureEvidence> {
+		match self {
+			KeygenFailureReason::BroadcastFailure(reason, stage) =>
+				reason.evidence(stage.stage_number(), conflicts),
+			KeygenFailureReason::InvalidCommitment =>
+				evidence_without_conflicts(BlameReason::InvalidCommitment),
+			KeygenFailureReason::DeserializationError =>
+				evidence_without_conflicts(BlameReason::InvalidMessage),
+			KeygenFailureReason::InvalidBlameResponse =>
+				evidence_without_conflicts(BlameReason::InvalidBlameResponse),
+			KeygenFailureReason::InvalidComplaint =>
+				evidence_without_conflicts(BlameReason::InvalidComplaint),
+			KeygenFailureReason::RefreshedKeyMismatch =>
+				evidence_without_conflicts(BlameReason::RefreshedKeyMismatch),
+			KeygenFailureReason::NotParticipatingInUnauthorisedCeremony |
+			KeygenFailureReason::InvalidParticipants |
+			KeygenFailureReason::UnknownKey => None,
+		}
+	}
 }
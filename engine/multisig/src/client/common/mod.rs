@@ -9,11 +9,14 @@ pub use ceremony_stage::{
 	CeremonyCommon, CeremonyStage, PreProcessStageDataCheck, ProcessMessageResult, StageResult,
 };
 
-pub use broadcast_verification::{BroadcastVerificationMessage, DelayDeserialization};
+pub use broadcast_verification::{
+	BroadcastSigner, BroadcastVerificationMessage, DelayDeserialization, SignedBroadcast,
+};
 
 use cf_primitives::{AccountId, AuthorityCount};
 pub use failure_reason::{
-	BroadcastFailureReason, CeremonyFailureReason, KeygenFailureReason, SigningFailureReason,
+	BroadcastConflicts, BroadcastFailureReason, CeremonyFailureReason, KeygenFailureReason,
+	SigningFailureReason,
 };
 use strum_macros::EnumIter;
 
@@ -252,6 +255,24 @@ pub enum KeygenStageName {
 	VerifyBlameResponsesBroadcastStage9,
 }
 
+impl KeygenStageName {
+	/// The number shown in the stage's name
+	pub fn stage_number(&self) -> u8 {
+		match self {
+			KeygenStageName::PubkeyShares0 => 0,
+			KeygenStageName::HashCommitments1 => 1,
+			KeygenStageName::VerifyHashCommitmentsBroadcast2 => 2,
+			KeygenStageName::CoefficientCommitments3 => 3,
+			KeygenStageName::VerifyCommitmentsBroadcast4 => 4,
+			KeygenStageName::SecretSharesStage5 => 5,
+			KeygenStageName::ComplaintsStage6 => 6,
+			KeygenStageName::VerifyComplaintsBroadcastStage7 => 7,
+			KeygenStageName::BlameResponsesStage8 => 8,
+			KeygenStageName::VerifyBlameResponsesBroadcastStage9 => 9,
+		}
+	}
+}
+
 #[derive(Error, Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Copy)]
 pub enum SigningStageName {
 	#[error("Commitments [1]")]
@@ -264,6 +285,18 @@ pub enum SigningStageName {
 	VerifyLocalSigsBroadcastStage4,
 }
 
+impl SigningStageName {
+	/// The number shown in the stage's name
+	pub fn stage_number(&self) -> u8 {
+		match self {
+			SigningStageName::AwaitCommitments1 => 1,
+			SigningStageName::VerifyCommitmentsBroadcast2 => 2,
+			SigningStageName::LocalSigStage3 => 3,
+			SigningStageName::VerifyLocalSigsBroadcastStage4 => 4,
+		}
+	}
+}
+
 /// Try to deserialize all messages. If at least one fails,
 /// return the parties for which deserialization failed.
 pub fn try_deserialize<T: serde::de::DeserializeOwned>(
@@ -1,13 +1,16 @@
 use anyhow::Result;
 use async_trait::async_trait;
-use cf_primitives::{AuthorityCount, CeremonyId};
+use cf_primitives::{AuthorityCount, CeremonyId, ForeignChain};
 use futures::{stream, StreamExt};
 use itertools::{Either, Itertools};
 use lazy_static::lazy_static;
 use rand::{RngCore, SeedableRng};
+use serde::Serialize;
+use sp_core::{ed25519, Pair};
 use std::{
-	collections::{BTreeSet, HashMap},
+	collections::{BTreeMap, BTreeSet, HashMap},
 	fmt::Display,
+	sync::Arc,
 	time::Duration,
 };
 use tokio::sync::mpsc::{UnboundedReceiver, UnboundedSender};
@@ -24,9 +27,14 @@ use crate::{
 			KeygenCeremony, SigningCeremony,
 		},
 		ceremony_runner::CeremonyRunner,
-		common::CeremonyFailureReason,
+		common::{
+			BroadcastConflicts, BroadcastSigner, CeremonyFailureReason, KeygenStageName,
+			SignedBroadcast, SigningStageName,
+		},
 		keygen::{generate_key_data, HashComm1, HashContext},
-		signing, KeygenResultInfo,
+		signing,
+		utils::PartyIdxMapping,
+		KeygenResultInfo,
 	},
 	crypto::{CryptoTag, ECPoint, Rng},
 	eth::{EthSigning, EvmCryptoScheme},
@@ -38,7 +46,7 @@ use crate::{
 	// (we make arbitrary choice to use eth)
 	p2p::{OutgoingMultisigStageMessages, VersionedCeremonyMessage, CURRENT_PROTOCOL_VERSION},
 };
-use keygen::{generate_shares_and_commitment, DKGUnverifiedCommitment};
+use keygen::generate_shares_and_commitment;
 use signing::{LocalSig3, SigningCommitment};
 use state_chain_runtime::{constants::common::MAX_STAGE_DURATION_SECONDS, AccountId};
 
@@ -101,7 +109,9 @@ macro_rules! test_all_crypto_chains {
 	($test_function:ident ($($lt:tt),*)) => {
 		({
 			use $crate::{
-				bitcoin::BtcSigning, ed25519::Ed25519Signing, eth::EthSigning,
+				bitcoin::BtcSigning,
+				ed25519::Ed25519Signing,
+				eth::{ArbSigning, EthSigning},
 				polkadot::PolkadotSigning,
 			};
 
@@ -113,6 +123,7 @@ macro_rules! test_all_crypto_chains {
 			}
 			// Run the test on all Chains
 			test::<EthSigning>();
+			test::<ArbSigning>();
 			test::<PolkadotSigning>();
 			test::<BtcSigning>();
 			test::<Ed25519Signing>();
@@ -153,11 +164,14 @@ macro_rules! test_all_crypto_chains_async {
 	($test_function:ident ($($lt:tt),*)) => {
 		({
 			use crate::{
-				bitcoin::BtcSigning, ed25519::Ed25519Signing, eth::EthSigning,
+				bitcoin::BtcSigning,
+				ed25519::Ed25519Signing,
+				eth::{ArbSigning, EthSigning},
 				polkadot::PolkadotSigning,
 			};
 			// Run the test on all CryptoSchemes
 			$test_function::<EthSigning>($($lt)*).await;
+			$test_function::<ArbSigning>($($lt)*).await;
 			$test_function::<PolkadotSigning>($($lt)*).await;
 			$test_function::<BtcSigning>($($lt)*).await;
 			$test_function::<Ed25519Signing>($($lt)*).await;
@@ -179,11 +193,14 @@ where
 {
 	own_account_id: AccountId,
 	outgoing_p2p_message_sender: UnboundedSender<OutgoingMultisigStageMessages>,
+	broadcast_signer: Arc<BroadcastSigner>,
 	pub ceremony_runner: CeremonyRunner<C, Chain>,
 	outgoing_p2p_message_receiver: UnboundedReceiver<OutgoingMultisigStageMessages>,
 	/// If any of the methods we called on the ceremony runner returned the outcome,
 	/// it will be stored here
 	outcome: Option<CeremonyOutcome<C>>,
+	/// Kept across signing ceremonies, like in the `CeremonyManager`
+	pub preprocessed_nonce_pool: signing::SharedPreprocessedNoncePool<C::Crypto>,
 }
 
 fn new_node<C, Chain>(account_id: AccountId) -> Node<C, Chain>
@@ -198,10 +215,12 @@ where
 
 	Node {
 		outgoing_p2p_message_sender,
+		broadcast_signer: new_broadcast_signer(&account_id),
 		own_account_id: account_id,
 		ceremony_runner,
 		outgoing_p2p_message_receiver,
 		outcome: None,
+		preprocessed_nonce_pool: Default::default(),
 	}
 }
 
@@ -238,7 +257,7 @@ where
 			Ok(_) => {
 				debug!("Node got successful outcome");
 			},
-			Err((reported_parties, failure_reason)) => {
+			Err((reported_parties, failure_reason, _)) => {
 				failure_reason.log(reported_parties);
 			},
 		}
@@ -275,6 +294,8 @@ impl<Chain: ChainSigning> Node<SigningCeremony<Chain::CryptoScheme>, Chain> {
 			signers,
 			payloads.into_iter().map(|p| (p.keygen_result_info, p.payload)).collect(),
 			&self.outgoing_p2p_message_sender,
+			&self.broadcast_signer,
+			&self.preprocessed_nonce_pool,
 			rng,
 		)
 		.expect("invalid request");
@@ -303,6 +324,7 @@ impl<Chain: ChainSigning> Node<KeygenCeremony<Chain::CryptoScheme>, Chain> {
 			&self.own_account_id,
 			participants,
 			&self.outgoing_p2p_message_sender,
+			&self.broadcast_signer,
 			resharing_context,
 			rng,
 		)
@@ -326,6 +348,7 @@ impl<Chain: ChainSigning> Node<KeygenCeremony<Chain::CryptoScheme>, Chain> {
 			&self.own_account_id,
 			participants,
 			&self.outgoing_p2p_message_sender,
+			&self.broadcast_signer,
 			rng,
 		)
 		.expect("invalid request");
@@ -411,6 +434,40 @@ where
 			.unwrap()
 	}
 
+	/// Signs a broadcast value as the given node would for this ceremony
+	pub fn sign_broadcast<T: Serialize>(
+		&self,
+		account_id: &AccountId,
+		verification_stage: u8,
+		value: T,
+	) -> SignedBroadcast<T> {
+		self.nodes[account_id]
+			.broadcast_signer
+			.sign(self.ceremony_id, verification_stage, value)
+	}
+
+	/// The conflicts that all nodes are expected to return after the given node broadcast
+	/// inconsistently, keeping `own_message` for itself and sending `messages` to the others
+	pub fn expected_conflicts<T: Serialize>(
+		&self,
+		bad_account_id: &AccountId,
+		own_message: &SignedBroadcast<T>,
+		messages: &HashMap<AccountId, SignedBroadcast<T>>,
+	) -> BroadcastConflicts {
+		let validator_mapping =
+			PartyIdxMapping::from_participants(self.nodes.keys().cloned().collect());
+		let idx = |account_id: &AccountId| validator_mapping.get_idx(account_id).unwrap();
+
+		BroadcastConflicts(BTreeMap::from([(
+			idx(bad_account_id),
+			messages
+				.iter()
+				.map(|(account_id, message)| (idx(account_id), Some(message.signed_hash())))
+				.chain(std::iter::once((idx(bad_account_id), Some(own_message.signed_hash()))))
+				.collect(),
+		)]))
+	}
+
 	pub async fn distribute_messages<StageData: Into<StrategyData<Self>>>(
 		&mut self,
 		stage_data: StageMessages<StageData>,
@@ -570,7 +627,7 @@ where
 		&mut self,
 	) -> Result<
 		<Self as CeremonyRunnerStrategy>::CheckedOutput,
-		(BTreeSet<AccountId>, StrategyFailureReason<Self>),
+		(BTreeSet<AccountId>, StrategyFailureReason<Self>, BroadcastConflicts),
 	> {
 		// Gather the outcomes from all the nodes
 		let results: HashMap<_, _> = self
@@ -590,13 +647,16 @@ where
 		}
 
 		// Split up the outcomes into success and fails
-		let (ok_results, (all_reported_parties, failure_reasons)): (
-			HashMap<_, _>,
-			(BTreeSet<_>, BTreeSet<_>),
-		) = results.into_iter().partition_map(|(account_id, result)| match result {
-			Ok(output) => Either::Left((account_id, output)),
-			Err((reported_parties, reason)) => Either::Right((reported_parties, reason)),
-		});
+		let (ok_results, failures): (HashMap<_, _>, Vec<_>) =
+			results.into_iter().partition_map(|(account_id, result)| match result {
+				Ok(output) => Either::Left((account_id, output)),
+				Err(failure) => Either::Right(failure),
+			});
+		let (all_reported_parties, failure_reasons, all_conflicts): (
+			BTreeSet<_>,
+			BTreeSet<_>,
+			BTreeSet<_>,
+		) = failures.into_iter().multiunzip();
 
 		if !ok_results.is_empty() && failure_reasons.is_empty() {
 			// All nodes completed successfully
@@ -613,9 +673,15 @@ where
 				1,
 				"The ceremony failure reason was not the same for all nodes: {failure_reasons:?}",
 			);
+			assert_eq!(
+				all_conflicts.len(),
+				1,
+				"The broadcast conflicts were not the same for all nodes: {all_conflicts:?}",
+			);
 			Err((
 				all_reported_parties.into_iter().next().unwrap(),
 				failure_reasons.into_iter().next().unwrap(),
+				all_conflicts.into_iter().next().unwrap(),
 			))
 		} else {
 			panic!("Ceremony results weren't consistently Ok() or Err() for all nodes");
@@ -632,10 +698,12 @@ where
 		&mut self,
 		bad_account_ids: &[AccountId],
 		expected_failure_reason: StrategyFailureReason<Self>,
+		expected_conflicts: BroadcastConflicts,
 	) -> Option<()> {
-		let (reported, reason) = self.collect_and_check_outcomes().unwrap_err();
+		let (reported, reason, conflicts) = self.collect_and_check_outcomes().unwrap_err();
 		assert_eq!(BTreeSet::from_iter(bad_account_ids.iter()), reported.iter().collect());
 		assert_eq!(expected_failure_reason, reason);
+		assert_eq!(expected_conflicts, conflicts);
 		Some(())
 	}
 
@@ -647,7 +715,20 @@ where
 		bad_account_ids: &[AccountId],
 		expected_failure_reason: StrategyFailureReason<Self>,
 	) {
-		self.try_complete_with_error(bad_account_ids, expected_failure_reason)
+		self.try_complete_with_error(bad_account_ids, expected_failure_reason, Default::default())
+			.expect("Failed to get all ceremony outcomes");
+	}
+
+	/// Same as [Self::complete_with_error], for failures due to an inconsistent broadcast, which
+	/// also return the conflicting values that each party received from the reported parties.
+	#[track_caller]
+	pub fn complete_with_conflicts(
+		&mut self,
+		bad_account_ids: &[AccountId],
+		expected_failure_reason: StrategyFailureReason<Self>,
+		expected_conflicts: BroadcastConflicts,
+	) {
+		self.try_complete_with_error(bad_account_ids, expected_failure_reason, expected_conflicts)
 			.expect("Failed to get all ceremony outcomes");
 	}
 
@@ -706,7 +787,7 @@ impl<Chain: ChainSigning> CeremonyRunnerStrategy for KeygenCeremonyRunner<Chain>
 		<Chain::CryptoScheme as CryptoScheme>::PublicKey,
 		HashMap<AccountId, StrategyOutput<Self>>,
 	);
-	type InitialStageData = keygen::HashComm1;
+	type InitialStageData = SignedBroadcast<keygen::HashComm1>;
 
 	fn post_successful_complete_check(
 		&self,
@@ -788,7 +869,7 @@ pub type SigningCeremonyRunner<Chain> = CeremonyTestRunner<
 impl<Chain: ChainSigning> CeremonyRunnerStrategy for SigningCeremonyRunner<Chain> {
 	type CeremonyType = SigningCeremony<Chain::CryptoScheme>;
 	type CheckedOutput = <SigningCeremony<Chain::CryptoScheme> as CeremonyTrait>::Output;
-	type InitialStageData = signing::Comm1<Point<Chain>>;
+	type InitialStageData = SignedBroadcast<signing::Comm1<Point<Chain>>>;
 
 	fn post_successful_complete_check(
 		&self,
@@ -910,7 +991,7 @@ pub async fn standard_signing<Chain: ChainSigning>(
 		signing_ceremony,
 		stage_1_messages,
 		signing::VerifyComm2<Point<Chain>>,
-		signing::LocalSig3<Point<Chain>>,
+		SignedBroadcast<signing::LocalSig3<Point<Chain>>>,
 		signing::VerifyLocalSig4<Point<Chain>>
 	);
 	signing_ceremony.distribute_messages(messages).await;
@@ -937,38 +1018,62 @@ pub async fn run_keygen(
 		keygen_ceremony,
 		stage_1_messages,
 		keygen::VerifyHashComm2,
-		keygen::CoeffComm3<Point>,
+		SignedBroadcast<keygen::CoeffComm3<Point>>,
 		keygen::VerifyCoeffComm4<Point>,
 		keygen::SecretShare5<Point>,
-		keygen::Complaints6,
+		SignedBroadcast<keygen::Complaints6>,
 		keygen::VerifyComplaints7
 	);
 	keygen_ceremony.distribute_messages(messages).await;
 	keygen_ceremony.complete()
 }
 
+/// Creates the broadcast signer of a test node, using a peer key derived from its account id
+pub fn new_broadcast_signer(account_id: &AccountId) -> Arc<BroadcastSigner> {
+	let seed: &[u8; 32] = account_id.as_ref();
+	Arc::new(BroadcastSigner::new(ed25519::Pair::from_seed(seed), ForeignChain::Ethereum))
+}
+
+/// Signs a dummy broadcast value (the engine doesn't check the signatures, so any signer will do)
+pub fn sign_dummy_broadcast<T: Serialize>(verification_stage: u8, value: T) -> SignedBroadcast<T> {
+	new_broadcast_signer(&AccountId::new([0; 32])).sign(
+		DEFAULT_KEYGEN_CEREMONY_ID,
+		verification_stage,
+		value,
+	)
+}
+
 /// Generate an invalid local sig for stage3
-pub fn gen_dummy_local_sig<P: ECPoint>(rng: &mut Rng, number_of_responses: u64) -> LocalSig3<P> {
+pub fn gen_dummy_local_sig<P: ECPoint>(
+	rng: &mut Rng,
+	number_of_responses: u64,
+) -> SignedBroadcast<LocalSig3<P>> {
 	use crate::crypto::ECScalar;
 
-	DelayDeserialization::new(&signing::LocalSig3Inner::<P> {
-		responses: (0..number_of_responses).map(|_| P::Scalar::random(rng)).collect(),
-	})
+	sign_dummy_broadcast(
+		SigningStageName::VerifyLocalSigsBroadcastStage4.stage_number(),
+		DelayDeserialization::new(&signing::LocalSig3Inner::<P> {
+			responses: (0..number_of_responses).map(|_| P::Scalar::random(rng)).collect(),
+		}),
+	)
 }
 
-pub fn get_dummy_hash_comm(rng: &mut Rng) -> keygen::HashComm1 {
+pub fn get_dummy_hash_comm(rng: &mut Rng) -> SignedBroadcast<keygen::HashComm1> {
 	use sp_core::H256;
 
 	let mut buffer: [u8; 32] = [0; 32];
 	rng.fill_bytes(&mut buffer);
 
-	HashComm1(H256::from(buffer))
+	sign_dummy_broadcast(
+		KeygenStageName::VerifyHashCommitmentsBroadcast2.stage_number(),
+		HashComm1(H256::from(buffer)),
+	)
 }
 
 pub fn gen_dummy_keygen_comm3<P: ECPoint>(
 	rng: &mut Rng,
 	share_count: AuthorityCount,
-) -> DKGUnverifiedCommitment<P> {
+) -> SignedBroadcast<keygen::CoeffComm3<P>> {
 	let (_, fake_comm1) = generate_shares_and_commitment(
 		rng,
 		// The commitment is only invalid because of the invalid context
@@ -977,14 +1082,44 @@ pub fn gen_dummy_keygen_comm3<P: ECPoint>(
 		&SharingParameters::for_keygen(ThresholdParameters::from_share_count(share_count)),
 		None,
 	);
-	fake_comm1
+	sign_dummy_broadcast(
+		KeygenStageName::VerifyCommitmentsBroadcast4.stage_number(),
+		DelayDeserialization::new(&fake_comm1),
+	)
 }
 
-pub fn gen_dummy_signing_comm1<P: ECPoint>(rng: &mut Rng, number_of_commitments: u64) -> Comm1<P> {
+/// Generate an invalid stage 1 signing message, of the largest size an honest party may send for
+/// this number of commitments
+pub fn gen_dummy_signing_comm1<P: ECPoint>(
+	rng: &mut Rng,
+	number_of_commitments: u64,
+) -> SignedBroadcast<Comm1<P>> {
 	use crate::crypto::ECScalar;
+	use signing::{
+		max_preprocessing_nonces, Comm1Inner, PreprocessedNonceId, PreprocessedSig,
+		MAX_PREPROCESSED_NONCES,
+	};
+
 	let point = P::from_scalar(&P::Scalar::random(rng));
-	let comm1: Vec<_> = (0..number_of_commitments)
-		.map(|_| SigningCommitment { d: point, e: point })
-		.collect();
-	DelayDeserialization::new(&comm1)
+	let gen_commitments = |count: u64| -> Vec<_> {
+		(0..count).map(|_| SigningCommitment { d: point, e: point }).collect()
+	};
+
+	sign_dummy_broadcast(
+		SigningStageName::VerifyCommitmentsBroadcast2.stage_number(),
+		DelayDeserialization::new(&Comm1Inner::<P> {
+			commitments: gen_commitments(number_of_commitments),
+			preprocessing_commitments: gen_commitments(max_preprocessing_nonces(
+				number_of_commitments as usize,
+			) as u64),
+			preprocessed_sig: (number_of_commitments <= MAX_PREPROCESSED_NONCES as u64).then(
+				|| PreprocessedSig {
+					nonce_ids: (0..number_of_commitments)
+						.map(|index| PreprocessedNonceId { ceremony_id: 0, index: index as u32 })
+						.collect(),
+					responses: (0..number_of_commitments).map(|_| P::Scalar::random(rng)).collect(),
+				},
+			),
+		}),
+	)
 }
@@ -1,3 +1,5 @@
+use cf_primitives::CeremonyId;
+
 use super::KeygenResultInfo;
 use crate::{crypto::KeyId, ChainSigning};
 
@@ -11,4 +13,19 @@ pub trait KeyStoreAPI<C: ChainSigning>: Send + Sync {
 
 	/// Save or update the key data and write it to persistent memory
 	fn set_key(&mut self, key_id: KeyId, key: KeygenResultInfo<C::CryptoScheme>);
+
+	/// Save the refreshed key data from a share refresh ceremony and write it to persistent
+	/// memory, where it is kept until the refresh is completed
+	fn set_pending_share_refresh(
+		&mut self,
+		ceremony_id: CeremonyId,
+		key_id: KeyId,
+		key: KeygenResultInfo<C::CryptoScheme>,
+	);
+
+	/// Remove and return the refreshed key data from a share refresh ceremony
+	fn take_pending_share_refresh(
+		&mut self,
+		ceremony_id: CeremonyId,
+	) -> Option<(KeyId, KeygenResultInfo<C::CryptoScheme>)>;
 }
@@ -10,7 +10,7 @@ use state_chain_runtime::constants::common::MAX_AUTHORITIES;
 use crate::{
 	client::common::{
 		BroadcastVerificationMessage, DelayDeserialization, KeygenStageName,
-		PreProcessStageDataCheck,
+		PreProcessStageDataCheck, SignedBroadcast,
 	},
 	crypto::ECPoint,
 	ChainSigning,
@@ -26,18 +26,18 @@ pub use tests::{gen_keygen_data_hash_comm1, gen_keygen_data_verify_hash_comm2};
 pub enum KeygenData<P: ECPoint> {
 	#[serde(bound = "")]
 	PubkeyShares0(PubkeyShares0<P>),
-	HashComm1(HashComm1),
+	HashComm1(SignedBroadcast<HashComm1>),
 	VerifyHashComm2(VerifyHashComm2),
 	#[serde(bound = "")] // see https://github.com/serde-rs/serde/issues/1296
-	CoeffComm3(CoeffComm3<P>),
+	CoeffComm3(SignedBroadcast<CoeffComm3<P>>),
 	#[serde(bound = "")]
 	VerifyCoeffComm4(VerifyCoeffComm4<P>),
 	#[serde(bound = "")]
 	SecretShares5(SecretShare5<P>),
-	Complaints6(Complaints6),
+	Complaints6(SignedBroadcast<Complaints6>),
 	VerifyComplaints7(VerifyComplaints7),
 	#[serde(bound = "")]
-	BlameResponse8(BlameResponse8<P>),
+	BlameResponse8(SignedBroadcast<BlameResponse8<P>>),
 	#[serde(bound = "")]
 	VerifyBlameResponses9(VerifyBlameResponses9<P>),
 }
@@ -71,13 +71,13 @@ impl<P: ECPoint> PreProcessStageDataCheck<KeygenStageName> for KeygenData<P> {
 			KeygenData::PubkeyShares0(_) | KeygenData::HashComm1(_) =>
 				self.is_initial_stage_data_size_valid::<Chain>(),
 			KeygenData::VerifyHashComm2(message) => message.data.len() == num_of_parties,
-			KeygenData::CoeffComm3(message) => message.payload.len() <= MAX_COEFF_COMM_3_SIZE,
+			KeygenData::CoeffComm3(message) => message.value.payload.len() <= MAX_COEFF_COMM_3_SIZE,
 			KeygenData::VerifyCoeffComm4(message) =>
 				message.is_data_size_valid(num_of_parties, MAX_COEFF_COMM_3_SIZE),
 			KeygenData::SecretShares5(_) => true,
 			KeygenData::Complaints6(complaints) => {
 				// The complaints are optional, so we just check the max length
-				complaints.0.len() <= num_of_parties
+				complaints.value.0.len() <= num_of_parties
 			},
 			KeygenData::VerifyComplaints7(message) =>
 				message.data.len() == num_of_parties &&
@@ -85,10 +85,10 @@ impl<P: ECPoint> PreProcessStageDataCheck<KeygenStageName> for KeygenData<P> {
 						.data
 						.values()
 						.flatten()
-						.any(|complaints| complaints.0.len() > num_of_parties),
+						.any(|complaints| complaints.value.0.len() > num_of_parties),
 			KeygenData::BlameResponse8(blame_response) => {
 				// The blame response will only contain a subset, so we just check the max length
-				blame_response.0.len() <= num_of_parties
+				blame_response.value.0.len() <= num_of_parties
 			},
 			KeygenData::VerifyBlameResponses9(message) =>
 				message.data.len() == num_of_parties &&
@@ -96,7 +96,7 @@ impl<P: ECPoint> PreProcessStageDataCheck<KeygenStageName> for KeygenData<P> {
 						.data
 						.values()
 						.flatten()
-						.any(|blame_response| blame_response.0.len() > num_of_parties),
+						.any(|blame_response| blame_response.value.0.len() > num_of_parties),
 		}
 	}
 
@@ -155,11 +155,11 @@ pub struct HashComm1(pub sp_core::H256);
 #[derive(Clone, Debug, Serialize, Deserialize, PartialOrd, Ord, PartialEq, Eq)]
 pub struct PubkeyShares0<P: ECPoint>(#[serde(bound = "")] pub BTreeMap<AuthorityCount, P>);
 
-pub type VerifyHashComm2 = BroadcastVerificationMessage<HashComm1>;
+pub type VerifyHashComm2 = BroadcastVerificationMessage<SignedBroadcast<HashComm1>>;
 
 pub type CoeffComm3<P> = DelayDeserialization<super::keygen_detail::DKGUnverifiedCommitment<P>>;
 
-pub type VerifyCoeffComm4<P> = BroadcastVerificationMessage<CoeffComm3<P>>;
+pub type VerifyCoeffComm4<P> = BroadcastVerificationMessage<SignedBroadcast<CoeffComm3<P>>>;
 
 /// Secret share of our locally generated secret calculated separately
 /// for each party as the result of evaluating sharing polynomial (generated
@@ -170,7 +170,7 @@ pub type SecretShare5<P> = ShamirShare<P>;
 #[derive(Clone, Debug, Serialize, Deserialize, PartialOrd, Ord, PartialEq, Eq)]
 pub struct Complaints6(pub BTreeSet<AuthorityCount>);
 
-pub type VerifyComplaints7 = BroadcastVerificationMessage<Complaints6>;
+pub type VerifyComplaints7 = BroadcastVerificationMessage<SignedBroadcast<Complaints6>>;
 
 /// For each party blaming a node, it responds with the corresponding (valid)
 /// secret share. Unlike secret shares sent at the earlier stage, these shares
@@ -184,17 +184,18 @@ pub struct BlameResponse8<P: ECPoint>(
 	#[serde(bound = "")] pub BTreeMap<AuthorityCount, ShamirShare<P>>,
 );
 
-pub type VerifyBlameResponses9<P> = BroadcastVerificationMessage<BlameResponse8<P>>;
+pub type VerifyBlameResponses9<P> =
+	BroadcastVerificationMessage<SignedBroadcast<BlameResponse8<P>>>;
 
 derive_impls_for_enum_variants!(impl<P: ECPoint> for PubkeyShares0<P>, KeygenData::PubkeyShares0, KeygenData<P>);
-derive_impls_for_enum_variants!(impl<P: ECPoint> for HashComm1, KeygenData::HashComm1, KeygenData<P>);
+derive_impls_for_enum_variants!(impl<P: ECPoint> for SignedBroadcast<HashComm1>, KeygenData::HashComm1, KeygenData<P>);
 derive_impls_for_enum_variants!(impl<P: ECPoint> for VerifyHashComm2, KeygenData::VerifyHashComm2, KeygenData<P>);
-derive_impls_for_enum_variants!(impl<P: ECPoint> for CoeffComm3<P>, KeygenData::CoeffComm3, KeygenData<P>);
+derive_impls_for_enum_variants!(impl<P: ECPoint> for SignedBroadcast<CoeffComm3<P>>, KeygenData::CoeffComm3, KeygenData<P>);
 derive_impls_for_enum_variants!(impl<P: ECPoint> for VerifyCoeffComm4<P>, KeygenData::VerifyCoeffComm4, KeygenData<P>);
 derive_impls_for_enum_variants!(impl<P: ECPoint> for SecretShare5<P>, KeygenData::SecretShares5, KeygenData<P>);
-derive_impls_for_enum_variants!(impl<P: ECPoint> for Complaints6, KeygenData::Complaints6, KeygenData<P>);
+derive_impls_for_enum_variants!(impl<P: ECPoint> for SignedBroadcast<Complaints6>, KeygenData::Complaints6, KeygenData<P>);
 derive_impls_for_enum_variants!(impl<P: ECPoint> for VerifyComplaints7, KeygenData::VerifyComplaints7, KeygenData<P>);
-derive_impls_for_enum_variants!(impl<P: ECPoint> for BlameResponse8<P>, KeygenData::BlameResponse8, KeygenData<P>);
+derive_impls_for_enum_variants!(impl<P: ECPoint> for SignedBroadcast<BlameResponse8<P>>, KeygenData::BlameResponse8, KeygenData<P>);
 derive_impls_for_enum_variants!(impl<P: ECPoint> for VerifyBlameResponses9<P>, KeygenData::VerifyBlameResponses9, KeygenData<P>);
 
 // TODO: combine these with derive_impls_for_enum_variants! macro?
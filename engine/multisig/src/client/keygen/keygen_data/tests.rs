@@ -7,11 +7,8 @@ use state_chain_runtime::constants::common::MAX_AUTHORITIES;
 use crate::{
 	bitcoin::BtcSigning,
 	client::{
-		common::{
-			BroadcastVerificationMessage, DelayDeserialization, KeygenStageName,
-			PreProcessStageDataCheck,
-		},
-		helpers::{gen_dummy_keygen_comm3, get_dummy_hash_comm},
+		common::{BroadcastVerificationMessage, KeygenStageName, PreProcessStageDataCheck},
+		helpers::{gen_dummy_keygen_comm3, get_dummy_hash_comm, sign_dummy_broadcast},
 		keygen::{BlameResponse8, Complaints6, KeygenData, PubkeyShares0, SecretShare5},
 	},
 	crypto::Rng,
@@ -47,10 +44,7 @@ pub fn gen_keygen_data_verify_hash_comm2(participant_count: AuthorityCount) -> K
 
 fn gen_keygen_data_coeff_comm3(participant_count: AuthorityCount) -> KeygenData<Point> {
 	let mut rng = Rng::from_seed([0; 32]);
-	KeygenData::CoeffComm3(DelayDeserialization::new(&gen_dummy_keygen_comm3::<Point>(
-		&mut rng,
-		participant_count,
-	)))
+	KeygenData::CoeffComm3(gen_dummy_keygen_comm3::<Point>(&mut rng, participant_count))
 }
 
 fn gen_keygen_data_verify_coeff_comm4(
@@ -64,7 +58,7 @@ fn gen_keygen_data_verify_coeff_comm4(
 			.map(|i| {
 				(
 					i as AuthorityCount,
-					Some(DelayDeserialization::new(&gen_dummy_keygen_comm3::<Point>(
+					Some(gen_dummy_keygen_comm3::<Point>(
 						&mut rng,
 						// Use a 2 different sizes for a more complex test
 						if i <= participant_count_outer / 2 {
@@ -72,7 +66,7 @@ fn gen_keygen_data_verify_coeff_comm4(
 						} else {
 							participant_count_inner_second_half
 						},
-					))),
+					)),
 				)
 			})
 			.collect(),
@@ -85,7 +79,10 @@ fn gen_keygen_secret_shares5() -> KeygenData<Point> {
 }
 
 fn gen_keygen_data_complaints6(participant_count: AuthorityCount) -> KeygenData<Point> {
-	KeygenData::Complaints6(Complaints6(BTreeSet::from_iter(1..=participant_count)))
+	KeygenData::Complaints6(sign_dummy_broadcast(
+		KeygenStageName::VerifyComplaintsBroadcastStage7.stage_number(),
+		Complaints6(BTreeSet::from_iter(1..=participant_count)),
+	))
 }
 
 fn gen_keygen_data_verify_complaints7(
@@ -101,7 +98,13 @@ fn gen_keygen_data_verify_complaints7(
 				} else {
 					participant_count_inner_second_half
 				};
-				(i as AuthorityCount, Some(Complaints6(BTreeSet::from_iter(1..=inner_count))))
+				(
+					i as AuthorityCount,
+					Some(sign_dummy_broadcast(
+						KeygenStageName::VerifyComplaintsBroadcastStage7.stage_number(),
+						Complaints6(BTreeSet::from_iter(1..=inner_count)),
+					)),
+				)
 			})
 			.collect(),
 	})
@@ -109,10 +112,13 @@ fn gen_keygen_data_verify_complaints7(
 
 fn gen_keygen_data_blame_response8(participant_count: AuthorityCount) -> KeygenData<Point> {
 	let mut rng = Rng::from_seed([0; 32]);
-	KeygenData::BlameResponse8(BlameResponse8(
-		(1..=participant_count)
-			.map(|i| (i, SecretShare5::create_random(&mut rng)))
-			.collect(),
+	KeygenData::BlameResponse8(sign_dummy_broadcast(
+		KeygenStageName::VerifyBlameResponsesBroadcastStage9.stage_number(),
+		BlameResponse8(
+			(1..=participant_count)
+				.map(|i| (i, SecretShare5::create_random(&mut rng)))
+				.collect(),
+		),
 	))
 }
 
@@ -130,10 +136,13 @@ fn gen_keygen_data_verify_blame_response9(
 				(
 					i as AuthorityCount,
 					// Create a nested collection with a changing size
-					Some(BlameResponse8(
-						(0..inner_count)
-							.map(|i| (i, SecretShare5::create_random(&mut rng)))
-							.collect(),
+					Some(sign_dummy_broadcast(
+						KeygenStageName::VerifyBlameResponsesBroadcastStage9.stage_number(),
+						BlameResponse8(
+							(0..inner_count)
+								.map(|i| (i, SecretShare5::create_random(&mut rng)))
+								.collect(),
+						),
 					)),
 				)
 			})
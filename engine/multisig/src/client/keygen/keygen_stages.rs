@@ -9,7 +9,7 @@ use crate::{
 		ceremony_manager::KeygenCeremony,
 		common::{
 			try_deserialize, BroadcastFailureReason, DelayDeserialization, KeygenFailureReason,
-			KeygenStageName, ParticipantStatus, ResharingContext,
+			KeygenStageName, ParticipantStatus, ResharingContext, SignedBroadcast,
 		},
 		utils::{find_frequent_element, threshold_for_broadcast_verification},
 		KeygenResult, KeygenResultInfo,
@@ -141,6 +141,7 @@ impl<Crypto: CryptoScheme> BroadcastStageProcessor<KeygenCeremony<Crypto>>
 					BroadcastFailureReason::InsufficientMessages,
 					Self::NAME,
 				),
+				Default::default(),
 			)
 		}
 
@@ -159,6 +160,7 @@ impl<Crypto: CryptoScheme> BroadcastStageProcessor<KeygenCeremony<Crypto>>
 						BroadcastFailureReason::Inconsistency,
 						Self::NAME,
 					),
+					Default::default(),
 				)
 			}
 
@@ -199,6 +201,7 @@ impl<Crypto: CryptoScheme> BroadcastStageProcessor<KeygenCeremony<Crypto>>
 					BroadcastFailureReason::Inconsistency,
 					Self::NAME,
 				),
+				Default::default(),
 			)
 		}
 	}
@@ -284,12 +287,15 @@ impl<Crypto: CryptoScheme> HashCommitments1<Crypto> {
 impl<Crypto: CryptoScheme> BroadcastStageProcessor<KeygenCeremony<Crypto>>
 	for HashCommitments1<Crypto>
 {
-	type Message = HashComm1;
+	type Message = SignedBroadcast<HashComm1>;
 	const NAME: KeygenStageName = KeygenStageName::HashCommitments1;
 
 	fn init(&mut self) -> DataToSend<Self::Message> {
 		// We don't want to reveal the public coefficients yet, so sending the hash commitment only
-		DataToSend::Broadcast(HashComm1(self.hash_commitment))
+		DataToSend::Broadcast(self.keygen_common.common.sign_broadcast(
+			KeygenStageName::VerifyHashCommitmentsBroadcast2.stage_number(),
+			HashComm1(self.hash_commitment),
+		))
 	}
 
 	async fn process(
@@ -314,7 +320,7 @@ impl<Crypto: CryptoScheme> BroadcastStageProcessor<KeygenCeremony<Crypto>>
 pub struct VerifyHashCommitmentsBroadcast2<Crypto: CryptoScheme> {
 	keygen_common: KeygenCommon<Crypto>,
 	own_commitment: DKGUnverifiedCommitment<Crypto::Point>,
-	hash_commitments: BTreeMap<AuthorityCount, Option<HashComm1>>,
+	hash_commitments: BTreeMap<AuthorityCount, Option<SignedBroadcast<HashComm1>>>,
 	shares_to_send: OutgoingShares<Crypto::Point>,
 }
 
@@ -337,11 +343,12 @@ impl<Crypto: CryptoScheme> BroadcastStageProcessor<KeygenCeremony<Crypto>>
 	) -> StageResult<KeygenCeremony<Crypto>> {
 		let hash_commitments = match verify_broadcasts_non_blocking(messages).await {
 			Ok(hash_commitments) => hash_commitments,
-			Err((reported_parties, abort_reason)) => {
+			Err((reported_parties, abort_reason, conflicts)) => {
 				warn!("Broadcast verification is not successful for {}", Self::NAME);
 				return KeygenStageResult::Error(
 					reported_parties,
 					KeygenFailureReason::BroadcastFailure(abort_reason, Self::NAME),
+					conflicts,
 				)
 			},
 		};
@@ -381,11 +388,14 @@ derive_display_as_type_name!(CoefficientCommitments3<Crypto: CryptoScheme>);
 impl<Crypto: CryptoScheme> BroadcastStageProcessor<KeygenCeremony<Crypto>>
 	for CoefficientCommitments3<Crypto>
 {
-	type Message = CoeffComm3<Crypto::Point>;
+	type Message = SignedBroadcast<CoeffComm3<Crypto::Point>>;
 	const NAME: KeygenStageName = KeygenStageName::CoefficientCommitments3;
 
 	fn init(&mut self) -> DataToSend<Self::Message> {
-		DataToSend::Broadcast(DelayDeserialization::new(&self.own_commitment))
+		DataToSend::Broadcast(self.keygen_common.common.sign_broadcast(
+			KeygenStageName::VerifyCommitmentsBroadcast4.stage_number(),
+			DelayDeserialization::new(&self.own_commitment),
+		))
 	}
 
 	async fn process(
@@ -412,7 +422,7 @@ impl<Crypto: CryptoScheme> BroadcastStageProcessor<KeygenCeremony<Crypto>>
 struct VerifyCommitmentsBroadcast4<Crypto: CryptoScheme> {
 	keygen_common: KeygenCommon<Crypto>,
 	hash_commitments: BTreeMap<AuthorityCount, HashComm1>,
-	commitments: BTreeMap<AuthorityCount, Option<CoeffComm3<Crypto::Point>>>,
+	commitments: BTreeMap<AuthorityCount, Option<SignedBroadcast<CoeffComm3<Crypto::Point>>>>,
 	shares_to_send: OutgoingShares<Crypto::Point>,
 }
 
@@ -437,10 +447,11 @@ impl<Crypto: CryptoScheme> BroadcastStageProcessor<KeygenCeremony<Crypto>>
 	) -> KeygenStageResult<Crypto> {
 		let commitments = match verify_broadcasts_non_blocking(messages).await {
 			Ok(comms) => comms,
-			Err((reported_parties, abort_reason)) =>
+			Err((reported_parties, abort_reason, conflicts)) =>
 				return KeygenStageResult::Error(
 					reported_parties,
 					KeygenFailureReason::BroadcastFailure(abort_reason, Self::NAME),
+					conflicts,
 				),
 		};
 
@@ -464,6 +475,7 @@ impl<Crypto: CryptoScheme> BroadcastStageProcessor<KeygenCeremony<Crypto>>
 				return KeygenStageResult::Error(
 					bad_parties,
 					KeygenFailureReason::DeserializationError,
+					Default::default(),
 				),
 		};
 
@@ -475,7 +487,8 @@ impl<Crypto: CryptoScheme> BroadcastStageProcessor<KeygenCeremony<Crypto>>
 			common.validator_mapping.clone(),
 		) {
 			Ok(comms) => comms,
-			Err((blamed_parties, reason)) => return StageResult::Error(blamed_parties, reason),
+			Err((blamed_parties, reason)) =>
+				return StageResult::Error(blamed_parties, reason, Default::default()),
 		};
 
 		debug!("{} is successful", Self::NAME);
@@ -634,11 +647,14 @@ derive_display_as_type_name!(ComplaintsStage6<Crypto: CryptoScheme>);
 impl<Crypto: CryptoScheme> BroadcastStageProcessor<KeygenCeremony<Crypto>>
 	for ComplaintsStage6<Crypto>
 {
-	type Message = Complaints6;
+	type Message = SignedBroadcast<Complaints6>;
 	const NAME: KeygenStageName = KeygenStageName::ComplaintsStage6;
 
 	fn init(&mut self) -> DataToSend<Self::Message> {
-		DataToSend::Broadcast(Complaints6(self.complaints.clone()))
+		DataToSend::Broadcast(self.keygen_common.common.sign_broadcast(
+			KeygenStageName::VerifyComplaintsBroadcastStage7.stage_number(),
+			Complaints6(self.complaints.clone()),
+		))
 	}
 
 	async fn process(
@@ -664,7 +680,7 @@ impl<Crypto: CryptoScheme> BroadcastStageProcessor<KeygenCeremony<Crypto>>
 struct VerifyComplaintsBroadcastStage7<Crypto: CryptoScheme> {
 	keygen_common: KeygenCommon<Crypto>,
 	agg_pubkey: ValidAggregateKey<Crypto::Point>,
-	received_complaints: BTreeMap<AuthorityCount, Option<Complaints6>>,
+	received_complaints: BTreeMap<AuthorityCount, Option<SignedBroadcast<Complaints6>>>,
 	commitments: BTreeMap<AuthorityCount, DKGCommitment<Crypto::Point>>,
 	shares: IncomingShares<Crypto::Point>,
 	outgoing_shares: OutgoingShares<Crypto::Point>,
@@ -691,10 +707,11 @@ impl<Crypto: CryptoScheme> BroadcastStageProcessor<KeygenCeremony<Crypto>>
 	) -> KeygenStageResult<Crypto> {
 		let verified_complaints = match verify_broadcasts_non_blocking(messages).await {
 			Ok(comms) => comms,
-			Err((reported_parties, abort_reason)) =>
+			Err((reported_parties, abort_reason, conflicts)) =>
 				return KeygenStageResult::Error(
 					reported_parties,
 					KeygenFailureReason::BroadcastFailure(abort_reason, Self::NAME),
+					conflicts,
 				),
 		};
 
@@ -751,7 +768,11 @@ impl<Crypto: CryptoScheme> BroadcastStageProcessor<KeygenCeremony<Crypto>>
 
 			StageResult::NextStage(Box::new(stage))
 		} else {
-			StageResult::Error(idxs_to_report, KeygenFailureReason::InvalidComplaint)
+			StageResult::Error(
+				idxs_to_report,
+				KeygenFailureReason::InvalidComplaint,
+				Default::default(),
+			)
 		}
 	}
 }
@@ -805,7 +826,7 @@ derive_display_as_type_name!(BlameResponsesStage8<Crypto: CryptoScheme>);
 impl<Crypto: CryptoScheme> BroadcastStageProcessor<KeygenCeremony<Crypto>>
 	for BlameResponsesStage8<Crypto>
 {
-	type Message = BlameResponse8<Crypto::Point>;
+	type Message = SignedBroadcast<BlameResponse8<Crypto::Point>>;
 	const NAME: KeygenStageName = KeygenStageName::BlameResponsesStage8;
 
 	fn init(&mut self) -> DataToSend<Self::Message> {
@@ -827,18 +848,23 @@ impl<Crypto: CryptoScheme> BroadcastStageProcessor<KeygenCeremony<Crypto>>
 			.collect();
 
 		// TODO: put a limit on how many shares to reveal?
-		let data = DataToSend::Broadcast(BlameResponse8(
-			idxs_to_reveal
-				.iter()
-				.map(|idx| {
-					debug!(
-						"Revealing share for {}",
-						common.validator_mapping.get_id(*idx).to_string()
-					);
-					(*idx, self.outgoing_shares.0[idx].clone())
-				})
-				.collect(),
-		));
+		let data = DataToSend::Broadcast(
+			common.sign_broadcast(
+				KeygenStageName::VerifyBlameResponsesBroadcastStage9.stage_number(),
+				BlameResponse8(
+					idxs_to_reveal
+						.iter()
+						.map(|idx| {
+							debug!(
+								"Revealing share for {}",
+								common.validator_mapping.get_id(*idx).to_string()
+							);
+							(*idx, self.outgoing_shares.0[idx].clone())
+						})
+						.collect(),
+				),
+			),
+		);
 
 		// Outgoing shares are no longer needed, so we zeroize them
 		drop(std::mem::take(&mut self.outgoing_shares));
@@ -871,7 +897,8 @@ struct VerifyBlameResponsesBroadcastStage9<Crypto: CryptoScheme> {
 	complaints: BTreeMap<AuthorityCount, Complaints6>,
 	agg_pubkey: ValidAggregateKey<Crypto::Point>,
 	// Blame responses received from other parties in the previous communication round
-	blame_responses: BTreeMap<AuthorityCount, Option<BlameResponse8<Crypto::Point>>>,
+	blame_responses:
+		BTreeMap<AuthorityCount, Option<SignedBroadcast<BlameResponse8<Crypto::Point>>>>,
 	shares: IncomingShares<Crypto::Point>,
 	commitments: BTreeMap<AuthorityCount, DKGCommitment<Crypto::Point>>,
 }
@@ -975,10 +1002,11 @@ impl<Crypto: CryptoScheme> BroadcastStageProcessor<KeygenCeremony<Crypto>>
 
 		let verified_responses = match verify_broadcasts_non_blocking(messages).await {
 			Ok(comms) => comms,
-			Err((reported_parties, abort_reason)) =>
+			Err((reported_parties, abort_reason, conflicts)) =>
 				return KeygenStageResult::Error(
 					reported_parties,
 					KeygenFailureReason::BroadcastFailure(abort_reason, Self::NAME),
+					conflicts,
 				),
 		};
 
@@ -991,8 +1019,11 @@ impl<Crypto: CryptoScheme> BroadcastStageProcessor<KeygenCeremony<Crypto>>
 				finalize_keygen(self.keygen_common, self.agg_pubkey, self.shares, self.commitments)
 					.await
 			},
-			Err(bad_parties) =>
-				StageResult::Error(bad_parties, KeygenFailureReason::InvalidBlameResponse),
+			Err(bad_parties) => StageResult::Error(
+				bad_parties,
+				KeygenFailureReason::InvalidBlameResponse,
+				Default::default(),
+			),
 		}
 	}
 }
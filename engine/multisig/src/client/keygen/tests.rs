@@ -6,7 +6,7 @@ use crate::{
 	client::{
 		common::{
 			BroadcastFailureReason, DelayDeserialization, KeygenFailureReason, KeygenStageName,
-			ResharingContext,
+			ResharingContext, SignedBroadcast,
 		},
 		helpers::{
 			gen_dummy_keygen_comm3, get_dummy_hash_comm, new_nodes, run_keygen, run_stages,
@@ -14,7 +14,7 @@ use crate::{
 			PayloadAndKeyData, SigningCeremonyRunner, ACCOUNT_IDS, DEFAULT_KEYGEN_CEREMONY_ID,
 			DEFAULT_KEYGEN_SEED, DEFAULT_SIGNING_CEREMONY_ID,
 		},
-		keygen::{self, Complaints6, VerifyComplaints7, VerifyHashComm2},
+		keygen::{self, VerifyComplaints7, VerifyHashComm2},
 		utils::PartyIdxMapping,
 	},
 	crypto::{ECPoint, Rng},
@@ -23,10 +23,12 @@ use crate::{
 };
 
 use crate::crypto::eth::Point;
-type CoeffComm3 = keygen::CoeffComm3<Point>;
+type HashComm1 = SignedBroadcast<keygen::HashComm1>;
+type CoeffComm3 = SignedBroadcast<keygen::CoeffComm3<Point>>;
 type VerifyCoeffComm4 = keygen::VerifyCoeffComm4<Point>;
 type SecretShare5 = keygen::SecretShare5<Point>;
-type BlameResponse8 = keygen::BlameResponse8<Point>;
+type Complaints6 = SignedBroadcast<keygen::Complaints6>;
+type BlameResponse8 = SignedBroadcast<keygen::BlameResponse8<Point>>;
 type VerifyBlameResponses9 = keygen::VerifyBlameResponses9<Point>;
 type KeygenData = keygen::KeygenData<Point>;
 type KeygenCeremonyRunnerEth = KeygenCeremonyRunner<EthSigning>;
@@ -91,7 +93,7 @@ async fn should_enter_blaming_stage_on_invalid_secret_shares() {
 	let messages = run_stages!(
 		ceremony,
 		messages,
-		keygen::Complaints6,
+		Complaints6,
 		keygen::VerifyComplaints7,
 		BlameResponse8,
 		VerifyBlameResponses9
@@ -167,7 +169,7 @@ async fn should_report_on_invalid_blame_response() {
 	// bad_node_id_1 also sends a bad blame response, and so gets blamed when ceremony finished
 	let secret_share = SecretShare5::create_random(&mut ceremony.rng);
 	for message in messages.get_mut(&bad_node_id_1).unwrap().values_mut() {
-		*message = keygen::BlameResponse8(
+		message.value = keygen::BlameResponse8(
 			std::iter::once((
 				party_idx_mapping.get_idx(&target_node_id).unwrap(),
 				secret_share.clone(),
@@ -212,7 +214,7 @@ async fn should_report_on_incomplete_blame_response() {
 
 	// bad_node_id_1 sends an empty BlameResponse
 	for message in messages.get_mut(&bad_node_id_1).unwrap().values_mut() {
-		*message = keygen::BlameResponse8::<Point>(std::collections::BTreeMap::default())
+		message.value = keygen::BlameResponse8::<Point>(std::collections::BTreeMap::default())
 	}
 
 	let messages = ceremony.run_stage::<VerifyBlameResponses9, _, _>(messages).await;
@@ -235,22 +237,29 @@ async fn should_report_on_inconsistent_broadcast_coeff_comm() {
 
 	// Make one of the nodes send a different commitment to half of the others
 	// Note: the bad node must send different comm1 to more than 1/3 of the participants
-	let commitment = DelayDeserialization::new(&gen_dummy_keygen_comm3::<Point>(
-		&mut ceremony.rng,
-		ACCOUNT_IDS.len() as AuthorityCount,
-	));
+	let commitment =
+		gen_dummy_keygen_comm3::<Point>(&mut ceremony.rng, ACCOUNT_IDS.len() as AuthorityCount);
+	let commitment = ceremony.sign_broadcast(
+		bad_account_id,
+		KeygenStageName::VerifyCommitmentsBroadcast4.stage_number(),
+		commitment.value,
+	);
+	let own_message = messages[bad_account_id].values().next().unwrap().clone();
 	for message in messages.get_mut(bad_account_id).unwrap().values_mut().step_by(2) {
 		*message = commitment.clone();
 	}
+	let expected_conflicts =
+		ceremony.expected_conflicts(bad_account_id, &own_message, &messages[bad_account_id]);
 
 	let messages = ceremony.run_stage::<VerifyCoeffComm4, _, _>(messages).await;
 	ceremony.distribute_messages(messages).await;
-	ceremony.complete_with_error(
+	ceremony.complete_with_conflicts(
 		&[bad_account_id.clone()],
 		KeygenFailureReason::BroadcastFailure(
 			BroadcastFailureReason::Inconsistency,
 			KeygenStageName::VerifyCommitmentsBroadcast4,
 		),
+		expected_conflicts,
 	);
 }
 
@@ -265,19 +274,28 @@ async fn should_report_on_inconsistent_broadcast_hash_comm() {
 	// Make one of the nodes send a different hash commitment to half of the others
 	// Note: the bad node must send different values to more than 1/3 of the participants
 	let hash_comm = get_dummy_hash_comm(&mut ceremony.rng);
+	let hash_comm = ceremony.sign_broadcast(
+		bad_account_id,
+		KeygenStageName::VerifyHashCommitmentsBroadcast2.stage_number(),
+		hash_comm.value,
+	);
+	let own_message = messages[bad_account_id].values().next().unwrap().clone();
 	for message in messages.get_mut(bad_account_id).unwrap().values_mut().step_by(2) {
 		*message = hash_comm.clone();
 	}
+	let expected_conflicts =
+		ceremony.expected_conflicts(bad_account_id, &own_message, &messages[bad_account_id]);
 
 	let messages = run_stages!(ceremony, messages, VerifyHashComm2,);
 
 	ceremony.distribute_messages(messages).await;
-	ceremony.complete_with_error(
+	ceremony.complete_with_conflicts(
 		&[bad_account_id.clone()],
 		KeygenFailureReason::BroadcastFailure(
 			BroadcastFailureReason::Inconsistency,
 			KeygenStageName::VerifyHashCommitmentsBroadcast2,
 		),
+		expected_conflicts,
 	);
 }
 
@@ -302,12 +320,12 @@ async fn should_report_on_invalid_hash_comm() {
 			messages.get(&bad_account_id).unwrap().values().next().unwrap().clone();
 
 		let mut commitment: keygen::DKGUnverifiedCommitment<Point> =
-			original_message.deserialize().unwrap();
+			original_message.value.deserialize().unwrap();
 		commitment.corrupt_secondary_coefficient(&mut ceremony.rng);
 		DelayDeserialization::new(&commitment)
 	};
 	for message in messages.get_mut(&bad_account_id).unwrap().values_mut() {
-		*message = corrupted_message.clone();
+		message.value = corrupted_message.clone();
 	}
 
 	let messages = ceremony.run_stage::<VerifyCoeffComm4, _, _>(messages).await;
@@ -336,21 +354,30 @@ async fn should_report_on_inconsistent_broadcast_complaints() {
 
 	// Make one of the nodes send 2 different complaints evenly to the others
 	// Note: the bad node must send different complaints to more than 1/3 of the participants
+	let own_message = messages[bad_account_id].values().next().unwrap().clone();
 	for (counter, message) in messages.get_mut(bad_account_id).unwrap().values_mut().enumerate() {
 		let counter = counter as AuthorityCount;
-		*message = Complaints6(BTreeSet::from_iter(
-			counter % 2..((counter % 2) + ACCOUNT_IDS.len() as AuthorityCount),
-		));
+		*message = ceremony.sign_broadcast(
+			bad_account_id,
+			KeygenStageName::VerifyComplaintsBroadcastStage7.stage_number(),
+			keygen::Complaints6(BTreeSet::from_iter(
+				counter % 2..((counter % 2) + ACCOUNT_IDS.len() as AuthorityCount),
+			)),
+		);
 	}
 
+	let expected_conflicts =
+		ceremony.expected_conflicts(bad_account_id, &own_message, &messages[bad_account_id]);
+
 	let messages = ceremony.run_stage::<keygen::VerifyComplaints7, _, _>(messages).await;
 	ceremony.distribute_messages(messages).await;
-	ceremony.complete_with_error(
+	ceremony.complete_with_conflicts(
 		&[bad_account_id.clone()],
 		KeygenFailureReason::BroadcastFailure(
 			BroadcastFailureReason::Inconsistency,
 			KeygenStageName::VerifyComplaintsBroadcastStage7,
 		),
+		expected_conflicts,
 	);
 }
 
@@ -390,24 +417,33 @@ async fn should_report_on_inconsistent_broadcast_blame_responses() {
 	// Make one of the nodes send 2 different blame responses evenly to the others
 	// Note: the bad node must send different blame response to more than 1/3 of the participants
 	let secret_share = SecretShare5::create_random(&mut ceremony.rng);
-	for message in messages.get_mut(bad_node_id).unwrap().values_mut().step_by(2) {
-		*message = keygen::BlameResponse8::<Point>(
+	let blame_response = ceremony.sign_broadcast(
+		bad_node_id,
+		KeygenStageName::VerifyBlameResponsesBroadcastStage9.stage_number(),
+		keygen::BlameResponse8::<Point>(
 			std::iter::once((
 				party_idx_mapping.get_idx(blamed_node_id).unwrap(),
 				secret_share.clone(),
 			))
 			.collect(),
-		)
+		),
+	);
+	let own_message = messages[bad_node_id].values().next().unwrap().clone();
+	for message in messages.get_mut(bad_node_id).unwrap().values_mut().step_by(2) {
+		*message = blame_response.clone();
 	}
+	let expected_conflicts =
+		ceremony.expected_conflicts(bad_node_id, &own_message, &messages[bad_node_id]);
 
 	let messages = ceremony.run_stage::<VerifyBlameResponses9, _, _>(messages).await;
 	ceremony.distribute_messages(messages).await;
-	ceremony.complete_with_error(
+	ceremony.complete_with_conflicts(
 		&[bad_account_id.clone()],
 		KeygenFailureReason::BroadcastFailure(
 			BroadcastFailureReason::Inconsistency,
 			KeygenStageName::VerifyBlameResponsesBroadcastStage9,
 		),
+		expected_conflicts,
 	);
 }
 
@@ -425,7 +461,7 @@ async fn should_report_on_deserialization_failure_coeff_commitments() {
 	// or we will fail on the `inconsistent` error instead of the validation error.
 	let corrupted_message = DelayDeserialization::new(&b"Not a CoeffComm3");
 	for message in messages.get_mut(&bad_account_id).unwrap().values_mut() {
-		*message = corrupted_message.clone();
+		message.value = corrupted_message.clone();
 	}
 
 	let messages = ceremony.run_stage::<VerifyCoeffComm4, _, _>(messages).await;
@@ -452,12 +488,12 @@ async fn should_report_on_invalid_zkp_in_coeff_comm() {
 		let original_message =
 			messages.get(&bad_account_id).unwrap().values().next().unwrap().clone();
 		let mut commitment: keygen::DKGUnverifiedCommitment<Point> =
-			original_message.deserialize().unwrap();
+			original_message.value.deserialize().unwrap();
 		commitment.corrupt_primary_coefficient(&mut ceremony.rng);
 		DelayDeserialization::new(&commitment)
 	};
 	for message in messages.get_mut(&bad_account_id).unwrap().values_mut() {
-		*message = corrupted_message.clone();
+		message.value = corrupted_message.clone();
 	}
 
 	let messages = ceremony.run_stage::<VerifyCoeffComm4, _, _>(messages).await;
@@ -485,10 +521,10 @@ async fn should_report_on_invalid_complaints() {
 	let [bad_account_id] = ceremony.select_account_ids();
 
 	// This complaint is invalid because it has an invalid index
-	let invalid_complaint: Complaints6 = keygen::Complaints6([1, u32::MAX].into_iter().collect());
+	let invalid_complaint = keygen::Complaints6([1, u32::MAX].into_iter().collect());
 
 	for message in messages.get_mut(&bad_account_id).unwrap().values_mut() {
-		*message = invalid_complaint.clone();
+		message.value = invalid_complaint.clone();
 	}
 
 	let messages = ceremony.run_stage::<keygen::VerifyComplaints7, _, _>(messages).await;
@@ -1090,7 +1126,7 @@ mod key_handover {
 		let messages = run_stages!(
 			ceremony,
 			messages,
-			keygen::HashComm1,
+			HashComm1,
 			keygen::VerifyHashComm2,
 			CoeffComm3,
 			VerifyCoeffComm4,
@@ -1222,7 +1258,7 @@ mod key_handover {
 
 		ceremony.distribute_messages_with_non_sender(messages, &bad_account_id).await;
 
-		let messages = ceremony.gather_outgoing_messages::<keygen::HashComm1, _>().await;
+		let messages = ceremony.gather_outgoing_messages::<HashComm1, _>().await;
 
 		let messages = run_stages!(
 			ceremony,
@@ -1282,7 +1318,7 @@ mod key_handover {
 		let messages = run_stages!(
 			ceremony,
 			messages,
-			keygen::HashComm1,
+			HashComm1,
 			keygen::VerifyHashComm2,
 			CoeffComm3,
 			VerifyCoeffComm4
@@ -25,14 +25,14 @@ use state_chain_runtime::AccountId;
 use serde::{Deserialize, Serialize};
 
 use tokio::sync::mpsc::UnboundedSender;
-use tracing::{debug, info, info_span, Instrument};
+use tracing::{debug, info, info_span, warn, Instrument};
 
 use keygen::KeygenData;
 
 pub use crate::client::utils::PartyIdxMapping;
 pub use common::{
-	CeremonyFailureReason, KeygenFailureReason, KeygenResult, KeygenResultInfo, KeygenStageName,
-	SigningFailureReason,
+	BroadcastConflicts, BroadcastSigner, CeremonyFailureReason, KeygenFailureReason, KeygenResult,
+	KeygenResultInfo, KeygenStageName, SigningFailureReason,
 };
 
 #[cfg(test)]
@@ -121,7 +121,10 @@ pub trait MultisigClientApi<C: CryptoScheme> {
 		ceremony_id: CeremonyId,
 		epoch_index: EpochIndex,
 		participants: BTreeSet<AccountId>,
-	) -> BoxFuture<'_, Result<C::PublicKey, (BTreeSet<AccountId>, KeygenFailureReason)>>;
+	) -> BoxFuture<
+		'_,
+		Result<C::PublicKey, (BTreeSet<AccountId>, KeygenFailureReason, BroadcastConflicts)>,
+	>;
 
 	fn initiate_key_handover(
 		&self,
@@ -130,14 +133,38 @@ pub trait MultisigClientApi<C: CryptoScheme> {
 		epoch_index: EpochIndex,
 		sharing_participants: BTreeSet<AccountId>,
 		new_participants: BTreeSet<AccountId>,
-	) -> BoxFuture<'_, Result<C::PublicKey, (BTreeSet<AccountId>, KeygenFailureReason)>>;
+	) -> BoxFuture<
+		'_,
+		Result<C::PublicKey, (BTreeSet<AccountId>, KeygenFailureReason, BroadcastConflicts)>,
+	>;
+
+	/// Re-randomises our share of an existing key together with the other participants, without
+	/// changing the key. The refreshed share is held back until the refresh is completed with
+	/// [complete_share_refresh](MultisigClientApi::complete_share_refresh), so that all
+	/// participants switch to their new shares at the same time.
+	fn initiate_share_refresh(
+		&self,
+		ceremony_id: CeremonyId,
+		key_id: KeyId,
+		participants: BTreeSet<AccountId>,
+	) -> BoxFuture<
+		'_,
+		Result<C::PublicKey, (BTreeSet<AccountId>, KeygenFailureReason, BroadcastConflicts)>,
+	>;
+
+	/// Replaces our share of the key with the one from the share refresh ceremony if the refresh
+	/// succeeded for all participants, otherwise discards it.
+	fn complete_share_refresh(&self, ceremony_id: CeremonyId, succeeded: bool);
 
 	fn initiate_signing(
 		&self,
 		ceremony_id: CeremonyId,
 		signers: BTreeSet<AccountId>,
 		signing_info: Vec<(KeyId, C::SigningPayload)>,
-	) -> BoxFuture<'_, Result<Vec<C::Signature>, (BTreeSet<AccountId>, SigningFailureReason)>>;
+	) -> BoxFuture<
+		'_,
+		Result<Vec<C::Signature>, (BTreeSet<AccountId>, SigningFailureReason, BroadcastConflicts)>,
+	>;
 
 	fn update_latest_ceremony_id(&self, ceremony_id: CeremonyId);
 }
@@ -201,14 +228,18 @@ impl<C: ChainSigning, KeyStore: KeyStoreAPI<C>> MultisigClient<C, KeyStore> {
 		}
 	}
 
-	fn start_keygen_with_resharing_context(
+	fn request_keygen_ceremony(
 		&self,
 		ceremony_id: CeremonyId,
-		// The epoch the key will be associated with if successful.
-		epoch_index: EpochIndex,
 		participants: BTreeSet<AccountId>,
 		resharing_context: Option<ResharingContext<C::CryptoScheme>>,
-	) -> BoxFuture<'_, Result<PublicKey<C>, (BTreeSet<AccountId>, KeygenFailureReason)>> {
+	) -> BoxFuture<
+		'static,
+		Result<
+			KeygenResultInfo<C::CryptoScheme>,
+			(BTreeSet<AccountId>, KeygenFailureReason, BroadcastConflicts),
+		>,
+	> {
 		use rand::SeedableRng;
 		let rng = Rng::from_entropy();
 
@@ -229,22 +260,41 @@ impl<C: ChainSigning, KeyStore: KeyStoreAPI<C>> MultisigClient<C, KeyStore> {
 			result_receiver
 				.await
 				.expect("Keygen result channel dropped before receiving a result")
-				.map(|keygen_result_info| {
-					let agg_key = keygen_result_info.key.get_agg_public_key();
-
-					self.key_store
-						.lock()
-						.unwrap()
-						.set_key(KeyId::new(epoch_index, agg_key.clone()), keygen_result_info);
-					agg_key
-				})
-				.map_err(|(reported_parties, failure_reason)| {
+				.map_err(|(reported_parties, failure_reason, conflicts)| {
 					failure_reason.log(&reported_parties);
-					(reported_parties, failure_reason)
+					(reported_parties, failure_reason, conflicts)
 				})
 		}
 		.boxed()
 	}
+
+	fn start_keygen_with_resharing_context(
+		&self,
+		ceremony_id: CeremonyId,
+		// The epoch the key will be associated with if successful.
+		epoch_index: EpochIndex,
+		participants: BTreeSet<AccountId>,
+		resharing_context: Option<ResharingContext<C::CryptoScheme>>,
+	) -> BoxFuture<
+		'_,
+		Result<PublicKey<C>, (BTreeSet<AccountId>, KeygenFailureReason, BroadcastConflicts)>,
+	> {
+		let keygen_result_future =
+			self.request_keygen_ceremony(ceremony_id, participants, resharing_context);
+
+		async move {
+			keygen_result_future.await.map(|keygen_result_info| {
+				let agg_key = keygen_result_info.key.get_agg_public_key();
+
+				self.key_store
+					.lock()
+					.unwrap()
+					.set_key(KeyId::new(epoch_index, agg_key.clone()), keygen_result_info);
+				agg_key
+			})
+		}
+		.boxed()
+	}
 }
 
 impl<C: ChainSigning, KeyStore: KeyStoreAPI<C>> MultisigClientApi<C::CryptoScheme>
@@ -256,7 +306,10 @@ impl<C: ChainSigning, KeyStore: KeyStoreAPI<C>> MultisigClientApi<C::CryptoSchem
 		// The epoch the key will be associated with if successful.
 		epoch_index: EpochIndex,
 		participants: BTreeSet<AccountId>,
-	) -> BoxFuture<'_, Result<PublicKey<C>, (BTreeSet<AccountId>, KeygenFailureReason)>> {
+	) -> BoxFuture<
+		'_,
+		Result<PublicKey<C>, (BTreeSet<AccountId>, KeygenFailureReason, BroadcastConflicts)>,
+	> {
 		assert!(participants.contains(&self.my_account_id));
 		let span =
 			info_span!("Keygen Ceremony", ceremony_id = ceremony_id_string::<C>(ceremony_id));
@@ -279,7 +332,10 @@ impl<C: ChainSigning, KeyStore: KeyStoreAPI<C>> MultisigClientApi<C::CryptoSchem
 		epoch_index: EpochIndex,
 		sharing_participants: BTreeSet<AccountId>,
 		receiving_participants: BTreeSet<AccountId>,
-	) -> BoxFuture<'_, Result<PublicKey<C>, (BTreeSet<AccountId>, KeygenFailureReason)>> {
+	) -> BoxFuture<
+		'_,
+		Result<PublicKey<C>, (BTreeSet<AccountId>, KeygenFailureReason, BroadcastConflicts)>,
+	> {
 		let span =
 			info_span!("Key Handover Ceremony", ceremony_id = ceremony_id_string::<C>(ceremony_id));
 		let _entered = span.enter();
@@ -317,12 +373,119 @@ impl<C: ChainSigning, KeyStore: KeyStoreAPI<C>> MultisigClientApi<C::CryptoSchem
 		.boxed()
 	}
 
+	fn initiate_share_refresh(
+		&self,
+		ceremony_id: CeremonyId,
+		key_id: KeyId,
+		participants: BTreeSet<AccountId>,
+	) -> BoxFuture<
+		'_,
+		Result<PublicKey<C>, (BTreeSet<AccountId>, KeygenFailureReason, BroadcastConflicts)>,
+	> {
+		assert!(participants.contains(&self.my_account_id));
+		let span = info_span!(
+			"Share Refresh Ceremony",
+			ceremony_id = ceremony_id_string::<C>(ceremony_id)
+		);
+		let _entered = span.enter();
+
+		debug!(
+			key_id = key_id.to_string(),
+			participants = format_iterator(&participants).to_string(),
+			"Received a share refresh request",
+		);
+
+		let Some(key) = self.key_store.lock().unwrap().get_key(&key_id) else {
+			// We can't contribute to the refresh without our share of the key
+			self.update_latest_ceremony_id(ceremony_id);
+			let reported_parties = Default::default();
+			let failure_reason = KeygenFailureReason::UnknownKey;
+			failure_reason.log(&reported_parties);
+			return futures::future::ready(Err((
+				reported_parties,
+				failure_reason,
+				Default::default(),
+			)))
+			.boxed()
+		};
+
+		if participants.iter().any(|id| key.validator_mapping.get_idx(id).is_none()) {
+			self.update_latest_ceremony_id(ceremony_id);
+			let reported_parties = Default::default();
+			let failure_reason = KeygenFailureReason::InvalidParticipants;
+			failure_reason.log(&reported_parties);
+			return futures::future::ready(Err((
+				reported_parties,
+				failure_reason,
+				Default::default(),
+			)))
+			.boxed()
+		}
+
+		// All participants share their existing key shares with themselves, which results in new
+		// shares of the same key.
+		let resharing_context =
+			ResharingContext::from_key(&key, &self.my_account_id, &participants, &participants);
+
+		let refresh_result_future =
+			self.request_keygen_ceremony(ceremony_id, participants, Some(resharing_context));
+
+		async move {
+			let keygen_result_info = refresh_result_future.await?;
+			let agg_key = keygen_result_info.key.get_agg_public_key();
+
+			if KeyId::new(key_id.epoch_index(), agg_key.clone()) != key_id {
+				let reported_parties = Default::default();
+				let failure_reason = KeygenFailureReason::RefreshedKeyMismatch;
+				failure_reason.log(&reported_parties);
+				return Err((reported_parties, failure_reason, Default::default()))
+			}
+
+			// Kept in the key store (and persisted) until the refresh is completed, so that the
+			// refreshed share isn't lost if we restart before then.
+			self.key_store.lock().unwrap().set_pending_share_refresh(
+				ceremony_id,
+				key_id,
+				keygen_result_info,
+			);
+			Ok(agg_key)
+		}
+		.instrument(span.clone())
+		.boxed()
+	}
+
+	fn complete_share_refresh(&self, ceremony_id: CeremonyId, succeeded: bool) {
+		let Some((key_id, keygen_result_info)) =
+			self.key_store.lock().unwrap().take_pending_share_refresh(ceremony_id)
+		else {
+			if succeeded {
+				warn!(
+					ceremony_id = ceremony_id_string::<C>(ceremony_id),
+					"No refreshed key share to complete the share refresh with"
+				);
+			}
+			return
+		};
+
+		if succeeded {
+			info!(
+				ceremony_id = ceremony_id_string::<C>(ceremony_id),
+				key_id = key_id.to_string(),
+				"Replacing key share with the refreshed share"
+			);
+			self.key_store.lock().unwrap().set_key(key_id, keygen_result_info);
+		}
+	}
+
 	fn initiate_signing(
 		&self,
 		ceremony_id: CeremonyId,
 		signers: BTreeSet<AccountId>,
 		signing_info: Vec<(KeyId, SigningPayload<C>)>,
-	) -> BoxFuture<'_, Result<Vec<Signature<C>>, (BTreeSet<AccountId>, SigningFailureReason)>> {
+	) -> BoxFuture<
+		'_,
+		Result<Vec<Signature<C>>, (BTreeSet<AccountId>, SigningFailureReason, BroadcastConflicts)>,
+	> {
 		let span =
 			info_span!("Signing Ceremony", ceremony_id = ceremony_id_string::<C>(ceremony_id));
 		let _entered = span.enter();
@@ -365,10 +528,10 @@ impl<C: ChainSigning, KeyStore: KeyStoreAPI<C>> MultisigClientApi<C::CryptoSchem
 				result_receiver
 					.await
 					.expect("Signing result oneshot channel dropped before receiving a result")
-					.map_err(|(reported_parties, failure_reason)| {
+					.map_err(|(reported_parties, failure_reason, conflicts)| {
 						failure_reason.log(&reported_parties);
 
-						(reported_parties, failure_reason)
+						(reported_parties, failure_reason, conflicts)
 					})
 			}
 			.instrument(span.clone())
@@ -379,7 +542,8 @@ impl<C: ChainSigning, KeyStore: KeyStoreAPI<C>> MultisigClientApi<C::CryptoSchem
 			let reported_parties = Default::default();
 			let failure_reason = SigningFailureReason::UnknownKey;
 			failure_reason.log(&reported_parties);
-			futures::future::ready(Err((reported_parties, failure_reason))).boxed()
+			futures::future::ready(Err((reported_parties, failure_reason, Default::default())))
+				.boxed()
 		}
 	}
 
@@ -45,7 +45,7 @@ async fn should_ignore_rts_for_unknown_key() {
 	);
 
 	// Check that the signing request fails immediately with an "unknown key" error
-	let (_, failure_reason) = assert_err!(assert_future_can_complete(signing_request_fut));
+	let (_, failure_reason, _) = assert_err!(assert_future_can_complete(signing_request_fut));
 	assert_eq!(failure_reason, SigningFailureReason::UnknownKey);
 	assert!(matches!(
 		assert_ok!(assert_future_can_complete(ceremony_request_receiver.recv())),
@@ -104,3 +104,83 @@ async fn should_save_key_after_keygen() {
 	// Complete the keygen request
 	assert_ok!(keygen_request_fut.await);
 }
+
+async fn run_share_refresh_and_complete(succeeded: bool) {
+	const REFRESH_CEREMONY_ID: CeremonyId = DEFAULT_KEYGEN_CEREMONY_ID + 1;
+
+	let (public_key, keygen_result_info) = {
+		let (public_key, key_data) =
+			helpers::run_keygen(new_nodes(ACCOUNT_IDS.clone()), DEFAULT_KEYGEN_CEREMONY_ID).await;
+		(public_key, key_data.into_iter().next().unwrap().1)
+	};
+	let key_id = KeyId::new(GENESIS_EPOCH, public_key.clone());
+
+	// The refreshed key share is persisted as pending, and must only replace the key once the
+	// refresh is completed successfully
+	let mut mock_key_store = MockKeyStoreAPI::<EthSigning>::new();
+	mock_key_store
+		.expect_get_key()
+		.with(predicate::eq(key_id.clone()))
+		.once()
+		.return_const(Some(keygen_result_info.clone()));
+	mock_key_store
+		.expect_set_pending_share_refresh()
+		.with(
+			predicate::eq(REFRESH_CEREMONY_ID),
+			predicate::eq(key_id.clone()),
+			predicate::eq(keygen_result_info.clone()),
+		)
+		.once()
+		.returning(|_, _, _| ());
+	mock_key_store
+		.expect_take_pending_share_refresh()
+		.with(predicate::eq(REFRESH_CEREMONY_ID))
+		.once()
+		.return_const(Some((key_id.clone(), keygen_result_info.clone())));
+	mock_key_store
+		.expect_set_key()
+		.with(predicate::eq(key_id.clone()), predicate::eq(keygen_result_info.clone()))
+		.times(if succeeded { 1 } else { 0 })
+		.returning(|_, _| ());
+
+	let (ceremony_request_sender, mut ceremony_request_receiver) =
+		tokio::sync::mpsc::unbounded_channel();
+	let client = MultisigClient::<EthSigning, _>::new(
+		ACCOUNT_IDS[0].clone(),
+		mock_key_store,
+		ceremony_request_sender,
+	);
+
+	let refresh_request_fut = client.initiate_share_refresh(
+		REFRESH_CEREMONY_ID,
+		key_id,
+		BTreeSet::from_iter(ACCOUNT_IDS.iter().cloned()),
+	);
+
+	// The refresh is run as a resharing ceremony between the same participants
+	let request = ceremony_request_receiver.recv().await.unwrap();
+	assert_eq!(request.ceremony_id, REFRESH_CEREMONY_ID);
+	match request.details.unwrap() {
+		CeremonyRequestDetails::Keygen(details) => {
+			assert!(details.resharing_context.is_some());
+			details.result_sender.send(Ok(keygen_result_info)).unwrap();
+		},
+		_ => {
+			panic!("Unexpected ceremony request");
+		},
+	}
+
+	assert_eq!(assert_ok!(refresh_request_fut.await), public_key);
+
+	client.complete_share_refresh(REFRESH_CEREMONY_ID, succeeded);
+}
+
+#[tokio::test]
+async fn should_save_refreshed_key_share_after_refresh_succeeds() {
+	run_share_refresh_and_complete(true).await;
+}
+
+#[tokio::test]
+async fn should_discard_refreshed_key_share_after_refresh_fails() {
+	run_share_refresh_and_complete(false).await;
+}
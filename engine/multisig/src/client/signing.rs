@@ -1,3 +1,4 @@
+mod preprocessing;
 mod signing_data;
 mod signing_detail;
 mod signing_stages;
@@ -12,7 +13,13 @@ use crate::CryptoScheme;
 use super::common::KeygenResult;
 
 pub use signing_data::{
-	Comm1, LocalSig3, LocalSig3Inner, SigningCommitment, SigningData, VerifyComm2, VerifyLocalSig4,
+	Comm1, Comm1Inner, LocalSig3, LocalSig3Inner, PreprocessedSig, SigningCommitment, SigningData,
+	VerifyComm2, VerifyLocalSig4,
+};
+
+pub use preprocessing::{
+	max_preprocessing_nonces, PreprocessedNonceId, PreprocessedNoncePool,
+	SharedPreprocessedNoncePool, MAX_PREPROCESSED_NONCES, PREPROCESSED_NONCES_PER_CEREMONY,
 };
 
 pub use signing_detail::generate_schnorr_response;
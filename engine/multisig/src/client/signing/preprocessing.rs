@@ -0,0 +1,300 @@
+//! Nonces that are generated and verified ahead of time, so that a signing ceremony can complete
+//! after its first broadcast and the verification of that broadcast, rather than after four stages
+//! (see "Preprocess" in Section 5.3 of <https://eprint.iacr.org/2020/852.pdf>).
+//!
+//! This is not a single round: the pools are kept by each party and aren't guaranteed to be in
+//! sync, so the signature shares are only used once the broadcast verification has shown that all
+//! parties used the same nonces.
+//!
+//! Rather than running separate preprocessing ceremonies, every signing ceremony also broadcasts
+//! commitments to as many extra nonces as it signs payloads (up to a limit), to top up the pool.
+//! Once their broadcast has been verified, every party adds them to its pool for the ceremony's set
+//! of signers. A later ceremony with the same signers takes its nonces from the pool and sends its
+//! signature shares straight away, and uses them once the broadcast of all shares has been
+//! verified.
+//!
+//! A nonce is removed from the pool as soon as it is taken, whether or not the ceremony that took
+//! it succeeds, so it is never used twice. The pool is only kept in memory, so it is empty after a
+//! restart. When the parties find that they didn't use the same preprocessed nonces, they fall back
+//! to the full ceremony and discard the nonces that are out of sync.
+
+use std::{
+	collections::{BTreeMap, BTreeSet},
+	sync::{Arc, Mutex},
+};
+
+use cf_primitives::{CeremonyId, MAX_SIGNING_BATCH_SIZE};
+use serde::{Deserialize, Serialize};
+use state_chain_runtime::AccountId;
+use zeroize::Zeroize;
+
+use crate::crypto::CryptoScheme;
+
+use super::{signing_detail::SecretNoncePair, SigningCommitment};
+
+/// The maximum number of preprocessed nonces kept for a set of signers.
+pub const MAX_PREPROCESSED_NONCES: usize = 32;
+
+/// The maximum number of preprocessed nonces generated by a single ceremony.
+pub const PREPROCESSED_NONCES_PER_CEREMONY: usize = MAX_SIGNING_BATCH_SIZE as usize;
+
+/// The maximum number of preprocessed nonces generated by a ceremony signing this many payloads.
+/// Generating as many as the ceremony could have used keeps the pool topped up for ceremonies of
+/// the same size, while limiting the extra data in the first broadcast of every ceremony.
+pub const fn max_preprocessing_nonces(payload_count: usize) -> usize {
+	if payload_count < PREPROCESSED_NONCES_PER_CEREMONY {
+		payload_count
+	} else {
+		PREPROCESSED_NONCES_PER_CEREMONY
+	}
+}
+
+/// The maximum number of signer sets we keep preprocessed nonces for. Sets that haven't been
+/// replenished for the longest are dropped first.
+const MAX_SIGNER_SETS: usize = 8;
+
+pub type SharedPreprocessedNoncePool<C> = Arc<Mutex<PreprocessedNoncePool<C>>>;
+
+/// Identifies a preprocessed nonce by the ceremony that generated it and its position in the
+/// batch generated by that ceremony.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct PreprocessedNonceId {
+	pub ceremony_id: CeremonyId,
+	pub index: u32,
+}
+
+/// Our secret nonce pair, together with the commitments of all signers for the same id.
+pub struct PreprocessedNonce<C: CryptoScheme> {
+	pub id: PreprocessedNonceId,
+	pub commitments: BTreeMap<AccountId, SigningCommitment<C::Point>>,
+	pub own_nonce: Box<SecretNoncePair<C::Point>>,
+}
+
+impl<C: CryptoScheme> Drop for PreprocessedNonce<C> {
+	fn drop(&mut self) {
+		self.own_nonce.zeroize();
+	}
+}
+
+struct SignerSetNonces<C: CryptoScheme> {
+	nonces: BTreeMap<PreprocessedNonceId, PreprocessedNonce<C>>,
+	/// Nonces from ceremonies before this one have been discarded and must not be added again.
+	discarded_before: CeremonyId,
+	last_replenished: CeremonyId,
+}
+
+pub struct PreprocessedNoncePool<C: CryptoScheme> {
+	signer_sets: BTreeMap<BTreeSet<AccountId>, SignerSetNonces<C>>,
+}
+
+impl<C: CryptoScheme> Default for PreprocessedNoncePool<C> {
+	fn default() -> Self {
+		Self { signer_sets: Default::default() }
+	}
+}
+
+impl<C: CryptoScheme> PreprocessedNoncePool<C> {
+	/// Removes and returns the oldest `count` nonces for the signers, or `None` if there are not
+	/// enough of them.
+	pub fn take(
+		&mut self,
+		signers: &BTreeSet<AccountId>,
+		count: usize,
+	) -> Option<Vec<PreprocessedNonce<C>>> {
+		let signer_set = self.signer_sets.get_mut(signers)?;
+
+		if count == 0 || signer_set.nonces.len() < count {
+			return None
+		}
+
+		Some(
+			(0..count)
+				.map(|_| signer_set.nonces.pop_first().expect("checked the length above").1)
+				.collect(),
+		)
+	}
+
+	/// The number of new nonces that a ceremony with these signers, signing this many payloads,
+	/// should generate.
+	pub fn replenish_count(&self, signers: &BTreeSet<AccountId>, payload_count: usize) -> usize {
+		let held = self.signer_sets.get(signers).map_or(0, |signer_set| signer_set.nonces.len());
+
+		MAX_PREPROCESSED_NONCES
+			.saturating_sub(held)
+			.min(max_preprocessing_nonces(payload_count))
+	}
+
+	/// Adds nonces generated by ceremony `ceremony_id`, whose commitments have been verified to be
+	/// broadcast consistently.
+	pub fn add(
+		&mut self,
+		signers: BTreeSet<AccountId>,
+		ceremony_id: CeremonyId,
+		nonces: Vec<PreprocessedNonce<C>>,
+	) {
+		if !self.signer_sets.contains_key(&signers) && self.signer_sets.len() >= MAX_SIGNER_SETS {
+			if let Some(stale_signers) = self
+				.signer_sets
+				.iter()
+				.min_by_key(|(_, signer_set)| signer_set.last_replenished)
+				.map(|(signers, _)| signers.clone())
+			{
+				self.signer_sets.remove(&stale_signers);
+			}
+		}
+
+		let signer_set = self.signer_sets.entry(signers).or_insert_with(|| SignerSetNonces {
+			nonces: Default::default(),
+			discarded_before: 0,
+			last_replenished: ceremony_id,
+		});
+		signer_set.last_replenished = signer_set.last_replenished.max(ceremony_id);
+
+		for nonce in nonces {
+			if signer_set.nonces.len() < MAX_PREPROCESSED_NONCES &&
+				nonce.id.ceremony_id >= signer_set.discarded_before
+			{
+				signer_set.nonces.insert(nonce.id, nonce);
+			}
+		}
+	}
+
+	/// Discards all nonces for the signers that were generated before ceremony `ceremony_id`,
+	/// because they are out of sync with other parties' pools.
+	pub fn discard_before(&mut self, signers: &BTreeSet<AccountId>, ceremony_id: CeremonyId) {
+		if let Some(signer_set) = self.signer_sets.get_mut(signers) {
+			signer_set.nonces.retain(|id, _| id.ceremony_id >= ceremony_id);
+			signer_set.discarded_before = signer_set.discarded_before.max(ceremony_id);
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::{
+		crypto::{ECPoint, Rng},
+		eth::EvmCryptoScheme,
+	};
+	use rand::SeedableRng;
+
+	fn signer_set(ids: impl IntoIterator<Item = u8>) -> BTreeSet<AccountId> {
+		ids.into_iter().map(|i| AccountId::new([i; 32])).collect()
+	}
+
+	fn gen_nonces(
+		rng: &mut Rng,
+		signers: &BTreeSet<AccountId>,
+		ceremony_id: CeremonyId,
+		count: u32,
+	) -> Vec<PreprocessedNonce<EvmCryptoScheme>> {
+		(0..count)
+			.map(|index| PreprocessedNonce {
+				id: PreprocessedNonceId { ceremony_id, index },
+				commitments: signers
+					.iter()
+					.map(|account_id| {
+						(
+							account_id.clone(),
+							SigningCommitment { d: ECPoint::random(rng), e: ECPoint::random(rng) },
+						)
+					})
+					.collect(),
+				own_nonce: SecretNoncePair::sample_random(rng),
+			})
+			.collect()
+	}
+
+	fn ids(nonces: &[PreprocessedNonce<EvmCryptoScheme>]) -> Vec<PreprocessedNonceId> {
+		nonces.iter().map(|nonce| nonce.id).collect()
+	}
+
+	#[test]
+	fn nonces_are_taken_oldest_first_and_only_once() {
+		let mut rng = Rng::from_seed([0; 32]);
+		let signers = signer_set([1, 2, 3]);
+		let mut pool = PreprocessedNoncePool::<EvmCryptoScheme>::default();
+
+		pool.add(signers.clone(), 2, gen_nonces(&mut rng, &signers, 2, 2));
+		pool.add(signers.clone(), 1, gen_nonces(&mut rng, &signers, 1, 1));
+
+		// Only another set of signers, or too many payloads
+		assert!(pool.take(&signer_set([1, 2]), 1).is_none());
+		assert!(pool.take(&signers, 4).is_none());
+
+		assert_eq!(
+			ids(&pool.take(&signers, 2).unwrap()),
+			vec![
+				PreprocessedNonceId { ceremony_id: 1, index: 0 },
+				PreprocessedNonceId { ceremony_id: 2, index: 0 }
+			]
+		);
+		assert_eq!(
+			ids(&pool.take(&signers, 1).unwrap()),
+			vec![PreprocessedNonceId { ceremony_id: 2, index: 1 }]
+		);
+		assert!(pool.take(&signers, 1).is_none());
+	}
+
+	#[test]
+	fn pool_size_is_limited() {
+		let mut rng = Rng::from_seed([0; 32]);
+		let signers = signer_set([1, 2, 3]);
+		let mut pool = PreprocessedNoncePool::<EvmCryptoScheme>::default();
+
+		assert_eq!(pool.replenish_count(&signers, 1), 1);
+		assert_eq!(
+			pool.replenish_count(&signers, MAX_PREPROCESSED_NONCES),
+			PREPROCESSED_NONCES_PER_CEREMONY
+		);
+
+		for ceremony_id in 1..=(MAX_PREPROCESSED_NONCES / PREPROCESSED_NONCES_PER_CEREMONY + 1) {
+			pool.add(
+				signers.clone(),
+				ceremony_id as CeremonyId,
+				gen_nonces(
+					&mut rng,
+					&signers,
+					ceremony_id as CeremonyId,
+					PREPROCESSED_NONCES_PER_CEREMONY as u32,
+				),
+			);
+		}
+
+		assert_eq!(pool.replenish_count(&signers, PREPROCESSED_NONCES_PER_CEREMONY), 0);
+		assert_eq!(pool.signer_sets[&signers].nonces.len(), MAX_PREPROCESSED_NONCES);
+
+		for i in 0..=MAX_SIGNER_SETS as u8 {
+			let signers = signer_set([i, 100]);
+			pool.add(signers.clone(), i as CeremonyId + 10, gen_nonces(&mut rng, &signers, 10, 1));
+		}
+
+		// The signer set that was replenished the longest ago has been dropped
+		assert_eq!(pool.signer_sets.len(), MAX_SIGNER_SETS);
+		assert!(pool.take(&signers, 1).is_none());
+		assert!(pool.take(&signer_set([0, 100]), 1).is_none());
+		assert!(pool.take(&signer_set([1, 100]), 1).is_some());
+	}
+
+	#[test]
+	fn discarded_nonces_are_not_added_again() {
+		let mut rng = Rng::from_seed([0; 32]);
+		let signers = signer_set([1, 2, 3]);
+		let mut pool = PreprocessedNoncePool::<EvmCryptoScheme>::default();
+
+		pool.add(signers.clone(), 1, gen_nonces(&mut rng, &signers, 1, 2));
+		pool.add(signers.clone(), 3, gen_nonces(&mut rng, &signers, 3, 1));
+
+		pool.discard_before(&signers, 3);
+
+		// Nonces from a ceremony that was verified late, after the pools were found to be out of
+		// sync, are ignored
+		pool.add(signers.clone(), 2, gen_nonces(&mut rng, &signers, 2, 1));
+
+		assert_eq!(
+			ids(&pool.take(&signers, 1).unwrap()),
+			vec![PreprocessedNonceId { ceremony_id: 3, index: 0 }]
+		);
+		assert!(pool.take(&signers, 1).is_none());
+	}
+}
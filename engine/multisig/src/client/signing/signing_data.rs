@@ -1,17 +1,21 @@
 use std::fmt::Display;
 
-use cf_primitives::AuthorityCount;
+use cf_primitives::{AuthorityCount, MAX_SIGNING_BATCH_SIZE};
 use serde::{Deserialize, Serialize};
 
 use crate::{
 	client::common::{
 		BroadcastVerificationMessage, DelayDeserialization, PreProcessStageDataCheck,
-		SigningStageName,
+		SignedBroadcast, SigningStageName,
 	},
 	crypto::{ECPoint, MAX_POINT_SIZE, MAX_SCALAR_SIZE},
 	ChainSigning, ChainTag, MAX_BTC_SIGNING_PAYLOADS,
 };
 
+use super::preprocessing::{
+	max_preprocessing_nonces, PreprocessedNonceId, MAX_PREPROCESSED_NONCES,
+};
+
 #[cfg(test)]
 pub use tests::{gen_signing_data_stage1, gen_signing_data_stage2, gen_signing_data_stage4};
 
@@ -27,7 +31,25 @@ pub struct SigningCommitment<P: ECPoint> {
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
-pub struct Comm1Inner<P: ECPoint>(#[serde(bound = "")] pub Vec<SigningCommitment<P>>);
+pub struct Comm1Inner<P: ECPoint> {
+	/// Commitments to fresh nonces, one per payload, used if the ceremony can't be completed with
+	/// preprocessed nonces
+	#[serde(bound = "")]
+	pub commitments: Vec<SigningCommitment<P>>,
+	/// Commitments to nonces that will be added to the preprocessed nonce pool
+	#[serde(bound = "")]
+	pub preprocessing_commitments: Vec<SigningCommitment<P>>,
+	/// Signature responses generated with preprocessed nonces
+	#[serde(bound = "")]
+	pub preprocessed_sig: Option<PreprocessedSig<P>>,
+}
+
+/// Signature responses (one per payload) generated with the preprocessed nonces with the given ids
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub struct PreprocessedSig<P: ECPoint> {
+	pub nonce_ids: Vec<PreprocessedNonceId>,
+	pub responses: Vec<P::Scalar>,
+}
 
 /// Calculate the size limit of the signing commitments. This scales with the number of payloads in
 /// the ceremony.
@@ -36,6 +58,24 @@ const fn max_signing_commitments_size(number_of_payloads: usize) -> usize {
 	2 * MAX_POINT_SIZE * number_of_payloads + 8
 }
 
+/// Calculate the size limit of the stage 1 message. This scales with the number of payloads in the
+/// ceremony.
+const fn max_comm1_size(number_of_payloads: usize) -> usize {
+	// Preprocessed nonces are only used if there are enough of them for all payloads
+	let max_preprocessed_sig_size = if number_of_payloads <= MAX_PREPROCESSED_NONCES {
+		// (ceremony id + index) * payloads + length of vector, and 1 scalar * payloads + length of
+		// vector
+		(8 + 4) * number_of_payloads + 8 + MAX_SCALAR_SIZE * number_of_payloads + 8
+	} else {
+		0
+	};
+
+	max_signing_commitments_size(number_of_payloads) +
+		max_signing_commitments_size(max_preprocessing_nonces(number_of_payloads)) +
+		// Option tag
+		1 + max_preprocessed_sig_size
+}
+
 /// Calculate the size limit of the local sigs. This scales with the number of payloads in the
 /// ceremony.
 fn max_local_sigs_size(number_of_payloads: usize) -> usize {
@@ -57,13 +97,13 @@ mod serialisation {
 		let comm1 = helpers::gen_dummy_signing_comm1::<
 			<<Chain as ChainSigning>::CryptoScheme as CryptoScheme>::Point,
 		>(&mut rng, 1);
-		if matches!(<Chain as ChainSigning>::CHAIN_TAG, ChainTag::Ethereum) {
+		if matches!(<Chain as ChainSigning>::CHAIN_TAG, ChainTag::Ethereum | ChainTag::Arbitrum) {
 			// The constants are defined as to exactly match Ethereum/secp256k1,
 			// which we demonstrate here:
-			assert!(comm1.payload.len() == max_signing_commitments_size(1));
+			assert!(comm1.value.payload.len() == max_comm1_size(1));
 		} else {
 			// Other chains might use a more compact serialization of primitives:
-			assert!(comm1.payload.len() <= max_signing_commitments_size(1));
+			assert!(comm1.value.payload.len() <= max_comm1_size(1));
 		}
 	}
 
@@ -78,13 +118,13 @@ mod serialisation {
 			<<Chain as ChainSigning>::CryptoScheme as CryptoScheme>::Point,
 		>(&mut rng, 1);
 
-		if matches!(<Chain as ChainSigning>::CHAIN_TAG, ChainTag::Ethereum) {
+		if matches!(<Chain as ChainSigning>::CHAIN_TAG, ChainTag::Ethereum | ChainTag::Arbitrum) {
 			// The constants are defined as to exactly match Ethereum/secp256k1,
 			// which we demonstrate here:
-			assert!(sig.payload.len() == max_local_sigs_size(1));
+			assert!(sig.value.payload.len() == max_local_sigs_size(1));
 		} else {
 			// Other chains might use a more compact serialization of primitives:
-			assert!(sig.payload.len() <= max_local_sigs_size(1));
+			assert!(sig.value.payload.len() <= max_local_sigs_size(1));
 		}
 	}
 
@@ -96,10 +136,10 @@ mod serialisation {
 
 pub type Comm1<P> = DelayDeserialization<Comm1Inner<P>>;
 
-pub type VerifyComm2<P> = BroadcastVerificationMessage<Comm1<P>>;
+pub type VerifyComm2<P> = BroadcastVerificationMessage<SignedBroadcast<Comm1<P>>>;
 
 pub type LocalSig3<P> = DelayDeserialization<LocalSig3Inner<P>>;
-pub type VerifyLocalSig4<P> = BroadcastVerificationMessage<LocalSig3<P>>;
+pub type VerifyLocalSig4<P> = BroadcastVerificationMessage<SignedBroadcast<LocalSig3<P>>>;
 
 /// Signature (the "response" part) shard generated by a single party
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
@@ -112,18 +152,18 @@ pub struct LocalSig3Inner<P: ECPoint> {
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
 pub enum SigningData<P: ECPoint> {
 	#[serde(bound = "")]
-	CommStage1(Comm1<P>),
+	CommStage1(SignedBroadcast<Comm1<P>>),
 	#[serde(bound = "")]
 	BroadcastVerificationStage2(VerifyComm2<P>),
 	#[serde(bound = "")]
-	LocalSigStage3(LocalSig3<P>),
+	LocalSigStage3(SignedBroadcast<LocalSig3<P>>),
 	#[serde(bound = "")]
 	VerifyLocalSigsStage4(VerifyLocalSig4<P>),
 }
 
-derive_impls_for_enum_variants!(impl<P: ECPoint> for Comm1<P>, SigningData::CommStage1, SigningData<P>);
+derive_impls_for_enum_variants!(impl<P: ECPoint> for SignedBroadcast<Comm1<P>>, SigningData::CommStage1, SigningData<P>);
 derive_impls_for_enum_variants!(impl<P: ECPoint> for VerifyComm2<P>, SigningData::BroadcastVerificationStage2, SigningData<P>);
-derive_impls_for_enum_variants!(impl<P: ECPoint> for LocalSig3<P>, SigningData::LocalSigStage3, SigningData<P>);
+derive_impls_for_enum_variants!(impl<P: ECPoint> for SignedBroadcast<LocalSig3<P>>, SigningData::LocalSigStage3, SigningData<P>);
 derive_impls_for_enum_variants!(impl<P: ECPoint> for VerifyLocalSig4<P>, SigningData::VerifyLocalSigsStage4, SigningData<P>);
 
 derive_display_as_type_name!(Comm1<P: ECPoint>);
@@ -154,12 +194,10 @@ impl<P: ECPoint> PreProcessStageDataCheck<SigningStageName> for SigningData<P> {
 			SigningData::CommStage1(_) => self.is_initial_stage_data_size_valid::<Chain>(),
 			// It is safe to unwrap after the first stage because the number of payloads is always
 			// known from then on (only for signing ceremonies)
-			SigningData::BroadcastVerificationStage2(message) => message.is_data_size_valid(
-				num_of_parties,
-				max_signing_commitments_size(num_of_payloads.unwrap()),
-			),
+			SigningData::BroadcastVerificationStage2(message) =>
+				message.is_data_size_valid(num_of_parties, max_comm1_size(num_of_payloads.unwrap())),
 			SigningData::LocalSigStage3(message) =>
-				message.payload.len() <= max_local_sigs_size(num_of_payloads.unwrap()),
+				message.value.payload.len() <= max_local_sigs_size(num_of_payloads.unwrap()),
 
 			SigningData::VerifyLocalSigsStage4(message) => message
 				.is_data_size_valid(num_of_parties, max_local_sigs_size(num_of_payloads.unwrap())),
@@ -169,11 +207,15 @@ impl<P: ECPoint> PreProcessStageDataCheck<SigningStageName> for SigningData<P> {
 	fn is_initial_stage_data_size_valid<Chain: ChainSigning>(&self) -> bool {
 		match self {
 			SigningData::CommStage1(message) => match Chain::CHAIN_TAG {
-				ChainTag::Ethereum | ChainTag::Polkadot | ChainTag::Ed25519 =>
-					message.payload.len() <= max_signing_commitments_size(1),
+				ChainTag::Ethereum |
+				ChainTag::Arbitrum |
+				ChainTag::Polkadot |
+				ChainTag::Ed25519 =>
+				// Requests may be signed in batches, so we use the maximum batch size
+					message.value.payload.len() <= max_comm1_size(MAX_SIGNING_BATCH_SIZE as usize),
 				ChainTag::Bitcoin =>
 				// At this stage we may not know the number of payloads, so we use a maximum
-					message.payload.len() <= max_signing_commitments_size(MAX_BTC_SIGNING_PAYLOADS),
+					message.value.payload.len() <= max_comm1_size(MAX_BTC_SIGNING_PAYLOADS),
 			},
 			_ => panic!("unexpected stage"),
 		}
@@ -267,10 +309,16 @@ mod tests {
 
 	#[test]
 	fn check_data_size_stage1() {
-		// Should only pass if the message contains exactly one commitment for ethereum and Polkadot
+		// Ethereum and Polkadot requests may be batched, so we limit them to the maximum batch size
+		const MAX_BATCH: u64 = MAX_SIGNING_BATCH_SIZE as u64;
 		assert!(gen_signing_data_stage1(1).is_initial_stage_data_size_valid::<EthSigning>());
-		assert!(!gen_signing_data_stage1(2).is_initial_stage_data_size_valid::<EthSigning>());
-		assert!(!gen_signing_data_stage1(2).is_initial_stage_data_size_valid::<PolkadotSigning>());
+		assert!(gen_signing_data_stage1(MAX_BATCH).is_initial_stage_data_size_valid::<EthSigning>());
+		assert!(!gen_signing_data_stage1(MAX_BATCH + 1)
+			.is_initial_stage_data_size_valid::<EthSigning>());
+		assert!(gen_signing_data_stage1(MAX_BATCH)
+			.is_initial_stage_data_size_valid::<PolkadotSigning>());
+		assert!(!gen_signing_data_stage1(MAX_BATCH + 1)
+			.is_initial_stage_data_size_valid::<PolkadotSigning>());
 
 		// Because we might not know the number of payloads yet, we limit btc to a constant
 		assert!(gen_signing_data_stage1(MAX_BTC_SIGNING_PAYLOADS as u64)
@@ -372,21 +420,40 @@ mod tests {
 	fn should_not_exceed_spam_limits() {
 		const MULTI_PAYLOAD_SPAM_LIMIT_BYTES: u64 = 100_000_000; // ~100mb
 		assert!(
-			max_signing_commitments_size(MAX_BTC_SIGNING_PAYLOADS) as u64 *
+			max_comm1_size(MAX_BTC_SIGNING_PAYLOADS) as u64 *
 				<BtcSigning as ChainSigning>::CEREMONY_ID_WINDOW <=
 				MULTI_PAYLOAD_SPAM_LIMIT_BYTES
 		);
 
-		const SINGLE_PAYLOAD_SPAM_LIMIT_BYTES: u64 = 500_000; // ~0.5mb
+		// A single payload message is 209 bytes: 74 for the fresh nonce commitments, 74 for the
+		// commitment that replenishes the preprocessed nonce it uses and 61 for the preprocessed
+		// signature. Over a window of 6000 ceremonies this is ~1.3mb, up from ~0.45mb for the fresh
+		// nonce commitments alone, which is the cost of not having to wait for a second broadcast.
+		assert_eq!(max_comm1_size(1), 209);
+		const SINGLE_PAYLOAD_SPAM_LIMIT_BYTES: u64 = 1_300_000; // ~1.3mb
 		assert!(
-			max_signing_commitments_size(1) as u64 *
-				<EthSigning as ChainSigning>::CEREMONY_ID_WINDOW <=
+			max_comm1_size(1) as u64 * <EthSigning as ChainSigning>::CEREMONY_ID_WINDOW <=
 				SINGLE_PAYLOAD_SPAM_LIMIT_BYTES
 		);
 		assert!(
-			max_signing_commitments_size(1) as u64 *
-				<PolkadotSigning as ChainSigning>::CEREMONY_ID_WINDOW <=
+			max_comm1_size(1) as u64 * <PolkadotSigning as ChainSigning>::CEREMONY_ID_WINDOW <=
 				SINGLE_PAYLOAD_SPAM_LIMIT_BYTES
 		);
+
+		// A message for a full batch of 4 payloads is 737 bytes: 272 for the fresh nonce
+		// commitments, 272 for the commitments that replenish the preprocessed nonces and 193 for
+		// the preprocessed signatures, so ~4.4mb over a window of 6000 ceremonies.
+		assert_eq!(max_comm1_size(MAX_SIGNING_BATCH_SIZE as usize), 737);
+		const BATCH_SPAM_LIMIT_BYTES: u64 = 4_500_000; // ~4.5mb
+		assert!(
+			max_comm1_size(MAX_SIGNING_BATCH_SIZE as usize) as u64 *
+				<EthSigning as ChainSigning>::CEREMONY_ID_WINDOW <=
+				BATCH_SPAM_LIMIT_BYTES
+		);
+		assert!(
+			max_comm1_size(MAX_SIGNING_BATCH_SIZE as usize) as u64 *
+				<PolkadotSigning as ChainSigning>::CEREMONY_ID_WINDOW <=
+				BATCH_SPAM_LIMIT_BYTES
+		);
 	}
 }
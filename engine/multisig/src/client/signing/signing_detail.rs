@@ -1,7 +1,9 @@
 //! The types and operations as discussed in <https://eprint.iacr.org/2020/852.pdf>.
 //! Comments in this file reference sections from this document.
 //! Note that unlike the protocol described in the document, we don't have a
-//! centralised signature aggregator and don't have a preprocessing stage.
+//! centralised signature aggregator and don't have a separate preprocessing stage
+//! (preprocessed nonces are generated by earlier signing ceremonies instead, see
+//! the `preprocessing` module).
 use std::collections::{BTreeMap, BTreeSet};
 
 use cf_primitives::AuthorityCount;
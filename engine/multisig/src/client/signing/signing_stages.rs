@@ -4,10 +4,13 @@ use crate::{
 	client::{
 		self,
 		ceremony_manager::SigningCeremony,
-		common::{try_deserialize, DelayDeserialization, SigningFailureReason, SigningStageName},
+		common::{
+			try_deserialize, DelayDeserialization, SignedBroadcast, SigningFailureReason,
+			SigningStageName,
+		},
 		signing::{self, signing_data::LocalSig3Inner, PayloadAndKey},
 	},
-	crypto::CryptoScheme,
+	crypto::{CryptoScheme, ECPoint},
 };
 
 use async_trait::async_trait;
@@ -18,6 +21,7 @@ use client::common::{
 	},
 	CeremonyCommon, StageResult,
 };
+use state_chain_runtime::AccountId;
 
 use signing::signing_detail::{self, SecretNoncePair};
 
@@ -26,7 +30,11 @@ use signing_detail::get_lagrange_coeff;
 use tracing::{debug, warn};
 
 use super::{
-	signing_data::{Comm1, LocalSig3, VerifyComm2, VerifyLocalSig4},
+	preprocessing::{
+		max_preprocessing_nonces, PreprocessedNonce, PreprocessedNonceId,
+		SharedPreprocessedNoncePool,
+	},
+	signing_data::{Comm1, Comm1Inner, LocalSig3, PreprocessedSig, VerifyComm2, VerifyLocalSig4},
 	signing_detail::{NonceBinding, SchnorrCommitment},
 	SigningCommitment,
 };
@@ -36,7 +44,10 @@ type SigningStageResult<Crypto> = StageResult<SigningCeremony<Crypto>>;
 // *********** Await Commitments1 *************
 
 /// Stage 1: Generate an broadcast our secret nonce pair
-/// and collect those from all other parties
+/// and collect those from all other parties. If we have
+/// preprocessed nonces, also broadcast our signature responses
+/// generated with them, which lets the ceremony complete
+/// after the next stage if all other parties did the same.
 pub struct AwaitCommitments1<Crypto: CryptoScheme> {
 	common: CeremonyCommon,
 	signing_common: SigningStateCommonInfo<Crypto>,
@@ -45,15 +56,50 @@ pub struct AwaitCommitments1<Crypto: CryptoScheme> {
 	// copies on the stack when the data is moved. We can probably
 	// remove `Box` now that the items are stored in Vec
 	nonces: Vec<Box<SecretNoncePair<Crypto::Point>>>,
+	// Nonces that are added to the preprocessed nonce pool once
+	// the broadcast of their commitments has been verified
+	preprocessing_nonces: Vec<Box<SecretNoncePair<Crypto::Point>>>,
+	// Preprocessed nonces taken from the pool for this ceremony,
+	// deleted as soon as our signature responses are generated
+	preprocessed_nonces: Option<Vec<PreprocessedNonce<Crypto>>>,
+	preprocessed: Option<PreprocessedSignatureData<Crypto>>,
+	preprocessed_nonce_pool: SharedPreprocessedNoncePool<Crypto>,
 }
 
 impl<Crypto: CryptoScheme> AwaitCommitments1<Crypto> {
-	pub fn new(mut common: CeremonyCommon, signing_common: SigningStateCommonInfo<Crypto>) -> Self {
+	pub fn new(
+		mut common: CeremonyCommon,
+		signing_common: SigningStateCommonInfo<Crypto>,
+		preprocessed_nonce_pool: SharedPreprocessedNoncePool<Crypto>,
+	) -> Self {
 		let nonces = (0..signing_common.payload_count())
 			.map(|_| SecretNoncePair::sample_random(&mut common.rng))
 			.collect();
 
-		AwaitCommitments1 { common, signing_common, nonces }
+		// Nonces are taken from the pool when the ceremony is created, which happens in the
+		// same (ceremony id) order for all parties
+		let signers = signer_account_ids(&common);
+		let (preprocessed_nonces, preprocessing_nonce_count) = {
+			let mut pool = preprocessed_nonce_pool.lock().unwrap();
+			(
+				pool.take(&signers, signing_common.payload_count()),
+				pool.replenish_count(&signers, signing_common.payload_count()),
+			)
+		};
+
+		let preprocessing_nonces = (0..preprocessing_nonce_count)
+			.map(|_| SecretNoncePair::sample_random(&mut common.rng))
+			.collect();
+
+		AwaitCommitments1 {
+			common,
+			signing_common,
+			nonces,
+			preprocessing_nonces,
+			preprocessed_nonces,
+			preprocessed: None,
+			preprocessed_nonce_pool,
+		}
 	}
 }
 
@@ -63,28 +109,80 @@ derive_display_as_type_name!(AwaitCommitments1<Crypto: CryptoScheme>);
 impl<Crypto: CryptoScheme> BroadcastStageProcessor<SigningCeremony<Crypto>>
 	for AwaitCommitments1<Crypto>
 {
-	type Message = Comm1<Crypto::Point>;
+	type Message = SignedBroadcast<Comm1<Crypto::Point>>;
 	const NAME: SigningStageName = SigningStageName::AwaitCommitments1;
 
 	fn init(&mut self) -> DataToSend<Self::Message> {
-		let comm1: Vec<_> = self
-			.nonces
-			.iter()
-			.map(|nonce| SigningCommitment::<Crypto::Point> { d: nonce.d_pub, e: nonce.e_pub })
-			.collect();
-		DataToSend::Broadcast(DelayDeserialization::new(&comm1))
+		let to_commitments = |nonces: &Vec<Box<SecretNoncePair<Crypto::Point>>>| -> Vec<_> {
+			nonces
+				.iter()
+				.map(|nonce| SigningCommitment::<Crypto::Point> { d: nonce.d_pub, e: nonce.e_pub })
+				.collect()
+		};
+
+		// The preprocessed nonces are deleted at the end of this block, so they are never
+		// used again (even if this ceremony fails)
+		let preprocessed_sig = self.preprocessed_nonces.take().map(|preprocessed_nonces| {
+			let commitments = preprocessed_nonces
+				.iter()
+				.map(|nonce| {
+					self.common
+						.all_idxs
+						.iter()
+						.map(|idx| {
+							// The pool holds commitments from all signers
+							(
+								*idx,
+								nonce.commitments[self.common.validator_mapping.get_id(*idx)]
+									.clone(),
+							)
+						})
+						.collect()
+				})
+				.collect();
+
+			let signature_data =
+				derive_signature_data(&self.signing_common, &self.common.all_idxs, commitments);
+
+			let responses = generate_responses(
+				&self.common,
+				&self.signing_common,
+				&preprocessed_nonces.iter().map(|nonce| &*nonce.own_nonce).collect::<Vec<_>>(),
+				&signature_data,
+			);
+
+			let nonce_ids: Vec<_> = preprocessed_nonces.iter().map(|nonce| nonce.id).collect();
+
+			self.preprocessed =
+				Some(PreprocessedSignatureData { nonce_ids: nonce_ids.clone(), signature_data });
+
+			PreprocessedSig { nonce_ids, responses }
+		});
+
+		DataToSend::Broadcast(self.common.sign_broadcast(
+			SigningStageName::VerifyCommitmentsBroadcast2.stage_number(),
+			DelayDeserialization::new(&Comm1Inner::<Crypto::Point> {
+				commitments: to_commitments(&self.nonces),
+				preprocessing_commitments: to_commitments(&self.preprocessing_nonces),
+				preprocessed_sig,
+			}),
+		))
 	}
 
 	async fn process(
 		self,
 		messages: BTreeMap<AuthorityCount, Option<Self::Message>>,
 	) -> SigningStageResult<Crypto> {
-		// No verification is necessary here, just generating new stage
-
+		// Even if all parties used the same preprocessed nonces as us, the ceremony can only
+		// complete once the broadcast of their signature responses has been verified, which is
+		// also when the nonces generated by this ceremony are added to the pool.
 		let processor = VerifyCommitmentsBroadcast2::<Crypto> {
 			common: self.common.clone(),
 			signing_common: self.signing_common,
 			nonces: self.nonces,
+			preprocessing_nonces: self.preprocessing_nonces,
+			preprocessed: self.preprocessed,
+			preprocessed_nonce_pool: self.preprocessed_nonce_pool,
 			commitments: messages,
 		};
 
@@ -102,8 +200,12 @@ struct VerifyCommitmentsBroadcast2<Crypto: CryptoScheme> {
 	signing_common: SigningStateCommonInfo<Crypto>,
 	// Our nonce pair generated in the previous stage
 	nonces: Vec<Box<SecretNoncePair<Crypto::Point>>>,
+	preprocessing_nonces: Vec<Box<SecretNoncePair<Crypto::Point>>>,
+	// Set if we generated signature responses with preprocessed nonces
+	preprocessed: Option<PreprocessedSignatureData<Crypto>>,
+	preprocessed_nonce_pool: SharedPreprocessedNoncePool<Crypto>,
 	// Public nonce commitments collected in the previous stage
-	commitments: BTreeMap<AuthorityCount, Option<Comm1<Crypto::Point>>>,
+	commitments: BTreeMap<AuthorityCount, Option<SignedBroadcast<Comm1<Crypto::Point>>>>,
 }
 
 derive_display_as_type_name!(VerifyCommitmentsBroadcast2<Crypto: CryptoScheme>);
@@ -115,6 +217,12 @@ pub struct DerivedSignatureData<C: CryptoScheme> {
 	bound_commitments: BTreeMap<AuthorityCount, SchnorrCommitment<C>>,
 }
 
+/// Data for signing with preprocessed nonces
+struct PreprocessedSignatureData<C: CryptoScheme> {
+	nonce_ids: Vec<PreprocessedNonceId>,
+	signature_data: Vec<DerivedSignatureData<C>>,
+}
+
 #[async_trait]
 impl<Crypto: CryptoScheme> BroadcastStageProcessor<SigningCeremony<Crypto>>
 	for VerifyCommitmentsBroadcast2<Crypto>
@@ -132,15 +240,16 @@ impl<Crypto: CryptoScheme> BroadcastStageProcessor<SigningCeremony<Crypto>>
 
 	/// Verify that all values have been broadcast correctly during stage 1
 	async fn process(
-		self,
+		mut self,
 		messages: BTreeMap<AuthorityCount, Option<Self::Message>>,
 	) -> SigningStageResult<Crypto> {
 		let verified_commitments = match verify_broadcasts_non_blocking(messages).await {
 			Ok(comms) => comms,
-			Err((reported_parties, abort_reason)) =>
+			Err((reported_parties, abort_reason, conflicts)) =>
 				return SigningStageResult::Error(
 					reported_parties,
 					SigningFailureReason::BroadcastFailure(abort_reason, Self::NAME),
+					conflicts,
 				),
 		};
 
@@ -151,21 +260,28 @@ impl<Crypto: CryptoScheme> BroadcastStageProcessor<SigningCeremony<Crypto>>
 				return SigningStageResult::Error(
 					bad_parties,
 					SigningFailureReason::DeserializationError,
+					Default::default(),
 				),
 		};
 
-		// Check that the number of commitments matches
-		// the number of payloads
+		// Check that the number of commitments (and preprocessed signature responses)
+		// matches the number of payloads
 		// TODO: see if there is a way to deduplicate this
 		// (that doesn't add too much complexity)
 		let bad_parties: BTreeSet<_> = verified_commitments
 			.iter()
-			.filter_map(|(party_idx, commitments)| {
-				if commitments.0.len() != self.signing_common.payload_count() {
+			.filter_map(|(party_idx, comm1)| {
+				if comm1.commitments.len() != self.signing_common.payload_count() ||
+					comm1.preprocessing_commitments.len() >
+						max_preprocessing_nonces(self.signing_common.payload_count()) ||
+					comm1.preprocessed_sig.as_ref().map_or(false, |sig| {
+						sig.nonce_ids.len() != self.signing_common.payload_count() ||
+							sig.responses.len() != self.signing_common.payload_count()
+					}) {
 					warn!(
 						from_id = self.common.validator_mapping.get_id(*party_idx).to_string(),
 						"Unexpected number of commitments from party: {} (expected: {})",
-						commitments.0.len(),
+						comm1.commitments.len(),
 						self.signing_common.payload_count(),
 					);
 					Some(*party_idx)
@@ -179,42 +295,62 @@ impl<Crypto: CryptoScheme> BroadcastStageProcessor<SigningCeremony<Crypto>>
 			return SigningStageResult::Error(
 				bad_parties,
 				SigningFailureReason::InvalidNumberOfPayloads,
+				Default::default(),
 			)
 		}
 
 		debug!("{} is successful", Self::NAME);
 
-		let signature_data = self
-			.signing_common
-			.payloads_and_keys
-			.iter()
-			.enumerate()
-			.map(|(payload_idx, PayloadAndKey { payload, .. })| {
-				let commitments = verified_commitments
-					.iter()
-					.map(|(party_idx, commitments)| {
-						(*party_idx, commitments.0[payload_idx].clone())
-					})
-					.collect::<BTreeMap<_, _>>();
-
-				let bindings = signing_detail::generate_bindings::<Crypto>(
-					payload,
-					&commitments,
-					&self.common.all_idxs,
-				);
+		let preprocessed_sig_responses = self.preprocessed.as_ref().and_then(|preprocessed| {
+			preprocessed_responses(&verified_commitments, &preprocessed.nonce_ids)
+		});
 
-				let bound_commitments = commitments
-					.iter()
-					.map(|(idx, comm)| (*idx, comm.d + comm.e * bindings[idx].clone()))
-					.collect::<BTreeMap<_, _>>();
+		self.update_preprocessed_nonce_pool(
+			&verified_commitments,
+			preprocessed_sig_responses.is_some(),
+		);
 
-				// Combine individual commitments into group (schnorr) commitment.
-				// See "Signing Protocol" in Section 5.2 (page 14).
-				let group_commitment = bound_commitments.values().cloned().sum();
+		// All parties used the same preprocessed nonces, and now that the broadcast of their
+		// responses is verified, we can blame anyone whose response is invalid
+		if let (Some(preprocessed), Some(responses)) =
+			(&self.preprocessed, preprocessed_sig_responses)
+		{
+			use zeroize::Zeroize;
+
+			// The fresh nonces are never used when signing with preprocessed nonces
+			for nonce in &mut self.nonces {
+				nonce.zeroize();
+			}
+
+			return match aggregate_signatures(
+				&self.common,
+				&self.signing_common,
+				&preprocessed.signature_data,
+				&responses,
+			) {
+				Ok(signatures) => StageResult::Done(signatures),
+				Err(failed_idxs) => StageResult::Error(
+					failed_idxs,
+					SigningFailureReason::InvalidSigShare,
+					Default::default(),
+				),
+			}
+		}
 
-				DerivedSignatureData { group_commitment, bindings, bound_commitments }
-			})
-			.collect();
+		let signature_data = derive_signature_data(
+			&self.signing_common,
+			&self.common.all_idxs,
+			(0..self.signing_common.payload_count())
+				.map(|payload_idx| {
+					verified_commitments
+						.iter()
+						.map(|(party_idx, comm1)| {
+							(*party_idx, comm1.commitments[payload_idx].clone())
+						})
+						.collect()
+				})
+				.collect(),
+		);
 
 		let processor = LocalSigStage3::<Crypto> {
 			common: self.common.clone(),
@@ -229,6 +365,65 @@ impl<Crypto: CryptoScheme> BroadcastStageProcessor<SigningCeremony<Crypto>>
 	}
 }
 
+impl<Crypto: CryptoScheme> VerifyCommitmentsBroadcast2<Crypto> {
+	/// Add the nonces that were generated for the preprocessed nonce pool by all parties, and
+	/// if the parties used different preprocessed nonces, discard the ones that are out of sync.
+	fn update_preprocessed_nonce_pool(
+		&mut self,
+		verified_commitments: &BTreeMap<AuthorityCount, Comm1Inner<Crypto::Point>>,
+		preprocessed_nonces_in_sync: bool,
+	) {
+		use zeroize::Zeroize;
+
+		let signers = signer_account_ids(&self.common);
+		let mut pool = self.preprocessed_nonce_pool.lock().unwrap();
+
+		// Parties that used different preprocessed nonces have pools that are out of sync
+		// (e.g. because one of them restarted), so we discard all nonces from earlier ceremonies
+		if !preprocessed_nonces_in_sync &&
+			verified_commitments.values().any(|comm1| comm1.preprocessed_sig.is_some())
+		{
+			pool.discard_before(&signers, self.common.ceremony_id);
+		}
+
+		let count = verified_commitments
+			.values()
+			.map(|comm1| comm1.preprocessing_commitments.len())
+			.chain([self.preprocessing_nonces.len()])
+			.min()
+			.unwrap_or_default();
+
+		let mut own_nonces = std::mem::take(&mut self.preprocessing_nonces);
+		for nonce in own_nonces.iter_mut().skip(count) {
+			nonce.zeroize();
+		}
+
+		let nonces = own_nonces
+			.into_iter()
+			.take(count)
+			.enumerate()
+			.map(|(index, own_nonce)| PreprocessedNonce {
+				id: PreprocessedNonceId {
+					ceremony_id: self.common.ceremony_id,
+					index: index as u32,
+				},
+				commitments: verified_commitments
+					.iter()
+					.map(|(party_idx, comm1)| {
+						(
+							self.common.validator_mapping.get_id(*party_idx).clone(),
+							comm1.preprocessing_commitments[index].clone(),
+						)
+					})
+					.collect(),
+				own_nonce,
+			})
+			.collect();
+
+		pool.add(signers, self.common.ceremony_id, nonces);
+	}
+}
+
 /// Stage 3: Generating and broadcasting signature response shares
 struct LocalSigStage3<Crypto: CryptoScheme> {
 	common: CeremonyCommon,
@@ -244,33 +439,23 @@ derive_display_as_type_name!(LocalSigStage3<Crypto: CryptoScheme>);
 impl<Crypto: CryptoScheme> BroadcastStageProcessor<SigningCeremony<Crypto>>
 	for LocalSigStage3<Crypto>
 {
-	type Message = LocalSig3<Crypto::Point>;
+	type Message = SignedBroadcast<LocalSig3<Crypto::Point>>;
 	const NAME: SigningStageName = SigningStageName::LocalSigStage3;
 
 	/// With all nonce commitments verified, and the group commitment computed,
 	/// we can generate our share of signature response, which we broadcast to other parties.
 	fn init(&mut self) -> DataToSend<Self::Message> {
-		let responses = (0..self.signing_common.payload_count())
-			.map(|i| {
-				let PayloadAndKey { payload, key } = &self.signing_common.payloads_and_keys[i];
-				let signature_data = &self.signature_data[i];
-
-				signing_detail::generate_local_sig::<Crypto>(
-					payload,
-					&key.key_share,
-					&self.nonces[i],
-					&signature_data.bindings,
-					signature_data.group_commitment,
-					self.common.own_idx,
-					&self.common.all_idxs,
-				)
-			})
-			.collect();
-
-		let data =
-			DataToSend::Broadcast(DelayDeserialization::new(&LocalSig3Inner::<Crypto::Point> {
-				responses,
-			}));
+		let responses = generate_responses(
+			&self.common,
+			&self.signing_common,
+			&self.nonces.iter().map(|nonce| &**nonce).collect::<Vec<_>>(),
+			&self.signature_data,
+		);
+
+		let data = DataToSend::Broadcast(self.common.sign_broadcast(
+			SigningStageName::VerifyLocalSigsBroadcastStage4.stage_number(),
+			DelayDeserialization::new(&LocalSig3Inner::<Crypto::Point> { responses }),
+		));
 
 		use zeroize::Zeroize;
 
@@ -308,7 +493,7 @@ struct VerifyLocalSigsBroadcastStage4<Crypto: CryptoScheme> {
 	signing_common: SigningStateCommonInfo<Crypto>,
 	signature_data: Vec<DerivedSignatureData<Crypto>>,
 	/// Signature shares sent to us (NOT verified to be correctly broadcast)
-	local_sigs: BTreeMap<AuthorityCount, Option<LocalSig3<Crypto::Point>>>,
+	local_sigs: BTreeMap<AuthorityCount, Option<SignedBroadcast<LocalSig3<Crypto::Point>>>>,
 }
 
 derive_display_as_type_name!(VerifyLocalSigsBroadcastStage4<Crypto: CryptoScheme>);
@@ -335,10 +520,11 @@ impl<Crypto: CryptoScheme> BroadcastStageProcessor<SigningCeremony<Crypto>>
 	) -> SigningStageResult<Crypto> {
 		let local_sigs = match verify_broadcasts_non_blocking(messages).await {
 			Ok(sigs) => sigs,
-			Err((reported_parties, abort_reason)) =>
+			Err((reported_parties, abort_reason, conflicts)) =>
 				return SigningStageResult::Error(
 					reported_parties,
 					SigningFailureReason::BroadcastFailure(abort_reason, Self::NAME),
+					conflicts,
 				),
 		};
 
@@ -349,6 +535,7 @@ impl<Crypto: CryptoScheme> BroadcastStageProcessor<SigningCeremony<Crypto>>
 				return SigningStageResult::Error(
 					bad_parties,
 					SigningFailureReason::DeserializationError,
+					Default::default(),
 				),
 		};
 
@@ -375,70 +562,167 @@ impl<Crypto: CryptoScheme> BroadcastStageProcessor<SigningCeremony<Crypto>>
 			return SigningStageResult::Error(
 				bad_parties,
 				SigningFailureReason::InvalidNumberOfPayloads,
+				Default::default(),
 			)
 		}
 
 		debug!("{} is successful", Self::NAME);
 
-		let all_idxs = &self.common.all_idxs;
-
-		let lagrange_coefficients: BTreeMap<_, _> = all_idxs
-			.iter()
-			.map(|signer_idx| {
-				(*signer_idx, get_lagrange_coeff::<Crypto::Point>(*signer_idx, all_idxs))
-			})
+		let responses = local_sigs
+			.into_iter()
+			.map(|(party_idx, LocalSig3Inner { responses })| (party_idx, responses))
 			.collect();
 
-		let signatures_result = (0..self.signing_common.payload_count())
-			.map(|i| {
-				// Extract local signatures for a specific payload (there is some
-				// room for optimization here)
-				let local_sigs = local_sigs
-					.iter()
-					.map(|(party_idx, local_signatures)| {
-						(*party_idx, local_signatures.responses[i].clone())
-					})
-					.collect();
-
-				let PayloadAndKey { payload, key } = &self.signing_common.payloads_and_keys[i];
-
-				// NOTE: depending on how many payloads we will need to sign with
-				// the same key, we may want to compute this value once per key
-				let pubkeys: BTreeMap<_, _> = all_idxs
-					.iter()
-					.map(|idx| {
-						(
-							*idx,
-							*key.party_public_keys
-								.get(self.common.validator_mapping.get_id(*idx))
-								.expect("should have a public key for this party"),
-						)
-					})
-					.collect();
-
-				let payload_data = &self.signature_data[i];
-
-				signing_detail::aggregate_signature::<Crypto>(
-					payload,
-					all_idxs,
-					key.get_agg_public_key_point(),
-					&pubkeys,
-					payload_data.group_commitment,
-					&payload_data.bound_commitments,
-					&local_sigs,
-					&lagrange_coefficients,
-				)
-			})
-			.collect::<Result<Vec<_>, _>>();
-
-		match signatures_result {
+		match aggregate_signatures(
+			&self.common,
+			&self.signing_common,
+			&self.signature_data,
+			&responses,
+		) {
 			Ok(signatures) => StageResult::Done(signatures),
-			Err(failed_idxs) =>
-				StageResult::Error(failed_idxs, SigningFailureReason::InvalidSigShare),
+			Err(failed_idxs) => StageResult::Error(
+				failed_idxs,
+				SigningFailureReason::InvalidSigShare,
+				Default::default(),
+			),
 		}
 	}
 }
 
+fn signer_account_ids(common: &CeremonyCommon) -> BTreeSet<AccountId> {
+	common
+		.all_idxs
+		.iter()
+		.map(|idx| common.validator_mapping.get_id(*idx).clone())
+		.collect()
+}
+
+/// The signature responses from all parties, if they were all generated
+/// with the preprocessed nonces with the given ids
+fn preprocessed_responses<P: ECPoint>(
+	messages: &BTreeMap<AuthorityCount, Comm1Inner<P>>,
+	nonce_ids: &[PreprocessedNonceId],
+) -> Option<BTreeMap<AuthorityCount, Vec<P::Scalar>>> {
+	messages
+		.iter()
+		.map(|(party_idx, comm1)| {
+			let sig = comm1.preprocessed_sig.as_ref()?;
+			(sig.nonce_ids.as_slice() == nonce_ids && sig.responses.len() == nonce_ids.len())
+				.then(|| (*party_idx, sig.responses.clone()))
+		})
+		.collect()
+}
+
+/// Derive the bindings and group commitment for each payload from
+/// the nonce commitments of all parties for that payload
+fn derive_signature_data<Crypto: CryptoScheme>(
+	signing_common: &SigningStateCommonInfo<Crypto>,
+	all_idxs: &BTreeSet<AuthorityCount>,
+	commitments: Vec<BTreeMap<AuthorityCount, SigningCommitment<Crypto::Point>>>,
+) -> Vec<DerivedSignatureData<Crypto>> {
+	signing_common
+		.payloads_and_keys
+		.iter()
+		.zip(commitments)
+		.map(|(PayloadAndKey { payload, .. }, commitments)| {
+			let bindings =
+				signing_detail::generate_bindings::<Crypto>(payload, &commitments, all_idxs);
+
+			let bound_commitments = commitments
+				.iter()
+				.map(|(idx, comm)| (*idx, comm.d + comm.e * bindings[idx].clone()))
+				.collect::<BTreeMap<_, _>>();
+
+			// Combine individual commitments into group (schnorr) commitment.
+			// See "Signing Protocol" in Section 5.2 (page 14).
+			let group_commitment = bound_commitments.values().cloned().sum();
+
+			DerivedSignatureData { group_commitment, bindings, bound_commitments }
+		})
+		.collect()
+}
+
+/// Generate our signature response for each payload
+fn generate_responses<Crypto: CryptoScheme>(
+	common: &CeremonyCommon,
+	signing_common: &SigningStateCommonInfo<Crypto>,
+	nonces: &[&SecretNoncePair<Crypto::Point>],
+	signature_data: &[DerivedSignatureData<Crypto>],
+) -> Vec<<Crypto::Point as ECPoint>::Scalar> {
+	(0..signing_common.payload_count())
+		.map(|i| {
+			let PayloadAndKey { payload, key } = &signing_common.payloads_and_keys[i];
+			let signature_data = &signature_data[i];
+
+			signing_detail::generate_local_sig::<Crypto>(
+				payload,
+				&key.key_share,
+				nonces[i],
+				&signature_data.bindings,
+				signature_data.group_commitment,
+				common.own_idx,
+				&common.all_idxs,
+			)
+		})
+		.collect()
+}
+
+/// Combine the signature responses from all parties into a signature for each
+/// payload, or return the parties whose responses are invalid
+fn aggregate_signatures<Crypto: CryptoScheme>(
+	common: &CeremonyCommon,
+	signing_common: &SigningStateCommonInfo<Crypto>,
+	signature_data: &[DerivedSignatureData<Crypto>],
+	responses: &BTreeMap<AuthorityCount, Vec<<Crypto::Point as ECPoint>::Scalar>>,
+) -> Result<Vec<Crypto::Signature>, BTreeSet<AuthorityCount>> {
+	let all_idxs = &common.all_idxs;
+
+	let lagrange_coefficients: BTreeMap<_, _> = all_idxs
+		.iter()
+		.map(|signer_idx| (*signer_idx, get_lagrange_coeff::<Crypto::Point>(*signer_idx, all_idxs)))
+		.collect();
+
+	(0..signing_common.payload_count())
+		.map(|i| {
+			// Extract local signatures for a specific payload (there is some
+			// room for optimization here)
+			let local_sigs = responses
+				.iter()
+				.map(|(party_idx, responses)| (*party_idx, responses[i].clone()))
+				.collect();
+
+			let PayloadAndKey { payload, key } = &signing_common.payloads_and_keys[i];
+
+			// NOTE: depending on how many payloads we will need to sign with
+			// the same key, we may want to compute this value once per key
+			let pubkeys: BTreeMap<_, _> = all_idxs
+				.iter()
+				.map(|idx| {
+					(
+						*idx,
+						*key.party_public_keys
+							.get(common.validator_mapping.get_id(*idx))
+							.expect("should have a public key for this party"),
+					)
+				})
+				.collect();
+
+			let payload_data = &signature_data[i];
+
+			signing_detail::aggregate_signature::<Crypto>(
+				payload,
+				all_idxs,
+				key.get_agg_public_key_point(),
+				&pubkeys,
+				payload_data.group_commitment,
+				&payload_data.bound_commitments,
+				&local_sigs,
+				&lagrange_coefficients,
+			)
+		})
+		.collect()
+}
+
 #[cfg(test)]
 mod tests {
 	use super::*;
@@ -446,6 +730,7 @@ mod tests {
 	use crate::{
 		bitcoin::BtcCryptoScheme,
 		client::{
+			helpers::new_broadcast_signer,
 			signing::{gen_signing_data_stage2, SigningData},
 			PartyIdxMapping,
 		},
@@ -472,6 +757,7 @@ mod tests {
 			validator_mapping: Arc::new(PartyIdxMapping::from_participants(participants)),
 			all_idxs: BTreeSet::new(),
 			rng: Rng::from_seed([0; 32]),
+			broadcast_signer: new_broadcast_signer(&AccountId::from([0; 32])),
 		};
 
 		// Create the dummy stage 2 with the common data
@@ -479,6 +765,9 @@ mod tests {
 			common,
 			signing_common: SigningStateCommonInfo { payloads_and_keys: vec![] },
 			nonces: vec![],
+			preprocessing_nonces: vec![],
+			preprocessed: None,
+			preprocessed_nonce_pool: Default::default(),
 			commitments: BTreeMap::new(),
 		};
 
@@ -488,7 +777,7 @@ mod tests {
 		{
 			let messages = BTreeMap::from_iter([(OWN_IDX, Some(bv))]);
 			// Process the message and check that we get the correct error
-			if let SigningStageResult::Error(blamed, reason) = stage.process(messages).await {
+			if let SigningStageResult::Error(blamed, reason, _) = stage.process(messages).await {
 				assert_eq!(reason, SigningFailureReason::InvalidNumberOfPayloads);
 				assert_eq!(blamed, BTreeSet::from_iter([OWN_IDX]));
 			} else {
@@ -516,6 +805,7 @@ mod tests {
 			validator_mapping: Arc::new(PartyIdxMapping::from_participants(participants)),
 			all_idxs: BTreeSet::new(),
 			rng: Rng::from_seed([0; 32]),
+			broadcast_signer: new_broadcast_signer(&AccountId::from([0; 32])),
 		};
 
 		// Create the dummy stage 4 with the common data
@@ -533,7 +823,7 @@ mod tests {
 		{
 			let messages = BTreeMap::from_iter([(OWN_IDX, Some(bv))]);
 			// Process the message and check that we get the correct error
-			if let SigningStageResult::Error(blamed, reason) = stage.process(messages).await {
+			if let SigningStageResult::Error(blamed, reason, _) = stage.process(messages).await {
 				assert_eq!(reason, SigningFailureReason::InvalidNumberOfPayloads);
 				// For this test we report our own index because we are the only participant. This
 				// does not matter, the code is the same.
@@ -7,7 +7,8 @@ use crate::{
 	bitcoin::{self, BtcSigning},
 	client::{
 		common::{
-			BroadcastFailureReason, DelayDeserialization, SigningFailureReason, SigningStageName,
+			BroadcastFailureReason, DelayDeserialization, SignedBroadcast, SigningFailureReason,
+			SigningStageName,
 		},
 		helpers::{
 			gen_dummy_local_sig, gen_dummy_signing_comm1, new_nodes, new_signing_ceremony,
@@ -15,7 +16,7 @@ use crate::{
 			ACCOUNT_IDS, DEFAULT_SIGNING_CEREMONY_ID,
 		},
 		keygen::generate_key_data,
-		signing::signing_data,
+		signing::{signing_data, PREPROCESSED_NONCES_PER_CEREMONY},
 	},
 	ChainSigning, CryptoScheme, Rng,
 };
@@ -24,7 +25,7 @@ use crate::{
 use crate::{crypto::eth::Point, eth::EthSigning};
 
 type VerifyComm2 = signing_data::VerifyComm2<Point>;
-type LocalSig3 = signing_data::LocalSig3<Point>;
+type LocalSig3 = SignedBroadcast<signing_data::LocalSig3<Point>>;
 type VerifyLocalSig4 = signing_data::VerifyLocalSig4<Point>;
 
 type ChainPoint<Chain> = <<Chain as ChainSigning>::CryptoScheme as CryptoScheme>::Point;
@@ -40,18 +41,30 @@ mod broadcast_commitments_stage {
 
 		// This account id will "broadcast" inconsistently
 		let [bad_account_id] = signing_ceremony.select_account_ids();
+		let own_message = messages[&bad_account_id].values().next().unwrap().clone();
 		for message in messages.get_mut(&bad_account_id).unwrap().values_mut() {
-			*message = gen_dummy_signing_comm1(&mut signing_ceremony.rng, 1);
+			let comm1 = gen_dummy_signing_comm1(&mut signing_ceremony.rng, 1);
+			*message = signing_ceremony.sign_broadcast(
+				&bad_account_id,
+				SigningStageName::VerifyCommitmentsBroadcast2.stage_number(),
+				comm1.value,
+			);
 		}
+		let expected_conflicts = signing_ceremony.expected_conflicts(
+			&bad_account_id,
+			&own_message,
+			&messages[&bad_account_id],
+		);
 
 		let messages = signing_ceremony.run_stage::<VerifyComm2, _, _>(messages).await;
 		signing_ceremony.distribute_messages(messages).await;
-		signing_ceremony.complete_with_error(
+		signing_ceremony.complete_with_conflicts(
 			&[bad_account_id],
 			SigningFailureReason::BroadcastFailure(
 				BroadcastFailureReason::Inconsistency,
 				SigningStageName::VerifyCommitmentsBroadcast2,
 			),
+			expected_conflicts,
 		);
 	}
 
@@ -65,7 +78,7 @@ mod broadcast_commitments_stage {
 
 		let [bad_account_id] = signing_ceremony.select_account_ids();
 		for message in messages.get_mut(&bad_account_id).unwrap().values_mut() {
-			*message = DelayDeserialization::new(&"Not a valid Comm1");
+			message.value = DelayDeserialization::new(&"Not a valid Comm1");
 		}
 
 		let messages = signing_ceremony.run_stage::<VerifyComm2, _, _>(messages).await;
@@ -91,18 +104,30 @@ mod local_signatures_stage {
 
 		// This account id will send an invalid signature
 		let [bad_account_id] = signing_ceremony.select_account_ids();
+		let own_message = messages[&bad_account_id].values().next().unwrap().clone();
 		for message in messages.get_mut(&bad_account_id).unwrap().values_mut() {
-			*message = gen_dummy_local_sig(&mut signing_ceremony.rng, 1);
+			let sig = gen_dummy_local_sig(&mut signing_ceremony.rng, 1);
+			*message = signing_ceremony.sign_broadcast(
+				&bad_account_id,
+				SigningStageName::VerifyLocalSigsBroadcastStage4.stage_number(),
+				sig.value,
+			);
 		}
+		let expected_conflicts = signing_ceremony.expected_conflicts(
+			&bad_account_id,
+			&own_message,
+			&messages[&bad_account_id],
+		);
 
 		let messages = signing_ceremony.run_stage::<VerifyLocalSig4, _, _>(messages).await;
 		signing_ceremony.distribute_messages(messages).await;
-		signing_ceremony.complete_with_error(
+		signing_ceremony.complete_with_conflicts(
 			&[bad_account_id],
 			SigningFailureReason::BroadcastFailure(
 				BroadcastFailureReason::Inconsistency,
 				SigningStageName::VerifyLocalSigsBroadcastStage4,
 			),
+			expected_conflicts,
 		);
 	}
 
@@ -117,7 +142,7 @@ mod local_signatures_stage {
 		let [bad_account_id] = signing_ceremony.select_account_ids();
 		let invalid_sig3 = gen_dummy_local_sig(&mut signing_ceremony.rng, 1);
 		for message in messages.get_mut(&bad_account_id).unwrap().values_mut() {
-			*message = invalid_sig3.clone();
+			message.value = invalid_sig3.value.clone();
 		}
 
 		let messages = signing_ceremony.run_stage::<VerifyLocalSig4, _, _>(messages).await;
@@ -136,7 +161,7 @@ mod local_signatures_stage {
 		// This account id will a message that cannot be deserialized
 		let [bad_account_id] = signing_ceremony.select_account_ids();
 		for message in messages.get_mut(&bad_account_id).unwrap().values_mut() {
-			*message = DelayDeserialization::new(&"Not a valid LocalSig3");
+			message.value = DelayDeserialization::new(&"Not a valid LocalSig3");
 		}
 
 		let messages = signing_ceremony.run_stage::<VerifyLocalSig4, _, _>(messages).await;
@@ -172,7 +197,7 @@ async fn test_sign_multiple_payloads<Chain: ChainSigning>(
 		signing_ceremony,
 		messages,
 		signing_data::VerifyComm2<ChainPoint<Chain>>,
-		signing_data::LocalSig3<ChainPoint<Chain>>,
+		SignedBroadcast<signing_data::LocalSig3<ChainPoint<Chain>>>,
 		signing_data::VerifyLocalSig4<ChainPoint<Chain>>
 	);
 	signing_ceremony.distribute_messages(messages).await;
@@ -227,7 +252,7 @@ async fn should_sign_with_all_parties<Chain: ChainSigning>(participants: &BTreeS
 			signing_ceremony,
 			messages,
 			signing_data::VerifyComm2<ChainPoint<Chain>>,
-			signing_data::LocalSig3<ChainPoint<Chain>>,
+			SignedBroadcast<signing_data::LocalSig3<ChainPoint<Chain>>>,
 			signing_data::VerifyLocalSig4<ChainPoint<Chain>>
 		);
 		signing_ceremony.distribute_messages(messages).await;
@@ -290,7 +315,7 @@ async fn should_sign_with_different_keys() {
 		signing_ceremony,
 		messages,
 		signing_data::VerifyComm2<Point>,
-		signing_data::LocalSig3<Point>,
+		SignedBroadcast<signing_data::LocalSig3<Point>>,
 		signing_data::VerifyLocalSig4<Point>
 	);
 	signing_ceremony.distribute_messages(messages).await;
@@ -306,6 +331,114 @@ async fn should_sign_with_different_keys() {
 		.is_ok());
 }
 
+mod preprocessed_nonces {
+
+	use super::*;
+	use crate::{
+		client::{ceremony_runner::CeremonyRunner, helpers::standard_signing},
+		crypto::{eth::Scalar, ECScalar},
+	};
+
+	fn start_next_ceremony<Chain: ChainSigning>(
+		signing_ceremony: &mut SigningCeremonyRunner<Chain>,
+	) {
+		signing_ceremony.ceremony_id += 1;
+		for node in signing_ceremony.nodes.values_mut() {
+			node.ceremony_runner = CeremonyRunner::new_unauthorised_for_test();
+		}
+	}
+
+	/// Runs a full signing ceremony, which fills the nodes' preprocessed nonce pools, and prepares
+	/// the nodes for the next ceremony with the same signers.
+	async fn new_signing_ceremony_with_preprocessed_nonces<Chain: ChainSigning>(
+	) -> SigningCeremonyRunner<Chain> {
+		let (mut signing_ceremony, _) = new_signing_ceremony::<Chain>().await;
+		standard_signing(&mut signing_ceremony).await;
+		start_next_ceremony(&mut signing_ceremony);
+		signing_ceremony
+	}
+
+	/// Runs a ceremony that signs with preprocessed nonces, which completes once the broadcast of
+	/// the first stage has been verified.
+	async fn sign_with_preprocessed_nonces<Chain: ChainSigning>(
+		signing_ceremony: &mut SigningCeremonyRunner<Chain>,
+	) {
+		let messages = signing_ceremony.request().await;
+		let messages = signing_ceremony.run_stage::<VerifyComm2, _, _>(messages).await;
+		signing_ceremony.distribute_messages(messages).await;
+		signing_ceremony.complete();
+	}
+
+	async fn should_sign_after_verifying_the_first_broadcast<Chain: ChainSigning>() {
+		let mut signing_ceremony = new_signing_ceremony_with_preprocessed_nonces::<Chain>().await;
+
+		sign_with_preprocessed_nonces(&mut signing_ceremony).await;
+	}
+
+	#[tokio::test]
+	async fn should_sign_after_verifying_the_first_broadcast_on_all_schemes() {
+		test_all_crypto_chains_async!(should_sign_after_verifying_the_first_broadcast());
+	}
+
+	#[tokio::test]
+	async fn should_replenish_preprocessed_nonces_on_each_use() {
+		let mut signing_ceremony =
+			new_signing_ceremony_with_preprocessed_nonces::<EthSigning>().await;
+
+		// More ceremonies than the nonces generated by the first one, which only succeed if the
+		// nonces are replenished by the ceremonies that use them
+		for _ in 0..=PREPROCESSED_NONCES_PER_CEREMONY {
+			sign_with_preprocessed_nonces(&mut signing_ceremony).await;
+			start_next_ceremony(&mut signing_ceremony);
+		}
+	}
+
+	#[tokio::test]
+	async fn should_fall_back_to_full_ceremony_if_pools_are_out_of_sync() {
+		let mut signing_ceremony =
+			new_signing_ceremony_with_preprocessed_nonces::<EthSigning>().await;
+
+		// As if this party had restarted
+		let [restarted_account_id] = signing_ceremony.select_account_ids();
+		signing_ceremony
+			.nodes
+			.get_mut(&restarted_account_id)
+			.unwrap()
+			.preprocessed_nonce_pool = Default::default();
+
+		standard_signing(&mut signing_ceremony).await;
+
+		// The pools have been brought back in sync by the full ceremony
+		start_next_ceremony(&mut signing_ceremony);
+		sign_with_preprocessed_nonces(&mut signing_ceremony).await;
+	}
+
+	#[tokio::test]
+	async fn should_report_on_invalid_preprocessed_signature_share() {
+		let mut signing_ceremony =
+			new_signing_ceremony_with_preprocessed_nonces::<EthSigning>().await;
+
+		let mut messages = signing_ceremony.request().await;
+
+		// This account id will send an invalid signature share
+		let [bad_account_id] = signing_ceremony.select_account_ids();
+		let invalid_response = Scalar::random(&mut signing_ceremony.rng);
+		for message in messages.get_mut(&bad_account_id).unwrap().values_mut() {
+			let mut comm1 = message.value.clone().deserialize().unwrap();
+			comm1.preprocessed_sig.as_mut().unwrap().responses = vec![invalid_response.clone()];
+			message.value = DelayDeserialization::new(&comm1);
+		}
+
+		// Nobody completes the ceremony before the broadcast of the shares has been verified, and
+		// then the bad party is reported
+		let messages = signing_ceremony.run_stage::<VerifyComm2, _, _>(messages).await;
+		signing_ceremony.distribute_messages(messages).await;
+
+		signing_ceremony
+			.complete_with_error(&[bad_account_id], SigningFailureReason::InvalidSigShare);
+	}
+}
+
 mod timeout {
 
 	use super::*;
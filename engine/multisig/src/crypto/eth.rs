@@ -10,7 +10,7 @@ use super::{
 // solely use "CryptoScheme" as generic parameter instead.
 pub use super::secp256k1::{Point, Scalar};
 use anyhow::Context;
-use cf_chains::{evm::ParityBit, Chain, ChainCrypto, Ethereum};
+use cf_chains::{arb::Arbitrum, evm::ParityBit, Chain, ChainCrypto, Ethereum};
 use num_bigint::BigUint;
 use secp256k1::constants::CURVE_ORDER;
 use serde::{Deserialize, Serialize};
@@ -43,6 +43,10 @@ impl SignatureToThresholdSignature<<Ethereum as Chain>::ChainCrypto> for Vec<Eth
 #[derive(Clone, Debug, PartialEq)]
 pub struct EthSigning {}
 
+/// Arbitrum shares the Evm crypto scheme with Ethereum, but uses its own key.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ArbSigning {}
+
 #[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Hash, Eq)]
 pub struct SigningPayload(pub [u8; 32]);
 
@@ -68,6 +72,13 @@ impl ChainSigning for EthSigning {
 	const NAME: &'static str = "Ethereum";
 	const CHAIN_TAG: ChainTag = ChainTag::Ethereum;
 }
+
+impl ChainSigning for ArbSigning {
+	type CryptoScheme = EvmCryptoScheme;
+	type ChainCrypto = <Arbitrum as Chain>::ChainCrypto;
+	const NAME: &'static str = "Arbitrum";
+	const CHAIN_TAG: ChainTag = ChainTag::Arbitrum;
+}
 impl CryptoScheme for EvmCryptoScheme {
 	type Point = Point;
 	type Signature = EthSchnorrSignature;
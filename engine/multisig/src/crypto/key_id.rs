@@ -16,6 +16,10 @@ impl KeyId {
 	pub fn new<Key: CanonicalEncoding>(epoch_index: EpochIndex, key: Key) -> Self {
 		KeyId { epoch_index, public_key_bytes: key.encode_key() }
 	}
+
+	pub fn epoch_index(&self) -> EpochIndex {
+		self.epoch_index
+	}
 }
 
 impl CanonicalEncoding for cf_chains::dot::PolkadotPublicKey {
@@ -64,6 +68,21 @@ impl core::fmt::Display for KeyId {
 	}
 }
 
+/// Parses a key id from `<epoch_index>:<hex encoded public key>`, as used on the command line.
+impl core::str::FromStr for KeyId {
+	type Err = anyhow::Error;
+
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		let (epoch_index, public_key) = s
+			.split_once(':')
+			.ok_or_else(|| anyhow::anyhow!("Expected <epoch_index>:<public_key>, got {s}"))?;
+		Ok(KeyId {
+			epoch_index: epoch_index.parse()?,
+			public_key_bytes: hex::decode(public_key.trim_start_matches("0x"))?,
+		})
+	}
+}
+
 #[cfg(test)]
 mod test_super {
 	use super::*;
@@ -100,4 +119,18 @@ mod test_super {
 		assert_eq!(expected_bytes, bincode::serialize(&key_id).unwrap());
 		assert_eq!(key_id, bincode::deserialize::<KeyId>(&expected_bytes).unwrap());
 	}
+
+	#[test]
+	fn key_id_from_str() {
+		assert_eq!(
+			"29:0x0a5d8dff".parse::<KeyId>().unwrap(),
+			KeyId { epoch_index: 29, public_key_bytes: vec![0x0a, 0x5d, 0x8d, 0xff] }
+		);
+		assert_eq!(
+			"29:0a5d8dff".parse::<KeyId>().unwrap(),
+			KeyId::new(29, [0x0a, 0x5d, 0x8d, 0xff])
+		);
+		assert!("0a5d8dff".parse::<KeyId>().is_err());
+		assert!("29:0a5d8dfz".parse::<KeyId>().is_err());
+	}
 }
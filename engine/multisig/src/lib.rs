@@ -22,7 +22,7 @@ pub mod p2p {
 	pub type ProtocolVersion = u16;
 
 	/// Currently active wire protocol version
-	pub const CURRENT_PROTOCOL_VERSION: ProtocolVersion = 1;
+	pub const CURRENT_PROTOCOL_VERSION: ProtocolVersion = 2;
 
 	// TODO: Consider if this should be removed, particularly once we no longer use Substrate for
 	// peering
@@ -2,6 +2,7 @@ use bitcoin::{Block, BlockHash, Txid};
 use utilities::task_scope::Scope;
 
 use crate::{
+	recording::Recorder,
 	retrier::{Attempt, RequestLog, RetrierClient},
 	settings::{HttpBasicAuthEndpoint, NodeContainer},
 	witness::common::chain_source::{ChainClient, Header},
@@ -11,11 +12,12 @@ use core::time::Duration;
 
 use anyhow::Result;
 
-use super::rpc::{BlockHeader, BtcRpcApi, BtcRpcClient};
+use super::rpc::{check_block_filters, BlockHeader, BtcRpcApi, BtcRpcClient};
 
 #[derive(Clone)]
 pub struct BtcRetryRpcClient {
 	retry_client: RetrierClient<BtcRpcClient>,
+	block_filters: bool,
 }
 
 const BITCOIN_RPC_TIMEOUT: Duration = Duration::from_millis(4 * 1000);
@@ -24,11 +26,25 @@ const MAX_CONCURRENT_SUBMISSIONS: u32 = 100;
 const MAX_BROADCAST_RETRIES: Attempt = 2;
 
 impl BtcRetryRpcClient {
+	/// With `block_filters` deposits are witnessed using the compact block filters of the blocks,
+	/// only downloading the blocks that may contain a deposit. This fails if a node doesn't serve
+	/// the filters.
 	pub async fn new(
 		scope: &Scope<'_, anyhow::Error>,
 		nodes: NodeContainer<HttpBasicAuthEndpoint>,
 		expected_btc_network: BitcoinNetwork,
+		block_filters: bool,
 	) -> Result<Self> {
+		if block_filters {
+			check_block_filters(nodes.primary.clone()).await?;
+			if let Some(backup) = &nodes.backup {
+				check_block_filters(backup.clone()).await?;
+			}
+		}
+
+		let primary_rate_limit = nodes.primary.rate_limit;
+		let backup_rate_limit = nodes.backup.as_ref().and_then(|backup| backup.rate_limit);
+
 		let rpc_client = BtcRpcClient::new(nodes.primary, Some(expected_btc_network))?;
 
 		let backup_rpc_client = nodes
@@ -37,16 +53,37 @@ impl BtcRetryRpcClient {
 			.transpose()?;
 
 		Ok(Self {
-			retry_client: RetrierClient::new(
+			retry_client: RetrierClient::new_with_rate_limits(
 				scope,
 				"btc_rpc",
 				rpc_client,
 				backup_rpc_client,
 				BITCOIN_RPC_TIMEOUT,
 				MAX_CONCURRENT_SUBMISSIONS,
+				primary_rate_limit,
+				backup_rate_limit,
 			),
+			block_filters,
 		})
 	}
+
+	/// Records the responses to the requests made by this client to the given recorder.
+	pub fn with_recorder(self, recorder: Recorder) -> Self {
+		Self { retry_client: self.retry_client.with_recorder(recorder), ..self }
+	}
+
+	pub fn recorder(&self) -> Option<&Recorder> {
+		self.retry_client.recorder()
+	}
+
+	/// Cross-checks the reads we witness from between the primary and backup rpcs.
+	pub fn with_cross_checking(self, scope: &Scope<'_, anyhow::Error>) -> Self {
+		Self { retry_client: self.retry_client.with_cross_checking(scope), ..self }
+	}
+
+	pub fn block_filters(&self) -> bool {
+		self.block_filters
+	}
 }
 
 #[async_trait::async_trait]
@@ -62,13 +99,15 @@ pub trait BtcRetryRpcApi: Clone {
 	async fn average_block_fee_rate(&self, block_hash: BlockHash) -> cf_chains::btc::BtcAmount;
 
 	async fn best_block_header(&self) -> BlockHeader;
+
+	async fn block_filter(&self, block_hash: BlockHash) -> Vec<u8>;
 }
 
 #[async_trait::async_trait]
 impl BtcRetryRpcApi for BtcRetryRpcClient {
 	async fn block(&self, block_hash: BlockHash) -> Block {
 		self.retry_client
-			.request(
+			.request_cross_checked(
 				Box::pin(move |client| {
 					#[allow(clippy::redundant_async_block)]
 					Box::pin(async move { client.block(block_hash).await })
@@ -80,7 +119,7 @@ impl BtcRetryRpcApi for BtcRetryRpcClient {
 
 	async fn block_hash(&self, block_number: cf_chains::btc::BlockNumber) -> BlockHash {
 		self.retry_client
-			.request(
+			.request_cross_checked(
 				Box::pin(move |client| {
 					#[allow(clippy::redundant_async_block)]
 					Box::pin(async move { client.block_hash(block_number).await })
@@ -110,7 +149,7 @@ impl BtcRetryRpcApi for BtcRetryRpcClient {
 
 	async fn next_block_fee_rate(&self) -> Option<cf_chains::btc::BtcAmount> {
 		self.retry_client
-			.request(
+			.request_recorded(
 				Box::pin(move |client| {
 					#[allow(clippy::redundant_async_block)]
 					Box::pin(async move { client.next_block_fee_rate().await })
@@ -122,7 +161,7 @@ impl BtcRetryRpcApi for BtcRetryRpcClient {
 
 	async fn average_block_fee_rate(&self, block_hash: BlockHash) -> cf_chains::btc::BtcAmount {
 		self.retry_client
-			.request(
+			.request_recorded(
 				Box::pin(move |client| {
 					#[allow(clippy::redundant_async_block)]
 					Box::pin(async move { client.average_block_fee_rate(block_hash).await })
@@ -137,7 +176,7 @@ impl BtcRetryRpcApi for BtcRetryRpcClient {
 
 	async fn best_block_header(&self) -> BlockHeader {
 		self.retry_client
-			.request(
+			.request_recorded(
 				Box::pin(move |client| {
 					#[allow(clippy::redundant_async_block)]
 					Box::pin(async move {
@@ -151,6 +190,18 @@ impl BtcRetryRpcApi for BtcRetryRpcClient {
 			)
 			.await
 	}
+
+	async fn block_filter(&self, block_hash: BlockHash) -> Vec<u8> {
+		self.retry_client
+			.request_cross_checked(
+				Box::pin(move |client| {
+					#[allow(clippy::redundant_async_block)]
+					Box::pin(async move { client.block_filter(block_hash).await })
+				}),
+				RequestLog::new("block_filter".to_string(), Some(format!("{block_hash}"))),
+			)
+			.await
+	}
 }
 
 #[async_trait::async_trait]
@@ -164,7 +215,7 @@ impl ChainClient for BtcRetryRpcClient {
 		index: Self::Index,
 	) -> Header<Self::Index, Self::Hash, Self::Data> {
 		self.retry_client
-			.request(
+			.request_cross_checked(
 				Box::pin(move |client| {
 					#[allow(clippy::redundant_async_block)]
 					Box::pin(async move {
@@ -212,6 +263,8 @@ pub mod mocks {
 			async fn average_block_fee_rate(&self, block_hash: BlockHash) -> cf_chains::btc::BtcAmount;
 
 			async fn best_block_header(&self) -> BlockHeader;
+
+			async fn block_filter(&self, block_hash: BlockHash) -> Vec<u8>;
 		}
 	}
 }
@@ -9,10 +9,14 @@ use serde;
 use serde_json::json;
 
 use bitcoin::{block::Version, Amount, Block, BlockHash, Transaction, Txid};
-use tracing::error;
+use tracing::{error, warn};
 use utilities::make_periodic_tick;
 
-use crate::{constants::RPC_RETRY_CONNECTION_INTERVAL, settings::HttpBasicAuthEndpoint};
+use crate::{
+	constants::RPC_RETRY_CONNECTION_INTERVAL,
+	retrier::rate_limit::{parse_retry_after, RateLimited},
+	settings::HttpBasicAuthEndpoint,
+};
 
 use anyhow::{anyhow, Context, Result};
 
@@ -32,6 +36,7 @@ pub enum Error {
 	Transport(reqwest::Error),
 	Json(serde_json::Error),
 	Rpc(RpcError),
+	RateLimited(#[source] RateLimited),
 }
 
 impl std::fmt::Display for Error {
@@ -40,6 +45,7 @@ impl std::fmt::Display for Error {
 			Error::Transport(ref e) => write!(f, "Transport error: {}", e),
 			Error::Json(ref e) => write!(f, "JSON decode error: {}", e),
 			Error::Rpc(ref e) => write!(f, "RPC error response: {:?}", e),
+			Error::RateLimited(ref e) => write!(f, "{}", e),
 		}
 	}
 }
@@ -154,6 +160,16 @@ async fn call_rpc_raw(
 		.await
 		.map_err(Error::Transport)?;
 
+	if response.status() == reqwest::StatusCode::TOO_MANY_REQUESTS {
+		return Err(Error::RateLimited(RateLimited {
+			retry_after: response
+				.headers()
+				.get(reqwest::header::RETRY_AFTER)
+				.and_then(|value| value.to_str().ok())
+				.and_then(parse_retry_after),
+		}))
+	}
+
 	let response = response
 		.json::<Vec<serde_json::Value>>()
 		.await
@@ -258,6 +274,9 @@ pub trait BtcRpcApi {
 	async fn get_raw_mempool(&self) -> anyhow::Result<Vec<Txid>>;
 
 	async fn get_raw_transactions(&self, tx_hashes: Vec<Txid>) -> anyhow::Result<Vec<Transaction>>;
+
+	/// The BIP158 basic compact block filter of the block.
+	async fn block_filter(&self, block_hash: BlockHash) -> anyhow::Result<Vec<u8>>;
 }
 
 #[async_trait::async_trait]
@@ -385,6 +404,82 @@ impl BtcRpcApi for BtcRpcClient {
 			})
 			.collect::<Result<_>>()
 	}
+
+	async fn block_filter(&self, block_hash: BlockHash) -> anyhow::Result<Vec<u8>> {
+		#[derive(Deserialize)]
+		struct BlockFilter {
+			filter: String,
+		}
+
+		// bitcoind serves filters with `getblockfilter` when started with `-blockfilterindex`,
+		// while nodes based on btcd serve them with `getcfilter`.
+		let hex_filter = match self
+			.call_rpc::<BlockFilter>(
+				"getblockfilter",
+				ReqParams::Batch(vec![json!([json!(block_hash), json!("basic")])]),
+			)
+			.await
+		{
+			Ok(block_filter) =>
+				block_filter.into_iter().next().map(|block_filter| block_filter.filter),
+			Err(error) if is_method_not_found(&error) => self
+				.call_rpc::<String>(
+					"getcfilter",
+					ReqParams::Batch(vec![json!([json!(block_hash), json!(0)])]),
+				)
+				.await?
+				.into_iter()
+				.next(),
+			Err(error) => return Err(error),
+		}
+		.ok_or_else(|| anyhow!("Response missing block filter"))?;
+
+		hex::decode(hex_filter).context("Response not valid hex")
+	}
+}
+
+/// Checks that the node serves the compact block filters requested by `block_filter`. Without
+/// `-blockfilterindex` bitcoind rejects every request for a filter, which would otherwise be
+/// retried forever. A node that can't be reached is only warned about, as we can't tell what it
+/// supports.
+pub async fn check_block_filters(endpoint: HttpBasicAuthEndpoint) -> Result<()> {
+	const CHECK_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(10);
+
+	let client = BtcRpcClient { client: Client::builder().build()?, endpoint };
+	let result = tokio::time::timeout(CHECK_TIMEOUT, async {
+		let best_block_hash = client.best_block_hash().await?;
+		client.block_filter(best_block_hash).await
+	})
+	.await
+	.map_err(anyhow::Error::new)
+	.and_then(|result| result);
+
+	match result {
+		Ok(_) => Ok(()),
+		Err(error) => match error.downcast_ref::<Error>() {
+			Some(Error::Rpc(rpc_error)) => Err(anyhow!(
+				"Bitcoin node at {} doesn't serve compact block filters: {}. Start bitcoind with `-blockfilterindex=1`, or disable `btc.block_filters`.",
+				client.endpoint.http_endpoint,
+				rpc_error.message
+			)),
+			_ => {
+				warn!(
+					"Failed to check that the Bitcoin node at {} serves compact block filters: {error}",
+					client.endpoint.http_endpoint
+				);
+				Ok(())
+			},
+		},
+	}
+}
+
+// Whether the request failed because the node doesn't support the method.
+fn is_method_not_found(error: &anyhow::Error) -> bool {
+	const METHOD_NOT_FOUND: i32 = -32601;
+	matches!(
+		error.downcast_ref::<Error>(),
+		Some(Error::Rpc(RpcError { code: METHOD_NOT_FOUND, .. }))
+	)
 }
 
 #[cfg(test)]
@@ -404,6 +499,7 @@ mod tests {
 				http_endpoint: "http://localhost:8332".into(),
 				basic_auth_user: "flip".to_string(),
 				basic_auth_password: "flip".to_string(),
+				rate_limit: None,
 			},
 			Some(BitcoinNetwork::Regtest),
 		)
@@ -434,6 +530,10 @@ mod tests {
 
 		println!("average_block_fee_rate: {average_block_fee_rate}");
 
+		let block_filter = client.block_filter(best_block_hash).await.unwrap();
+
+		println!("block_filter: {}", hex::encode(block_filter));
+
 		// Generate new hex bytes using ./bouncer/commands/create_raw_btc_tx.ts;
 		// let hex_str =
 		// "0200000000010133e287d3a464b226a1917303e1714af508a6bfe219265184a93c7f78851085a30000000000fdffffff0200e1f505000000001976a9149a1c78a507689f6f54b847ad1cef1e614ee23f1e88ac58d94a1f000000001600145baa7941ea1268fbd6279a0408a0419f8acd8245024730440220590dcc64661a362b54543f66d3cd24fdeae8a210643ad4f6fd39031281a9657902201f7a750d01f7cfc948ae4f8b76221b5c325f8ff82ac1b0d1d9a927632b40dd6001210386dc234ecbc4e677b927da260349cbd399c622507feb9dd2895a3537f6d4aa5d00000000";
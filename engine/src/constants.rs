@@ -25,6 +25,12 @@ pub const ETH_WS_ENDPOINT: &str = "ETH__RPC__WS_ENDPOINT";
 pub const ETH_BACKUP_HTTP_ENDPOINT: &str = "ETH__BACKUP_RPC__HTTP_ENDPOINT";
 pub const ETH_BACKUP_WS_ENDPOINT: &str = "ETH__BACKUP_RPC__WS_ENDPOINT";
 
+pub const ARB_HTTP_ENDPOINT: &str = "ARB__RPC__HTTP_ENDPOINT";
+pub const ARB_WS_ENDPOINT: &str = "ARB__RPC__WS_ENDPOINT";
+
+pub const ARB_BACKUP_HTTP_ENDPOINT: &str = "ARB__BACKUP_RPC__HTTP_ENDPOINT";
+pub const ARB_BACKUP_WS_ENDPOINT: &str = "ARB__BACKUP_RPC__WS_ENDPOINT";
+
 pub const BTC_HTTP_ENDPOINT: &str = "BTC__RPC__HTTP_ENDPOINT";
 pub const BTC_RPC_USER: &str = "BTC__RPC__BASIC_AUTH_USER";
 pub const BTC_RPC_PASSWORD: &str = "BTC__RPC__BASIC_AUTH_PASSWORD";
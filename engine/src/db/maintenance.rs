@@ -0,0 +1,69 @@
+//! Keeps the [PersistentKeyDB] tidy while the engine is running: the processed block records of
+//! expired epochs are pruned as soon as the State Chain expires the epoch, as are their key shares
+//! if the engine is configured to do so, and a snapshot of the database is taken whenever the
+//! engine receives a `SIGUSR1` signal. Snapshots can also be taken with the `db snapshot` command
+//! while the engine is running.
+
+use std::{path::PathBuf, sync::Arc};
+
+use anyhow::Context;
+use futures::StreamExt;
+use tokio::signal::unix::{signal, SignalKind};
+use tracing::{error, info};
+
+use crate::{
+	state_chain_observer::client::{storage_api::StorageApi, StateChainStreamApi},
+	witness::common::STATE_CHAIN_CONNECTION,
+};
+
+use super::{KeySharePruning, PersistentKeyDB};
+
+#[tracing::instrument(name = "db-maintenance", skip_all)]
+pub async fn start<StateChainStream, StateChainClient>(
+	db: Arc<PersistentKeyDB>,
+	db_path: PathBuf,
+	prune_expired_key_shares: bool,
+	state_chain_client: Arc<StateChainClient>,
+	mut state_chain_stream: StateChainStream,
+) -> anyhow::Result<()>
+where
+	StateChainStream: StateChainStreamApi,
+	StateChainClient: StorageApi + Send + Sync + 'static,
+{
+	let mut snapshot_requests =
+		signal(SignalKind::user_defined1()).context("Failed to listen for snapshot requests")?;
+	let mut pruned_up_to_epoch = None;
+
+	info!("Starting");
+
+	utilities::loop_select!(
+		if let Some(block) = state_chain_stream.next() => {
+			let last_expired_epoch = state_chain_client
+				.storage_value::<pallet_cf_validator::LastExpiredEpoch<state_chain_runtime::Runtime>>(
+					block.hash,
+				)
+				.await
+				.expect(STATE_CHAIN_CONNECTION);
+
+			if pruned_up_to_epoch != Some(last_expired_epoch) {
+				// A failure here is not fatal, pruning will be retried on the next block
+				let key_share_pruning = if prune_expired_key_shares {
+					KeySharePruning::SnapshotAndRemove { db_path: &db_path }
+				} else {
+					KeySharePruning::Keep
+				};
+				match db.prune_expired_epochs(last_expired_epoch, key_share_pruning) {
+					Ok(_) => pruned_up_to_epoch = Some(last_expired_epoch),
+					Err(e) => error!("Failed to prune expired epochs: {e:?}"),
+				}
+			}
+		},
+		if let Some(()) = snapshot_requests.recv() => {
+			if let Err(e) = db.create_snapshot(&db_path, None) {
+				error!("Failed to create database snapshot: {e:?}");
+			}
+		},
+	);
+
+	Ok(())
+}
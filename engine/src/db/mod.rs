@@ -1,7 +1,16 @@
+pub mod maintenance;
 pub mod persistent;
-use std::{collections::HashMap, sync::Arc};
+use std::{
+	collections::{BTreeMap, HashMap},
+	sync::Arc,
+};
+
+use cf_primitives::CeremonyId;
 
-pub use persistent::PersistentKeyDB;
+pub use persistent::{
+	verify_key_backup, DbSummary, EncryptionSecret, KeyBackupSummary, KeySharePruning,
+	PersistentKeyDB, PruneSummary,
+};
 
 use multisig::{
 	client::{key_store_api::KeyStoreAPI, KeygenResultInfo},
@@ -14,13 +23,18 @@ where
 	C: ChainSigning,
 {
 	keys: HashMap<KeyId, KeygenResultInfo<C::CryptoScheme>>,
+	pending_share_refreshes: BTreeMap<CeremonyId, (KeyId, KeygenResultInfo<C::CryptoScheme>)>,
 	db: Arc<PersistentKeyDB>,
 }
 
 impl<C: ChainSigning> KeyStore<C> {
 	/// Load the keys from persistent memory and put them into a new keystore
 	pub fn new(db: Arc<PersistentKeyDB>) -> Self {
-		KeyStore { keys: db.load_keys::<C>(), db }
+		KeyStore {
+			keys: db.load_keys::<C>(),
+			pending_share_refreshes: db.load_pending_share_refreshes::<C>(),
+			db,
+		}
 	}
 }
 
@@ -33,6 +47,25 @@ impl<C: ChainSigning> KeyStoreAPI<C> for KeyStore<C> {
 		self.db.update_key::<C>(&key_id, &key);
 		self.keys.insert(key_id, key);
 	}
+
+	fn set_pending_share_refresh(
+		&mut self,
+		ceremony_id: CeremonyId,
+		key_id: KeyId,
+		key: KeygenResultInfo<C::CryptoScheme>,
+	) {
+		self.db.update_pending_share_refresh::<C>(ceremony_id, &key_id, &key);
+		self.pending_share_refreshes.insert(ceremony_id, (key_id, key));
+	}
+
+	fn take_pending_share_refresh(
+		&mut self,
+		ceremony_id: CeremonyId,
+	) -> Option<(KeyId, KeygenResultInfo<C::CryptoScheme>)> {
+		let pending_share_refresh = self.pending_share_refreshes.remove(&ceremony_id)?;
+		self.db.delete_pending_share_refresh::<C>(ceremony_id);
+		Some(pending_share_refresh)
+	}
 }
 
 #[cfg(test)]
@@ -88,4 +121,43 @@ mod tests {
 			stored_keygen_result_info
 		);
 	}
+
+	#[tokio::test]
+	async fn should_keep_pending_share_refreshes_until_taken() {
+		const CEREMONY_ID: CeremonyId = 7;
+
+		let (public_key, key_data) = keygen::generate_key_data::<EvmCryptoScheme>(
+			BTreeSet::from([AccountId::new([1; 32])]),
+			&mut Rng::from_entropy(),
+		);
+		let refreshed_keygen_result_info = key_data.values().next().unwrap().clone();
+		let key_id = KeyId::new(0, public_key);
+
+		let (_dir, db_file) = utilities::testing::new_temp_directory_with_nonexistent_file();
+		let open_key_store = || {
+			KeyStore::<EthSigning>::new(Arc::new(
+				PersistentKeyDB::open_and_migrate_to_latest(&db_file, None)
+					.expect("Failed to open database"),
+			))
+		};
+
+		open_key_store().set_pending_share_refresh(
+			CEREMONY_ID,
+			key_id.clone(),
+			refreshed_keygen_result_info.clone(),
+		);
+
+		// The refreshed share survives a restart, but is only returned once
+		let mut key_store = open_key_store();
+		assert!(key_store.take_pending_share_refresh(CEREMONY_ID + 1).is_none());
+		assert_eq!(
+			key_store.take_pending_share_refresh(CEREMONY_ID),
+			Some((key_id, refreshed_keygen_result_info))
+		);
+		drop(key_store);
+
+		assert!(open_key_store().take_pending_share_refresh(CEREMONY_ID).is_none());
+		// The refreshed share is not a key until the refresh is completed
+		assert!(open_key_store().keys.is_empty());
+	}
 }
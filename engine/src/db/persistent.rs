@@ -1,32 +1,51 @@
+mod backup;
+mod encryption;
+mod housekeeping;
 mod rocksdb_kv;
 #[cfg(test)]
 mod tests;
 
-use std::{cmp::Ordering, collections::HashMap, path::Path};
+use std::{
+	cmp::Ordering,
+	collections::{BTreeMap, HashMap},
+	path::{Path, PathBuf},
+};
 
-use cf_primitives::EpochIndex;
-use serde::{de::DeserializeOwned, Serialize};
-use tracing::{debug, info, info_span};
+use cf_primitives::{CeremonyId, EpochIndex};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use tracing::{debug, error, info, info_span, warn};
 use utilities::rle_bitmap::RleBitmap;
+use zeroize::Zeroizing;
 
 use multisig::{client::KeygenResultInfo, ChainSigning, KeyId, CHAIN_TAG_SIZE};
 
 use anyhow::{anyhow, bail, Context, Result};
 
+use encryption::{DataKey, EncryptedData, WrappedDataKey};
 use rocksdb_kv::{RocksDBKeyValueStore, PREFIX_SIZE};
 
-/// Name of the directory that the backups will go into (only created before migrations)
+pub use backup::{verify_key_backup, KeyBackupSummary};
+pub use encryption::EncryptionSecret;
+pub use housekeeping::{
+	DbSummary, KeyShareEntry, KeySharePruning, ProcessedBlocksEntry, PruneSummary,
+};
+
+/// Name of the directory that the backups will go into (created before migrations, and by default
+/// also used for snapshots)
 const BACKUPS_DIRECTORY: &str = "backups";
 
 /// This is the version of the data on this current branch
 /// This version *must* be bumped, and appropriate migrations
 /// written on any changes to the persistent application data format
-const LATEST_SCHEMA_VERSION: u32 = 0;
+const LATEST_SCHEMA_VERSION: u32 = 1;
 
 const PARTIAL_PREFIX_SIZE: usize = PREFIX_SIZE - CHAIN_TAG_SIZE;
 
 /// Keygen data uses a prefix that is a combination of a keygen data prefix and the chain tag
 const KEYGEN_DATA_PARTIAL_PREFIX: &[u8; PARTIAL_PREFIX_SIZE] = b"key_____";
+/// Refreshed key shares that are waiting for the share refresh to complete use a prefix that is a
+/// combination of a prefix and the chain tag
+const PENDING_SHARE_REFRESH_PARTIAL_PREFIX: &[u8; PARTIAL_PREFIX_SIZE] = b"refresh_";
 /// The continuous adapter uses a prefix that is a combination of a prefix, and the
 /// witnesser name
 const PROCESSED_BLOCKS_PARTIAL_PREFIX: &[u8; PARTIAL_PREFIX_SIZE] = b"seen____";
@@ -34,6 +53,9 @@ const PROCESSED_BLOCKS_PARTIAL_PREFIX: &[u8; PARTIAL_PREFIX_SIZE] = b"seen____";
 /// Key used to store the `LATEST_SCHEMA_VERSION` value in the `METADATA_COLUMN`
 const DB_SCHEMA_VERSION_KEY: &[u8; 17] = b"db_schema_version";
 const GENESIS_HASH_KEY: &[u8; 12] = b"genesis_hash";
+/// Key used to store the [WrappedDataKey] in the `METADATA_COLUMN`. Only present if the key shares
+/// in the db are encrypted.
+const WRAPPED_DATA_KEY_KEY: &[u8; 16] = b"wrapped_data_key";
 
 /// Used to specify whether a backup should be created, and if so,
 /// the provided path is used to derive the name of the backup
@@ -42,10 +64,47 @@ enum BackupOption<'a> {
 	CreateBackup(&'a Path),
 }
 
+/// The format that key shares are stored in since schema version 1. The key share itself is the
+/// bincode serialized `KeygenResultInfo`.
+#[derive(Serialize, Deserialize)]
+enum StoredKeyShare {
+	Plaintext(Vec<u8>),
+	/// Encrypted with the data key, using the db key the share is stored under as associated data.
+	Encrypted(EncryptedData),
+}
+
+impl StoredKeyShare {
+	fn new(data_key: Option<&DataKey>, key_share: &[u8], db_key: &[u8]) -> Self {
+		match data_key {
+			Some(data_key) => StoredKeyShare::Encrypted(data_key.encrypt(key_share, db_key)),
+			None => StoredKeyShare::Plaintext(key_share.to_vec()),
+		}
+	}
+
+	fn into_plaintext(
+		self,
+		data_key: Option<&DataKey>,
+		db_key: &[u8],
+	) -> Result<Zeroizing<Vec<u8>>> {
+		match self {
+			StoredKeyShare::Plaintext(key_share) => Ok(Zeroizing::new(key_share)),
+			StoredKeyShare::Encrypted(encrypted_key_share) => Ok(Zeroizing::new(
+				data_key
+					.ok_or_else(|| {
+						anyhow!("Key share is encrypted, but no encryption secret was provided")
+					})?
+					.decrypt(&encrypted_key_share, db_key)?,
+			)),
+		}
+	}
+}
+
 /// Database for keys and persistent metadata
 pub struct PersistentKeyDB {
 	/// Underlying key-value database instance
 	kv_db: RocksDBKeyValueStore,
+	/// If set, key shares are encrypted with this key before being written to the db
+	data_key: Option<DataKey>,
 }
 
 impl PersistentKeyDB {
@@ -54,11 +113,48 @@ impl PersistentKeyDB {
 	pub fn open_and_migrate_to_latest(
 		db_path: &Path,
 		genesis_hash: Option<state_chain_runtime::Hash>,
+	) -> Result<Self> {
+		Self::open_and_migrate_to_latest_with_encryption(db_path, genesis_hash, None)
+	}
+
+	/// As [Self::open_and_migrate_to_latest], but if an encryption secret is provided, the key
+	/// shares in the db are encrypted at rest. Key shares in a db that was not previously encrypted
+	/// will be encrypted when it is opened.
+	pub fn open_and_migrate_to_latest_with_encryption(
+		db_path: &Path,
+		genesis_hash: Option<state_chain_runtime::Hash>,
+		encryption_secret: Option<&EncryptionSecret>,
 	) -> Result<Self> {
 		let span = info_span!("PersistentKeyDB");
 		let _entered = span.enter();
 
-		Self::open_and_migrate_to_version(db_path, genesis_hash, LATEST_SCHEMA_VERSION)
+		let mut db =
+			Self::open_and_migrate_to_version(db_path, genesis_hash, LATEST_SCHEMA_VERSION)?;
+
+		db.unlock(encryption_secret).with_context(|| {
+			format!("Failed to set up encryption of the database at {}", db_path.display())
+		})?;
+
+		if db.is_encrypted() {
+			for backup_path in Self::find_unencrypted_backups(db_path) {
+				warn!(
+					"The backup at {} contains unencrypted key shares. It can be removed with the `db remove-unencrypted-backups` command",
+					backup_path.display()
+				);
+			}
+		}
+
+		Ok(db)
+	}
+
+	/// Opens an existing key database without decrypting its key shares, for maintenance that
+	/// doesn't need to read them, such as inspecting, pruning or taking snapshots.
+	pub fn open_for_maintenance(db_path: &Path) -> Result<Self> {
+		if !db_path.exists() {
+			bail!("No database found at {}", db_path.display());
+		}
+
+		Self::open_and_migrate_to_version(db_path, None, LATEST_SCHEMA_VERSION)
 	}
 
 	/// As [Self::open_and_migrate_to_latest], but allows specifying a specific version
@@ -70,7 +166,7 @@ impl PersistentKeyDB {
 	) -> Result<Self> {
 		let is_existing_db = db_path.exists();
 
-		let db = PersistentKeyDB { kv_db: RocksDBKeyValueStore::open(db_path)? };
+		let db = PersistentKeyDB { kv_db: RocksDBKeyValueStore::open(db_path)?, data_key: None };
 
 		// Only create a backup if there is an existing db that we don't
 		// want to accidentally corrupt
@@ -79,7 +175,7 @@ impl PersistentKeyDB {
 		} else {
 			let mut batch = db.kv_db.create_batch();
 
-			batch.put_metadata(DB_SCHEMA_VERSION_KEY, 0u32.to_be_bytes());
+			batch.put_metadata(DB_SCHEMA_VERSION_KEY, version.to_be_bytes());
 
 			if let Some(genesis_hash) = genesis_hash {
 				batch.put_metadata(GENESIS_HASH_KEY, genesis_hash);
@@ -98,14 +194,164 @@ impl PersistentKeyDB {
 		Ok(db)
 	}
 
+	/// Uses the encryption secret to decrypt the data key if the db is encrypted, or enables
+	/// encryption if the db is not encrypted yet.
+	fn unlock(&mut self, encryption_secret: Option<&EncryptionSecret>) -> Result<()> {
+		match (self.get_wrapped_data_key()?, encryption_secret) {
+			(Some(wrapped_data_key), Some(secret)) => {
+				self.data_key = Some(DataKey::from_wrapped(secret, &wrapped_data_key)?);
+			},
+			(Some(_), None) => {
+				bail!("The key shares in the database are encrypted, but no encryption secret was provided")
+			},
+			(None, Some(secret)) => {
+				info!("Encrypting the key shares in the database");
+				self.change_encryption(Some(secret), true)?;
+			},
+			(None, None) => {},
+		}
+		Ok(())
+	}
+
+	/// Re-encrypts the key shares in the db so that they can only be decrypted with the new secret,
+	/// or stores them unencrypted if no new secret is provided. Unless `rotate_data_key` is set,
+	/// the key shares remain encrypted with the same data key and only the data key is
+	/// re-encrypted.
+	pub fn change_encryption(
+		&mut self,
+		new_secret: Option<&EncryptionSecret>,
+		rotate_data_key: bool,
+	) -> Result<()> {
+		// Decrypt all key shares first, so that nothing is written if any of them can't be read.
+		let key_shares = self
+			.kv_db
+			.get_raw_data_for_partial_prefix(KEYGEN_DATA_PARTIAL_PREFIX)
+			.chain(self.kv_db.get_raw_data_for_partial_prefix(PENDING_SHARE_REFRESH_PARTIAL_PREFIX))
+			.map(|(db_key, value)| {
+				let key_share = bincode::deserialize::<StoredKeyShare>(&value)
+					.context("Failed to deserialize key share")?
+					.into_plaintext(self.data_key.as_ref(), &db_key)?;
+				Ok((db_key, key_share))
+			})
+			.collect::<Result<Vec<_>>>()?;
+
+		let new_data_key = new_secret.map(|_| match &self.data_key {
+			Some(data_key) if !rotate_data_key => data_key.clone(),
+			_ => DataKey::generate(),
+		});
+
+		let mut batch = self.kv_db.create_batch();
+		for (db_key, key_share) in &key_shares {
+			batch.put_value(
+				db_key,
+				&bincode::serialize(&StoredKeyShare::new(new_data_key.as_ref(), key_share, db_key))
+					.expect("Serialization is not expected to fail"),
+			);
+		}
+		match (&new_data_key, new_secret) {
+			(Some(data_key), Some(secret)) => batch.put_metadata(
+				WRAPPED_DATA_KEY_KEY,
+				bincode::serialize(&data_key.wrap(secret)?)
+					.expect("Serialization is not expected to fail"),
+			),
+			_ => batch.delete_metadata(WRAPPED_DATA_KEY_KEY),
+		}
+		batch
+			.write()
+			.context("Failed to write re-encrypted key shares to the database")?;
+
+		self.data_key = new_data_key;
+
+		// The previously stored key shares would otherwise remain in the files of the db until
+		// they happen to be compacted away.
+		self.kv_db
+			.purge_stale_data()
+			.context("Failed to remove the previously stored key shares from the database files")?;
+
+		info!(
+			"Changed encryption of {} key shares in the database, encrypted: {}",
+			key_shares.len(),
+			self.data_key.is_some()
+		);
+
+		Ok(())
+	}
+
+	/// Returns the backups in the backups directory next to the database that contain unencrypted
+	/// key shares, i.e. those that were taken before the key shares were encrypted. Backups that
+	/// can't be inspected are logged as errors, and must be checked manually. Copies of the
+	/// database in other locations are not found.
+	pub fn find_unencrypted_backups(db_path: &Path) -> Vec<PathBuf> {
+		let backups_path = db_path.parent().expect("Should have parent").join(BACKUPS_DIRECTORY);
+		let entries = match std::fs::read_dir(&backups_path) {
+			Ok(entries) => entries,
+			Err(_) => return Vec::new(),
+		};
+
+		entries
+			.filter_map(|entry| Some(entry.ok()?.path()))
+			.filter(|backup_path| backup_path.is_dir())
+			.filter(|backup_path| {
+				match RocksDBKeyValueStore::open_read_only(backup_path)
+					.map(|kv_db| kv_db.get_metadata(WRAPPED_DATA_KEY_KEY).is_some())
+				{
+					Ok(encrypted) => !encrypted,
+					Err(e) => {
+						error!(
+							"Could not check whether the backup at {} contains unencrypted key shares. If it was taken before the key shares were encrypted, it must be removed manually: {e:#}",
+							backup_path.display()
+						);
+						false
+					},
+				}
+			})
+			.collect()
+	}
+
+	/// Removes the given backups, as found by [Self::find_unencrypted_backups]. Each removed
+	/// backup is logged.
+	pub fn remove_backups(backup_paths: &[PathBuf]) -> Result<()> {
+		for backup_path in backup_paths {
+			std::fs::remove_dir_all(backup_path).with_context(|| {
+				format!("Failed to remove the backup at {}", backup_path.display())
+			})?;
+			info!("Removed the backup at {}", backup_path.display());
+		}
+		Ok(())
+	}
+
+	pub fn is_encrypted(&self) -> bool {
+		self.data_key.is_some()
+	}
+
+	fn get_wrapped_data_key(&self) -> Result<Option<WrappedDataKey>> {
+		self.kv_db
+			.get_metadata(WRAPPED_DATA_KEY_KEY)
+			.map(|wrapped_data_key| {
+				bincode::deserialize(&wrapped_data_key).context("Failed to deserialize data key")
+			})
+			.transpose()
+	}
+
 	/// Write the keyshare to the db, indexed by the key id
 	pub fn update_key<C: ChainSigning>(
 		&self,
 		key_id: &KeyId,
 		keygen_result_info: &KeygenResultInfo<C::CryptoScheme>,
 	) {
+		let key_share = Zeroizing::new(
+			bincode::serialize(keygen_result_info).expect("Serialization is not expected to fail"),
+		);
 		self.kv_db
-			.put_data(&keygen_data_prefix::<C>(), &key_id, &keygen_result_info)
+			.put_data(
+				&keygen_data_prefix::<C>(),
+				&key_id,
+				&StoredKeyShare::new(
+					self.data_key.as_ref(),
+					&key_share,
+					&keygen_data_db_key::<C>(key_id),
+				),
+			)
 			.unwrap_or_else(|e| panic!("Failed to update key {}. Error: {}", &key_id, e));
 	}
 
@@ -113,8 +359,19 @@ impl PersistentKeyDB {
 		let span = info_span!("PersistentKeyDB");
 		let _entered = span.enter();
 
-		let keys: HashMap<_, _> =
-			self.kv_db.get_data_for_prefix(&keygen_data_prefix::<C>()).collect();
+		let keys: HashMap<_, _> = self
+			.kv_db
+			.get_data_for_prefix::<KeyId, StoredKeyShare>(&keygen_data_prefix::<C>())
+			.map(|(key_id, stored_key_share)| {
+				let key_share = stored_key_share
+					.into_plaintext(self.data_key.as_ref(), &keygen_data_db_key::<C>(&key_id))
+					.and_then(|key_share| {
+						bincode::deserialize(&key_share).context("Deserialization failed")
+					})
+					.unwrap_or_else(|e| panic!("Failed to load key {}. Error: {}", &key_id, e));
+				(key_id, key_share)
+			})
+			.collect();
 
 		for key in &keys {
 			tracing::trace!("Loaded {} key from the database: {}", C::NAME, key.0);
@@ -127,6 +384,66 @@ impl PersistentKeyDB {
 		keys
 	}
 
+	/// Write a refreshed key share to the db, where it is kept until the share refresh ceremony
+	/// `ceremony_id` is completed.
+	pub fn update_pending_share_refresh<C: ChainSigning>(
+		&self,
+		ceremony_id: CeremonyId,
+		key_id: &KeyId,
+		keygen_result_info: &KeygenResultInfo<C::CryptoScheme>,
+	) {
+		let pending_share_refresh = Zeroizing::new(
+			bincode::serialize(&(key_id, keygen_result_info))
+				.expect("Serialization is not expected to fail"),
+		);
+		self.kv_db
+			.put_data(
+				&pending_share_refresh_prefix::<C>(),
+				&ceremony_id,
+				&StoredKeyShare::new(
+					self.data_key.as_ref(),
+					&pending_share_refresh,
+					&pending_share_refresh_db_key::<C>(ceremony_id),
+				),
+			)
+			.unwrap_or_else(|e| {
+				panic!("Failed to store refreshed key share for ceremony {ceremony_id}. Error: {e}")
+			});
+	}
+
+	pub fn delete_pending_share_refresh<C: ChainSigning>(&self, ceremony_id: CeremonyId) {
+		let mut batch = self.kv_db.create_batch();
+		batch.delete_value(&pending_share_refresh_db_key::<C>(ceremony_id));
+		batch.write().unwrap_or_else(|e| {
+			panic!("Failed to delete refreshed key share for ceremony {ceremony_id}. Error: {e}")
+		});
+	}
+
+	pub fn load_pending_share_refreshes<C: ChainSigning>(
+		&self,
+	) -> BTreeMap<CeremonyId, (KeyId, KeygenResultInfo<C::CryptoScheme>)> {
+		self.kv_db
+			.get_data_for_prefix::<CeremonyId, StoredKeyShare>(&pending_share_refresh_prefix::<C>())
+			.map(|(ceremony_id, stored_key_share)| {
+				let pending_share_refresh = stored_key_share
+					.into_plaintext(
+						self.data_key.as_ref(),
+						&pending_share_refresh_db_key::<C>(ceremony_id),
+					)
+					.and_then(|pending_share_refresh| {
+						bincode::deserialize(&pending_share_refresh)
+							.context("Deserialization failed")
+					})
+					.unwrap_or_else(|e| {
+						panic!(
+							"Failed to load refreshed key share for ceremony {ceremony_id}. Error: {e}"
+						)
+					});
+				(ceremony_id, pending_share_refresh)
+			})
+			.collect()
+	}
+
 	pub fn update_processed_blocks<Index: Ord + Serialize>(
 		&self,
 		witnesser_name: &str,
@@ -192,6 +509,28 @@ fn keygen_data_prefix<C: ChainSigning>() -> Vec<u8> {
 	[&KEYGEN_DATA_PARTIAL_PREFIX[..], &(C::CHAIN_TAG.to_bytes())[..]].concat()
 }
 
+/// The full db key that a key share is stored under
+fn keygen_data_db_key<C: ChainSigning>(key_id: &KeyId) -> Vec<u8> {
+	[
+		keygen_data_prefix::<C>(),
+		bincode::serialize(key_id).expect("Serialization is not expected to fail."),
+	]
+	.concat()
+}
+
+fn pending_share_refresh_prefix<C: ChainSigning>() -> Vec<u8> {
+	[&PENDING_SHARE_REFRESH_PARTIAL_PREFIX[..], &(C::CHAIN_TAG.to_bytes())[..]].concat()
+}
+
+/// The full db key that a refreshed key share is stored under
+fn pending_share_refresh_db_key<C: ChainSigning>(ceremony_id: CeremonyId) -> Vec<u8> {
+	[
+		pending_share_refresh_prefix::<C>(),
+		bincode::serialize(&ceremony_id).expect("Serialization is not expected to fail."),
+	]
+	.concat()
+}
+
 fn processed_blocks_prefix(witnessner_name: &str) -> Vec<u8> {
 	[PROCESSED_BLOCKS_PARTIAL_PREFIX, witnessner_name.as_bytes()].concat()
 }
@@ -248,8 +587,10 @@ fn migrate_db_to_version(
 
 			for version in current_version..target_version {
 				info!("Database is migrating from version {version} to {}", version + 1);
-				// Once we have migrations, we will match on the version here
-				panic!("Unexpected migration from version {version}");
+				match version {
+					0 => migrate_0_to_1(db)?,
+					_ => panic!("Unexpected migration from version {version}"),
+				}
 			}
 
 			Ok(())
@@ -257,6 +598,24 @@ fn migrate_db_to_version(
 	}
 }
 
+/// Wraps all key shares in [StoredKeyShare], so that they can be encrypted.
+fn migrate_0_to_1(db: &PersistentKeyDB) -> Result<()> {
+	let mut batch = db.kv_db.create_batch();
+
+	for (db_key, key_share) in db.kv_db.get_raw_data_for_partial_prefix(KEYGEN_DATA_PARTIAL_PREFIX)
+	{
+		batch.put_value(
+			&db_key,
+			&bincode::serialize(&StoredKeyShare::Plaintext(key_share.into_vec()))
+				.expect("Serialization is not expected to fail"),
+		);
+	}
+
+	batch.put_metadata(DB_SCHEMA_VERSION_KEY, 1u32.to_be_bytes());
+
+	batch.write().context("Failed to migrate database from version 0 to 1")
+}
+
 // Creates a backup of the database folder to BACKUPS_DIRECTORY/backup_vx_xx_xx
 fn create_backup(path: &Path, schema_version: u32) -> Result<String, anyhow::Error> {
 	// Build the name for the new backup using the schema version and a timestamp
@@ -0,0 +1,304 @@
+//! Encrypted backups of the key shares in the [PersistentKeyDB], so that they can be moved between
+//! hosts or restored after a failure without copying the whole database.
+//!
+//! A backup contains the key shares of a single chain, along with the schema version and genesis
+//! hash of the database they were exported from. The contents are encrypted with a fresh data key,
+//! which is stored in the backup encrypted with a key derived from the backup secret. Since the
+//! encryption is authenticated, a backup that decrypts successfully has not been modified.
+
+use anyhow::{anyhow, bail, ensure, Context, Result};
+use cf_primitives::ForeignChain;
+use multisig::{
+	bitcoin::BtcSigning,
+	client::KeygenResultInfo,
+	eth::{ArbSigning, EthSigning},
+	polkadot::PolkadotSigning,
+	ChainSigning, ChainTag, KeyId, CHAIN_TAG_SIZE,
+};
+use num_traits::FromPrimitive;
+use serde::{Deserialize, Serialize};
+use tracing::info;
+use zeroize::Zeroizing;
+
+use super::{
+	encryption::{DataKey, EncryptedData, EncryptionSecret, WrappedDataKey},
+	keygen_data_db_key, PersistentKeyDB, StoredKeyShare,
+};
+
+/// Identifies a file as a key share backup
+const BACKUP_MAGIC: &[u8; 8] = b"cfkeybak";
+
+/// This version *must* be bumped on any change to the format of [KeyBackupFile] or
+/// [KeyBackupContents].
+const BACKUP_FORMAT_VERSION: u32 = 1;
+
+#[derive(Serialize, Deserialize)]
+struct KeyBackupFile {
+	format_version: u32,
+	wrapped_data_key: WrappedDataKey,
+	/// The serialized [KeyBackupContents], encrypted with the data key
+	contents: EncryptedData,
+}
+
+#[derive(Serialize, Deserialize)]
+struct KeyBackupContents {
+	chain_tag: [u8; CHAIN_TAG_SIZE],
+	/// Schema version of the database the keys were exported from
+	schema_version: u32,
+	genesis_hash: Option<state_chain_runtime::Hash>,
+	/// The serialized `KeygenResultInfo` of each key
+	key_shares: Vec<(KeyId, Vec<u8>)>,
+}
+
+/// Describes the contents of a backup, as found by [verify_key_backup].
+#[derive(Serialize, Debug, Clone, PartialEq, Eq)]
+pub struct KeyBackupSummary {
+	pub chain: String,
+	pub schema_version: u32,
+	pub genesis_hash: Option<state_chain_runtime::Hash>,
+	pub key_ids: Vec<KeyId>,
+}
+
+/// The data that the contents are authenticated with, so that the header can't be swapped.
+fn backup_associated_data(format_version: u32) -> Vec<u8> {
+	[&BACKUP_MAGIC[..], &format_version.to_be_bytes()[..]].concat()
+}
+
+fn chain_tag_of_backup(contents: &KeyBackupContents) -> Result<ChainTag> {
+	ChainTag::from_u16(u16::from_be_bytes(contents.chain_tag))
+		.ok_or_else(|| anyhow!("The backup contains keys for an unknown chain"))
+}
+
+fn encode_backup(contents: &KeyBackupContents, secret: &EncryptionSecret) -> Result<Vec<u8>> {
+	let data_key = DataKey::generate();
+	let serialized_contents = Zeroizing::new(
+		bincode::serialize(contents).expect("Serialization is not expected to fail"),
+	);
+
+	let backup_file = KeyBackupFile {
+		format_version: BACKUP_FORMAT_VERSION,
+		wrapped_data_key: data_key.wrap(secret)?,
+		contents: data_key
+			.encrypt(&serialized_contents, &backup_associated_data(BACKUP_FORMAT_VERSION)),
+	};
+
+	Ok([
+		&BACKUP_MAGIC[..],
+		&bincode::serialize(&backup_file).expect("Serialization is not expected to fail"),
+	]
+	.concat())
+}
+
+fn decode_backup(backup: &[u8], secret: &EncryptionSecret) -> Result<KeyBackupContents> {
+	let backup_file = backup
+		.strip_prefix(&BACKUP_MAGIC[..])
+		.ok_or_else(|| anyhow!("Not a key share backup file"))?;
+	let backup_file: KeyBackupFile =
+		bincode::deserialize(backup_file).context("The backup file is corrupted")?;
+
+	ensure!(
+		backup_file.format_version == BACKUP_FORMAT_VERSION,
+		"Unsupported backup format version {}, expected {}",
+		backup_file.format_version,
+		BACKUP_FORMAT_VERSION
+	);
+
+	let data_key = DataKey::from_wrapped(secret, &backup_file.wrapped_data_key)?;
+	let contents = Zeroizing::new(
+		data_key
+			.decrypt(&backup_file.contents, &backup_associated_data(backup_file.format_version))
+			.context("The backup file is corrupted")?,
+	);
+
+	bincode::deserialize(&contents).context("The backup file is corrupted")
+}
+
+/// Checks that every key share in the backup is valid for the chain
+fn decode_key_shares<C: ChainSigning>(
+	contents: &KeyBackupContents,
+) -> Result<Vec<(KeyId, KeygenResultInfo<C::CryptoScheme>)>> {
+	contents
+		.key_shares
+		.iter()
+		.map(|(key_id, key_share)| {
+			Ok((
+				key_id.clone(),
+				bincode::deserialize(key_share)
+					.with_context(|| format!("Invalid {} key share for {key_id}", C::NAME))?,
+			))
+		})
+		.collect()
+}
+
+/// Decrypts the backup and checks that all of the key shares in it are valid, without needing
+/// access to a database.
+pub fn verify_key_backup(backup: &[u8], secret: &EncryptionSecret) -> Result<KeyBackupSummary> {
+	let contents = decode_backup(backup, secret)?;
+	let chain_tag = chain_tag_of_backup(&contents)?;
+
+	match chain_tag {
+		ChainTag::Ethereum => {
+			decode_key_shares::<EthSigning>(&contents)?;
+		},
+		ChainTag::Polkadot => {
+			decode_key_shares::<PolkadotSigning>(&contents)?;
+		},
+		ChainTag::Bitcoin => {
+			decode_key_shares::<BtcSigning>(&contents)?;
+		},
+		ChainTag::Arbitrum => {
+			decode_key_shares::<ArbSigning>(&contents)?;
+		},
+		ChainTag::Ed25519 => bail!("Backups of {chain_tag} keys are not supported"),
+	}
+
+	Ok(KeyBackupSummary {
+		chain: chain_tag.to_string(),
+		schema_version: contents.schema_version,
+		genesis_hash: contents.genesis_hash,
+		key_ids: contents.key_shares.into_iter().map(|(key_id, _)| key_id).collect(),
+	})
+}
+
+impl PersistentKeyDB {
+	/// Creates an encrypted backup of the chain's key shares with the given key ids, or of all of
+	/// the chain's key shares if no key ids are given.
+	pub fn export_keys(
+		&self,
+		chain: ForeignChain,
+		key_ids: &[KeyId],
+		secret: &EncryptionSecret,
+	) -> Result<Vec<u8>> {
+		match chain {
+			ForeignChain::Ethereum => self.export_chain_keys::<EthSigning>(key_ids, secret),
+			ForeignChain::Polkadot => self.export_chain_keys::<PolkadotSigning>(key_ids, secret),
+			ForeignChain::Bitcoin => self.export_chain_keys::<BtcSigning>(key_ids, secret),
+			ForeignChain::Arbitrum => self.export_chain_keys::<ArbSigning>(key_ids, secret),
+		}
+	}
+
+	fn export_chain_keys<C: ChainSigning>(
+		&self,
+		key_ids: &[KeyId],
+		secret: &EncryptionSecret,
+	) -> Result<Vec<u8>> {
+		let mut keys = self.load_keys::<C>();
+
+		let key_shares = if key_ids.is_empty() {
+			keys.into_iter().collect::<Vec<_>>()
+		} else {
+			key_ids
+				.iter()
+				.map(|key_id| {
+					keys.remove_entry(key_id)
+						.ok_or_else(|| anyhow!("No {} key {key_id} in the database", C::NAME))
+				})
+				.collect::<Result<Vec<_>>>()?
+		};
+		ensure!(!key_shares.is_empty(), "There are no {} keys in the database", C::NAME);
+
+		let contents = KeyBackupContents {
+			chain_tag: C::CHAIN_TAG.to_bytes(),
+			schema_version: self.get_schema_version()?,
+			genesis_hash: self.get_genesis_hash()?,
+			key_shares: key_shares
+				.iter()
+				.map(|(key_id, key_share)| {
+					(
+						key_id.clone(),
+						bincode::serialize(key_share)
+							.expect("Serialization is not expected to fail"),
+					)
+				})
+				.collect(),
+		};
+
+		info!("Exporting {} {} keys", contents.key_shares.len(), C::NAME);
+
+		encode_backup(&contents, secret)
+	}
+
+	/// Imports the key shares from a backup created with [Self::export_keys]. The backup must
+	/// have been created from a database with the same schema version and genesis hash. Unless
+	/// `allow_unknown_genesis_hash` is set, backups from databases that don't record their genesis
+	/// hash are refused. None of the keys may already be in the database. Returns the ids of the
+	/// imported keys.
+	pub fn import_keys(
+		&self,
+		backup: &[u8],
+		secret: &EncryptionSecret,
+		allow_unknown_genesis_hash: bool,
+	) -> Result<Vec<KeyId>> {
+		let contents = decode_backup(backup, secret)?;
+
+		let schema_version = self.get_schema_version()?;
+		ensure!(
+			contents.schema_version == schema_version,
+			"The backup was exported from a database with schema version {}, but this database has schema version {}. Use an engine with the same schema version to import it.",
+			contents.schema_version,
+			schema_version
+		);
+
+		let genesis_hash = self.get_genesis_hash()?;
+		match (contents.genesis_hash, genesis_hash) {
+			(Some(backup_genesis_hash), Some(genesis_hash)) => ensure!(
+				backup_genesis_hash == genesis_hash,
+				"Genesis hash mismatch. The backup was exported for a different Chainflip network."
+			),
+			(None, _) => ensure!(
+				allow_unknown_genesis_hash,
+				"The backup doesn't record which Chainflip network it was exported for, so it can't be checked against this database."
+			),
+			(Some(_), None) => {},
+		}
+
+		let imported_key_ids = match chain_tag_of_backup(&contents)? {
+			ChainTag::Ethereum => self.import_chain_keys::<EthSigning>(&contents),
+			ChainTag::Polkadot => self.import_chain_keys::<PolkadotSigning>(&contents),
+			ChainTag::Bitcoin => self.import_chain_keys::<BtcSigning>(&contents),
+			ChainTag::Arbitrum => self.import_chain_keys::<ArbSigning>(&contents),
+			chain_tag @ ChainTag::Ed25519 => bail!("Backups of {chain_tag} keys are not supported"),
+		}?;
+
+		// A fresh database won't know which network it belongs to yet
+		if let (Some(backup_genesis_hash), None) = (contents.genesis_hash, genesis_hash) {
+			self.put_genesis_hash(backup_genesis_hash)?;
+		}
+
+		Ok(imported_key_ids)
+	}
+
+	fn import_chain_keys<C: ChainSigning>(
+		&self,
+		contents: &KeyBackupContents,
+	) -> Result<Vec<KeyId>> {
+		let key_shares = decode_key_shares::<C>(contents)?;
+
+		let existing_keys = self.load_keys::<C>();
+		if let Some((key_id, _)) =
+			key_shares.iter().find(|(key_id, _)| existing_keys.contains_key(key_id))
+		{
+			bail!("The {} key {key_id} is already in the database", C::NAME);
+		}
+
+		// Write all keys at once, so that a failed import doesn't leave some of them behind
+		let mut batch = self.kv_db.create_batch();
+		for (key_id, key_share) in &contents.key_shares {
+			let db_key = keygen_data_db_key::<C>(key_id);
+			batch.put_value(
+				&db_key,
+				&bincode::serialize(&StoredKeyShare::new(
+					self.data_key.as_ref(),
+					key_share,
+					&db_key,
+				))
+				.expect("Serialization is not expected to fail"),
+			);
+		}
+		batch.write().context("Failed to write imported keys to the database")?;
+
+		info!("Imported {} {} keys", key_shares.len(), C::NAME);
+
+		Ok(key_shares.into_iter().map(|(key_id, _)| key_id).collect())
+	}
+}
@@ -0,0 +1,169 @@
+//! Envelope encryption of the key shares stored in the [PersistentKeyDB](super::PersistentKeyDB).
+//!
+//! Key shares are encrypted with a randomly generated data key. The data key itself is stored in
+//! the database, encrypted with a key-encryption key that is derived from a user provided secret
+//! using Argon2id. This means that changing the secret only requires re-encrypting the data key.
+
+use std::path::PathBuf;
+
+use anyhow::{anyhow, Context, Result};
+use argon2::{Algorithm, Argon2, Params, Version};
+use chacha20poly1305::{
+	aead::{Aead, AeadCore, KeyInit, OsRng, Payload},
+	XChaCha20Poly1305, XNonce,
+};
+use rand::RngCore;
+use serde::{Deserialize, Serialize};
+use zeroize::Zeroizing;
+
+const KEY_SIZE: usize = 32;
+const SALT_SIZE: usize = 16;
+
+// Argon2id parameters used to derive the key-encryption key. These must not be changed without a
+// migration, since existing databases could no longer be decrypted.
+const ARGON2_MEMORY_COST_KIB: u32 = 19 * 1024;
+const ARGON2_ITERATIONS: u32 = 2;
+const ARGON2_PARALLELISM: u32 = 1;
+
+/// Associated data used when encrypting the data key, binding its ciphertext to its purpose.
+const DATA_KEY_ASSOCIATED_DATA: &[u8] = b"chainflip_key_db_data_key";
+
+/// The secret that the key-encryption key is derived from.
+#[derive(Clone)]
+pub enum EncryptionSecret {
+	Passphrase(Zeroizing<String>),
+	/// The entire contents of the file are used as the secret.
+	KeyFile(PathBuf),
+}
+
+impl std::fmt::Debug for EncryptionSecret {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		match self {
+			EncryptionSecret::Passphrase(_) => write!(f, "Passphrase(<redacted>)"),
+			EncryptionSecret::KeyFile(path) => write!(f, "KeyFile({})", path.display()),
+		}
+	}
+}
+
+impl EncryptionSecret {
+	fn read(&self) -> Result<Zeroizing<Vec<u8>>> {
+		let secret = match self {
+			EncryptionSecret::Passphrase(passphrase) =>
+				Zeroizing::new(passphrase.as_bytes().to_vec()),
+			EncryptionSecret::KeyFile(path) =>
+				Zeroizing::new(std::fs::read(path).with_context(|| {
+					format!("Failed to read db encryption key file {}", path.display())
+				})?),
+		};
+		if secret.is_empty() {
+			return Err(anyhow!("The db encryption secret must not be empty"))
+		}
+		Ok(secret)
+	}
+}
+
+/// Data encrypted with XChaCha20Poly1305, as it is stored in the database.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct EncryptedData {
+	nonce: [u8; 24],
+	ciphertext: Vec<u8>,
+}
+
+/// The data key, encrypted with the key-encryption key, along with the salt needed to derive the
+/// key-encryption key from the secret.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct WrappedDataKey {
+	salt: [u8; SALT_SIZE],
+	key: EncryptedData,
+}
+
+/// The key that key shares are encrypted with.
+#[derive(Clone)]
+pub struct DataKey {
+	key: Zeroizing<[u8; KEY_SIZE]>,
+}
+
+impl DataKey {
+	pub fn generate() -> Self {
+		let mut key = Zeroizing::new([0u8; KEY_SIZE]);
+		OsRng.fill_bytes(&mut key[..]);
+		DataKey { key }
+	}
+
+	pub fn encrypt(&self, plaintext: &[u8], associated_data: &[u8]) -> EncryptedData {
+		encrypt_with_key(&self.key, plaintext, associated_data)
+	}
+
+	pub fn decrypt(&self, data: &EncryptedData, associated_data: &[u8]) -> Result<Vec<u8>> {
+		decrypt_with_key(&self.key, data, associated_data)
+	}
+
+	/// Encrypts the data key with a key derived from the secret and a fresh salt.
+	pub fn wrap(&self, secret: &EncryptionSecret) -> Result<WrappedDataKey> {
+		let mut salt = [0u8; SALT_SIZE];
+		OsRng.fill_bytes(&mut salt);
+		let key_encryption_key = derive_key_encryption_key(secret, &salt)?;
+		Ok(WrappedDataKey {
+			salt,
+			key: encrypt_with_key(&key_encryption_key, &self.key[..], DATA_KEY_ASSOCIATED_DATA),
+		})
+	}
+
+	/// Decrypts a data key previously encrypted with [DataKey::wrap].
+	pub fn from_wrapped(secret: &EncryptionSecret, wrapped_key: &WrappedDataKey) -> Result<Self> {
+		let key_encryption_key = derive_key_encryption_key(secret, &wrapped_key.salt)?;
+		let key = Zeroizing::new(
+			decrypt_with_key(&key_encryption_key, &wrapped_key.key, DATA_KEY_ASSOCIATED_DATA)
+				.context(
+					"Failed to decrypt the db data key. Is the db encryption secret correct?",
+				)?,
+		);
+		Ok(DataKey {
+			key: Zeroizing::new(
+				key[..]
+					.try_into()
+					.map_err(|_| anyhow!("Decrypted data key has invalid length"))?,
+			),
+		})
+	}
+}
+
+fn derive_key_encryption_key(
+	secret: &EncryptionSecret,
+	salt: &[u8],
+) -> Result<Zeroizing<[u8; KEY_SIZE]>> {
+	let params =
+		Params::new(ARGON2_MEMORY_COST_KIB, ARGON2_ITERATIONS, ARGON2_PARALLELISM, Some(KEY_SIZE))
+			.map_err(|e| anyhow!("Invalid Argon2 parameters: {e}"))?;
+
+	let mut key = Zeroizing::new([0u8; KEY_SIZE]);
+	Argon2::new(Algorithm::Argon2id, Version::V0x13, params)
+		.hash_password_into(&secret.read()?, salt, &mut key[..])
+		.map_err(|e| anyhow!("Failed to derive db encryption key: {e}"))?;
+	Ok(key)
+}
+
+fn encrypt_with_key(
+	key: &[u8; KEY_SIZE],
+	plaintext: &[u8],
+	associated_data: &[u8],
+) -> EncryptedData {
+	let nonce = XChaCha20Poly1305::generate_nonce(&mut OsRng);
+	let ciphertext = XChaCha20Poly1305::new(key.into())
+		.encrypt(&nonce, Payload { msg: plaintext, aad: associated_data })
+		.expect("Encryption is not expected to fail");
+	EncryptedData { nonce: nonce.into(), ciphertext }
+}
+
+fn decrypt_with_key(
+	key: &[u8; KEY_SIZE],
+	data: &EncryptedData,
+	associated_data: &[u8],
+) -> Result<Vec<u8>> {
+	XChaCha20Poly1305::new(key.into())
+		.decrypt(
+			XNonce::from_slice(&data.nonce),
+			Payload { msg: &data.ciphertext, aad: associated_data },
+		)
+		.map_err(|_| anyhow!("Failed to decrypt data"))
+}
@@ -0,0 +1,240 @@
+//! Maintenance of the [PersistentKeyDB]: listing its contents, removing data that belongs to
+//! expired epochs and taking consistent snapshots while the database is in use, either by the
+//! process that opened it or by another one.
+
+use std::path::{Path, PathBuf};
+
+use anyhow::{anyhow, bail, Context, Result};
+use cf_primitives::EpochIndex;
+use multisig::{ChainTag, KeyId, CHAIN_TAG_SIZE};
+use num_traits::FromPrimitive;
+use serde::Serialize;
+use tracing::info;
+
+use super::{
+	rocksdb_kv::{RocksDBKeyValueStore, PREFIX_SIZE},
+	PersistentKeyDB, BACKUPS_DIRECTORY, KEYGEN_DATA_PARTIAL_PREFIX, PARTIAL_PREFIX_SIZE,
+	PROCESSED_BLOCKS_PARTIAL_PREFIX,
+};
+
+/// The size of a bincode serialized [EpochIndex]
+const EPOCH_INDEX_SIZE: usize = std::mem::size_of::<EpochIndex>();
+
+#[derive(Serialize, Debug, Clone, PartialEq, Eq)]
+pub struct KeyShareEntry {
+	pub chain: String,
+	pub key_id: KeyId,
+}
+
+#[derive(Serialize, Debug, Clone, PartialEq, Eq)]
+pub struct ProcessedBlocksEntry {
+	pub witnesser: String,
+	pub epoch_index: EpochIndex,
+}
+
+/// Describes the contents of a database, as found by [PersistentKeyDB::inspect].
+#[derive(Serialize, Debug, Clone, PartialEq, Eq)]
+pub struct DbSummary {
+	pub schema_version: u32,
+	pub genesis_hash: Option<state_chain_runtime::Hash>,
+	pub encrypted: bool,
+	pub key_shares: Vec<KeyShareEntry>,
+	pub processed_blocks: Vec<ProcessedBlocksEntry>,
+}
+
+/// Whether [PersistentKeyDB::prune_expired_epochs] removes the key shares of expired epochs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum KeySharePruning<'a> {
+	/// The key shares are kept, only the processed block records are removed.
+	Keep,
+	/// The key shares are removed, after taking a snapshot of the database at `db_path` that
+	/// still contains them.
+	SnapshotAndRemove { db_path: &'a Path },
+}
+
+/// What was removed by [PersistentKeyDB::prune_expired_epochs].
+#[derive(Serialize, Debug, Clone, Default, PartialEq, Eq)]
+pub struct PruneSummary {
+	pub key_shares: Vec<KeyShareEntry>,
+	pub processed_blocks: Vec<ProcessedBlocksEntry>,
+	/// The snapshot taken before removing the key shares, if any were removed.
+	pub snapshot: Option<PathBuf>,
+}
+
+impl PruneSummary {
+	pub fn is_empty(&self) -> bool {
+		self.key_shares.is_empty() && self.processed_blocks.is_empty()
+	}
+}
+
+fn decode_key_share_db_key(db_key: &[u8]) -> Result<KeyShareEntry> {
+	let chain_tag: [u8; CHAIN_TAG_SIZE] = db_key[PARTIAL_PREFIX_SIZE..PREFIX_SIZE]
+		.try_into()
+		.expect("Chain tag has a fixed size");
+	let chain_tag = ChainTag::from_u16(u16::from_be_bytes(chain_tag))
+		.ok_or_else(|| anyhow!("Found a key share for an unknown chain"))?;
+
+	Ok(KeyShareEntry {
+		chain: chain_tag.to_string(),
+		key_id: bincode::deserialize(&db_key[PREFIX_SIZE..])
+			.context("Failed to deserialize key id")?,
+	})
+}
+
+/// The processed blocks are stored under the witnesser name followed by the epoch index. As the
+/// witnesser name doesn't have a fixed length, the epoch index is read from the end of the key.
+fn decode_processed_blocks_db_key(db_key: &[u8]) -> Result<ProcessedBlocksEntry> {
+	let epoch_index_start = db_key
+		.len()
+		.checked_sub(EPOCH_INDEX_SIZE)
+		.filter(|start| *start >= PARTIAL_PREFIX_SIZE)
+		.ok_or_else(|| anyhow!("Processed blocks key is too short"))?;
+
+	Ok(ProcessedBlocksEntry {
+		witnesser: String::from_utf8(db_key[PARTIAL_PREFIX_SIZE..epoch_index_start].to_vec())
+			.context("Invalid witnesser name")?,
+		epoch_index: bincode::deserialize(&db_key[epoch_index_start..])
+			.context("Failed to deserialize epoch index")?,
+	})
+}
+
+impl PersistentKeyDB {
+	/// Lists the key shares and processed block records in the database. The key shares are not
+	/// decrypted, so this also works for databases that haven't been unlocked.
+	pub fn inspect(&self) -> Result<DbSummary> {
+		let mut key_shares = self
+			.kv_db
+			.get_raw_data_for_partial_prefix(KEYGEN_DATA_PARTIAL_PREFIX)
+			.map(|(db_key, _)| decode_key_share_db_key(&db_key))
+			.collect::<Result<Vec<_>>>()?;
+		key_shares.sort_by_key(|entry| (entry.chain.clone(), entry.key_id.epoch_index()));
+
+		let mut processed_blocks = self
+			.kv_db
+			.get_raw_data_for_partial_prefix(PROCESSED_BLOCKS_PARTIAL_PREFIX)
+			.map(|(db_key, _)| decode_processed_blocks_db_key(&db_key))
+			.collect::<Result<Vec<_>>>()?;
+		processed_blocks.sort_by_key(|entry| (entry.witnesser.clone(), entry.epoch_index));
+
+		Ok(DbSummary {
+			schema_version: self.get_schema_version()?,
+			genesis_hash: self.get_genesis_hash()?,
+			encrypted: self.get_wrapped_data_key()?.is_some(),
+			key_shares,
+			processed_blocks,
+		})
+	}
+
+	/// Removes the processed block records, and if requested the key shares, of all epochs up to
+	/// and including `last_expired_epoch`. Once an epoch has expired its vaults are no longer
+	/// used, so none of this data is needed anymore. Removing key shares can't be undone, so a
+	/// snapshot is taken first.
+	pub fn prune_expired_epochs(
+		&self,
+		last_expired_epoch: EpochIndex,
+		key_share_pruning: KeySharePruning<'_>,
+	) -> Result<PruneSummary> {
+		let mut batch = self.kv_db.create_batch();
+		let mut summary = PruneSummary::default();
+
+		if let KeySharePruning::SnapshotAndRemove { db_path } = key_share_pruning {
+			for (db_key, _) in
+				self.kv_db.get_raw_data_for_partial_prefix(KEYGEN_DATA_PARTIAL_PREFIX)
+			{
+				let entry = decode_key_share_db_key(&db_key)?;
+				if entry.key_id.epoch_index() <= last_expired_epoch {
+					batch.delete_value(&db_key);
+					summary.key_shares.push(entry);
+				}
+			}
+
+			if !summary.key_shares.is_empty() {
+				summary.snapshot = Some(
+					self.create_snapshot(db_path, None)
+						.context("Not removing key shares without a snapshot")?,
+				);
+			}
+		}
+
+		for (db_key, _) in
+			self.kv_db.get_raw_data_for_partial_prefix(PROCESSED_BLOCKS_PARTIAL_PREFIX)
+		{
+			let entry = decode_processed_blocks_db_key(&db_key)?;
+			if entry.epoch_index <= last_expired_epoch {
+				batch.delete_value(&db_key);
+				summary.processed_blocks.push(entry);
+			}
+		}
+
+		batch.write().context("Failed to prune expired epochs from the database")?;
+
+		if !summary.is_empty() {
+			info!(
+				"Pruned {} key shares and {} processed block records of epochs up to {last_expired_epoch}",
+				summary.key_shares.len(),
+				summary.processed_blocks.len(),
+			);
+		}
+
+		Ok(summary)
+	}
+
+	/// Creates a consistent snapshot of the database in `directory`, or in the backups directory
+	/// next to the database if no directory is given. Unlike the backups taken before migrations,
+	/// this can be done while the database is in use. Returns the path of the snapshot.
+	pub fn create_snapshot(&self, db_path: &Path, directory: Option<&Path>) -> Result<PathBuf> {
+		let snapshot_path = self.snapshot_path(db_path, directory)?;
+
+		self.kv_db.create_checkpoint(&snapshot_path)?;
+
+		info!("Database snapshot created at {}", snapshot_path.display());
+
+		Ok(snapshot_path)
+	}
+
+	/// As [Self::create_snapshot], but for a database that may be opened by another process, such
+	/// as a running engine. The database is read as a RocksDB secondary instance, which doesn't
+	/// need the lock held by that process, and its contents are copied into the snapshot.
+	pub fn create_snapshot_of_shared_db(
+		db_path: &Path,
+		directory: Option<&Path>,
+	) -> Result<PathBuf> {
+		if !db_path.exists() {
+			bail!("No database found at {}", db_path.display());
+		}
+
+		let secondary_path = db_path.with_extension("secondary");
+		let result =
+			RocksDBKeyValueStore::open_as_secondary(db_path, &secondary_path).and_then(|kv_db| {
+				let db = PersistentKeyDB { kv_db, data_key: None };
+				let snapshot_path = db.snapshot_path(db_path, directory)?;
+				db.kv_db.copy_to(&snapshot_path)?;
+				Ok(snapshot_path)
+			});
+		// The secondary instance only keeps its logs here
+		let _result = std::fs::remove_dir_all(&secondary_path);
+
+		let snapshot_path = result?;
+		info!("Database snapshot created at {}", snapshot_path.display());
+		Ok(snapshot_path)
+	}
+
+	/// The path of a new snapshot of the database at `db_path`, in `directory` or in the backups
+	/// directory next to the database if no directory is given. The directory is created if needed.
+	fn snapshot_path(&self, db_path: &Path, directory: Option<&Path>) -> Result<PathBuf> {
+		let directory = match directory {
+			Some(directory) => directory.to_path_buf(),
+			None => db_path.parent().expect("Should have parent").join(BACKUPS_DIRECTORY),
+		};
+		std::fs::create_dir_all(&directory).with_context(|| {
+			format!("Failed to create snapshot directory {}", directory.display())
+		})?;
+
+		Ok(directory.join(format!(
+			"snapshot_v{}_{}_{}",
+			self.get_schema_version()?,
+			chrono::Utc::now().to_rfc3339(),
+			db_path.file_name().expect("Should have file name").to_string_lossy(),
+		)))
+	}
+}
@@ -1,9 +1,12 @@
 use std::path::Path;
 
-use rocksdb::{ColumnFamily, ColumnFamilyDescriptor, Options, WriteBatch, DB};
+use rocksdb::{
+	BottommostLevelCompaction, ColumnFamily, ColumnFamilyDescriptor, CompactOptions, Direction,
+	IteratorMode, Options, ReadOptions, WriteBatch, DB,
+};
 use serde::{de::DeserializeOwned, Serialize};
 
-use anyhow::{Context, Result};
+use anyhow::{bail, Context, Result};
 
 /// A static length prefix is used on the `DATA_COLUMN`
 pub const PREFIX_SIZE: usize = 10;
@@ -48,6 +51,83 @@ impl RocksDBKeyValueStore {
 		Ok(RocksDBKeyValueStore { db })
 	}
 
+	/// Opens an existing database without writing to it, e.g. to inspect a backup.
+	pub fn open_read_only(db_path: &Path) -> Result<Self> {
+		let column_families = DB::list_cf(&Options::default(), db_path)
+			.with_context(|| format!("Failed to list column families at: {}", db_path.display()))?;
+		if !column_families.iter().any(|name| name == METADATA_COLUMN) {
+			bail!("No {METADATA_COLUMN} column in database at: {}", db_path.display());
+		}
+
+		let db = DB::open_cf_for_read_only(&Options::default(), db_path, column_families, false)
+			.with_context(|| format!("Failed to open database at: {}", db_path.display()))?;
+
+		Ok(RocksDBKeyValueStore { db })
+	}
+
+	/// Opens a database that may be in use by another process as a secondary instance, which reads
+	/// the files of the database without taking its lock. The secondary instance keeps its own logs
+	/// in `secondary_path`.
+	pub fn open_as_secondary(db_path: &Path, secondary_path: &Path) -> Result<Self> {
+		let mut options = Options::default();
+		// Secondary instances must keep all files open, so that the primary can't delete them
+		options.set_max_open_files(-1);
+
+		let db = DB::open_cf_as_secondary(
+			&options,
+			db_path,
+			secondary_path,
+			[METADATA_COLUMN, DATA_COLUMN],
+		)
+		.with_context(|| format!("Failed to open database at: {}", db_path.display()))?;
+		db.try_catch_up_with_primary().with_context(|| {
+			format!("Failed to read the latest changes to: {}", db_path.display())
+		})?;
+
+		Ok(RocksDBKeyValueStore { db })
+	}
+
+	/// Writes all data in the database to a new database at `path`, which must not exist yet.
+	pub fn copy_to(&self, path: &Path) -> Result<()> {
+		if path.exists() {
+			bail!("Can't copy the database to {}, it already exists", path.display());
+		}
+
+		let copy = Self::open(path)?;
+		let mut batch = WriteBatch::default();
+		for (column, copy_column) in [
+			(get_metadata_column_handle(&self.db), get_metadata_column_handle(&copy.db)),
+			(get_data_column_handle(&self.db), get_data_column_handle(&copy.db)),
+		] {
+			for result in self.db.iterator_cf(column, IteratorMode::Start) {
+				let (key, value) = result.context("Failed to read from database")?;
+				batch.put_cf(copy_column, key, value);
+			}
+		}
+		copy.db
+			.write(batch)
+			.with_context(|| format!("Failed to write database copy to {}", path.display()))
+	}
+
+	/// Makes sure that overwritten and deleted values are no longer present in any of the files of
+	/// the database: the memtables are flushed, so that the write-ahead log files holding the old
+	/// values become obsolete, and all levels are compacted, so that the old values are dropped
+	/// from the sst files. RocksDB deletes the obsolete files afterwards.
+	pub fn purge_stale_data(&self) -> Result<()> {
+		let mut compact_options = CompactOptions::default();
+		compact_options.set_exclusive_manual_compaction(true);
+		compact_options.set_bottommost_level_compaction(BottommostLevelCompaction::Force);
+
+		for column in [get_metadata_column_handle(&self.db), get_data_column_handle(&self.db)] {
+			self.db.flush_cf(column).context("Failed to flush memtable")?;
+			self.db
+				.compact_range_cf_opt(column, None::<&[u8]>, None::<&[u8]>, &compact_options);
+		}
+		self.db.flush_wal(true).context("Failed to flush write-ahead log")?;
+
+		Ok(())
+	}
+
 	pub fn put_data<T: Serialize, K: Serialize>(
 		&self,
 		prefix: &[u8],
@@ -97,6 +177,27 @@ impl RocksDBKeyValueStore {
 			})
 	}
 
+	/// Returns the raw keys (including the prefix) and values of all data whose key starts with
+	/// `partial_prefix`. Unlike [Self::get_data_for_prefix], the prefix doesn't need to be
+	/// `PREFIX_SIZE` long.
+	pub fn get_raw_data_for_partial_prefix<'a>(
+		&'a self,
+		partial_prefix: &'a [u8],
+	) -> impl Iterator<Item = (Box<[u8]>, Box<[u8]>)> + 'a {
+		// The prefix extractor only applies to full prefixes, so we need a total order seek
+		let mut read_options = ReadOptions::default();
+		read_options.set_total_order_seek(true);
+
+		self.db
+			.iterator_cf_opt(
+				get_data_column_handle(&self.db),
+				read_options,
+				IteratorMode::From(partial_prefix, Direction::Forward),
+			)
+			.map(|result| result.expect("iterator should not fail"))
+			.take_while(move |(key, _)| key.starts_with(partial_prefix))
+	}
+
 	pub fn put_metadata<V>(&self, key: &[u8], value: V) -> Result<()>
 	where
 		V: AsRef<[u8]>,
@@ -112,6 +213,14 @@ impl RocksDBKeyValueStore {
 			.expect("metadata column must exist")
 	}
 
+	/// Creates a consistent copy of the database at `path`, which must not exist yet. Where
+	/// possible, the files are hard linked rather than copied.
+	pub fn create_checkpoint(&self, path: &Path) -> Result<()> {
+		rocksdb::checkpoint::Checkpoint::new(&self.db)
+			.and_then(|checkpoint| checkpoint.create_checkpoint(path))
+			.with_context(|| format!("Failed to create checkpoint at {}", path.display()))
+	}
+
 	pub fn create_batch(&self) -> KVWriteBatch<'_> {
 		KVWriteBatch { db: &self.db, batch: WriteBatch::default() }
 	}
@@ -123,12 +232,10 @@ pub struct KVWriteBatch<'a> {
 }
 
 impl<'a> KVWriteBatch<'a> {
-	#[allow(dead_code)]
 	pub fn put_value(&mut self, key: &[u8], value: &[u8]) {
 		self.batch.put_cf(get_data_column_handle(self.db), key, value);
 	}
 
-	#[allow(dead_code)]
 	pub fn delete_value(&mut self, key: &[u8]) {
 		self.batch.delete_cf(get_data_column_handle(self.db), key);
 	}
@@ -140,6 +247,10 @@ impl<'a> KVWriteBatch<'a> {
 		self.batch.put_cf(get_metadata_column_handle(self.db), key, value);
 	}
 
+	pub fn delete_metadata(&mut self, key: &[u8]) {
+		self.batch.delete_cf(get_metadata_column_handle(self.db), key);
+	}
+
 	pub fn write(self) -> anyhow::Result<()> {
 		self.db.write(self.batch).context("failed to write batch")
 	}
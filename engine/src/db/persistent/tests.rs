@@ -311,3 +311,544 @@ fn test_migration_to_latest_from_0() {
 
 	assert_eq!(db.get_schema_version().unwrap(), LATEST_SCHEMA_VERSION);
 }
+
+#[test]
+fn test_migration_from_0_keeps_existing_keys() {
+	type Scheme = EthSigning;
+
+	let (_dir, db_file) = new_temp_directory_with_nonexistent_file();
+	let key_id = KeyId::new(GENESIS_EPOCH, [0; 33]);
+	let key_data = get_single_key_data::<<Scheme as ChainSigning>::CryptoScheme>();
+
+	// Write the key in the version 0 format, which is the serialized key without a wrapper
+	{
+		let db = PersistentKeyDB::open_and_migrate_to_version(&db_file, None, 0).unwrap();
+		db.kv_db.put_data(&keygen_data_prefix::<Scheme>(), &key_id, &key_data).unwrap();
+	}
+
+	let db = PersistentKeyDB::open_and_migrate_to_latest(&db_file, None).unwrap();
+
+	assert_eq!(db.load_keys::<Scheme>().get(&key_id), Some(&key_data));
+}
+
+fn passphrase(passphrase: &str) -> EncryptionSecret {
+	EncryptionSecret::Passphrase(passphrase.to_string().into())
+}
+
+fn is_stored_encrypted<C: ChainSigning>(db: &PersistentKeyDB, key_id: &KeyId) -> bool {
+	matches!(
+		db.kv_db
+			.get_data::<_, StoredKeyShare>(&keygen_data_prefix::<C>(), key_id)
+			.unwrap()
+			.expect("Key should be stored"),
+		StoredKeyShare::Encrypted(_)
+	)
+}
+
+#[test]
+fn can_load_encrypted_keys() {
+	type Scheme = EthSigning;
+
+	let (_dir, db_path) = new_temp_directory_with_nonexistent_file();
+	let key_id = KeyId::new(GENESIS_EPOCH, [0; 33]);
+	let key_data = get_single_key_data::<<Scheme as ChainSigning>::CryptoScheme>();
+	let secret = passphrase("correct horse battery staple");
+
+	{
+		let db = PersistentKeyDB::open_and_migrate_to_latest_with_encryption(
+			&db_path,
+			None,
+			Some(&secret),
+		)
+		.unwrap();
+		assert!(db.is_encrypted());
+		db.update_key::<Scheme>(&key_id, &key_data);
+		assert!(is_stored_encrypted::<Scheme>(&db, &key_id));
+	}
+
+	let db =
+		PersistentKeyDB::open_and_migrate_to_latest_with_encryption(&db_path, None, Some(&secret))
+			.unwrap();
+	assert_eq!(db.load_keys::<Scheme>().get(&key_id), Some(&key_data));
+}
+
+#[test]
+fn can_use_key_file_as_encryption_secret() {
+	type Scheme = EthSigning;
+
+	let (dir, db_path) = new_temp_directory_with_nonexistent_file();
+	let key_file = dir.path().join("db_key");
+	fs::write(&key_file, rand::random::<[u8; 32]>()).unwrap();
+	let secret = EncryptionSecret::KeyFile(key_file);
+	let key_id = KeyId::new(GENESIS_EPOCH, [0; 33]);
+
+	{
+		let db = PersistentKeyDB::open_and_migrate_to_latest_with_encryption(
+			&db_path,
+			None,
+			Some(&secret),
+		)
+		.unwrap();
+		db.update_key::<Scheme>(
+			&key_id,
+			&get_single_key_data::<<Scheme as ChainSigning>::CryptoScheme>(),
+		);
+	}
+
+	let db =
+		PersistentKeyDB::open_and_migrate_to_latest_with_encryption(&db_path, None, Some(&secret))
+			.unwrap();
+	assert!(db.load_keys::<Scheme>().contains_key(&key_id));
+}
+
+#[test]
+fn cannot_open_encrypted_db_without_correct_secret() {
+	let (_dir, db_path) = new_temp_directory_with_nonexistent_file();
+
+	assert_ok!(PersistentKeyDB::open_and_migrate_to_latest_with_encryption(
+		&db_path,
+		None,
+		Some(&passphrase("correct"))
+	));
+
+	assert!(PersistentKeyDB::open_and_migrate_to_latest(&db_path, None).is_err());
+	assert!(PersistentKeyDB::open_and_migrate_to_latest_with_encryption(
+		&db_path,
+		None,
+		Some(&passphrase("incorrect"))
+	)
+	.is_err());
+}
+
+#[test]
+fn opening_with_secret_encrypts_existing_keys() {
+	type Scheme = EthSigning;
+
+	let (_dir, db_path) = new_temp_directory_with_nonexistent_file();
+	let key_id = KeyId::new(GENESIS_EPOCH, [0; 33]);
+	let key_data = get_single_key_data::<<Scheme as ChainSigning>::CryptoScheme>();
+	let secret = passphrase("correct horse battery staple");
+
+	{
+		let db = PersistentKeyDB::open_and_migrate_to_latest(&db_path, None).unwrap();
+		db.update_key::<Scheme>(&key_id, &key_data);
+		assert!(!is_stored_encrypted::<Scheme>(&db, &key_id));
+	}
+
+	let db =
+		PersistentKeyDB::open_and_migrate_to_latest_with_encryption(&db_path, None, Some(&secret))
+			.unwrap();
+	assert!(is_stored_encrypted::<Scheme>(&db, &key_id));
+	assert_eq!(db.load_keys::<Scheme>().get(&key_id), Some(&key_data));
+}
+
+#[test]
+fn encrypting_removes_unencrypted_key_shares_from_disk() {
+	type Scheme = EthSigning;
+
+	let (directory, db_path) = new_temp_directory_with_nonexistent_file();
+	let key_id = KeyId::new(GENESIS_EPOCH, [0; 33]);
+	let key_data = get_single_key_data::<<Scheme as ChainSigning>::CryptoScheme>();
+	let plaintext_key_share = bincode::serialize(&key_data).unwrap();
+	let secret = passphrase("correct horse battery staple");
+
+	PersistentKeyDB::open_and_migrate_to_latest(&db_path, None)
+		.unwrap()
+		.update_key::<Scheme>(&key_id, &key_data);
+	assert_ok!(create_backup_with_directory_name(&db_path, "unencrypted".to_string()));
+
+	drop(
+		PersistentKeyDB::open_and_migrate_to_latest_with_encryption(&db_path, None, Some(&secret))
+			.unwrap(),
+	);
+	assert_ok!(create_backup_with_directory_name(&db_path, "encrypted".to_string()));
+
+	// Opening the db doesn't remove any backups
+	assert_eq!(find_backups(&directory, db_path.clone()).unwrap().len(), 2);
+
+	// Only the backup taken after the encryption is kept once the unencrypted ones are removed
+	let unencrypted_backups = PersistentKeyDB::find_unencrypted_backups(&db_path);
+	assert_eq!(
+		unencrypted_backups,
+		vec![directory.path().join(BACKUPS_DIRECTORY).join("unencrypted")]
+	);
+	assert_ok!(PersistentKeyDB::remove_backups(&unencrypted_backups));
+	assert_eq!(
+		find_backups(&directory, db_path.clone()).unwrap(),
+		vec![directory.path().join(BACKUPS_DIRECTORY).join("encrypted")]
+	);
+	assert!(PersistentKeyDB::find_unencrypted_backups(&db_path).is_empty());
+
+	// The plaintext key share is no longer in any of the files of the db
+	for entry in fs::read_dir(&db_path).unwrap() {
+		let contents = fs::read(entry.unwrap().path()).unwrap();
+		assert!(!contents
+			.windows(plaintext_key_share.len())
+			.any(|window| window == &plaintext_key_share[..]));
+	}
+
+	let db =
+		PersistentKeyDB::open_and_migrate_to_latest_with_encryption(&db_path, None, Some(&secret))
+			.unwrap();
+	assert_eq!(db.load_keys::<Scheme>().get(&key_id), Some(&key_data));
+}
+
+#[test]
+fn can_change_encryption() {
+	type Scheme = EthSigning;
+
+	let (_dir, db_path) = new_temp_directory_with_nonexistent_file();
+	let key_id = KeyId::new(GENESIS_EPOCH, [0; 33]);
+	let key_data = get_single_key_data::<<Scheme as ChainSigning>::CryptoScheme>();
+	let old_secret = passphrase("old");
+	let new_secret = passphrase("new");
+
+	let open = |secret: Option<&EncryptionSecret>| {
+		PersistentKeyDB::open_and_migrate_to_latest_with_encryption(&db_path, None, secret)
+	};
+
+	let change_encryption =
+		|current: Option<&EncryptionSecret>, new: Option<&EncryptionSecret>, rotate_data_key| {
+			open(current).unwrap().change_encryption(new, rotate_data_key).unwrap();
+		};
+
+	let assert_only_opens_with = |secret: &EncryptionSecret| {
+		assert_eq!(open(Some(secret)).unwrap().load_keys::<Scheme>().get(&key_id), Some(&key_data));
+		assert!(open(None).is_err());
+		assert!(open(Some(&passphrase("wrong"))).is_err());
+	};
+
+	open(Some(&old_secret)).unwrap().update_key::<Scheme>(&key_id, &key_data);
+	assert_only_opens_with(&old_secret);
+
+	// Only re-encrypt the data key
+	change_encryption(Some(&old_secret), Some(&new_secret), false);
+	assert_only_opens_with(&new_secret);
+	assert!(open(Some(&old_secret)).is_err());
+
+	// Replace the data key as well
+	change_encryption(Some(&new_secret), Some(&old_secret), true);
+	assert_only_opens_with(&old_secret);
+	assert!(open(Some(&new_secret)).is_err());
+
+	// Remove the encryption
+	change_encryption(Some(&old_secret), None, false);
+	let db = open(None).unwrap();
+	assert!(!db.is_encrypted());
+	assert_eq!(db.load_keys::<Scheme>().get(&key_id), Some(&key_data));
+}
+
+mod key_backup {
+	use super::*;
+	use cf_primitives::ForeignChain;
+	use std::collections::HashSet;
+
+	type Scheme = EthSigning;
+
+	struct ExportedKeys {
+		backup: Vec<u8>,
+		secret: EncryptionSecret,
+		key_ids: Vec<KeyId>,
+		genesis_hash: state_chain_runtime::Hash,
+	}
+
+	fn export_keys(number_of_keys: usize) -> ExportedKeys {
+		let (_dir, db_path) = new_temp_directory_with_nonexistent_file();
+		let genesis_hash = sp_core::H256::random();
+		let db = PersistentKeyDB::open_and_migrate_to_latest(&db_path, Some(genesis_hash)).unwrap();
+
+		let key_ids = (0..number_of_keys)
+			.map(|_| {
+				let key_id = KeyId::new(GENESIS_EPOCH, rand::random::<[u8; 32]>());
+				db.update_key::<Scheme>(
+					&key_id,
+					&get_single_key_data::<<Scheme as ChainSigning>::CryptoScheme>(),
+				);
+				key_id
+			})
+			.collect::<Vec<_>>();
+		// Keys of other chains are not exported
+		db.update_key::<BtcSigning>(
+			&KeyId::new(GENESIS_EPOCH, [0; 32]),
+			&get_single_key_data::<<BtcSigning as ChainSigning>::CryptoScheme>(),
+		);
+
+		let secret = passphrase("backup");
+		let backup = db.export_keys(ForeignChain::Ethereum, &[], &secret).unwrap();
+
+		ExportedKeys { backup, secret, key_ids, genesis_hash }
+	}
+
+	#[test]
+	fn can_export_verify_and_import_keys() {
+		let ExportedKeys { backup, secret, key_ids, genesis_hash } = export_keys(2);
+
+		let summary = verify_key_backup(&backup, &secret).unwrap();
+		assert_eq!(summary.chain, "Ethereum");
+		assert_eq!(summary.schema_version, LATEST_SCHEMA_VERSION);
+		assert_eq!(summary.genesis_hash, Some(genesis_hash));
+		assert_eq!(HashSet::<_>::from_iter(summary.key_ids), HashSet::from_iter(key_ids.clone()));
+
+		// Import into a fresh, encrypted db
+		let (_dir, db_path) = new_temp_directory_with_nonexistent_file();
+		let db = PersistentKeyDB::open_and_migrate_to_latest_with_encryption(
+			&db_path,
+			None,
+			Some(&passphrase("db")),
+		)
+		.unwrap();
+		assert_eq!(db.import_keys(&backup, &secret, false).unwrap().len(), key_ids.len());
+
+		let keys = db.load_keys::<Scheme>();
+		assert_eq!(keys.len(), key_ids.len());
+		assert!(key_ids.iter().all(|key_id| keys.contains_key(key_id)));
+		assert!(db.load_keys::<BtcSigning>().is_empty());
+		assert_eq!(db.get_genesis_hash().unwrap(), Some(genesis_hash));
+		assert!(key_ids.iter().all(|key_id| is_stored_encrypted::<Scheme>(&db, key_id)));
+
+		// Importing the same keys again fails
+		assert!(db.import_keys(&backup, &secret, false).is_err());
+	}
+
+	#[test]
+	fn can_export_selected_keys() {
+		let (_dir, db_path) = new_temp_directory_with_nonexistent_file();
+		let db = PersistentKeyDB::open_and_migrate_to_latest(&db_path, None).unwrap();
+		let secret = passphrase("backup");
+
+		let key_ids = [1, 2].map(|i| KeyId::new(GENESIS_EPOCH, [i; 33]));
+		for key_id in &key_ids {
+			db.update_key::<Scheme>(
+				key_id,
+				&get_single_key_data::<<Scheme as ChainSigning>::CryptoScheme>(),
+			);
+		}
+
+		let backup = db.export_keys(ForeignChain::Ethereum, &key_ids[..1], &secret).unwrap();
+		assert_eq!(verify_key_backup(&backup, &secret).unwrap().key_ids, key_ids[..1].to_vec());
+
+		assert!(db
+			.export_keys(ForeignChain::Ethereum, &[KeyId::new(GENESIS_EPOCH, [3; 33])], &secret)
+			.is_err());
+		assert!(db.export_keys(ForeignChain::Polkadot, &[], &secret).is_err());
+	}
+
+	#[test]
+	fn cannot_read_backup_with_wrong_secret_or_modifications() {
+		let ExportedKeys { backup, secret, .. } = export_keys(1);
+
+		assert!(verify_key_backup(&backup, &passphrase("wrong")).is_err());
+
+		for i in [0, backup.len() / 2, backup.len() - 1] {
+			let mut modified_backup = backup.clone();
+			modified_backup[i] ^= 1;
+			assert!(verify_key_backup(&modified_backup, &secret).is_err());
+		}
+
+		assert!(verify_key_backup(&backup[..backup.len() - 1], &secret).is_err());
+	}
+
+	#[test]
+	fn cannot_import_into_db_for_other_network() {
+		let ExportedKeys { backup, secret, .. } = export_keys(1);
+
+		let (_dir, db_path) = new_temp_directory_with_nonexistent_file();
+		let db =
+			PersistentKeyDB::open_and_migrate_to_latest(&db_path, Some(sp_core::H256::random()))
+				.unwrap();
+
+		assert!(db.import_keys(&backup, &secret, false).is_err());
+		assert!(db.load_keys::<Scheme>().is_empty());
+	}
+
+	#[test]
+	fn can_only_import_backup_of_unknown_network_if_allowed() {
+		let (_dir, db_path) = new_temp_directory_with_nonexistent_file();
+		let db = PersistentKeyDB::open_and_migrate_to_latest(&db_path, None).unwrap();
+		let key_id = KeyId::new(GENESIS_EPOCH, [1; 33]);
+		db.update_key::<Scheme>(
+			&key_id,
+			&get_single_key_data::<<Scheme as ChainSigning>::CryptoScheme>(),
+		);
+		let secret = passphrase("backup");
+		let backup = db.export_keys(ForeignChain::Ethereum, &[], &secret).unwrap();
+
+		let (_dir, db_path) = new_temp_directory_with_nonexistent_file();
+		let db =
+			PersistentKeyDB::open_and_migrate_to_latest(&db_path, Some(sp_core::H256::random()))
+				.unwrap();
+
+		assert!(db.import_keys(&backup, &secret, false).is_err());
+		assert!(db.load_keys::<Scheme>().is_empty());
+		assert_eq!(db.import_keys(&backup, &secret, true).unwrap(), vec![key_id]);
+	}
+
+	#[test]
+	fn cannot_import_into_db_with_other_schema_version() {
+		let ExportedKeys { backup, secret, .. } = export_keys(1);
+
+		let (_dir, db_path) = new_temp_directory_with_nonexistent_file();
+		let db = PersistentKeyDB::open_and_migrate_to_latest(&db_path, None).unwrap();
+		db.put_schema_version(LATEST_SCHEMA_VERSION + 1).unwrap();
+
+		assert!(db.import_keys(&backup, &secret, false).is_err());
+		assert!(db.load_keys::<Scheme>().is_empty());
+	}
+}
+
+mod housekeeping {
+	use super::*;
+	use utilities::rle_bitmap::RleBitmap;
+
+	fn add_key<C: ChainSigning>(db: &PersistentKeyDB, epoch_index: EpochIndex) -> KeyId {
+		let key_id = KeyId::new(epoch_index, rand::random::<[u8; 32]>());
+		db.update_key::<C>(&key_id, &get_single_key_data::<C::CryptoScheme>());
+		key_id
+	}
+
+	fn add_processed_blocks(db: &PersistentKeyDB, witnesser_name: &str, epoch: EpochIndex) {
+		db.update_processed_blocks(witnesser_name, epoch, &RleBitmap::<u64>::new(true))
+			.unwrap();
+	}
+
+	#[test]
+	fn can_inspect_and_prune_expired_epochs() {
+		let (_dir, db_path) = new_temp_directory_with_nonexistent_file();
+		let db = PersistentKeyDB::open_and_migrate_to_latest_with_encryption(
+			&db_path,
+			None,
+			Some(&passphrase("db")),
+		)
+		.unwrap();
+
+		let expired_key = add_key::<EthSigning>(&db, GENESIS_EPOCH);
+		let current_eth_key = add_key::<EthSigning>(&db, GENESIS_EPOCH + 1);
+		let current_btc_key = add_key::<BtcSigning>(&db, GENESIS_EPOCH + 1);
+		for epoch in [GENESIS_EPOCH, GENESIS_EPOCH + 1] {
+			add_processed_blocks(&db, "Bitcoin", epoch);
+			add_processed_blocks(&db, "KeyManager", epoch);
+		}
+		drop(db);
+
+		// The key shares don't need to be decrypted to inspect or prune the db
+		let db = PersistentKeyDB::open_for_maintenance(&db_path).unwrap();
+
+		let summary = db.inspect().unwrap();
+		assert!(summary.encrypted);
+		assert_eq!(summary.schema_version, LATEST_SCHEMA_VERSION);
+		assert_eq!(
+			summary.key_shares,
+			vec![
+				KeyShareEntry { chain: "Bitcoin".to_string(), key_id: current_btc_key.clone() },
+				KeyShareEntry { chain: "Ethereum".to_string(), key_id: expired_key.clone() },
+				KeyShareEntry { chain: "Ethereum".to_string(), key_id: current_eth_key.clone() },
+			]
+		);
+		assert_eq!(summary.processed_blocks.len(), 4);
+
+		let expired_processed_blocks = ["Bitcoin", "KeyManager"]
+			.map(|witnesser| ProcessedBlocksEntry {
+				witnesser: witnesser.to_string(),
+				epoch_index: GENESIS_EPOCH,
+			})
+			.to_vec();
+
+		// Key shares are only removed if requested
+		let pruned = db.prune_expired_epochs(GENESIS_EPOCH, KeySharePruning::Keep).unwrap();
+		assert!(pruned.key_shares.is_empty());
+		assert!(pruned.snapshot.is_none());
+		assert_eq!(pruned.processed_blocks, expired_processed_blocks);
+		assert_eq!(db.inspect().unwrap().key_shares.len(), 3);
+
+		let pruned = db
+			.prune_expired_epochs(
+				GENESIS_EPOCH,
+				KeySharePruning::SnapshotAndRemove { db_path: &db_path },
+			)
+			.unwrap();
+		assert_eq!(
+			pruned.key_shares,
+			vec![KeyShareEntry { chain: "Ethereum".to_string(), key_id: expired_key.clone() }]
+		);
+		assert!(pruned.processed_blocks.is_empty());
+
+		// The removed key shares are kept in a snapshot
+		let snapshot = PersistentKeyDB::open_for_maintenance(&pruned.snapshot.unwrap()).unwrap();
+		assert_eq!(snapshot.inspect().unwrap().key_shares.len(), 3);
+		drop(snapshot);
+
+		// Pruning is idempotent, and no snapshot is taken if there is nothing to remove
+		let pruned = db
+			.prune_expired_epochs(
+				GENESIS_EPOCH,
+				KeySharePruning::SnapshotAndRemove { db_path: &db_path },
+			)
+			.unwrap();
+		assert!(pruned.is_empty());
+		assert!(pruned.snapshot.is_none());
+		drop(db);
+
+		let db = PersistentKeyDB::open_and_migrate_to_latest_with_encryption(
+			&db_path,
+			None,
+			Some(&passphrase("db")),
+		)
+		.unwrap();
+		assert_eq!(
+			db.load_keys::<EthSigning>().into_keys().collect::<Vec<_>>(),
+			vec![current_eth_key]
+		);
+		assert_eq!(db.load_keys::<BtcSigning>().len(), 1);
+		assert!(db.load_processed_blocks::<u64>("Bitcoin", GENESIS_EPOCH).unwrap().is_none());
+		assert!(db.load_processed_blocks::<u64>("Bitcoin", GENESIS_EPOCH + 1).unwrap().is_some());
+	}
+
+	#[test]
+	fn can_create_snapshot_of_open_db() {
+		let (_dir, db_path) = new_temp_directory_with_nonexistent_file();
+		let db = PersistentKeyDB::open_and_migrate_to_latest(&db_path, None).unwrap();
+		let key_id = add_key::<EthSigning>(&db, GENESIS_EPOCH);
+
+		let snapshot_path = db.create_snapshot(&db_path, None).unwrap();
+		assert_eq!(
+			snapshot_path.parent().unwrap(),
+			db_path.parent().unwrap().join(BACKUPS_DIRECTORY)
+		);
+
+		// Changes after the snapshot was taken are not included
+		add_key::<EthSigning>(&db, GENESIS_EPOCH + 1);
+
+		let snapshot = PersistentKeyDB::open_and_migrate_to_latest(&snapshot_path, None).unwrap();
+		assert_eq!(
+			snapshot.load_keys::<EthSigning>().into_keys().collect::<Vec<_>>(),
+			vec![key_id]
+		);
+	}
+
+	#[test]
+	fn can_create_snapshot_of_db_opened_by_another_instance() {
+		let (dir, db_path) = new_temp_directory_with_nonexistent_file();
+		let db = PersistentKeyDB::open_and_migrate_to_latest(&db_path, None).unwrap();
+		let key_id = add_key::<EthSigning>(&db, GENESIS_EPOCH);
+		add_processed_blocks(&db, "Bitcoin", GENESIS_EPOCH);
+
+		// The db is locked by the open instance
+		assert!(PersistentKeyDB::open_for_maintenance(&db_path).is_err());
+
+		let snapshot_dir = dir.path().join("snapshots");
+		let snapshot_path =
+			PersistentKeyDB::create_snapshot_of_shared_db(&db_path, Some(&snapshot_dir)).unwrap();
+		assert_eq!(snapshot_path.parent().unwrap(), snapshot_dir);
+		assert!(!db_path.with_extension("secondary").exists());
+
+		let snapshot = PersistentKeyDB::open_and_migrate_to_latest(&snapshot_path, None).unwrap();
+		assert_eq!(
+			snapshot.load_keys::<EthSigning>().into_keys().collect::<Vec<_>>(),
+			vec![key_id]
+		);
+		assert!(snapshot
+			.load_processed_blocks::<u64>("Bitcoin", GENESIS_EPOCH)
+			.unwrap()
+			.is_some());
+	}
+}
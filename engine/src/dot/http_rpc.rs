@@ -1,13 +1,16 @@
-use std::sync::Arc;
+use std::sync::{
+	atomic::{AtomicU64, Ordering},
+	Arc,
+};
 
 use cf_chains::dot::{PolkadotHash, RuntimeVersion};
 use cf_primitives::PolkadotBlockNumber;
 use futures_core::Future;
-use jsonrpsee::{
-	core::{client::ClientT, traits::ToRpcParams, Error as JsonRpseeError},
-	http_client::{HttpClient, HttpClientBuilder},
+use reqwest::{
+	header::{HeaderMap, AUTHORIZATION, RETRY_AFTER},
+	StatusCode,
 };
-use reqwest::header::{HeaderMap, AUTHORIZATION};
+use serde::{Deserialize, Serialize};
 use serde_json::value::RawValue;
 use sp_core::H256;
 use subxt::{
@@ -21,48 +24,106 @@ use subxt::{
 };
 
 use anyhow::Result;
+use thiserror::Error;
 use tracing::{error, warn};
 use utilities::{make_periodic_tick, redact_endpoint_secret::SecretUrl};
 
-use crate::constants::RPC_RETRY_CONNECTION_INTERVAL;
+use crate::{
+	constants::RPC_RETRY_CONNECTION_INTERVAL,
+	retrier::rate_limit::{parse_retry_after, RateLimited},
+};
 
 use super::rpc::DotRpcApi;
 
-pub struct PolkadotHttpClient(HttpClient);
+/// Makes the JSON-RPC requests with reqwest, rather than jsonrpsee's http client, so we can read
+/// the `Retry-After` header of the requests the endpoint rate limits.
+pub struct PolkadotHttpClient {
+	client: reqwest::Client,
+	url: SecretUrl,
+	next_request_id: AtomicU64,
+}
 
 impl PolkadotHttpClient {
 	pub fn new(url: &SecretUrl) -> Result<Self> {
 		let token = format!("Bearer {}", "TOKEN");
 		let mut headers = HeaderMap::new();
 		headers.insert(AUTHORIZATION, token.parse().unwrap());
-		let client = HttpClientBuilder::default().set_headers(headers).build(url)?;
+		let client = reqwest::Client::builder().default_headers(headers).build()?;
 
-		Ok(Self(client))
+		Ok(Self { client, url: url.clone(), next_request_id: AtomicU64::new(0) })
 	}
-}
 
-struct Params(Option<Box<RawValue>>);
+	async fn request(
+		&self,
+		method: &str,
+		params: Option<Box<RawValue>>,
+	) -> Result<Box<RawValue>, Box<dyn std::error::Error + Send + Sync>> {
+		let response = self
+			.client
+			.post(self.url.as_ref())
+			.json(&JsonRpcRequest {
+				jsonrpc: "2.0",
+				id: self.next_request_id.fetch_add(1, Ordering::Relaxed),
+				method,
+				params,
+			})
+			.send()
+			.await?;
 
-impl ToRpcParams for Params {
-	fn to_rpc_params(self) -> Result<Option<Box<RawValue>>, JsonRpseeError> {
-		Ok(self.0)
+		if response.status() == StatusCode::TOO_MANY_REQUESTS {
+			return Err(RateLimited {
+				retry_after: response
+					.headers()
+					.get(RETRY_AFTER)
+					.and_then(|value| value.to_str().ok())
+					.and_then(parse_retry_after),
+			}
+			.into())
+		}
+
+		let response = response.error_for_status()?.json::<JsonRpcResponse>().await?;
+		match response.error {
+			Some(error) => Err(error.into()),
+			// A missing result is the same as a null one, which `Option` can't tell apart.
+			None => Ok(match response.result {
+				Some(result) => result,
+				None => RawValue::from_string("null".to_string())?,
+			}),
+		}
 	}
 }
 
+#[derive(Serialize)]
+struct JsonRpcRequest<'a> {
+	jsonrpc: &'static str,
+	id: u64,
+	method: &'a str,
+	#[serde(skip_serializing_if = "Option::is_none")]
+	params: Option<Box<RawValue>>,
+}
+
+#[derive(Deserialize)]
+struct JsonRpcResponse {
+	#[serde(default)]
+	result: Option<Box<RawValue>>,
+	#[serde(default)]
+	error: Option<JsonRpcError>,
+}
+
+#[derive(Error, Debug, Deserialize)]
+#[error("JSON-RPC error {code}: {message}")]
+struct JsonRpcError {
+	code: i64,
+	message: String,
+}
+
 impl RpcClientT for PolkadotHttpClient {
 	fn request_raw<'a>(
 		&'a self,
 		method: &'a str,
 		params: Option<Box<RawValue>>,
 	) -> RpcFuture<'a, Box<RawValue>> {
-		Box::pin(async move {
-			let res = self
-				.0
-				.request(method, Params(params))
-				.await
-				.map_err(|e| RpcError::ClientError(Box::new(e)))?;
-			Ok(res)
-		})
+		Box::pin(async move { self.request(method, params).await.map_err(RpcError::ClientError) })
 	}
 
 	fn subscribe_raw<'a>(
@@ -75,6 +136,19 @@ impl RpcClientT for PolkadotHttpClient {
 	}
 }
 
+// The rate limits reported by `PolkadotHttpClient` are hidden inside subxt's error, so they are
+// brought back out for the retrier.
+fn rpc_error(error: subxt::Error) -> anyhow::Error {
+	match &error {
+		subxt::Error::Rpc(RpcError::ClientError(client_error)) =>
+			match client_error.downcast_ref::<RateLimited>() {
+				Some(rate_limited) => rate_limited.clone().into(),
+				None => error.into(),
+			},
+		_ => error.into(),
+	}
+}
+
 #[derive(Clone)]
 pub struct DotHttpRpcClient {
 	online_client: OnlineClient<PolkadotConfig>,
@@ -127,21 +201,29 @@ impl DotHttpRpcClient {
 	}
 
 	pub async fn metadata(&self, block_hash: H256) -> Result<subxt::Metadata> {
-		Ok(self.online_client.rpc().metadata_legacy(Some(block_hash)).await?)
+		self.online_client
+			.rpc()
+			.metadata_legacy(Some(block_hash))
+			.await
+			.map_err(rpc_error)
 	}
 }
 
 #[async_trait::async_trait]
 impl DotRpcApi for DotHttpRpcClient {
 	async fn block_hash(&self, block_number: PolkadotBlockNumber) -> Result<Option<PolkadotHash>> {
-		Ok(self.online_client.rpc().block_hash(Some(block_number.into())).await?)
+		self.online_client
+			.rpc()
+			.block_hash(Some(block_number.into()))
+			.await
+			.map_err(rpc_error)
 	}
 
 	async fn block(
 		&self,
 		block_hash: PolkadotHash,
 	) -> Result<Option<ChainBlockResponse<PolkadotConfig>>> {
-		Ok(self.online_client.rpc().block(Some(block_hash)).await?)
+		self.online_client.rpc().block(Some(block_hash)).await.map_err(rpc_error)
 	}
 
 	async fn extrinsics(
@@ -184,30 +266,30 @@ impl DotRpcApi for DotHttpRpcClient {
 			Ok(events) => Ok(Some(events)),
 			Err(e) => match e {
 				subxt::Error::Block(BlockError::NotFound(_)) => Ok(None),
-				_ => Err(e.into()),
+				_ => Err(rpc_error(e)),
 			},
 		}
 	}
 
 	async fn runtime_version(&self, block_hash: Option<H256>) -> Result<RuntimeVersion> {
-		Ok(self
-			.online_client
+		self.online_client
 			.rpc()
 			.runtime_version(block_hash)
 			.await
 			.map(|v| RuntimeVersion {
 				spec_version: v.spec_version,
 				transaction_version: v.transaction_version,
-			})?)
+			})
+			.map_err(rpc_error)
 	}
 
 	async fn submit_raw_encoded_extrinsic(&self, encoded_bytes: Vec<u8>) -> Result<PolkadotHash> {
 		let encoded_bytes: Bytes = encoded_bytes.into();
-		Ok(self
-			.online_client
+		self.online_client
 			.rpc()
 			.request("author_submitExtrinsic", rpc_params![encoded_bytes.clone()])
-			.await?)
+			.await
+			.map_err(rpc_error)
 	}
 }
 
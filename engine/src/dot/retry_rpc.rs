@@ -1,5 +1,6 @@
 use crate::{
 	common::option_inner,
+	recording::{RecordedData, Recorder},
 	retrier::Attempt,
 	settings::{NodeContainer, WsHttpEndpoints},
 	witness::common::chain_source::{ChainClient, Header},
@@ -9,9 +10,11 @@ use cf_chains::{
 	Polkadot,
 };
 use cf_primitives::PolkadotBlockNumber;
+use codec::Decode;
 use core::time::Duration;
 use futures_core::Stream;
-use sp_core::H256;
+use serde::{Deserialize, Serialize};
+use sp_core::{Bytes, H256};
 use std::pin::Pin;
 use subxt::{
 	config::Header as SubxtHeader, events::Events, rpc::types::ChainBlockExtrinsic, PolkadotConfig,
@@ -62,19 +65,24 @@ impl DotRetryRpcClient {
 			))
 		};
 
+		let primary_rate_limit = nodes.primary.rate_limit;
+		let backup_rate_limit = nodes.backup.as_ref().and_then(|backup| backup.rate_limit);
+
 		let (rpc_client, sub_client) = f_create_clients(nodes.primary)?;
 
 		let (backup_rpc_client, backup_sub_client) =
 			option_inner(nodes.backup.map(f_create_clients).transpose()?);
 
 		Ok(DotRetryRpcClient {
-			rpc_retry_client: RetrierClient::new(
+			rpc_retry_client: RetrierClient::new_with_rate_limits(
 				scope,
 				"dot_rpc",
 				rpc_client,
 				backup_rpc_client,
 				POLKADOT_RPC_TIMEOUT,
 				MAX_CONCURRENT_SUBMISSIONS,
+				primary_rate_limit,
+				backup_rate_limit,
 			),
 			sub_retry_client: RetrierClient::new(
 				scope,
@@ -86,6 +94,44 @@ impl DotRetryRpcClient {
 			),
 		})
 	}
+
+	/// Records the responses to the requests made by this client to the given recorder.
+	pub fn with_recorder(self, recorder: Recorder) -> Self {
+		Self { rpc_retry_client: self.rpc_retry_client.with_recorder(recorder), ..self }
+	}
+
+	pub fn recorder(&self) -> Option<&Recorder> {
+		self.rpc_retry_client.recorder()
+	}
+
+	/// Cross-checks the reads we witness from between the primary and backup rpcs.
+	pub fn with_cross_checking(self, scope: &Scope<'_, anyhow::Error>) -> Self {
+		Self { rpc_retry_client: self.rpc_retry_client.with_cross_checking(scope), ..self }
+	}
+}
+
+/// Polkadot events are recorded as their raw bytes, and are decoded again on replay with the
+/// metadata the engine is built with.
+#[derive(Serialize, Deserialize)]
+struct RecordedEvents {
+	block_hash: PolkadotHash,
+	bytes: Bytes,
+}
+
+impl RecordedData for Events<PolkadotConfig> {
+	fn to_recorded(&self) -> Result<serde_json::Value> {
+		Ok(serde_json::to_value(RecordedEvents {
+			block_hash: self.block_hash(),
+			bytes: self.bytes().to_vec().into(),
+		})?)
+	}
+
+	fn from_recorded(value: serde_json::Value) -> Result<Self> {
+		let RecordedEvents { block_hash, bytes } = serde_json::from_value(value)?;
+		let metadata =
+			subxt::Metadata::decode(&mut &include_bytes!("../../metadata.polkadot.scale")[..])?;
+		Ok(Events::new(metadata, block_hash, bytes.0))
+	}
 }
 
 #[async_trait::async_trait]
@@ -108,7 +154,7 @@ pub trait DotRetryRpcApi: Clone {
 impl DotRetryRpcApi for DotRetryRpcClient {
 	async fn block_hash(&self, block_number: PolkadotBlockNumber) -> Option<PolkadotHash> {
 		self.rpc_retry_client
-			.request(
+			.request_cross_checked(
 				Box::pin(move |client| {
 					#[allow(clippy::redundant_async_block)]
 					Box::pin(async move { client.block_hash(block_number).await })
@@ -120,7 +166,7 @@ impl DotRetryRpcApi for DotRetryRpcClient {
 
 	async fn extrinsics(&self, block_hash: PolkadotHash) -> Vec<ChainBlockExtrinsic> {
 		self.rpc_retry_client
-			.request(
+			.request_recorded(
 				Box::pin(move |client| {
 					#[allow(clippy::redundant_async_block)]
 					Box::pin(async move {
@@ -136,7 +182,7 @@ impl DotRetryRpcApi for DotRetryRpcClient {
 
 	async fn events(&self, block_hash: PolkadotHash) -> Option<Events<PolkadotConfig>> {
 		self.rpc_retry_client
-			.request(
+			.request_recorded_data(
 				Box::pin(move |client| {
 					#[allow(clippy::redundant_async_block)]
 					Box::pin(async move { client.events(block_hash).await })
@@ -148,12 +194,12 @@ impl DotRetryRpcApi for DotRetryRpcClient {
 
 	async fn runtime_version(&self, block_hash: Option<H256>) -> RuntimeVersion {
 		self.rpc_retry_client
-			.request(
+			.request_recorded(
 				Box::pin(move |client| {
 					#[allow(clippy::redundant_async_block)]
 					Box::pin(async move { client.runtime_version(block_hash).await })
 				}),
-				RequestLog::new("runtime_version".to_string(), None),
+				RequestLog::new("runtime_version".to_string(), Some(format!("{block_hash:?}"))),
 			)
 			.await
 	}
@@ -237,7 +283,7 @@ impl ChainClient for DotRetryRpcClient {
 		index: Self::Index,
 	) -> Header<Self::Index, Self::Hash, Self::Data> {
 		self.rpc_retry_client
-			.request(
+			.request_recorded_data(
 				Box::pin(move |client| {
 					#[allow(clippy::redundant_async_block)]
 					Box::pin(async move {
@@ -322,6 +368,7 @@ mod tests {
 						primary: WsHttpEndpoints {
 							http_endpoint: "http://127.0.0.1:9945".into(),
 							ws_endpoint: "ws://127.0.0.1:9945".into(),
+							rate_limit: None,
 						},
 						backup: None,
 					},
@@ -10,6 +10,7 @@ use utilities::task_scope::Scope;
 use crate::{
 	common::option_inner,
 	eth::rpc::EthRpcApi,
+	recording::Recorder,
 	retrier::{Attempt, RequestLog, RetrierClient},
 	settings::{NodeContainer, WsHttpEndpoints},
 	witness::common::chain_source::{ChainClient, Header},
@@ -37,11 +38,33 @@ const MAX_CONCURRENT_SUBMISSIONS: u32 = 100;
 const MAX_BROADCAST_RETRIES: Attempt = 2;
 
 impl EthersRetryRpcClient {
+	/// If no `private_key_file` is provided, the client is read-only and
+	/// `broadcast_transaction` will fail.
 	pub fn new(
 		scope: &Scope<'_, anyhow::Error>,
-		private_key_file: PathBuf,
+		private_key_file: Option<PathBuf>,
 		nodes: NodeContainer<WsHttpEndpoints>,
 		expected_chain_id: U256,
+	) -> Result<Self> {
+		Self::new_with_retrier_names(
+			scope,
+			private_key_file,
+			nodes,
+			expected_chain_id,
+			"eth_rpc",
+			"eth_subscribe",
+		)
+	}
+
+	/// As [`Self::new`], for clients of other EVM chains. The retrier names are used in logs and
+	/// metrics, so they must be unique per chain.
+	pub fn new_with_retrier_names(
+		scope: &Scope<'_, anyhow::Error>,
+		private_key_file: Option<PathBuf>,
+		nodes: NodeContainer<WsHttpEndpoints>,
+		expected_chain_id: U256,
+		rpc_retrier_name: &'static str,
+		subscribe_retrier_name: &'static str,
 	) -> Result<Self> {
 		let f_create_clients = |endpoints: WsHttpEndpoints| {
 			Result::<_, anyhow::Error>::Ok((
@@ -54,22 +77,27 @@ impl EthersRetryRpcClient {
 			))
 		};
 
+		let primary_rate_limit = nodes.primary.rate_limit;
+		let backup_rate_limit = nodes.backup.as_ref().and_then(|backup| backup.rate_limit);
+
 		let (rpc_client, sub_client) = f_create_clients(nodes.primary)?;
 		let (backup_rpc_client, backup_sub_client) =
 			option_inner(nodes.backup.map(f_create_clients).transpose()?);
 
 		Ok(Self {
-			rpc_retry_client: RetrierClient::new(
+			rpc_retry_client: RetrierClient::new_with_rate_limits(
 				scope,
-				"eth_rpc",
+				rpc_retrier_name,
 				rpc_client,
 				backup_rpc_client,
 				ETHERS_RPC_TIMEOUT,
 				MAX_CONCURRENT_SUBMISSIONS,
+				primary_rate_limit,
+				backup_rate_limit,
 			),
 			sub_retry_client: RetrierClient::new(
 				scope,
-				"eth_subscribe",
+				subscribe_retrier_name,
 				futures::future::ready(sub_client),
 				backup_sub_client.map(futures::future::ready),
 				ETHERS_RPC_TIMEOUT,
@@ -77,6 +105,20 @@ impl EthersRetryRpcClient {
 			),
 		})
 	}
+
+	/// Records the responses to the requests made by this client to the given recorder.
+	pub fn with_recorder(self, recorder: Recorder) -> Self {
+		Self { rpc_retry_client: self.rpc_retry_client.with_recorder(recorder), ..self }
+	}
+
+	pub fn recorder(&self) -> Option<&Recorder> {
+		self.rpc_retry_client.recorder()
+	}
+
+	/// Cross-checks the reads we witness from between the primary and backup rpcs.
+	pub fn with_cross_checking(self, scope: &Scope<'_, anyhow::Error>) -> Self {
+		Self { rpc_retry_client: self.rpc_retry_client.with_cross_checking(scope), ..self }
+	}
 }
 
 #[async_trait::async_trait]
@@ -96,6 +138,8 @@ pub trait EthersRetryRpcApi: Clone {
 
 	async fn block_with_txs(&self, block_number: U64) -> Block<Transaction>;
 
+	async fn block_by_tag(&self, tag: BlockNumber) -> Block<H256>;
+
 	async fn fee_history(
 		&self,
 		block_count: U256,
@@ -130,7 +174,9 @@ impl EthersRetryRpcApi for EthersRetryRpcClient {
 							// geth uses the latest block gas limit as an upper bound
 							gas: None,
 							access_list: AccessList::default(),
-							from: Some(client.address()),
+							from: Some(client.address().context(
+								"Cannot broadcast transactions with a read-only Ethereum client",
+							)?),
 							nonce: None,
 						};
 
@@ -171,7 +217,7 @@ impl EthersRetryRpcApi for EthersRetryRpcClient {
 
 	async fn get_logs(&self, block_hash: H256, contract_address: H160) -> Vec<Log> {
 		self.rpc_retry_client
-			.request(
+			.request_cross_checked(
 				Box::pin(move |client| {
 					#[allow(clippy::redundant_async_block)]
 					Box::pin(async move {
@@ -192,7 +238,7 @@ impl EthersRetryRpcApi for EthersRetryRpcClient {
 
 	async fn chain_id(&self) -> U256 {
 		self.rpc_retry_client
-			.request(
+			.request_recorded(
 				Box::pin(move |client| {
 					#[allow(clippy::redundant_async_block)]
 					Box::pin(async move { client.chain_id().await })
@@ -204,7 +250,7 @@ impl EthersRetryRpcApi for EthersRetryRpcClient {
 
 	async fn transaction_receipt(&self, tx_hash: H256) -> TransactionReceipt {
 		self.rpc_retry_client
-			.request(
+			.request_cross_checked(
 				Box::pin(move |client| {
 					#[allow(clippy::redundant_async_block)]
 					Box::pin(async move { client.transaction_receipt(tx_hash).await })
@@ -216,7 +262,7 @@ impl EthersRetryRpcApi for EthersRetryRpcClient {
 
 	async fn block(&self, block_number: U64) -> Block<H256> {
 		self.rpc_retry_client
-			.request(
+			.request_cross_checked(
 				Box::pin(move |client| {
 					#[allow(clippy::redundant_async_block)]
 					Box::pin(async move { client.block(block_number).await })
@@ -228,7 +274,7 @@ impl EthersRetryRpcApi for EthersRetryRpcClient {
 
 	async fn block_with_txs(&self, block_number: U64) -> Block<Transaction> {
 		self.rpc_retry_client
-			.request(
+			.request_recorded(
 				Box::pin(move |client| {
 					#[allow(clippy::redundant_async_block)]
 					Box::pin(async move { client.block_with_txs(block_number).await })
@@ -238,6 +284,18 @@ impl EthersRetryRpcApi for EthersRetryRpcClient {
 			.await
 	}
 
+	async fn block_by_tag(&self, tag: BlockNumber) -> Block<H256> {
+		self.rpc_retry_client
+			.request_recorded(
+				Box::pin(move |client| {
+					#[allow(clippy::redundant_async_block)]
+					Box::pin(async move { client.block_by_tag(tag).await })
+				}),
+				RequestLog::new("block_by_tag".to_string(), Some(format!("{tag:?}"))),
+			)
+			.await
+	}
+
 	async fn fee_history(
 		&self,
 		block_count: U256,
@@ -249,7 +307,7 @@ impl EthersRetryRpcApi for EthersRetryRpcClient {
 			Some(format!("{block_count}, {newest_block}, {reward_percentiles:?}")),
 		);
 		self.rpc_retry_client
-			.request(
+			.request_recorded(
 				Box::pin(move |client| {
 					let reward_percentiles = reward_percentiles.clone();
 					#[allow(clippy::redundant_async_block)]
@@ -264,7 +322,7 @@ impl EthersRetryRpcApi for EthersRetryRpcClient {
 
 	async fn get_transaction(&self, tx_hash: H256) -> Transaction {
 		self.rpc_retry_client
-			.request(
+			.request_recorded(
 				Box::pin(move |client| {
 					#[allow(clippy::redundant_async_block)]
 					Box::pin(async move { client.get_transaction(tx_hash).await })
@@ -308,7 +366,7 @@ impl ChainClient for EthersRetryRpcClient {
 		index: Self::Index,
 	) -> Header<Self::Index, Self::Hash, Self::Data> {
 		self.rpc_retry_client
-			.request(
+			.request_cross_checked(
 				Box::pin(move |client| {
 					#[allow(clippy::redundant_async_block)]
 					Box::pin(async move {
@@ -365,6 +423,8 @@ pub mod mocks {
 
 			async fn block_with_txs(&self, block_number: U64) -> Block<Transaction>;
 
+			async fn block_by_tag(&self, tag: BlockNumber) -> Block<H256>;
+
 			async fn fee_history(
 				&self,
 				block_count: U256,
@@ -394,7 +454,7 @@ mod tests {
 
 				let retry_client = EthersRetryRpcClient::new(
 					scope,
-					settings.eth.private_key_file,
+					Some(settings.eth.private_key_file),
 					settings.eth.nodes,
 					U256::from(1337u64),
 				)
@@ -31,8 +31,12 @@ impl AddressCheckerRetryRpcApi for EthersRetryRpcClient {
 		contract_address: H160,
 		addresses: Vec<H160>,
 	) -> Vec<AddressState> {
+		let log = RequestLog::new(
+			"address_states".to_string(),
+			Some(format!("{block_hash:?}, {contract_address:?}, {addresses:?}")),
+		);
 		self.rpc_retry_client
-			.request(
+			.request_cross_checked(
 				Box::pin(move |client| {
 					let addresses = addresses.clone();
 					#[allow(clippy::redundant_async_block)]
@@ -40,10 +44,7 @@ impl AddressCheckerRetryRpcApi for EthersRetryRpcClient {
 						client.address_states(block_hash, contract_address, addresses).await
 					})
 				}),
-				RequestLog::new(
-					"address_states".to_string(),
-					Some(format!("{block_hash:?}, {contract_address:?}")),
-				),
+				log,
 			)
 			.await
 	}
@@ -54,8 +55,12 @@ impl AddressCheckerRetryRpcApi for EthersRetryRpcClient {
 		contract_address: H160,
 		addresses: Vec<H160>,
 	) -> Vec<U256> {
+		let log = RequestLog::new(
+			"balances".to_string(),
+			Some(format!("{block_hash:?}, {contract_address:?}, {addresses:?}")),
+		);
 		self.rpc_retry_client
-			.request(
+			.request_cross_checked(
 				Box::pin(move |client| {
 					let addresses = addresses.clone();
 					#[allow(clippy::redundant_async_block)]
@@ -63,10 +68,7 @@ impl AddressCheckerRetryRpcApi for EthersRetryRpcClient {
 						client.balances(block_hash, contract_address, addresses).await
 					})
 				}),
-				RequestLog::new(
-					"balances".to_string(),
-					Some(format!("{block_hash:?}, {contract_address:?}")),
-				),
+				log,
 			)
 			.await
 	}
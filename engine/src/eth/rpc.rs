@@ -1,13 +1,23 @@
 pub mod address_checker;
 
 use anyhow::bail;
-use ethers::{prelude::*, signers::Signer, types::transaction::eip2718::TypedTransaction};
+use ethers::{
+	prelude::*, providers::RpcError, signers::Signer, types::transaction::eip2718::TypedTransaction,
+};
 use futures_core::Future;
 use utilities::redact_endpoint_secret::SecretUrl;
 
-use crate::constants::{RPC_RETRY_CONNECTION_INTERVAL, SYNC_POLL_INTERVAL};
+use crate::{
+	constants::{RPC_RETRY_CONNECTION_INTERVAL, SYNC_POLL_INTERVAL},
+	retrier::rate_limit::RateLimited,
+};
 use anyhow::{anyhow, Context, Result};
-use std::{path::PathBuf, str::FromStr, sync::Arc, time::Instant};
+use std::{
+	path::PathBuf,
+	str::FromStr,
+	sync::Arc,
+	time::{Duration, Instant},
+};
 use tokio::sync::Mutex;
 use utilities::make_periodic_tick;
 
@@ -20,25 +30,36 @@ struct NonceInfo {
 
 #[derive(Clone)]
 pub struct EthRpcClient {
-	signer: SignerMiddleware<Arc<Provider<Http>>, LocalWallet>,
+	provider: Arc<Provider<Http>>,
+	// Only available if the client was created with a private key, otherwise the client is
+	// read-only and cannot send transactions.
+	signer: Option<SignerMiddleware<Arc<Provider<Http>>, LocalWallet>>,
 	nonce_info: Arc<Mutex<Option<NonceInfo>>>,
 }
 
 impl EthRpcClient {
+	/// Creates a client that signs transactions with the key in `private_key_file`. If no key file
+	/// is provided the client is read-only.
 	pub fn new(
-		private_key_file: PathBuf,
+		private_key_file: Option<PathBuf>,
 		http_endpoint: SecretUrl,
 		expected_chain_id: u64,
 	) -> Result<impl Future<Output = Self>> {
 		let provider = Arc::new(Provider::<Http>::try_from(http_endpoint.as_ref())?);
-		let wallet =
-			read_clean_and_decode_hex_str_file(&private_key_file, "Ethereum Private Key", |key| {
-				ethers::signers::Wallet::from_str(key).map_err(anyhow::Error::new)
-			})?;
-
-		let signer = SignerMiddleware::new(provider, wallet.with_chain_id(expected_chain_id));
-
-		let client = Self { signer, nonce_info: Arc::new(Mutex::new(None)) };
+		let signer = private_key_file
+			.map(|private_key_file| {
+				read_clean_and_decode_hex_str_file(
+					&private_key_file,
+					"Ethereum Private Key",
+					|key| ethers::signers::Wallet::from_str(key).map_err(anyhow::Error::new),
+				)
+				.map(|wallet| {
+					SignerMiddleware::new(provider.clone(), wallet.with_chain_id(expected_chain_id))
+				})
+			})
+			.transpose()?;
+
+		let client = Self { provider, signer, nonce_info: Arc::new(Mutex::new(None)) };
 
 		Ok(async move {
 			// We don't want to return an error here. Returning an error means that we'll exit the
@@ -65,6 +86,12 @@ impl EthRpcClient {
 		})
 	}
 
+	fn signer(&self) -> Result<&SignerMiddleware<Arc<Provider<Http>>, LocalWallet>> {
+		self.signer
+			.as_ref()
+			.ok_or_else(|| anyhow!("Ethereum client is read-only: no private key was provided"))
+	}
+
 	async fn get_next_nonce(&self) -> Result<U256> {
 		let mut nonce_info_lock = self.nonce_info.lock().await;
 
@@ -83,9 +110,9 @@ impl EthRpcClient {
 		let nonce_info = match nonce_info_lock.as_mut() {
 			Some(nonce_info) => nonce_info,
 			None => {
-				let tx_count = self
-					.signer
-					.get_transaction_count(self.address(), Some(BlockNumber::Pending.into()))
+				let signer = self.signer()?;
+				let tx_count = signer
+					.get_transaction_count(signer.address(), Some(BlockNumber::Pending.into()))
 					.await?;
 				nonce_info_lock
 					.insert(NonceInfo { next_nonce: tx_count, requested_at: Instant::now() })
@@ -98,9 +125,33 @@ impl EthRpcClient {
 	}
 }
 
+/// Node providers report rate limits in the JSON-RPC error rather than just with the HTTP status,
+/// which ethers doesn't expose. Most use the code 429. Infura uses -32005, which it also uses for
+/// other limits such as the number of logs returned, so it is only a rate limit if Infura says how
+/// long to back off for.
+fn rpc_error(error: ProviderError) -> anyhow::Error {
+	if let ProviderError::JsonRpcClientError(client_error) = &error {
+		if let Some(response) = client_error.as_error_response() {
+			let infura_rate = response.data.as_ref().and_then(|data| data.get("rate"));
+			if response.code == 429 || (response.code == -32005 && infura_rate.is_some()) {
+				return RateLimited {
+					retry_after: infura_rate
+						.and_then(|rate| rate["backoff_seconds"].as_f64())
+						.and_then(|backoff_seconds| {
+							Duration::try_from_secs_f64(backoff_seconds).ok()
+						}),
+				}
+				.into()
+			}
+		}
+	}
+	error.into()
+}
+
 #[async_trait::async_trait]
 pub trait EthRpcApi: Send {
-	fn address(&self) -> H160;
+	/// The address transactions are sent from, or `None` if the client is read-only.
+	fn address(&self) -> Option<H160>;
 
 	async fn estimate_gas(&self, req: &Eip1559TransactionRequest) -> Result<U256>;
 
@@ -119,6 +170,9 @@ pub trait EthRpcApi: Send {
 
 	async fn block_with_txs(&self, block_number: U64) -> Result<Block<Transaction>>;
 
+	/// Gets the block for a block tag such as `finalized` or `safe`.
+	async fn block_by_tag(&self, tag: BlockNumber) -> Result<Block<H256>>;
+
 	async fn fee_history(
 		&self,
 		block_count: U256,
@@ -131,18 +185,21 @@ pub trait EthRpcApi: Send {
 
 #[async_trait::async_trait]
 impl EthRpcApi for EthRpcClient {
-	fn address(&self) -> H160 {
-		self.signer.address()
+	fn address(&self) -> Option<H160> {
+		self.signer.as_ref().map(|signer| signer.address())
 	}
 
 	async fn estimate_gas(&self, req: &Eip1559TransactionRequest) -> Result<U256> {
-		Ok(self.signer.estimate_gas(&TypedTransaction::Eip1559(req.clone()), None).await?)
+		Ok(self
+			.signer()?
+			.estimate_gas(&TypedTransaction::Eip1559(req.clone()), None)
+			.await?)
 	}
 
 	async fn send_transaction(&self, mut tx: Eip1559TransactionRequest) -> Result<TxHash> {
 		tx.nonce = Some(self.get_next_nonce().await?);
 
-		let res = self.signer.send_transaction(tx, None).await;
+		let res = self.signer()?.send_transaction(tx, None).await;
 
 		if res.is_err() {
 			// Reset the nonce just in case (it will be re-requested during next broadcast)
@@ -154,32 +211,48 @@ impl EthRpcApi for EthRpcClient {
 	}
 
 	async fn get_logs(&self, filter: Filter) -> Result<Vec<Log>> {
-		Ok(self.signer.get_logs(&filter).await?)
+		self.provider.get_logs(&filter).await.map_err(rpc_error)
 	}
 
 	async fn chain_id(&self) -> Result<U256> {
-		Ok(self.signer.get_chainid().await?)
+		self.provider.get_chainid().await.map_err(rpc_error)
 	}
 
 	async fn transaction_receipt(&self, tx_hash: TxHash) -> Result<TransactionReceipt> {
-		self.signer.get_transaction_receipt(tx_hash).await?.ok_or_else(|| {
-			anyhow!("Getting ETH transaction receipt for tx hash {tx_hash} returned None")
-		})
+		self.provider
+			.get_transaction_receipt(tx_hash)
+			.await
+			.map_err(rpc_error)?
+			.ok_or_else(|| {
+				anyhow!("Getting ETH transaction receipt for tx hash {tx_hash} returned None")
+			})
 	}
 
 	/// Gets block, returning error when either:
 	/// - Request fails
 	/// - Request succeeds, but doesn't return a block
 	async fn block(&self, block_number: U64) -> Result<Block<H256>> {
-		self.signer.get_block(block_number).await?.ok_or_else(|| {
+		self.provider.get_block(block_number).await.map_err(rpc_error)?.ok_or_else(|| {
 			anyhow!("Getting ETH block for block number {block_number} returned None")
 		})
 	}
 
 	async fn block_with_txs(&self, block_number: U64) -> Result<Block<Transaction>> {
-		self.signer.get_block_with_txs(block_number).await?.ok_or_else(|| {
-			anyhow!("Getting ETH block with txs for block number {block_number} returned None")
-		})
+		self.provider
+			.get_block_with_txs(block_number)
+			.await
+			.map_err(rpc_error)?
+			.ok_or_else(|| {
+				anyhow!("Getting ETH block with txs for block number {block_number} returned None")
+			})
+	}
+
+	async fn block_by_tag(&self, tag: BlockNumber) -> Result<Block<H256>> {
+		self.provider
+			.get_block(tag)
+			.await
+			.map_err(rpc_error)?
+			.ok_or_else(|| anyhow!("Getting ETH block for block tag {tag:?} returned None"))
 	}
 
 	async fn fee_history(
@@ -188,13 +261,17 @@ impl EthRpcApi for EthRpcClient {
 		last_block: BlockNumber,
 		reward_percentiles: &[f64],
 	) -> Result<FeeHistory> {
-		Ok(self.signer.fee_history(block_count, last_block, reward_percentiles).await?)
+		self.provider
+			.fee_history(block_count, last_block, reward_percentiles)
+			.await
+			.map_err(rpc_error)
 	}
 
 	async fn get_transaction(&self, tx_hash: H256) -> Result<Transaction> {
-		self.signer
+		self.provider
 			.get_transaction(tx_hash)
-			.await?
+			.await
+			.map_err(rpc_error)?
 			.ok_or_else(|| anyhow!("Getting ETH transaction for tx hash {tx_hash} returned None"))
 	}
 }
@@ -267,7 +344,7 @@ mod tests {
 		let settings = Settings::new_test().unwrap();
 
 		let client = EthRpcClient::new(
-			settings.eth.private_key_file,
+			Some(settings.eth.private_key_file),
 			settings.eth.nodes.primary.http_endpoint,
 			2u64,
 		)
@@ -4,7 +4,11 @@ use anyhow::{Ok, Result};
 
 use super::EthRpcClient;
 
-abigen!(AddressChecker, "$CF_ETH_CONTRACT_ABI_ROOT/$CF_ETH_CONTRACT_ABI_TAG/IAddressChecker.json");
+abigen!(
+	AddressChecker,
+	"$CF_ETH_CONTRACT_ABI_ROOT/$CF_ETH_CONTRACT_ABI_TAG/IAddressChecker.json",
+	derives(serde::Serialize, serde::Deserialize)
+);
 
 #[async_trait::async_trait]
 pub trait AddressCheckerRpcApi {
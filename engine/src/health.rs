@@ -2,18 +2,314 @@
 //! allowing external services to query, ensuring it's online
 //! Returns a HTTP 200 response to any request on {hostname}:{port}/health
 //! Method returns a Sender, allowing graceful termination of the infinite loop
+//!
+//! The health of each subsystem is reported as JSON on {hostname}:{port}/health/status, and the
+//! same report is returned on /health/live and /health/ready with a HTTP 503 status if the engine
+//! is not live or not ready respectively, for use as liveness and readiness probes.
 
-use std::{net::IpAddr, sync::Arc};
+use std::{
+	collections::BTreeMap,
+	net::IpAddr,
+	sync::{Arc, Mutex},
+	time::{Duration, Instant},
+};
 
+use cf_primitives::EpochIndex;
+use serde::Serialize;
 use tracing::info;
 use utilities::task_scope;
-use warp::Filter;
+use warp::{http::StatusCode, Filter};
 
 use crate::settings;
 
 const INITIALISING: &str = "INITIALISING";
 const RUNNING: &str = "RUNNING";
 
+/// The engine isn't ready if it hasn't received a State Chain block for this long. Blocks are
+/// normally finalized every few seconds, so this allows for finality stalling briefly.
+const STATE_CHAIN_READY_TIMEOUT: Duration = Duration::from_secs(60);
+/// The engine isn't live if it hasn't received a State Chain block for this long, which means it
+/// is stuck and should be restarted.
+const STATE_CHAIN_LIVE_TIMEOUT: Duration = Duration::from_secs(300);
+
+lazy_static::lazy_static! {
+	static ref HEALTH: Mutex<HealthState> = Default::default();
+}
+
+fn with_health<R>(f: impl FnOnce(&mut HealthState) -> R) -> R {
+	f(&mut HEALTH.lock().unwrap())
+}
+
+/// Records a block at the tip of an external chain. Witnessing of the chain isn't ready when a
+/// pipeline falls more than `max_witnessing_lag` blocks behind the tip of the source it consumes.
+pub fn record_chain_tip(chain: &'static str, index: u64, max_witnessing_lag: u64) {
+	with_health(|health| {
+		let chain = health.chains.entry(chain).or_default();
+		chain.tip = Some(chain.tip.map_or(index, |tip| std::cmp::max(tip, index)));
+		chain.max_witnessing_lag = max_witnessing_lag;
+	})
+}
+
+/// Records a block produced by a chain source of an external chain, which the witnessing pipelines
+/// consuming that source are compared against.
+pub fn record_source_tip(chain: &'static str, source: &'static str, index: u64) {
+	with_health(|health| {
+		let tip = health
+			.chains
+			.entry(chain)
+			.or_default()
+			.source_tips
+			.entry(source)
+			.or_insert(index);
+		*tip = std::cmp::max(*tip, index);
+	})
+}
+
+/// The progress of a witnessing pipeline of an external chain over the blocks of one vault. The
+/// progress is removed from the health check when this is dropped, i.e. when the vault's stream
+/// stops.
+pub struct WitnessedBlocks {
+	chain: &'static str,
+	pipeline: &'static str,
+	epoch: EpochIndex,
+}
+
+impl WitnessedBlocks {
+	pub fn new(
+		chain: &'static str,
+		source: &'static str,
+		pipeline: &'static str,
+		epoch: EpochIndex,
+	) -> Self {
+		with_health(|health| {
+			health
+				.chains
+				.entry(chain)
+				.or_default()
+				.witnessed
+				.entry(pipeline)
+				.or_insert_with(|| PipelineHealth { source, vaults: Default::default() })
+				.source = source;
+		});
+		Self { chain, pipeline, epoch }
+	}
+
+	/// Records that the pipeline has processed a block of the vault.
+	pub fn record(&self, index: u64) {
+		with_health(|health| {
+			if let Some(pipeline) = health
+				.chains
+				.get_mut(self.chain)
+				.and_then(|chain| chain.witnessed.get_mut(self.pipeline))
+			{
+				let witnessed = pipeline.vaults.entry(self.epoch).or_insert(index);
+				*witnessed = std::cmp::max(*witnessed, index);
+			}
+		})
+	}
+}
+
+impl Drop for WitnessedBlocks {
+	fn drop(&mut self) {
+		with_health(|health| {
+			if let Some(chain) = health.chains.get_mut(self.chain) {
+				if let Some(pipeline) = chain.witnessed.get_mut(self.pipeline) {
+					pipeline.vaults.remove(&self.epoch);
+					if pipeline.vaults.is_empty() {
+						chain.witnessed.remove(self.pipeline);
+					}
+				}
+			}
+		})
+	}
+}
+
+/// Records a successful request to an rpc endpoint of a retrier.
+pub fn record_rpc_success(client: &'static str, endpoint: &'static str) {
+	with_health(|health| {
+		health.rpc_endpoints.insert((client, endpoint), Instant::now());
+	})
+}
+
+pub fn record_p2p_connected_peers(connected_peers: usize) {
+	with_health(|health| health.p2p_connected_peers = Some(connected_peers))
+}
+
+/// Records a finalized block received on the State Chain stream.
+pub fn record_state_chain_block(number: state_chain_runtime::BlockNumber) {
+	with_health(|health| health.state_chain_block = Some((number, Instant::now())))
+}
+
+/// Records the number of extrinsics submitted to the State Chain that aren't finalized yet.
+pub fn record_pending_extrinsics(pending_extrinsics: usize) {
+	with_health(|health| health.pending_extrinsics = Some(pending_extrinsics))
+}
+
+/// Records whether we participate in an epoch. Only the latest epoch is reported, which is the
+/// current epoch.
+pub fn record_epoch_participation(epoch: EpochIndex, participating: bool) {
+	with_health(|health| {
+		if health
+			.epoch_participation
+			.map_or(true, |(current_epoch, _)| current_epoch <= epoch)
+		{
+			health.epoch_participation = Some((epoch, participating));
+		}
+	})
+}
+
+#[derive(Debug)]
+struct PipelineHealth {
+	// The chain source the pipeline consumes.
+	source: &'static str,
+	// The highest block processed for each vault the pipeline is witnessing.
+	vaults: BTreeMap<EpochIndex, u64>,
+}
+
+impl PipelineHealth {
+	// The vault furthest behind, as every vault has to keep up.
+	fn witnessed(&self) -> Option<u64> {
+		self.vaults.values().min().copied()
+	}
+}
+
+#[derive(Debug, Default)]
+struct ChainHealth {
+	tip: Option<u64>,
+	max_witnessing_lag: u64,
+	// The highest block produced by each chain source of the chain.
+	source_tips: BTreeMap<&'static str, u64>,
+	witnessed: BTreeMap<&'static str, PipelineHealth>,
+}
+
+#[derive(Debug, Default)]
+struct HealthState {
+	chains: BTreeMap<&'static str, ChainHealth>,
+	rpc_endpoints: BTreeMap<(&'static str, &'static str), Instant>,
+	p2p_connected_peers: Option<usize>,
+	state_chain_block: Option<(state_chain_runtime::BlockNumber, Instant)>,
+	pending_extrinsics: Option<usize>,
+	epoch_participation: Option<(EpochIndex, bool)>,
+}
+
+#[derive(Debug, Serialize)]
+struct ChainReport {
+	tip: Option<u64>,
+	source_tips: BTreeMap<&'static str, u64>,
+	witnessed: BTreeMap<&'static str, u64>,
+	/// How far the slowest witnessing pipeline is behind the tip of the source it consumes.
+	lag: Option<u64>,
+	max_lag: u64,
+	ready: bool,
+}
+
+#[derive(Debug, Serialize)]
+struct StateChainReport {
+	latest_block: Option<state_chain_runtime::BlockNumber>,
+	seconds_since_latest_block: Option<u64>,
+	pending_extrinsics: Option<usize>,
+	ready: bool,
+}
+
+#[derive(Debug, Serialize)]
+struct EpochReport {
+	index: EpochIndex,
+	participating: bool,
+}
+
+#[derive(Debug, Serialize)]
+struct HealthReport {
+	status: &'static str,
+	live: bool,
+	ready: bool,
+	state_chain: StateChainReport,
+	chains: BTreeMap<&'static str, ChainReport>,
+	/// The seconds since the last successful request, by retrier and endpoint.
+	rpc_endpoints: BTreeMap<&'static str, BTreeMap<&'static str, u64>>,
+	p2p_connected_peers: Option<usize>,
+	epoch: Option<EpochReport>,
+}
+
+impl HealthState {
+	fn report(&self, has_completed_initialising: bool, now: Instant) -> HealthReport {
+		let since_latest_block = self
+			.state_chain_block
+			.map(|(_, received_at)| now.saturating_duration_since(received_at));
+
+		let state_chain = StateChainReport {
+			latest_block: self.state_chain_block.map(|(number, _)| number),
+			seconds_since_latest_block: since_latest_block.map(|duration| duration.as_secs()),
+			pending_extrinsics: self.pending_extrinsics,
+			ready: since_latest_block
+				.map_or(false, |duration| duration <= STATE_CHAIN_READY_TIMEOUT),
+		};
+
+		let chains: BTreeMap<_, _> = self
+			.chains
+			.iter()
+			.map(|(name, chain)| {
+				let lag = chain
+					.witnessed
+					.values()
+					.filter_map(|pipeline| {
+						chain
+							.source_tips
+							.get(pipeline.source)
+							.zip(pipeline.witnessed())
+							.map(|(tip, witnessed)| tip.saturating_sub(witnessed))
+					})
+					.max();
+				(
+					*name,
+					ChainReport {
+						tip: chain.tip,
+						source_tips: chain.source_tips.clone(),
+						witnessed: chain
+							.witnessed
+							.iter()
+							.filter_map(|(name, pipeline)| Some((*name, pipeline.witnessed()?)))
+							.collect(),
+						lag,
+						max_lag: chain.max_witnessing_lag,
+						// Until both the tip of a source and a block witnessed from it are known
+						// there is nothing to compare, for example when we don't participate in any
+						// vault.
+						ready: lag.map_or(true, |lag| lag <= chain.max_witnessing_lag),
+					},
+				)
+			})
+			.collect();
+
+		let mut rpc_endpoints = BTreeMap::<_, BTreeMap<_, _>>::new();
+		for ((client, endpoint), succeeded_at) in &self.rpc_endpoints {
+			rpc_endpoints
+				.entry(*client)
+				.or_default()
+				.insert(*endpoint, now.saturating_duration_since(*succeeded_at).as_secs());
+		}
+
+		// While initialising the State Chain stream may not have started yet.
+		let live = !has_completed_initialising ||
+			since_latest_block.map_or(false, |duration| duration <= STATE_CHAIN_LIVE_TIMEOUT);
+		let ready = has_completed_initialising &&
+			state_chain.ready &&
+			chains.values().all(|chain| chain.ready);
+
+		HealthReport {
+			status: if has_completed_initialising { RUNNING } else { INITIALISING },
+			live,
+			ready,
+			state_chain,
+			chains,
+			rpc_endpoints,
+			p2p_connected_peers: self.p2p_connected_peers,
+			epoch: self
+				.epoch_participation
+				.map(|(index, participating)| EpochReport { index, participating }),
+		}
+	}
+}
+
 #[tracing::instrument(name = "health-check", skip_all)]
 pub async fn start<'a, 'env>(
 	scope: &'a task_scope::Scope<'env, anyhow::Error>,
@@ -24,18 +320,57 @@ pub async fn start<'a, 'env>(
 
 	const PATH: &str = "health";
 
-	let future =
-		warp::serve(warp::any().and(warp::path(PATH)).and(warp::path::end()).map(move || {
+	let status = {
+		let has_completed_initialising = has_completed_initialising.clone();
+		warp::path::end().map(move || {
+			if has_completed_initialising.load(std::sync::atomic::Ordering::Relaxed) {
+				RUNNING
+			} else {
+				INITIALISING
+			}
+		})
+	};
+
+	let report = {
+		let report = move || {
+			with_health(|health| {
+				health.report(
+					has_completed_initialising.load(std::sync::atomic::Ordering::Relaxed),
+					Instant::now(),
+				)
+			})
+		};
+		let reply = |ok: bool, report: HealthReport| {
 			warp::reply::with_status(
-				if has_completed_initialising.load(std::sync::atomic::Ordering::Relaxed) {
-					RUNNING
-				} else {
-					INITIALISING
-				},
-				warp::http::StatusCode::OK,
+				warp::reply::json(&report),
+				if ok { StatusCode::OK } else { StatusCode::SERVICE_UNAVAILABLE },
 			)
-		}))
-		.bind((health_check_settings.hostname.parse::<IpAddr>()?, health_check_settings.port));
+		};
+		warp::path("status")
+			.and(warp::path::end())
+			.map({
+				let report = report.clone();
+				move || reply(true, report())
+			})
+			.or(warp::path("live").and(warp::path::end()).map({
+				let report = report.clone();
+				move || {
+					let report = report();
+					reply(report.live, report)
+				}
+			}))
+			.or(warp::path("ready").and(warp::path::end()).map(move || {
+				let report = report();
+				reply(report.ready, report)
+			}))
+	};
+
+	let future = warp::serve(
+		warp::any()
+			.and(warp::path(PATH))
+			.and(status.map(|status| warp::reply::with_status(status, StatusCode::OK)).or(report)),
+	)
+	.bind((health_check_settings.hostname.parse::<IpAddr>()?, health_check_settings.port));
 
 	scope.spawn_weak(async move {
 		future.await;
@@ -82,13 +417,42 @@ mod tests {
 					}
 				};
 
+				let request_report = |path: &'static str, expected_status: reqwest::StatusCode| {
+					let health_check = health_check.clone();
+
+					async move {
+						let resp = reqwest::get(&format!(
+							"http://{}:{}/{}",
+							&health_check.hostname, &health_check.port, path
+						))
+						.await
+						.unwrap();
+
+						assert_eq!(expected_status, resp.status());
+						serde_json::from_str::<serde_json::Value>(&resp.text().await.unwrap())
+							.unwrap()
+					}
+				};
+
 				// starts with `has_completed_initialising` set to false
 				request_test("health", reqwest::StatusCode::OK, INITIALISING).await;
 				request_test("invalid", reqwest::StatusCode::NOT_FOUND, "").await;
+				assert_eq!(
+					request_report("health/status", reqwest::StatusCode::OK).await["status"],
+					INITIALISING
+				);
+				request_report("health/live", reqwest::StatusCode::OK).await;
+				request_report("health/ready", reqwest::StatusCode::SERVICE_UNAVAILABLE).await;
 
 				has_completed_initialising.store(true, std::sync::atomic::Ordering::Relaxed);
+				record_state_chain_block(1);
 
 				request_test("health", reqwest::StatusCode::OK, RUNNING).await;
+				assert_eq!(
+					request_report("health/ready", reqwest::StatusCode::OK).await["state_chain"]
+						["latest_block"],
+					1
+				);
 
 				Ok(())
 			}
@@ -97,4 +461,96 @@ mod tests {
 		.await
 		.unwrap();
 	}
+
+	#[test]
+	fn reports_the_witnessing_lag_of_the_slowest_pipeline() {
+		let now = Instant::now();
+		let mut health = HealthState {
+			state_chain_block: Some((10, now)),
+			chains: BTreeMap::from([(
+				"Ethereum",
+				ChainHealth {
+					tip: Some(120),
+					max_witnessing_lag: 8,
+					source_tips: BTreeMap::from([("safe", 110), ("unsafe", 120)]),
+					witnessed: BTreeMap::from([
+						(
+							"Vault",
+							PipelineHealth { source: "safe", vaults: BTreeMap::from([(1, 105)]) },
+						),
+						(
+							"KeyManager",
+							PipelineHealth {
+								source: "safe",
+								vaults: BTreeMap::from([(1, 104), (2, 109)]),
+							},
+						),
+						(
+							"Prewitnessing",
+							PipelineHealth { source: "unsafe", vaults: BTreeMap::from([(2, 118)]) },
+						),
+					]),
+				},
+			)]),
+			..Default::default()
+		};
+
+		let report = health.report(true, now);
+		assert_eq!(report.chains["Ethereum"].witnessed["KeyManager"], 104);
+		assert_eq!(report.chains["Ethereum"].lag, Some(6));
+		assert!(report.chains["Ethereum"].ready);
+		assert!(report.ready);
+
+		health.chains.get_mut("Ethereum").unwrap().source_tips.insert("safe", 120);
+		let report = health.report(true, now);
+		assert_eq!(report.chains["Ethereum"].lag, Some(16));
+		assert!(!report.chains["Ethereum"].ready);
+		assert!(!report.ready);
+		assert!(report.live);
+	}
+
+	#[test]
+	fn witnessing_progress_is_removed_once_the_vault_stops() {
+		const CHAIN: &str = "TestChain";
+		let chain_report = || {
+			with_health(|health| health.report(true, Instant::now()))
+				.chains
+				.remove(CHAIN)
+				.unwrap()
+		};
+
+		record_chain_tip(CHAIN, 120, 8);
+		record_source_tip(CHAIN, "safe", 110);
+		let old_vault = WitnessedBlocks::new(CHAIN, "safe", "witnessing", 1);
+		let new_vault = WitnessedBlocks::new(CHAIN, "safe", "witnessing", 2);
+		old_vault.record(90);
+		new_vault.record(108);
+		assert_eq!(chain_report().lag, Some(20));
+
+		// The old vault's stream ends, so it no longer holds the pipeline back.
+		drop(old_vault);
+		assert_eq!(chain_report().lag, Some(2));
+
+		// Once the pipeline stops there is nothing to compare.
+		drop(new_vault);
+		let report = chain_report();
+		assert!(report.witnessed.is_empty());
+		assert_eq!(report.lag, None);
+		assert!(report.ready);
+	}
+
+	#[test]
+	fn not_live_once_the_state_chain_stream_stops() {
+		let start = Instant::now();
+		let health = HealthState { state_chain_block: Some((10, start)), ..Default::default() };
+
+		let report = health.report(true, start + STATE_CHAIN_READY_TIMEOUT * 2);
+		assert!(report.live);
+		assert!(!report.ready);
+
+		let report = health.report(true, start + STATE_CHAIN_LIVE_TIMEOUT * 2);
+		assert!(!report.live);
+		// It is live while initialising, whatever the State Chain stream is doing.
+		assert!(health.report(false, start + STATE_CHAIN_LIVE_TIMEOUT * 2).live);
+	}
 }
@@ -11,7 +11,9 @@ pub mod constants;
 pub mod db;
 pub mod health;
 pub mod multisig;
+pub mod observer;
 pub mod p2p;
+pub mod recording;
 pub mod retrier;
 pub mod settings;
 pub mod state_chain_observer;
@@ -1,30 +1,47 @@
 use anyhow::Context;
 use cf_chains::dot::PolkadotHash;
-use cf_primitives::{AccountRole, SemVer};
+use cf_primitives::{AccountRole, ForeignChain, SemVer};
 use chainflip_engine::{
 	btc::retry_rpc::BtcRetryRpcClient,
-	db::{KeyStore, PersistentKeyDB},
+	db::{self, EncryptionSecret, KeySharePruning, KeyStore, PersistentKeyDB},
 	dot::retry_rpc::DotRetryRpcClient,
 	eth::retry_rpc::EthersRetryRpcClient,
 	health, p2p,
-	settings::{CommandLineOptions, Settings, DEFAULT_SETTINGS_DIR},
+	recording::Recorder,
+	settings::{
+		self, BackupSecretOptions, CommandLineOptions, DbCommand, DbSecretOptions, DbSubcommand,
+		EngineCommand, RewitnessChain, Settings, DEFAULT_SETTINGS_DIR,
+	},
 	state_chain_observer::{
 		self,
 		client::{
 			chain_api::ChainApi, extrinsic_api::signed::SignedExtrinsicApi, storage_api::StorageApi,
 		},
 	},
-	witness::{self, common::STATE_CHAIN_CONNECTION},
+	witness::{self, common::STATE_CHAIN_CONNECTION, rewitness::RewitnessClient},
 };
 use chainflip_node::chain_spec::use_chainflip_account_id_encoding;
 use clap::Parser;
 use futures::FutureExt;
-use multisig::{self, bitcoin::BtcSigning, eth::EthSigning, polkadot::PolkadotSigning};
+use multisig::{
+	self,
+	bitcoin::BtcSigning,
+	client::BroadcastSigner,
+	eth::{ArbSigning, EthSigning},
+	polkadot::PolkadotSigning,
+};
 use std::{
+	io::Write,
+	path::{Path, PathBuf},
 	sync::{atomic::AtomicBool, Arc},
 	time::Duration,
 };
-use utilities::{metrics, task_scope::task_scope, CachedStream};
+use utilities::{
+	logging::LoggingSettings,
+	metrics,
+	task_scope::{task_scope, Scope},
+	CachedStream,
+};
 
 lazy_static::lazy_static! {
 	static ref CFE_VERSION: SemVer = SemVer {
@@ -39,14 +56,358 @@ async fn main() -> anyhow::Result<()> {
 	use_chainflip_account_id_encoding();
 
 	let opts = CommandLineOptions::parse();
+	let cmd = opts.cmd.clone();
+
+	// The database is local, so the settings aren't needed.
+	if let Some(EngineCommand::Db(db_command)) = cmd {
+		let _start_logger_server_fn =
+			utilities::logging::init_json_logger(LoggingSettings::default()).await;
+		return run_db_command(db_command)
+	}
 
 	// the settings directory from opts.config_root that we'll use to read the settings file
 	let settings = Settings::new_with_settings_dir(DEFAULT_SETTINGS_DIR, opts)
 		.context("Error reading settings")?;
 
-	// Note: the greeting should only be printed in normal mode (i.e. not for short-lived commands
-	// like `--version`), so we execute it only after the settings have been parsed.
-	utilities::print_start_and_end!(async run_main(settings));
+	match cmd {
+		None => {
+			// Note: the greeting should only be printed in normal mode (i.e. not for short-lived
+			// commands like `--version`), so we execute it only after the settings have been
+			// parsed.
+			if let Some(observer_settings) = settings.observer.clone() {
+				utilities::print_start_and_end!(async run_observer(settings, observer_settings));
+			} else {
+				utilities::print_start_and_end!(async run_main(settings));
+			}
+		},
+		Some(EngineCommand::Rewitness { chain, from, to }) =>
+			run_rewitness(settings, chain, from, to).await?,
+		Some(EngineCommand::Db(_)) => unreachable!("Db commands are handled above"),
+	}
+
+	Ok(())
+}
+
+/// Witnesses a range of blocks of a chain again, submitting only the witnesses that are still
+/// pending on the State Chain, and exits once the whole range has been processed.
+async fn run_rewitness(
+	settings: Settings,
+	chain: RewitnessChain,
+	from: u64,
+	to: u64,
+) -> anyhow::Result<()> {
+	// The engine holds the lock on its database while it is running. Holding it here means we
+	// never submit witnesses with the same account, and so the same nonces, as a running engine.
+	let _db = PersistentKeyDB::open_for_maintenance(&settings.signing.db_file).context(
+		"Failed to open the engine's database. The engine must be stopped before rewitnessing",
+	)?;
+
+	let (rewitnessed_sender, rewitnessed_receiver) = tokio::sync::oneshot::channel();
+
+	// The State Chain client and the witnessing adapters spawn tasks that never end, so instead of
+	// waiting for the scope to exit we drop it, cancelling them, once the range is rewitnessed.
+	tokio::select! {
+		result = task_scope(|scope| {
+			async move {
+				// The logger's command server is not started, as the engine may already be using
+				// its port.
+				let _start_logger_server_fn =
+					utilities::logging::init_json_logger(settings.logging.clone()).await;
+
+				let (state_chain_stream, _unfinalised_state_chain_stream, state_chain_client) =
+					state_chain_observer::client::StateChainClient::connect_with_account(
+						scope,
+						&settings.state_chain.ws_endpoint,
+						&settings.state_chain.signing_key_file,
+						AccountRole::Validator,
+						false,
+						Some((*CFE_VERSION, false)),
+					)
+					.await?;
+
+				let client = match chain {
+					RewitnessChain::Eth => RewitnessClient::Ethereum(
+						create_eth_client(scope, settings.eth, true, &*state_chain_client).await?,
+					),
+					RewitnessChain::Dot => RewitnessClient::Polkadot(
+						create_dot_client(scope, settings.dot, &*state_chain_client).await?,
+					),
+					RewitnessChain::Btc => RewitnessClient::Bitcoin(
+						create_btc_client(scope, settings.btc, &*state_chain_client).await?,
+					),
+					RewitnessChain::Arb => RewitnessClient::Arbitrum(
+						create_arb_client(
+							scope,
+							settings.arb.context("Arbitrum is not configured")?,
+							true,
+							&*state_chain_client,
+						)
+						.await?,
+					),
+				};
+
+				witness::rewitness::rewitness(
+					scope,
+					client,
+					from,
+					to,
+					state_chain_client,
+					state_chain_stream,
+				)
+				.await?;
+
+				let _result = rewitnessed_sender.send(());
+
+				futures::future::pending().await
+			}
+			.boxed()
+		}) => result,
+		Ok(()) = rewitnessed_receiver => Ok(()),
+	}
+}
+
+/// Runs a maintenance command on the database. The engine must not be using the database.
+fn run_db_command(DbCommand { assume_yes, subcommand }: DbCommand) -> anyhow::Result<()> {
+	fn encryption_secret(
+		key_file: Option<PathBuf>,
+		passphrase: Option<String>,
+	) -> Option<EncryptionSecret> {
+		key_file.map(EncryptionSecret::KeyFile).or_else(|| {
+			passphrase.map(|passphrase| EncryptionSecret::Passphrase(passphrase.into()))
+		})
+	}
+
+	fn db_secret(options: DbSecretOptions) -> Option<EncryptionSecret> {
+		encryption_secret(options.db_key_file, options.db_passphrase)
+	}
+
+	fn backup_secret(options: BackupSecretOptions) -> EncryptionSecret {
+		encryption_secret(options.backup_key_file, options.backup_passphrase)
+			.expect("Clap requires one of the backup secrets")
+	}
+
+	fn ensure_db_exists(db_file: &Path) -> anyhow::Result<()> {
+		anyhow::ensure!(db_file.exists(), "No database found at {}", db_file.display());
+		Ok(())
+	}
+
+	fn read_backup(backup_file: &Path) -> anyhow::Result<Vec<u8>> {
+		std::fs::read(backup_file)
+			.with_context(|| format!("Could not read {}", backup_file.display()))
+	}
+
+	fn confirm(assume_yes: bool, message: std::fmt::Arguments<'_>) -> anyhow::Result<()> {
+		println!("{message}");
+		if assume_yes {
+			return Ok(())
+		}
+		print!("Do you wish to proceed? [y/n] > ");
+		std::io::stdout().flush()?;
+		let mut input = String::new();
+		std::io::stdin().read_line(&mut input)?;
+		match input.trim() {
+			"y" | "yes" => Ok(()),
+			_ => Err(anyhow::anyhow!("Aborted")),
+		}
+	}
+
+	match subcommand {
+		DbSubcommand::RotateEncryption {
+			db_file,
+			genesis_hash,
+			current_key_file,
+			current_passphrase,
+			new_key_file,
+			new_passphrase,
+			disable_encryption: _,
+			rotate_data_key,
+		} => {
+			ensure_db_exists(&db_file)?;
+
+			let mut db = PersistentKeyDB::open_and_migrate_to_latest_with_encryption(
+				&db_file,
+				genesis_hash.genesis_hash,
+				encryption_secret(current_key_file, current_passphrase).as_ref(),
+			)?;
+
+			let new_secret = encryption_secret(new_key_file, new_passphrase);
+
+			confirm(
+				assume_yes,
+				format_args!(
+					"Changing the encryption of the key shares in {}. Encrypted after the change: {}",
+					db_file.display(),
+					new_secret.is_some()
+				),
+			)?;
+
+			db.change_encryption(new_secret.as_ref(), rotate_data_key)?;
+			if db.is_encrypted() {
+				println!("The key shares are now encrypted with the new secret.");
+				let unencrypted_backups = PersistentKeyDB::find_unencrypted_backups(&db_file);
+				if !unencrypted_backups.is_empty() {
+					println!(
+						"{} backups still contain unencrypted key shares. They can be removed with the `db remove-unencrypted-backups` command.",
+						unencrypted_backups.len()
+					);
+				}
+			} else {
+				println!("The key shares are now stored unencrypted.");
+			}
+		},
+		DbSubcommand::Export {
+			db_file,
+			genesis_hash,
+			db_secret: db_secret_options,
+			chain,
+			key_ids,
+			backup_file,
+			backup_secret: backup_secret_options,
+		} => {
+			ensure_db_exists(&db_file)?;
+
+			let db = PersistentKeyDB::open_and_migrate_to_latest_with_encryption(
+				&db_file,
+				genesis_hash.genesis_hash,
+				db_secret(db_secret_options).as_ref(),
+			)?;
+
+			let backup = db.export_keys(chain, &key_ids, &backup_secret(backup_secret_options))?;
+
+			std::fs::write(&backup_file, backup)
+				.with_context(|| format!("Could not write to {}", backup_file.display()))?;
+
+			println!("{chain:?} key shares exported to {}.", backup_file.display());
+		},
+		DbSubcommand::VerifyBackup { backup_file, backup_secret: backup_secret_options } => {
+			let summary = db::verify_key_backup(
+				&read_backup(&backup_file)?,
+				&backup_secret(backup_secret_options),
+			)?;
+
+			println!(
+				"The backup is valid. It contains {} {} keys from a database with schema version {} and genesis hash {}:\n{}",
+				summary.key_ids.len(),
+				summary.chain,
+				summary.schema_version,
+				summary.genesis_hash.map(|hash| format!("{hash:#x}")).unwrap_or_else(|| "unknown".to_string()),
+				summary.key_ids.iter().map(|key_id| key_id.to_string()).collect::<Vec<_>>().join("\n"),
+			);
+		},
+		DbSubcommand::Import {
+			db_file,
+			genesis_hash,
+			db_secret: db_secret_options,
+			backup_file,
+			backup_secret: backup_secret_options,
+		} => {
+			let backup = read_backup(&backup_file)?;
+
+			let db = PersistentKeyDB::open_and_migrate_to_latest_with_encryption(
+				&db_file,
+				genesis_hash.genesis_hash,
+				db_secret(db_secret_options).as_ref(),
+			)?;
+
+			confirm(
+				assume_yes,
+				format_args!(
+					"Importing the key shares in {} into {}.",
+					backup_file.display(),
+					db_file.display()
+				),
+			)?;
+
+			let key_ids = db.import_keys(
+				&backup,
+				&backup_secret(backup_secret_options),
+				genesis_hash.allow_unknown_genesis_hash,
+			)?;
+
+			println!("Imported {} keys into {}.", key_ids.len(), db_file.display());
+		},
+		DbSubcommand::Inspect { db_file } => {
+			let summary = PersistentKeyDB::open_for_maintenance(&db_file)?.inspect()?;
+
+			println!(
+				"Schema version: {}\nGenesis hash: {}\nEncrypted: {}\nKey shares:\n{}\nProcessed blocks:\n{}",
+				summary.schema_version,
+				summary.genesis_hash.map(|hash| format!("{hash:#x}")).unwrap_or_else(|| "unknown".to_string()),
+				summary.encrypted,
+				summary.key_shares.iter().map(|entry| format!("  {}: {}", entry.chain, entry.key_id)).collect::<Vec<_>>().join("\n"),
+				summary.processed_blocks.iter().map(|entry| format!("  {} epoch {}", entry.witnesser, entry.epoch_index)).collect::<Vec<_>>().join("\n"),
+			);
+		},
+		DbSubcommand::RemoveUnencryptedBackups { db_file } => {
+			anyhow::ensure!(
+				PersistentKeyDB::open_for_maintenance(&db_file)?.inspect()?.encrypted,
+				"The key shares in {} are not encrypted, so its backups are kept",
+				db_file.display()
+			);
+
+			let backup_paths = PersistentKeyDB::find_unencrypted_backups(&db_file);
+			if backup_paths.is_empty() {
+				println!("No backups of {} contain unencrypted key shares.", db_file.display());
+			} else {
+				confirm(
+					assume_yes,
+					format_args!(
+						"Removing these backups of {}, which contain unencrypted key shares:\n{}",
+						db_file.display(),
+						backup_paths
+							.iter()
+							.map(|path| format!("  {}", path.display()))
+							.collect::<Vec<_>>()
+							.join("\n")
+					),
+				)?;
+
+				PersistentKeyDB::remove_backups(&backup_paths)?;
+
+				println!("Removed {} backups.", backup_paths.len());
+			}
+		},
+		DbSubcommand::Snapshot { db_file, snapshot_dir } => {
+			let snapshot_path =
+				PersistentKeyDB::create_snapshot_of_shared_db(&db_file, snapshot_dir.as_deref())?;
+
+			println!("Snapshot created at {}.", snapshot_path.display());
+		},
+		DbSubcommand::Prune { db_file, last_expired_epoch, key_shares } => {
+			let db = PersistentKeyDB::open_for_maintenance(&db_file)?;
+
+			confirm(
+				assume_yes,
+				format_args!(
+					"Removing the {} of epochs up to {last_expired_epoch} from {}.",
+					if key_shares {
+						"key shares and processed block records"
+					} else {
+						"processed block records"
+					},
+					db_file.display()
+				),
+			)?;
+
+			let summary = db.prune_expired_epochs(
+				last_expired_epoch,
+				if key_shares {
+					KeySharePruning::SnapshotAndRemove { db_path: &db_file }
+				} else {
+					KeySharePruning::Keep
+				},
+			)?;
+
+			if let Some(snapshot) = &summary.snapshot {
+				println!("Snapshot of the key shares created at {}.", snapshot.display());
+			}
+			println!(
+				"Removed {} key shares and {} processed block records.",
+				summary.key_shares.len(),
+				summary.processed_blocks.len()
+			);
+		},
+	}
 
 	Ok(())
 }
@@ -87,13 +448,27 @@ async fn run_main(settings: Settings) -> anyhow::Result<()> {
 			}
 
 			let db = Arc::new(
-				PersistentKeyDB::open_and_migrate_to_latest(
+				PersistentKeyDB::open_and_migrate_to_latest_with_encryption(
 					&settings.signing.db_file,
 					Some(state_chain_client.genesis_hash()),
+					settings
+						.signing
+						.db_encryption_key_file
+						.clone()
+						.map(EncryptionSecret::KeyFile)
+						.as_ref(),
 				)
 				.context("Failed to open database")?,
 			);
 
+			scope.spawn(db::maintenance::start(
+				db.clone(),
+				settings.signing.db_file.clone(),
+				settings.signing.prune_expired_key_shares,
+				state_chain_client.clone(),
+				state_chain_stream.clone(),
+			));
+
 			let (
 				eth_outgoing_sender,
 				eth_incoming_receiver,
@@ -101,6 +476,8 @@ async fn run_main(settings: Settings) -> anyhow::Result<()> {
 				dot_incoming_receiver,
 				btc_outgoing_sender,
 				btc_incoming_receiver,
+				arb_outgoing_sender,
+				arb_incoming_receiver,
 				peer_update_sender,
 				p2p_ready_receiver,
 				p2p_fut,
@@ -114,9 +491,12 @@ async fn run_main(settings: Settings) -> anyhow::Result<()> {
 
 			scope.spawn(p2p_fut);
 
+			let peer_key = p2p::read_peer_key(&settings.node_p2p.node_key_file)?;
+
 			let (eth_multisig_client, eth_multisig_client_backend_future) =
 				chainflip_engine::multisig::start_client::<EthSigning>(
 					state_chain_client.account_id(),
+					BroadcastSigner::new(peer_key.clone(), ForeignChain::Ethereum),
 					KeyStore::new(db.clone()),
 					eth_incoming_receiver,
 					eth_outgoing_sender,
@@ -134,6 +514,7 @@ async fn run_main(settings: Settings) -> anyhow::Result<()> {
 			let (dot_multisig_client, dot_multisig_client_backend_future) =
 				chainflip_engine::multisig::start_client::<PolkadotSigning>(
 					state_chain_client.account_id(),
+					BroadcastSigner::new(peer_key.clone(), ForeignChain::Polkadot),
 					KeyStore::new(db.clone()),
 					dot_incoming_receiver,
 					dot_outgoing_sender,
@@ -151,6 +532,7 @@ async fn run_main(settings: Settings) -> anyhow::Result<()> {
 			let (btc_multisig_client, btc_multisig_client_backend_future) =
 				chainflip_engine::multisig::start_client::<BtcSigning>(
 					state_chain_client.account_id(),
+					BroadcastSigner::new(peer_key.clone(), ForeignChain::Bitcoin),
 					KeyStore::new(db.clone()),
 					btc_incoming_receiver,
 					btc_outgoing_sender,
@@ -165,44 +547,48 @@ async fn run_main(settings: Settings) -> anyhow::Result<()> {
 
 			scope.spawn(btc_multisig_client_backend_future);
 
-			// Create all the clients
-			let eth_client = {
-				let expected_eth_chain_id = web3::types::U256::from(
-					state_chain_client
-						.storage_value::<pallet_cf_environment::EthereumChainId<state_chain_runtime::Runtime>>(
-							state_chain_client.latest_finalized_block().hash,
-						)
-						.await
-						.expect(STATE_CHAIN_CONNECTION),
-				);
-				EthersRetryRpcClient::new(
-					scope,
-					settings.eth.private_key_file,
-					settings.eth.nodes,
-					expected_eth_chain_id,
-				)?
-			};
-			let btc_client = {
-				let expected_btc_network = cf_chains::btc::BitcoinNetwork::from(
-					state_chain_client
-						.storage_value::<pallet_cf_environment::ChainflipNetworkEnvironment<
-							state_chain_runtime::Runtime,
-						>>(state_chain_client.latest_finalized_block().hash)
-						.await
-						.expect(STATE_CHAIN_CONNECTION),
+			// Without the Arbitrum settings, the Arbitrum channels are dropped and Arbitrum
+			// ceremonies are ignored.
+			let arb_multisig_client = if settings.arb.is_some() {
+				let (arb_multisig_client, arb_multisig_client_backend_future) =
+					chainflip_engine::multisig::start_client::<ArbSigning>(
+						state_chain_client.account_id(),
+						BroadcastSigner::new(peer_key.clone(), ForeignChain::Arbitrum),
+						KeyStore::new(db.clone()),
+						arb_incoming_receiver,
+						arb_outgoing_sender,
+						state_chain_client
+							.storage_value::<pallet_cf_vaults::CeremonyIdCounter<
+								state_chain_runtime::Runtime,
+								state_chain_runtime::ArbitrumInstance,
+							>>(state_chain_stream.cache().hash)
+							.await
+							.context("Failed to get Arbitrum CeremonyIdCounter from SC")?,
+					);
+
+				scope.spawn(arb_multisig_client_backend_future);
+
+				Some(arb_multisig_client)
+			} else {
+				tracing::warn!(
+					"Arbitrum is not configured, so it won't be witnessed and this node won't take part in Arbitrum ceremonies"
 				);
-				BtcRetryRpcClient::new(scope, settings.btc.nodes, expected_btc_network).await?
+				drop((arb_incoming_receiver, arb_outgoing_sender));
+				None
 			};
-			let dot_client = {
-				let expected_dot_genesis_hash = PolkadotHash::from(
-					state_chain_client
-						.storage_value::<pallet_cf_environment::PolkadotGenesisHash<state_chain_runtime::Runtime>>(
-							state_chain_client.latest_finalized_block().hash,
-						)
-						.await
-						.expect(STATE_CHAIN_CONNECTION),
-				);
-				DotRetryRpcClient::new(scope, settings.dot.nodes, expected_dot_genesis_hash)?
+
+			let eth_witnessing_mode = settings.eth.witnessing_mode;
+			let dot_witnessing_mode = settings.dot.witnessing_mode;
+
+			// Create all the clients
+			let eth_client =
+				create_eth_client(scope, settings.eth, true, &*state_chain_client).await?;
+			let btc_client = create_btc_client(scope, settings.btc, &*state_chain_client).await?;
+			let dot_client = create_dot_client(scope, settings.dot, &*state_chain_client).await?;
+			let arb_client = match settings.arb {
+				Some(arb_settings) =>
+					Some(create_arb_client(scope, arb_settings, true, &*state_chain_client).await?),
+				None => None,
 			};
 
 			witness::start::start(
@@ -210,6 +596,9 @@ async fn run_main(settings: Settings) -> anyhow::Result<()> {
 				eth_client.clone(),
 				btc_client.clone(),
 				dot_client.clone(),
+				arb_client.clone(),
+				eth_witnessing_mode,
+				dot_witnessing_mode,
 				state_chain_client.clone(),
 				state_chain_stream.clone(),
 				unfinalised_state_chain_stream.clone(),
@@ -226,6 +615,7 @@ async fn run_main(settings: Settings) -> anyhow::Result<()> {
 				eth_multisig_client,
 				dot_multisig_client,
 				btc_multisig_client,
+				arb_client.zip(arb_multisig_client),
 				peer_update_sender,
 			));
 
@@ -239,3 +629,188 @@ async fn run_main(settings: Settings) -> anyhow::Result<()> {
 	})
 	.await
 }
+
+/// Witnesses every vault without a validator account, publishing what would have been witnessed
+/// instead of signing and submitting it.
+async fn run_observer(
+	settings: Settings,
+	observer_settings: settings::Observer,
+) -> anyhow::Result<()> {
+	task_scope(|scope| {
+		async move {
+			let start_logger_server_fn =
+				utilities::logging::init_json_logger(settings.logging.clone()).await;
+
+			let has_completed_initialising = Arc::new(AtomicBool::new(false));
+
+			let (state_chain_stream, unfinalised_state_chain_stream, state_chain_client) =
+				state_chain_observer::client::StateChainClient::connect_without_account(
+					scope,
+					&settings.state_chain.ws_endpoint,
+					Some((*CFE_VERSION, true)),
+				)
+				.await?;
+
+			start_logger_server_fn(scope);
+
+			if let Some(health_check_settings) = &settings.health_check {
+				health::start(scope, health_check_settings, has_completed_initialising.clone())
+					.await?;
+			}
+
+			if let Some(prometheus_settings) = &settings.prometheus {
+				metrics::start(scope, prometheus_settings).await?;
+			}
+
+			// Only used to keep track of the witnessing progress, so there is nothing to encrypt.
+			let db = Arc::new(
+				PersistentKeyDB::open_and_migrate_to_latest(
+					&settings.signing.db_file,
+					Some(state_chain_client.genesis_hash()),
+				)
+				.context("Failed to open database")?,
+			);
+
+			let eth_witnessing_mode = settings.eth.witnessing_mode;
+			let dot_witnessing_mode = settings.dot.witnessing_mode;
+
+			let eth_client =
+				create_eth_client(scope, settings.eth, false, &*state_chain_client).await?;
+			let btc_client = create_btc_client(scope, settings.btc, &*state_chain_client).await?;
+			let dot_client = create_dot_client(scope, settings.dot, &*state_chain_client).await?;
+			let arb_client = match settings.arb {
+				Some(arb_settings) =>
+					Some(create_arb_client(scope, arb_settings, false, &*state_chain_client).await?),
+				None => None,
+			};
+
+			chainflip_engine::observer::start(
+				scope,
+				&observer_settings,
+				eth_client,
+				btc_client,
+				dot_client,
+				arb_client,
+				eth_witnessing_mode,
+				dot_witnessing_mode,
+				state_chain_client,
+				state_chain_stream,
+				unfinalised_state_chain_stream,
+				db,
+			)
+			.await?;
+
+			has_completed_initialising.store(true, std::sync::atomic::Ordering::Relaxed);
+
+			Ok(())
+		}
+		.boxed()
+	})
+	.await
+}
+
+/// Without a signer the client can only be used for witnessing.
+async fn create_eth_client<StateChainClient: StorageApi + ChainApi>(
+	scope: &Scope<'_, anyhow::Error>,
+	settings: settings::Eth,
+	with_signer: bool,
+	state_chain_client: &StateChainClient,
+) -> anyhow::Result<EthersRetryRpcClient> {
+	let expected_eth_chain_id = web3::types::U256::from(
+		state_chain_client
+			.storage_value::<pallet_cf_environment::EthereumChainId<state_chain_runtime::Runtime>>(
+				state_chain_client.latest_finalized_block().hash,
+			)
+			.await
+			.expect(STATE_CHAIN_CONNECTION),
+	);
+	let eth_client = EthersRetryRpcClient::new(
+		scope,
+		with_signer.then_some(settings.private_key_file),
+		settings.nodes,
+		expected_eth_chain_id,
+	)?;
+	let eth_client = match settings.recording_file {
+		Some(path) => eth_client.with_recorder(Recorder::new(scope, &path)?),
+		None => eth_client,
+	};
+	Ok(if settings.cross_check_rpcs { eth_client.with_cross_checking(scope) } else { eth_client })
+}
+
+async fn create_btc_client<StateChainClient: StorageApi + ChainApi>(
+	scope: &Scope<'_, anyhow::Error>,
+	settings: settings::Btc,
+	state_chain_client: &StateChainClient,
+) -> anyhow::Result<BtcRetryRpcClient> {
+	let expected_btc_network = cf_chains::btc::BitcoinNetwork::from(
+		state_chain_client
+			.storage_value::<pallet_cf_environment::ChainflipNetworkEnvironment<state_chain_runtime::Runtime>>(
+				state_chain_client.latest_finalized_block().hash,
+			)
+			.await
+			.expect(STATE_CHAIN_CONNECTION),
+	);
+	let btc_client = BtcRetryRpcClient::new(
+		scope,
+		settings.nodes,
+		expected_btc_network,
+		settings.block_filters,
+	)
+	.await?;
+	let btc_client = match settings.recording_file {
+		Some(path) => btc_client.with_recorder(Recorder::new(scope, &path)?),
+		None => btc_client,
+	};
+	Ok(if settings.cross_check_rpcs { btc_client.with_cross_checking(scope) } else { btc_client })
+}
+
+async fn create_dot_client<StateChainClient: StorageApi + ChainApi>(
+	scope: &Scope<'_, anyhow::Error>,
+	settings: settings::Dot,
+	state_chain_client: &StateChainClient,
+) -> anyhow::Result<DotRetryRpcClient> {
+	let expected_dot_genesis_hash = PolkadotHash::from(
+		state_chain_client
+			.storage_value::<pallet_cf_environment::PolkadotGenesisHash<state_chain_runtime::Runtime>>(
+				state_chain_client.latest_finalized_block().hash,
+			)
+			.await
+			.expect(STATE_CHAIN_CONNECTION),
+	);
+	let dot_client = DotRetryRpcClient::new(scope, settings.nodes, expected_dot_genesis_hash)?;
+	let dot_client = match settings.recording_file {
+		Some(path) => dot_client.with_recorder(Recorder::new(scope, &path)?),
+		None => dot_client,
+	};
+	Ok(if settings.cross_check_rpcs { dot_client.with_cross_checking(scope) } else { dot_client })
+}
+
+/// Without a signer the client can only be used for witnessing.
+async fn create_arb_client<StateChainClient: StorageApi + ChainApi>(
+	scope: &Scope<'_, anyhow::Error>,
+	settings: settings::Arb,
+	with_signer: bool,
+	state_chain_client: &StateChainClient,
+) -> anyhow::Result<EthersRetryRpcClient> {
+	let expected_arb_chain_id = web3::types::U256::from(
+		state_chain_client
+			.storage_value::<pallet_cf_environment::ArbitrumChainId<state_chain_runtime::Runtime>>(
+				state_chain_client.latest_finalized_block().hash,
+			)
+			.await
+			.expect(STATE_CHAIN_CONNECTION),
+	);
+	let arb_client = EthersRetryRpcClient::new_with_retrier_names(
+		scope,
+		with_signer.then_some(settings.private_key_file),
+		settings.nodes,
+		expected_arb_chain_id,
+		"arb_rpc",
+		"arb_subscribe",
+	)?;
+	let arb_client = match settings.recording_file {
+		Some(path) => arb_client.with_recorder(Recorder::new(scope, &path)?),
+		None => arb_client,
+	};
+	Ok(if settings.cross_check_rpcs { arb_client.with_cross_checking(scope) } else { arb_client })
+}
@@ -1,7 +1,9 @@
+use std::sync::Arc;
+
 use anyhow::Result;
 use cf_primitives::CeremonyId;
 
-use multisig::{ChainSigning, MultisigClient};
+use multisig::{client::BroadcastSigner, ChainSigning, MultisigClient};
 use tracing::{info, info_span, Instrument};
 
 use crate::{
@@ -13,6 +15,7 @@ use state_chain_runtime::AccountId;
 /// Start the multisig client, which listens for p2p messages and requests from the SC
 pub fn start_client<C: ChainSigning>(
 	my_account_id: AccountId,
+	broadcast_signer: BroadcastSigner,
 	key_store: KeyStore<C>,
 	incoming_p2p_message_receiver: MultisigMessageReceiver<<C as ChainSigning>::ChainCrypto>,
 	outgoing_p2p_message_sender: MultisigMessageSender<<C as ChainSigning>::ChainCrypto>,
@@ -31,6 +34,7 @@ pub fn start_client<C: ChainSigning>(
 
 		let ceremony_manager = CeremonyManager::<C>::new(
 			my_account_id,
+			Arc::new(broadcast_signer),
 			outgoing_p2p_message_sender.0,
 			latest_ceremony_id,
 		);
@@ -0,0 +1,289 @@
+//! Observer mode of the CFE, for infrastructure that isn't a validator.
+//! Runs the same witnessing as a validator, for every vault, but instead of signing and submitting
+//! the witnesses it publishes them to the subscribers of
+//! `subscribe_witnessing` on {listen_address}.
+
+use std::{
+	collections::VecDeque,
+	sync::{Arc, Mutex},
+};
+
+use cf_primitives::EpochIndex;
+use codec::Encode;
+use jsonrpsee::{server::ServerBuilder, RpcModule};
+use serde::Serialize;
+use tokio::sync::watch;
+use tracing::{debug, info, warn};
+use utilities::task_scope::Scope;
+
+use crate::{
+	btc::retry_rpc::BtcRetryRpcClient,
+	db::PersistentKeyDB,
+	dot::retry_rpc::DotRetryRpcClient,
+	eth::retry_rpc::EthersRetryRpcClient,
+	settings::{self, DotWitnessingMode, EthWitnessingMode},
+	state_chain_observer::client::{
+		chain_api::ChainApi, storage_api::StorageApi, StateChainStreamApi,
+	},
+	witness::{self, common::epoch_source::EpochSource},
+};
+
+// The number of the latest witnesses that are kept, so subscribers can catch up on the witnesses
+// they missed. Older witnesses are dropped, so a slow subscriber can't make the observer run out of
+// memory.
+const WITNESS_HISTORY_SIZE: usize = 16 * 1024;
+
+/// A witness that a validator would have submitted.
+#[derive(Clone, Debug, Serialize)]
+pub struct ObservedWitness {
+	/// Numbers the witnesses in the order they were observed, starting from 0 each time the
+	/// observer starts.
+	pub sequence: u64,
+	pub epoch_index: EpochIndex,
+	/// Whether the call would have been prewitnessed rather than witnessed.
+	pub prewitness: bool,
+	/// The hex encoded SCALE encoding of the `RuntimeCall`.
+	pub call: String,
+}
+
+/// The latest witnesses, which subscribers read from in order, each at their own pace.
+struct WitnessLog {
+	capacity: usize,
+	history: Mutex<VecDeque<ObservedWitness>>,
+	// The sequence number of the next witness.
+	next_sequence: watch::Sender<u64>,
+}
+
+impl WitnessLog {
+	fn new(capacity: usize) -> Self {
+		Self {
+			capacity,
+			history: Mutex::new(VecDeque::with_capacity(capacity)),
+			next_sequence: watch::channel(0).0,
+		}
+	}
+
+	fn push(&self, epoch_index: EpochIndex, prewitness: bool, call: String) {
+		let mut history = self.history.lock().unwrap();
+		let sequence = *self.next_sequence.borrow();
+		if history.len() == self.capacity {
+			history.pop_front();
+		}
+		history.push_back(ObservedWitness { sequence, epoch_index, prewitness, call });
+		self.next_sequence.send_replace(sequence + 1);
+	}
+
+	/// Subscribes to the witnesses from `from_sequence` on, if they are still in the history, or
+	/// otherwise to the witnesses observed from now on.
+	fn subscribe(self: &Arc<Self>, from_sequence: Option<u64>) -> WitnessSubscription {
+		let receiver = self.next_sequence.subscribe();
+		WitnessSubscription {
+			next_sequence: from_sequence.unwrap_or_else(|| *receiver.borrow()),
+			log: self.clone(),
+			receiver,
+		}
+	}
+}
+
+struct WitnessSubscription {
+	log: Arc<WitnessLog>,
+	next_sequence: u64,
+	receiver: watch::Receiver<u64>,
+}
+
+impl WitnessSubscription {
+	/// Waits for the next witness. If the witnesses the subscriber is up to have already been
+	/// dropped from the history, returns how many were missed instead, and continues from the
+	/// oldest witness in the history.
+	async fn next(&mut self) -> Result<ObservedWitness, u64> {
+		loop {
+			{
+				let history = self.log.history.lock().unwrap();
+				if let Some(oldest) = history.front() {
+					if self.next_sequence < oldest.sequence {
+						let missed = oldest.sequence - self.next_sequence;
+						self.next_sequence = oldest.sequence;
+						return Err(missed)
+					}
+					if let Some(witness) =
+						history.get((self.next_sequence - oldest.sequence) as usize)
+					{
+						self.next_sequence += 1;
+						return Ok(witness.clone())
+					}
+				}
+			}
+			let next_sequence = self.next_sequence;
+			self.receiver
+				.wait_for(|sequence| *sequence > next_sequence)
+				.await
+				.expect("The sender is owned by the log, which the subscription holds");
+		}
+	}
+}
+
+#[tracing::instrument(name = "observer", skip_all)]
+pub async fn start<StateChainClient>(
+	scope: &Scope<'_, anyhow::Error>,
+	observer_settings: &settings::Observer,
+	eth_client: EthersRetryRpcClient,
+	btc_client: BtcRetryRpcClient,
+	dot_client: DotRetryRpcClient,
+	arb_client: Option<EthersRetryRpcClient>,
+	eth_witnessing_mode: EthWitnessingMode,
+	dot_witnessing_mode: DotWitnessingMode,
+	state_chain_client: Arc<StateChainClient>,
+	state_chain_stream: impl StateChainStreamApi + Clone,
+	unfinalised_state_chain_stream: impl StateChainStreamApi<false> + Clone,
+	db: Arc<PersistentKeyDB>,
+) -> anyhow::Result<()>
+where
+	StateChainClient: StorageApi + ChainApi + 'static + Send + Sync,
+{
+	info!("Starting");
+
+	let witness_log = Arc::new(WitnessLog::new(WITNESS_HISTORY_SIZE));
+
+	let observe = |prewitness: bool| {
+		let witness_log = witness_log.clone();
+		move |call: state_chain_runtime::RuntimeCall, epoch_index| {
+			let witness_log = witness_log.clone();
+			async move {
+				debug!("Observed {call:?} at epoch {epoch_index}");
+				witness_log.push(
+					epoch_index,
+					prewitness,
+					format!("0x{}", hex::encode(call.encode())),
+				);
+			}
+		}
+	};
+
+	// Not participating in any epoch, we witness the vaults of all of them.
+	let epoch_source =
+		EpochSource::builder(scope, state_chain_stream.clone(), state_chain_client.clone()).await;
+
+	witness::start::start_witnessing(
+		scope,
+		eth_client,
+		btc_client,
+		dot_client,
+		arb_client,
+		eth_witnessing_mode,
+		dot_witnessing_mode,
+		observe(false),
+		observe(true),
+		state_chain_client,
+		state_chain_stream,
+		unfinalised_state_chain_stream,
+		epoch_source,
+		db,
+	)
+	.await?;
+
+	let mut module = RpcModule::new(());
+	module.register_subscription(
+		"subscribe_witnessing",
+		"s_witnessing",
+		"unsubscribe_witnessing",
+		move |params, mut sink, _context| {
+			// Subscribers can pass the sequence number to start from, to catch up on the witnesses
+			// they missed, e.g. while reconnecting.
+			let from_sequence = match params.sequence().optional_next::<u64>() {
+				Ok(from_sequence) => from_sequence,
+				Err(e) => {
+					let error: jsonrpsee::core::Error = e.into();
+					let _result = sink.reject(error);
+					return Ok(())
+				},
+			};
+			let mut subscription = witness_log.subscribe(from_sequence);
+
+			tokio::spawn(async move {
+				loop {
+					match subscription.next().await {
+						Ok(witness) =>
+							if let Ok(false) = sink.send(&witness) {
+								debug!("Subscription is closed");
+								break
+							},
+						Err(missed) => {
+							warn!("Subscriber is too slow, missed {missed} witnesses");
+						},
+					}
+				}
+			});
+			Ok(())
+		},
+	)?;
+
+	let server = ServerBuilder::default().build(observer_settings.listen_address).await?;
+	info!("Listening on http://{}", server.local_addr()?);
+	let server_handle = server.start(module)?;
+
+	scope.spawn(async move {
+		server_handle.stopped().await;
+		// The observer has no other purpose, so the engine stops with it.
+		Err(anyhow::anyhow!("Observer RPC server stopped"))
+	});
+
+	Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+	use std::time::Duration;
+
+	use super::*;
+
+	fn push_witnesses(log: &WitnessLog, count: u32) {
+		for epoch_index in 0..count {
+			log.push(epoch_index, false, "0x".to_string());
+		}
+	}
+
+	async fn next_sequence(subscription: &mut WitnessSubscription) -> Result<u64, u64> {
+		tokio::time::timeout(Duration::from_secs(1), subscription.next())
+			.await
+			.expect("A witness should be available")
+			.map(|witness| witness.sequence)
+	}
+
+	#[tokio::test]
+	async fn subscribers_catch_up_from_the_given_sequence() {
+		let log = Arc::new(WitnessLog::new(10));
+		push_witnesses(&log, 3);
+
+		let mut subscription = log.subscribe(Some(1));
+		assert_eq!(next_sequence(&mut subscription).await, Ok(1));
+		assert_eq!(next_sequence(&mut subscription).await, Ok(2));
+
+		push_witnesses(&log, 1);
+		assert_eq!(next_sequence(&mut subscription).await, Ok(3));
+	}
+
+	#[tokio::test]
+	async fn subscribers_only_receive_new_witnesses_by_default() {
+		let log = Arc::new(WitnessLog::new(10));
+		push_witnesses(&log, 3);
+
+		let mut subscription = log.subscribe(None);
+		assert!(tokio::time::timeout(Duration::from_millis(100), subscription.next())
+			.await
+			.is_err());
+
+		push_witnesses(&log, 1);
+		assert_eq!(next_sequence(&mut subscription).await, Ok(3));
+	}
+
+	#[tokio::test]
+	async fn subscribers_are_told_how_many_witnesses_they_missed() {
+		let log = Arc::new(WitnessLog::new(10));
+		let mut subscription = log.subscribe(None);
+
+		push_witnesses(&log, 12);
+
+		assert_eq!(next_sequence(&mut subscription).await, Err(2));
+		assert_eq!(next_sequence(&mut subscription).await, Ok(2));
+	}
+}
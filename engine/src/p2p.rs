@@ -5,6 +5,7 @@ mod peer_info_submitter;
 use std::{
 	marker::PhantomData,
 	net::{IpAddr, Ipv4Addr},
+	path::Path,
 	sync::Arc,
 };
 
@@ -28,7 +29,7 @@ use cf_primitives::AccountId;
 use futures::{Future, FutureExt};
 use multisig::p2p::OutgoingMultisigStageMessages;
 use muxer::P2PMuxer;
-use sp_core::{ed25519, H256};
+use sp_core::{ed25519, Pair, H256};
 use tokio::sync::{
 	mpsc::{UnboundedReceiver, UnboundedSender},
 	oneshot,
@@ -87,6 +88,22 @@ fn pk_to_string(pk: &XPublicKey) -> String {
 	hex::encode(pk.as_bytes())
 }
 
+fn read_node_key(node_key_file: &Path) -> anyhow::Result<ed25519_dalek::SecretKey> {
+	read_clean_and_decode_hex_str_file(node_key_file, "Node Key", |str| {
+		ed25519_dalek::SecretKey::from_bytes(
+			&Zeroizing::new(hex::decode(str).map_err(anyhow::Error::msg)?)[..],
+		)
+		.map_err(anyhow::Error::msg)
+	})
+	.context("Failed to build key from file.")
+}
+
+/// Reads the node key as the peer key that is registered on the state chain, which the multisig
+/// clients use to sign the values they broadcast
+pub fn read_peer_key(node_key_file: &Path) -> anyhow::Result<ed25519::Pair> {
+	Ok(ed25519::Pair::from_seed(&read_node_key(node_key_file)?.to_bytes()))
+}
+
 pub async fn start<StateChainClient>(
 	state_chain_client: Arc<StateChainClient>,
 	settings: P2PSettings,
@@ -98,6 +115,8 @@ pub async fn start<StateChainClient>(
 	MultisigMessageReceiver<PolkadotCrypto>,
 	MultisigMessageSender<BitcoinCrypto>,
 	MultisigMessageReceiver<BitcoinCrypto>,
+	MultisigMessageSender<EvmCrypto>,
+	MultisigMessageReceiver<EvmCrypto>,
 	UnboundedSender<PeerUpdate>,
 	oneshot::Receiver<()>,
 	impl Future<Output = anyhow::Result<()>>,
@@ -113,18 +132,7 @@ where
 		anyhow::bail!("Provided IP address is not globally routable");
 	}
 
-	let node_key = {
-		let ed_secret_key =
-			read_clean_and_decode_hex_str_file(&settings.node_key_file, "Node Key", |str| {
-				ed25519_dalek::SecretKey::from_bytes(
-					&Zeroizing::new(hex::decode(str).map_err(anyhow::Error::msg)?)[..],
-				)
-				.map_err(anyhow::Error::msg)
-			})
-			.context("Failed to build key from file.")?;
-
-		P2PKey::new(ed_secret_key)
-	};
+	let node_key = P2PKey::new(read_node_key(&settings.node_key_file)?);
 
 	let current_peers =
 		peer_info_submitter::get_current_peer_infos(&state_chain_client, initial_block_hash)
@@ -151,6 +159,8 @@ where
 		dot_incoming_receiver,
 		btc_outgoing_sender,
 		btc_incoming_receiver,
+		arb_outgoing_sender,
+		arb_incoming_receiver,
 		muxer_future,
 	) = P2PMuxer::start(incoming_message_receiver, outgoing_message_sender);
 
@@ -172,15 +182,15 @@ where
 				core::start(
 					node_key,
 					settings.port,
+					settings.transport,
+					settings.enable_relay,
 					current_peers,
 					our_account_id,
 					incoming_message_sender,
 					outgoing_message_receiver,
 					peer_update_receiver,
 				)
-				.await;
-
-				Ok(())
+				.await
 			});
 
 			scope.spawn(async move {
@@ -200,6 +210,8 @@ where
 		dot_incoming_receiver,
 		btc_outgoing_sender,
 		btc_incoming_receiver,
+		arb_outgoing_sender,
+		arb_incoming_receiver,
 		peer_update_sender,
 		p2p_ready_receiver,
 		fut,
@@ -1,18 +1,18 @@
 mod auth;
 mod monitor;
+mod relay;
 mod socket;
 #[cfg(test)]
 mod tests;
+mod transport;
 
 use std::{
 	cell::Cell,
 	collections::{BTreeMap, HashMap},
-	net::Ipv6Addr,
-	sync::Arc,
+	net::{IpAddr, Ipv6Addr, SocketAddr},
 	time::Duration,
 };
 
-use auth::Authenticator;
 use serde::{Deserialize, Serialize};
 use state_chain_runtime::AccountId;
 use tokio::sync::mpsc::{UnboundedReceiver, UnboundedSender};
@@ -20,16 +20,27 @@ use tracing::{debug, error, info, info_span, trace, warn, Instrument};
 use utilities::{
 	make_periodic_tick,
 	metrics::{
-		P2P_ACTIVE_CONNECTIONS, P2P_BAD_MSG, P2P_MSG_RECEIVED, P2P_MSG_SENT, P2P_RECONNECT_PEERS,
+		P2P_ACTIVE_CONNECTIONS, P2P_BAD_MSG, P2P_MONITOR_EVENT, P2P_MSG_SENT, P2P_RECONNECT_PEERS,
 	},
 	Port,
 };
 use x25519_dalek::StaticSecret;
 
-use crate::p2p::{pk_to_string, OutgoingMultisigStageMessages};
-use monitor::MonitorEvent;
+use crate::{
+	health,
+	p2p::{pk_to_string, OutgoingMultisigStageMessages},
+	settings::P2PTransport,
+};
 
-use socket::{ConnectedOutgoingSocket, OutgoingSocket, RECONNECT_INTERVAL, RECONNECT_INTERVAL_MAX};
+use relay::{
+	is_relayed_message, RejectedMessage, Relay, RelayedMessage, RELAY_GRACE_PERIOD,
+	RELAY_REDUNDANCY,
+};
+use socket::{RECONNECT_INTERVAL, RECONNECT_INTERVAL_MAX};
+use transport::{
+	noise::NoiseTransport, zmq_curve::ZmqTransport, AcceptingBoth, ConnectionEvent,
+	OutgoingConnection, Transport, TransportEvents, NOISE_PORT_OFFSET,
+};
 
 use super::{EdPublicKey, P2PKey, XPublicKey};
 
@@ -81,6 +92,21 @@ impl PeerInfo {
 	pub fn zmq_endpoint(&self) -> String {
 		format!("tcp://[{}]:{}", self.ip, self.port)
 	}
+
+	pub fn socket_address(&self) -> SocketAddr {
+		let ip = match self.ip.to_ipv4_mapped() {
+			Some(ipv4) => IpAddr::V4(ipv4),
+			None => IpAddr::V6(self.ip),
+		};
+		SocketAddr::new(ip, self.port)
+	}
+
+	/// Where the peer accepts connections over the Noise transport
+	pub fn noise_socket_address(&self) -> SocketAddr {
+		let mut address = self.socket_address();
+		address.set_port(self.port.wrapping_add(NOISE_PORT_OFFSET));
+		address
+	}
 }
 
 impl std::fmt::Display for PeerInfo {
@@ -175,13 +201,13 @@ impl ReconnectContext {
 	}
 }
 
-enum ConnectionState {
-	// There is a ZMQ socket for this peer (which might or might
-	// not be connected, but reconnection is handled by ZMQ).
-	Connected(ConnectedOutgoingSocket),
-	// There is no ZMQ socket for this peer (because we don't
-	// want ZMQ's default behavior yet), but we have arranged
-	// for a ZMQ socket to be created again in the future.
+enum ConnectionState<C> {
+	// There is a connection for this peer (which might or might
+	// not be established, but reconnection is handled by the transport).
+	Connected(C),
+	// There is no connection for this peer (because the peer has
+	// declined it), but we have arranged for a connection to be
+	// created again in the future.
 	ReconnectionScheduled,
 	// There hasn't been recent interaction with the node, so we
 	// don't maintain an active connection with it. We will connect
@@ -189,100 +215,166 @@ enum ConnectionState {
 	Stale,
 }
 
-struct ConnectionStateInfo {
-	state: ConnectionState,
+struct ConnectionStateInfo<C> {
+	state: ConnectionState<C>,
 	// Last time we received an instruction to send a message
 	// to this node
 	last_activity: Cell<tokio::time::Instant>,
+	// Whether the connection is currently established, and when we
+	// started (re)connecting to this node if it isn't
+	connection_established: bool,
+	disconnected_since: tokio::time::Instant,
 	info: PeerInfo,
 }
 
-struct ActiveConnectionWrapper {
+impl<C> ConnectionStateInfo<C> {
+	/// Whether we should give up on sending messages directly, and relay them instead
+	fn is_unreachable(&self) -> bool {
+		match self.state {
+			ConnectionState::Connected(_) =>
+				!self.connection_established &&
+					self.disconnected_since.elapsed() > RELAY_GRACE_PERIOD,
+			ConnectionState::ReconnectionScheduled => true,
+			// We will connect (and thus find out if the peer is reachable) when needed
+			ConnectionState::Stale => false,
+		}
+	}
+}
+
+struct ActiveConnectionWrapper<C> {
 	metric: &'static P2P_ACTIVE_CONNECTIONS,
-	map: BTreeMap<AccountId, ConnectionStateInfo>,
+	map: BTreeMap<AccountId, ConnectionStateInfo<C>>,
 }
 
-impl ActiveConnectionWrapper {
-	fn new() -> ActiveConnectionWrapper {
+impl<C> ActiveConnectionWrapper<C> {
+	fn new() -> ActiveConnectionWrapper<C> {
 		ActiveConnectionWrapper { metric: &P2P_ACTIVE_CONNECTIONS, map: Default::default() }
 	}
-	fn get(&self, account_id: &AccountId) -> Option<&ConnectionStateInfo> {
+	fn get(&self, account_id: &AccountId) -> Option<&ConnectionStateInfo<C>> {
 		self.map.get(account_id)
 	}
-	fn get_mut(&mut self, account_id: &AccountId) -> Option<&mut ConnectionStateInfo> {
+	fn get_mut(&mut self, account_id: &AccountId) -> Option<&mut ConnectionStateInfo<C>> {
 		self.map.get_mut(account_id)
 	}
 	fn insert(
 		&mut self,
 		key: AccountId,
-		value: ConnectionStateInfo,
-	) -> Option<ConnectionStateInfo> {
+		value: ConnectionStateInfo<C>,
+	) -> Option<ConnectionStateInfo<C>> {
 		let result = self.map.insert(key, value);
 		self.metric.set(self.map.len());
+		self.report_connected_peers();
 		result
 	}
-	fn remove(&mut self, key: &AccountId) -> Option<ConnectionStateInfo> {
+	fn remove(&mut self, key: &AccountId) -> Option<ConnectionStateInfo<C>> {
 		let result = self.map.remove(key);
 		self.metric.set(self.map.len());
+		self.report_connected_peers();
 		result
 	}
+	/// Reports the number of peers we have established a connection with to the health check.
+	fn report_connected_peers(&self) {
+		health::record_p2p_connected_peers(
+			self.map
+				.values()
+				.filter(|peer| {
+					matches!(peer.state, ConnectionState::Connected(_)) &&
+						peer.connection_established
+				})
+				.count(),
+		);
+	}
 }
 
 /// The state a nodes needs for p2p
-struct P2PContext {
-	/// Our own key, used for initiating and accepting secure connections
-	key: X25519KeyPair,
-	/// A handle to the authenticator thread that can be used to make changes to the
-	/// list of allowed peers
-	authenticator: Arc<Authenticator>,
+struct P2PContext<T: Transport> {
+	/// Used for connecting to peers, and for controlling which peers can connect to us
+	transport: T,
 	/// Contain entries for all nodes that we *should* be connected to (i.e. all registered
 	/// nodes), which are either connected or scheduled for reconnection
-	active_connections: ActiveConnectionWrapper,
+	active_connections: ActiveConnectionWrapper<T::Connection>,
 	/// NOTE: this is used for incoming messages when we want to map them to account_id
 	/// NOTE: we don't use BTreeMap here because XPublicKey doesn't implement Ord.
 	x25519_to_account_id: HashMap<XPublicKey, AccountId>,
 	/// Channel through which we send incoming messages to the multisig
 	incoming_message_sender: UnboundedSender<(AccountId, Vec<u8>)>,
 	reconnect_context: ReconnectContext,
-	/// This is how we communicate with the "monitor" thread
-	monitor_handle: monitor::MonitorHandle,
+	/// Only set if relaying of messages to unreachable peers is enabled
+	relay: Option<Relay>,
 	our_account_id: AccountId,
-	/// NOTE: zmq context is intentionally declared at the bottom of the struct
-	/// to ensure its destructor is called after that of any zmq sockets
-	zmq_context: zmq::Context,
 }
 
+#[allow(clippy::too_many_arguments)]
 pub(super) async fn start(
 	p2p_key: P2PKey,
 	port: Port,
+	transport: P2PTransport,
+	enable_relay: bool,
 	current_peers: Vec<PeerInfo>,
 	our_account_id: AccountId,
 	incoming_message_sender: UnboundedSender<(AccountId, Vec<u8>)>,
 	outgoing_message_receiver: UnboundedReceiver<OutgoingMultisigStageMessages>,
 	peer_update_receiver: UnboundedReceiver<PeerUpdate>,
-) {
+) -> anyhow::Result<()> {
 	debug!("Our derived x25519 pubkey: {}", pk_to_string(&p2p_key.encryption_key.public_key));
 
-	let zmq_context = zmq::Context::new();
-
-	zmq_context.set_max_sockets(65536).expect("should update socket limit");
+	let relay = enable_relay.then(|| Relay::new(p2p_key.encryption_key.clone()));
+
+	// We accept connections over both transports, and only use the configured one to connect
+	let (zmq_transport, zmq_events) = ZmqTransport::start(p2p_key.encryption_key.clone(), port);
+	let (noise_transport, noise_events) =
+		NoiseTransport::start(p2p_key.encryption_key, port.wrapping_add(NOISE_PORT_OFFSET))?;
+	let transport_events = zmq_events.merge(noise_events);
+
+	match transport {
+		P2PTransport::Zmq =>
+			run(
+				AcceptingBoth::new(zmq_transport, noise_transport),
+				transport_events,
+				relay,
+				current_peers,
+				our_account_id,
+				incoming_message_sender,
+				outgoing_message_receiver,
+				peer_update_receiver,
+			)
+			.await,
+		P2PTransport::Noise =>
+			run(
+				AcceptingBoth::new(noise_transport, zmq_transport),
+				transport_events,
+				relay,
+				current_peers,
+				our_account_id,
+				incoming_message_sender,
+				outgoing_message_receiver,
+				peer_update_receiver,
+			)
+			.await,
+	}
 
-	let authenticator = auth::start_authentication_thread(zmq_context.clone());
+	Ok(())
+}
 
+async fn run<T: Transport>(
+	transport: T,
+	transport_events: TransportEvents,
+	relay: Option<Relay>,
+	current_peers: Vec<PeerInfo>,
+	our_account_id: AccountId,
+	incoming_message_sender: UnboundedSender<(AccountId, Vec<u8>)>,
+	outgoing_message_receiver: UnboundedReceiver<OutgoingMultisigStageMessages>,
+	peer_update_receiver: UnboundedReceiver<PeerUpdate>,
+) {
 	let (reconnect_sender, reconnect_receiver) = tokio::sync::mpsc::unbounded_channel();
 
-	let (monitor_handle, monitor_event_receiver) =
-		monitor::start_monitoring_thread(zmq_context.clone());
-
 	let mut context = P2PContext {
-		zmq_context,
-		key: p2p_key.encryption_key,
-		monitor_handle,
-		authenticator,
+		transport,
 		active_connections: ActiveConnectionWrapper::new(),
 		x25519_to_account_id: Default::default(),
 		reconnect_context: ReconnectContext::new(reconnect_sender),
 		incoming_message_sender,
+		relay,
 		our_account_id,
 	};
 
@@ -291,31 +383,29 @@ pub(super) async fn start(
 		context.add_or_update_peer(peer_info);
 	}
 
-	let incoming_message_receiver_ed25519 = context.start_listening_thread(port);
-
 	context
 		.control_loop(
 			outgoing_message_receiver,
-			incoming_message_receiver_ed25519,
+			transport_events.incoming_message_receiver,
 			peer_update_receiver,
-			monitor_event_receiver,
+			transport_events.connection_event_receiver,
 			reconnect_receiver,
 		)
 		.instrument(info_span!("p2p"))
 		.await;
 }
 
-fn disconnect_socket(_socket: ConnectedOutgoingSocket) {
-	// Simply dropping the socket is enough
+fn disconnect<C: OutgoingConnection>(_connection: C) {
+	// Simply dropping the connection is enough
 }
 
-impl P2PContext {
+impl<T: Transport> P2PContext<T> {
 	async fn control_loop(
 		mut self,
 		mut outgoing_message_receiver: UnboundedReceiver<OutgoingMultisigStageMessages>,
 		mut incoming_message_receiver: UnboundedReceiver<(XPublicKey, Vec<u8>)>,
 		mut peer_update_receiver: UnboundedReceiver<PeerUpdate>,
-		mut monitor_event_receiver: UnboundedReceiver<MonitorEvent>,
+		mut connection_event_receiver: UnboundedReceiver<ConnectionEvent>,
 		mut reconnect_receiver: UnboundedReceiver<AccountId>,
 	) {
 		let mut check_activity_interval = make_periodic_tick(ACTIVITY_CHECK_INTERVAL, false);
@@ -333,8 +423,8 @@ impl P2PContext {
 					// the x25519 pubkey to their account id here
 					self.forward_incoming_message(pubkey, payload);
 				}
-				Some(event) = monitor_event_receiver.recv() => {
-					self.handle_monitor_event(event);
+				Some(event) = connection_event_receiver.recv() => {
+					self.handle_connection_event(event);
 				}
 				Some(account_id) = reconnect_receiver.recv() => {
 					self.reconnect_to_peer(&account_id);
@@ -368,15 +458,19 @@ impl P2PContext {
 			peer.last_activity.set(tokio::time::Instant::now());
 
 			match &peer.state {
-				ConnectionState::Connected(socket) => {
-					socket.send(payload);
-					P2P_MSG_SENT.inc();
+				ConnectionState::Connected(connection) => {
+					if !(peer.is_unreachable() && self.send_relayed_message(peer, &payload)) {
+						connection.send(payload);
+						P2P_MSG_SENT.inc();
+					}
 				},
 				ConnectionState::ReconnectionScheduled => {
-					// TODO: buffer the messages and send them later?
-					warn!(
-						"Failed to send message. Peer is scheduled for reconnection: {account_id}"
-					);
+					if !self.send_relayed_message(peer, &payload) {
+						// TODO: buffer the messages and send them later?
+						warn!(
+							"Failed to send message. Peer is scheduled for reconnection: {account_id}"
+						);
+					}
 				},
 				ConnectionState::Stale => {
 					// Connect and try again (there is no infinite loop here
@@ -394,6 +488,141 @@ impl P2PContext {
 		}
 	}
 
+	/// Sends the message to the peer via other peers that we are connected to.
+	/// Returns false if relaying is disabled or there are no suitable relays.
+	fn send_relayed_message(
+		&self,
+		peer: &ConnectionStateInfo<T::Connection>,
+		payload: &[u8],
+	) -> bool {
+		use rand::seq::IteratorRandom;
+
+		let Some(relay) = &self.relay else { return false };
+
+		let relays = self
+			.active_connections
+			.map
+			.iter()
+			.filter_map(|(account_id, relay_peer)| match &relay_peer.state {
+				ConnectionState::Connected(connection)
+					if relay_peer.connection_established && *account_id != peer.info.account_id =>
+					Some((account_id, connection)),
+				_ => None,
+			})
+			.choose_multiple(&mut rand::thread_rng(), RELAY_REDUNDANCY);
+
+		if relays.is_empty() {
+			return false
+		}
+
+		// The same message is sent through every relay, so that the recipient can ignore duplicates
+		let message = RelayedMessage::seal(&relay.key, &peer.info.pubkey, payload).serialize();
+		for (relay_account_id, connection) in relays {
+			trace!("Relaying a message to {} via {relay_account_id}", peer.info.account_id);
+			connection.send(message.clone());
+			P2P_MSG_SENT.inc();
+			P2P_MONITOR_EVENT.inc(&["relay_sent"]);
+		}
+
+		true
+	}
+
+	/// Handles a relayed message that is either addressed to us, or that we should forward
+	fn handle_relayed_message(&mut self, from: AccountId, from_pubkey: XPublicKey, data: Vec<u8>) {
+		let Some(relay) = &mut self.relay else {
+			P2P_BAD_MSG.inc(&["relay_disabled"]);
+			warn!("Ignoring a relayed message from {from}: relaying is disabled");
+			return
+		};
+
+		let message = match RelayedMessage::deserialize(&data) {
+			Ok(message) => message,
+			Err(e) => {
+				P2P_BAD_MSG.inc(&["relay_deserialization"]);
+				warn!("Ignoring a relayed message from {from}: {e}");
+				return
+			},
+		};
+
+		if message.recipient == *relay.key.public_key.as_bytes() {
+			let Some(sender) = self.x25519_to_account_id.get(&XPublicKey::from(message.sender))
+			else {
+				P2P_BAD_MSG.inc(&["relay_unknown_sender"]);
+				warn!(
+					"Ignoring a message relayed by {from} from an unknown x25519 key: {}",
+					hex::encode(message.sender)
+				);
+				return
+			};
+
+			// The message is authenticated before it is recorded, so that relays
+			// can't prevent messages from being received by sending fake ones first
+			let payload = match message.open(&relay.key) {
+				Ok(payload) => payload,
+				Err(e) => {
+					P2P_BAD_MSG.inc(&["relay_decryption"]);
+					warn!("Ignoring a message from {sender} relayed by {from}: {e}");
+					return
+				},
+			};
+
+			match relay.record_message(&message) {
+				Ok(()) => {
+					trace!("Received a message from {sender} relayed by {from}");
+					P2P_MONITOR_EVENT.inc(&["relay_received"]);
+					self.incoming_message_sender.send((sender.clone(), payload)).unwrap();
+				},
+				Err(RejectedMessage::Duplicate) => {
+					trace!("Ignoring a duplicate message from {sender} relayed by {from}");
+				},
+				Err(RejectedMessage::Expired) => {
+					P2P_BAD_MSG.inc(&["relay_expired"]);
+					warn!(
+						"Ignoring an expired message from {sender} relayed by {from} (sent at {})",
+						message.timestamp
+					);
+				},
+				Err(RejectedMessage::TooManyMessages) => {
+					P2P_BAD_MSG.inc(&["relay_too_many_messages"]);
+					warn!("Ignoring a message from {sender} relayed by {from}: too many messages");
+				},
+			}
+		} else {
+			// Only forwarding messages that come from their original sender ensures
+			// that messages are never relayed more than once
+			if message.sender != *from_pubkey.as_bytes() {
+				P2P_BAD_MSG.inc(&["relay_sender_mismatch"]);
+				warn!("Ignoring a message from {from} that was relayed by another peer");
+				return
+			}
+
+			let recipient = self
+				.x25519_to_account_id
+				.get(&XPublicKey::from(message.recipient))
+				.and_then(|recipient| self.active_connections.get(recipient));
+
+			match recipient {
+				Some(ConnectionStateInfo {
+					state: ConnectionState::Connected(connection),
+					info,
+					..
+				}) => {
+					trace!("Forwarding a message from {from} to {}", info.account_id);
+					connection.send(data);
+					P2P_MSG_SENT.inc();
+					P2P_MONITOR_EVENT.inc(&["relay_forwarded"]);
+				},
+				_ => {
+					P2P_MONITOR_EVENT.inc(&["relay_dropped"]);
+					debug!(
+						"Can't forward a message from {from} to {}: not connected to the recipient",
+						hex::encode(message.recipient)
+					);
+				},
+			}
+		}
+	}
+
 	fn on_peer_update(&mut self, update: PeerUpdate) {
 		match update {
 			PeerUpdate::Registered(peer_info) => self.add_or_update_peer(peer_info),
@@ -404,8 +633,12 @@ impl P2PContext {
 
 	fn forward_incoming_message(&mut self, pubkey: XPublicKey, payload: Vec<u8>) {
 		if let Some(acc_id) = self.x25519_to_account_id.get(&pubkey) {
-			trace!("Received a message from {acc_id}");
-			self.incoming_message_sender.send((acc_id.clone(), payload)).unwrap();
+			if is_relayed_message(&payload) {
+				self.handle_relayed_message(acc_id.clone(), pubkey, payload);
+			} else {
+				trace!("Received a message from {acc_id}");
+				self.incoming_message_sender.send((acc_id.clone(), payload)).unwrap();
+			}
 		} else {
 			P2P_BAD_MSG.inc(&["unknown_x25519_key"]);
 			warn!("Received a message for an unknown x25519 key: {}", pk_to_string(&pubkey));
@@ -413,7 +646,7 @@ impl P2PContext {
 	}
 
 	fn clean_up_for_peer_pubkey(&mut self, pubkey: &XPublicKey) {
-		self.authenticator.remove_peer(pubkey);
+		self.transport.disallow_peer(pubkey);
 		if self.x25519_to_account_id.remove(pubkey).is_none() {
 			error!("Invariant violation: pubkey must be present");
 			debug_assert!(false, "Invariant violation: pubkey must be present");
@@ -421,14 +654,9 @@ impl P2PContext {
 	}
 
 	/// Removing a peer means: (1) removing it from the list of allowed nodes,
-	/// (2) disconnecting our "client" socket with that node, (3) removing
+	/// (2) disconnecting our "client" connection with that node, (3) removing
 	/// any references to it in local state (mappings)
 	fn handle_peer_deregistration(&mut self, account_id: AccountId) {
-		// NOTE: There is no (trivial) way to disconnect peers that are
-		// already connected to our listening ZMQ socket, we can only
-		// prevent future connections from being established and rely
-		// on peer from disconnecting from "client side".
-
 		if account_id == self.our_account_id {
 			warn!("Received peer info deregistration of our own node!");
 			return
@@ -436,8 +664,8 @@ impl P2PContext {
 
 		if let Some(peer) = self.active_connections.remove(&account_id) {
 			match peer.state {
-				ConnectionState::Connected(existing_socket) => {
-					disconnect_socket(existing_socket);
+				ConnectionState::Connected(existing_connection) => {
+					disconnect(existing_connection);
 				},
 				ConnectionState::ReconnectionScheduled => {
 					self.reconnect_context.reset(&account_id);
@@ -458,9 +686,9 @@ impl P2PContext {
 	}
 
 	/// Reconnect to peer assuming that its peer info hasn't changed
-	fn handle_monitor_event(&mut self, event: MonitorEvent) {
+	fn handle_connection_event(&mut self, event: ConnectionEvent) {
 		match event {
-			MonitorEvent::ConnectionFailure(account_id) => {
+			ConnectionEvent::ConnectionFailure(account_id) => {
 				self.reconnect_context.schedule_reconnect(account_id.clone());
 				if let Some(peer) = self.active_connections.get_mut(&account_id) {
 					peer.state = ConnectionState::ReconnectionScheduled;
@@ -468,10 +696,22 @@ impl P2PContext {
 					error!("Unexpected attempt to reconnect to an unknown peer: {account_id}");
 				}
 			},
-			MonitorEvent::ConnectionSuccess(account_id) => {
+			ConnectionEvent::ConnectionSuccess(account_id) => {
 				self.reconnect_context.reset(&account_id);
+				if let Some(peer) = self.active_connections.get_mut(&account_id) {
+					peer.connection_established = true;
+				}
+			},
+			ConnectionEvent::Disconnected(account_id) => {
+				if let Some(peer) = self.active_connections.get_mut(&account_id) {
+					if peer.connection_established {
+						peer.connection_established = false;
+						peer.disconnected_since = tokio::time::Instant::now();
+					}
+				}
 			},
 		};
+		self.active_connections.report_connected_peers();
 	}
 
 	fn reconnect_to_peer(&mut self, account_id: &AccountId) {
@@ -485,12 +725,12 @@ impl P2PContext {
 					// It is possible that while we were waiting to reconnect,
 					// we received a peer info update and created a new "connection".
 					// It is safe to drop the reconnection attempt even if this
-					// ZMQ connection is not "healthy" since reconnecting
-					// is now in ZMQ's hands, and it shouldn't be possible that we
+					// connection is not "healthy" since reconnecting is now in the
+					// transport's hands, and it shouldn't be possible that we
 					// have missed any new `ConnectionFailure` event since we wouldn't
 					// be in `Connected` state now.
 					debug!(
-						"Reconnection attempt to {} cancelled: ZMQ socket already exists.",
+						"Reconnection attempt to {} cancelled: connection already exists.",
 						account_id
 					);
 				},
@@ -509,18 +749,16 @@ impl P2PContext {
 	fn connect_to_peer(&mut self, peer: PeerInfo) {
 		let account_id = peer.account_id.clone();
 
-		let socket = OutgoingSocket::new(&self.zmq_context, &self.key);
-
-		self.monitor_handle.start_monitoring_for(&socket, &peer);
-
-		let connected_socket = socket.connect(peer.clone());
+		let connection = self.transport.connect(peer.clone());
 
 		if let Some(connection) = self.active_connections.insert(
 			account_id.clone(),
 			ConnectionStateInfo {
-				state: ConnectionState::Connected(connected_socket),
+				state: ConnectionState::Connected(connection),
 				info: peer,
 				last_activity: Cell::new(tokio::time::Instant::now()),
+				connection_established: false,
+				disconnected_since: tokio::time::Instant::now(),
 			},
 		) {
 			if !matches!(connection.state, ConnectionState::Stale) {
@@ -547,8 +785,8 @@ impl P2PContext {
 			);
 
 			match existing_peer_state.state {
-				ConnectionState::Connected(socket) => {
-					disconnect_socket(socket);
+				ConnectionState::Connected(connection) => {
+					disconnect(connection);
 				},
 				ConnectionState::ReconnectionScheduled => {
 					self.reconnect_context.reset(&peer.account_id);
@@ -567,66 +805,13 @@ impl P2PContext {
 			);
 		}
 
-		self.authenticator.add_peer(&peer);
+		self.transport.allow_peer(&peer);
 
 		self.x25519_to_account_id.insert(peer.pubkey, peer.account_id.clone());
 
 		self.connect_to_peer(peer);
 	}
 
-	/// Start listening for incoming p2p messages on a separate thread
-	fn start_listening_thread(&mut self, port: Port) -> UnboundedReceiver<(XPublicKey, Vec<u8>)> {
-		let socket = self.zmq_context.socket(zmq::SocketType::ROUTER).unwrap();
-
-		socket.set_router_mandatory(true).unwrap();
-		socket.set_router_handover(true).unwrap();
-		socket.set_curve_server(true).unwrap();
-		socket.set_curve_secretkey(&self.key.secret_key.to_bytes()).unwrap();
-		socket.set_handshake_ivl(HANDSHAKE_TIMEOUT.as_millis() as i32).unwrap();
-
-		// Listen on all interfaces
-		let endpoint = format!("tcp://0.0.0.0:{port}");
-		info!("Started listening for incoming p2p connections on: {endpoint}");
-
-		socket.bind(&endpoint).expect("invalid endpoint");
-
-		let (incoming_message_sender, incoming_message_receiver) =
-			tokio::sync::mpsc::unbounded_channel();
-
-		// This OS thread is for incoming messages
-		// TODO: combine this with the authentication thread?
-		std::thread::spawn(move || loop {
-			let mut parts = receive_multipart(&socket).unwrap();
-			P2P_MSG_RECEIVED.inc();
-			// We require that all messages exchanged between
-			// peers only consist of one part. ZMQ dealer
-			// sockets automatically prepend a sender id
-			// (which we ignore) to every message, giving
-			// us a 2 part message.
-			if parts.len() == 2 {
-				let msg = &mut parts[1];
-
-				// This value is ZMQ convention for the public
-				// key of message's origin
-				const PUBLIC_KEY_TAG: &str = "User-Id";
-				let pubkey = msg.gets(PUBLIC_KEY_TAG).expect("pubkey is always present");
-
-				let pubkey: [u8; 32] = hex::decode(pubkey).unwrap().try_into().unwrap();
-				let pubkey = XPublicKey::from(pubkey);
-
-				incoming_message_sender.send((pubkey, msg.to_vec())).unwrap();
-			} else {
-				P2P_BAD_MSG.inc(&["bad_number_of_parts"]);
-				warn!(
-					"Ignoring a multipart message with unexpected number of parts ({})",
-					parts.len()
-				)
-			}
-		});
-
-		incoming_message_receiver
-	}
-
 	fn check_activity(&mut self) {
 		for (account_id, state) in &mut self.active_connections.map {
 			if !matches!(state.state, ConnectionState::Stale) &&
@@ -634,31 +819,10 @@ impl P2PContext {
 			{
 				debug!("Peer connection is deemed stale due to inactivity: {}", account_id);
 				self.reconnect_context.reset(account_id);
-				// ZMQ socket is dropped here
+				// The connection is dropped here
 				state.state = ConnectionState::Stale;
 			}
 		}
+		self.active_connections.report_connected_peers();
 	}
 }
-
-/// Unlike recv_multipart available on zmq::Socket, this collects
-/// original message structs rather than payload bytes only
-fn receive_multipart(socket: &zmq::Socket) -> zmq::Result<Vec<zmq::Message>> {
-	// This indicates that we always want to block while
-	// waiting for new messages
-	let flags = 0;
-
-	let mut parts = vec![];
-
-	loop {
-		let mut part = zmq::Message::new();
-		socket.recv(&mut part, flags)?;
-		parts.push(part);
-
-		let more_parts = socket.get_rcvmore()?;
-		if !more_parts {
-			break
-		}
-	}
-	Ok(parts)
-}
@@ -1,7 +1,8 @@
 //! Implements ZAP (ZeroMQ Authentication Protocol) handler.
 //! For details, see https://rfc.zeromq.org/spec/27.
 //! To use, create one Authenticator instance, and call
-//! run on a separate thread.
+//! run on a separate thread. Transports not based on ZMQ
+//! use the Authenticator's list of allowed peers directly.
 
 use std::{
 	collections::HashMap,
@@ -59,7 +60,7 @@ pub struct Authenticator {
 }
 
 impl Authenticator {
-	fn new() -> Self {
+	pub(super) fn new() -> Self {
 		Authenticator { allowed_pubkeys: RwLock::new(AllowedPubkeysWrapper::new()) }
 	}
 
@@ -90,16 +91,25 @@ impl Authenticator {
 	fn process_authentication_request(&self, socket: &zmq::Socket) {
 		let req = parse_request(socket);
 
-		if let Some(account_id) = self.allowed_pubkeys.read().unwrap().get(&req.pubkey) {
-			trace!("Allowing an incoming connection for account id: {account_id}");
+		if self.is_allowed(&req.pubkey) {
 			send_auth_response(socket, &req.request_id, ZAP_AUTH_SUCCESS, &req.pubkey)
+		} else {
+			send_auth_response(socket, &req.request_id, ZAP_AUTH_FAILURE, &req.pubkey)
+		}
+	}
+
+	/// Checks whether an incoming connection from the peer with this key should be allowed
+	pub fn is_allowed(&self, pubkey: &XPublicKey) -> bool {
+		if let Some(account_id) = self.allowed_pubkeys.read().unwrap().get(pubkey) {
+			trace!("Allowing an incoming connection for account id: {account_id}");
+			true
 		} else {
 			warn!(
 				"Declining an incoming connection for an unknown pubkey: {}",
-				pk_to_string(&req.pubkey)
+				pk_to_string(pubkey)
 			);
 			P2P_DECLINED_CONNECTIONS.inc();
-			send_auth_response(socket, &req.request_id, ZAP_AUTH_FAILURE, &req.pubkey)
+			false
 		}
 	}
 
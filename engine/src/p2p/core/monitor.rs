@@ -15,7 +15,7 @@ use tracing::{info, info_span, trace, warn};
 
 use super::socket::DO_NOT_LINGER;
 
-use super::{socket::OutgoingSocket, PeerInfo};
+use super::{socket::OutgoingSocket, transport::ConnectionEvent, PeerInfo};
 
 use utilities::metrics::P2P_MONITOR_EVENT;
 /// Describes peer connection to start monitoring
@@ -38,12 +38,6 @@ pub struct MonitorHandle {
 	socket: zmq::Socket,
 }
 
-#[derive(Debug)]
-pub enum MonitorEvent {
-	ConnectionSuccess(AccountId),
-	ConnectionFailure(AccountId),
-}
-
 impl MonitorHandle {
 	pub fn start_monitoring_for(&mut self, socket_to_monitor: &OutgoingSocket, peer: &PeerInfo) {
 		use rand::RngCore;
@@ -96,7 +90,7 @@ fn stop_monitoring_for_peer(sockets_to_poll: &mut Vec<(zmq::Socket, SocketType)>
 /// by p2p control loop to receive commands to reconnect to the peer)
 pub fn start_monitoring_thread(
 	context: zmq::Context,
-) -> (MonitorHandle, UnboundedReceiver<MonitorEvent>) {
+) -> (MonitorHandle, UnboundedReceiver<ConnectionEvent>) {
 	// This essentially opens a (ZMQ) channel that the monitor thread
 	// uses to receive new peer sockets to monitor
 	const PEER_INFO_ENDPOINT: &str = "inproc://peer_info_for_monitoring";
@@ -104,7 +98,7 @@ pub fn start_monitoring_thread(
 	monitor_socket.connect(PEER_INFO_ENDPOINT).unwrap();
 
 	let (monitor_event_sender, monitor_event_receiver) =
-		tokio::sync::mpsc::unbounded_channel::<MonitorEvent>();
+		tokio::sync::mpsc::unbounded_channel::<ConnectionEvent>();
 
 	std::thread::spawn(move || {
 		let span = info_span!("p2p");
@@ -176,7 +170,7 @@ pub fn start_monitoring_thread(
 									"Socket event: handshake failed with {account_id} ({event:?})"
 								);
 								monitor_event_sender
-									.send(MonitorEvent::ConnectionFailure(account_id.clone()))
+									.send(ConnectionEvent::ConnectionFailure(account_id.clone()))
 									.unwrap();
 							},
 							zmq::SocketEvent::MONITOR_STOPPED => {
@@ -199,7 +193,7 @@ pub fn start_monitoring_thread(
 								// internal event loop, seemingly blocking all other sockets.
 								trace!("Socket event: authentication success with {account_id}");
 								monitor_event_sender
-									.send(MonitorEvent::ConnectionSuccess(account_id.clone()))
+									.send(ConnectionEvent::ConnectionSuccess(account_id.clone()))
 									.unwrap();
 							},
 							zmq::SocketEvent::CONNECT_RETRIED => {
@@ -213,6 +207,9 @@ pub fn start_monitoring_thread(
 							zmq::SocketEvent::DISCONNECTED => {
 								P2P_MONITOR_EVENT.inc(&["disconnected"]);
 								trace!("Socket event: disconnected from {account_id}");
+								monitor_event_sender
+									.send(ConnectionEvent::Disconnected(account_id.clone()))
+									.unwrap();
 							},
 							unknown_event => {
 								panic!(
@@ -0,0 +1,332 @@
+//! Relayed delivery of messages to peers that we can't reach directly (e.g.
+//! due to a partitioned link). Instead of being dropped, such messages are
+//! sent to some of the other peers that we are connected to, which forward
+//! them to the recipient. Relayed messages are encrypted and authenticated
+//! with a key derived from the sender's and recipient's x25519 keys, so
+//! relays can't read or forge them, only drop or duplicate them.
+//!
+//! Messages are only ever relayed over a single hop: a relay only forwards
+//! messages that it receives from their original sender.
+//!
+//! Each message is bound to the time it was sent, and is only accepted for
+//! [MAX_RELAYED_MESSAGE_AGE] after that, so that the recipient only needs to
+//! remember the messages it received within that window to reject replays.
+
+use std::{
+	collections::{HashSet, VecDeque},
+	time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use anyhow::{anyhow, Context, Result};
+use chacha20poly1305::{
+	aead::{Aead, AeadCore, KeyInit, OsRng, Payload},
+	XChaCha20Poly1305, XNonce,
+};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use zeroize::Zeroizing;
+
+use super::{X25519KeyPair, XPublicKey};
+
+/// Relayed messages start with this prefix. This is the highest protocol
+/// version, which is reserved for messages handled by the p2p layer itself,
+/// so that relayed messages can't be confused with multisig messages.
+const RELAYED_MESSAGE_PREFIX: [u8; 2] = [0xff, 0xff];
+
+/// How long to wait for a new connection to be established before deeming
+/// the peer unreachable and relaying messages to it
+pub const RELAY_GRACE_PERIOD: Duration = Duration::from_secs(5);
+
+/// How many peers each relayed message is sent through
+pub const RELAY_REDUNDANCY: usize = 2;
+
+/// How long after being sent a relayed message is accepted. This also allows
+/// for some clock drift between the sender and the recipient.
+pub const MAX_RELAYED_MESSAGE_AGE: Duration = Duration::from_secs(120);
+
+/// The most relayed messages to remember in order to ignore duplicates (which
+/// we get due to sending messages through more than one relay). If more messages
+/// than this are received within [MAX_RELAYED_MESSAGE_AGE], new messages are
+/// rejected rather than forgetting ones that could then be replayed.
+const RECENT_MESSAGES_CAPACITY: usize = 10_000;
+
+const KEY_DERIVATION_CONTEXT: &[u8] = b"chainflip_p2p_relay";
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct RelayedMessage {
+	pub sender: [u8; 32],
+	pub recipient: [u8; 32],
+	/// Unique to each message, so it also serves as the message id
+	pub nonce: [u8; 24],
+	/// When the message was sent, in seconds since the unix epoch. This is
+	/// authenticated along with the payload, so relays can't change it.
+	pub timestamp: u64,
+	ciphertext: Vec<u8>,
+}
+
+/// Why a relayed message was not accepted by [Relay::record_message]
+#[derive(Debug, PartialEq, Eq)]
+pub enum RejectedMessage {
+	/// We have already received this message (e.g. via another relay)
+	Duplicate,
+	/// The message was sent too long ago (or too far in the future)
+	Expired,
+	/// Too many messages have been received recently
+	TooManyMessages,
+}
+
+fn now() -> u64 {
+	SystemTime::now()
+		.duration_since(UNIX_EPOCH)
+		.expect("system time is after the unix epoch")
+		.as_secs()
+}
+
+/// The data authenticated along with the payload
+fn associated_data(sender: &[u8; 32], recipient: &[u8; 32], timestamp: u64) -> Vec<u8> {
+	[&sender[..], &recipient[..], &timestamp.to_be_bytes()[..]].concat()
+}
+
+pub fn is_relayed_message(data: &[u8]) -> bool {
+	data.starts_with(&RELAYED_MESSAGE_PREFIX)
+}
+
+impl RelayedMessage {
+	/// Encrypts the payload so that only the recipient can read it
+	pub fn seal(our_key: &X25519KeyPair, recipient: &XPublicKey, payload: &[u8]) -> Self {
+		let sender = *our_key.public_key.as_bytes();
+		let recipient = *recipient.as_bytes();
+		let key = message_key(our_key, &XPublicKey::from(recipient), &sender, &recipient);
+
+		let nonce = XChaCha20Poly1305::generate_nonce(&mut OsRng);
+		let timestamp = now();
+		let ciphertext = XChaCha20Poly1305::new((&*key).into())
+			.encrypt(
+				&nonce,
+				Payload { msg: payload, aad: &associated_data(&sender, &recipient, timestamp) },
+			)
+			.expect("Encryption is not expected to fail");
+
+		RelayedMessage { sender, recipient, nonce: nonce.into(), timestamp, ciphertext }
+	}
+
+	/// Decrypts a message addressed to us, which also authenticates its sender
+	pub fn open(&self, our_key: &X25519KeyPair) -> Result<Vec<u8>> {
+		let key =
+			message_key(our_key, &XPublicKey::from(self.sender), &self.sender, &self.recipient);
+
+		XChaCha20Poly1305::new((&*key).into())
+			.decrypt(
+				XNonce::from_slice(&self.nonce),
+				Payload {
+					msg: &self.ciphertext,
+					aad: &associated_data(&self.sender, &self.recipient, self.timestamp),
+				},
+			)
+			.map_err(|_| anyhow!("Failed to decrypt relayed message"))
+	}
+
+	pub fn serialize(&self) -> Vec<u8> {
+		[
+			&RELAYED_MESSAGE_PREFIX[..],
+			&bincode::serialize(self).expect("Serialization is not expected to fail"),
+		]
+		.concat()
+	}
+
+	pub fn deserialize(data: &[u8]) -> Result<Self> {
+		let message = data
+			.strip_prefix(&RELAYED_MESSAGE_PREFIX[..])
+			.ok_or_else(|| anyhow!("Not a relayed message"))?;
+		bincode::deserialize(message).context("Invalid relayed message")
+	}
+}
+
+/// Both the sender and the recipient derive the same key, from their own
+/// secret key and the other party's public key
+fn message_key(
+	our_key: &X25519KeyPair,
+	their_public_key: &XPublicKey,
+	sender: &[u8; 32],
+	recipient: &[u8; 32],
+) -> Zeroizing<[u8; 32]> {
+	let shared_secret = our_key.secret_key.diffie_hellman(their_public_key);
+
+	let mut hasher = Sha256::new();
+	hasher.update(KEY_DERIVATION_CONTEXT);
+	hasher.update(shared_secret.as_bytes());
+	hasher.update(sender);
+	hasher.update(recipient);
+	Zeroizing::new(hasher.finalize().into())
+}
+
+/// The state needed to send and receive relayed messages
+pub struct Relay {
+	pub key: X25519KeyPair,
+	recent_messages: HashSet<[u8; 24]>,
+	/// The nonces of recent messages and when they were sent, in the order they were received
+	recent_messages_order: VecDeque<([u8; 24], u64)>,
+}
+
+impl Relay {
+	pub fn new(key: X25519KeyPair) -> Self {
+		Relay {
+			key,
+			recent_messages: Default::default(),
+			recent_messages_order: Default::default(),
+		}
+	}
+
+	/// Records the message as received, unless it is a replay of a message
+	/// we have received before, or is too old for us to know if it is
+	pub fn record_message(&mut self, message: &RelayedMessage) -> Result<(), RejectedMessage> {
+		self.record_message_at(message, now())
+	}
+
+	fn record_message_at(
+		&mut self,
+		message: &RelayedMessage,
+		now: u64,
+	) -> Result<(), RejectedMessage> {
+		let max_age = MAX_RELAYED_MESSAGE_AGE.as_secs();
+
+		if message.timestamp.abs_diff(now) > max_age {
+			return Err(RejectedMessage::Expired)
+		}
+
+		// Messages sent before the window would be rejected as expired, so we don't need to
+		// remember them. Messages are received roughly in the order they were sent, so a
+		// message is at most remembered a little longer than needed.
+		while let Some((nonce, timestamp)) = self.recent_messages_order.front() {
+			if now.saturating_sub(*timestamp) <= max_age {
+				break
+			}
+			self.recent_messages.remove(nonce);
+			self.recent_messages_order.pop_front();
+		}
+
+		if self.recent_messages.contains(&message.nonce) {
+			return Err(RejectedMessage::Duplicate)
+		}
+		if self.recent_messages.len() >= RECENT_MESSAGES_CAPACITY {
+			return Err(RejectedMessage::TooManyMessages)
+		}
+
+		self.recent_messages.insert(message.nonce);
+		self.recent_messages_order.push_back((message.nonce, message.timestamp));
+		Ok(())
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::p2p::core::ed25519_secret_key_to_x25519_secret_key;
+
+	fn create_key() -> X25519KeyPair {
+		use rand::RngCore;
+		let mut secret_key_bytes = [0; 32];
+		rand::thread_rng().fill_bytes(&mut secret_key_bytes);
+		let secret_key = ed25519_secret_key_to_x25519_secret_key(
+			&ed25519_dalek::SecretKey::from_bytes(&secret_key_bytes).unwrap(),
+		);
+		X25519KeyPair { public_key: (&secret_key).into(), secret_key }
+	}
+
+	#[test]
+	fn only_recipient_can_open_relayed_message() {
+		let sender = create_key();
+		let recipient = create_key();
+		let relay = create_key();
+
+		let message = RelayedMessage::deserialize(
+			&RelayedMessage::seal(&sender, &recipient.public_key, b"test").serialize(),
+		)
+		.unwrap();
+
+		assert_eq!(message.open(&recipient).unwrap(), b"test");
+		assert!(message.open(&relay).is_err());
+
+		// The relay can't pretend to be the sender
+		let forged = RelayedMessage {
+			sender: *sender.public_key.as_bytes(),
+			..RelayedMessage::seal(&relay, &recipient.public_key, b"forged")
+		};
+		assert!(forged.open(&recipient).is_err());
+	}
+
+	#[test]
+	fn relayed_messages_are_distinct_from_multisig_messages() {
+		use crate::p2p::CURRENT_PROTOCOL_VERSION;
+
+		assert!(!is_relayed_message(&CURRENT_PROTOCOL_VERSION.to_be_bytes()));
+		assert!(is_relayed_message(
+			&RelayedMessage::seal(&create_key(), &create_key().public_key, b"").serialize()
+		));
+	}
+
+	#[test]
+	fn duplicate_messages_are_detected() {
+		let sender = create_key();
+		let mut relay = Relay::new(create_key());
+
+		let message = RelayedMessage::seal(&sender, &relay.key.public_key, b"test");
+		assert_eq!(relay.record_message(&message), Ok(()));
+		assert_eq!(relay.record_message(&message), Err(RejectedMessage::Duplicate));
+
+		// Once the limit is reached, new messages are rejected rather than
+		// forgetting the ones we have received
+		for _ in 1..RECENT_MESSAGES_CAPACITY {
+			assert_eq!(
+				relay.record_message(&RelayedMessage::seal(
+					&sender,
+					&relay.key.public_key,
+					b"test"
+				)),
+				Ok(())
+			);
+		}
+		assert_eq!(
+			relay.record_message(&RelayedMessage::seal(&sender, &relay.key.public_key, b"test")),
+			Err(RejectedMessage::TooManyMessages)
+		);
+		assert_eq!(relay.record_message(&message), Err(RejectedMessage::Duplicate));
+	}
+
+	#[test]
+	fn messages_outside_of_the_window_are_rejected() {
+		let sender = create_key();
+		let mut relay = Relay::new(create_key());
+		let max_age = MAX_RELAYED_MESSAGE_AGE.as_secs();
+
+		let message = RelayedMessage::seal(&sender, &relay.key.public_key, b"test");
+		assert_eq!(relay.record_message_at(&message, message.timestamp), Ok(()));
+
+		// The message is forgotten once it is too old, but replaying it is still rejected
+		let later = message.timestamp + max_age + 1;
+		let new_message = RelayedMessage {
+			timestamp: later,
+			..RelayedMessage::seal(&sender, &relay.key.public_key, b"test")
+		};
+		assert_eq!(relay.record_message_at(&new_message, later), Ok(()));
+		assert!(!relay.recent_messages.contains(&message.nonce));
+		assert_eq!(relay.record_message_at(&message, later), Err(RejectedMessage::Expired));
+
+		// Messages from too far in the future are also rejected
+		let future_message = RelayedMessage {
+			timestamp: later + max_age + 1,
+			..RelayedMessage::seal(&sender, &relay.key.public_key, b"test")
+		};
+		assert_eq!(relay.record_message_at(&future_message, later), Err(RejectedMessage::Expired));
+	}
+
+	#[test]
+	fn relays_cant_change_the_timestamp() {
+		let sender = create_key();
+		let recipient = create_key();
+
+		let message = RelayedMessage::seal(&sender, &recipient.public_key, b"test");
+		let tampered = RelayedMessage { timestamp: message.timestamp + 1, ..message };
+		assert!(tampered.open(&recipient).is_err());
+	}
+}
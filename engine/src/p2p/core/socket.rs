@@ -13,7 +13,7 @@ pub const RECONNECT_INTERVAL_MAX: Duration = Duration::from_secs(30);
 /// Maximum incoming message size: if a remote tries sending a message larger than
 /// this they get disconnected (TODO: make sure this is slightly more that the
 /// theoretical maximum needed for multisig; 2MB is a conservative estimate.)
-const MAX_MESSAGE_SIZE: i64 = 2 * 1024 * 1024;
+pub const MAX_MESSAGE_SIZE: i64 = 2 * 1024 * 1024;
 
 /// How often should ZMQ send heartbeat messages in order to detect
 /// dead connections sooner (setting this to 0 disables heartbeats)
@@ -27,7 +27,7 @@ const CONNECTION_HEARTBEAT_TIMEOUT: Duration = Duration::from_secs(30);
 pub const DO_NOT_LINGER: i32 = 0;
 
 /// How many messages to keep in a "resend" buffer per peer
-const OUTGOING_MESSAGES_BUFFER_SIZE: i32 = 100;
+pub const OUTGOING_MESSAGES_BUFFER_SIZE: i32 = 100;
 
 /// Socket to be used for connecting to peer on the network
 pub struct OutgoingSocket {
@@ -1,7 +1,14 @@
-use super::{PeerInfo, PeerUpdate};
-use crate::p2p::{
-	core::{ACTIVITY_CHECK_INTERVAL, MAX_INACTIVITY_THRESHOLD},
-	OutgoingMultisigStageMessages, P2PKey,
+use super::{
+	relay::{Relay, RELAY_GRACE_PERIOD},
+	transport::in_memory::{InMemoryNetwork, InMemoryTransport},
+	PeerInfo, PeerUpdate,
+};
+use crate::{
+	p2p::{
+		core::{ACTIVITY_CHECK_INTERVAL, MAX_INACTIVITY_THRESHOLD},
+		OutgoingMultisigStageMessages, P2PKey,
+	},
+	settings::P2PTransport,
 };
 use sp_core::ed25519::Public;
 use state_chain_runtime::AccountId;
@@ -38,6 +45,16 @@ fn spawn_node(
 	idx: usize,
 	our_peer_info: PeerInfo,
 	peer_infos: &[PeerInfo],
+) -> Node {
+	spawn_node_with_transport(key, idx, our_peer_info, peer_infos, P2PTransport::Zmq)
+}
+
+fn spawn_node_with_transport(
+	key: &ed25519_dalek::Keypair,
+	idx: usize,
+	our_peer_info: PeerInfo,
+	peer_infos: &[PeerInfo],
+	transport: P2PTransport,
 ) -> Node {
 	let account_id = AccountId::new([idx as u8 + 1; 32]);
 
@@ -57,6 +74,53 @@ fn spawn_node(
 		super::start(
 			key,
 			our_peer_info.port,
+			transport,
+			false,
+			peer_infos.to_vec(),
+			account_id.clone(),
+			incoming_message_sender,
+			outgoing_message_receiver,
+			peer_update_receiver,
+		)
+		.instrument(info_span!("node", idx = idx))
+	});
+
+	Node {
+		account_id,
+		msg_sender: outgoing_message_sender,
+		peer_update_sender,
+		msg_receiver: incoming_message_receiver,
+	}
+}
+
+fn spawn_in_memory_node(
+	network: &InMemoryNetwork,
+	key: &ed25519_dalek::Keypair,
+	idx: usize,
+	peer_infos: &[PeerInfo],
+	enable_relay: bool,
+) -> Node {
+	let account_id = AccountId::new([idx as u8 + 1; 32]);
+
+	// Secret key does not implement clone:
+	let secret = ed25519_dalek::SecretKey::from_bytes(&key.secret.to_bytes()).unwrap();
+	let key = P2PKey::new(secret).encryption_key;
+
+	let (transport, transport_events) = InMemoryTransport::start(network, key.public_key);
+
+	let (incoming_message_sender, incoming_message_receiver) =
+		tokio::sync::mpsc::unbounded_channel();
+
+	let (outgoing_message_sender, outgoing_message_receiver) =
+		tokio::sync::mpsc::unbounded_channel();
+
+	let (peer_update_sender, peer_update_receiver) = tokio::sync::mpsc::unbounded_channel();
+
+	tokio::spawn({
+		super::run(
+			transport,
+			transport_events,
+			enable_relay.then(|| Relay::new(key)),
 			peer_infos.to_vec(),
 			account_id.clone(),
 			incoming_message_sender,
@@ -97,7 +161,7 @@ async fn connect_two_nodes() {
 	// TODO: automatically select ports to avoid any potential conflicts
 	// with other tests
 	let pi1 = create_node_info(AccountId::new([1; 32]), &node_key1, 8087);
-	let pi2 = create_node_info(AccountId::new([2; 32]), &node_key2, 8088);
+	let pi2 = create_node_info(AccountId::new([2; 32]), &node_key2, 8089);
 
 	// Node 1 knows about node 2 from the startup
 	let node1 = spawn_node(&node_key1, 0, pi1.clone(), &[pi1.clone(), pi2.clone()]);
@@ -164,8 +228,8 @@ async fn can_connect_after_pubkey_change() {
 
 	// TODO: automatically select ports to avoid any potential conflicts
 	// with other tests
-	let pi1 = create_node_info(AccountId::new([1; 32]), &node_key1, 8089);
-	let pi2 = create_node_info(AccountId::new([2; 32]), &node_key2, 8090);
+	let pi1 = create_node_info(AccountId::new([1; 32]), &node_key1, 8091);
+	let pi2 = create_node_info(AccountId::new([2; 32]), &node_key2, 8093);
 
 	let mut node1 = spawn_node(&node_key1, 0, pi1.clone(), &[pi1.clone(), pi2.clone()]);
 	let mut node2 = spawn_node(&node_key2, 1, pi2.clone(), &[pi1.clone(), pi2.clone()]);
@@ -184,7 +248,7 @@ async fn can_connect_after_pubkey_change() {
 
 	// Node 2 connects with a different key:
 	let node_key2b = create_keypair();
-	let pi2 = create_node_info(AccountId::new([2; 32]), &node_key2b, 8091);
+	let pi2 = create_node_info(AccountId::new([2; 32]), &node_key2b, 8095);
 	let mut node2b = spawn_node(&node_key2b, 1, pi2.clone(), &[pi1.clone(), pi2.clone()]);
 
 	// Node 1 learn about Node 2's new key:
@@ -204,8 +268,8 @@ async fn stale_connections() {
 	let node_key1 = create_keypair();
 	let node_key2 = create_keypair();
 
-	let pi1 = create_node_info(AccountId::new([1; 32]), &node_key1, 8094);
-	let pi2 = create_node_info(AccountId::new([2; 32]), &node_key2, 8095);
+	let pi1 = create_node_info(AccountId::new([1; 32]), &node_key1, 8097);
+	let pi2 = create_node_info(AccountId::new([2; 32]), &node_key2, 8099);
 
 	let mut node1 = spawn_node(&node_key1, 0, pi1.clone(), &[pi1.clone(), pi2.clone()]);
 	let mut node2 = spawn_node(&node_key2, 1, pi2.clone(), &[pi1.clone(), pi2.clone()]);
@@ -223,3 +287,300 @@ async fn stale_connections() {
 	send_and_receive_message(&node1, &mut node2).await.unwrap();
 	send_and_receive_message(&node2, &mut node1).await.unwrap();
 }
+
+#[tokio::test]
+async fn connect_two_nodes_with_noise() {
+	let node_key1 = create_keypair();
+	let node_key2 = create_keypair();
+
+	let pi1 = create_node_info(AccountId::new([1; 32]), &node_key1, 8101);
+	let pi2 = create_node_info(AccountId::new([2; 32]), &node_key2, 8103);
+
+	let node1 = spawn_node_with_transport(
+		&node_key1,
+		0,
+		pi1.clone(),
+		&[pi1.clone(), pi2.clone()],
+		P2PTransport::Noise,
+	);
+
+	// Node 2 only knows about itself from the startup, so it declines
+	// node 1's connection until it learns about node 1
+	let mut node2 =
+		spawn_node_with_transport(&node_key2, 1, pi2.clone(), &[pi2.clone()], P2PTransport::Noise);
+
+	tokio::time::sleep(std::time::Duration::from_millis(500)).await;
+	node2.peer_update_sender.send(PeerUpdate::Registered(pi1.clone())).unwrap();
+
+	// Node 1 should reconnect after the handshake failure
+	tokio::time::sleep(std::time::Duration::from_secs(1)).await;
+	send_and_receive_message(&node1, &mut node2).await.unwrap();
+}
+
+#[tokio::test]
+async fn can_send_large_messages_with_noise() {
+	let node_key1 = create_keypair();
+	let node_key2 = create_keypair();
+
+	let pi1 = create_node_info(AccountId::new([1; 32]), &node_key1, 8105);
+	let pi2 = create_node_info(AccountId::new([2; 32]), &node_key2, 8107);
+
+	let node1 = spawn_node_with_transport(
+		&node_key1,
+		0,
+		pi1.clone(),
+		&[pi1.clone(), pi2.clone()],
+		P2PTransport::Noise,
+	);
+	let mut node2 = spawn_node_with_transport(
+		&node_key2,
+		1,
+		pi2.clone(),
+		&[pi1.clone(), pi2.clone()],
+		P2PTransport::Noise,
+	);
+
+	tokio::time::sleep(std::time::Duration::from_millis(500)).await;
+
+	// Larger than a single Noise message
+	let payload: Vec<u8> = (0..1024 * 1024).map(|i| i as u8).collect();
+	node1
+		.msg_sender
+		.send(OutgoingMultisigStageMessages::Private(vec![(
+			node2.account_id.clone(),
+			payload.clone(),
+		)]))
+		.unwrap();
+
+	assert_eq!(expect_recv_with_timeout(&mut node2.msg_receiver).await, (pi1.account_id, payload));
+}
+
+#[tokio::test]
+async fn nodes_using_different_transports_can_communicate() {
+	let node_key1 = create_keypair();
+	let node_key2 = create_keypair();
+
+	let pi1 = create_node_info(AccountId::new([1; 32]), &node_key1, 8109);
+	let pi2 = create_node_info(AccountId::new([2; 32]), &node_key2, 8111);
+
+	let mut node1 = spawn_node_with_transport(
+		&node_key1,
+		0,
+		pi1.clone(),
+		&[pi1.clone(), pi2.clone()],
+		P2PTransport::Zmq,
+	);
+	let mut node2 = spawn_node_with_transport(
+		&node_key2,
+		1,
+		pi2.clone(),
+		&[pi1.clone(), pi2.clone()],
+		P2PTransport::Noise,
+	);
+
+	tokio::time::sleep(std::time::Duration::from_millis(500)).await;
+
+	send_and_receive_message(&node1, &mut node2).await.unwrap();
+	send_and_receive_message(&node2, &mut node1).await.unwrap();
+}
+
+#[tokio::test(start_paused = true)]
+async fn in_memory_nodes_connect_after_registration() {
+	let network = InMemoryNetwork::default();
+
+	let node_key1 = create_keypair();
+	let node_key2 = create_keypair();
+
+	let pi1 = create_node_info(AccountId::new([1; 32]), &node_key1, 0);
+	let pi2 = create_node_info(AccountId::new([2; 32]), &node_key2, 0);
+
+	let mut node1 =
+		spawn_in_memory_node(&network, &node_key1, 0, &[pi1.clone(), pi2.clone()], false);
+	let mut node2 = spawn_in_memory_node(&network, &node_key2, 1, &[pi2.clone()], false);
+
+	// Node 2 declines messages from node 1 until it learns about it
+	tokio::time::sleep(std::time::Duration::from_secs(1)).await;
+	assert!(send_and_receive_message(&node1, &mut node2).await.is_none());
+
+	node2.peer_update_sender.send(PeerUpdate::Registered(pi1.clone())).unwrap();
+	tokio::time::sleep(std::time::Duration::from_secs(1)).await;
+
+	send_and_receive_message(&node1, &mut node2).await.unwrap();
+	send_and_receive_message(&node2, &mut node1).await.unwrap();
+
+	// Node 2 no longer accepts messages from node 1 once it is deregistered
+	node2
+		.peer_update_sender
+		.send(PeerUpdate::Deregistered(pi1.account_id.clone(), Public(node_key1.public.to_bytes())))
+		.unwrap();
+	tokio::time::sleep(std::time::Duration::from_secs(1)).await;
+	assert!(send_and_receive_message(&node1, &mut node2).await.is_none());
+}
+
+#[tokio::test(start_paused = true)]
+async fn messages_are_relayed_to_unreachable_peers() {
+	let keys: Vec<_> = (0..4).map(|_| create_keypair()).collect();
+	let peer_infos: Vec<_> = keys
+		.iter()
+		.enumerate()
+		.map(|(idx, key)| create_node_info(AccountId::new([idx as u8 + 1; 32]), key, 0))
+		.collect();
+
+	let spawn_nodes = |enable_relay: bool| -> [Node; 4] {
+		let network = InMemoryNetwork::default();
+
+		// Node 0 can't connect to node 2 (but can connect to the others)
+		network.block_link(&peer_infos[0].pubkey, &peer_infos[2].pubkey);
+
+		keys.iter()
+			.enumerate()
+			.map(|(idx, key)| spawn_in_memory_node(&network, key, idx, &peer_infos, enable_relay))
+			.collect::<Vec<_>>()
+			.try_into()
+			.unwrap_or_else(|_| panic!("there are 4 keys"))
+	};
+
+	// Without relaying, node 2 never receives messages from node 0
+	let [node0, _node1, mut node2, _node3] = spawn_nodes(false);
+	tokio::time::sleep(std::time::Duration::from_secs(1)).await;
+	assert!(send_and_receive_message(&node0, &mut node2).await.is_none());
+
+	let [mut node0, _node1, mut node2, _node3] = spawn_nodes(true);
+	tokio::time::sleep(std::time::Duration::from_secs(1)).await;
+
+	// The message is relayed via both node 1 and node 3, but only received once
+	assert_eq!(
+		send_and_receive_message(&node0, &mut node2).await.unwrap(),
+		(peer_infos[0].account_id.clone(), b"test".to_vec())
+	);
+	assert!(recv_with_custom_timeout(&mut node2.msg_receiver, MAX_CONNECTION_DELAY)
+		.await
+		.is_none());
+
+	// The link in the other direction is unaffected
+	send_and_receive_message(&node2, &mut node0).await.unwrap();
+}
+
+#[tokio::test(start_paused = true)]
+async fn messages_are_relayed_once_an_established_connection_is_lost() {
+	let keys: Vec<_> = (0..3).map(|_| create_keypair()).collect();
+	let peer_infos: Vec<_> = keys
+		.iter()
+		.enumerate()
+		.map(|(idx, key)| create_node_info(AccountId::new([idx as u8 + 1; 32]), key, 0))
+		.collect();
+
+	let network = InMemoryNetwork::default();
+	let [node0, _node1, mut node2]: [Node; 3] = keys
+		.iter()
+		.enumerate()
+		.map(|(idx, key)| spawn_in_memory_node(&network, key, idx, &peer_infos, true))
+		.collect::<Vec<_>>()
+		.try_into()
+		.unwrap_or_else(|_| panic!("there are 3 keys"));
+	tokio::time::sleep(std::time::Duration::from_secs(1)).await;
+
+	send_and_receive_message(&node0, &mut node2).await.unwrap();
+
+	// The link fails after the connection has been established, so the message is lost...
+	network.block_link(&peer_infos[0].pubkey, &peer_infos[2].pubkey);
+	assert!(send_and_receive_message(&node0, &mut node2).await.is_none());
+
+	// ...but once the peer has been unreachable for long enough, messages are relayed
+	tokio::time::sleep(RELAY_GRACE_PERIOD).await;
+	assert_eq!(
+		send_and_receive_message(&node0, &mut node2).await.unwrap(),
+		(peer_infos[0].account_id.clone(), b"test".to_vec())
+	);
+}
+
+#[tokio::test(start_paused = true)]
+async fn in_memory_nodes_can_run_keygen_and_signing_ceremonies() {
+	use crate::{
+		db::{KeyStore, PersistentKeyDB},
+		p2p::muxer::P2PMuxer,
+	};
+	use cf_primitives::{ForeignChain, GENESIS_EPOCH};
+	use multisig::{
+		client::{BroadcastSigner, MultisigClientApi},
+		eth::{EthSigning, SigningPayload},
+		KeyId,
+	};
+	use sp_core::{ed25519, Pair};
+	use std::{collections::BTreeSet, sync::Arc};
+
+	let keys: Vec<_> = (0..3).map(|_| create_keypair()).collect();
+	let peer_infos: Vec<_> = keys
+		.iter()
+		.enumerate()
+		.map(|(idx, key)| create_node_info(AccountId::new([idx as u8 + 1; 32]), key, 0))
+		.collect();
+
+	let network = InMemoryNetwork::default();
+
+	// Node 0 can only reach node 2 via node 1, so the ceremonies rely on relaying
+	network.block_link(&peer_infos[0].pubkey, &peer_infos[2].pubkey);
+
+	let temp_dir = tempfile::tempdir().unwrap();
+	let clients: Vec<_> = keys
+		.iter()
+		.enumerate()
+		.map(|(idx, key)| {
+			let Node { account_id, msg_sender, msg_receiver, .. } =
+				spawn_in_memory_node(&network, key, idx, &peer_infos, true);
+
+			let (eth_outgoing_sender, eth_incoming_receiver, .., muxer_future) =
+				P2PMuxer::start(msg_receiver, msg_sender);
+			tokio::spawn(muxer_future);
+
+			let db = PersistentKeyDB::open_and_migrate_to_latest(
+				&temp_dir.path().join(format!("db_{idx}")),
+				None,
+			)
+			.unwrap();
+			let (client, client_future) = crate::multisig::start_client::<EthSigning>(
+				account_id,
+				BroadcastSigner::new(
+					ed25519::Pair::from_seed(&key.secret.to_bytes()),
+					ForeignChain::Ethereum,
+				),
+				KeyStore::new(Arc::new(db)),
+				eth_incoming_receiver,
+				eth_outgoing_sender,
+				0,
+			);
+			tokio::spawn(client_future);
+
+			client
+		})
+		.collect();
+
+	tokio::time::sleep(std::time::Duration::from_secs(1)).await;
+
+	let participants: BTreeSet<_> =
+		peer_infos.iter().map(|peer_info| peer_info.account_id.clone()).collect();
+
+	let public_keys = futures::future::join_all(
+		clients
+			.iter()
+			.map(|client| client.initiate_keygen(1, GENESIS_EPOCH, participants.clone())),
+	)
+	.await
+	.into_iter()
+	.collect::<Result<Vec<_>, _>>()
+	.unwrap();
+	assert!(public_keys.windows(2).all(|keys| keys[0] == keys[1]));
+
+	let signing_info =
+		vec![(KeyId::new(GENESIS_EPOCH, public_keys[0].clone()), SigningPayload([1; 32]))];
+	let signatures = futures::future::join_all(
+		clients
+			.iter()
+			.map(|client| client.initiate_signing(2, participants.clone(), signing_info.clone())),
+	)
+	.await
+	.into_iter()
+	.collect::<Result<Vec<_>, _>>()
+	.unwrap();
+	assert_eq!(signatures.len(), participants.len());
+}
@@ -0,0 +1,130 @@
+//! Abstracts the way encrypted connections between peers are established,
+//! so that the p2p control loop (peer bookkeeping, reconnection and
+//! inactivity tracking) doesn't depend on any particular transport.
+//!
+//! All transports authenticate peers using their x25519 keys (derived from
+//! their ed25519 node keys) and only accept connections from allowed peers.
+//! Connections are one-directional: we send messages over connections that
+//! we initiate, and receive messages over connections that peers initiate.
+//!
+//! Every node accepts incoming connections over all transports (ZMQ on its
+//! registered port, Noise on the port after it), and only uses the configured
+//! transport to connect to its peers. This way nodes using different transports
+//! can communicate, and each node can switch transports on its own.
+
+#[cfg(test)]
+pub mod in_memory;
+pub mod noise;
+pub mod zmq_curve;
+
+use state_chain_runtime::AccountId;
+use tokio::sync::mpsc::{UnboundedReceiver, UnboundedSender};
+use utilities::Port;
+
+use super::{PeerInfo, XPublicKey};
+
+#[derive(Debug)]
+pub enum ConnectionEvent {
+	ConnectionSuccess(AccountId),
+	/// An established connection has been lost (or messages can't be sent over it). The
+	/// transport keeps trying to reconnect, and reports a success once it has.
+	Disconnected(AccountId),
+	/// The connection can't be established (e.g. the peer has declined it).
+	/// The transport won't retry, so a new connection needs to be made.
+	ConnectionFailure(AccountId),
+}
+
+/// The Noise transport listens on the port after the one in the node's registered peer info,
+/// which is used by the ZMQ transport.
+pub const NOISE_PORT_OFFSET: Port = 1;
+
+/// Channels through which a transport reports events to the p2p control loop
+pub struct TransportEvents {
+	/// Messages from peers, identified by their x25519 public key
+	pub incoming_message_receiver: UnboundedReceiver<(XPublicKey, Vec<u8>)>,
+	pub connection_event_receiver: UnboundedReceiver<ConnectionEvent>,
+}
+
+impl TransportEvents {
+	/// Combines the events of two transports
+	pub fn merge(self, other: TransportEvents) -> TransportEvents {
+		fn forward<T: Send + 'static>(
+			mut receiver: UnboundedReceiver<T>,
+			sender: UnboundedSender<T>,
+		) {
+			tokio::spawn(async move {
+				while let Some(item) = receiver.recv().await {
+					if sender.send(item).is_err() {
+						break
+					}
+				}
+			});
+		}
+
+		let (incoming_message_sender, incoming_message_receiver) =
+			tokio::sync::mpsc::unbounded_channel();
+		let (connection_event_sender, connection_event_receiver) =
+			tokio::sync::mpsc::unbounded_channel();
+
+		for events in [self, other] {
+			forward(events.incoming_message_receiver, incoming_message_sender.clone());
+			forward(events.connection_event_receiver, connection_event_sender.clone());
+		}
+
+		TransportEvents { incoming_message_receiver, connection_event_receiver }
+	}
+}
+
+pub trait OutgoingConnection: Send {
+	/// Sends a message to the peer without blocking. The message may be dropped
+	/// if the peer can't keep up or has been unreachable for a while.
+	fn send(&self, payload: Vec<u8>);
+}
+
+pub trait Transport: Send {
+	/// Dropping the connection disconnects from the peer
+	type Connection: OutgoingConnection;
+
+	/// Accept incoming connections from the peer
+	fn allow_peer(&self, peer: &PeerInfo);
+
+	/// Stop accepting incoming connections from the peer with this key. Any existing
+	/// connections from the peer are not guaranteed to be closed.
+	fn disallow_peer(&self, pubkey: &XPublicKey);
+
+	/// Start connecting to the peer. The transport handles reconnecting if the
+	/// peer is unreachable, but reports a [ConnectionEvent::ConnectionFailure] if
+	/// the peer declines the connection.
+	fn connect(&mut self, peer: PeerInfo) -> Self::Connection;
+}
+
+/// Connects to peers using one transport, while accepting incoming connections over both, so
+/// that peers can reach us whichever transport they use.
+pub struct AcceptingBoth<T, Other> {
+	transport: T,
+	other: Other,
+}
+
+impl<T: Transport, Other: Transport> AcceptingBoth<T, Other> {
+	pub fn new(transport: T, other: Other) -> Self {
+		AcceptingBoth { transport, other }
+	}
+}
+
+impl<T: Transport, Other: Transport> Transport for AcceptingBoth<T, Other> {
+	type Connection = T::Connection;
+
+	fn allow_peer(&self, peer: &PeerInfo) {
+		self.transport.allow_peer(peer);
+		self.other.allow_peer(peer);
+	}
+
+	fn disallow_peer(&self, pubkey: &XPublicKey) {
+		self.transport.disallow_peer(pubkey);
+		self.other.disallow_peer(pubkey);
+	}
+
+	fn connect(&mut self, peer: PeerInfo) -> Self::Connection {
+		self.transport.connect(peer)
+	}
+}
@@ -0,0 +1,152 @@
+//! A transport that delivers messages between nodes running in the same
+//! process, for testing the p2p layer (and anything built on top of it)
+//! without opening any sockets.
+
+use std::{
+	collections::{HashMap, HashSet},
+	sync::{Arc, Mutex},
+};
+
+use state_chain_runtime::AccountId;
+use tokio::sync::mpsc::UnboundedSender;
+
+use crate::p2p::core::{PeerInfo, XPublicKey};
+
+use super::{ConnectionEvent, OutgoingConnection, Transport, TransportEvents};
+
+struct InMemoryNode {
+	allowed_pubkeys: HashSet<XPublicKey>,
+	incoming_message_sender: UnboundedSender<(XPublicKey, Vec<u8>)>,
+}
+
+#[derive(Default)]
+struct NetworkState {
+	nodes: HashMap<XPublicKey, InMemoryNode>,
+	/// Pairs of (sender, recipient) that can't reach each other
+	blocked_links: HashSet<(XPublicKey, XPublicKey)>,
+}
+
+impl NetworkState {
+	/// Returns the recipient if it would accept a message from the sender
+	fn reachable_node(&self, from: &XPublicKey, to: &XPublicKey) -> Option<&InMemoryNode> {
+		self.nodes.get(to).filter(|node| {
+			node.allowed_pubkeys.contains(from) && !self.blocked_links.contains(&(*from, *to))
+		})
+	}
+}
+
+/// The nodes that can reach each other, identified by their x25519 public keys
+#[derive(Clone, Default)]
+pub struct InMemoryNetwork {
+	state: Arc<Mutex<NetworkState>>,
+}
+
+impl InMemoryNetwork {
+	/// Prevents the node with the first key from connecting to the node with the second key
+	pub fn block_link(&self, from: &XPublicKey, to: &XPublicKey) {
+		self.state.lock().unwrap().blocked_links.insert((*from, *to));
+	}
+}
+
+pub struct InMemoryTransport {
+	network: InMemoryNetwork,
+	pubkey: XPublicKey,
+	connection_event_sender: UnboundedSender<ConnectionEvent>,
+}
+
+impl InMemoryTransport {
+	/// Joins the network as the node with this public key, replacing any node
+	/// previously using the same key
+	pub fn start(network: &InMemoryNetwork, pubkey: XPublicKey) -> (Self, TransportEvents) {
+		let (incoming_message_sender, incoming_message_receiver) =
+			tokio::sync::mpsc::unbounded_channel();
+		let (connection_event_sender, connection_event_receiver) =
+			tokio::sync::mpsc::unbounded_channel();
+
+		network.state.lock().unwrap().nodes.insert(
+			pubkey,
+			InMemoryNode { allowed_pubkeys: Default::default(), incoming_message_sender },
+		);
+
+		(
+			InMemoryTransport { network: network.clone(), pubkey, connection_event_sender },
+			TransportEvents { incoming_message_receiver, connection_event_receiver },
+		)
+	}
+
+	fn update_allowed_pubkeys(&self, f: impl FnOnce(&mut HashSet<XPublicKey>)) {
+		if let Some(node) = self.network.state.lock().unwrap().nodes.get_mut(&self.pubkey) {
+			f(&mut node.allowed_pubkeys);
+		}
+	}
+}
+
+impl Drop for InMemoryTransport {
+	fn drop(&mut self) {
+		self.network.state.lock().unwrap().nodes.remove(&self.pubkey);
+	}
+}
+
+impl Transport for InMemoryTransport {
+	type Connection = InMemoryConnection;
+
+	fn allow_peer(&self, peer: &PeerInfo) {
+		self.update_allowed_pubkeys(|allowed_pubkeys| {
+			allowed_pubkeys.insert(peer.pubkey);
+		});
+	}
+
+	fn disallow_peer(&self, pubkey: &XPublicKey) {
+		self.update_allowed_pubkeys(|allowed_pubkeys| {
+			allowed_pubkeys.remove(pubkey);
+		});
+	}
+
+	fn connect(&mut self, peer: PeerInfo) -> Self::Connection {
+		// Like other transports, report a failure if the peer doesn't know about us yet, so
+		// that the control loop reconnects later
+		let event = if self
+			.network
+			.state
+			.lock()
+			.unwrap()
+			.reachable_node(&self.pubkey, &peer.pubkey)
+			.is_some()
+		{
+			ConnectionEvent::ConnectionSuccess(peer.account_id.clone())
+		} else {
+			ConnectionEvent::ConnectionFailure(peer.account_id.clone())
+		};
+		self.connection_event_sender.send(event).unwrap();
+
+		InMemoryConnection {
+			network: self.network.clone(),
+			from: self.pubkey,
+			to: peer.pubkey,
+			account_id: peer.account_id,
+			connection_event_sender: self.connection_event_sender.clone(),
+		}
+	}
+}
+
+pub struct InMemoryConnection {
+	network: InMemoryNetwork,
+	from: XPublicKey,
+	to: XPublicKey,
+	account_id: AccountId,
+	connection_event_sender: UnboundedSender<ConnectionEvent>,
+}
+
+impl OutgoingConnection for InMemoryConnection {
+	fn send(&self, payload: Vec<u8>) {
+		// Messages to peers that are offline, or don't accept messages from us, are dropped
+		if let Some(node) = self.network.state.lock().unwrap().reachable_node(&self.from, &self.to)
+		{
+			let _result = node.incoming_message_sender.send((self.from, payload));
+		} else {
+			let _result = self
+				.connection_event_sender
+				.send(ConnectionEvent::Disconnected(self.account_id.clone()));
+		}
+	}
+}
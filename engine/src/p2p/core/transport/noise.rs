@@ -0,0 +1,424 @@
+//! A transport based on plain TCP connections secured with the Noise protocol
+//! (`Noise_IK_25519_ChaChaPoly_BLAKE2s`), using the same x25519 keys as the
+//! ZMQ CURVE transport. The IK pattern authenticates both sides in a single
+//! round trip: we know the peer's key from its registered peer info, and the
+//! peer learns ours from the first handshake message.
+//!
+//! Every Noise message is prefixed with its length as a big-endian u16. Since
+//! Noise messages are limited to 64KiB, each p2p message is sent as a Noise
+//! message containing its length (a big-endian u32), followed by its contents
+//! split into as many Noise messages as needed.
+
+use std::{net::SocketAddr, sync::Arc};
+
+use anyhow::{anyhow, ensure, Context, Result};
+use snow::{HandshakeState, TransportState};
+use tokio::{
+	io::{AsyncReadExt, AsyncWriteExt},
+	net::{TcpListener, TcpStream},
+	sync::{
+		mpsc::{self, UnboundedSender},
+		OwnedSemaphorePermit, Semaphore,
+	},
+	task::JoinHandle,
+};
+use tracing::{debug, info, trace, warn};
+use utilities::{
+	metrics::{P2P_BAD_MSG, P2P_MONITOR_EVENT, P2P_MSG_RECEIVED},
+	Port,
+};
+use x25519_dalek::StaticSecret;
+use zeroize::Zeroizing;
+
+use crate::p2p::core::{
+	auth::Authenticator,
+	pk_to_string,
+	socket::{
+		MAX_MESSAGE_SIZE, OUTGOING_MESSAGES_BUFFER_SIZE, RECONNECT_INTERVAL, RECONNECT_INTERVAL_MAX,
+	},
+	PeerInfo, X25519KeyPair, XPublicKey, HANDSHAKE_TIMEOUT,
+};
+
+use super::{ConnectionEvent, OutgoingConnection, Transport, TransportEvents};
+
+const NOISE_PARAMS: &str = "Noise_IK_25519_ChaChaPoly_BLAKE2s";
+/// The maximum size of a Noise message, including the authentication tag
+const MAX_NOISE_MESSAGE_SIZE: usize = 65535;
+const NOISE_TAG_SIZE: usize = 16;
+/// The maximum amount of a p2p message that fits in one Noise message
+const MAX_CHUNK_SIZE: usize = MAX_NOISE_MESSAGE_SIZE - NOISE_TAG_SIZE;
+/// The maximum number of incoming connections that can be handshaking at once. Connections
+/// beyond this are closed straight away, so unauthenticated clients can't use up our memory.
+const MAX_CONCURRENT_HANDSHAKES: usize = 64;
+
+pub struct NoiseTransport {
+	key: X25519KeyPair,
+	/// Keeps track of the peers we accept incoming connections from
+	authenticator: Arc<Authenticator>,
+	connection_event_sender: UnboundedSender<ConnectionEvent>,
+	listener_handle: JoinHandle<()>,
+}
+
+impl Drop for NoiseTransport {
+	fn drop(&mut self) {
+		self.listener_handle.abort();
+	}
+}
+
+impl NoiseTransport {
+	pub fn start(key: X25519KeyPair, port: Port) -> Result<(Self, TransportEvents)> {
+		let authenticator = Arc::new(Authenticator::new());
+
+		let (incoming_message_sender, incoming_message_receiver) =
+			tokio::sync::mpsc::unbounded_channel();
+		let (connection_event_sender, connection_event_receiver) =
+			tokio::sync::mpsc::unbounded_channel();
+
+		// Listen on all interfaces
+		let address = SocketAddr::from((std::net::Ipv6Addr::UNSPECIFIED, port));
+		let listener = std::net::TcpListener::bind(address)
+			.with_context(|| format!("Failed to listen for p2p connections on {address}"))?;
+		listener.set_nonblocking(true)?;
+		let listener = TcpListener::from_std(listener)?;
+		info!("Started listening for incoming p2p connections on: {address}");
+
+		let listener_handle = tokio::spawn(accept_connections(
+			listener,
+			key.secret_key.clone(),
+			authenticator.clone(),
+			incoming_message_sender,
+		));
+
+		Ok((
+			NoiseTransport { key, authenticator, connection_event_sender, listener_handle },
+			TransportEvents { incoming_message_receiver, connection_event_receiver },
+		))
+	}
+}
+
+impl Transport for NoiseTransport {
+	type Connection = NoiseConnection;
+
+	fn allow_peer(&self, peer: &PeerInfo) {
+		self.authenticator.add_peer(peer);
+	}
+
+	fn disallow_peer(&self, pubkey: &XPublicKey) {
+		self.authenticator.remove_peer(pubkey);
+	}
+
+	fn connect(&mut self, peer: PeerInfo) -> Self::Connection {
+		debug!("Connecting to peer {} at {}", peer.account_id, peer.noise_socket_address());
+
+		// Buffer at most OUTGOING_MESSAGES_BUFFER_SIZE messages per peer, same as with ZMQ
+		let (message_sender, message_receiver) =
+			mpsc::channel(OUTGOING_MESSAGES_BUFFER_SIZE as usize);
+
+		NoiseConnection {
+			account_id: peer.account_id.clone(),
+			message_sender,
+			connection_event_sender: self.connection_event_sender.clone(),
+			handle: tokio::spawn(send_to_peer(
+				peer,
+				self.key.secret_key.clone(),
+				message_receiver,
+				self.connection_event_sender.clone(),
+			)),
+		}
+	}
+}
+
+pub struct NoiseConnection {
+	account_id: state_chain_runtime::AccountId,
+	message_sender: mpsc::Sender<Vec<u8>>,
+	connection_event_sender: UnboundedSender<ConnectionEvent>,
+	handle: JoinHandle<()>,
+}
+
+impl Drop for NoiseConnection {
+	fn drop(&mut self) {
+		self.handle.abort();
+	}
+}
+
+impl OutgoingConnection for NoiseConnection {
+	fn send(&self, payload: Vec<u8>) {
+		// Messages are dropped rather than waiting if the buffer for this peer
+		// is full (this usually means that the peer has been offline for a while)
+		if let Err(e) = self.message_sender.try_send(payload) {
+			warn!("Failed to send a message to {}: {e}", self.account_id);
+			let _result = self
+				.connection_event_sender
+				.send(ConnectionEvent::Disconnected(self.account_id.clone()));
+		}
+	}
+}
+
+fn build_handshake(
+	secret_key: &StaticSecret,
+	remote_pubkey: Option<&XPublicKey>,
+) -> Result<HandshakeState> {
+	let secret_key = Zeroizing::new(secret_key.to_bytes());
+	let builder = snow::Builder::new(NOISE_PARAMS.parse().expect("valid noise params"))
+		.local_private_key(&secret_key[..]);
+
+	Ok(match remote_pubkey {
+		Some(remote_pubkey) =>
+			builder.remote_public_key(remote_pubkey.as_bytes()).build_initiator(),
+		None => builder.build_responder(),
+	}?)
+}
+
+/// Keeps (re)connecting to the peer and sending it messages until the connection is dropped,
+/// or the peer declines the connection.
+async fn send_to_peer(
+	peer: PeerInfo,
+	secret_key: StaticSecret,
+	mut message_receiver: mpsc::Receiver<Vec<u8>>,
+	connection_event_sender: UnboundedSender<ConnectionEvent>,
+) {
+	let account_id = peer.account_id.clone();
+	let mut reconnect_delay = RECONNECT_INTERVAL;
+
+	loop {
+		match TcpStream::connect(peer.noise_socket_address()).await {
+			Ok(mut stream) => {
+				stream.set_nodelay(true).unwrap();
+
+				let transport = match initiate_handshake(&mut stream, &peer, &secret_key).await {
+					Ok(transport) => transport,
+					Err(e) => {
+						P2P_MONITOR_EVENT.inc(&["handshake_failed"]);
+						warn!("Handshake failed with {account_id}: {e:#}");
+						// The control loop will make a new connection after a delay
+						let _result = connection_event_sender
+							.send(ConnectionEvent::ConnectionFailure(account_id));
+						return
+					},
+				};
+
+				trace!("Authentication success with {account_id}");
+				let _result = connection_event_sender
+					.send(ConnectionEvent::ConnectionSuccess(account_id.clone()));
+				reconnect_delay = RECONNECT_INTERVAL;
+
+				match send_messages(&mut stream, transport, &mut message_receiver).await {
+					// The connection has been dropped
+					Ok(()) => return,
+					Err(e) => {
+						P2P_MONITOR_EVENT.inc(&["disconnected"]);
+						trace!("Disconnected from {account_id}: {e:#}");
+						let _result = connection_event_sender
+							.send(ConnectionEvent::Disconnected(account_id.clone()));
+					},
+				}
+			},
+			Err(e) => {
+				P2P_MONITOR_EVENT.inc(&["connect_retried"]);
+				trace!(
+					"Failed to connect to {account_id}, retrying after {reconnect_delay:?}: {e}"
+				);
+			},
+		}
+
+		tokio::time::sleep(reconnect_delay).await;
+		reconnect_delay = std::cmp::min(reconnect_delay * 2, RECONNECT_INTERVAL_MAX);
+	}
+}
+
+async fn initiate_handshake(
+	stream: &mut TcpStream,
+	peer: &PeerInfo,
+	secret_key: &StaticSecret,
+) -> Result<TransportState> {
+	let mut handshake = build_handshake(secret_key, Some(&peer.pubkey))?;
+	let mut buffer = vec![0; MAX_NOISE_MESSAGE_SIZE];
+
+	tokio::time::timeout(HANDSHAKE_TIMEOUT, async {
+		let len = handshake.write_message(&[], &mut buffer)?;
+		write_frames(stream, &[&buffer[..len]]).await?;
+
+		// The peer closes the connection instead of responding if it doesn't know about us
+		let frame = read_frame(stream).await.context("Connection declined")?;
+		handshake
+			.read_message(&frame, &mut buffer)
+			.context("Invalid handshake response")?;
+
+		Ok::<_, anyhow::Error>(())
+	})
+	.await
+	.map_err(|_| anyhow!("Handshake timed out"))??;
+
+	Ok(handshake.into_transport_mode()?)
+}
+
+/// Returns once the message sender is dropped, or with an error if the connection fails
+async fn send_messages(
+	stream: &mut TcpStream,
+	mut transport: TransportState,
+	message_receiver: &mut mpsc::Receiver<Vec<u8>>,
+) -> Result<()> {
+	let mut buffer = vec![0; MAX_NOISE_MESSAGE_SIZE];
+
+	while let Some(payload) = message_receiver.recv().await {
+		let header = (payload.len() as u32).to_be_bytes();
+		let frames = std::iter::once(&header[..])
+			.chain(payload.chunks(MAX_CHUNK_SIZE))
+			.map(|chunk| {
+				let len = transport.write_message(chunk, &mut buffer)?;
+				Ok(buffer[..len].to_vec())
+			})
+			.collect::<Result<Vec<_>>>()?;
+
+		write_frames(stream, &frames.iter().map(Vec::as_slice).collect::<Vec<_>>()).await?;
+	}
+
+	Ok(())
+}
+
+async fn accept_connections(
+	listener: TcpListener,
+	secret_key: StaticSecret,
+	authenticator: Arc<Authenticator>,
+	incoming_message_sender: UnboundedSender<(XPublicKey, Vec<u8>)>,
+) {
+	let handshake_permits = Arc::new(Semaphore::new(MAX_CONCURRENT_HANDSHAKES));
+
+	loop {
+		match listener.accept().await {
+			Ok((stream, address)) => {
+				trace!("Incoming connection from {address}");
+				let Ok(handshake_permit) = handshake_permits.clone().try_acquire_owned() else {
+					P2P_MONITOR_EVENT.inc(&["too_many_handshakes"]);
+					debug!(
+						"Closing incoming connection from {address}: too many handshakes in progress"
+					);
+					continue
+				};
+				tokio::spawn({
+					let secret_key = secret_key.clone();
+					let authenticator = authenticator.clone();
+					let incoming_message_sender = incoming_message_sender.clone();
+					async move {
+						if let Err(e) = receive_from_peer(
+							stream,
+							handshake_permit,
+							&secret_key,
+							&authenticator,
+							&incoming_message_sender,
+						)
+						.await
+						{
+							debug!("Incoming connection from {address} closed: {e:#}");
+						}
+					}
+				});
+			},
+			Err(e) => warn!("Failed to accept an incoming connection: {e}"),
+		}
+	}
+}
+
+async fn receive_from_peer(
+	mut stream: TcpStream,
+	handshake_permit: OwnedSemaphorePermit,
+	secret_key: &StaticSecret,
+	authenticator: &Authenticator,
+	incoming_message_sender: &UnboundedSender<(XPublicKey, Vec<u8>)>,
+) -> Result<()> {
+	// The timeout limits how long unauthenticated clients can hold on to resources
+	let (pubkey, mut transport) = tokio::time::timeout(
+		HANDSHAKE_TIMEOUT,
+		respond_to_handshake(&mut stream, secret_key, authenticator),
+	)
+	.await
+	.map_err(|_| anyhow!("Handshake timed out"))??;
+	drop(handshake_permit);
+
+	let mut buffer = vec![0; MAX_NOISE_MESSAGE_SIZE];
+
+	loop {
+		let message = read_message(&mut stream, &mut transport, &mut buffer).await?;
+		P2P_MSG_RECEIVED.inc();
+		if incoming_message_sender.send((pubkey, message)).is_err() {
+			// The transport has been stopped
+			return Ok(())
+		}
+	}
+}
+
+async fn respond_to_handshake(
+	stream: &mut TcpStream,
+	secret_key: &StaticSecret,
+	authenticator: &Authenticator,
+) -> Result<(XPublicKey, TransportState)> {
+	let mut handshake = build_handshake(secret_key, None)?;
+	let mut buffer = vec![0; MAX_NOISE_MESSAGE_SIZE];
+
+	let frame = read_frame(stream).await?;
+	handshake
+		.read_message(&frame, &mut buffer)
+		.context("Invalid handshake message")?;
+
+	let pubkey: [u8; 32] = handshake
+		.get_remote_static()
+		.expect("the initiator's key is known after the first IK message")
+		.try_into()
+		.expect("x25519 keys are 32 bytes");
+	let pubkey = XPublicKey::from(pubkey);
+
+	// Closing the connection without responding lets the peer know it has been declined
+	ensure!(
+		authenticator.is_allowed(&pubkey),
+		"Declined connection from unknown pubkey {}",
+		pk_to_string(&pubkey)
+	);
+
+	let len = handshake.write_message(&[], &mut buffer)?;
+	write_frames(stream, &[&buffer[..len]]).await?;
+
+	Ok((pubkey, handshake.into_transport_mode()?))
+}
+
+async fn read_message(
+	stream: &mut TcpStream,
+	transport: &mut TransportState,
+	buffer: &mut [u8],
+) -> Result<Vec<u8>> {
+	let len = transport.read_message(&read_frame(stream).await?, buffer)?;
+	let message_len = u32::from_be_bytes(
+		buffer[..len].try_into().map_err(|_| anyhow!("Invalid message header"))?,
+	) as usize;
+
+	if message_len > MAX_MESSAGE_SIZE as usize {
+		P2P_BAD_MSG.inc(&["message_too_large"]);
+		return Err(anyhow!("Message of {message_len} bytes is too large"))
+	}
+
+	let mut message = Vec::with_capacity(message_len);
+	while message.len() < message_len {
+		let len = transport.read_message(&read_frame(stream).await?, buffer)?;
+		ensure!(message.len() + len <= message_len, "Message is longer than its header states");
+		message.extend_from_slice(&buffer[..len]);
+	}
+
+	Ok(message)
+}
+
+async fn read_frame(stream: &mut TcpStream) -> Result<Vec<u8>> {
+	let len = stream.read_u16().await?;
+	let mut frame = vec![0; len as usize];
+	stream.read_exact(&mut frame).await?;
+	Ok(frame)
+}
+
+/// Writes all frames at once, to avoid sending lots of small TCP packets
+async fn write_frames(stream: &mut TcpStream, frames: &[&[u8]]) -> Result<()> {
+	let mut data = Vec::with_capacity(frames.iter().map(|frame| frame.len() + 2).sum());
+	for frame in frames {
+		data.extend_from_slice(&(frame.len() as u16).to_be_bytes());
+		data.extend_from_slice(frame);
+	}
+	stream.write_all(&data).await?;
+	Ok(())
+}
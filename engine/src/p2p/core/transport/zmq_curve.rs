@@ -0,0 +1,158 @@
+//! The transport based on ZMQ sockets secured with CURVE, with incoming
+//! connections authorised by our own ZAP handler.
+
+use std::sync::Arc;
+
+use tracing::{info, warn};
+use utilities::{
+	metrics::{P2P_BAD_MSG, P2P_MSG_RECEIVED},
+	Port,
+};
+
+use crate::p2p::core::{
+	auth::{self, Authenticator},
+	monitor,
+	socket::{ConnectedOutgoingSocket, OutgoingSocket},
+	PeerInfo, X25519KeyPair, XPublicKey, HANDSHAKE_TIMEOUT,
+};
+
+use super::{OutgoingConnection, Transport, TransportEvents};
+
+pub struct ZmqTransport {
+	/// Our own key, used for initiating and accepting secure connections
+	key: X25519KeyPair,
+	/// A handle to the authenticator thread that can be used to make changes to the
+	/// list of allowed peers
+	authenticator: Arc<Authenticator>,
+	/// This is how we communicate with the "monitor" thread
+	monitor_handle: monitor::MonitorHandle,
+	/// NOTE: zmq context is intentionally declared at the bottom of the struct
+	/// to ensure its destructor is called after that of any zmq sockets
+	zmq_context: zmq::Context,
+}
+
+impl ZmqTransport {
+	pub fn start(key: X25519KeyPair, port: Port) -> (Self, TransportEvents) {
+		let zmq_context = zmq::Context::new();
+
+		zmq_context.set_max_sockets(65536).expect("should update socket limit");
+
+		let authenticator = auth::start_authentication_thread(zmq_context.clone());
+
+		let (monitor_handle, connection_event_receiver) =
+			monitor::start_monitoring_thread(zmq_context.clone());
+
+		let incoming_message_receiver = start_listening_thread(&zmq_context, &key, port);
+
+		(
+			ZmqTransport { key, authenticator, monitor_handle, zmq_context },
+			TransportEvents { incoming_message_receiver, connection_event_receiver },
+		)
+	}
+}
+
+impl OutgoingConnection for ConnectedOutgoingSocket {
+	fn send(&self, payload: Vec<u8>) {
+		ConnectedOutgoingSocket::send(self, payload)
+	}
+}
+
+impl Transport for ZmqTransport {
+	type Connection = ConnectedOutgoingSocket;
+
+	fn allow_peer(&self, peer: &PeerInfo) {
+		self.authenticator.add_peer(peer);
+	}
+
+	fn disallow_peer(&self, pubkey: &XPublicKey) {
+		// NOTE: There is no (trivial) way to disconnect peers that are
+		// already connected to our listening ZMQ socket, we can only
+		// prevent future connections from being established and rely
+		// on peer from disconnecting from "client side".
+		self.authenticator.remove_peer(pubkey);
+	}
+
+	fn connect(&mut self, peer: PeerInfo) -> Self::Connection {
+		let socket = OutgoingSocket::new(&self.zmq_context, &self.key);
+
+		self.monitor_handle.start_monitoring_for(&socket, &peer);
+
+		socket.connect(peer)
+	}
+}
+
+/// Start listening for incoming p2p messages on a separate thread
+fn start_listening_thread(
+	zmq_context: &zmq::Context,
+	key: &X25519KeyPair,
+	port: Port,
+) -> tokio::sync::mpsc::UnboundedReceiver<(XPublicKey, Vec<u8>)> {
+	let socket = zmq_context.socket(zmq::SocketType::ROUTER).unwrap();
+
+	socket.set_router_mandatory(true).unwrap();
+	socket.set_router_handover(true).unwrap();
+	socket.set_curve_server(true).unwrap();
+	socket.set_curve_secretkey(&key.secret_key.to_bytes()).unwrap();
+	socket.set_handshake_ivl(HANDSHAKE_TIMEOUT.as_millis() as i32).unwrap();
+
+	// Listen on all interfaces
+	let endpoint = format!("tcp://0.0.0.0:{port}");
+	info!("Started listening for incoming p2p connections on: {endpoint}");
+
+	socket.bind(&endpoint).expect("invalid endpoint");
+
+	let (incoming_message_sender, incoming_message_receiver) =
+		tokio::sync::mpsc::unbounded_channel();
+
+	// This OS thread is for incoming messages
+	// TODO: combine this with the authentication thread?
+	std::thread::spawn(move || loop {
+		let mut parts = receive_multipart(&socket).unwrap();
+		P2P_MSG_RECEIVED.inc();
+		// We require that all messages exchanged between
+		// peers only consist of one part. ZMQ dealer
+		// sockets automatically prepend a sender id
+		// (which we ignore) to every message, giving
+		// us a 2 part message.
+		if parts.len() == 2 {
+			let msg = &mut parts[1];
+
+			// This value is ZMQ convention for the public
+			// key of message's origin
+			const PUBLIC_KEY_TAG: &str = "User-Id";
+			let pubkey = msg.gets(PUBLIC_KEY_TAG).expect("pubkey is always present");
+
+			let pubkey: [u8; 32] = hex::decode(pubkey).unwrap().try_into().unwrap();
+			let pubkey = XPublicKey::from(pubkey);
+
+			incoming_message_sender.send((pubkey, msg.to_vec())).unwrap();
+		} else {
+			P2P_BAD_MSG.inc(&["bad_number_of_parts"]);
+			warn!("Ignoring a multipart message with unexpected number of parts ({})", parts.len())
+		}
+	});
+
+	incoming_message_receiver
+}
+
+/// Unlike recv_multipart available on zmq::Socket, this collects
+/// original message structs rather than payload bytes only
+fn receive_multipart(socket: &zmq::Socket) -> zmq::Result<Vec<zmq::Message>> {
+	// This indicates that we always want to block while
+	// waiting for new messages
+	let flags = 0;
+
+	let mut parts = vec![];
+
+	loop {
+		let mut part = zmq::Message::new();
+		socket.recv(&mut part, flags)?;
+		parts.push(part);
+
+		let more_parts = socket.get_rcvmore()?;
+		if !more_parts {
+			break
+		}
+	}
+	Ok(parts)
+}
@@ -19,6 +19,8 @@ pub struct P2PMuxer {
 	dot_outgoing_receiver: UnboundedReceiver<OutgoingMultisigStageMessages>,
 	btc_incoming_sender: UnboundedSender<(AccountId, VersionedCeremonyMessage)>,
 	btc_outgoing_receiver: UnboundedReceiver<OutgoingMultisigStageMessages>,
+	arb_incoming_sender: UnboundedSender<(AccountId, VersionedCeremonyMessage)>,
+	arb_outgoing_receiver: UnboundedReceiver<OutgoingMultisigStageMessages>,
 }
 
 /// Top-level protocol message, encapsulates all others
@@ -93,6 +95,8 @@ impl P2PMuxer {
 		MultisigMessageReceiver<PolkadotCrypto>,
 		MultisigMessageSender<BitcoinCrypto>,
 		MultisigMessageReceiver<BitcoinCrypto>,
+		MultisigMessageSender<EvmCrypto>,
+		MultisigMessageReceiver<EvmCrypto>,
 		impl Future<Output = ()>,
 	) {
 		let (eth_outgoing_sender, eth_outgoing_receiver) = tokio::sync::mpsc::unbounded_channel();
@@ -104,6 +108,9 @@ impl P2PMuxer {
 		let (btc_outgoing_sender, btc_outgoing_receiver) = tokio::sync::mpsc::unbounded_channel();
 		let (btc_incoming_sender, btc_incoming_receiver) = tokio::sync::mpsc::unbounded_channel();
 
+		let (arb_outgoing_sender, arb_outgoing_receiver) = tokio::sync::mpsc::unbounded_channel();
+		let (arb_incoming_sender, arb_incoming_receiver) = tokio::sync::mpsc::unbounded_channel();
+
 		let muxer = P2PMuxer {
 			all_incoming_receiver,
 			all_outgoing_sender,
@@ -113,6 +120,8 @@ impl P2PMuxer {
 			dot_incoming_sender,
 			btc_outgoing_receiver,
 			btc_incoming_sender,
+			arb_outgoing_receiver,
+			arb_incoming_sender,
 		};
 
 		let muxer_fut = muxer.run().instrument(info_span!("P2PMuxer"));
@@ -124,6 +133,8 @@ impl P2PMuxer {
 			MultisigMessageReceiver::<PolkadotCrypto>::new(dot_incoming_receiver),
 			MultisigMessageSender::<BitcoinCrypto>::new(btc_outgoing_sender),
 			MultisigMessageReceiver::<BitcoinCrypto>::new(btc_incoming_receiver),
+			MultisigMessageSender::<EvmCrypto>::new(arb_outgoing_sender),
+			MultisigMessageReceiver::<EvmCrypto>::new(arb_incoming_receiver),
 			muxer_fut,
 		)
 	}
@@ -152,6 +163,13 @@ impl P2PMuxer {
 									.send((account_id, message))
 									.expect("bitcoin receiver dropped");
 							},
+							ChainTag::Arbitrum => {
+								// The receiver is dropped when Arbitrum is not configured.
+								if self.arb_incoming_sender.send((account_id, message)).is_err() {
+									P2P_BAD_MSG.inc(&["arbitrum_not_configured"]);
+									trace!("Ignoring Arbitrum p2p message, as Arbitrum is not configured");
+								}
+							},
 							ChainTag::Ed25519 => {
 								P2P_BAD_MSG.inc(&["Ed25519_not_supported"]);
 								warn!("Ed25519 not yet supported")
@@ -205,6 +223,9 @@ impl P2PMuxer {
 				Some(data) = self.btc_outgoing_receiver.recv() => {
 					self.process_outgoing(ChainTag::Bitcoin, data).await;
 				}
+				Some(data) = self.arb_outgoing_receiver.recv() => {
+					self.process_outgoing(ChainTag::Arbitrum, data).await;
+				}
 			}
 		}
 	}
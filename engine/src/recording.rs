@@ -0,0 +1,348 @@
+//! Recording and replaying of external chain data.
+//!
+//! When a [Recorder] is given to one of the retry rpc clients, the responses to its requests are
+//! written to a file, one JSON object per line. The `recording` chain source adapter also writes
+//! each header it produces to the same file. A [Recording] loaded from such a file can then be
+//! replayed with a [replay_client::ReplayClient] and a `ReplaySource`, so the witnessing pipeline
+//! can be run against real chain data without connecting to any nodes.
+
+pub mod replay_client;
+#[cfg(test)]
+mod replay_tests;
+
+use std::{
+	collections::HashMap,
+	fs::File,
+	io::{BufRead, BufReader},
+	path::Path,
+	sync::atomic::{AtomicUsize, Ordering},
+};
+
+use anyhow::{anyhow, Context, Result};
+use ethers::types::Bloom;
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use serde_json::Value;
+use tokio::{io::AsyncWriteExt, sync::mpsc};
+use tracing::error;
+use utilities::task_scope::Scope;
+
+use crate::{retrier::RequestLog, witness::common::chain_source::Header};
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+enum Entry {
+	Header(Value),
+	/// The `sequence` numbers the responses to the same request, in the order they were recorded,
+	/// so that repeated requests are replayed with the response each of them got.
+	Response {
+		request: String,
+		sequence: usize,
+		response: Value,
+	},
+}
+
+/// Header data that can be written to a recording, and read back from it.
+pub trait RecordedData: Sized {
+	fn to_recorded(&self) -> Result<Value>;
+
+	fn from_recorded(value: Value) -> Result<Self>;
+}
+
+impl RecordedData for () {
+	fn to_recorded(&self) -> Result<Value> {
+		Ok(Value::Null)
+	}
+
+	fn from_recorded(_value: Value) -> Result<Self> {
+		Ok(())
+	}
+}
+
+impl<T: RecordedData> RecordedData for Option<T> {
+	fn to_recorded(&self) -> Result<Value> {
+		self.as_ref().map_or(Ok(Value::Null), RecordedData::to_recorded)
+	}
+
+	fn from_recorded(value: Value) -> Result<Self> {
+		if value.is_null() {
+			Ok(None)
+		} else {
+			T::from_recorded(value).map(Some)
+		}
+	}
+}
+
+impl<Index, Hash, Data> RecordedData for Header<Index, Hash, Data>
+where
+	Index: Serialize + DeserializeOwned,
+	Hash: Serialize + DeserializeOwned,
+	Data: RecordedData,
+{
+	fn to_recorded(&self) -> Result<Value> {
+		Ok(serde_json::to_value(Header {
+			index: serde_json::to_value(&self.index)?,
+			hash: serde_json::to_value(&self.hash)?,
+			parent_hash: self.parent_hash.as_ref().map(serde_json::to_value).transpose()?,
+			data: self.data.to_recorded()?,
+		})?)
+	}
+
+	fn from_recorded(value: Value) -> Result<Self> {
+		let header: Header<Value, Value, Value> = serde_json::from_value(value)?;
+		Ok(Header {
+			index: serde_json::from_value(header.index)?,
+			hash: serde_json::from_value(header.hash)?,
+			parent_hash: header.parent_hash.map(serde_json::from_value).transpose()?,
+			data: Data::from_recorded(header.data)?,
+		})
+	}
+}
+
+impl RecordedData for Bloom {
+	fn to_recorded(&self) -> Result<Value> {
+		Ok(serde_json::to_value(self)?)
+	}
+
+	fn from_recorded(value: Value) -> Result<Self> {
+		Ok(serde_json::from_value(value)?)
+	}
+}
+
+/// Writes headers and rpc responses to a recording file. The entries are sent to a task that does
+/// the writing, so recording never blocks the requests. Cloned recorders write to the same file.
+#[derive(Clone)]
+pub struct Recorder {
+	entry_sender: mpsc::UnboundedSender<Entry>,
+}
+
+impl Recorder {
+	/// The file is written to until every clone of the recorder is dropped, or the scope ends.
+	pub fn new(scope: &Scope<'_, anyhow::Error>, path: &Path) -> Result<Self> {
+		let file = File::create(path)
+			.with_context(|| format!("Failed to create recording file {}", path.display()))?;
+		let (entry_sender, mut entry_receiver) = mpsc::unbounded_channel::<Entry>();
+
+		scope.spawn(async move {
+			let mut writer = tokio::io::BufWriter::new(tokio::fs::File::from_std(file));
+			let mut sequences = HashMap::<String, usize>::new();
+			while let Some(mut entry) = entry_receiver.recv().await {
+				// All the entries that are waiting are written before flushing, instead of flushing
+				// after each one.
+				loop {
+					if let Entry::Response { request, sequence, .. } = &mut entry {
+						let next_sequence = sequences.entry(request.clone()).or_default();
+						*sequence = *next_sequence;
+						*next_sequence += 1;
+					}
+					if let Err(e) = write_entry(&mut writer, &entry).await {
+						error!("Failed to write to recording file: {e}");
+					}
+					match entry_receiver.try_recv() {
+						Ok(next_entry) => entry = next_entry,
+						Err(_) => break,
+					}
+				}
+				if let Err(e) = writer.flush().await {
+					error!("Failed to write to recording file: {e}");
+				}
+			}
+			Ok(())
+		});
+
+		Ok(Self { entry_sender })
+	}
+
+	pub fn record_header<RecordedHeader: RecordedData>(&self, header: &RecordedHeader) {
+		match header.to_recorded() {
+			Ok(header) => self.send(Entry::Header(header)),
+			Err(e) => error!("Failed to record header: {e}"),
+		}
+	}
+
+	pub fn record_response<T: Serialize>(&self, request_log: &RequestLog, response: &T) {
+		match serde_json::to_value(response) {
+			Ok(response) => self.send_response(request_log, response),
+			Err(e) => error!("Failed to record response to `{request_log}`: {e}"),
+		}
+	}
+
+	/// As [`Self::record_response`], for responses that aren't serializable themselves.
+	pub fn record_response_data<T: RecordedData>(&self, request_log: &RequestLog, response: &T) {
+		match response.to_recorded() {
+			Ok(response) => self.send_response(request_log, response),
+			Err(e) => error!("Failed to record response to `{request_log}`: {e}"),
+		}
+	}
+
+	fn send_response(&self, request_log: &RequestLog, response: Value) {
+		// The writer numbers the responses, as it sees them in the order they are written.
+		self.send(Entry::Response { request: request_log.to_string(), sequence: 0, response })
+	}
+
+	fn send(&self, entry: Entry) {
+		if self.entry_sender.send(entry).is_err() {
+			error!("Failed to record entry, the recording file is no longer being written");
+		}
+	}
+}
+
+async fn write_entry(
+	writer: &mut tokio::io::BufWriter<tokio::fs::File>,
+	entry: &Entry,
+) -> Result<()> {
+	let mut line = serde_json::to_vec(entry)?;
+	line.push(b'\n');
+	writer.write_all(&line).await?;
+	Ok(())
+}
+
+/// The responses recorded for a request, in the order they were recorded, and how many of them
+/// have been replayed.
+#[derive(Default)]
+struct RecordedResponses {
+	responses: Vec<Value>,
+	replayed: AtomicUsize,
+}
+
+/// The contents of a recording file.
+pub struct Recording {
+	headers: Vec<Value>,
+	responses: HashMap<String, RecordedResponses>,
+}
+
+impl Recording {
+	pub fn load(path: &Path) -> Result<Self> {
+		let file = File::open(path)
+			.with_context(|| format!("Failed to open recording file {}", path.display()))?;
+		let mut headers = Vec::new();
+		let mut responses = HashMap::<String, RecordedResponses>::new();
+		for (line_number, line) in BufReader::new(file).lines().enumerate() {
+			match serde_json::from_str(&line?)
+				.with_context(|| format!("Invalid entry on line {}", line_number + 1))?
+			{
+				Entry::Header(header) => headers.push(header),
+				Entry::Response { request, sequence, response } => {
+					let recorded_responses = &mut responses.entry(request).or_default().responses;
+					anyhow::ensure!(
+						sequence == recorded_responses.len(),
+						"Response out of sequence on line {}",
+						line_number + 1
+					);
+					recorded_responses.push(response);
+				},
+			}
+		}
+		Ok(Self { headers, responses })
+	}
+
+	/// The recorded headers, in the order they were produced by the chain source.
+	pub fn headers<RecordedHeader: RecordedData>(&self) -> Result<Vec<RecordedHeader>> {
+		self.headers.iter().cloned().map(RecordedHeader::from_recorded).collect()
+	}
+
+	/// The last header recorded with the given index.
+	pub fn header_at_index<
+		Index: Serialize + DeserializeOwned,
+		Hash: Serialize + DeserializeOwned,
+		Data: RecordedData,
+	>(
+		&self,
+		index: Index,
+	) -> Result<Header<Index, Hash, Data>> {
+		let index = serde_json::to_value(index)?;
+		self.headers
+			.iter()
+			.rev()
+			.find(|header| header.get("index") == Some(&index))
+			.cloned()
+			.map(Header::from_recorded)
+			.ok_or_else(|| anyhow!("No header recorded at index {index}"))?
+	}
+
+	/// The response to the next replay of the request. Each time the same request is replayed, it
+	/// gets the next response recorded for it, and once they have all been replayed, the last one.
+	pub fn response<T: DeserializeOwned>(&self, request_log: &RequestLog) -> Result<T> {
+		Ok(serde_json::from_value(self.recorded_response(request_log)?)?)
+	}
+
+	/// As [`Self::response`], for responses that aren't deserializable themselves.
+	pub fn response_data<T: RecordedData>(&self, request_log: &RequestLog) -> Result<T> {
+		T::from_recorded(self.recorded_response(request_log)?)
+	}
+
+	fn recorded_response(&self, request_log: &RequestLog) -> Result<Value> {
+		let request = request_log.to_string();
+		let RecordedResponses { responses, replayed } = self
+			.responses
+			.get(&request)
+			.ok_or_else(|| anyhow!("No response recorded for request `{request}`"))?;
+		let sequence = replayed.fetch_add(1, Ordering::Relaxed).min(responses.len() - 1);
+		Ok(responses[sequence].clone())
+	}
+}
+
+/// Records whatever `record` gives the recorder to a temporary file, and loads it back.
+#[cfg(test)]
+pub async fn test_recording(record: impl FnOnce(&Recorder) + Send + 'static) -> Recording {
+	use futures::FutureExt;
+
+	let directory = tempfile::tempdir().unwrap();
+	let path = directory.path().join("recording.jsonl");
+
+	// The scope only ends once everything recorded has been written.
+	utilities::task_scope::task_scope(|scope| {
+		let path = path.clone();
+		async move {
+			record(&Recorder::new(scope, &path)?);
+			Ok(())
+		}
+		.boxed()
+	})
+	.await
+	.unwrap();
+
+	Recording::load(&path).unwrap()
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[tokio::test]
+	async fn recording_round_trip() {
+		let headers = [
+			Header { index: 1u64, hash: 11u64, parent_hash: Some(0u64), data: () },
+			Header { index: 2u64, hash: 22u64, parent_hash: Some(11u64), data: () },
+			Header { index: 2u64, hash: 23u64, parent_hash: Some(11u64), data: () },
+		];
+		let request_log = RequestLog::new("block_hash".to_string(), Some("1".to_string()));
+		let other_request_log = RequestLog::new("block_hash".to_string(), Some("2".to_string()));
+
+		let recording = test_recording({
+			let request_log = request_log.clone();
+			let other_request_log = other_request_log.clone();
+			move |recorder| {
+				for header in &headers {
+					recorder.record_header(header);
+				}
+				recorder.record_response(&request_log, &11u64);
+				recorder.record_response(&other_request_log, &21u64);
+				recorder.record_response(&request_log, &12u64);
+			}
+		})
+		.await;
+
+		assert_eq!(recording.headers::<Header<u64, u64, ()>>().unwrap(), headers);
+		assert_eq!(recording.header_at_index::<u64, u64, ()>(2).unwrap(), headers[2]);
+		assert!(recording.header_at_index::<u64, u64, ()>(3).is_err());
+		// Repeated requests are replayed with the responses in the order they were recorded, and
+		// then with the last one.
+		assert_eq!(recording.response::<u64>(&request_log).unwrap(), 11);
+		assert_eq!(recording.response::<u64>(&request_log).unwrap(), 12);
+		assert_eq!(recording.response::<u64>(&request_log).unwrap(), 12);
+		assert_eq!(recording.response::<u64>(&other_request_log).unwrap(), 21);
+		assert!(recording
+			.response::<u64>(&RequestLog::new("block_hash".to_string(), None))
+			.is_err());
+	}
+}
@@ -0,0 +1,259 @@
+use std::{marker::PhantomData, sync::Arc};
+
+use anyhow::anyhow;
+use bitcoin::{Block as BtcBlock, BlockHash, Txid};
+use cf_chains::{
+	dot::{PolkadotHash, RuntimeVersion},
+	Arbitrum, Bitcoin, Ethereum, Polkadot,
+};
+use cf_primitives::PolkadotBlockNumber;
+use ethers::{
+	prelude::*,
+	types::{Block, TransactionReceipt},
+};
+use serde::de::DeserializeOwned;
+use sp_core::H256 as SpH256;
+use subxt::{events::Events, rpc::types::ChainBlockExtrinsic, PolkadotConfig};
+
+use crate::{
+	btc::{retry_rpc::BtcRetryRpcApi, rpc::BlockHeader},
+	dot::retry_rpc::DotRetryRpcApi,
+	eth::{
+		retry_rpc::{address_checker::AddressCheckerRetryRpcApi, EthersRetryRpcApi},
+		rpc::address_checker::AddressState,
+	},
+	retrier::RequestLog,
+	witness::common::chain_source::{ChainClient, Header},
+};
+
+use super::{RecordedData, Recording};
+
+/// Answers requests for a chain with the responses in a [Recording], in place of the retry rpc
+/// client that made the recording. The request logs must match the ones used by that client.
+///
+/// Requests that were not recorded panic, as do requests that submit anything to the chain.
+#[derive(Clone)]
+pub struct ReplayClient<Chain> {
+	recording: Arc<Recording>,
+	_phantom: PhantomData<Chain>,
+}
+
+impl<Chain> ReplayClient<Chain> {
+	pub fn new(recording: Arc<Recording>) -> Self {
+		Self { recording, _phantom: PhantomData }
+	}
+
+	pub fn recording(&self) -> &Arc<Recording> {
+		&self.recording
+	}
+
+	fn response<T: DeserializeOwned>(&self, rpc_method: &str, args: Option<String>) -> T {
+		self.recording
+			.response(&RequestLog::new(rpc_method.to_string(), args))
+			.unwrap_or_else(|e| panic!("Failed to replay request: {e}"))
+	}
+
+	fn response_data<T: RecordedData>(&self, rpc_method: &str, args: Option<String>) -> T {
+		self.recording
+			.response_data(&RequestLog::new(rpc_method.to_string(), args))
+			.unwrap_or_else(|e| panic!("Failed to replay request: {e}"))
+	}
+
+	/// The recorded `header_at_index` response, or if there isn't one, the last header the chain
+	/// source produced at this index.
+	fn replay_header_at_index<Index, Hash, Data>(&self, index: Index) -> Header<Index, Hash, Data>
+	where
+		Index: serde::Serialize + DeserializeOwned + std::fmt::Display + Copy,
+		Hash: serde::Serialize + DeserializeOwned,
+		Data: RecordedData,
+	{
+		self.recording
+			.response_data(&RequestLog::new(
+				"header_at_index".to_string(),
+				Some(format!("{index}")),
+			))
+			.or_else(|_| self.recording.header_at_index(index))
+			.unwrap_or_else(|e| panic!("Failed to replay header at index {index}: {e}"))
+	}
+}
+
+/// The chains that are witnessed with the Ethereum rpc client.
+pub trait EvmChain: cf_chains::Chain<ChainBlockNumber = u64> {}
+impl EvmChain for Ethereum {}
+impl EvmChain for Arbitrum {}
+
+#[async_trait::async_trait]
+impl<C: EvmChain> EthersRetryRpcApi for ReplayClient<C> {
+	async fn broadcast_transaction(
+		&self,
+		_tx: cf_chains::evm::Transaction,
+	) -> anyhow::Result<TxHash> {
+		Err(anyhow!("Transactions can't be broadcast when replaying a recording"))
+	}
+
+	async fn get_logs(&self, block_hash: H256, contract_address: H160) -> Vec<Log> {
+		self.response("get_logs", Some(format!("{block_hash:?}, {contract_address:?}")))
+	}
+
+	async fn chain_id(&self) -> U256 {
+		self.response("chain_id", None)
+	}
+
+	async fn transaction_receipt(&self, tx_hash: H256) -> TransactionReceipt {
+		self.response("transaction_receipt", Some(format!("{tx_hash:?}")))
+	}
+
+	async fn block(&self, block_number: U64) -> Block<H256> {
+		self.response("block", Some(format!("{block_number}")))
+	}
+
+	async fn block_with_txs(&self, block_number: U64) -> Block<Transaction> {
+		self.response("block_with_txs", Some(format!("{block_number}")))
+	}
+
+	async fn block_by_tag(&self, tag: BlockNumber) -> Block<H256> {
+		self.response("block_by_tag", Some(format!("{tag:?}")))
+	}
+
+	async fn fee_history(
+		&self,
+		block_count: U256,
+		newest_block: BlockNumber,
+		reward_percentiles: Vec<f64>,
+	) -> FeeHistory {
+		self.response(
+			"fee_history",
+			Some(format!("{block_count}, {newest_block}, {reward_percentiles:?}")),
+		)
+	}
+
+	async fn get_transaction(&self, tx_hash: H256) -> Transaction {
+		self.response("get_transaction", Some(format!("{tx_hash:?}")))
+	}
+}
+
+#[async_trait::async_trait]
+impl<C: EvmChain> AddressCheckerRetryRpcApi for ReplayClient<C> {
+	async fn address_states(
+		&self,
+		block_hash: H256,
+		contract_address: H160,
+		addresses: Vec<H160>,
+	) -> Vec<AddressState> {
+		self.response(
+			"address_states",
+			Some(format!("{block_hash:?}, {contract_address:?}, {addresses:?}")),
+		)
+	}
+
+	async fn balances(
+		&self,
+		block_hash: H256,
+		contract_address: H160,
+		addresses: Vec<H160>,
+	) -> Vec<U256> {
+		self.response(
+			"balances",
+			Some(format!("{block_hash:?}, {contract_address:?}, {addresses:?}")),
+		)
+	}
+}
+
+#[async_trait::async_trait]
+impl<C: EvmChain> ChainClient for ReplayClient<C> {
+	type Index = <C as cf_chains::Chain>::ChainBlockNumber;
+	type Hash = H256;
+	type Data = Bloom;
+
+	async fn header_at_index(
+		&self,
+		index: Self::Index,
+	) -> Header<Self::Index, Self::Hash, Self::Data> {
+		self.replay_header_at_index(index)
+	}
+}
+
+#[async_trait::async_trait]
+impl BtcRetryRpcApi for ReplayClient<Bitcoin> {
+	async fn block(&self, block_hash: BlockHash) -> BtcBlock {
+		self.response("block", Some(format!("{block_hash}")))
+	}
+
+	async fn block_hash(&self, block_number: cf_chains::btc::BlockNumber) -> BlockHash {
+		self.response("block_hash", Some(format!("{block_number}")))
+	}
+
+	async fn send_raw_transaction(&self, _transaction_bytes: Vec<u8>) -> anyhow::Result<Txid> {
+		Err(anyhow!("Transactions can't be sent when replaying a recording"))
+	}
+
+	async fn next_block_fee_rate(&self) -> Option<cf_chains::btc::BtcAmount> {
+		self.response("next_block_fee_rate", None)
+	}
+
+	async fn average_block_fee_rate(&self, block_hash: BlockHash) -> cf_chains::btc::BtcAmount {
+		self.response("average_block_fee_rate", Some(format!("{block_hash}")))
+	}
+
+	async fn best_block_header(&self) -> BlockHeader {
+		self.response("best_block_header", None)
+	}
+
+	async fn block_filter(&self, block_hash: BlockHash) -> Vec<u8> {
+		self.response("block_filter", Some(format!("{block_hash}")))
+	}
+}
+
+#[async_trait::async_trait]
+impl ChainClient for ReplayClient<Bitcoin> {
+	type Index = <Bitcoin as cf_chains::Chain>::ChainBlockNumber;
+	type Hash = BlockHash;
+	type Data = ();
+
+	async fn header_at_index(
+		&self,
+		index: Self::Index,
+	) -> Header<Self::Index, Self::Hash, Self::Data> {
+		self.replay_header_at_index(index)
+	}
+}
+
+#[async_trait::async_trait]
+impl DotRetryRpcApi for ReplayClient<Polkadot> {
+	async fn block_hash(&self, block_number: PolkadotBlockNumber) -> Option<PolkadotHash> {
+		self.response("block_hash", Some(format!("{block_number}")))
+	}
+
+	async fn extrinsics(&self, block_hash: PolkadotHash) -> Vec<ChainBlockExtrinsic> {
+		self.response("extrinsics", Some(format!("{block_hash:?}")))
+	}
+
+	async fn events(&self, block_hash: PolkadotHash) -> Option<Events<PolkadotConfig>> {
+		self.response_data("events", Some(format!("{block_hash:?}")))
+	}
+
+	async fn runtime_version(&self, block_hash: Option<SpH256>) -> RuntimeVersion {
+		self.response("runtime_version", Some(format!("{block_hash:?}")))
+	}
+
+	async fn submit_raw_encoded_extrinsic(
+		&self,
+		_encoded_bytes: Vec<u8>,
+	) -> anyhow::Result<PolkadotHash> {
+		Err(anyhow!("Extrinsics can't be submitted when replaying a recording"))
+	}
+}
+
+#[async_trait::async_trait]
+impl ChainClient for ReplayClient<Polkadot> {
+	type Index = <Polkadot as cf_chains::Chain>::ChainBlockNumber;
+	type Hash = PolkadotHash;
+	type Data = Events<PolkadotConfig>;
+
+	async fn header_at_index(
+		&self,
+		index: Self::Index,
+	) -> Header<Self::Index, Self::Hash, Self::Data> {
+		self.replay_header_at_index(index)
+	}
+}
@@ -0,0 +1,570 @@
+//! Replays recordings through the witnessing pipelines, and checks they submit exactly the calls
+//! they would have submitted when the recording was made.
+
+use std::{collections::HashMap, sync::Arc};
+
+use bitcoin::{
+	absolute::{Height, LockTime},
+	block::Version,
+	hashes::Hash,
+	Block, BlockHash, CompactTarget, ScriptBuf, Transaction, TxMerkleNode, TxOut,
+};
+use cf_chains::{
+	assets::{btc, dot, eth},
+	btc::{deposit_address::DepositAddress, AggKey, BitcoinFeeInfo, BitcoinTrackedData, UtxoId},
+	dot::{
+		PolkadotAccountId, PolkadotBalance, PolkadotChannelState, PolkadotHash, PolkadotTrackedData,
+	},
+	eth::EthereumTrackedData,
+	evm::DeploymentStatus,
+	Bitcoin, Chain, ChainCrypto, ChainState, DepositChannel, Ethereum, Polkadot,
+};
+use cf_primitives::{Asset, EpochIndex, ForeignChain, PolkadotBlockNumber, GENESIS_EPOCH};
+use codec::{Decode, Encode};
+use ethers::{
+	abi::{ethereum_types::BloomInput, Token},
+	contract::EthEvent,
+	types::{Bloom, Log, H160, H256, U256},
+};
+use futures::FutureExt;
+use pallet_cf_ingress_egress::{ChannelAction, DepositChannelDetails, DepositWitness};
+use sp_runtime::AccountId32;
+use state_chain_runtime::{
+	BitcoinInstance, EthereumInstance, PalletInstanceAlias, PolkadotInstance, Runtime, RuntimeCall,
+};
+use subxt::{events::Events, PolkadotConfig};
+use tokio::sync::mpsc;
+use utilities::{task_scope::task_scope, MakeCachedStream};
+
+use crate::{
+	btc::retry_rpc::BtcRetryRpcApi,
+	retrier::RequestLog,
+	state_chain_observer::{
+		client::{
+			finalized_stream::FinalizedCachedStream, mocks::MockStateChainClient, BlockInfo,
+			StateChainStreamApi,
+		},
+		test_helpers::test_header,
+	},
+	witness::{
+		common::{
+			chain_source::{extension::ChainSourceExt, recording::ReplaySource, Header},
+			epoch_source::EpochSource,
+			RuntimeHasChain,
+		},
+		dot::filter_map_events,
+		eth::{
+			erc20_deposits::usdc::{self, UsdcEvents},
+			vault::TransferNativeFailedFilter,
+		},
+	},
+};
+
+use super::{replay_client::ReplayClient, test_recording};
+
+const VAULT_ADDRESS: H160 = H160::repeat_byte(0x01);
+const USDC_ADDRESS: H160 = H160::repeat_byte(0x02);
+const BLOCK_NUMBER: u64 = 11;
+// Every block is ready to be witnessed, as far as the chain tracking is concerned.
+const CHAIN_TRACKING_BLOCK_NUMBER: u32 = 100;
+
+/// A State Chain with a single epoch, whose vault is active from the first block, and the given
+/// deposit channels.
+fn mock_state_chain_client<C: Chain + PalletInstanceAlias>(
+	vault_key: <C::ChainCrypto as ChainCrypto>::AggKey,
+	tracked_data: C::TrackedData,
+	deposit_channels: Vec<DepositChannelDetails<Runtime, <C as PalletInstanceAlias>::Instance>>,
+) -> MockStateChainClient
+where
+	Runtime: RuntimeHasChain<C>,
+	C::ChainBlockNumber: From<u32>,
+{
+	let mut state_chain_client = MockStateChainClient::new();
+	state_chain_client
+		.expect_storage_value::<pallet_cf_validator::CurrentEpoch<Runtime>>()
+		.returning(|_| Ok(GENESIS_EPOCH));
+	state_chain_client
+		.expect_storage_map::<pallet_cf_validator::EpochExpiries<Runtime>, Vec<(u32, EpochIndex)>>()
+		.returning(|_| Ok(vec![]));
+	state_chain_client
+		.expect_storage_map_entry::<pallet_cf_vaults::Vaults<
+			Runtime,
+			<C as PalletInstanceAlias>::Instance,
+		>>()
+		.returning(move |_, _| {
+			Ok(Some(pallet_cf_vaults::Vault { public_key: vault_key.clone(), active_from_block: 0 }))
+		});
+	state_chain_client
+		.expect_storage_value::<pallet_cf_chain_tracking::CurrentChainState<
+			Runtime,
+			<C as PalletInstanceAlias>::Instance,
+		>>()
+		.returning(move |_| {
+			Ok(Some(ChainState {
+				block_height: CHAIN_TRACKING_BLOCK_NUMBER.into(),
+				tracked_data: tracked_data.clone(),
+			}))
+		});
+	state_chain_client
+		.expect_storage_map::<pallet_cf_ingress_egress::DepositChannelLookup<
+			Runtime,
+			<C as PalletInstanceAlias>::Instance,
+		>, Vec<(C::ChainAccount, DepositChannelDetails<Runtime, <C as PalletInstanceAlias>::Instance>)>>(
+		)
+		.returning(move |_| {
+			Ok(deposit_channels
+				.iter()
+				.map(|details| (details.deposit_channel.address.clone(), details.clone()))
+				.collect())
+		});
+	state_chain_client
+}
+
+/// A State Chain stream that has no new blocks.
+fn state_chain_stream() -> impl StateChainStreamApi {
+	FinalizedCachedStream::new(
+		tokio_stream::iter(std::iter::empty::<BlockInfo>())
+			.make_cached(test_header(20, None), |block| *block),
+	)
+}
+
+fn deposit_channel_details<C: Chain + PalletInstanceAlias>(
+	address: C::ChainAccount,
+	asset: C::ChainAsset,
+	state: C::DepositChannelState,
+) -> DepositChannelDetails<Runtime, <C as PalletInstanceAlias>::Instance>
+where
+	Runtime: RuntimeHasChain<C>,
+	C::ChainBlockNumber: From<u32>,
+{
+	DepositChannelDetails {
+		deposit_channel: DepositChannel { channel_id: 1, address, asset, state },
+		opened_at: 1,
+		expires_at: CHAIN_TRACKING_BLOCK_NUMBER.into(),
+		action: ChannelAction::LiquidityProvision { lp_account: AccountId32::new([0xab; 32]) },
+	}
+}
+
+/// A `process_call` for the witnessing pipelines, and the receiver of the calls it is given.
+fn call_collector() -> (
+	impl Fn(RuntimeCall, EpochIndex) -> futures::future::Ready<()> + Send + Sync + Clone + 'static,
+	mpsc::UnboundedReceiver<RuntimeCall>,
+) {
+	let (call_sender, call_receiver) = mpsc::unbounded_channel();
+	(
+		move |call, _epoch_index| {
+			call_sender.send(call).unwrap();
+			futures::future::ready(())
+		},
+		call_receiver,
+	)
+}
+
+fn encoded_calls(mut call_receiver: mpsc::UnboundedReceiver<RuntimeCall>) -> Vec<Vec<u8>> {
+	std::iter::from_fn(|| call_receiver.try_recv().ok())
+		.map(|call| call.encode())
+		.collect()
+}
+
+fn eth_header(contract_address: H160) -> Header<u64, H256, Bloom> {
+	let mut bloom = Bloom::default();
+	bloom.accrue(BloomInput::Raw(&contract_address.0));
+	Header {
+		index: BLOCK_NUMBER,
+		hash: H256::repeat_byte(0x11),
+		parent_hash: Some(H256::repeat_byte(0x10)),
+		data: bloom,
+	}
+}
+
+/// Records the header, and the logs of the contract in the block.
+async fn eth_recording(
+	header: Header<u64, H256, Bloom>,
+	contract_address: H160,
+	logs: Vec<Log>,
+) -> ReplayClient<Ethereum> {
+	let recording = test_recording(move |recorder| {
+		recorder.record_header(&header);
+		recorder.record_response(
+			&RequestLog::new(
+				"get_logs".to_string(),
+				Some(format!("{:?}, {contract_address:?}", header.hash)),
+			),
+			&logs,
+		);
+	})
+	.await;
+	ReplayClient::new(Arc::new(recording))
+}
+
+fn eth_chain_tracking() -> EthereumTrackedData {
+	EthereumTrackedData { base_fee: 1_000_000, priority_fee: 100 }
+}
+
+#[tokio::test]
+async fn vault_witnessing_replays_the_same_calls() {
+	const AMOUNT: u128 = 1_000_000;
+	let recipient = H160::repeat_byte(0xaa);
+
+	let client = eth_recording(
+		eth_header(VAULT_ADDRESS),
+		VAULT_ADDRESS,
+		vec![Log {
+			address: VAULT_ADDRESS,
+			topics: vec![TransferNativeFailedFilter::signature(), recipient.into()],
+			data: ethers::abi::encode(&[Token::Uint(AMOUNT.into())]).into(),
+			transaction_hash: Some(H256::repeat_byte(0x22)),
+			log_index: Some(U256::zero()),
+			..Default::default()
+		}],
+	)
+	.await;
+
+	let (process_call, call_receiver) = call_collector();
+	task_scope(|scope| {
+		async move {
+			let state_chain_client = Arc::new(mock_state_chain_client::<Ethereum>(
+				Default::default(),
+				eth_chain_tracking(),
+				vec![],
+			));
+			let vaults = EpochSource::builder(scope, state_chain_stream(), state_chain_client)
+				.await
+				.vaults::<Ethereum>()
+				.await;
+
+			ReplaySource::new(client.clone())
+				.chunk_by_vault_unshared(vaults)
+				.vault_witnessing(
+					process_call,
+					client,
+					VAULT_ADDRESS,
+					Asset::Eth,
+					ForeignChain::Ethereum,
+					HashMap::new(),
+				)
+				.run_active()
+				.await;
+			Ok(())
+		}
+		.boxed()
+	})
+	.await
+	.unwrap();
+
+	assert_eq!(
+		encoded_calls(call_receiver),
+		vec![RuntimeCall::from(
+			pallet_cf_ingress_egress::Call::<Runtime, EthereumInstance>::vault_transfer_failed {
+				asset: eth::Asset::Eth,
+				amount: AMOUNT,
+				destination_address: recipient,
+			}
+		)
+		.encode()]
+	);
+}
+
+#[tokio::test]
+async fn erc20_deposits_replays_the_same_calls() {
+	const AMOUNT: u128 = 2_000_000;
+	let deposit_address = H160::repeat_byte(0xdd);
+
+	let transfer_log = |to: H160, log_index: u64| Log {
+		address: USDC_ADDRESS,
+		topics: vec![usdc::TransferFilter::signature(), H160::repeat_byte(0xcc).into(), to.into()],
+		data: ethers::abi::encode(&[Token::Uint(AMOUNT.into())]).into(),
+		transaction_hash: Some(H256::repeat_byte(0x22)),
+		log_index: Some(log_index.into()),
+		..Default::default()
+	};
+	let client = eth_recording(
+		eth_header(USDC_ADDRESS),
+		USDC_ADDRESS,
+		// Only the transfer to the deposit address is witnessed.
+		vec![transfer_log(H160::repeat_byte(0xee), 0), transfer_log(deposit_address, 1)],
+	)
+	.await;
+
+	let (process_call, call_receiver) = call_collector();
+	task_scope(|scope| {
+		async move {
+			let state_chain_client = Arc::new(mock_state_chain_client::<Ethereum>(
+				Default::default(),
+				eth_chain_tracking(),
+				vec![deposit_channel_details::<Ethereum>(
+					deposit_address,
+					eth::Asset::Usdc,
+					DeploymentStatus::Deployed,
+				)],
+			));
+			let vaults =
+				EpochSource::builder(scope, state_chain_stream(), state_chain_client.clone())
+					.await
+					.vaults::<Ethereum>()
+					.await;
+
+			ReplaySource::new(client.clone())
+				.chunk_by_vault_unshared(vaults)
+				.deposit_addresses(scope, state_chain_stream(), state_chain_client)
+				.await
+				.erc20_deposits::<_, _, _, UsdcEvents>(
+					process_call,
+					client,
+					eth::Asset::Usdc,
+					USDC_ADDRESS,
+				)
+				.await?
+				.run_active()
+				.await;
+			Ok(())
+		}
+		.boxed()
+	})
+	.await
+	.unwrap();
+
+	assert_eq!(
+		encoded_calls(call_receiver),
+		vec![RuntimeCall::from(
+			pallet_cf_ingress_egress::Call::<Runtime, EthereumInstance>::process_deposits {
+				deposit_witnesses: vec![DepositWitness {
+					deposit_address,
+					asset: eth::Asset::Usdc,
+					amount: AMOUNT,
+					deposit_details: (),
+				}],
+				block_height: BLOCK_NUMBER,
+			}
+		)
+		.encode()]
+	);
+}
+
+#[tokio::test]
+async fn btc_deposits_replays_the_same_calls() {
+	const AMOUNT: u64 = 50_000;
+	let deposit_script = DepositAddress::new([0x01; 32], 1).script_pubkey();
+
+	let transaction = Transaction {
+		version: 2,
+		lock_time: LockTime::Blocks(Height::from_consensus(0).unwrap()),
+		input: vec![],
+		output: vec![
+			TxOut { value: 12_345, script_pubkey: ScriptBuf::from(vec![0, 20, 121, 9]) },
+			TxOut { value: AMOUNT, script_pubkey: ScriptBuf::from(deposit_script.bytes()) },
+		],
+	};
+	let block = Block {
+		header: bitcoin::block::Header {
+			version: Version::ONE,
+			prev_blockhash: BlockHash::all_zeros(),
+			merkle_root: TxMerkleNode::all_zeros(),
+			time: 0,
+			bits: CompactTarget::from_consensus(0),
+			nonce: 0,
+		},
+		txdata: vec![transaction.clone()],
+	};
+	let block_hash = block.block_hash();
+
+	let client = ReplayClient::<Bitcoin>::new(Arc::new(
+		test_recording(move |recorder| {
+			recorder.record_header(&Header {
+				index: BLOCK_NUMBER,
+				hash: block_hash,
+				parent_hash: Some(BlockHash::all_zeros()),
+				data: (),
+			});
+			recorder.record_response(
+				&RequestLog::new("block".to_string(), Some(format!("{block_hash}"))),
+				&block,
+			);
+		})
+		.await,
+	));
+
+	let (process_call, call_receiver) = call_collector();
+	task_scope(|scope| {
+		let deposit_script = deposit_script.clone();
+		async move {
+			let state_chain_client = Arc::new(mock_state_chain_client::<Bitcoin>(
+				AggKey { previous: None, current: [0x01; 32] },
+				BitcoinTrackedData { btc_fee_info: BitcoinFeeInfo::new(1_000) },
+				vec![deposit_channel_details::<Bitcoin>(
+					deposit_script,
+					btc::Asset::Btc,
+					DepositAddress::new([0x01; 32], 1),
+				)],
+			));
+			let vaults =
+				EpochSource::builder(scope, state_chain_stream(), state_chain_client.clone())
+					.await
+					.vaults::<Bitcoin>()
+					.await;
+
+			ReplaySource::new(client.clone())
+				.then(move |header| {
+					let client = client.clone();
+					async move { ((), client.block(header.hash).await.txdata) }
+				})
+				.chunk_by_vault_unshared(vaults)
+				.deposit_addresses(scope, state_chain_stream(), state_chain_client)
+				.await
+				.btc_deposits(process_call)
+				.run_active()
+				.await;
+			Ok(())
+		}
+		.boxed()
+	})
+	.await
+	.unwrap();
+
+	assert_eq!(
+		encoded_calls(call_receiver),
+		vec![RuntimeCall::from(
+			pallet_cf_ingress_egress::Call::<Runtime, BitcoinInstance>::process_deposits {
+				deposit_witnesses: vec![DepositWitness {
+					deposit_address: deposit_script,
+					asset: btc::Asset::Btc,
+					amount: AMOUNT,
+					deposit_details: UtxoId {
+						tx_id: transaction.txid().as_raw_hash().to_byte_array(),
+						vout: 1,
+					},
+				}],
+				block_height: BLOCK_NUMBER,
+			}
+		)
+		.encode()]
+	);
+}
+
+/// The events of a Polkadot block, as the rpc returns them, with a balance transfer made by the
+/// extrinsic at the given index.
+fn dot_transfer_events(
+	block_hash: PolkadotHash,
+	extrinsic_index: u32,
+	from: PolkadotAccountId,
+	to: PolkadotAccountId,
+	amount: PolkadotBalance,
+) -> Events<PolkadotConfig> {
+	// The indices of the Balances pallet and its Transfer event in the Polkadot metadata.
+	const BALANCES_PALLET_INDEX: u8 = 5;
+	const TRANSFER_EVENT_INDEX: u8 = 2;
+	// The index of `Phase::ApplyExtrinsic`.
+	const APPLY_EXTRINSIC_PHASE: u8 = 0;
+
+	let event_records = vec![(
+		APPLY_EXTRINSIC_PHASE,
+		extrinsic_index,
+		BALANCES_PALLET_INDEX,
+		TRANSFER_EVENT_INDEX,
+		from,
+		to,
+		amount,
+		// The topics of the event.
+		Vec::<PolkadotHash>::new(),
+	)];
+
+	let metadata =
+		subxt::Metadata::decode(&mut &include_bytes!("../../metadata.polkadot.scale")[..]).unwrap();
+	Events::new(metadata, block_hash, event_records.encode())
+}
+
+#[tokio::test]
+async fn dot_deposits_replays_the_same_calls() {
+	const AMOUNT: PolkadotBalance = 3_000_000;
+	const DOT_BLOCK_NUMBER: PolkadotBlockNumber = BLOCK_NUMBER as PolkadotBlockNumber;
+	let deposit_address = PolkadotAccountId::from_aliased([0xdd; 32]);
+	let block_hash = PolkadotHash::repeat_byte(0x11);
+
+	let client = ReplayClient::<Polkadot>::new(Arc::new(
+		test_recording(move |recorder| {
+			recorder.record_header(&Header {
+				index: DOT_BLOCK_NUMBER,
+				hash: block_hash,
+				parent_hash: Some(PolkadotHash::repeat_byte(0x10)),
+				data: dot_transfer_events(
+					block_hash,
+					1,
+					PolkadotAccountId::from_aliased([0xcc; 32]),
+					deposit_address,
+					AMOUNT,
+				),
+			});
+		})
+		.await,
+	));
+
+	let (process_call, call_receiver) = call_collector();
+	task_scope(|scope| {
+		async move {
+			let mut state_chain_client = mock_state_chain_client::<Polkadot>(
+				PolkadotAccountId::from_aliased([0x01; 32]),
+				PolkadotTrackedData::default(),
+				vec![deposit_channel_details::<Polkadot>(
+					deposit_address,
+					dot::Asset::Dot,
+					PolkadotChannelState,
+				)],
+			);
+			state_chain_client
+				.expect_storage_value::<pallet_cf_environment::PolkadotVaultAccountId<Runtime>>()
+				.returning(|_| Ok(Some(PolkadotAccountId::from_aliased([0x02; 32]))));
+			let state_chain_client = Arc::new(state_chain_client);
+
+			let vaults =
+				EpochSource::builder(scope, state_chain_stream(), state_chain_client.clone())
+					.await
+					.filter_map(
+						|state_chain_client, _epoch_index, hash, _info| async move {
+							state_chain_client
+								.storage_value::<pallet_cf_environment::PolkadotVaultAccountId<Runtime>>(
+									hash,
+								)
+								.await
+								.unwrap()
+						},
+						|_state_chain_client, _epoch, _block_hash, historic_info| async move {
+							historic_info
+						},
+					)
+					.await
+					.vaults::<Polkadot>()
+					.await;
+
+			ReplaySource::new(client)
+				.then(|header| async move {
+					header.data.iter().filter_map(filter_map_events).collect::<Vec<_>>()
+				})
+				.chunk_by_vault_unshared(vaults)
+				.deposit_addresses(scope, state_chain_stream(), state_chain_client)
+				.await
+				.dot_deposits(process_call)
+				.run_active()
+				.await;
+			Ok(())
+		}
+		.boxed()
+	})
+	.await
+	.unwrap();
+
+	assert_eq!(
+		encoded_calls(call_receiver),
+		vec![RuntimeCall::from(
+			pallet_cf_ingress_egress::Call::<Runtime, PolkadotInstance>::process_deposits {
+				deposit_witnesses: vec![DepositWitness {
+					deposit_address,
+					asset: dot::Asset::Dot,
+					amount: AMOUNT,
+					deposit_details: (),
+				}],
+				block_height: DOT_BLOCK_NUMBER,
+			}
+		)
+		.encode()]
+	);
+}
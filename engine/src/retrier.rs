@@ -4,25 +4,40 @@
 //! that may fail due to network issues or other transient errors.
 //! On each request it applies a timeout, such that requests cannot hang.
 //! It applies exponential backoff and jitter to the requests if they fail, and will retry them
-//! until they succeed.
+//! until they succeed. Requests to each endpoint are kept within its configured rate limits, and
+//! are paused while the endpoint rate limits us. Requests are made to the other endpoint while one
+//! is unavailable.
+
+pub mod rate_limit;
 
 use std::{
 	any::Any,
 	collections::{BTreeMap, VecDeque},
 	pin::Pin,
+	sync::Arc,
 	time::Duration,
 };
 
-use crate::common::Signal;
+use crate::{
+	common::Signal,
+	health,
+	recording::{RecordedData, Recorder},
+	settings::RateLimit,
+};
 use anyhow::Result;
 use core::cmp::min;
 use futures::Future;
 use futures_util::stream::FuturesUnordered;
 use rand::Rng;
+use rate_limit::{EndpointLimiter, EndpointUnavailable, RateLimited};
+use serde::Serialize;
 use std::fmt;
 use tokio::sync::{mpsc, oneshot};
 use utilities::{
-	metrics::{RPC_RETRIER_REQUESTS, RPC_RETRIER_TOTAL_REQUESTS},
+	metrics::{
+		RPC_RETRIER_CROSS_CHECK_FAILURES, RPC_RETRIER_DISAGREEMENTS, RPC_RETRIER_RATE_LIMITED,
+		RPC_RETRIER_REQUESTS, RPC_RETRIER_TOTAL_REQUESTS,
+	},
 	task_scope::Scope,
 	UnendingStream,
 };
@@ -124,6 +139,34 @@ type RequestSent<Client> =
 pub struct RetrierClient<Client> {
 	// The channel to send requests to the client.
 	request_sender: mpsc::Sender<RequestSent<Client>>,
+
+	// If set, responses to recorded requests are written to a recording file.
+	recorder: Option<Recorder>,
+
+	// The name of the retrier that appears in the logs.
+	name: &'static str,
+
+	// Only exists if there's a secondary client.
+	cross_check_clients: Option<Arc<CrossCheckClients<Client>>>,
+
+	// Only exists if cross-checking is enabled, in which case cross-checked requests are made to
+	// both the primary and the secondary.
+	cross_check_retriers: Option<Arc<CrossCheckRetriers<Client>>>,
+}
+
+// What's needed to create the cross-check retriers once cross-checking is enabled.
+struct CrossCheckClients<Client> {
+	primary_signal: Signal<(Client, PrimaryOrSecondary)>,
+	secondary_signal: Signal<(Client, PrimaryOrSecondary)>,
+	limiters: EndpointLimiters,
+	initial_request_timeout: Duration,
+	maximum_concurrent_submissions: u32,
+}
+
+// Retriers that each only use one of the clients, so the same request can be made to both.
+struct CrossCheckRetriers<Client> {
+	primary: RetrierClient<Client>,
+	secondary: RetrierClient<Client>,
 }
 
 #[derive(Default)]
@@ -193,10 +236,43 @@ impl SubmissionHolder {
 
 const MAX_DELAY_TIME_MILLIS: Duration = Duration::from_secs(10 * 60);
 
+// How long to wait before making a cross-checked request again after the responses disagreed.
+const CROSS_CHECK_RETRY_DELAY: Duration = Duration::from_secs(6);
+
+// How many times the responses to a cross-checked request can disagree before the failure is
+// counted in the metrics so it can be alerted on.
+const MAX_CROSS_CHECK_DISAGREEMENTS: u32 = 10;
+
 fn max_sleep_duration(initial_request_timeout: Duration, attempt: u32) -> Duration {
 	min(MAX_DELAY_TIME_MILLIS, initial_request_timeout.saturating_mul(2u32.saturating_pow(attempt)))
 }
 
+// The limiters of the endpoints of a retrier's clients.
+#[derive(Clone)]
+struct EndpointLimiters {
+	primary: Arc<EndpointLimiter>,
+	secondary: Arc<EndpointLimiter>,
+}
+
+impl EndpointLimiters {
+	fn get(&self, primary_or_secondary: &PrimaryOrSecondary) -> Arc<EndpointLimiter> {
+		match primary_or_secondary {
+			PrimaryOrSecondary::Primary => self.primary.clone(),
+			PrimaryOrSecondary::Secondary => self.secondary.clone(),
+		}
+	}
+}
+
+// Finds out if the request failed because the endpoint rate limited it.
+fn rate_limited(error: &anyhow::Error) -> Option<&RateLimited> {
+	error.chain().find_map(|error| error.downcast_ref::<RateLimited>())
+}
+
+// Finds out if the request wasn't made because the endpoint's limits don't allow it for a while.
+fn endpoint_unavailable(error: &anyhow::Error) -> Option<&EndpointUnavailable> {
+	error.downcast_ref::<EndpointUnavailable>()
+}
+
 // Creates a future of a particular submission.
 fn submission_future<Client: Clone + Send + Sync + 'static>(
 	client: Client,
@@ -207,26 +283,35 @@ fn submission_future<Client: Clone + Send + Sync + 'static>(
 	initial_request_timeout: Duration,
 	attempt: Attempt,
 	primary_or_secondary: PrimaryOrSecondary,
+	limiter: Arc<EndpointLimiter>,
 ) -> SubmissionFuture {
 	let submission_fut = submission_fn(client);
 	// Apply exponential backoff to the request.
 	Box::pin(async move {
-		(
-			request_id,
-			request_log.clone(),
-			retry_limit,
-			primary_or_secondary,
-			match tokio::time::timeout(
+		// Waiting for the endpoint's limits doesn't count towards the request's timeout.
+		let result = match limiter.acquire().await {
+			Ok(()) => match tokio::time::timeout(
 				max_sleep_duration(initial_request_timeout, attempt),
 				submission_fut,
 			)
 			.await
 			{
-				Ok(Ok(t)) => Ok(t),
+				Ok(Ok(t)) => {
+					let [retrier_name, endpoint] = limiter.labels();
+					health::record_rpc_success(retrier_name, endpoint);
+					Ok(t)
+				},
 				Ok(Err(e)) => Err(e),
 				Err(_) => Err(anyhow::anyhow!("Request timed out")),
-			}
-			.map_err(|e| (e, attempt)),
+			},
+			Err(e) => Err(e.into()),
+		};
+		(
+			request_id,
+			request_log.clone(),
+			retry_limit,
+			primary_or_secondary,
+			result.map_err(|e| (e, attempt)),
 		)
 	})
 }
@@ -320,6 +405,53 @@ where
 		secondary_client_fut: Option<ClientFut>,
 		initial_request_timeout: Duration,
 		maximum_concurrent_submissions: u32,
+	) -> Self {
+		Self::new_with_rate_limits(
+			scope,
+			name,
+			primary_client_fut,
+			secondary_client_fut,
+			initial_request_timeout,
+			maximum_concurrent_submissions,
+			None,
+			None,
+		)
+	}
+
+	/// As [`Self::new`], keeping the requests made to each client's endpoint within its rate
+	/// limits.
+	pub fn new_with_rate_limits<ClientFut: Future<Output = Client> + Send + 'static>(
+		scope: &Scope<'_, anyhow::Error>,
+		name: &'static str,
+		primary_client_fut: ClientFut,
+		secondary_client_fut: Option<ClientFut>,
+		initial_request_timeout: Duration,
+		maximum_concurrent_submissions: u32,
+		primary_rate_limit: Option<RateLimit>,
+		secondary_rate_limit: Option<RateLimit>,
+	) -> Self {
+		Self::new_with_limiters(
+			scope,
+			name,
+			primary_client_fut,
+			secondary_client_fut,
+			initial_request_timeout,
+			maximum_concurrent_submissions,
+			EndpointLimiters {
+				primary: Arc::new(EndpointLimiter::new(name, "primary", primary_rate_limit)),
+				secondary: Arc::new(EndpointLimiter::new(name, "secondary", secondary_rate_limit)),
+			},
+		)
+	}
+
+	fn new_with_limiters<ClientFut: Future<Output = Client> + Send + 'static>(
+		scope: &Scope<'_, anyhow::Error>,
+		name: &'static str,
+		primary_client_fut: ClientFut,
+		secondary_client_fut: Option<ClientFut>,
+		initial_request_timeout: Duration,
+		maximum_concurrent_submissions: u32,
+		limiters: EndpointLimiters,
 	) -> Self {
 		let (request_sender, mut request_receiver) = mpsc::channel::<RequestSent<Client>>(1);
 
@@ -332,6 +464,21 @@ where
 
 		let client_selector: ClientSelector<Client> =
 			ClientSelector::new(scope, primary_client_fut, secondary_client_fut);
+		let has_secondary = client_selector.secondary_signal.is_some();
+
+		let cross_check_clients =
+			client_selector.secondary_signal.clone().map(|secondary_signal| {
+				Arc::new(CrossCheckClients {
+					primary_signal: client_selector.primary_signal.clone(),
+					secondary_signal,
+					limiters: EndpointLimiters {
+						primary: limiters.primary.clone(),
+						secondary: limiters.secondary.clone(),
+					},
+					initial_request_timeout,
+					maximum_concurrent_submissions,
+				})
+			});
 
 		scope.spawn(async move {
 			utilities::loop_select! {
@@ -340,7 +487,8 @@ where
 					let (client, primary_or_secondary) = client_selector.select_client(PrimaryOrSecondary::Primary).await;
 
 					tracing::debug!("Retrier {name}: Received request `{request_log}` assigning request_id `{request_id}` and requesting with `{primary_or_secondary:?}`");
-					submission_holder.push(submission_future(client, request_log, retry_limit, &closure, request_id, initial_request_timeout, 0, primary_or_secondary));
+					let limiter = limiters.get(&primary_or_secondary);
+					submission_holder.push(submission_future(client, request_log, retry_limit, &closure, request_id, initial_request_timeout, 0, primary_or_secondary, limiter));
 					request_holder.insert(request_id, (response_sender, closure));
 				},
 				let (request_id, request_log, retry_limit, primary_or_secondary, result) = submission_holder.next_or_pending() => {
@@ -357,7 +505,24 @@ where
 							// We avoid small delays by always having a time of at least half.
 							let half_max = max_sleep_duration(initial_request_timeout, attempt) / 2;
 							let sleep_duration = half_max + rand::thread_rng().gen_range(Duration::default()..half_max);
-							tracing::error!("Retrier {name}: Error for request `{request_log}` with id `{request_id}`, attempt `{attempt}`: {e}. Delaying for {}ms", sleep_duration.as_millis());
+
+							let sleep_duration = if let Some(EndpointUnavailable { available_in }) = endpoint_unavailable(&e) {
+								// The request is retried with the other client, as soon as its endpoint is available.
+								let retry_in = if has_secondary { limiters.get(&!primary_or_secondary).available_in() } else { *available_in };
+								tracing::warn!("Retrier {name}: Request `{request_log}` with id `{request_id}` was not made to the `{primary_or_secondary:?}` endpoint: {e}. Retrying in {}ms", retry_in.as_millis());
+								retry_in
+							} else if let Some(RateLimited { retry_after }) = rate_limited(&e) {
+								// Requests to the endpoint are paused instead, so the request is retried straight away if
+								// there's another client, and otherwise once the pause is over.
+								let pause_duration = retry_after.unwrap_or(sleep_duration);
+								RPC_RETRIER_RATE_LIMITED.inc(&[name, request_log.rpc_method.as_str()]);
+								tracing::warn!("Retrier {name}: Request `{request_log}` with id `{request_id}` was rate limited by the `{primary_or_secondary:?}` endpoint. Pausing requests to it for {}ms", pause_duration.as_millis());
+								limiters.get(&primary_or_secondary).pause(pause_duration);
+								Duration::default()
+							} else {
+								tracing::error!("Retrier {name}: Error for request `{request_log}` with id `{request_id}`, attempt `{attempt}`: {e}. Delaying for {}ms", sleep_duration.as_millis());
+								sleep_duration
+							};
 
 							// Delay the request before the next retry.
 							retry_delays.push(Box::pin(
@@ -389,7 +554,8 @@ where
 								// This await should always return immediately since we must already have a client if we've already made a request.
 								let (next_client, next_primary_or_secondary) = client_selector.select_client(!primary_or_secondary).await;
 								tracing::trace!("Retrier {name}: Retrying request `{request_log}` with id `{request_id}` and client `{next_primary_or_secondary:?}`, attempt `{next_attempt}`");
-								submission_holder.push(submission_future(next_client, request_log, retry_limit, closure, request_id, initial_request_timeout, next_attempt, next_primary_or_secondary));
+								let limiter = limiters.get(&next_primary_or_secondary);
+								submission_holder.push(submission_future(next_client, request_log, retry_limit, closure, request_id, initial_request_timeout, next_attempt, next_primary_or_secondary, limiter));
 							}
 						}
 					}
@@ -398,7 +564,58 @@ where
 			Ok(())
 		});
 
-		Self { request_sender }
+		Self {
+			request_sender,
+			recorder: None,
+			name,
+			cross_check_clients,
+			cross_check_retriers: None,
+		}
+	}
+
+	/// Records the responses to requests made with [`Self::request_recorded`].
+	pub fn with_recorder(mut self, recorder: Recorder) -> Self {
+		self.recorder = Some(recorder);
+		self
+	}
+
+	pub fn recorder(&self) -> Option<&Recorder> {
+		self.recorder.as_ref()
+	}
+
+	/// Makes requests made with [`Self::request_cross_checked`] to both the primary and the
+	/// secondary client, using a retrier for each of them. Has no effect if there is no secondary.
+	pub fn with_cross_checking(mut self, scope: &Scope<'_, anyhow::Error>) -> Self {
+		let name = self.name;
+		self.cross_check_retriers = self.cross_check_clients.as_ref().map(|clients| {
+			// These make requests to the same endpoints, so they share their limiters.
+			let single_client_retrier =
+				|signal: Signal<(Client, PrimaryOrSecondary)>, limiter: Arc<EndpointLimiter>| {
+					// Boxed so the retriers don't each instantiate `new` with a new future type.
+					let client_fut: Pin<Box<dyn Future<Output = Client> + Send>> =
+						Box::pin(async move { signal.wait().await.0 });
+					RetrierClient::new_with_limiters(
+						scope,
+						name,
+						client_fut,
+						None,
+						clients.initial_request_timeout,
+						clients.maximum_concurrent_submissions,
+						EndpointLimiters { primary: limiter.clone(), secondary: limiter },
+					)
+				};
+			Arc::new(CrossCheckRetriers {
+				primary: single_client_retrier(
+					clients.primary_signal.clone(),
+					clients.limiters.primary.clone(),
+				),
+				secondary: single_client_retrier(
+					clients.secondary_signal.clone(),
+					clients.limiters.secondary.clone(),
+				),
+			})
+		});
+		self
 	}
 
 	// Separate function so we can more easily test.
@@ -433,6 +650,87 @@ where
 		*result.downcast::<T>().expect("We know we cast the T into an any, and it is a T that we are receiving. Hitting this is a programmer error.")
 	}
 
+	/// As [`Self::request`], and also records the response if the client has a recorder, so the
+	/// request can be replayed later.
+	pub async fn request_recorded<T: Serialize + Send + 'static>(
+		&self,
+		specific_closure: TypedFutureGenerator<T, Client>,
+		request_log: RequestLog,
+	) -> T {
+		let response = self.request(specific_closure, request_log.clone()).await;
+		if let Some(recorder) = &self.recorder {
+			recorder.record_response(&request_log, &response);
+		}
+		response
+	}
+
+	/// As [`Self::request_recorded`], for responses that aren't serializable themselves.
+	pub async fn request_recorded_data<T: RecordedData + Send + 'static>(
+		&self,
+		specific_closure: TypedFutureGenerator<T, Client>,
+		request_log: RequestLog,
+	) -> T {
+		let response = self.request(specific_closure, request_log.clone()).await;
+		if let Some(recorder) = &self.recorder {
+			recorder.record_response_data(&request_log, &response);
+		}
+		response
+	}
+
+	/// As [`Self::request_recorded`], for requests whose responses we witness from. If
+	/// cross-checking is enabled, the request is made to both the primary and the secondary, and is
+	/// only returned once both return the same response. Until then, the disagreement is counted in
+	/// the metrics and the request is made again. If the responses still disagree after
+	/// [`MAX_CROSS_CHECK_DISAGREEMENTS`] attempts, the failure is counted in the metrics so it can
+	/// be alerted on. The request is still retried until the responses agree, so disputed data is
+	/// never witnessed, which halts the pipeline making the request until the rpcs are fixed.
+	pub async fn request_cross_checked<T: PartialEq + Serialize + Send + 'static>(
+		&self,
+		specific_closure: TypedFutureGenerator<T, Client>,
+		request_log: RequestLog,
+	) -> T {
+		let response = match &self.cross_check_retriers {
+			Some(retriers) => {
+				let specific_closure = Arc::new(specific_closure);
+				let shared_closure = || -> TypedFutureGenerator<T, Client> {
+					let specific_closure = specific_closure.clone();
+					Box::pin(move |client| specific_closure(client))
+				};
+				let mut disagreements = 0;
+				loop {
+					let (primary_response, secondary_response) = futures::join!(
+						retriers.primary.request(shared_closure(), request_log.clone()),
+						retriers.secondary.request(shared_closure(), request_log.clone()),
+					);
+					if primary_response == secondary_response {
+						break primary_response
+					}
+					RPC_RETRIER_DISAGREEMENTS.inc(&[self.name, request_log.rpc_method.as_str()]);
+					disagreements += 1;
+					if disagreements == MAX_CROSS_CHECK_DISAGREEMENTS {
+						RPC_RETRIER_CROSS_CHECK_FAILURES
+							.inc(&[self.name, request_log.rpc_method.as_str()]);
+						tracing::error!(
+							"Retrier {}: The primary and secondary rpcs returned different responses to `{request_log}` {disagreements} times. Witnessing is halted until they agree",
+							self.name,
+						);
+					}
+					tracing::error!(
+						"Retrier {}: The primary and secondary rpcs returned different responses to `{request_log}`. Retrying in {}s",
+						self.name,
+						CROSS_CHECK_RETRY_DELAY.as_secs()
+					);
+					tokio::time::sleep(CROSS_CHECK_RETRY_DELAY).await;
+				}
+			},
+			_ => self.request(specific_closure, request_log.clone()).await,
+		};
+		if let Some(recorder) = &self.recorder {
+			recorder.record_response(&request_log, &response);
+		}
+		response
+	}
+
 	/// Requests something to be retried by the retry client, with an explicit retry limit.
 	/// Returns an error if the retry limit is reached.
 	pub async fn request_with_limit<T: Send + 'static>(
@@ -816,6 +1114,87 @@ mod tests {
 		.unwrap();
 	}
 
+	#[tokio::test]
+	async fn cross_checked_requests_only_return_once_both_clients_agree() {
+		task_scope(|scope| {
+			async move {
+				const INITIAL_TIMEOUT: Duration = Duration::from_millis(100);
+
+				let retrier_client = RetrierClient::new(
+					scope,
+					"test",
+					futures::future::ready(1u32),
+					Some(futures::future::ready(2u32)),
+					INITIAL_TIMEOUT,
+					100,
+				)
+				.with_cross_checking(scope);
+
+				const REQUEST_1: u32 = 32;
+				assert_eq!(
+					REQUEST_1,
+					retrier_client
+						.request_cross_checked(
+							specific_fut_closure(REQUEST_1, INITIAL_TIMEOUT),
+							RequestLog::new("request 1".to_string(), None),
+						)
+						.await
+				);
+
+				// Each client returns its own value, so the responses never agree.
+				assert!(timeout(
+					INITIAL_TIMEOUT * 10,
+					retrier_client.request_cross_checked(
+						Box::pin(|client: u32| Box::pin(async move { Ok(client) })),
+						RequestLog::new("request 2".to_string(), None),
+					)
+				)
+				.await
+				.is_err());
+
+				Ok(())
+			}
+			.boxed()
+		})
+		.await
+		.unwrap();
+	}
+
+	#[tokio::test(start_paused = true)]
+	async fn cross_checked_requests_never_return_if_the_clients_keep_disagreeing() {
+		task_scope(|scope| {
+			async move {
+				const INITIAL_TIMEOUT: Duration = Duration::from_millis(100);
+
+				let retrier_client = RetrierClient::new(
+					scope,
+					"test",
+					futures::future::ready(1u32),
+					Some(futures::future::ready(2u32)),
+					INITIAL_TIMEOUT,
+					100,
+				)
+				.with_cross_checking(scope);
+
+				// Long enough for the failure to be counted, but no response is ever returned.
+				assert!(timeout(
+					CROSS_CHECK_RETRY_DELAY * (MAX_CROSS_CHECK_DISAGREEMENTS + 10),
+					retrier_client.request_cross_checked(
+						Box::pin(|client: u32| Box::pin(async move { Ok(client) })),
+						RequestLog::new("request".to_string(), None),
+					)
+				)
+				.await
+				.is_err());
+
+				Ok(())
+			}
+			.boxed()
+		})
+		.await
+		.unwrap();
+	}
+
 	#[tokio::test]
 	#[ignore = "Test runs forever. Useful for manually testing the failing requests will never return (because they are retried until success)."]
 	async fn request_always_fails() {
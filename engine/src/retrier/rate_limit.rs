@@ -0,0 +1,363 @@
+//! Client side limits on the requests made to an rpc endpoint.
+//!
+//! Metered node providers reject requests made faster than the plan allows, and stop serving
+//! requests once the plan's daily quota is spent. Each endpoint can be configured with a rate
+//! limit, which is enforced with a token bucket, and a daily budget, which is counted per UTC day.
+//! Requests to an endpoint are paused entirely while it asks us to back off. Requests only wait
+//! briefly for an endpoint's limits, and fail if it is unavailable for longer, so the retrier can
+//! make them to the other endpoint instead.
+
+use std::{
+	sync::Mutex,
+	time::{Duration, SystemTime},
+};
+
+use thiserror::Error;
+use tokio::time::Instant;
+use utilities::metrics::{RPC_BUDGET_REMAINING, RPC_THROTTLED_REQUESTS};
+
+use crate::settings::RateLimit;
+
+const SECONDS_PER_DAY: u64 = 24 * 60 * 60;
+
+// How long a request waits for the endpoint's limits, before failing because the endpoint is
+// unavailable, e.g. because its budget is spent or it asked us to back off.
+const MAX_ACQUIRE_WAIT: Duration = Duration::from_secs(5);
+
+/// Returned by rpc clients when the endpoint rejected a request because of its rate limit. The
+/// retrier then pauses all requests to the endpoint, for `retry_after` if the endpoint says how
+/// long to wait.
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+#[error("Rate limited by the rpc endpoint")]
+pub struct RateLimited {
+	pub retry_after: Option<Duration>,
+}
+
+/// Returned by [`EndpointLimiter::acquire`] when the endpoint's limits don't allow requests to it
+/// for a while.
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+#[error("The rpc endpoint's limits don't allow requests for {}s", available_in.as_secs())]
+pub struct EndpointUnavailable {
+	pub available_in: Duration,
+}
+
+/// Parses the value of a `Retry-After` header. Only the delay in seconds form is supported, as
+/// node providers don't use the date form.
+pub fn parse_retry_after(value: &str) -> Option<Duration> {
+	value.trim().parse::<u64>().ok().map(Duration::from_secs)
+}
+
+/// Holds up to `capacity` tokens, and is refilled with `refill_per_second` tokens every second.
+#[derive(Debug)]
+struct TokenBucket {
+	capacity: f64,
+	refill_per_second: f64,
+	tokens: f64,
+	last_refill: Instant,
+}
+
+impl TokenBucket {
+	/// The bucket starts full.
+	fn new(capacity: f64, refill_per_second: f64, now: Instant) -> Self {
+		Self { capacity, refill_per_second, tokens: capacity, last_refill: now }
+	}
+
+	fn refill(&mut self, now: Instant) {
+		let elapsed = now.saturating_duration_since(self.last_refill).as_secs_f64();
+		self.tokens = (self.tokens + elapsed * self.refill_per_second).min(self.capacity);
+		self.last_refill = now;
+	}
+
+	/// How long until a token can be taken.
+	fn time_until_available(&self) -> Duration {
+		if self.tokens >= 1.0 {
+			Duration::ZERO
+		} else {
+			Duration::from_secs_f64((1.0 - self.tokens) / self.refill_per_second)
+		}
+	}
+
+	fn take(&mut self) {
+		self.tokens -= 1.0;
+	}
+}
+
+/// The number of days since the unix epoch, in UTC, and how long until the next day starts.
+fn utc_day(now: SystemTime) -> (u64, Duration) {
+	let since_epoch = now.duration_since(SystemTime::UNIX_EPOCH).unwrap_or_default();
+	let day = since_epoch.as_secs() / SECONDS_PER_DAY;
+	(day, Duration::from_secs((day + 1) * SECONDS_PER_DAY).saturating_sub(since_epoch))
+}
+
+/// Counts the requests made each day. Node providers reset their daily quotas at midnight UTC, so
+/// the budget is reset then too.
+#[derive(Debug)]
+struct DailyBudget {
+	budget: u64,
+	day: u64,
+	spent: u64,
+}
+
+impl DailyBudget {
+	fn new(budget: u64, now: SystemTime) -> Self {
+		Self { budget, day: utc_day(now).0, spent: 0 }
+	}
+
+	fn remaining(&self) -> u64 {
+		self.budget.saturating_sub(self.spent)
+	}
+
+	/// How long until a request can be made. Resets the budget if a new day has started.
+	fn time_until_available(&mut self, now: SystemTime) -> Duration {
+		let (day, until_next_day) = utc_day(now);
+		if day != self.day {
+			self.day = day;
+			self.spent = 0;
+		}
+		if self.remaining() > 0 {
+			Duration::ZERO
+		} else {
+			until_next_day
+		}
+	}
+
+	fn take(&mut self) {
+		self.spent += 1;
+	}
+}
+
+#[derive(Debug)]
+struct LimiterState {
+	rate: Option<TokenBucket>,
+	daily_budget: Option<DailyBudget>,
+	paused_until: Option<Instant>,
+}
+
+impl LimiterState {
+	/// How long until a request can be made. `utc_now` is only used for the daily budget.
+	fn time_until_available(&mut self, now: Instant, utc_now: SystemTime) -> Duration {
+		let paused_for = self
+			.paused_until
+			.map_or(Duration::ZERO, |paused_until| paused_until.saturating_duration_since(now));
+		let rate_wait = self.rate.as_mut().map_or(Duration::ZERO, |rate| {
+			rate.refill(now);
+			rate.time_until_available()
+		});
+		let budget_wait = self
+			.daily_budget
+			.as_mut()
+			.map_or(Duration::ZERO, |daily_budget| daily_budget.time_until_available(utc_now));
+
+		paused_for.max(rate_wait).max(budget_wait)
+	}
+
+	/// Counts a request against the limits if it can be made now, otherwise returns how long to
+	/// wait before trying again.
+	fn try_acquire(&mut self, now: Instant, utc_now: SystemTime) -> Result<(), Duration> {
+		let wait = self.time_until_available(now, utc_now);
+		if wait.is_zero() {
+			self.rate.iter_mut().for_each(TokenBucket::take);
+			self.daily_budget.iter_mut().for_each(DailyBudget::take);
+			Ok(())
+		} else {
+			Err(wait)
+		}
+	}
+}
+
+/// Limits the requests made to one endpoint. Shared by every retrier that makes requests to it.
+#[derive(Debug)]
+pub struct EndpointLimiter {
+	// The name of the retrier and which of its endpoints this is, used as the metric labels.
+	labels: [&'static str; 2],
+	state: Mutex<LimiterState>,
+}
+
+impl EndpointLimiter {
+	pub fn new(
+		retrier_name: &'static str,
+		endpoint: &'static str,
+		rate_limit: Option<RateLimit>,
+	) -> Self {
+		let now = Instant::now();
+		let rate = rate_limit.and_then(|rate_limit| {
+			rate_limit.requests_per_second.map(|requests_per_second| {
+				TokenBucket::new(
+					rate_limit.burst.unwrap_or(requests_per_second).get() as f64,
+					requests_per_second.get() as f64,
+					now,
+				)
+			})
+		});
+		let daily_budget = rate_limit
+			.and_then(|rate_limit| rate_limit.daily_budget)
+			.map(|budget| DailyBudget::new(budget.get(), SystemTime::now()));
+		let labels = [retrier_name, endpoint];
+		if let Some(daily_budget) = &daily_budget {
+			RPC_BUDGET_REMAINING.set(&labels, daily_budget.remaining() as i64);
+		}
+
+		Self { labels, state: Mutex::new(LimiterState { rate, daily_budget, paused_until: None }) }
+	}
+
+	/// The name of the retrier and which of its endpoints this limits.
+	pub fn labels(&self) -> [&'static str; 2] {
+		self.labels
+	}
+
+	/// Waits until a request can be made to the endpoint without exceeding its limits, and counts
+	/// the request against them. Fails instead if that would take longer than
+	/// [`MAX_ACQUIRE_WAIT`], so the request can be made to another endpoint.
+	pub async fn acquire(&self) -> Result<(), EndpointUnavailable> {
+		let mut throttled = false;
+		loop {
+			let result = {
+				let mut state = self.state.lock().unwrap();
+				let result = state.try_acquire(Instant::now(), SystemTime::now());
+				if let Some(daily_budget) = &state.daily_budget {
+					RPC_BUDGET_REMAINING.set(&self.labels, daily_budget.remaining() as i64);
+				}
+				result
+			};
+			match result {
+				Ok(()) => return Ok(()),
+				Err(wait) => {
+					if !throttled {
+						throttled = true;
+						RPC_THROTTLED_REQUESTS.inc(&self.labels);
+					}
+					if wait > MAX_ACQUIRE_WAIT {
+						return Err(EndpointUnavailable { available_in: wait })
+					}
+					tokio::time::sleep(wait).await;
+				},
+			}
+		}
+	}
+
+	/// How long until the endpoint's limits allow a request to it.
+	pub fn available_in(&self) -> Duration {
+		self.state
+			.lock()
+			.unwrap()
+			.time_until_available(Instant::now(), SystemTime::now())
+	}
+
+	/// Stops any requests being made to the endpoint for `duration`.
+	pub fn pause(&self, duration: Duration) {
+		let mut state = self.state.lock().unwrap();
+		let paused_until = Instant::now() + duration;
+		state.paused_until = Some(
+			state
+				.paused_until
+				.map_or(paused_until, |current| std::cmp::max(current, paused_until)),
+		);
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use std::num::{NonZeroU32, NonZeroU64};
+
+	use super::*;
+
+	#[test]
+	fn parses_the_retry_after_header() {
+		assert_eq!(parse_retry_after("120"), Some(Duration::from_secs(120)));
+		assert_eq!(parse_retry_after(" 3 "), Some(Duration::from_secs(3)));
+		assert_eq!(parse_retry_after("Wed, 21 Oct 2015 07:28:00 GMT"), None);
+	}
+
+	#[test]
+	fn token_bucket_refills_up_to_its_capacity() {
+		let start = Instant::now();
+		let mut bucket = TokenBucket::new(2.0, 4.0, start);
+
+		bucket.take();
+		bucket.take();
+		assert_eq!(bucket.time_until_available(), Duration::from_millis(250));
+
+		bucket.refill(start + Duration::from_millis(250));
+		assert_eq!(bucket.time_until_available(), Duration::ZERO);
+
+		bucket.refill(start + Duration::from_secs(10));
+		assert_eq!(bucket.tokens, 2.0);
+	}
+
+	#[test]
+	fn requests_are_limited_by_the_rate_and_the_daily_budget() {
+		let start = Instant::now();
+		// An hour into a day.
+		let utc_start = SystemTime::UNIX_EPOCH + Duration::from_secs(100 * SECONDS_PER_DAY + 3600);
+		let after =
+			|secs| (start + Duration::from_secs(secs), utc_start + Duration::from_secs(secs));
+		let mut state = LimiterState {
+			rate: Some(TokenBucket::new(1.0, 1.0, start)),
+			daily_budget: Some(DailyBudget::new(2, utc_start)),
+			paused_until: None,
+		};
+
+		assert_eq!(state.try_acquire(start, utc_start), Ok(()));
+		// The rate limit allows one request per second.
+		assert_eq!(state.try_acquire(start, utc_start), Err(Duration::from_secs(1)));
+		let (now, utc_now) = after(1);
+		assert_eq!(state.try_acquire(now, utc_now), Ok(()));
+		// The budget is spent until the next day.
+		let (now, utc_now) = after(2);
+		assert_eq!(
+			state.try_acquire(now, utc_now),
+			Err(Duration::from_secs(SECONDS_PER_DAY - 3600 - 2))
+		);
+		let (now, utc_now) = after(SECONDS_PER_DAY - 3600);
+		assert_eq!(state.try_acquire(now, utc_now), Ok(()));
+	}
+
+	#[test]
+	fn requests_wait_while_the_endpoint_is_paused() {
+		let start = Instant::now();
+		let utc_now = SystemTime::now();
+		let mut state = LimiterState {
+			rate: None,
+			daily_budget: None,
+			paused_until: Some(start + Duration::from_secs(30)),
+		};
+
+		assert_eq!(state.try_acquire(start, utc_now), Err(Duration::from_secs(30)));
+		assert_eq!(state.try_acquire(start + Duration::from_secs(30), utc_now), Ok(()));
+	}
+
+	#[tokio::test(start_paused = true)]
+	async fn acquire_waits_for_the_rate_limit() {
+		let limiter = EndpointLimiter::new(
+			"test",
+			"primary",
+			Some(RateLimit {
+				requests_per_second: NonZeroU32::new(2),
+				burst: NonZeroU32::new(1),
+				daily_budget: NonZeroU64::new(1000),
+			}),
+		);
+
+		let start = Instant::now();
+		for _ in 0..5 {
+			limiter.acquire().await.unwrap();
+		}
+		assert_eq!(start.elapsed(), Duration::from_secs(2));
+	}
+
+	#[tokio::test(start_paused = true)]
+	async fn acquire_fails_while_the_endpoint_is_paused_for_long() {
+		let limiter = EndpointLimiter::new("test", "primary", None);
+
+		// Short pauses are waited out.
+		limiter.pause(Duration::from_secs(1));
+		limiter.acquire().await.unwrap();
+
+		limiter.pause(Duration::from_secs(60));
+		assert_eq!(
+			limiter.acquire().await,
+			Err(EndpointUnavailable { available_in: Duration::from_secs(60) })
+		);
+		assert_eq!(limiter.available_in(), Duration::from_secs(60));
+	}
+}
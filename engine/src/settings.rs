@@ -2,7 +2,8 @@ use std::{
 	collections::HashMap,
 	ffi::OsStr,
 	fmt,
-	net::IpAddr,
+	net::{IpAddr, SocketAddr},
+	num::{NonZeroU32, NonZeroU64},
 	path::{Path, PathBuf},
 };
 
@@ -21,9 +22,75 @@ use utilities::{
 };
 
 use crate::constants::{CONFIG_ROOT, DEFAULT_CONFIG_ROOT};
+use cf_primitives::ForeignChain;
+use multisig::KeyId;
 
 pub const DEFAULT_SETTINGS_DIR: &str = "config";
 
+/// How encrypted connections between peers are established
+#[derive(Debug, Deserialize, Clone, Copy, Default, PartialEq, Eq, clap::ArgEnum)]
+#[serde(rename_all = "snake_case")]
+pub enum P2PTransport {
+	/// ZMQ sockets secured with CURVE
+	#[default]
+	Zmq,
+	/// TCP connections secured with the Noise protocol
+	Noise,
+}
+
+impl std::fmt::Display for P2PTransport {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		match self {
+			P2PTransport::Zmq => write!(f, "zmq"),
+			P2PTransport::Noise => write!(f, "noise"),
+		}
+	}
+}
+
+/// How the Ethereum witnessers decide that a block is safe to witness
+#[derive(Debug, Deserialize, Clone, Copy, PartialEq, Eq, clap::ArgEnum)]
+#[serde(rename_all = "snake_case")]
+#[clap(rename_all = "snake_case")]
+pub enum EthWitnessingMode {
+	/// Wait for a fixed number of blocks to be built on top of a block
+	FixedLag,
+	/// Follow the blocks tagged `safe` by the node
+	Safe,
+	/// Follow the blocks tagged `finalized` by the node
+	Finalized,
+}
+
+impl std::fmt::Display for EthWitnessingMode {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		match self {
+			EthWitnessingMode::FixedLag => write!(f, "fixed_lag"),
+			EthWitnessingMode::Safe => write!(f, "safe"),
+			EthWitnessingMode::Finalized => write!(f, "finalized"),
+		}
+	}
+}
+
+/// How the Polkadot witnessers decide that a block is safe to witness. Polkadot has no notion of
+/// a safe block, GRANDPA finality is the only tag.
+#[derive(Debug, Deserialize, Clone, Copy, PartialEq, Eq, clap::ArgEnum)]
+#[serde(rename_all = "snake_case")]
+#[clap(rename_all = "snake_case")]
+pub enum DotWitnessingMode {
+	/// Wait for a fixed number of blocks to be built on top of a block
+	FixedLag,
+	/// Follow the blocks finalised by GRANDPA
+	Finalized,
+}
+
+impl std::fmt::Display for DotWitnessingMode {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		match self {
+			DotWitnessingMode::FixedLag => write!(f, "fixed_lag"),
+			DotWitnessingMode::Finalized => write!(f, "finalized"),
+		}
+	}
+}
+
 #[derive(Debug, Deserialize, Clone, PartialEq, Eq)]
 pub struct P2P {
 	#[serde(deserialize_with = "deser_path")]
@@ -31,6 +98,15 @@ pub struct P2P {
 	pub ip_address: IpAddr,
 	pub port: Port,
 	pub allow_local_ip: bool,
+	/// The transport used to connect to peers. Nodes accept connections over both transports, ZMQ
+	/// on `port` and Noise on the port after it, so a network can be migrated one node at a time.
+	/// Both ports need to be reachable.
+	#[serde(default)]
+	pub transport: P2PTransport,
+	/// Relay messages to peers that we can't connect to via other peers, and
+	/// forward relayed messages from other peers
+	#[serde(default)]
+	pub enable_relay: bool,
 }
 
 #[derive(Debug, Deserialize, Clone, Default, PartialEq, Eq)]
@@ -48,10 +124,30 @@ impl StateChain {
 	}
 }
 
+/// Limits on the rpc requests made to an endpoint, for node providers that meter requests. Requests
+/// over the limits are delayed until they are within them again.
+#[derive(Debug, Deserialize, Clone, Copy, PartialEq, Eq)]
+pub struct RateLimit {
+	/// The number of requests that can be made per second, on average.
+	#[serde(default)]
+	pub requests_per_second: Option<NonZeroU32>,
+	/// The number of requests that can be made at once after being idle. Defaults to
+	/// `requests_per_second`.
+	#[serde(default)]
+	pub burst: Option<NonZeroU32>,
+	/// The number of requests that can be made per day. The budget is reset at midnight UTC, like
+	/// node providers' daily quotas. Once it is spent, requests are made to the other endpoint.
+	#[serde(default)]
+	pub daily_budget: Option<NonZeroU64>,
+}
+
 #[derive(Debug, Deserialize, Clone, Default, PartialEq, Eq)]
 pub struct WsHttpEndpoints {
 	pub ws_endpoint: SecretUrl,
 	pub http_endpoint: SecretUrl,
+	/// Applies to the requests made to the `http_endpoint`.
+	#[serde(default)]
+	pub rate_limit: Option<RateLimit>,
 }
 
 pub trait ValidateSettings {
@@ -85,31 +181,83 @@ impl<NodeConfig: ValidateSettings> NodeContainer<NodeConfig> {
 		}
 		Ok(())
 	}
+
+	/// Cross-checking needs a backup rpc to check the primary rpc against.
+	pub fn validate_cross_check_rpcs(&self, cross_check_rpcs: bool) -> Result<(), ConfigError> {
+		if cross_check_rpcs && self.backup.is_none() {
+			return Err(ConfigError::Message(
+				"Cross-checking rpcs requires a backup rpc".to_string(),
+			))
+		}
+		Ok(())
+	}
 }
 
-#[derive(Debug, Deserialize, Clone, Default, PartialEq, Eq)]
+#[derive(Debug, Deserialize, Clone, PartialEq, Eq)]
 pub struct Eth {
 	#[serde(flatten)]
 	pub nodes: NodeContainer<WsHttpEndpoints>,
 	#[serde(deserialize_with = "deser_path")]
 	pub private_key_file: PathBuf,
+	pub witnessing_mode: EthWitnessingMode,
+	/// If set, the headers and rpc responses used for witnessing are recorded to this file, so
+	/// they can be replayed offline.
+	#[serde(default)]
+	pub recording_file: Option<PathBuf>,
+	/// If set, the block hashes, logs and receipts we witness from are requested from both the
+	/// primary and the backup rpc, and are only used once both agree.
+	#[serde(default)]
+	pub cross_check_rpcs: bool,
 }
 
 impl Eth {
 	pub fn validate_settings(&self) -> Result<(), ConfigError> {
-		self.nodes.validate()
+		self.nodes.validate()?;
+		self.nodes.validate_cross_check_rpcs(self.cross_check_rpcs)
 	}
 }
 
-#[derive(Debug, Deserialize, Clone, Default, PartialEq, Eq)]
+#[derive(Debug, Deserialize, Clone, PartialEq, Eq)]
+pub struct Arb {
+	#[serde(flatten)]
+	pub nodes: NodeContainer<WsHttpEndpoints>,
+	/// Has no default, as the account it is for has to be funded on Arbitrum. It may be the same
+	/// as the Ethereum private key file.
+	#[serde(deserialize_with = "deser_path")]
+	pub private_key_file: PathBuf,
+	/// See [`Eth::recording_file`].
+	#[serde(default)]
+	pub recording_file: Option<PathBuf>,
+	/// See [`Eth::cross_check_rpcs`].
+	#[serde(default)]
+	pub cross_check_rpcs: bool,
+}
+
+impl Arb {
+	pub fn validate_settings(&self) -> Result<(), ConfigError> {
+		self.nodes.validate()?;
+		self.nodes.validate_cross_check_rpcs(self.cross_check_rpcs)
+	}
+}
+
+#[derive(Debug, Deserialize, Clone, PartialEq, Eq)]
 pub struct Dot {
 	#[serde(flatten)]
 	pub nodes: NodeContainer<WsHttpEndpoints>,
+	pub witnessing_mode: DotWitnessingMode,
+	/// See [`Eth::recording_file`].
+	#[serde(default)]
+	pub recording_file: Option<PathBuf>,
+	/// If set, block hashes are requested from both the primary and the backup rpc, and are only
+	/// used once both agree.
+	#[serde(default)]
+	pub cross_check_rpcs: bool,
 }
 
 impl Dot {
 	pub fn validate_settings(&self) -> Result<(), ConfigError> {
 		self.nodes.validate()?;
+		self.nodes.validate_cross_check_rpcs(self.cross_check_rpcs)?;
 
 		// Check that all endpoints have a port number
 		let validate_dot_endpoints = |endpoints: &WsHttpEndpoints| -> Result<(), ConfigError> {
@@ -145,6 +293,8 @@ pub struct HttpBasicAuthEndpoint {
 	pub http_endpoint: SecretUrl,
 	pub basic_auth_user: String,
 	pub basic_auth_password: String,
+	#[serde(default)]
+	pub rate_limit: Option<RateLimit>,
 }
 
 impl ValidateSettings for HttpBasicAuthEndpoint {
@@ -160,11 +310,24 @@ impl ValidateSettings for HttpBasicAuthEndpoint {
 pub struct Btc {
 	#[serde(flatten)]
 	pub nodes: NodeContainer<HttpBasicAuthEndpoint>,
+	/// See [`Eth::recording_file`].
+	#[serde(default)]
+	pub recording_file: Option<PathBuf>,
+	/// If set, the block hashes and blocks we witness from are requested from both the primary and
+	/// the backup rpc, and are only used once both agree.
+	#[serde(default)]
+	pub cross_check_rpcs: bool,
+	/// If set, deposits are witnessed using the BIP158 compact block filters of the blocks, and a
+	/// block is only downloaded if its filter matches one of our deposit addresses, or if we have
+	/// broadcast transactions to look for. The nodes must serve block filters, e.g. bitcoind with
+	/// `-blockfilterindex=1`.
+	pub block_filters: bool,
 }
 
 impl Btc {
 	pub fn validate_settings(&self) -> Result<(), ConfigError> {
-		self.nodes.validate()
+		self.nodes.validate()?;
+		self.nodes.validate_cross_check_rpcs(self.cross_check_rpcs)
 	}
 }
 
@@ -174,10 +337,28 @@ pub struct HealthCheck {
 	pub port: Port,
 }
 
+/// Running as an observer, the engine witnesses every vault without signing or submitting anything,
+/// and publishes what it would have witnessed instead.
+#[derive(Debug, Deserialize, Clone, PartialEq, Eq)]
+pub struct Observer {
+	/// The address the observer's RPC server listens on, e.g. 127.0.0.1:13337
+	pub listen_address: SocketAddr,
+}
+
 #[derive(Debug, Deserialize, Clone, PartialEq, Eq)]
 pub struct Signing {
 	#[serde(deserialize_with = "deser_path")]
 	pub db_file: PathBuf,
+	/// If set, key shares are encrypted at rest with a key derived from the contents of this file.
+	/// Backups of the db in the `backups` directory that were taken before the key shares were
+	/// encrypted are removed. Any other copies of the db must be removed manually.
+	#[serde(default)]
+	pub db_encryption_key_file: Option<PathBuf>,
+	/// If set, the key shares of expired epochs are removed from the db, after taking a snapshot
+	/// of the db in the `backups` directory. Otherwise they are kept, and can be removed with the
+	/// `db prune` command.
+	#[serde(default)]
+	pub prune_expired_key_shares: bool,
 }
 
 #[derive(Debug, Deserialize, Clone, PartialEq, Eq)]
@@ -188,9 +369,13 @@ pub struct Settings {
 	pub eth: Eth,
 	pub dot: Dot,
 	pub btc: Btc,
+	/// Arbitrum is neither witnessed nor signed for if this is not set.
+	#[serde(default)]
+	pub arb: Option<Arb>,
 
 	pub health_check: Option<HealthCheck>,
 	pub prometheus: Option<Prometheus>,
+	pub observer: Option<Observer>,
 	pub signing: Signing,
 	pub logging: LoggingSettings,
 }
@@ -217,6 +402,37 @@ pub struct EthOptions {
 
 	#[clap(long = "eth.private_key_file")]
 	pub eth_private_key_file: Option<PathBuf>,
+
+	#[clap(long = "eth.witnessing_mode", arg_enum)]
+	pub eth_witnessing_mode: Option<EthWitnessingMode>,
+
+	#[clap(long = "eth.recording_file", parse(from_os_str))]
+	pub eth_recording_file: Option<PathBuf>,
+
+	#[clap(long = "eth.cross_check_rpcs")]
+	pub eth_cross_check_rpcs: Option<bool>,
+}
+
+#[derive(Parser, Debug, Clone, Default)]
+pub struct ArbOptions {
+	#[clap(long = "arb.rpc.ws_endpoint")]
+	pub arb_ws_endpoint: Option<String>,
+	#[clap(long = "arb.rpc.http_endpoint")]
+	pub arb_http_endpoint: Option<String>,
+
+	#[clap(long = "arb.backup_rpc.ws_endpoint")]
+	pub arb_backup_ws_endpoint: Option<String>,
+	#[clap(long = "arb.backup_rpc.http_endpoint")]
+	pub arb_backup_http_endpoint: Option<String>,
+
+	#[clap(long = "arb.private_key_file")]
+	pub arb_private_key_file: Option<PathBuf>,
+
+	#[clap(long = "arb.recording_file", parse(from_os_str))]
+	pub arb_recording_file: Option<PathBuf>,
+
+	#[clap(long = "arb.cross_check_rpcs")]
+	pub arb_cross_check_rpcs: Option<bool>,
 }
 
 #[derive(Parser, Debug, Clone, Default)]
@@ -230,6 +446,15 @@ pub struct DotOptions {
 	pub dot_backup_ws_endpoint: Option<String>,
 	#[clap(long = "dot.backup_rpc.http_endpoint")]
 	pub dot_backup_http_endpoint: Option<String>,
+
+	#[clap(long = "dot.witnessing_mode", arg_enum)]
+	pub dot_witnessing_mode: Option<DotWitnessingMode>,
+
+	#[clap(long = "dot.recording_file", parse(from_os_str))]
+	pub dot_recording_file: Option<PathBuf>,
+
+	#[clap(long = "dot.cross_check_rpcs")]
+	pub dot_cross_check_rpcs: Option<bool>,
 }
 
 #[derive(Parser, Debug, Clone, Default)]
@@ -247,6 +472,15 @@ pub struct BtcOptions {
 	pub btc_backup_basic_auth_user: Option<String>,
 	#[clap(long = "btc.backup_rpc.basic_auth_password")]
 	pub btc_backup_basic_auth_password: Option<String>,
+
+	#[clap(long = "btc.recording_file", parse(from_os_str))]
+	pub btc_recording_file: Option<PathBuf>,
+
+	#[clap(long = "btc.cross_check_rpcs")]
+	pub btc_cross_check_rpcs: Option<bool>,
+
+	#[clap(long = "btc.block_filters")]
+	pub btc_block_filters: Option<bool>,
 }
 
 #[derive(Parser, Debug, Clone, Default)]
@@ -259,6 +493,10 @@ pub struct P2POptions {
 	p2p_port: Option<Port>,
 	#[clap(long = "p2p.allow_local_ip")]
 	allow_local_ip: Option<bool>,
+	#[clap(long = "p2p.transport", arg_enum)]
+	transport: Option<P2PTransport>,
+	#[clap(long = "p2p.enable_relay")]
+	enable_relay: Option<bool>,
 }
 
 #[derive(Parser, Debug, Clone)]
@@ -283,6 +521,9 @@ pub struct CommandLineOptions {
 	#[clap(flatten)]
 	pub btc_opts: BtcOptions,
 
+	#[clap(flatten)]
+	pub arb_opts: ArbOptions,
+
 	// Health Check Settings
 	#[clap(long = "health_check.hostname")]
 	pub health_check_hostname: Option<String>,
@@ -295,16 +536,229 @@ pub struct CommandLineOptions {
 	#[clap(long = "prometheus.port")]
 	pub prometheus_port: Option<Port>,
 
+	// Observer Settings
+	#[clap(long = "observer.listen_address")]
+	pub observer_listen_address: Option<SocketAddr>,
+
 	// Signing Settings
 	#[clap(long = "signing.db_file", parse(from_os_str))]
 	pub signing_db_file: Option<PathBuf>,
 
+	#[clap(long = "signing.db_encryption_key_file", parse(from_os_str))]
+	pub signing_db_encryption_key_file: Option<PathBuf>,
+
+	#[clap(long = "signing.prune_expired_key_shares")]
+	pub signing_prune_expired_key_shares: Option<bool>,
+
 	// Logging settings
 	#[clap(long = "logging.span_lifecycle")]
 	pub logging_span_lifecycle: bool,
 
 	#[clap(long = "logging.command_server_port")]
 	pub logging_command_server_port: Option<Port>,
+
+	#[clap(subcommand)]
+	pub cmd: Option<EngineCommand>,
+}
+
+#[derive(clap::Subcommand, Debug, Clone)]
+pub enum EngineCommand {
+	/// Witness a range of external chain blocks again, submitting only the witnesses that are
+	/// still pending on the State Chain, then exit. Used to recover from outages.
+	Rewitness {
+		#[clap(long, arg_enum)]
+		chain: RewitnessChain,
+		/// The first block to witness.
+		#[clap(long)]
+		from: u64,
+		/// The last block to witness (inclusive).
+		#[clap(long)]
+		to: u64,
+	},
+	/// Manage the key share database. The engine must be stopped first, except for inspecting the
+	/// database and taking snapshots. A running engine also takes a snapshot when it receives a
+	/// `SIGUSR1` signal.
+	Db(DbCommand),
+}
+
+#[derive(clap::Args, Debug, Clone)]
+pub struct DbCommand {
+	/// Don't ask for confirmation before changing the database.
+	#[clap(short = 'y', long = "yes")]
+	pub assume_yes: bool,
+	#[clap(subcommand)]
+	pub subcommand: DbSubcommand,
+}
+
+#[derive(clap::Subcommand, Debug, Clone)]
+pub enum DbSubcommand {
+	/// Change the secret that the key shares in the database are encrypted with. This can also
+	/// encrypt a database that isn't encrypted yet, or remove the encryption.
+	RotateEncryption {
+		/// Path to the database.
+		#[clap(long, parse(from_os_str))]
+		db_file: PathBuf,
+		#[clap(flatten)]
+		genesis_hash: GenesisHashOptions,
+		/// File containing the current encryption secret. Omit if the database isn't encrypted.
+		#[clap(long, parse(from_os_str), conflicts_with = "current-passphrase")]
+		current_key_file: Option<PathBuf>,
+		/// The current encryption passphrase. Omit if the database isn't encrypted.
+		#[clap(long, env = "CF_DB_CURRENT_PASSPHRASE", hide_env_values = true)]
+		current_passphrase: Option<String>,
+		/// File containing the new encryption secret.
+		#[clap(long, parse(from_os_str), conflicts_with = "new-passphrase")]
+		new_key_file: Option<PathBuf>,
+		/// The new encryption passphrase.
+		#[clap(long, env = "CF_DB_NEW_PASSPHRASE", hide_env_values = true)]
+		new_passphrase: Option<String>,
+		/// Store the key shares unencrypted.
+		#[clap(
+			long,
+			conflicts_with_all = &["new-key-file", "new-passphrase"],
+			required_unless_present_any = &["new-key-file", "new-passphrase"]
+		)]
+		disable_encryption: bool,
+		/// Also replace the data key and re-encrypt all key shares with it. By default only the
+		/// data key is re-encrypted with the new secret.
+		#[clap(long, conflicts_with = "disable-encryption")]
+		rotate_data_key: bool,
+	},
+	/// Export a chain's key shares into an encrypted backup file.
+	Export {
+		/// Path to the database.
+		#[clap(long, parse(from_os_str))]
+		db_file: PathBuf,
+		#[clap(flatten)]
+		genesis_hash: GenesisHashOptions,
+		#[clap(flatten)]
+		db_secret: DbSecretOptions,
+		/// The chain whose key shares are exported.
+		#[clap(long)]
+		chain: ForeignChain,
+		/// A key to export, as `<epoch_index>:<hex encoded public key>`. Can be repeated. All of
+		/// the chain's keys are exported if omitted.
+		#[clap(long = "key-id")]
+		key_ids: Vec<KeyId>,
+		/// File to write the backup to.
+		#[clap(long, parse(from_os_str))]
+		backup_file: PathBuf,
+		#[clap(flatten)]
+		backup_secret: BackupSecretOptions,
+	},
+	/// Check that a backup file can be decrypted and that the key shares in it are valid. This
+	/// doesn't need access to a database.
+	VerifyBackup {
+		#[clap(long, parse(from_os_str))]
+		backup_file: PathBuf,
+		#[clap(flatten)]
+		backup_secret: BackupSecretOptions,
+	},
+	/// Import the key shares in a backup file into the database. The database is created if it
+	/// doesn't exist yet.
+	Import {
+		/// Path to the database.
+		#[clap(long, parse(from_os_str))]
+		db_file: PathBuf,
+		#[clap(flatten)]
+		genesis_hash: GenesisHashOptions,
+		#[clap(flatten)]
+		db_secret: DbSecretOptions,
+		#[clap(long, parse(from_os_str))]
+		backup_file: PathBuf,
+		#[clap(flatten)]
+		backup_secret: BackupSecretOptions,
+	},
+	/// List the key shares and witnesser progress records in the database by chain and epoch.
+	/// The key shares are not decrypted, so no secret is needed.
+	Inspect {
+		/// Path to the database.
+		#[clap(long, parse(from_os_str))]
+		db_file: PathBuf,
+	},
+	/// Remove the backups in the backups directory next to the database that were taken before
+	/// its key shares were encrypted, and so contain them unencrypted. Each backup is listed
+	/// before it is removed. Copies of the database in other locations must be removed manually.
+	RemoveUnencryptedBackups {
+		/// Path to the database.
+		#[clap(long, parse(from_os_str))]
+		db_file: PathBuf,
+	},
+	/// Take a consistent snapshot of the database, which may be in use by a running engine.
+	Snapshot {
+		/// Path to the database.
+		#[clap(long, parse(from_os_str))]
+		db_file: PathBuf,
+		/// Directory to create the snapshot in. Defaults to the backups directory next to the
+		/// database.
+		#[clap(long, parse(from_os_str))]
+		snapshot_dir: Option<PathBuf>,
+	},
+	/// Remove the witnesser progress records, and optionally the key shares, of all epochs up to
+	/// and including the given epoch. A running engine does this automatically once the State
+	/// Chain expires an epoch, but only removes key shares if `signing.prune_expired_key_shares`
+	/// is set.
+	Prune {
+		/// Path to the database.
+		#[clap(long, parse(from_os_str))]
+		db_file: PathBuf,
+		/// The last expired epoch, as found in the Validator pallet's `LastExpiredEpoch` storage.
+		#[clap(long)]
+		last_expired_epoch: u32,
+		/// Also remove the key shares. A snapshot of the database is taken first, in the backups
+		/// directory next to the database.
+		#[clap(long)]
+		key_shares: bool,
+	},
+}
+
+/// The Chainflip network that a database is expected to belong to. Opening a database with key
+/// shares of another network, or importing a backup from one, is refused.
+#[derive(clap::Args, Debug, Clone)]
+pub struct GenesisHashOptions {
+	/// Hex encoded genesis hash of the Chainflip network. A database that doesn't record its
+	/// network yet is marked as belonging to it.
+	#[clap(long, required_unless_present = "allow-unknown-genesis-hash")]
+	pub genesis_hash: Option<state_chain_runtime::Hash>,
+	/// Don't check which network the database and backups belong to. Only needed for databases
+	/// of networks that don't exist yet, such as those holding the genesis keys.
+	#[clap(long, conflicts_with = "genesis-hash")]
+	pub allow_unknown_genesis_hash: bool,
+}
+
+#[derive(clap::Args, Debug, Clone)]
+pub struct DbSecretOptions {
+	/// File containing the secret the database is encrypted with. Omit if the database isn't
+	/// encrypted.
+	#[clap(long, parse(from_os_str), conflicts_with = "db-passphrase")]
+	pub db_key_file: Option<PathBuf>,
+	/// The passphrase the database is encrypted with. Omit if the database isn't encrypted.
+	#[clap(long, env = "CF_DB_PASSPHRASE", hide_env_values = true)]
+	pub db_passphrase: Option<String>,
+}
+
+#[derive(clap::Args, Debug, Clone)]
+pub struct BackupSecretOptions {
+	/// File containing the secret the backup is encrypted with.
+	#[clap(
+		long,
+		parse(from_os_str),
+		conflicts_with = "backup-passphrase",
+		required_unless_present = "backup-passphrase"
+	)]
+	pub backup_key_file: Option<PathBuf>,
+	/// The passphrase the backup is encrypted with.
+	#[clap(long, env = "CF_BACKUP_PASSPHRASE", hide_env_values = true)]
+	pub backup_passphrase: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ArgEnum)]
+#[clap(rename_all = "snake_case")]
+pub enum RewitnessChain {
+	Eth,
+	Dot,
+	Btc,
+	Arb,
 }
 
 impl Default for CommandLineOptions {
@@ -319,13 +773,18 @@ impl Default for CommandLineOptions {
 			eth_opts: EthOptions::default(),
 			dot_opts: DotOptions::default(),
 			btc_opts: BtcOptions::default(),
+			arb_opts: ArbOptions::default(),
 			health_check_hostname: None,
 			health_check_port: None,
 			prometheus_hostname: None,
 			prometheus_port: None,
+			observer_listen_address: None,
 			signing_db_file: None,
+			signing_db_encryption_key_file: None,
+			signing_prune_expired_key_shares: None,
 			logging_span_lifecycle: false,
 			logging_command_server_port: None,
+			cmd: None,
 		}
 	}
 }
@@ -333,13 +792,32 @@ impl Default for CommandLineOptions {
 const NODE_P2P_KEY_FILE: &str = "node_p2p.node_key_file";
 const NODE_P2P_PORT: &str = "node_p2p.port";
 const NODE_P2P_ALLOW_LOCAL_IP: &str = "node_p2p.allow_local_ip";
+const NODE_P2P_TRANSPORT: &str = "node_p2p.transport";
+const NODE_P2P_ENABLE_RELAY: &str = "node_p2p.enable_relay";
 
 const STATE_CHAIN_WS_ENDPOINT: &str = "state_chain.ws_endpoint";
 const STATE_CHAIN_SIGNING_KEY_FILE: &str = "state_chain.signing_key_file";
 
 const ETH_PRIVATE_KEY_FILE: &str = "eth.private_key_file";
+const ETH_WITNESSING_MODE: &str = "eth.witnessing_mode";
+const ETH_RECORDING_FILE: &str = "eth.recording_file";
+const ETH_CROSS_CHECK_RPCS: &str = "eth.cross_check_rpcs";
+
+const DOT_WITNESSING_MODE: &str = "dot.witnessing_mode";
+const DOT_RECORDING_FILE: &str = "dot.recording_file";
+const DOT_CROSS_CHECK_RPCS: &str = "dot.cross_check_rpcs";
+
+const BTC_RECORDING_FILE: &str = "btc.recording_file";
+const BTC_CROSS_CHECK_RPCS: &str = "btc.cross_check_rpcs";
+const BTC_BLOCK_FILTERS: &str = "btc.block_filters";
+
+const ARB_PRIVATE_KEY_FILE: &str = "arb.private_key_file";
+const ARB_RECORDING_FILE: &str = "arb.recording_file";
+const ARB_CROSS_CHECK_RPCS: &str = "arb.cross_check_rpcs";
 
 const SIGNING_DB_FILE: &str = "signing.db_file";
+const SIGNING_DB_ENCRYPTION_KEY_FILE: &str = "signing.db_encryption_key_file";
+const SIGNING_PRUNE_EXPIRED_KEY_SHARES: &str = "signing.prune_expired_key_shares";
 
 const LOGGING_SPAN_LIFECYCLE: &str = "logging.span_lifecycle";
 const LOGGING_COMMAND_SERVER_PORT: &str = "logging.command_server_port";
@@ -500,25 +978,60 @@ impl CfSettings for Settings {
 
 		self.btc.validate_settings()?;
 
+		if let Some(arb) = &self.arb {
+			arb.validate_settings()?;
+		}
+
 		self.state_chain.validate_settings()?;
 
+		if self.node_p2p.port == Port::MAX {
+			return Err(ConfigError::Message(format!(
+				"{NODE_P2P_PORT} can't be {}, as the Noise transport listens on the port after it",
+				Port::MAX
+			)))
+		}
+
 		is_valid_db_path(&self.signing.db_file).map_err(|e| ConfigError::Message(e.to_string()))?;
 
+		// An observer never signs anything, so its keys don't need to exist.
+		let is_observer = self.observer.is_some();
+		let key_file_expectation =
+			|| (!is_observer).then_some(PathResolutionExpectation::ExistingFile);
+
 		self.state_chain.signing_key_file = resolve_settings_path(
 			config_root,
 			&self.state_chain.signing_key_file,
-			Some(PathResolutionExpectation::ExistingFile),
-		)?;
-		self.eth.private_key_file = resolve_settings_path(
-			config_root,
-			&self.eth.private_key_file,
-			Some(PathResolutionExpectation::ExistingFile),
+			key_file_expectation(),
 		)?;
+		self.eth.private_key_file =
+			resolve_settings_path(config_root, &self.eth.private_key_file, key_file_expectation())?;
+		if let Some(arb) = &mut self.arb {
+			arb.private_key_file =
+				resolve_settings_path(config_root, &arb.private_key_file, key_file_expectation())?;
+		}
+		for recording_file in [
+			&mut self.eth.recording_file,
+			&mut self.dot.recording_file,
+			&mut self.btc.recording_file,
+		]
+		.into_iter()
+		.chain(self.arb.as_mut().map(|arb| &mut arb.recording_file))
+		.flatten()
+		{
+			*recording_file = resolve_settings_path(config_root, recording_file, None)?;
+		}
 		self.signing.db_file = resolve_settings_path(config_root, &self.signing.db_file, None)?;
+		if let Some(db_encryption_key_file) = &self.signing.db_encryption_key_file {
+			self.signing.db_encryption_key_file = Some(resolve_settings_path(
+				config_root,
+				db_encryption_key_file,
+				Some(PathResolutionExpectation::ExistingFile),
+			)?);
+		}
 		self.node_p2p.node_key_file = resolve_settings_path(
 			config_root,
 			&self.node_p2p.node_key_file,
-			Some(PathResolutionExpectation::ExistingFile),
+			key_file_expectation(),
 		)?;
 
 		Ok(())
@@ -541,6 +1054,12 @@ impl CfSettings for Settings {
 			)?
 			.set_default(NODE_P2P_PORT, 8078)?
 			.set_default(STATE_CHAIN_WS_ENDPOINT, "ws://localhost:9944")?
+			.set_default(ETH_WITNESSING_MODE, EthWitnessingMode::FixedLag.to_string())?
+			.set_default(DOT_WITNESSING_MODE, DotWitnessingMode::Finalized.to_string())?
+			.set_default(ETH_CROSS_CHECK_RPCS, false)?
+			.set_default(DOT_CROSS_CHECK_RPCS, false)?
+			.set_default(BTC_CROSS_CHECK_RPCS, false)?
+			.set_default(BTC_BLOCK_FILTERS, false)?
 			.set_default(
 				STATE_CHAIN_SIGNING_KEY_FILE,
 				PathBuf::from(config_root)
@@ -583,13 +1102,31 @@ impl Source for CommandLineOptions {
 
 		self.btc_opts.insert_all(&mut map);
 
+		self.arb_opts.insert_all(&mut map);
+
 		insert_command_line_option(&mut map, "health_check.hostname", &self.health_check_hostname);
 		insert_command_line_option(&mut map, "health_check.port", &self.health_check_port);
 
 		insert_command_line_option(&mut map, "prometheus.hostname", &self.prometheus_hostname);
 		insert_command_line_option(&mut map, "prometheus.port", &self.prometheus_port);
 
+		insert_command_line_option(
+			&mut map,
+			"observer.listen_address",
+			&self.observer_listen_address.map(|address| address.to_string()),
+		);
+
 		insert_command_line_option_path(&mut map, SIGNING_DB_FILE, &self.signing_db_file);
+		insert_command_line_option_path(
+			&mut map,
+			SIGNING_DB_ENCRYPTION_KEY_FILE,
+			&self.signing_db_encryption_key_file,
+		);
+		insert_command_line_option(
+			&mut map,
+			SIGNING_PRUNE_EXPIRED_KEY_SHARES,
+			&self.signing_prune_expired_key_shares,
+		);
 		insert_command_line_option(
 			&mut map,
 			LOGGING_SPAN_LIFECYCLE,
@@ -657,6 +1194,32 @@ impl EthOptions {
 		);
 
 		insert_command_line_option_path(map, ETH_PRIVATE_KEY_FILE, &self.eth_private_key_file);
+		insert_command_line_option(
+			map,
+			ETH_WITNESSING_MODE,
+			&self.eth_witnessing_mode.map(|mode| mode.to_string()),
+		);
+		insert_command_line_option_path(map, ETH_RECORDING_FILE, &self.eth_recording_file);
+		insert_command_line_option(map, ETH_CROSS_CHECK_RPCS, &self.eth_cross_check_rpcs);
+	}
+}
+
+impl ArbOptions {
+	/// Inserts all the Arb Options into the given map (if Some)
+	pub fn insert_all(&self, map: &mut HashMap<String, Value>) {
+		insert_command_line_option(map, "arb.rpc.ws_endpoint", &self.arb_ws_endpoint);
+		insert_command_line_option(map, "arb.rpc.http_endpoint", &self.arb_http_endpoint);
+
+		insert_command_line_option(map, "arb.backup_rpc.ws_endpoint", &self.arb_backup_ws_endpoint);
+		insert_command_line_option(
+			map,
+			"arb.backup_rpc.http_endpoint",
+			&self.arb_backup_http_endpoint,
+		);
+
+		insert_command_line_option_path(map, ARB_PRIVATE_KEY_FILE, &self.arb_private_key_file);
+		insert_command_line_option_path(map, ARB_RECORDING_FILE, &self.arb_recording_file);
+		insert_command_line_option(map, ARB_CROSS_CHECK_RPCS, &self.arb_cross_check_rpcs);
 	}
 }
 
@@ -671,6 +1234,12 @@ impl P2POptions {
 		);
 		insert_command_line_option(map, NODE_P2P_PORT, &self.p2p_port);
 		insert_command_line_option(map, NODE_P2P_ALLOW_LOCAL_IP, &self.allow_local_ip);
+		insert_command_line_option(
+			map,
+			NODE_P2P_TRANSPORT,
+			&self.transport.map(|transport| transport.to_string()),
+		);
+		insert_command_line_option(map, NODE_P2P_ENABLE_RELAY, &self.enable_relay);
 	}
 }
 
@@ -699,6 +1268,10 @@ impl BtcOptions {
 			"btc.backup_rpc.basic_auth_password",
 			&self.btc_backup_basic_auth_password,
 		);
+
+		insert_command_line_option_path(map, BTC_RECORDING_FILE, &self.btc_recording_file);
+		insert_command_line_option(map, BTC_CROSS_CHECK_RPCS, &self.btc_cross_check_rpcs);
+		insert_command_line_option(map, BTC_BLOCK_FILTERS, &self.btc_block_filters);
 	}
 }
 
@@ -713,6 +1286,13 @@ impl DotOptions {
 			"dot.backup_rpc.http_endpoint",
 			&self.dot_backup_http_endpoint,
 		);
+		insert_command_line_option(
+			map,
+			DOT_WITNESSING_MODE,
+			&self.dot_witnessing_mode.map(|mode| mode.to_string()),
+		);
+		insert_command_line_option_path(map, DOT_RECORDING_FILE, &self.dot_recording_file);
+		insert_command_line_option(map, DOT_CROSS_CHECK_RPCS, &self.dot_cross_check_rpcs);
 	}
 }
 
@@ -779,6 +1359,7 @@ pub mod tests {
 	use utilities::assert_ok;
 
 	use crate::constants::{
+		ARB_BACKUP_HTTP_ENDPOINT, ARB_BACKUP_WS_ENDPOINT, ARB_HTTP_ENDPOINT, ARB_WS_ENDPOINT,
 		BTC_BACKUP_HTTP_ENDPOINT, BTC_BACKUP_RPC_PASSWORD, BTC_BACKUP_RPC_USER, BTC_HTTP_ENDPOINT,
 		BTC_RPC_PASSWORD, BTC_RPC_USER, DOT_BACKUP_HTTP_ENDPOINT, DOT_BACKUP_WS_ENDPOINT,
 		DOT_HTTP_ENDPOINT, DOT_WS_ENDPOINT, ETH_BACKUP_HTTP_ENDPOINT, ETH_BACKUP_WS_ENDPOINT,
@@ -816,6 +1397,11 @@ pub mod tests {
 		ETH_BACKUP_HTTP_ENDPOINT => "http://second.localhost:8545",
 		ETH_BACKUP_WS_ENDPOINT => "ws://second.localhost:8545",
 
+		ARB_HTTP_ENDPOINT => "http://localhost:8547",
+		ARB_WS_ENDPOINT => "ws://localhost:8548",
+		ARB_BACKUP_HTTP_ENDPOINT => "http://second.localhost:8547",
+		ARB_BACKUP_WS_ENDPOINT => "ws://second.localhost:8548",
+
 		NODE_P2P_IP_ADDRESS => "1.1.1.1",
 
 		BTC_HTTP_ENDPOINT => "http://localhost:18443",
@@ -845,6 +1431,8 @@ pub mod tests {
 		test_base_config_path_command_line_option();
 
 		test_all_command_line_options();
+
+		arb_settings_are_optional();
 	}
 
 	fn settings_valid_if_only_all_the_environment_set() {
@@ -866,6 +1454,19 @@ pub mod tests {
 			settings.dot.nodes.backup.unwrap().ws_endpoint.as_ref(),
 			"wss://second.my_fake_polkadot_rpc:443/<secret_key>"
 		);
+		let arb_settings = settings.arb.unwrap();
+		assert_eq!(arb_settings.nodes.primary.http_endpoint.as_ref(), "http://localhost:8547");
+		assert_eq!(
+			arb_settings.nodes.backup.unwrap().ws_endpoint.as_ref(),
+			"ws://second.localhost:8548"
+		);
+		assert_eq!(settings.eth.witnessing_mode, EthWitnessingMode::FixedLag);
+		assert_eq!(settings.dot.witnessing_mode, DotWitnessingMode::Finalized);
+		assert!(!settings.eth.cross_check_rpcs);
+		assert!(!arb_settings.cross_check_rpcs);
+		assert!(!settings.dot.cross_check_rpcs);
+		assert!(!settings.btc.cross_check_rpcs);
+		assert!(!settings.btc.block_filters);
 	}
 
 	fn test_init_config_with_testing_config() {
@@ -908,6 +1509,16 @@ pub mod tests {
 			"http://localhost:18443".into()
 		);
 		assert!(custom_base_path_settings.btc.nodes.backup.is_none());
+
+		assert_eq!(
+			custom_base_path_settings.eth.nodes.primary.rate_limit,
+			Some(RateLimit {
+				requests_per_second: NonZeroU32::new(25),
+				burst: None,
+				daily_budget: NonZeroU64::new(100000),
+			})
+		);
+		assert!(custom_base_path_settings.btc.nodes.primary.rate_limit.is_none());
 	}
 
 	fn test_all_command_line_options() {
@@ -922,6 +1533,8 @@ pub mod tests {
 				ip_address: Some("1.1.1.1".parse().unwrap()),
 				p2p_port: Some(8087),
 				allow_local_ip: Some(false),
+				transport: Some(P2PTransport::Noise),
+				enable_relay: Some(true),
 			},
 			state_chain_opts: StateChainOptions {
 				state_chain_ws_endpoint: Some("ws://endpoint:1234".to_owned()),
@@ -935,6 +1548,9 @@ pub mod tests {
 				eth_backup_ws_endpoint: Some("ws://second_endpoint:4321".to_owned()),
 				eth_backup_http_endpoint: Some("http://second_endpoint:4321".to_owned()),
 				eth_private_key_file: Some(PathBuf::from_str("keys/eth_private_key_2").unwrap()),
+				eth_witnessing_mode: Some(EthWitnessingMode::Safe),
+				eth_recording_file: Some(PathBuf::from_str("recordings/eth.jsonl").unwrap()),
+				eth_cross_check_rpcs: Some(true),
 			},
 			dot_opts: DotOptions {
 				dot_ws_endpoint: Some("ws://endpoint:4321".to_owned()),
@@ -942,6 +1558,9 @@ pub mod tests {
 
 				dot_backup_ws_endpoint: Some("ws://second.endpoint:4321".to_owned()),
 				dot_backup_http_endpoint: Some("http://second.endpoint:4321".to_owned()),
+				dot_witnessing_mode: Some(DotWitnessingMode::FixedLag),
+				dot_recording_file: None,
+				dot_cross_check_rpcs: Some(true),
 			},
 			btc_opts: BtcOptions {
 				btc_http_endpoint: Some("http://btc-endpoint:4321".to_owned()),
@@ -951,14 +1570,30 @@ pub mod tests {
 				btc_backup_http_endpoint: Some("http://second.btc-endpoint:4321".to_owned()),
 				btc_backup_basic_auth_user: Some("second.my_username".to_owned()),
 				btc_backup_basic_auth_password: Some("second.my_password".to_owned()),
+				btc_recording_file: None,
+				btc_cross_check_rpcs: Some(true),
+				btc_block_filters: Some(true),
+			},
+			arb_opts: ArbOptions {
+				arb_ws_endpoint: Some("ws://arb-endpoint:4321".to_owned()),
+				arb_http_endpoint: Some("http://arb-endpoint:4321".to_owned()),
+				arb_backup_ws_endpoint: Some("ws://second.arb-endpoint:4321".to_owned()),
+				arb_backup_http_endpoint: Some("http://second.arb-endpoint:4321".to_owned()),
+				arb_private_key_file: Some(PathBuf::from_str("keys/eth_private_key_2").unwrap()),
+				arb_recording_file: Some(PathBuf::from_str("recordings/arb.jsonl").unwrap()),
+				arb_cross_check_rpcs: Some(true),
 			},
 			health_check_hostname: Some("health_check_hostname".to_owned()),
 			health_check_port: Some(1337),
 			prometheus_hostname: Some(("prometheus_hostname").to_owned()),
 			prometheus_port: Some(9999),
+			observer_listen_address: Some("127.0.0.1:13337".parse().unwrap()),
 			signing_db_file: Some(PathBuf::from_str("also/not/real.db").unwrap()),
+			signing_db_encryption_key_file: None,
+			signing_prune_expired_key_shares: Some(true),
 			logging_span_lifecycle: true,
 			logging_command_server_port: Some(6969),
+			cmd: None,
 		};
 
 		// Load the test opts into the settings
@@ -971,6 +1606,8 @@ pub mod tests {
 		assert_eq!(opts.p2p_opts.p2p_port.unwrap(), settings.node_p2p.port);
 		assert_eq!(opts.p2p_opts.ip_address.unwrap(), settings.node_p2p.ip_address);
 		assert_eq!(opts.p2p_opts.allow_local_ip.unwrap(), settings.node_p2p.allow_local_ip);
+		assert_eq!(opts.p2p_opts.transport.unwrap(), settings.node_p2p.transport);
+		assert_eq!(opts.p2p_opts.enable_relay.unwrap(), settings.node_p2p.enable_relay);
 
 		assert_eq!(
 			opts.state_chain_opts.state_chain_ws_endpoint.unwrap(),
@@ -998,6 +1635,33 @@ pub mod tests {
 		);
 
 		assert!(settings.eth.private_key_file.ends_with("eth_private_key_2"));
+		assert_eq!(opts.eth_opts.eth_witnessing_mode.unwrap(), settings.eth.witnessing_mode);
+		assert!(settings.eth.recording_file.unwrap().ends_with("recordings/eth.jsonl"));
+		assert_eq!(opts.eth_opts.eth_cross_check_rpcs.unwrap(), settings.eth.cross_check_rpcs);
+
+		let arb_settings = settings.arb.unwrap();
+		assert_eq!(
+			opts.arb_opts.arb_ws_endpoint.unwrap(),
+			arb_settings.nodes.primary.ws_endpoint.as_ref()
+		);
+		assert_eq!(
+			opts.arb_opts.arb_http_endpoint.unwrap(),
+			arb_settings.nodes.primary.http_endpoint.as_ref()
+		);
+
+		let arb_backup_node = arb_settings.nodes.backup.unwrap();
+		assert_eq!(
+			opts.arb_opts.arb_backup_ws_endpoint.unwrap(),
+			arb_backup_node.ws_endpoint.as_ref()
+		);
+		assert_eq!(
+			opts.arb_opts.arb_backup_http_endpoint.unwrap(),
+			arb_backup_node.http_endpoint.as_ref()
+		);
+
+		assert!(arb_settings.private_key_file.ends_with("eth_private_key_2"));
+		assert!(arb_settings.recording_file.unwrap().ends_with("recordings/arb.jsonl"));
+		assert_eq!(opts.arb_opts.arb_cross_check_rpcs.unwrap(), arb_settings.cross_check_rpcs);
 
 		assert_eq!(
 			opts.dot_opts.dot_ws_endpoint.unwrap(),
@@ -1017,6 +1681,9 @@ pub mod tests {
 			opts.dot_opts.dot_backup_http_endpoint.unwrap(),
 			dot_backup_node.http_endpoint.as_ref()
 		);
+		assert_eq!(opts.dot_opts.dot_witnessing_mode.unwrap(), settings.dot.witnessing_mode);
+		assert_eq!(settings.dot.recording_file, None);
+		assert_eq!(opts.dot_opts.dot_cross_check_rpcs.unwrap(), settings.dot.cross_check_rpcs);
 
 		assert_eq!(
 			opts.btc_opts.btc_http_endpoint.unwrap(),
@@ -1040,6 +1707,8 @@ pub mod tests {
 			opts.btc_opts.btc_backup_basic_auth_password.unwrap(),
 			btc_backup_node.basic_auth_password
 		);
+		assert_eq!(opts.btc_opts.btc_cross_check_rpcs.unwrap(), settings.btc.cross_check_rpcs);
+		assert_eq!(opts.btc_opts.btc_block_filters.unwrap(), settings.btc.block_filters);
 
 		assert_eq!(
 			opts.health_check_hostname.unwrap(),
@@ -1053,7 +1722,40 @@ pub mod tests {
 		);
 		assert_eq!(opts.prometheus_port.unwrap(), settings.prometheus.as_ref().unwrap().port);
 
+		assert_eq!(
+			opts.observer_listen_address.unwrap(),
+			settings.observer.as_ref().unwrap().listen_address
+		);
+
 		assert!(settings.signing.db_file.ends_with("not/real.db"));
+		assert!(settings.signing.prune_expired_key_shares);
+	}
+
+	fn arb_settings_are_optional() {
+		let settings_dir = tempfile::tempdir().unwrap();
+		let testing_settings = std::fs::read_to_string(
+			PathBuf::from(env!("CF_TEST_CONFIG_ROOT")).join("config/Settings.toml"),
+		)
+		.unwrap();
+		std::fs::write(
+			settings_dir.path().join("Settings.toml"),
+			testing_settings
+				.split("\n\n")
+				.filter(|section| !section.trim_start().starts_with("[arb"))
+				.collect::<Vec<_>>()
+				.join("\n\n"),
+		)
+		.unwrap();
+
+		// The settings dir is absolute, so it replaces the config root when the settings file is
+		// located, while keys are still resolved relative to the config root.
+		let settings = Settings::new_with_settings_dir(
+			settings_dir.path().to_str().unwrap(),
+			CommandLineOptions::default(),
+		)
+		.unwrap();
+
+		assert!(settings.arb.is_none());
 	}
 
 	#[test]
@@ -1117,12 +1819,17 @@ pub mod tests {
 				primary: WsHttpEndpoints {
 					ws_endpoint: "wss://valid.endpoint_with_port:443/secret_key".into(),
 					http_endpoint: "https://valid.endpoint_with_port:443/secret_key".into(),
+					rate_limit: None,
 				},
 				backup: Some(WsHttpEndpoints {
 					ws_endpoint: "ws://valid.endpoint_with_port:1234".into(),
 					http_endpoint: "http://valid.endpoint_with_port:6969".into(),
+					rate_limit: None,
 				}),
 			},
+			witnessing_mode: DotWitnessingMode::Finalized,
+			recording_file: None,
+			cross_check_rpcs: true,
 		};
 		assert_ok!(valid_settings.validate_settings());
 
@@ -1135,8 +1842,22 @@ pub mod tests {
 		invalid_backup_settings.nodes.backup = Some(WsHttpEndpoints {
 			ws_endpoint: "ws://valid.endpoint_with_port:443".into(),
 			http_endpoint: "http://invalid.no_port_in_url/secret_key".into(),
+			rate_limit: None,
 		});
 		assert!(invalid_backup_settings.validate_settings().is_err());
+
+		let mut invalid_cross_check_settings = valid_settings.clone();
+		invalid_cross_check_settings.nodes.backup = None;
+		assert!(invalid_cross_check_settings.validate_settings().is_err());
+	}
+
+	#[test]
+	fn safe_witnessing_mode_is_only_supported_for_ethereum() {
+		assert_eq!(
+			serde_json::from_str::<EthWitnessingMode>("\"safe\"").unwrap(),
+			EthWitnessingMode::Safe
+		);
+		assert!(serde_json::from_str::<DotWitnessingMode>("\"safe\"").is_err());
 	}
 
 	#[test]
@@ -11,7 +11,7 @@ use tokio::sync::{mpsc, oneshot};
 use tracing::trace;
 use utilities::task_scope::{task_scope, Scope, ScopedJoinHandle, OR_CANCEL};
 
-use crate::constants::SIGNED_EXTRINSIC_LIFETIME;
+use crate::{constants::SIGNED_EXTRINSIC_LIFETIME, health};
 
 use super::{
 	super::{base_rpc_api, StateChainStreamApi},
@@ -21,6 +21,8 @@ use super::{
 pub mod signer;
 mod submission_watcher;
 
+pub use submission_watcher::{DryRunError, ExtrinsicError, FinalizationError, InBlockError};
+
 // Wrapper type to avoid await.await on submits/finalize calls being possible
 #[cfg_attr(test, mockall::automock)]
 #[async_trait]
@@ -175,6 +177,7 @@ impl SignedExtrinsicClient {
 					utilities::loop_select! {
 						if let Some((call, until_in_block_sender, until_finalized_sender, strategy)) = request_receiver.recv() => {
 							submission_watcher.new_request(&mut requests, call, until_in_block_sender, until_finalized_sender, strategy).await?;
+							health::record_pending_extrinsics(requests.len());
 						} else break Ok(()),
 						if let Some((call, result_sender)) = dry_run_receiver.recv() => {
 							let _ = result_sender.send(submission_watcher.dry_run_extrinsic(call).await.map_err(Into::into));
@@ -188,6 +191,7 @@ impl SignedExtrinsicClient {
 								&mut requests,
 								block.hash,
 							).await?;
+							health::record_pending_extrinsics(requests.len());
 						} else break Ok(()),
 					}
 				}.boxed())
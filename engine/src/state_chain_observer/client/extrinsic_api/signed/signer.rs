@@ -1,4 +1,5 @@
-use codec::Encode;
+use anyhow::{ensure, Result};
+use codec::{Decode, Encode};
 use sp_core::Pair;
 use sp_runtime::{
 	generic::Era,
@@ -8,6 +9,71 @@ use sp_runtime::{
 use sp_version::RuntimeVersion;
 use state_chain_runtime::{AccountId, Signature};
 
+/// Everything that is needed to sign an extrinsic. This allows extrinsics to be built on a machine
+/// that is connected to the State Chain, and signed on one that isn't.
+#[derive(Clone, Debug, PartialEq, Eq, Encode, Decode)]
+pub struct UnsignedPayload {
+	pub call: state_chain_runtime::RuntimeCall,
+	pub signer: AccountId,
+	pub nonce: state_chain_runtime::Nonce,
+	pub era: Era,
+	/// The hash of the block the era starts at.
+	pub era_block_hash: state_chain_runtime::Hash,
+	pub genesis_hash: state_chain_runtime::Hash,
+	pub spec_version: u32,
+	pub transaction_version: u32,
+}
+
+impl UnsignedPayload {
+	/// Returns the payload, and the range of blocks the extrinsic can be included in.
+	#[allow(clippy::too_many_arguments)]
+	pub fn new(
+		call: state_chain_runtime::RuntimeCall,
+		signer: AccountId,
+		runtime_version: &RuntimeVersion,
+		genesis_hash: state_chain_runtime::Hash,
+		current_hash: state_chain_runtime::Hash,
+		current_block_number: state_chain_runtime::BlockNumber,
+		lifetime: state_chain_runtime::BlockNumber,
+		nonce: state_chain_runtime::Nonce,
+	) -> (Self, std::ops::RangeTo<state_chain_runtime::BlockNumber>) {
+		assert!(lifetime <= state_chain_runtime::BlockHashCount::get());
+
+		let era = Era::mortal(lifetime as u64, current_block_number as u64);
+
+		let lifetime = ..era.death(current_block_number as u64) as state_chain_runtime::BlockNumber;
+
+		(
+			Self {
+				call,
+				signer,
+				nonce,
+				era,
+				era_block_hash: current_hash,
+				genesis_hash,
+				spec_version: runtime_version.spec_version,
+				transaction_version: runtime_version.transaction_version,
+			},
+			lifetime,
+		)
+	}
+
+	fn extra(&self) -> state_chain_runtime::SignedExtra {
+		(
+			frame_system::CheckNonZeroSender::new(),
+			frame_system::CheckSpecVersion::new(),
+			frame_system::CheckTxVersion::new(),
+			frame_system::CheckGenesis::new(),
+			frame_system::CheckEra::from(self.era),
+			frame_system::CheckNonce::from(self.nonce),
+			frame_system::CheckWeight::new(),
+			// This is the tx fee tip. Normally this determines transaction priority. We currently
+			// ignore this in the runtime but it needs to be set to some default value.
+			state_chain_runtime::ChargeTransactionPayment::from(0),
+		)
+	}
+}
+
 /// A wrapper around a substrate [`Pair`] that can be used for signing.
 #[derive(Clone, Debug)]
 pub struct PairSigner<P: Pair> {
@@ -41,50 +107,105 @@ where
 		state_chain_runtime::UncheckedExtrinsic,
 		std::ops::RangeTo<state_chain_runtime::BlockNumber>,
 	) {
-		assert!(lifetime <= state_chain_runtime::BlockHashCount::get());
-
-		let era = Era::mortal(lifetime as u64, current_block_number as u64);
+		let (payload, lifetime) = UnsignedPayload::new(
+			call,
+			self.account_id.clone(),
+			runtime_version,
+			genesis_hash,
+			current_hash,
+			current_block_number,
+			lifetime,
+			nonce,
+		);
 
-		let lifetime = ..era.death(current_block_number as u64) as state_chain_runtime::BlockNumber;
+		(self.sign(payload).expect("The payload was built for this signer"), lifetime)
+	}
 
-		let extra: state_chain_runtime::SignedExtra = (
-			frame_system::CheckNonZeroSender::new(),
-			frame_system::CheckSpecVersion::new(),
-			frame_system::CheckTxVersion::new(),
-			frame_system::CheckGenesis::new(),
-			frame_system::CheckEra::from(era),
-			frame_system::CheckNonce::from(nonce),
-			frame_system::CheckWeight::new(),
-			// This is the tx fee tip. Normally this determines transaction priority. We currently
-			// ignore this in the runtime but it needs to be set to some default value.
-			state_chain_runtime::ChargeTransactionPayment::from(0),
+	/// Signs a payload that was built for this signer's account.
+	pub fn sign(
+		&self,
+		payload: UnsignedPayload,
+	) -> Result<state_chain_runtime::UncheckedExtrinsic> {
+		ensure!(
+			payload.signer == self.account_id,
+			"The payload must be signed by account {}, but the key is for account {}",
+			payload.signer,
+			self.account_id
 		);
+
+		let extra = payload.extra();
 		let additional_signed = (
 			(),
-			runtime_version.spec_version,
-			runtime_version.transaction_version,
-			genesis_hash,
-			current_hash,
+			payload.spec_version,
+			payload.transaction_version,
+			payload.genesis_hash,
+			payload.era_block_hash,
 			(),
 			(),
 			(),
 		);
 
 		let signed_payload = state_chain_runtime::SignedPayload::from_raw(
-			call.clone(),
+			payload.call.clone(),
 			extra.clone(),
 			additional_signed,
 		);
 		let signature = signed_payload.using_encoded(|bytes| self.signer.sign(bytes).into());
 
-		(
-			state_chain_runtime::UncheckedExtrinsic::new_signed(
-				call,
-				MultiAddress::Id(self.account_id.clone()),
-				signature,
-				extra,
-			),
-			lifetime,
+		Ok(state_chain_runtime::UncheckedExtrinsic::new_signed(
+			payload.call,
+			MultiAddress::Id(self.account_id.clone()),
+			signature,
+			extra,
+		))
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn test_payload(signer: AccountId) -> UnsignedPayload {
+		UnsignedPayload::new(
+			frame_system::Call::remark { remark: vec![] }.into(),
+			signer,
+			&RuntimeVersion { spec_version: 1, transaction_version: 2, ..Default::default() },
+			sp_core::H256::repeat_byte(1),
+			sp_core::H256::repeat_byte(2),
+			100,
+			64,
+			5,
 		)
+		.0
+	}
+
+	#[test]
+	fn signing_an_encoded_payload_matches_signing_directly() {
+		// ed25519 signatures are deterministic, so the extrinsics can be compared.
+		let signer = PairSigner::new(sp_core::ed25519::Pair::from_seed(&[7; 32]));
+
+		let (signed_extrinsic, _) = signer.new_signed_extrinsic(
+			frame_system::Call::remark { remark: vec![] }.into(),
+			&RuntimeVersion { spec_version: 1, transaction_version: 2, ..Default::default() },
+			sp_core::H256::repeat_byte(1),
+			sp_core::H256::repeat_byte(2),
+			100,
+			64,
+			5,
+		);
+
+		let payload =
+			UnsignedPayload::decode(&mut &test_payload(signer.account_id.clone()).encode()[..])
+				.unwrap();
+
+		assert_eq!(signer.sign(payload).unwrap().encode(), signed_extrinsic.encode());
+	}
+
+	#[test]
+	fn cannot_sign_payload_for_another_account() {
+		let signer = PairSigner::new(sp_core::ed25519::Pair::from_seed(&[7; 32]));
+		let other_signer = PairSigner::new(sp_core::ed25519::Pair::from_seed(&[8; 32]));
+
+		assert!(other_signer.sign(test_payload(signer.account_id.clone())).is_err());
 	}
 }
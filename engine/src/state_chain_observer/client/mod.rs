@@ -25,6 +25,8 @@ use utilities::{
 	CachedStream, MakeCachedStream, MakeTryCachedStream, TryCachedStream,
 };
 
+use crate::health;
+
 use self::{
 	base_rpc_api::BaseRpcClient,
 	chain_api::ChainApi,
@@ -320,6 +322,7 @@ async fn create_finalized_block_subscription<
 				let result_block = finalized_block_stream.next().map(|option| option.unwrap()) => {
 					let block = result_block?;
 					latest_block = block;
+					health::record_state_chain_block(block.number);
 					if let Some((required_version, _)) = required_version_and_wait {
 						let current_release_version = base_rpc_client.storage_value::<pallet_cf_environment::CurrentReleaseVersion<state_chain_runtime::Runtime>>(block.hash).await?;
 						if !required_version.is_compatible_with(current_release_version) {
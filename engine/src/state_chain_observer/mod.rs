@@ -3,6 +3,6 @@ pub mod client;
 mod sc_observer;
 
 #[cfg(test)]
-mod test_helpers;
+pub mod test_helpers;
 
 pub use sc_observer::start;
@@ -5,9 +5,12 @@ use cf_chains::{
 	ChainCrypto,
 };
 use multisig::{
-	bitcoin::BtcSigning, eth::EthSigning, polkadot::PolkadotSigning, ChainSigning, CryptoScheme,
+	bitcoin::BtcSigning,
+	eth::{ArbSigning, EthSigning},
+	polkadot::PolkadotSigning,
+	ChainSigning, CryptoScheme,
 };
-use state_chain_runtime::{BitcoinInstance, EthereumInstance, PolkadotInstance};
+use state_chain_runtime::{ArbitrumInstance, BitcoinInstance, EthereumInstance, PolkadotInstance};
 
 /// Compatibility layer for converting between public keys generated using the [CryptoScheme] types
 /// and the on-chain representation as defined by [ChainCrypto].
@@ -25,6 +28,14 @@ impl CryptoCompat<EthSigning, EvmCrypto> for EthereumInstance {
 	}
 }
 
+impl CryptoCompat<ArbSigning, EvmCrypto> for ArbitrumInstance {
+	fn pubkey_to_aggkey(
+		pubkey: <<ArbSigning as ChainSigning>::CryptoScheme as CryptoScheme>::PublicKey,
+	) -> <EvmCrypto as ChainCrypto>::AggKey {
+		pubkey
+	}
+}
+
 impl CryptoCompat<BtcSigning, BitcoinCrypto> for BitcoinInstance {
 	fn pubkey_to_aggkey(
 		pubkey: <<BtcSigning as ChainSigning>::CryptoScheme as CryptoScheme>::PublicKey,
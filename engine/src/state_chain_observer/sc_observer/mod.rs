@@ -7,11 +7,13 @@ use cf_chains::{
 	btc::{self, PreviousOrCurrent},
 	Chain,
 };
-use cf_primitives::{BlockNumber, CeremonyId, EpochIndex};
+use cf_primitives::{BlockNumber, CeremonyFailureEvidence, CeremonyId, EpochIndex};
 use crypto_compat::CryptoCompat;
 use futures::{FutureExt, StreamExt};
 use sp_runtime::AccountId32;
-use state_chain_runtime::{AccountId, BitcoinInstance, EthereumInstance, PolkadotInstance};
+use state_chain_runtime::{
+	AccountId, ArbitrumInstance, BitcoinInstance, EthereumInstance, PolkadotInstance,
+};
 use std::{
 	collections::BTreeSet,
 	sync::{
@@ -21,7 +23,7 @@ use std::{
 	time::Duration,
 };
 use tokio::sync::mpsc::UnboundedSender;
-use tracing::{debug, error, info, info_span, Instrument};
+use tracing::{debug, error, info, info_span, warn, Instrument};
 
 use crate::{
 	btc::retry_rpc::BtcRetryRpcApi,
@@ -38,12 +40,51 @@ use crate::{
 	},
 };
 use multisig::{
-	bitcoin::BtcCryptoScheme, client::MultisigClientApi, eth::EvmCryptoScheme,
-	polkadot::PolkadotCryptoScheme, ChainSigning, CryptoScheme, KeyId,
-	SignatureToThresholdSignature,
+	bitcoin::BtcCryptoScheme,
+	client::{BroadcastConflicts, CeremonyFailureReason, MultisigClientApi},
+	eth::EvmCryptoScheme,
+	polkadot::PolkadotCryptoScheme,
+	ChainSigning, CryptoScheme, KeyId, SignatureToThresholdSignature,
 };
 use utilities::task_scope::{task_scope, Scope};
 
+/// The evidence supporting our report of the parties that caused a ceremony to fail, if any.
+fn failure_evidence(
+	bad_account_ids: &BTreeSet<AccountId>,
+	reason: &impl CeremonyFailureReason,
+	conflicts: &BroadcastConflicts,
+) -> Option<CeremonyFailureEvidence> {
+	reason.evidence(conflicts).filter(|_| !bad_account_ids.is_empty())
+}
+
+/// Submits the evidence supporting our report of the parties that caused a ceremony to fail. This
+/// is done after submitting the report itself, so that the report never waits for (or depends on)
+/// the evidence. The ceremony is still pending when the evidence is included, as ceremonies are
+/// only resolved after their reports are processed.
+async fn submit_failure_evidence<StateChainClient, I>(
+	state_chain_client: &StateChainClient,
+	ceremony_id: CeremonyId,
+	evidence: Option<CeremonyFailureEvidence>,
+) where
+	StateChainClient: SignedExtrinsicApi + Send + Sync,
+	I: 'static + Sync + Send,
+	state_chain_runtime::Runtime: pallet_cf_threshold_signature::Config<I>,
+	state_chain_runtime::RuntimeCall:
+		std::convert::From<pallet_cf_threshold_signature::Call<state_chain_runtime::Runtime, I>>,
+{
+	if let Some(evidence) = evidence {
+		state_chain_client
+			.finalize_signed_extrinsic(pallet_cf_threshold_signature::Call::<
+				state_chain_runtime::Runtime,
+				I,
+			>::report_failure_evidence {
+				ceremony_id,
+				evidence,
+			})
+			.await;
+	}
+}
+
 async fn handle_keygen_request<'a, StateChainClient, MultisigClient, C, I>(
 	scope: &Scope<'a, anyhow::Error>,
 	multisig_client: &'a MultisigClient,
@@ -60,24 +101,32 @@ async fn handle_keygen_request<'a, StateChainClient, MultisigClient, C, I>(
 	I: CryptoCompat<C, C::ChainCrypto> + 'static + Sync + Send,
 	state_chain_runtime::RuntimeCall:
 		std::convert::From<pallet_cf_vaults::Call<state_chain_runtime::Runtime, I>>,
+	state_chain_runtime::Runtime: pallet_cf_threshold_signature::Config<I>,
+	state_chain_runtime::RuntimeCall:
+		std::convert::From<pallet_cf_threshold_signature::Call<state_chain_runtime::Runtime, I>>,
 {
 	if keygen_participants.contains(&state_chain_client.account_id()) {
 		// We initiate keygen outside of the spawn to avoid requesting ceremonies out of order
 		let keygen_result_future =
 			multisig_client.initiate_keygen(ceremony_id, epoch_index, keygen_participants);
 		scope.spawn(async move {
+			let (reported_outcome, evidence) = match keygen_result_future.await {
+				Ok(public_key) => (Ok(I::pubkey_to_aggkey(public_key)), None),
+				Err((bad_account_ids, reason, conflicts)) => {
+					let evidence = failure_evidence(&bad_account_ids, &reason, &conflicts);
+					(Err(bad_account_ids), evidence)
+				},
+			};
 			state_chain_client
 				.finalize_signed_extrinsic(pallet_cf_vaults::Call::<
 					state_chain_runtime::Runtime,
 					I,
 				>::report_keygen_outcome {
 					ceremony_id,
-					reported_outcome: keygen_result_future
-						.await
-						.map(I::pubkey_to_aggkey)
-						.map_err(|(bad_account_ids, _reason)| bad_account_ids),
+					reported_outcome,
 				})
 				.await;
+			submit_failure_evidence::<_, I>(&*state_chain_client, ceremony_id, evidence).await;
 			Ok(())
 		});
 	} else {
@@ -104,6 +153,10 @@ async fn handle_key_handover_request<'a, StateChainClient, MultisigClient>(
 	state_chain_runtime::Runtime: pallet_cf_vaults::Config<BitcoinInstance>,
 	state_chain_runtime::RuntimeCall:
 		std::convert::From<pallet_cf_vaults::Call<state_chain_runtime::Runtime, BitcoinInstance>>,
+	state_chain_runtime::Runtime: pallet_cf_threshold_signature::Config<BitcoinInstance>,
+	state_chain_runtime::RuntimeCall: std::convert::From<
+		pallet_cf_threshold_signature::Call<state_chain_runtime::Runtime, BitcoinInstance>,
+	>,
 {
 	let account_id = &state_chain_client.account_id();
 	if sharing_participants.contains(account_id) || receiving_participants.contains(account_id) {
@@ -115,21 +168,82 @@ async fn handle_key_handover_request<'a, StateChainClient, MultisigClient>(
 			receiving_participants,
 		);
 		scope.spawn(async move {
+			let (reported_outcome, evidence) = match key_handover_result_future.await {
+				Ok(handover_key) => {
+					assert!(new_key.previous.replace(handover_key.serialize()).is_none());
+					(Ok(new_key), None)
+				},
+				Err((bad_account_ids, reason, conflicts)) => {
+					let evidence = failure_evidence(&bad_account_ids, &reason, &conflicts);
+					(Err(bad_account_ids), evidence)
+				},
+			};
 			let _result = state_chain_client
 				.finalize_signed_extrinsic(pallet_cf_vaults::Call::<
 					state_chain_runtime::Runtime,
 					BitcoinInstance,
 				>::report_key_handover_outcome {
 					ceremony_id,
-					reported_outcome: key_handover_result_future
-						.await
-						.map(move |handover_key| {
-							assert!(new_key.previous.replace(handover_key.serialize()).is_none());
-							new_key
-						})
-						.map_err(|(bad_account_ids, _reason)| bad_account_ids),
+					reported_outcome,
+				})
+				.await;
+			submit_failure_evidence::<_, BitcoinInstance>(
+				&*state_chain_client,
+				ceremony_id,
+				evidence,
+			)
+			.await;
+			Ok(())
+		});
+	} else {
+		multisig_client.update_latest_ceremony_id(ceremony_id);
+	}
+}
+
+async fn handle_share_refresh_request<'a, StateChainClient, MultisigClient, C, I>(
+	scope: &Scope<'a, anyhow::Error>,
+	multisig_client: &'a MultisigClient,
+	state_chain_client: Arc<StateChainClient>,
+	ceremony_id: CeremonyId,
+	key_id: KeyId,
+	key: <<<state_chain_runtime::Runtime as pallet_cf_vaults::Config<I>>::Chain as Chain>::ChainCrypto as cf_chains::ChainCrypto>::AggKey,
+	participants: BTreeSet<AccountId32>,
+) where
+	MultisigClient: MultisigClientApi<C::CryptoScheme>,
+	StateChainClient: SignedExtrinsicApi + 'static + Send + Sync,
+	state_chain_runtime::Runtime: pallet_cf_vaults::Config<I>,
+	C: ChainSigning<ChainCrypto = <<state_chain_runtime::Runtime as pallet_cf_vaults::Config<I>>::Chain as Chain>::ChainCrypto>
+		+ 'static,
+	I: CryptoCompat<C, C::ChainCrypto> + 'static + Sync + Send,
+	state_chain_runtime::RuntimeCall:
+		std::convert::From<pallet_cf_vaults::Call<state_chain_runtime::Runtime, I>>,
+	state_chain_runtime::Runtime: pallet_cf_threshold_signature::Config<I>,
+	state_chain_runtime::RuntimeCall:
+		std::convert::From<pallet_cf_threshold_signature::Call<state_chain_runtime::Runtime, I>>,
+{
+	if participants.contains(&state_chain_client.account_id()) {
+		let share_refresh_result_future =
+			multisig_client.initiate_share_refresh(ceremony_id, key_id, participants);
+		scope.spawn(async move {
+			// The multisig client has already checked that the refreshed shares are for the same
+			// key, so we report the key exactly as it is on chain.
+			let (reported_outcome, evidence) = match share_refresh_result_future.await {
+				Ok(_) => (Ok(key), None),
+				Err((bad_account_ids, reason, conflicts)) => {
+					let evidence = failure_evidence(&bad_account_ids, &reason, &conflicts);
+					(Err(bad_account_ids), evidence)
+				},
+			};
+			state_chain_client
+				.finalize_signed_extrinsic(pallet_cf_vaults::Call::<
+					state_chain_runtime::Runtime,
+					I,
+				>::report_share_refresh_outcome {
+					ceremony_id,
+					reported_outcome,
 				})
 				.await;
+			submit_failure_evidence::<_, I>(&*state_chain_client, ceremony_id, evidence).await;
 			Ok(())
 		});
 	} else {
@@ -155,6 +269,92 @@ async fn handle_signing_request<'a, StateChainClient, MultisigClient, C, I>(
 	Vec<C::Signature>: SignatureToThresholdSignature<
 		<state_chain_runtime::Runtime as pallet_cf_threshold_signature::Config<I>>::TargetChainCrypto,
 	>,
+{
+	handle_signing_request_with_outcome::<_, _, C, I>(
+		scope,
+		multisig_client,
+		state_chain_client,
+		ceremony_id,
+		signers,
+		signing_info,
+		move |signatures| pallet_cf_threshold_signature::Call::signature_success {
+			ceremony_id,
+			signature: signatures.to_threshold_signature(),
+		},
+	)
+	.await
+}
+
+/// Signs a batch of independent requests in a single ceremony. Each payload results in its own
+/// threshold signature, reported in the same order as the payloads were requested.
+async fn handle_batch_signing_request<'a, StateChainClient, MultisigClient, C, I>(
+	scope: &Scope<'a, anyhow::Error>,
+	multisig_client: &'a MultisigClient,
+	state_chain_client: Arc<StateChainClient>,
+	ceremony_id: CeremonyId,
+	signers: BTreeSet<AccountId>,
+	signing_info: Vec<(KeyId, C::SigningPayload)>,
+) where
+	MultisigClient: MultisigClientApi<C>,
+	StateChainClient: SignedExtrinsicApi + UnsignedExtrinsicApi + 'static + Send + Sync,
+	C: CryptoScheme,
+	I: 'static + Sync + Send,
+	state_chain_runtime::Runtime: pallet_cf_threshold_signature::Config<I>,
+	state_chain_runtime::RuntimeCall:
+		std::convert::From<pallet_cf_threshold_signature::Call<state_chain_runtime::Runtime, I>>,
+	Vec<C::Signature>: SignatureToThresholdSignature<
+		<state_chain_runtime::Runtime as pallet_cf_threshold_signature::Config<I>>::TargetChainCrypto,
+	>,
+{
+	if signing_info.len() > cf_primitives::MAX_SIGNING_BATCH_SIZE as usize {
+		error!(
+			ceremony_id = ceremony_id,
+			"Too many payloads, ignoring batch signing request ({}/{})",
+			signing_info.len(),
+			cf_primitives::MAX_SIGNING_BATCH_SIZE
+		);
+		multisig_client.update_latest_ceremony_id(ceremony_id);
+		return
+	}
+
+	handle_signing_request_with_outcome::<_, _, C, I>(
+		scope,
+		multisig_client,
+		state_chain_client,
+		ceremony_id,
+		signers,
+		signing_info,
+		move |signatures| pallet_cf_threshold_signature::Call::batch_signature_success {
+			ceremony_id,
+			signatures: signatures
+				.into_iter()
+				.map(|signature| vec![signature].to_threshold_signature())
+				.collect(),
+		},
+	)
+	.await
+}
+
+async fn handle_signing_request_with_outcome<'a, StateChainClient, MultisigClient, C, I>(
+	scope: &Scope<'a, anyhow::Error>,
+	multisig_client: &'a MultisigClient,
+	state_chain_client: Arc<StateChainClient>,
+	ceremony_id: CeremonyId,
+	signers: BTreeSet<AccountId>,
+	signing_info: Vec<(KeyId, C::SigningPayload)>,
+	success_call: impl FnOnce(
+			Vec<C::Signature>,
+		) -> pallet_cf_threshold_signature::Call<state_chain_runtime::Runtime, I>
+		+ Send
+		+ 'static,
+) where
+	MultisigClient: MultisigClientApi<C>,
+	StateChainClient: SignedExtrinsicApi + UnsignedExtrinsicApi + 'static + Send + Sync,
+	C: CryptoScheme,
+	I: 'static + Sync + Send,
+	state_chain_runtime::Runtime: pallet_cf_threshold_signature::Config<I>,
+	state_chain_runtime::RuntimeCall:
+		std::convert::From<pallet_cf_threshold_signature::Call<state_chain_runtime::Runtime, I>>,
 {
 	if signers.contains(&state_chain_client.account_id()) {
 		// We initiate signing outside of the spawn to avoid requesting ceremonies out of order
@@ -165,16 +365,11 @@ async fn handle_signing_request<'a, StateChainClient, MultisigClient, C, I>(
 			match signing_result_future.await {
 				Ok(signatures) => {
 					let _result = state_chain_client
-						.submit_unsigned_extrinsic(pallet_cf_threshold_signature::Call::<
-							state_chain_runtime::Runtime,
-							I,
-						>::signature_success {
-							ceremony_id,
-							signature: signatures.to_threshold_signature(),
-						})
+						.submit_unsigned_extrinsic(success_call(signatures))
 						.await;
 				},
-				Err((bad_account_ids, _reason)) => {
+				Err((bad_account_ids, reason, conflicts)) => {
+					let evidence = failure_evidence(&bad_account_ids, &reason, &conflicts);
 					state_chain_client
 						.finalize_signed_extrinsic(pallet_cf_threshold_signature::Call::<
 							state_chain_runtime::Runtime,
@@ -184,6 +379,8 @@ async fn handle_signing_request<'a, StateChainClient, MultisigClient, C, I>(
 							offenders: BTreeSet::from_iter(bad_account_ids),
 						})
 						.await;
+					submit_failure_evidence::<_, I>(&*state_chain_client, ceremony_id, evidence)
+						.await;
 				},
 			}
 			Ok(())
@@ -225,6 +422,8 @@ pub async fn start<
 	EthMultisigClient,
 	PolkadotMultisigClient,
 	BitcoinMultisigClient,
+	ArbRpc,
+	ArbMultisigClient,
 >(
 	state_chain_client: Arc<StateChainClient>,
 	sc_block_stream: BlockStream,
@@ -234,6 +433,7 @@ pub async fn start<
 	eth_multisig_client: EthMultisigClient,
 	dot_multisig_client: PolkadotMultisigClient,
 	btc_multisig_client: BitcoinMultisigClient,
+	arb: Option<(ArbRpc, ArbMultisigClient)>,
 	peer_update_sender: UnboundedSender<PeerUpdate>,
 ) -> Result<(), anyhow::Error>
 where
@@ -244,6 +444,8 @@ where
 	EthMultisigClient: MultisigClientApi<EvmCryptoScheme> + Send + Sync + 'static,
 	PolkadotMultisigClient: MultisigClientApi<PolkadotCryptoScheme> + Send + Sync + 'static,
 	BitcoinMultisigClient: MultisigClientApi<BtcCryptoScheme> + Send + Sync + 'static,
+	ArbRpc: EthersRetryRpcApi + Send + Sync + 'static,
+	ArbMultisigClient: MultisigClientApi<EvmCryptoScheme> + Send + Sync + 'static,
 	StateChainClient:
 		StorageApi + UnsignedExtrinsicApi + SignedExtrinsicApi + 'static + Send + Sync,
 {
@@ -337,6 +539,26 @@ where
                                             participants,
                                         ).await;
                                     }
+                                    state_chain_runtime::RuntimeEvent::ArbitrumVault(
+                                        pallet_cf_vaults::Event::KeygenRequest {
+                                            ceremony_id,
+                                            participants,
+                                            epoch_index
+                                        }
+                                    ) => {
+                                        if let Some((_, arb_multisig_client)) = &arb {
+                                            handle_keygen_request::<_, _, _, ArbitrumInstance>(
+                                                scope,
+                                                arb_multisig_client,
+                                                state_chain_client.clone(),
+                                                ceremony_id,
+                                                epoch_index,
+                                                participants,
+                                            ).await;
+                                        } else if participants.contains(&account_id) {
+                                            warn!("Not taking part in Arbitrum keygen ceremony {ceremony_id}, as Arbitrum is not configured");
+                                        }
+                                    }
                                     state_chain_runtime::RuntimeEvent::PolkadotVault(
                                         pallet_cf_vaults::Event::KeygenRequest {
                                             ceremony_id,
@@ -392,6 +614,83 @@ where
                                         ).await;
                                     }
 
+                                    state_chain_runtime::RuntimeEvent::EthereumThresholdSigner(
+                                        pallet_cf_threshold_signature::Event::ThresholdSignatureBatchRequest{
+                                            request_ids: _,
+                                            ceremony_id,
+                                            epoch,
+                                            key,
+                                            signatories,
+                                            payloads,
+                                        },
+                                    ) => {
+                                        handle_batch_signing_request::<_, _, _, EthereumInstance>(
+                                                scope,
+                                                &eth_multisig_client,
+                                            state_chain_client.clone(),
+                                            ceremony_id,
+                                            signatories,
+                                            payloads.into_iter().map(|payload| (
+                                                KeyId::new(epoch, key),
+                                                multisig::eth::SigningPayload(payload.0)
+                                            )).collect(),
+                                        ).await;
+                                    }
+
+                                    state_chain_runtime::RuntimeEvent::ArbitrumThresholdSigner(
+                                        pallet_cf_threshold_signature::Event::ThresholdSignatureRequest{
+                                            request_id: _,
+                                            ceremony_id,
+                                            epoch,
+                                            key,
+                                            signatories,
+                                            payload,
+                                        },
+                                    ) => {
+                                        if let Some((_, arb_multisig_client)) = &arb {
+                                            handle_signing_request::<_, _, _, ArbitrumInstance>(
+                                                scope,
+                                                arb_multisig_client,
+                                                state_chain_client.clone(),
+                                                ceremony_id,
+                                                signatories,
+                                                vec![(
+                                                    KeyId::new(epoch, key),
+                                                    multisig::eth::SigningPayload(payload.0)
+                                                )],
+                                            ).await;
+                                        } else if signatories.contains(&account_id) {
+                                            warn!("Not taking part in Arbitrum signing ceremony {ceremony_id}, as Arbitrum is not configured");
+                                        }
+                                    }
+
+                                    state_chain_runtime::RuntimeEvent::ArbitrumThresholdSigner(
+                                        pallet_cf_threshold_signature::Event::ThresholdSignatureBatchRequest{
+                                            request_ids: _,
+                                            ceremony_id,
+                                            epoch,
+                                            key,
+                                            signatories,
+                                            payloads,
+                                        },
+                                    ) => {
+                                        if let Some((_, arb_multisig_client)) = &arb {
+                                            handle_batch_signing_request::<_, _, _, ArbitrumInstance>(
+                                                scope,
+                                                arb_multisig_client,
+                                                state_chain_client.clone(),
+                                                ceremony_id,
+                                                signatories,
+                                                payloads.into_iter().map(|payload| (
+                                                    KeyId::new(epoch, key),
+                                                    multisig::eth::SigningPayload(payload.0)
+                                                )).collect(),
+                                            ).await;
+                                        } else if signatories.contains(&account_id) {
+                                            warn!("Not taking part in Arbitrum signing ceremony {ceremony_id}, as Arbitrum is not configured");
+                                        }
+                                    }
+
                                     state_chain_runtime::RuntimeEvent::PolkadotThresholdSigner(
                                         pallet_cf_threshold_signature::Event::ThresholdSignatureRequest{
                                             request_id: _,
@@ -415,6 +714,29 @@ where
                                             )],
                                         ).await;
                                     }
+                                    state_chain_runtime::RuntimeEvent::PolkadotThresholdSigner(
+                                        pallet_cf_threshold_signature::Event::ThresholdSignatureBatchRequest{
+                                            request_ids: _,
+                                            ceremony_id,
+                                            epoch,
+                                            key,
+                                            signatories,
+                                            payloads,
+                                        },
+                                    ) => {
+                                        handle_batch_signing_request::<_, _, _, PolkadotInstance>(
+                                                scope,
+                                                &dot_multisig_client,
+                                            state_chain_client.clone(),
+                                            ceremony_id,
+                                            signatories,
+                                            payloads.into_iter().map(|payload| (
+                                                KeyId::new(epoch, key),
+                                                multisig::polkadot::SigningPayload::new(payload.0)
+                                                    .expect("Payload should be correct size")
+                                            )).collect(),
+                                        ).await;
+                                    }
                                     state_chain_runtime::RuntimeEvent::BitcoinThresholdSigner(
                                         pallet_cf_threshold_signature::Event::ThresholdSignatureRequest{
                                             request_id: _,
@@ -493,6 +815,134 @@ where
                                     ) => {
                                         panic!("There should be no key handover requests made for Polkadot")
                                     }
+                                    state_chain_runtime::RuntimeEvent::ArbitrumVault(
+                                        pallet_cf_vaults::Event::KeyHandoverRequest {
+                                           ..
+                                        },
+                                    ) => {
+                                        panic!("There should be no key handover requests made for Arbitrum")
+                                    }
+                                    // ======= SHARE REFRESH =======
+                                    state_chain_runtime::RuntimeEvent::EthereumVault(
+                                        pallet_cf_vaults::Event::ShareRefreshRequest {
+                                            ceremony_id,
+                                            epoch_index,
+                                            key,
+                                            participants,
+                                        },
+                                    ) => {
+                                        handle_share_refresh_request::<_, _, _, EthereumInstance>(
+                                            scope,
+                                            &eth_multisig_client,
+                                            state_chain_client.clone(),
+                                            ceremony_id,
+                                            KeyId::new(epoch_index, key),
+                                            key,
+                                            participants,
+                                        ).await;
+                                    }
+                                    state_chain_runtime::RuntimeEvent::ArbitrumVault(
+                                        pallet_cf_vaults::Event::ShareRefreshRequest {
+                                            ceremony_id,
+                                            epoch_index,
+                                            key,
+                                            participants,
+                                        },
+                                    ) => {
+                                        if let Some((_, arb_multisig_client)) = &arb {
+                                            handle_share_refresh_request::<_, _, _, ArbitrumInstance>(
+                                                scope,
+                                                arb_multisig_client,
+                                                state_chain_client.clone(),
+                                                ceremony_id,
+                                                KeyId::new(epoch_index, key),
+                                                key,
+                                                participants,
+                                            ).await;
+                                        } else if participants.contains(&account_id) {
+                                            warn!("Not taking part in Arbitrum share refresh ceremony {ceremony_id}, as Arbitrum is not configured");
+                                        }
+                                    }
+                                    state_chain_runtime::RuntimeEvent::PolkadotVault(
+                                        pallet_cf_vaults::Event::ShareRefreshRequest {
+                                            ceremony_id,
+                                            epoch_index,
+                                            key,
+                                            participants,
+                                        },
+                                    ) => {
+                                        handle_share_refresh_request::<_, _, _, PolkadotInstance>(
+                                            scope,
+                                            &dot_multisig_client,
+                                            state_chain_client.clone(),
+                                            ceremony_id,
+                                            KeyId::new(epoch_index, key),
+                                            key,
+                                            participants,
+                                        ).await;
+                                    }
+                                    state_chain_runtime::RuntimeEvent::BitcoinVault(
+                                        pallet_cf_vaults::Event::ShareRefreshRequest {
+                                            ceremony_id,
+                                            epoch_index,
+                                            key,
+                                            participants,
+                                        },
+                                    ) => {
+                                        handle_share_refresh_request::<_, _, _, BitcoinInstance>(
+                                            scope,
+                                            &btc_multisig_client,
+                                            state_chain_client.clone(),
+                                            ceremony_id,
+                                            KeyId::new(epoch_index, key.current),
+                                            key,
+                                            participants,
+                                        ).await;
+                                    }
+                                    state_chain_runtime::RuntimeEvent::EthereumVault(
+                                        pallet_cf_vaults::Event::ShareRefreshSuccess { ceremony_id },
+                                    ) => {
+                                        eth_multisig_client.complete_share_refresh(ceremony_id, true);
+                                    }
+                                    state_chain_runtime::RuntimeEvent::PolkadotVault(
+                                        pallet_cf_vaults::Event::ShareRefreshSuccess { ceremony_id },
+                                    ) => {
+                                        dot_multisig_client.complete_share_refresh(ceremony_id, true);
+                                    }
+                                    state_chain_runtime::RuntimeEvent::BitcoinVault(
+                                        pallet_cf_vaults::Event::ShareRefreshSuccess { ceremony_id },
+                                    ) => {
+                                        btc_multisig_client.complete_share_refresh(ceremony_id, true);
+                                    }
+                                    state_chain_runtime::RuntimeEvent::ArbitrumVault(
+                                        pallet_cf_vaults::Event::ShareRefreshSuccess { ceremony_id },
+                                    ) => {
+                                        if let Some((_, arb_multisig_client)) = &arb {
+                                            arb_multisig_client.complete_share_refresh(ceremony_id, true);
+                                        }
+                                    }
+                                    state_chain_runtime::RuntimeEvent::EthereumVault(
+                                        pallet_cf_vaults::Event::ShareRefreshFailure { ceremony_id },
+                                    ) => {
+                                        eth_multisig_client.complete_share_refresh(ceremony_id, false);
+                                    }
+                                    state_chain_runtime::RuntimeEvent::PolkadotVault(
+                                        pallet_cf_vaults::Event::ShareRefreshFailure { ceremony_id },
+                                    ) => {
+                                        dot_multisig_client.complete_share_refresh(ceremony_id, false);
+                                    }
+                                    state_chain_runtime::RuntimeEvent::BitcoinVault(
+                                        pallet_cf_vaults::Event::ShareRefreshFailure { ceremony_id },
+                                    ) => {
+                                        btc_multisig_client.complete_share_refresh(ceremony_id, false);
+                                    }
+                                    state_chain_runtime::RuntimeEvent::ArbitrumVault(
+                                        pallet_cf_vaults::Event::ShareRefreshFailure { ceremony_id },
+                                    ) => {
+                                        if let Some((_, arb_multisig_client)) = &arb {
+                                            arb_multisig_client.complete_share_refresh(ceremony_id, false);
+                                        }
+                                    }
 
                                     state_chain_runtime::RuntimeEvent::EthereumBroadcaster(
                                         pallet_cf_broadcast::Event::TransactionBroadcastRequest {
@@ -528,6 +978,45 @@ where
                                             })
                                         }
                                     }
+                                    state_chain_runtime::RuntimeEvent::ArbitrumBroadcaster(
+                                        pallet_cf_broadcast::Event::TransactionBroadcastRequest {
+                                            broadcast_attempt_id,
+                                            nominee,
+                                            transaction_payload,
+                                            // We're already witnessing this since we witness the KeyManager for SignatureAccepted events.
+                                            transaction_out_id: _,
+                                        },
+                                    ) => {
+                                        if nominee == account_id {
+                                            let Some((arb_rpc, _)) = &arb else {
+                                                // Without an rpc we can't broadcast, so the broadcast is retried by another nominee once it times out.
+                                                warn!("Can't broadcast Arbitrum broadcast attempt {broadcast_attempt_id:?}, as Arbitrum is not configured");
+                                                continue
+                                            };
+                                            let arb_rpc = arb_rpc.clone();
+                                            let state_chain_client = state_chain_client.clone();
+                                            scope.spawn(async move {
+                                                match arb_rpc.broadcast_transaction(transaction_payload).await {
+                                                    Ok(tx_hash) => info!("Arbitrum TransactionBroadcastRequest {broadcast_attempt_id:?} success: tx_hash: {tx_hash:#x}"),
+                                                    Err(error) => {
+                                                        // Note: this error can indicate that we failed to estimate gas, or that there is
+                                                        // a problem with the arbitrum rpc node, or with the configured account. For example
+                                                        // if the account balance is too low to pay for required gas.
+                                                        error!("Error on Arbitrum TransactionBroadcastRequest {broadcast_attempt_id:?}: {error:?}");
+                                                        state_chain_client.finalize_signed_extrinsic(
+                                                            state_chain_runtime::RuntimeCall::ArbitrumBroadcaster(
+                                                                pallet_cf_broadcast::Call::transaction_signing_failure {
+                                                                    broadcast_attempt_id,
+                                                                },
+                                                            ),
+                                                        )
+                                                        .await;
+                                                    }
+                                                }
+                                                Ok(())
+                                            })
+                                        }
+                                    }
                                     state_chain_runtime::RuntimeEvent::PolkadotBroadcaster(
                                         pallet_cf_broadcast::Event::TransactionBroadcastRequest {
                                             broadcast_attempt_id,
@@ -63,6 +63,7 @@ async fn start_sc_observer<
 		MockMultisigClientApi::new(),
 		MockMultisigClientApi::new(),
 		MockMultisigClientApi::new(),
+		Some((MockEthRetryRpcClient::new(), MockMultisigClientApi::new())),
 		account_peer_mapping_change_sender,
 	)
 	.await
@@ -195,6 +196,7 @@ ChainCrypto>::ThresholdSignature: std::convert::From<<C as CryptoScheme>::Signat
 			futures::future::ready(Err((
 				BTreeSet::new(),
 				SigningFailureReason::InvalidParticipants,
+				Default::default(),
 			)))
 			.boxed()
 		});
@@ -287,6 +289,177 @@ async fn should_handle_signing_request_eth() {
 	should_handle_signing_request::<EvmCryptoScheme, EthereumInstance>().await;
 }
 
+#[tokio::test]
+async fn should_report_failure_evidence_after_signing_failure() {
+	use cf_primitives::{BlameReason, CeremonyFailureEvidence};
+
+	let key_id = KeyId::new(1, [0u8; 32]);
+	let payload = EvmCryptoScheme::signing_payload_for_test();
+	let our_account_id = AccountId32::new([0; 32]);
+	let bad_account_id = AccountId32::new([1; 32]);
+	let ceremony_id = 1;
+
+	let mut state_chain_client = MockStateChainClient::new();
+	state_chain_client
+		.expect_account_id()
+		.once()
+		.return_const(our_account_id.clone());
+
+	let mut multisig_client = MockMultisigClientApi::<EvmCryptoScheme>::new();
+	let offenders = BTreeSet::from_iter([bad_account_id.clone()]);
+	multisig_client.expect_initiate_signing().once().return_once({
+		let offenders = offenders.clone();
+		move |_, _, _| {
+			futures::future::ready(Err((
+				offenders,
+				SigningFailureReason::InvalidSigShare,
+				Default::default(),
+			)))
+			.boxed()
+		}
+	});
+
+	let mut sequence = mockall::Sequence::new();
+	state_chain_client
+		.expect_finalize_signed_extrinsic::<pallet_cf_threshold_signature::Call<Runtime, EthereumInstance>>()
+		.with(eq(pallet_cf_threshold_signature::Call::<Runtime, EthereumInstance>::report_signature_failed {
+			ceremony_id,
+			offenders,
+		}))
+		.once()
+		.in_sequence(&mut sequence)
+		.return_once(|_| {
+			(
+				extrinsic_api::signed::MockUntilInBlock::new(),
+				extrinsic_api::signed::MockUntilFinalized::new(),
+			)
+		});
+	state_chain_client
+		.expect_finalize_signed_extrinsic::<pallet_cf_threshold_signature::Call<Runtime, EthereumInstance>>()
+		.with(eq(pallet_cf_threshold_signature::Call::<Runtime, EthereumInstance>::report_failure_evidence {
+			ceremony_id,
+			evidence: CeremonyFailureEvidence {
+				reason: BlameReason::InvalidSignatureShare,
+				conflicting_broadcasts: vec![],
+			},
+		}))
+		.once()
+		.in_sequence(&mut sequence)
+		.return_once(|_| {
+			(
+				extrinsic_api::signed::MockUntilInBlock::new(),
+				extrinsic_api::signed::MockUntilFinalized::new(),
+			)
+		});
+
+	let state_chain_client = Arc::new(state_chain_client);
+	task_scope(|scope| {
+		async {
+			sc_observer::handle_signing_request::<_, _, EvmCryptoScheme, EthereumInstance>(
+				scope,
+				&multisig_client,
+				state_chain_client.clone(),
+				ceremony_id,
+				BTreeSet::from_iter([our_account_id, bad_account_id]),
+				vec![(key_id, payload)],
+			)
+			.await;
+			Ok(())
+		}
+		.boxed()
+	})
+	.await
+	.unwrap();
+}
+
+#[tokio::test]
+async fn should_handle_batch_signing_request_eth() {
+	let key_id = KeyId::new(1, [0u8; 32]);
+	let payloads =
+		vec![multisig::eth::SigningPayload([1u8; 32]), multisig::eth::SigningPayload([2u8; 32])];
+	let signing_info = payloads
+		.iter()
+		.map(|payload| (key_id.clone(), payload.clone()))
+		.collect::<Vec<_>>();
+	let our_account_id = AccountId32::new([0; 32]);
+	let ceremony_id = 1;
+
+	let mut state_chain_client = MockStateChainClient::new();
+	let mut multisig_client = MockMultisigClientApi::<EvmCryptoScheme>::new();
+
+	state_chain_client
+		.expect_account_id()
+		.times(2)
+		.return_const(our_account_id.clone());
+
+	// Each payload is signed within the same ceremony.
+	let signatures = vec![EvmCryptoScheme::signature_for_test(); payloads.len()];
+	let signatures_clone = signatures.clone();
+	multisig_client
+		.expect_initiate_signing()
+		.with(
+			eq(ceremony_id),
+			eq(BTreeSet::from_iter([our_account_id.clone()])),
+			eq(signing_info.clone()),
+		)
+		.once()
+		.return_once(move |_, _, _| futures::future::ready(Ok(signatures_clone)).boxed());
+	state_chain_client
+		.expect_submit_unsigned_extrinsic()
+		.with(eq(pallet_cf_threshold_signature::Call::<Runtime, EthereumInstance>::batch_signature_success {
+			ceremony_id,
+			signatures: signatures
+				.into_iter()
+				.map(|signature| vec![signature].to_threshold_signature())
+				.collect(),
+		}))
+		.once()
+		.return_once(|_: pallet_cf_threshold_signature::Call<Runtime, EthereumInstance>| {
+			Ok(H256::default())
+		});
+
+	// A batch that exceeds the maximum size is ignored.
+	let oversized_ceremony_id = ceremony_id + 1;
+	multisig_client
+		.expect_update_latest_ceremony_id()
+		.with(eq(oversized_ceremony_id))
+		.once()
+		.returning(|_| ());
+
+	let state_chain_client = Arc::new(state_chain_client);
+	task_scope(|scope| {
+		async {
+			sc_observer::handle_batch_signing_request::<_, _, EvmCryptoScheme, EthereumInstance>(
+				scope,
+				&multisig_client,
+				state_chain_client.clone(),
+				ceremony_id,
+				BTreeSet::from_iter([our_account_id.clone()]),
+				signing_info,
+			)
+			.await;
+
+			sc_observer::handle_batch_signing_request::<_, _, EvmCryptoScheme, EthereumInstance>(
+				scope,
+				&multisig_client,
+				state_chain_client.clone(),
+				oversized_ceremony_id,
+				BTreeSet::from_iter([our_account_id]),
+				vec![
+					(key_id, payloads[0].clone());
+					cf_primitives::MAX_SIGNING_BATCH_SIZE as usize + 1
+				],
+			)
+			.await;
+
+			Ok(())
+		}
+		.boxed()
+	})
+	.await
+	.unwrap();
+}
+
 mod dot_signing {
 
 	use multisig::polkadot::PolkadotCryptoScheme;
@@ -309,6 +482,8 @@ where
 	I: CryptoCompat<C, C::ChainCrypto> + 'static + Send + Sync,
 	Runtime: pallet_cf_vaults::Config<I>,
 	RuntimeCall: std::convert::From<pallet_cf_vaults::Call<Runtime, I>>,
+	Runtime: pallet_cf_threshold_signature::Config<I>,
+	RuntimeCall: std::convert::From<pallet_cf_threshold_signature::Call<Runtime, I>>,
 {
 	let first_ceremony_id = 1;
 	let our_account_id = AccountId32::new([0; 32]);
@@ -350,8 +525,12 @@ where
 		)
 		.once()
 		.return_once(|_, _, _| {
-			futures::future::ready(Err((BTreeSet::new(), KeygenFailureReason::InvalidParticipants)))
-				.boxed()
+			futures::future::ready(Err((
+				BTreeSet::new(),
+				KeygenFailureReason::InvalidParticipants,
+				Default::default(),
+			)))
+			.boxed()
 		});
 
 	task_scope(|scope| {
@@ -444,8 +623,12 @@ where
 		)
 		.once()
 		.return_once(|_, _, _, _, _| {
-			futures::future::ready(Err((BTreeSet::new(), KeygenFailureReason::InvalidParticipants)))
-				.boxed()
+			futures::future::ready(Err((
+				BTreeSet::new(),
+				KeygenFailureReason::InvalidParticipants,
+				Default::default(),
+			)))
+			.boxed()
 		});
 	state_chain_client
 		.expect_finalize_signed_extrinsic::<pallet_cf_vaults::Call<Runtime, BitcoinInstance>>()
@@ -497,6 +680,93 @@ where
 	.unwrap();
 }
 
+#[tokio::test]
+async fn should_handle_share_refresh_request() {
+	use multisig::bitcoin::{BtcCryptoScheme, BtcSigning};
+
+	let first_ceremony_id = 1;
+	let our_account_id = AccountId32::new([0; 32]);
+	let not_our_account_id = AccountId32::new([1u8; 32]);
+	assert_ne!(our_account_id, not_our_account_id);
+
+	let mut state_chain_client = MockStateChainClient::new();
+	let mut multisig_client = MockMultisigClientApi::<BtcCryptoScheme>::new();
+
+	state_chain_client
+		.expect_account_id()
+		.times(2)
+		.return_const(our_account_id.clone());
+
+	// The first ceremony is a non-participating ceremony so it should update the latest ceremony id
+	multisig_client
+		.expect_update_latest_ceremony_id()
+		.with(eq(first_ceremony_id))
+		.once()
+		.return_once(|_| ());
+
+	// The second ceremony is a failure and should submit a signed extrinsic
+	let next_ceremony_id = first_ceremony_id + 1;
+	let key = cf_chains::btc::AggKey::default();
+	multisig_client
+		.expect_initiate_share_refresh()
+		.with(
+			eq(next_ceremony_id),
+			eq(KeyId::new(GENESIS_EPOCH, key.current)),
+			eq(BTreeSet::from_iter([our_account_id.clone()])),
+		)
+		.once()
+		.return_once(|_, _, _| {
+			futures::future::ready(Err((
+				BTreeSet::new(),
+				KeygenFailureReason::UnknownKey,
+				Default::default(),
+			)))
+			.boxed()
+		});
+	state_chain_client
+		.expect_finalize_signed_extrinsic::<pallet_cf_vaults::Call<Runtime, BitcoinInstance>>()
+		.once()
+		.return_once(|_| {
+			(
+				extrinsic_api::signed::MockUntilInBlock::new(),
+				extrinsic_api::signed::MockUntilFinalized::new(),
+			)
+		});
+
+	let state_chain_client = Arc::new(state_chain_client);
+	task_scope(|scope| {
+		async {
+			// Handle the share refresh request that we are not participating in
+			sc_observer::handle_share_refresh_request::<_, _, BtcSigning, BitcoinInstance>(
+				scope,
+				&multisig_client,
+				state_chain_client.clone(),
+				first_ceremony_id,
+				KeyId::new(GENESIS_EPOCH, key.current),
+				key,
+				BTreeSet::from_iter([not_our_account_id.clone()]),
+			)
+			.await;
+
+			// Handle the share refresh request that we are participating in
+			sc_observer::handle_share_refresh_request::<_, _, BtcSigning, BitcoinInstance>(
+				scope,
+				&multisig_client,
+				state_chain_client.clone(),
+				next_ceremony_id,
+				KeyId::new(GENESIS_EPOCH, key.current),
+				key,
+				BTreeSet::from_iter([our_account_id.clone()]),
+			)
+			.await;
+			Ok(())
+		}
+		.boxed()
+	})
+	.await
+	.unwrap();
+}
+
 #[tokio::test]
 #[ignore = "runs forever, useful for testing without having to start the whole CFE"]
 async fn run_the_sc_observer() {
@@ -528,6 +798,7 @@ async fn run_the_sc_observer() {
 				MockMultisigClientApi::new(),
 				MockMultisigClientApi::new(),
 				MockMultisigClientApi::new(),
+				Some((MockEthRetryRpcClient::new(), MockMultisigClientApi::new())),
 				account_peer_mapping_change_sender,
 			)
 			.await
@@ -1,5 +1,7 @@
+pub mod arb;
 pub mod btc;
 pub mod common;
 pub mod dot;
 pub mod eth;
+pub mod rewitness;
 pub mod start;
@@ -0,0 +1,348 @@
+use std::{collections::HashMap, sync::Arc};
+
+use cf_chains::Arbitrum;
+use cf_primitives::{chains::assets::arb, EpochIndex};
+use futures_core::Future;
+use sp_core::H160;
+use utilities::task_scope::Scope;
+
+use crate::{
+	db::PersistentKeyDB,
+	eth::retry_rpc::EthersRetryRpcClient,
+	state_chain_observer::client::{
+		chain_api::ChainApi, storage_api::StorageApi, StateChainStreamApi,
+	},
+	witness::eth::{erc20_deposits::usdc::UsdcEvents, EvmSource},
+};
+
+use super::common::{
+	chain_source::{extension::ChainSourceExt, range::RangeSource},
+	epoch_source::EpochSourceBuilder,
+	STATE_CHAIN_CONNECTION,
+};
+
+use anyhow::{Context, Result};
+
+/// Arbitrum blocks are produced by a single sequencer and are not reorged in practice, but we still
+/// wait a few blocks in case the sequencer's feed and the L1 batches disagree.
+const SAFETY_MARGIN: usize = 2;
+// Arbitrum produces blocks every quarter of a second, so this is about five minutes.
+const MAX_WITNESSING_LAG: u64 = 1200;
+const REORG_TRACKED_BLOCKS: usize = 64;
+
+struct ContractAddresses {
+	key_manager_address: H160,
+	vault_address: H160,
+	address_checker_address: H160,
+	usdc_contract_address: H160,
+	/// The assets of the supported ERC20 tokens, by contract address.
+	supported_erc20_tokens: HashMap<H160, cf_primitives::Asset>,
+}
+
+async fn contract_addresses<StateChainClient>(
+	state_chain_client: &StateChainClient,
+) -> Result<ContractAddresses>
+where
+	StateChainClient: StorageApi + ChainApi,
+{
+	let key_manager_address = state_chain_client
+		.storage_value::<pallet_cf_environment::ArbitrumKeyManagerAddress<state_chain_runtime::Runtime>>(
+			state_chain_client.latest_finalized_block().hash,
+		)
+		.await
+		.context("Failed to get Arbitrum KeyManager address from SC")?;
+
+	let vault_address = state_chain_client
+		.storage_value::<pallet_cf_environment::ArbitrumVaultAddress<state_chain_runtime::Runtime>>(
+			state_chain_client.latest_finalized_block().hash,
+		)
+		.await
+		.context("Failed to get Arbitrum Vault contract address from SC")?;
+
+	let address_checker_address = state_chain_client
+		.storage_value::<pallet_cf_environment::ArbitrumAddressCheckerAddress<state_chain_runtime::Runtime>>(
+			state_chain_client.latest_finalized_block().hash,
+		)
+		.await
+		.expect(STATE_CHAIN_CONNECTION);
+
+	let supported_erc20_tokens: HashMap<arb::Asset, H160> = state_chain_client
+		.storage_map::<pallet_cf_environment::ArbitrumSupportedAssets<state_chain_runtime::Runtime>, _>(
+			state_chain_client.latest_finalized_block().hash,
+		)
+		.await
+		.context("Failed to fetch Arbitrum supported assets")?;
+
+	let usdc_contract_address =
+		*supported_erc20_tokens.get(&arb::Asset::ArbUsdc).context("USDC not supported")?;
+
+	let supported_erc20_tokens: HashMap<H160, cf_primitives::Asset> = supported_erc20_tokens
+		.into_iter()
+		.map(|(asset, address)| (address, asset.into()))
+		.collect();
+
+	Ok(ContractAddresses {
+		key_manager_address,
+		vault_address,
+		address_checker_address,
+		usdc_contract_address,
+		supported_erc20_tokens,
+	})
+}
+
+/// Witnesses Arbitrum using the same EVM adapters as Ethereum. Arbitrum has no StateChainGateway
+/// and no FLIP, so only the KeyManager, the Vault and the native and USDC deposits are witnessed.
+pub async fn start<
+	StateChainClient,
+	StateChainStream,
+	ProcessCall,
+	ProcessingFut,
+	PrewitnessCall,
+	PrewitnessFut,
+>(
+	scope: &Scope<'_, anyhow::Error>,
+	arb_client: EthersRetryRpcClient,
+	process_call: ProcessCall,
+	prewitness_call: PrewitnessCall,
+	state_chain_client: Arc<StateChainClient>,
+	state_chain_stream: StateChainStream,
+	unfinalized_state_chain_stream: impl StateChainStreamApi<false>,
+	epoch_source: EpochSourceBuilder<'_, '_, StateChainClient, (), ()>,
+	db: Arc<PersistentKeyDB>,
+) -> Result<()>
+where
+	StateChainClient: StorageApi + ChainApi + 'static + Send + Sync,
+	StateChainStream: StateChainStreamApi + Clone,
+	ProcessCall: Fn(state_chain_runtime::RuntimeCall, EpochIndex) -> ProcessingFut
+		+ Send
+		+ Sync
+		+ Clone
+		+ 'static,
+	ProcessingFut: Future<Output = ()> + Send + 'static,
+	PrewitnessCall: Fn(state_chain_runtime::RuntimeCall, EpochIndex) -> PrewitnessFut
+		+ Send
+		+ Sync
+		+ Clone
+		+ 'static,
+	PrewitnessFut: Future<Output = ()> + Send + 'static,
+{
+	let ContractAddresses {
+		key_manager_address,
+		vault_address,
+		address_checker_address,
+		usdc_contract_address,
+		supported_erc20_tokens,
+	} = contract_addresses(&*state_chain_client).await?;
+
+	let arb_source = EvmSource::<_, Arbitrum>::new(arb_client.clone())
+		.recording(arb_client.recorder().cloned())
+		// See the Ethereum source for why this order matters.
+		.reorg_aware("Arbitrum", REORG_TRACKED_BLOCKS)
+		.strictly_monotonic()
+		.shared(scope);
+
+	arb_source
+		.clone()
+		.chunk_by_time(epoch_source.clone(), scope)
+		.chain_tracking(process_call.clone(), arb_client.clone())
+		.report_chain_tip(MAX_WITNESSING_LAG)
+		.logging("chain tracking")
+		.spawn(scope);
+
+	let vaults = epoch_source.vaults().await;
+
+	// ===== Prewitnessing stream =====
+	let prewitness_source = arb_source.clone().chunk_by_vault(vaults.clone(), scope);
+
+	let prewitness_source_deposit_addresses = prewitness_source
+		.clone()
+		.deposit_addresses(scope, unfinalized_state_chain_stream, state_chain_client.clone())
+		.await;
+
+	prewitness_source_deposit_addresses
+		.clone()
+		.erc20_deposits::<_, _, _, UsdcEvents>(
+			prewitness_call.clone(),
+			arb_client.clone(),
+			arb::Asset::ArbUsdc,
+			usdc_contract_address,
+		)
+		.await?
+		.logging("pre-witnessing USDCDeposits")
+		.spawn(scope);
+
+	prewitness_source_deposit_addresses
+		.clone()
+		.ethereum_deposits(
+			prewitness_call.clone(),
+			arb_client.clone(),
+			arb::Asset::ArbEth,
+			address_checker_address,
+			vault_address,
+		)
+		.await
+		.logging("pre-witnessing ArbitrumDeposits")
+		.spawn(scope);
+
+	prewitness_source
+		.vault_witnessing(
+			prewitness_call,
+			arb_client.clone(),
+			vault_address,
+			cf_primitives::Asset::ArbEth,
+			cf_primitives::ForeignChain::Arbitrum,
+			supported_erc20_tokens.clone(),
+		)
+		.logging("pre-witnessing Vault")
+		.spawn(scope);
+
+	// ===== Full witnessing stream =====
+
+	let arb_safe_vault_source = arb_source
+		.lag_safety(SAFETY_MARGIN)
+		.logging("safe block produced")
+		.report_tip("safe")
+		.chunk_by_vault(vaults, scope);
+
+	let arb_safe_vault_source_deposit_addresses = arb_safe_vault_source
+		.clone()
+		.deposit_addresses(scope, state_chain_stream.clone(), state_chain_client.clone())
+		.await;
+
+	arb_safe_vault_source
+		.clone()
+		.key_manager_witnessing(process_call.clone(), arb_client.clone(), key_manager_address)
+		.continuous("ArbitrumKeyManager".to_string(), db.clone())
+		.report_witnessed("safe", "KeyManager")
+		.logging("KeyManager")
+		.spawn(scope);
+
+	arb_safe_vault_source_deposit_addresses
+		.clone()
+		.erc20_deposits::<_, _, _, UsdcEvents>(
+			process_call.clone(),
+			arb_client.clone(),
+			arb::Asset::ArbUsdc,
+			usdc_contract_address,
+		)
+		.await?
+		.continuous("ArbitrumUSDCDeposits".to_string(), db.clone())
+		.report_witnessed("safe", "USDCDeposits")
+		.logging("USDCDeposits")
+		.spawn(scope);
+
+	arb_safe_vault_source_deposit_addresses
+		.clone()
+		.ethereum_deposits(
+			process_call.clone(),
+			arb_client.clone(),
+			arb::Asset::ArbEth,
+			address_checker_address,
+			vault_address,
+		)
+		.await
+		.continuous("ArbitrumDeposits".to_string(), db.clone())
+		.report_witnessed("safe", "ArbitrumDeposits")
+		.logging("ArbitrumDeposits")
+		.spawn(scope);
+
+	arb_safe_vault_source
+		.vault_witnessing(
+			process_call,
+			arb_client.clone(),
+			vault_address,
+			cf_primitives::Asset::ArbEth,
+			cf_primitives::ForeignChain::Arbitrum,
+			supported_erc20_tokens,
+		)
+		.continuous("ArbitrumVault".to_string(), db)
+		.report_witnessed("safe", "Vault")
+		.logging("Vault")
+		.spawn(scope);
+
+	Ok(())
+}
+
+/// Witnesses the blocks `from..=to` again with the full witnessing pipelines, returning once they
+/// have all been processed.
+pub async fn rewitness<StateChainClient, StateChainStream, ProcessCall, ProcessingFut>(
+	scope: &Scope<'_, anyhow::Error>,
+	arb_client: EthersRetryRpcClient,
+	from: u64,
+	to: u64,
+	process_call: ProcessCall,
+	state_chain_client: Arc<StateChainClient>,
+	state_chain_stream: StateChainStream,
+	epoch_source: EpochSourceBuilder<'_, '_, StateChainClient, (), ()>,
+) -> Result<()>
+where
+	StateChainClient: StorageApi + ChainApi + 'static + Send + Sync,
+	StateChainStream: StateChainStreamApi + Clone,
+	ProcessCall: Fn(state_chain_runtime::RuntimeCall, EpochIndex) -> ProcessingFut
+		+ Send
+		+ Sync
+		+ Clone
+		+ 'static,
+	ProcessingFut: Future<Output = ()> + Send + 'static,
+{
+	let ContractAddresses {
+		key_manager_address,
+		vault_address,
+		address_checker_address,
+		usdc_contract_address,
+		supported_erc20_tokens,
+	} = contract_addresses(&*state_chain_client).await?;
+
+	let vaults = epoch_source.vaults().await;
+
+	let arb_vault_source = RangeSource::<_, Arbitrum>::new(arb_client.clone(), from, to)
+		.chunk_by_vault_unshared(vaults);
+
+	let arb_vault_source_deposit_addresses = arb_vault_source
+		.clone()
+		.deposit_addresses(scope, state_chain_stream.clone(), state_chain_client.clone())
+		.await;
+
+	let usdc_deposits = arb_vault_source_deposit_addresses
+		.clone()
+		.erc20_deposits::<_, _, _, UsdcEvents>(
+			process_call.clone(),
+			arb_client.clone(),
+			arb::Asset::ArbUsdc,
+			usdc_contract_address,
+		)
+		.await?;
+
+	let arbitrum_deposits = arb_vault_source_deposit_addresses
+		.ethereum_deposits(
+			process_call.clone(),
+			arb_client.clone(),
+			arb::Asset::ArbEth,
+			address_checker_address,
+			vault_address,
+		)
+		.await;
+
+	futures::join!(
+		arb_vault_source
+			.clone()
+			.key_manager_witnessing(process_call.clone(), arb_client.clone(), key_manager_address)
+			.logging("rewitnessing KeyManager")
+			.run_active(),
+		usdc_deposits.logging("rewitnessing USDCDeposits").run_active(),
+		arbitrum_deposits.logging("rewitnessing ArbitrumDeposits").run_active(),
+		arb_vault_source
+			.vault_witnessing(
+				process_call,
+				arb_client.clone(),
+				vault_address,
+				cf_primitives::Asset::ArbEth,
+				cf_primitives::ForeignChain::Arbitrum,
+				supported_erc20_tokens,
+			)
+			.logging("rewitnessing Vault")
+			.run_active(),
+	);
+
+	Ok(())
+}
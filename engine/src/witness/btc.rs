@@ -1,3 +1,4 @@
+mod btc_block_filters;
 mod btc_chain_tracking;
 mod btc_deposits;
 pub mod btc_source;
@@ -14,14 +15,13 @@ use utilities::task_scope::Scope;
 use crate::{
 	btc::retry_rpc::{BtcRetryRpcApi, BtcRetryRpcClient},
 	db::PersistentKeyDB,
-	state_chain_observer::client::{
-		extrinsic_api::signed::SignedExtrinsicApi, storage_api::StorageApi, StateChainStreamApi,
-	},
+	state_chain_observer::client::{storage_api::StorageApi, StateChainStreamApi},
 };
+use btc_block_filters::BlockTransactions;
 use btc_source::BtcSource;
 
 use super::common::{
-	chain_source::{extension::ChainSourceExt, Header},
+	chain_source::{extension::ChainSourceExt, range::RangeSource, Header},
 	epoch_source::{EpochSourceBuilder, Vault},
 };
 
@@ -29,6 +29,9 @@ use anyhow::Result;
 
 // safety margin of 5 implies 6 block confirmations
 const SAFETY_MARGIN: usize = 5;
+// Witnessing lags the tip by the safety margin, plus a few blocks for slow processing.
+const MAX_WITNESSING_LAG: u64 = 10;
+const REORG_TRACKED_BLOCKS: usize = 16;
 
 pub async fn process_egress<ProcessCall, ProcessingFut, ExtraInfo, ExtraHistoricInfo>(
 	epoch: Vault<cf_chains::Bitcoin, ExtraInfo, ExtraHistoricInfo>,
@@ -85,7 +88,7 @@ pub async fn start<
 	db: Arc<PersistentKeyDB>,
 ) -> Result<()>
 where
-	StateChainClient: StorageApi + SignedExtrinsicApi + 'static + Send + Sync,
+	StateChainClient: StorageApi + 'static + Send + Sync,
 	StateChainStream: StateChainStreamApi + Clone + 'static + Send + Sync,
 	ProcessCall: Fn(state_chain_runtime::RuntimeCall, EpochIndex) -> ProcessingFut
 		+ Send
@@ -100,62 +103,162 @@ where
 		+ 'static,
 	PrewitnessFut: Future<Output = ()> + Send + 'static,
 {
-	let btc_source = BtcSource::new(btc_client.clone()).shared(scope);
+	let btc_source = BtcSource::new(btc_client.clone())
+		.recording(btc_client.recorder().cloned())
+		// See the Ethereum source for why this order matters.
+		.reorg_aware("Bitcoin", REORG_TRACKED_BLOCKS)
+		.strictly_monotonic()
+		.shared(scope);
 
 	btc_source
 		.clone()
 		.chunk_by_time(epoch_source.clone(), scope)
-		.chain_tracking(state_chain_client.clone(), btc_client.clone())
+		.chain_tracking(process_call.clone(), btc_client.clone())
+		.report_chain_tip(MAX_WITNESSING_LAG)
 		.logging("chain tracking")
 		.spawn(scope);
 
 	let vaults = epoch_source.vaults().await;
 
-	let strictly_monotonic_source = btc_source
-		.strictly_monotonic()
+	let transactions_source = btc_source
 		.then({
 			let btc_client = btc_client.clone();
 			move |header| {
 				let btc_client = btc_client.clone();
 				async move {
-					let block = btc_client.block(header.hash).await;
-					(header.data, block.txdata)
+					let block_filters = btc_client.block_filters();
+					(
+						header.data,
+						BlockTransactions::fetch(&btc_client, header.hash, block_filters).await,
+					)
 				}
 			}
 		})
 		.shared(scope);
 
 	// Pre-witnessing stream.
-	strictly_monotonic_source
+	transactions_source
 		.clone()
 		.chunk_by_vault(vaults.clone(), scope)
 		.deposit_addresses(scope, unfinalised_state_chain_stream, state_chain_client.clone())
 		.await
+		.then({
+			let btc_client = btc_client.clone();
+			move |_epoch, header| {
+				let btc_client = btc_client.clone();
+				async move {
+					let (((), block_transactions), addresses) = header.data;
+					let txs = block_transactions
+						.resolve(&btc_client, header.hash, &addresses, false)
+						.await;
+					(((), txs), addresses)
+				}
+			}
+		})
 		.btc_deposits(prewitness_call)
 		.logging("pre-witnessing")
 		.spawn(scope);
 
 	// Full witnessing stream.
-	strictly_monotonic_source
+	transactions_source
 		.lag_safety(SAFETY_MARGIN)
 		.logging("safe block produced")
+		.report_tip("safe")
 		.chunk_by_vault(vaults, scope)
-		.deposit_addresses(scope, state_chain_stream.clone(), state_chain_client.clone())
+		.egress_items(scope, state_chain_stream.clone(), state_chain_client.clone())
 		.await
-		.btc_deposits(process_call.clone())
-		.egress_items(scope, state_chain_stream, state_chain_client.clone())
+		.deposit_addresses(scope, state_chain_stream, state_chain_client.clone())
 		.await
+		.then({
+			let btc_client = btc_client.clone();
+			move |_epoch, header| {
+				let btc_client = btc_client.clone();
+				async move {
+					let ((((), block_transactions), tx_out_ids), addresses) = header.data;
+					// Our broadcasts aren't looked for in the filters, so while any are pending the
+					// block is always downloaded.
+					let txs = block_transactions
+						.resolve(&btc_client, header.hash, &addresses, !tx_out_ids.is_empty())
+						.await;
+					((tx_out_ids, txs), addresses)
+				}
+			}
+		})
+		.btc_deposits(process_call.clone())
 		.then({
 			let process_call = process_call.clone();
 			move |epoch, header| process_egress(epoch, header, process_call.clone())
 		})
 		.continuous("Bitcoin".to_string(), db)
+		.report_witnessed("safe", "witnessing")
 		.logging("witnessing")
 		.spawn(scope);
 
 	Ok(())
 }
 
+/// Witnesses the blocks `from..=to` again with the full witnessing pipeline, returning once they
+/// have all been processed.
+pub async fn rewitness<StateChainClient, StateChainStream, ProcessCall, ProcessingFut>(
+	scope: &Scope<'_, anyhow::Error>,
+	btc_client: BtcRetryRpcClient,
+	from: BlockNumber,
+	to: BlockNumber,
+	process_call: ProcessCall,
+	state_chain_client: Arc<StateChainClient>,
+	state_chain_stream: StateChainStream,
+	epoch_source: EpochSourceBuilder<'_, '_, StateChainClient, (), ()>,
+) -> Result<()>
+where
+	StateChainClient: StorageApi + 'static + Send + Sync,
+	StateChainStream: StateChainStreamApi + Clone + 'static + Send + Sync,
+	ProcessCall: Fn(state_chain_runtime::RuntimeCall, EpochIndex) -> ProcessingFut
+		+ Send
+		+ Sync
+		+ Clone
+		+ 'static,
+	ProcessingFut: Future<Output = ()> + Send + 'static,
+{
+	let vaults = epoch_source.vaults().await;
+
+	RangeSource::<_, cf_chains::Bitcoin>::new(btc_client.clone(), from, to)
+		.then({
+			let btc_client = btc_client.clone();
+			move |header| {
+				let btc_client = btc_client.clone();
+				async move {
+					let block_filters = btc_client.block_filters();
+					(
+						header.data,
+						BlockTransactions::fetch(&btc_client, header.hash, block_filters).await,
+					)
+				}
+			}
+		})
+		.chunk_by_vault_unshared(vaults)
+		.egress_items(scope, state_chain_stream.clone(), state_chain_client.clone())
+		.await
+		.deposit_addresses(scope, state_chain_stream, state_chain_client)
+		.await
+		.then(move |_epoch, header| {
+			let btc_client = btc_client.clone();
+			async move {
+				let ((((), block_transactions), tx_out_ids), addresses) = header.data;
+				let txs = block_transactions
+					.resolve(&btc_client, header.hash, &addresses, !tx_out_ids.is_empty())
+					.await;
+				((tx_out_ids, txs), addresses)
+			}
+		})
+		.btc_deposits(process_call.clone())
+		.then(move |epoch, header| process_egress(epoch, header, process_call.clone()))
+		.logging("rewitnessing")
+		.run_active()
+		.await;
+
+	Ok(())
+}
+
 fn success_witnesses<'a>(
 	monitored_tx_hashes: impl Iterator<Item = &'a btc::Hash> + Clone,
 	txs: &Vec<Transaction>,
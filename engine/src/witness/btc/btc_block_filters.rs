@@ -0,0 +1,124 @@
+use bitcoin::{bip158::BlockFilter, BlockHash, Transaction};
+use pallet_cf_ingress_egress::DepositChannelDetails;
+use state_chain_runtime::BitcoinInstance;
+use tracing::warn;
+
+use crate::btc::retry_rpc::BtcRetryRpcApi;
+
+/// The transactions of a block, or, if we are witnessing using compact block filters, the BIP158
+/// filter of the block, which is used to decide if the block itself needs to be downloaded.
+#[derive(Clone, Debug)]
+pub enum BlockTransactions {
+	Downloaded(Vec<Transaction>),
+	Filtered(Vec<u8>),
+}
+
+impl BlockTransactions {
+	pub async fn fetch<BtcRetryClient: BtcRetryRpcApi>(
+		btc_client: &BtcRetryClient,
+		block_hash: BlockHash,
+		block_filters: bool,
+	) -> Self {
+		if block_filters {
+			Self::Filtered(btc_client.block_filter(block_hash).await)
+		} else {
+			Self::Downloaded(btc_client.block(block_hash).await.txdata)
+		}
+	}
+
+	/// Returns the transactions of the block. If we only have the filter of the block, the block is
+	/// downloaded if the filter matches any of the deposit addresses, or if `must_download` is set,
+	/// otherwise the block contains nothing we are witnessing and no transactions are returned.
+	pub async fn resolve<BtcRetryClient: BtcRetryRpcApi>(
+		self,
+		btc_client: &BtcRetryClient,
+		block_hash: BlockHash,
+		addresses: &[DepositChannelDetails<state_chain_runtime::Runtime, BitcoinInstance>],
+		must_download: bool,
+	) -> Vec<Transaction> {
+		match self {
+			Self::Downloaded(txs) => txs,
+			Self::Filtered(filter) =>
+				if must_download ||
+					filter_matches(
+						&filter,
+						&block_hash,
+						addresses.iter().map(|channel| channel.deposit_channel.address.bytes()),
+					) {
+					btc_client.block(block_hash).await.txdata
+				} else {
+					Vec::new()
+				},
+		}
+	}
+}
+
+fn filter_matches(
+	filter: &[u8],
+	block_hash: &BlockHash,
+	scripts: impl Iterator<Item = Vec<u8>>,
+) -> bool {
+	let mut scripts = scripts.peekable();
+	// `match_any` treats an empty query as a match, but then there is nothing to witness.
+	if scripts.peek().is_none() {
+		return false
+	}
+	BlockFilter::new(filter).match_any(block_hash, scripts).unwrap_or_else(|error| {
+		// We can't tell what the block contains, so it is downloaded to be safe.
+		warn!("Failed to match the filter of Bitcoin block {block_hash}: {error}");
+		true
+	})
+}
+
+#[cfg(test)]
+mod tests {
+
+	use super::*;
+	use bitcoin::{
+		absolute::{Height, LockTime},
+		block::{Header, Version},
+		hashes::Hash,
+		Block, CompactTarget, ScriptBuf, TxMerkleNode, TxOut,
+	};
+
+	fn fake_block(scripts: Vec<Vec<u8>>) -> Block {
+		Block {
+			header: Header {
+				version: Version::ONE,
+				prev_blockhash: BlockHash::all_zeros(),
+				merkle_root: TxMerkleNode::all_zeros(),
+				time: 0,
+				bits: CompactTarget::from_consensus(0),
+				nonce: 0,
+			},
+			txdata: vec![Transaction {
+				version: 2,
+				lock_time: LockTime::Blocks(Height::from_consensus(0).unwrap()),
+				input: vec![],
+				output: scripts
+					.into_iter()
+					.map(|script| TxOut { value: 2324, script_pubkey: ScriptBuf::from(script) })
+					.collect(),
+			}],
+		}
+	}
+
+	#[test]
+	fn filter_matches_only_scripts_in_the_block() {
+		let deposit_script = vec![0, 20, 121, 9, 3, 4];
+		let other_script = vec![0, 20, 33, 2, 1, 9];
+
+		let block = fake_block(vec![deposit_script.clone()]);
+		let filter = BlockFilter::new_script_filter(&block, |_| unreachable!()).unwrap();
+		let block_hash = block.block_hash();
+
+		assert!(filter_matches(&filter.content, &block_hash, [deposit_script.clone()].into_iter()));
+		assert!(filter_matches(
+			&filter.content,
+			&block_hash,
+			[other_script.clone(), deposit_script].into_iter()
+		));
+		assert!(!filter_matches(&filter.content, &block_hash, [other_script].into_iter()));
+		assert!(!filter_matches(&filter.content, &block_hash, std::iter::empty()));
+	}
+}
@@ -11,8 +11,8 @@ use super::super::common::chunked_chain_source::chunked_by_vault::{
 	builder::ChunkedByVaultBuilder, ChunkedByVault,
 };
 use crate::witness::common::{
-	chunked_chain_source::chunked_by_vault::deposit_addresses::Addresses, RuntimeCallHasChain,
-	RuntimeHasChain,
+	chain_source::aliases, chunked_chain_source::chunked_by_vault::deposit_addresses::Addresses,
+	RuntimeCallHasChain, RuntimeHasChain,
 };
 use bitcoin::BlockHash;
 use cf_chains::{
@@ -22,19 +22,27 @@ use cf_chains::{
 };
 
 impl<Inner: ChunkedByVault> ChunkedByVaultBuilder<Inner> {
-	pub fn btc_deposits<ProcessCall, ProcessingFut>(
+	/// Witnesses the deposits to the deposit addresses in each block, passing the transactions of
+	/// the block on along with any extra data that came with them.
+	pub fn btc_deposits<ProcessCall, ProcessingFut, ExtraData>(
 		self,
 		process_call: ProcessCall,
 	) -> ChunkedByVaultBuilder<
-		impl ChunkedByVault<Index = u64, Hash = BlockHash, Data = Vec<Transaction>, Chain = Bitcoin>,
+		impl ChunkedByVault<
+			Index = u64,
+			Hash = BlockHash,
+			Data = (Vec<Transaction>, ExtraData),
+			Chain = Bitcoin,
+		>,
 	>
 	where
 		Inner: ChunkedByVault<
 			Index = u64,
 			Hash = BlockHash,
-			Data = (((), Vec<Transaction>), Addresses<Inner>),
+			Data = ((ExtraData, Vec<Transaction>), Addresses<Inner>),
 			Chain = Bitcoin,
 		>,
+		ExtraData: aliases::Data,
 		ProcessCall: Fn(state_chain_runtime::RuntimeCall, EpochIndex) -> ProcessingFut
 			+ Send
 			+ Sync
@@ -49,7 +57,7 @@ impl<Inner: ChunkedByVault> ChunkedByVaultBuilder<Inner> {
 			let process_call = process_call.clone();
 			async move {
 				// TODO: Make addresses a Map of some kind?
-				let (((), txs), addresses) = header.data;
+				let ((extra_data, txs), addresses) = header.data;
 
 				let script_addresses = script_addresses(addresses);
 
@@ -67,7 +75,7 @@ impl<Inner: ChunkedByVault> ChunkedByVaultBuilder<Inner> {
 					)
 					.await;
 				}
-				txs
+				(txs, extra_data)
 			}
 		})
 	}
@@ -1,7 +1,12 @@
 pub mod and_then;
+pub mod either;
 pub mod extension;
+pub mod health;
 pub mod lag_safety;
 pub mod logging;
+pub mod range;
+pub mod recording;
+pub mod reorg_aware;
 pub mod shared;
 pub mod strictly_monotonic;
 pub mod then;
@@ -9,6 +14,7 @@ pub mod then;
 use std::pin::Pin;
 
 use futures_core::{Future, Stream};
+use serde::{Deserialize, Serialize};
 
 pub mod aliases {
 	use codec::FullCodec;
@@ -28,7 +34,7 @@ pub mod aliases {
 	define_trait_alias!(pub trait Data: Send + Sync + Unpin + 'static);
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub struct Header<Index, Hash, Data> {
 	pub index: Index,
 	pub hash: Hash,
@@ -0,0 +1,51 @@
+use crate::witness::common::ExternalChainSource;
+
+use super::{BoxChainStream, ChainSource};
+
+/// Allows choosing between two chain sources at runtime, for example based on the settings, while
+/// keeping a single type for the downstream adapters. Both sources must produce the same headers
+/// and use the same client.
+#[derive(Clone)]
+pub enum EitherSource<Left, Right> {
+	Left(Left),
+	Right(Right),
+}
+
+#[async_trait::async_trait]
+impl<Left: ChainSource, Right> ChainSource for EitherSource<Left, Right>
+where
+	Right: ChainSource<
+		Index = Left::Index,
+		Hash = Left::Hash,
+		Data = Left::Data,
+		Client = Left::Client,
+	>,
+{
+	type Index = Left::Index;
+	type Hash = Left::Hash;
+	type Data = Left::Data;
+
+	type Client = Left::Client;
+
+	async fn stream_and_client(
+		&self,
+	) -> (BoxChainStream<'_, Self::Index, Self::Hash, Self::Data>, Self::Client) {
+		match self {
+			EitherSource::Left(source) => source.stream_and_client().await,
+			EitherSource::Right(source) => source.stream_and_client().await,
+		}
+	}
+}
+
+impl<Left: ExternalChainSource, Right> ExternalChainSource for EitherSource<Left, Right>
+where
+	Right: ExternalChainSource<
+		Index = Left::Index,
+		Hash = Left::Hash,
+		Data = Left::Data,
+		Client = Left::Client,
+		Chain = Left::Chain,
+	>,
+{
+	type Chain = Left::Chain;
+}
@@ -11,9 +11,11 @@ use crate::witness::common::{
 };
 
 use super::{
-	aliases, and_then::AndThen, lag_safety::LagSafety, logging::Logging, shared::SharedSource,
+	aliases, and_then::AndThen, health::ReportTip, lag_safety::LagSafety, logging::Logging,
+	recording::RecordingSource, reorg_aware::ReorgAware, shared::SharedSource,
 	strictly_monotonic::StrictlyMonotonic, then::Then, ChainSource, Header,
 };
+use crate::recording::Recorder;
 
 #[async_trait::async_trait]
 pub trait ChainSourceExt: ChainSource {
@@ -51,6 +53,26 @@ pub trait ChainSourceExt: ChainSource {
 		LagSafety::new(self, margin)
 	}
 
+	/// Checks that each header builds on the last `tracked_blocks` headers. If it doesn't, the
+	/// canonical branch is fetched back to the fork point and re-emitted, and the depth of the
+	/// reorg is recorded in the metrics under the given name. Gaps in the inner stream are also
+	/// filled.
+	fn reorg_aware(self, name: &'static str, tracked_blocks: usize) -> ReorgAware<Self>
+	where
+		Self: Sized,
+	{
+		ReorgAware::new(self, name, tracked_blocks)
+	}
+
+	/// Writes each header to the recorder, if one is given, so the chain source can be replayed
+	/// later.
+	fn recording(self, recorder: Option<Recorder>) -> RecordingSource<Self>
+	where
+		Self: Sized,
+	{
+		RecordingSource::new(self, recorder)
+	}
+
 	/// Allows sharing an underlying chain source between multiple consumers. This ensures that work
 	/// done in previous chain source adapters is not duplicated by downstream consumers.
 	fn shared<'env>(self, scope: &Scope<'env, anyhow::Error>) -> SharedSource<Self>
@@ -71,6 +93,15 @@ pub trait ChainSourceExt: ChainSource {
 		Logging::new(self, log_prefix)
 	}
 
+	/// Reports the index of each header produced to the health check as the tip of the named
+	/// source, which the witnessing pipelines consuming it are compared against.
+	fn report_tip(self, source: &'static str) -> ReportTip<Self>
+	where
+		Self: Sized,
+	{
+		ReportTip::new(self, source)
+	}
+
 	/// Ensures the stream is always increasing with respect to the header index (normally the block
 	/// number). We don't assume the root chain source is strictly increasing, since we could
 	/// encounter reorgs.
@@ -124,5 +155,26 @@ pub trait ChainSourceExt: ChainSource {
 		// the same underlying stream and client for each epoch:
 		ChunkedByVaultBuilder::new(ChunkByVault::new(self.shared(scope)), vaults.into())
 	}
+
+	/// Chunk the chain source by vault, without sharing the underlying stream between vaults. Each
+	/// vault (and each clone of the builder) gets its own stream from the start of the source, so
+	/// none of them can miss headers by subscribing late. Only meant for sources that end, such as
+	/// a `RangeSource`, where there is no progress tracking to backfill missed headers.
+	fn chunk_by_vault_unshared<
+		ExtraInfo,
+		ExtraHistoricInfo,
+		Vaults: Into<VaultSource<Self::Chain, ExtraInfo, ExtraHistoricInfo>>,
+	>(
+		self,
+		vaults: Vaults,
+	) -> ChunkedByVaultBuilder<ChunkByVault<Self, ExtraInfo, ExtraHistoricInfo>>
+	where
+		Self: ExternalChainSource + Sized,
+		state_chain_runtime::Runtime: RuntimeHasChain<Self::Chain>,
+		ExtraInfo: Clone + Send + Sync + 'static,
+		ExtraHistoricInfo: Clone + Send + Sync + 'static,
+	{
+		ChunkedByVaultBuilder::new(ChunkByVault::new(self), vaults.into())
+	}
 }
 impl<T: ChainSource> ChainSourceExt for T {}
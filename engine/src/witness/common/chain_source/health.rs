@@ -0,0 +1,52 @@
+use futures_util::StreamExt;
+
+use crate::{health, witness::common::ExternalChainSource};
+
+use super::{BoxChainStream, ChainSource};
+
+#[derive(Clone)]
+pub struct ReportTip<InnerSource: ChainSource> {
+	inner_source: InnerSource,
+	source: &'static str,
+}
+impl<InnerSource: ChainSource> ReportTip<InnerSource> {
+	pub fn new(inner_source: InnerSource, source: &'static str) -> Self {
+		Self { inner_source, source }
+	}
+}
+
+#[async_trait::async_trait]
+impl<InnerSource: ChainSource + ExternalChainSource> ChainSource for ReportTip<InnerSource>
+where
+	InnerSource::Client: Clone,
+{
+	type Index = InnerSource::Index;
+	type Hash = InnerSource::Hash;
+	type Data = InnerSource::Data;
+
+	type Client = InnerSource::Client;
+
+	async fn stream_and_client(
+		&self,
+	) -> (BoxChainStream<'_, Self::Index, Self::Hash, Self::Data>, Self::Client) {
+		let (chain_stream, chain_client) = self.inner_source.stream_and_client().await;
+		(
+			Box::pin(chain_stream.map(move |header| {
+				health::record_source_tip(
+					<<InnerSource as ExternalChainSource>::Chain as cf_chains::Chain>::NAME,
+					self.source,
+					header.index.into(),
+				);
+				header
+			})),
+			chain_client,
+		)
+	}
+}
+
+impl<InnerSource: ExternalChainSource> ExternalChainSource for ReportTip<InnerSource>
+where
+	InnerSource::Client: Clone,
+{
+	type Chain = InnerSource::Chain;
+}
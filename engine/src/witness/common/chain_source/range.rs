@@ -0,0 +1,88 @@
+use std::{iter::Step, marker::PhantomData};
+
+use futures_util::stream;
+
+use crate::witness::common::{ExternalChain, ExternalChainSource};
+
+use super::{BoxChainStream, ChainClient, ChainSource};
+
+/// Produces the headers of a fixed range of blocks, in order, and then ends. Used to witness blocks
+/// again, for example after an outage.
+#[derive(Clone)]
+pub struct RangeSource<C: ChainClient, Chain> {
+	client: C,
+	from: C::Index,
+	to: C::Index,
+	_phantom: PhantomData<Chain>,
+}
+
+impl<C: ChainClient, Chain> RangeSource<C, Chain> {
+	/// Both ends of the range are inclusive.
+	pub fn new(client: C, from: C::Index, to: C::Index) -> Self {
+		Self { client, from, to, _phantom: PhantomData }
+	}
+}
+
+#[async_trait::async_trait]
+impl<C: ChainClient, Chain: Send + Sync> ChainSource for RangeSource<C, Chain> {
+	type Index = C::Index;
+	type Hash = C::Hash;
+	type Data = C::Data;
+
+	type Client = C;
+
+	async fn stream_and_client(
+		&self,
+	) -> (BoxChainStream<'_, Self::Index, Self::Hash, Self::Data>, Self::Client) {
+		(
+			Box::pin(stream::unfold(Some(self.from), move |next_index| async move {
+				let index = next_index.filter(|index| *index <= self.to)?;
+				let header = self.client.header_at_index(index).await;
+				Some((header, (index < self.to).then(|| Step::forward(index, 1))))
+			})),
+			self.client.clone(),
+		)
+	}
+}
+
+impl<C: ChainClient, Chain: ExternalChain<ChainBlockNumber = C::Index>> ExternalChainSource
+	for RangeSource<C, Chain>
+{
+	type Chain = Chain;
+}
+
+#[cfg(test)]
+mod tests {
+	use futures::StreamExt;
+
+	use crate::witness::common::chain_source::Header;
+
+	use super::*;
+
+	#[derive(Clone)]
+	struct MockClient;
+
+	#[async_trait::async_trait]
+	impl ChainClient for MockClient {
+		type Index = u64;
+		type Hash = u64;
+		type Data = ();
+
+		async fn header_at_index(&self, index: u64) -> Header<u64, u64, ()> {
+			Header { index, hash: index, parent_hash: index.checked_sub(1), data: () }
+		}
+	}
+
+	#[tokio::test]
+	async fn produces_the_headers_in_the_range_then_ends() {
+		let source = RangeSource::<_, cf_chains::Bitcoin>::new(MockClient, 5, 8);
+		let (stream, _client) = source.stream_and_client().await;
+
+		assert_eq!(stream.map(|header| header.index).collect::<Vec<_>>().await, vec![5, 6, 7, 8]);
+
+		let source = RangeSource::<_, cf_chains::Bitcoin>::new(MockClient, 8, 5);
+		let (stream, _client) = source.stream_and_client().await;
+
+		assert!(stream.collect::<Vec<_>>().await.is_empty());
+	}
+}
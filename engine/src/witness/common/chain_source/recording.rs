@@ -0,0 +1,146 @@
+use futures_util::StreamExt;
+use serde::{de::DeserializeOwned, Serialize};
+
+use crate::{
+	recording::{replay_client::ReplayClient, RecordedData, Recorder},
+	witness::common::{ExternalChain, ExternalChainSource},
+};
+
+use super::{BoxChainStream, ChainClient, ChainSource, Header};
+
+/// Writes each header pulled from the inner source to the recorder, if there is one, so the stream
+/// can later be replayed with a [ReplaySource].
+#[derive(Clone)]
+pub struct RecordingSource<InnerSource> {
+	inner_source: InnerSource,
+	recorder: Option<Recorder>,
+}
+impl<InnerSource> RecordingSource<InnerSource> {
+	pub fn new(inner_source: InnerSource, recorder: Option<Recorder>) -> Self {
+		Self { inner_source, recorder }
+	}
+}
+
+#[async_trait::async_trait]
+impl<InnerSource: ChainSource> ChainSource for RecordingSource<InnerSource>
+where
+	InnerSource::Hash: Serialize,
+	InnerSource::Data: RecordedData,
+{
+	type Index = InnerSource::Index;
+	type Hash = InnerSource::Hash;
+	type Data = InnerSource::Data;
+
+	type Client = InnerSource::Client;
+
+	async fn stream_and_client(
+		&self,
+	) -> (BoxChainStream<'_, Self::Index, Self::Hash, Self::Data>, Self::Client) {
+		let (chain_stream, chain_client) = self.inner_source.stream_and_client().await;
+
+		match &self.recorder {
+			Some(recorder) => (
+				Box::pin(chain_stream.map(move |header| {
+					recorder.record_header(&header);
+					header
+				})),
+				chain_client,
+			),
+			None => (chain_stream, chain_client),
+		}
+	}
+}
+
+impl<InnerSource: ExternalChainSource> ExternalChainSource for RecordingSource<InnerSource>
+where
+	InnerSource::Hash: Serialize,
+	InnerSource::Data: RecordedData,
+{
+	type Chain = InnerSource::Chain;
+}
+
+/// Produces the headers of a recording, in the order they were recorded, and then ends. The
+/// client answers requests with the recorded responses.
+#[derive(Clone)]
+pub struct ReplaySource<Chain> {
+	client: ReplayClient<Chain>,
+}
+impl<Chain> ReplaySource<Chain> {
+	pub fn new(client: ReplayClient<Chain>) -> Self {
+		Self { client }
+	}
+}
+
+#[async_trait::async_trait]
+impl<Chain> ChainSource for ReplaySource<Chain>
+where
+	Chain: Send + Sync + 'static,
+	ReplayClient<Chain>: ChainClient + Clone,
+	<ReplayClient<Chain> as ChainClient>::Hash: Serialize + DeserializeOwned,
+	<ReplayClient<Chain> as ChainClient>::Data: RecordedData,
+{
+	type Index = <ReplayClient<Chain> as ChainClient>::Index;
+	type Hash = <ReplayClient<Chain> as ChainClient>::Hash;
+	type Data = <ReplayClient<Chain> as ChainClient>::Data;
+
+	type Client = ReplayClient<Chain>;
+
+	async fn stream_and_client(
+		&self,
+	) -> (BoxChainStream<'_, Self::Index, Self::Hash, Self::Data>, Self::Client) {
+		let headers: Vec<Header<Self::Index, Self::Hash, Self::Data>> =
+			self.client.recording().headers().expect("Recording contains invalid headers");
+
+		(Box::pin(futures::stream::iter(headers)), self.client.clone())
+	}
+}
+
+impl<Chain> ExternalChainSource for ReplaySource<Chain>
+where
+	Chain: ExternalChain,
+	ReplayClient<Chain>: ChainClient<Index = Chain::ChainBlockNumber> + Clone,
+	<ReplayClient<Chain> as ChainClient>::Hash: Serialize + DeserializeOwned,
+	<ReplayClient<Chain> as ChainClient>::Data: RecordedData,
+{
+	type Chain = Chain;
+}
+
+#[cfg(test)]
+mod tests {
+	use std::sync::Arc;
+
+	use bitcoin::{hashes::Hash, BlockHash};
+	use cf_chains::Bitcoin;
+
+	use crate::recording::test_recording;
+
+	use super::*;
+
+	#[tokio::test]
+	async fn replays_recorded_headers_and_responses() {
+		let headers = (5..8)
+			.map(|index| Header {
+				index,
+				hash: BlockHash::hash(&[index as u8]),
+				parent_hash: None,
+				data: (),
+			})
+			.collect::<Vec<_>>();
+
+		let recording = test_recording({
+			let headers = headers.clone();
+			move |recorder| {
+				for header in &headers {
+					recorder.record_header(header);
+				}
+			}
+		})
+		.await;
+
+		let client = ReplayClient::<Bitcoin>::new(Arc::new(recording));
+		let (stream, client) = ReplaySource::new(client).stream_and_client().await;
+
+		assert_eq!(stream.collect::<Vec<_>>().await, headers);
+		assert_eq!(client.header_at_index(6).await, headers[1]);
+	}
+}
@@ -0,0 +1,404 @@
+use std::{collections::VecDeque, iter::Step};
+
+use futures::stream;
+use futures_util::StreamExt;
+use tracing::{error, warn};
+use utilities::metrics::CHAIN_SOURCE_REORG_DEPTH;
+
+use crate::witness::common::{chain_source::ChainClient, ExternalChainSource};
+
+use super::{BoxChainStream, ChainSource, Header};
+
+/// Tracks the hashes of the last `tracked_blocks` headers pulled from the inner source, and checks
+/// that each new header builds on them. When it doesn't, the new branch is fetched from the client
+/// back to the point where it forks from the tracked headers, and re-emitted in order. Gaps in the
+/// inner stream are filled in the same way, so the output never skips a block.
+///
+/// The depth of each detected reorg is recorded in the `chain_source_reorg_depth` metric. A reorg
+/// deeper than `tracked_blocks` can't be fully resolved, in which case we log an error and continue
+/// from the new branch.
+///
+/// A witness can't be undone once it has been submitted, so the witnessing pipelines only use this
+/// to detect reorgs. They follow it with `strictly_monotonic`, which drops the re-emitted blocks,
+/// and rely on the margin of `lag_safety` to only witness blocks that won't be reorged.
+#[derive(Clone)]
+pub struct ReorgAware<InnerSource> {
+	inner_source: InnerSource,
+	name: &'static str,
+	tracked_blocks: usize,
+}
+impl<InnerSource> ReorgAware<InnerSource> {
+	pub fn new(inner_source: InnerSource, name: &'static str, tracked_blocks: usize) -> Self {
+		assert!(tracked_blocks > 0);
+		Self { inner_source, name, tracked_blocks }
+	}
+}
+
+/// The headers of the branch that connects `header` to the `tracked` headers, in ascending order,
+/// along with the number of tracked headers the branch replaces. The replaced headers are removed
+/// from `tracked`.
+async fn canonical_branch<Client: ChainClient>(
+	chain_client: &Client,
+	tracked: &mut VecDeque<(Client::Index, Client::Hash)>,
+	header: Header<Client::Index, Client::Hash, Client::Data>,
+) -> (Vec<Header<Client::Index, Client::Hash, Client::Data>>, usize) {
+	let mut branch = vec![header];
+	let mut reorg_depth = 0;
+
+	loop {
+		let (tip_index, tip_hash, tip_parent_hash) = {
+			let tip = branch.last().unwrap();
+			(tip.index, tip.hash, tip.parent_hash)
+		};
+
+		while let Some((index, hash)) = tracked.back() {
+			if *index < tip_index {
+				break
+			}
+			if *index != tip_index || *hash != tip_hash {
+				reorg_depth += 1;
+			}
+			tracked.pop_back();
+		}
+
+		match tracked.back() {
+			Some((index, hash)) if Step::forward_checked(*index, 1) == Some(tip_index) =>
+				if tip_parent_hash.map_or(true, |parent_hash| parent_hash == *hash) {
+					break
+				},
+			Some(_) => {},
+			// Either this is the first header, or the fork point is further back than we track.
+			None => break,
+		}
+
+		// There is a tracked header below the tip, so the tip index can't be the minimum.
+		let parent_index = Step::backward_checked(tip_index, 1).unwrap();
+		branch.push(chain_client.header_at_index(parent_index).await);
+	}
+
+	branch.reverse();
+	(branch, reorg_depth)
+}
+
+#[async_trait::async_trait]
+impl<InnerSource: ChainSource> ChainSource for ReorgAware<InnerSource>
+where
+	InnerSource::Client: Clone,
+{
+	type Index = InnerSource::Index;
+	type Hash = InnerSource::Hash;
+	type Data = InnerSource::Data;
+
+	type Client = InnerSource::Client;
+
+	async fn stream_and_client(
+		&self,
+	) -> (BoxChainStream<'_, Self::Index, Self::Hash, Self::Data>, Self::Client) {
+		let (chain_stream, chain_client) = self.inner_source.stream_and_client().await;
+		let name = self.name;
+		let tracked_blocks = self.tracked_blocks;
+
+		(
+			Box::pin(stream::unfold(
+				(
+					chain_stream,
+					chain_client.clone(),
+					VecDeque::<(Self::Index, Self::Hash)>::new(),
+					VecDeque::<Header<Self::Index, Self::Hash, Self::Data>>::new(),
+				),
+				move |(mut chain_stream, chain_client, mut tracked, mut pending)| async move {
+					let item = loop {
+						if let Some(header) = pending.pop_front() {
+							break Some(header)
+						}
+						let Some(header) = chain_stream.next().await else { break None };
+
+						// A header we have already seen (for example from a lagging node) is passed
+						// through as is, it doesn't tell us anything new about the chain.
+						if tracked.back().map_or(false, |(index, _)| *index != header.index) &&
+							tracked.iter().any(|(index, hash)| {
+								*index == header.index && *hash == header.hash
+							}) {
+							break Some(header)
+						}
+
+						let had_tracked = !tracked.is_empty();
+						let header_index = header.index;
+						let (branch, reorg_depth) =
+							canonical_branch(&chain_client, &mut tracked, header).await;
+
+						if reorg_depth > 0 {
+							CHAIN_SOURCE_REORG_DEPTH.observe(&[name], reorg_depth as f64);
+							if had_tracked && tracked.is_empty() {
+								error!("{name}: Reorg at block {header_index:?} is deeper than the {tracked_blocks} tracked blocks. Re-emitting the last {} blocks of the new branch.", branch.len());
+							} else {
+								warn!("{name}: Reorg of depth {reorg_depth} detected at block {header_index:?}. Re-emitting {} blocks of the canonical branch.", branch.len());
+							}
+						}
+
+						for header in branch {
+							tracked.push_back((header.index, header.hash));
+							pending.push_back(header);
+						}
+						while tracked.len() > tracked_blocks {
+							tracked.pop_front();
+						}
+					};
+					item.map(move |item| (item, (chain_stream, chain_client, tracked, pending)))
+				},
+			)),
+			chain_client,
+		)
+	}
+}
+
+impl<InnerSource: ExternalChainSource> ExternalChainSource for ReorgAware<InnerSource>
+where
+	InnerSource::Client: Clone,
+{
+	type Chain = InnerSource::Chain;
+}
+
+#[cfg(test)]
+mod tests {
+	use std::sync::Arc;
+
+	use crate::common::Mutex;
+
+	use super::*;
+
+	use futures::Stream;
+
+	#[derive(Clone)]
+	pub struct MockChainClient {
+		queried_indices: Arc<Mutex<Vec<u64>>>,
+	}
+
+	impl MockChainClient {
+		pub async fn queried_indices(&self) -> Vec<u64> {
+			let guard = self.queried_indices.lock().await;
+			guard.clone()
+		}
+	}
+
+	#[async_trait::async_trait]
+	impl ChainClient for MockChainClient {
+		type Index = u64;
+
+		type Hash = u64;
+
+		type Data = ();
+
+		async fn header_at_index(
+			&self,
+			index: Self::Index,
+		) -> Header<Self::Index, Self::Hash, Self::Data> {
+			let mut queried = self.queried_indices.lock().await;
+			queried.push(index);
+			normal_header(index)
+		}
+	}
+
+	pub struct MockChainSource<HeaderStream: Stream<Item = Header<u64, u64, ()>> + Send + Sync> {
+		stream: Arc<Mutex<Option<HeaderStream>>>,
+		client: MockChainClient,
+	}
+
+	impl<HeaderStream: Stream<Item = Header<u64, u64, ()>> + Send + Sync>
+		MockChainSource<HeaderStream>
+	{
+		fn new(stream: HeaderStream) -> Self {
+			Self {
+				stream: Arc::new(Mutex::new(Some(stream))),
+				client: MockChainClient { queried_indices: Arc::new(Mutex::new(Vec::new())) },
+			}
+		}
+	}
+
+	#[async_trait::async_trait]
+	impl<HeaderStream: Stream<Item = Header<u64, u64, ()>> + Send + Sync> ChainSource
+		for MockChainSource<HeaderStream>
+	{
+		type Index = u64;
+		type Hash = u64;
+		type Data = ();
+
+		type Client = MockChainClient;
+
+		async fn stream_and_client(
+			&self,
+		) -> (BoxChainStream<'_, Self::Index, Self::Hash, Self::Data>, Self::Client) {
+			let mut guard = self.stream.lock().await;
+			let stream = guard.take().expect("should only be called once, with a stream set");
+			(Box::pin(stream), self.client.clone())
+		}
+	}
+
+	fn normal_header(index: u64) -> Header<u64, u64, ()> {
+		Header { index, hash: index, parent_hash: Some(index - 1), data: () }
+	}
+
+	fn test_header(index: u64, hash: u64, parent_hash: u64) -> Header<u64, u64, ()> {
+		Header { index, hash, parent_hash: Some(parent_hash), data: () }
+	}
+
+	async fn collect_output(
+		headers: Vec<Header<u64, u64, ()>>,
+		tracked_blocks: usize,
+	) -> (Vec<Header<u64, u64, ()>>, Vec<u64>) {
+		let reorg_aware =
+			ReorgAware::new(MockChainSource::new(stream::iter(headers)), "test", tracked_blocks);
+		let (chain_stream, client) = reorg_aware.stream_and_client().await;
+		let output = chain_stream.collect::<Vec<_>>().await;
+		(output, client.queried_indices().await)
+	}
+
+	#[tokio::test]
+	async fn canonical_chain_passes_through() {
+		let (output, queried) = collect_output((5..10).map(normal_header).collect(), 4).await;
+
+		assert_eq!(output, (5..10).map(normal_header).collect::<Vec<_>>());
+		assert!(queried.is_empty());
+	}
+
+	#[tokio::test]
+	async fn gaps_are_filled_from_the_client() {
+		let (output, queried) =
+			collect_output(vec![normal_header(5), normal_header(8), normal_header(9)], 4).await;
+
+		assert_eq!(output, (5..10).map(normal_header).collect::<Vec<_>>());
+		assert_eq!(queried, vec![7, 6]);
+	}
+
+	#[tokio::test]
+	async fn reorg_re_emits_the_canonical_branch() {
+		let (output, queried) = collect_output(
+			vec![
+				normal_header(5),
+				// these two are on a fork
+				test_header(6, 66, 5),
+				test_header(7, 77, 66),
+				// canonical chain
+				normal_header(8),
+				normal_header(9),
+			],
+			4,
+		)
+		.await;
+
+		assert_eq!(
+			output,
+			vec![
+				normal_header(5),
+				test_header(6, 66, 5),
+				test_header(7, 77, 66),
+				normal_header(6),
+				normal_header(7),
+				normal_header(8),
+				normal_header(9),
+			]
+		);
+		assert_eq!(queried, vec![7, 6]);
+	}
+
+	#[tokio::test]
+	async fn competing_header_at_the_same_index_replaces_the_tracked_one() {
+		let (output, queried) = collect_output(
+			vec![normal_header(5), test_header(6, 66, 5), normal_header(6), normal_header(7)],
+			4,
+		)
+		.await;
+
+		assert_eq!(
+			output,
+			vec![normal_header(5), test_header(6, 66, 5), normal_header(6), normal_header(7)]
+		);
+		assert!(queried.is_empty());
+	}
+
+	#[tokio::test]
+	async fn reorg_deeper_than_tracked_blocks_continues_from_the_new_branch() {
+		let (output, queried) = collect_output(
+			vec![
+				// these three are on a fork
+				test_header(5, 55, 4),
+				test_header(6, 66, 55),
+				test_header(7, 77, 66),
+				// canonical chain
+				normal_header(8),
+			],
+			2,
+		)
+		.await;
+
+		assert_eq!(
+			output,
+			vec![
+				test_header(5, 55, 4),
+				test_header(6, 66, 55),
+				test_header(7, 77, 66),
+				normal_header(6),
+				normal_header(7),
+				normal_header(8),
+			]
+		);
+		assert_eq!(queried, vec![7, 6]);
+	}
+
+	#[tokio::test]
+	async fn the_witnessing_pipeline_does_not_output_forked_blocks() {
+		use crate::witness::common::chain_source::extension::ChainSourceExt;
+
+		// The node switches back to the canonical chain after reporting a fork, so it reports
+		// blocks 6 and 7 again before reporting 8.
+		let safe_source = MockChainSource::new(stream::iter(vec![
+			normal_header(5),
+			test_header(6, 66, 5),
+			test_header(7, 77, 66),
+			normal_header(6),
+			normal_header(7),
+			normal_header(8),
+		]))
+		.reorg_aware("test", 4)
+		.strictly_monotonic()
+		.lag_safety(2);
+
+		let (chain_stream, _client) = safe_source.stream_and_client().await;
+
+		// Strictly monotonic drops the canonical blocks 6 and 7, as the forked ones were output
+		// before them. The forked blocks are still within the safety margin when block 8 arrives,
+		// so the lag safety fetches the canonical block 6 instead of outputting the forked one.
+		assert_eq!(
+			chain_stream.map(|header| (header.index, header.hash)).collect::<Vec<_>>().await,
+			vec![(3, 3), (4, 4), (5, 5), (6, 6)]
+		);
+	}
+
+	#[tokio::test]
+	async fn already_seen_headers_pass_through_without_a_reorg() {
+		let (output, queried) = collect_output(
+			vec![normal_header(5), normal_header(6), normal_header(7), normal_header(6)],
+			4,
+		)
+		.await;
+
+		assert_eq!(
+			output,
+			vec![normal_header(5), normal_header(6), normal_header(7), normal_header(6)]
+		);
+		assert!(queried.is_empty());
+
+		// The stale header didn't replace the tracked ones, so the next header still connects.
+		let (output, queried) = collect_output(
+			vec![normal_header(5), normal_header(6), normal_header(5), normal_header(7)],
+			4,
+		)
+		.await;
+
+		assert_eq!(
+			output,
+			vec![normal_header(5), normal_header(6), normal_header(5), normal_header(7)]
+		);
+		assert!(queried.is_empty());
+	}
+}
@@ -1,6 +1,7 @@
 pub mod and_then;
 pub mod chunked_by_time;
 pub mod chunked_by_vault;
+pub mod health;
 pub mod latest_then;
 pub mod logging;
 pub mod then;
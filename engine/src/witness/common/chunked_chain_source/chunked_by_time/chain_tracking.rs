@@ -1,14 +1,11 @@
-use std::sync::Arc;
-
 use cf_chains::ChainState;
+use cf_primitives::EpochIndex;
+use futures_core::Future;
 use state_chain_runtime::PalletInstanceAlias;
 
 use crate::witness::common::chain_source::Header;
 
-use crate::{
-	state_chain_observer::client::extrinsic_api::signed::SignedExtrinsicApi,
-	witness::common::{RuntimeCallHasChain, RuntimeHasChain},
-};
+use crate::witness::common::{RuntimeCallHasChain, RuntimeHasChain};
 
 use super::{builder::ChunkedByTimeBuilder, ChunkedByTime};
 
@@ -21,24 +18,29 @@ pub trait GetTrackedData<C: cf_chains::Chain, Hash, Data>: Send + Sync + Clone {
 }
 
 impl<Inner: ChunkedByTime> ChunkedByTimeBuilder<Inner> {
-	pub fn chain_tracking<StateChainClient, TrackedDataClient>(
+	pub fn chain_tracking<ProcessCall, ProcessingFut, TrackedDataClient>(
 		self,
-		state_chain_client: Arc<StateChainClient>,
+		process_call: ProcessCall,
 		tracked_data_client: TrackedDataClient,
 	) -> ChunkedByTimeBuilder<impl ChunkedByTime>
 	where
 		Inner: ChunkedByTime,
-		StateChainClient: SignedExtrinsicApi + Send + Sync + 'static,
+		ProcessCall: Fn(state_chain_runtime::RuntimeCall, EpochIndex) -> ProcessingFut
+			+ Send
+			+ Sync
+			+ Clone
+			+ 'static,
+		ProcessingFut: Future<Output = ()> + Send + 'static,
 		TrackedDataClient: GetTrackedData<Inner::Chain, Inner::Hash, Inner::Data>,
 		state_chain_runtime::Runtime: RuntimeHasChain<Inner::Chain>,
 		state_chain_runtime::RuntimeCall:
 			RuntimeCallHasChain<state_chain_runtime::Runtime, Inner::Chain>,
 	{
 		self.latest_then(move |epoch, header| {
-			let state_chain_client = state_chain_client.clone();
+			let process_call = process_call.clone();
 			let tracked_data_client = tracked_data_client.clone();
 			async move {
-				let call: Box<state_chain_runtime::RuntimeCall> = Box::new(
+				process_call(
 					pallet_cf_chain_tracking::Call::<
 						state_chain_runtime::Runtime,
 						<Inner::Chain as PalletInstanceAlias>::Instance,
@@ -49,13 +51,9 @@ impl<Inner: ChunkedByTime> ChunkedByTimeBuilder<Inner> {
 						},
 					}
 					.into(),
-				);
-				state_chain_client
-					.finalize_signed_extrinsic(pallet_cf_witnesser::Call::witness_at_epoch {
-						call,
-						epoch_index: epoch.index,
-					})
-					.await;
+					epoch.index,
+				)
+				.await;
 
 				Ok::<_, anyhow::Error>(header.data)
 			}
@@ -48,6 +48,17 @@ impl<Inner: ChunkedByVault> ChunkedByVaultBuilder<Inner> {
 			Ok(())
 		});
 	}
+
+	/// Runs the streams of the vaults that are active now until they end. Vaults that become active
+	/// later are ignored, so this only makes sense with a chain source that ends, such as a
+	/// `RangeSource`.
+	pub async fn run_active(self) {
+		let stream = assert_stream_send(
+			futures::stream::iter(self.source.stream(self.parameters).await.active)
+				.flat_map_unordered(None, |(_vault, chain_stream, _chain_client)| chain_stream),
+		);
+		stream.for_each(|_| futures::future::ready(())).await;
+	}
 }
 
 impl<T: ChunkedByVault> ChunkedByVaultBuilder<T> {
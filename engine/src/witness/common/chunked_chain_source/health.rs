@@ -0,0 +1,107 @@
+use futures_util::StreamExt;
+
+use crate::{
+	health::{self, WitnessedBlocks},
+	witness::common::{chain_source::ChainStream, BoxActiveAndFuture},
+};
+
+use super::{
+	chunked_by_time::{builder::ChunkedByTimeBuilder, ChunkedByTime},
+	chunked_by_vault::{
+		builder::{ChunkedByVaultBuilder, Generic},
+		ChunkedByVault,
+	},
+	ChunkedChainSource,
+};
+
+pub struct ReportWitnessed<Inner> {
+	inner: Inner,
+	source: &'static str,
+	pipeline: &'static str,
+}
+
+#[async_trait::async_trait]
+impl<Inner: ChunkedChainSource> ChunkedChainSource for ReportWitnessed<Inner> {
+	type Info = Inner::Info;
+	type HistoricInfo = Inner::HistoricInfo;
+
+	type Index = Inner::Index;
+	type Hash = Inner::Hash;
+	type Data = Inner::Data;
+
+	type Client = Inner::Client;
+
+	type Chain = Inner::Chain;
+
+	type Parameters = Inner::Parameters;
+
+	async fn stream(
+		&self,
+		parameters: Self::Parameters,
+	) -> BoxActiveAndFuture<'_, super::Item<'_, Self, Self::Info, Self::HistoricInfo>> {
+		self.inner
+			.stream(parameters)
+			.await
+			.then(move |(epoch, chain_stream, chain_client)| async move {
+				// Dropped with the vault's stream, which removes its progress from the health
+				// check.
+				let witnessed = WitnessedBlocks::new(
+					<Inner::Chain as cf_chains::Chain>::NAME,
+					self.source,
+					self.pipeline,
+					epoch.index,
+				);
+				(
+					epoch,
+					chain_stream
+						.map(move |header| {
+							witnessed.record(header.index.into());
+							header
+						})
+						.into_box(),
+					chain_client,
+				)
+			})
+			.await
+			.into_box()
+	}
+}
+
+impl<Inner: ChunkedByVault> ChunkedByVaultBuilder<Inner> {
+	/// Reports the blocks processed by this witnessing pipeline to the health check, to be compared
+	/// against the tip of the chain source it consumes.
+	pub fn report_witnessed(
+		self,
+		source: &'static str,
+		pipeline: &'static str,
+	) -> ChunkedByVaultBuilder<impl ChunkedByVault>
+	where
+		Inner: ChunkedByVault,
+	{
+		ChunkedByVaultBuilder {
+			source: ReportWitnessed { inner: Generic(self.source), source, pipeline },
+			parameters: self.parameters,
+		}
+	}
+}
+
+impl<Inner: ChunkedByTime> ChunkedByTimeBuilder<Inner> {
+	/// Reports the tip of the chain to the health check, which the witnessing pipelines should stay
+	/// within `max_witnessing_lag` blocks of.
+	pub fn report_chain_tip(
+		self,
+		max_witnessing_lag: u64,
+	) -> ChunkedByTimeBuilder<impl ChunkedByTime>
+	where
+		Inner: ChunkedByTime,
+	{
+		self.then(move |_epoch, header| async move {
+			health::record_chain_tip(
+				<Inner::Chain as cf_chains::Chain>::NAME,
+				header.index.into(),
+				max_witnessing_lag,
+			);
+			header.data
+		})
+	}
+}
@@ -4,7 +4,7 @@ use std::{
 };
 
 use crate::{
-	common::Signal, state_chain_observer::client, witness::common::STATE_CHAIN_CONNECTION,
+	common::Signal, health, state_chain_observer::client, witness::common::STATE_CHAIN_CONNECTION,
 };
 use cf_chains::Chain;
 use cf_primitives::{AccountId, EpochIndex};
@@ -256,8 +256,10 @@ impl<
 						.iter()
 						.any(|participating_epoch| *participating_epoch == epoch)
 					{
+						health::record_epoch_participation(epoch, true);
 						Some(info)
 					} else {
+						health::record_epoch_participation(epoch, false);
 						None
 					}
 				}
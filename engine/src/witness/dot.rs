@@ -24,10 +24,11 @@ use utilities::task_scope::Scope;
 use crate::{
 	db::PersistentKeyDB,
 	dot::retry_rpc::{DotRetryRpcApi, DotRetryRpcClient},
-	state_chain_observer::client::{
-		extrinsic_api::signed::SignedExtrinsicApi, storage_api::StorageApi, StateChainStreamApi,
+	settings::DotWitnessingMode,
+	state_chain_observer::client::{storage_api::StorageApi, StateChainStreamApi},
+	witness::common::chain_source::{
+		either::EitherSource, extension::ChainSourceExt, range::RangeSource,
 	},
-	witness::common::chain_source::extension::ChainSourceExt,
 };
 use anyhow::Result;
 pub use dot_source::{DotFinalisedSource, DotUnfinalisedSource};
@@ -38,6 +39,12 @@ use super::common::{
 	STATE_CHAIN_CONNECTION,
 };
 
+/// The number of blocks to lag behind the best block when witnessing with a fixed lag. GRANDPA
+/// usually finalises blocks well within this margin.
+const SAFETY_MARGIN: usize = 6;
+// Allows for GRANDPA finalising more slowly than usual in the finalized witnessing mode.
+const MAX_WITNESSING_LAG: u64 = 30;
+
 // To generate the metadata file, use the subxt-cli tool (`cargo install subxt-cli`):
 // subxt metadata --format=json --pallets Proxy,Balances,TransactionPayment,System --url
 // wss://polkadot-rpc.dwellir.com:443 > metadata.polkadot.json.scale
@@ -92,6 +99,17 @@ pub fn filter_map_events(
 	}
 }
 
+/// The transfers made by the extrinsics of a block, with the index of their extrinsic.
+pub fn transfers(
+	events: &[(Phase, EventWrapper)],
+) -> impl Iterator<Item = (PolkadotExtrinsicIndex, PolkadotAccountId, PolkadotBalance)> + '_ {
+	events.iter().filter_map(|(phase, event)| match (phase, event) {
+		(Phase::ApplyExtrinsic(extrinsic_index), EventWrapper::Transfer { to, amount, .. }) =>
+			Some((*extrinsic_index, PolkadotAccountId::from_aliased(to.0), *amount)),
+		_ => None,
+	})
+}
+
 pub async fn proxy_added_witnessing(
 	epoch: Vault<cf_chains::Polkadot, PolkadotAccountId, ()>,
 	header: Header<PolkadotBlockNumber, PolkadotHash, Vec<(Phase, EventWrapper)>>,
@@ -173,6 +191,7 @@ pub async fn process_egress<ProcessCall, ProcessingFut>(
 pub async fn start<StateChainClient, ProcessCall, ProcessingFut, PrewitnessCall, PrewitnessFut>(
 	scope: &Scope<'_, anyhow::Error>,
 	dot_client: DotRetryRpcClient,
+	witnessing_mode: DotWitnessingMode,
 	process_call: ProcessCall,
 	prewitness_call: PrewitnessCall,
 	state_chain_client: Arc<StateChainClient>,
@@ -182,7 +201,7 @@ pub async fn start<StateChainClient, ProcessCall, ProcessingFut, PrewitnessCall,
 	db: Arc<PersistentKeyDB>,
 ) -> Result<()>
 where
-	StateChainClient: StorageApi + SignedExtrinsicApi + 'static + Send + Sync,
+	StateChainClient: StorageApi + 'static + Send + Sync,
 	ProcessCall: Fn(state_chain_runtime::RuntimeCall, EpochIndex) -> ProcessingFut
 		+ Send
 		+ Sync
@@ -203,7 +222,8 @@ where
 	unfinalised_source
 		.clone()
 		.chunk_by_time(epoch_source.clone(), scope)
-		.chain_tracking(state_chain_client.clone(), dot_client.clone())
+		.chain_tracking(process_call.clone(), dot_client.clone())
+		.report_chain_tip(MAX_WITNESSING_LAG)
 		.logging("chain tracking")
 		.spawn(scope);
 
@@ -234,9 +254,20 @@ where
 		.spawn(scope);
 
 	// Full witnessing
-	DotFinalisedSource::new(dot_client.clone())
-		.strictly_monotonic()
-		.logging("finalised block produced")
+	let safe_source = match witnessing_mode {
+		DotWitnessingMode::FixedLag => EitherSource::Left(
+			DotUnfinalisedSource::new(dot_client.clone())
+				.strictly_monotonic()
+				.lag_safety(SAFETY_MARGIN),
+		),
+		DotWitnessingMode::Finalized =>
+			EitherSource::Right(DotFinalisedSource::new(dot_client.clone()).strictly_monotonic()),
+	};
+
+	safe_source
+		.recording(dot_client.recorder().cloned())
+		.logging("safe block produced")
+		.report_tip("safe")
 		.then(|header| async move {
 			header.data.iter().filter_map(filter_map_events).collect::<Vec<_>>()
 		})
@@ -258,12 +289,71 @@ where
 			}
 		})
 		.continuous("Polkadot".to_string(), db)
+		.report_witnessed("safe", "witnessing")
 		.logging("witnessing")
 		.spawn(scope);
 
 	Ok(())
 }
 
+/// Witnesses the blocks `from..=to` again with the full witnessing pipeline, returning once they
+/// have all been processed.
+pub async fn rewitness<StateChainClient, ProcessCall, ProcessingFut>(
+	scope: &Scope<'_, anyhow::Error>,
+	dot_client: DotRetryRpcClient,
+	from: PolkadotBlockNumber,
+	to: PolkadotBlockNumber,
+	process_call: ProcessCall,
+	state_chain_client: Arc<StateChainClient>,
+	state_chain_stream: impl StateChainStreamApi + Clone,
+	epoch_source: EpochSourceBuilder<'_, '_, StateChainClient, (), ()>,
+) -> Result<()>
+where
+	StateChainClient: StorageApi + 'static + Send + Sync,
+	ProcessCall: Fn(state_chain_runtime::RuntimeCall, EpochIndex) -> ProcessingFut
+		+ Send
+		+ Sync
+		+ Clone
+		+ 'static,
+	ProcessingFut: Future<Output = ()> + Send + 'static,
+{
+	let vaults = epoch_source
+		.filter_map(
+			|state_chain_client, _epoch_index, hash, _info| async move {
+				state_chain_client
+					.storage_value::<pallet_cf_environment::PolkadotVaultAccountId<state_chain_runtime::Runtime>>(
+						hash,
+					)
+					.await
+					.expect(STATE_CHAIN_CONNECTION)
+			},
+			|_state_chain_client, _epoch, _block_hash, historic_info| async move { historic_info },
+		)
+		.await
+		.vaults()
+		.await;
+
+	RangeSource::<_, cf_chains::Polkadot>::new(dot_client.clone(), from, to)
+		.then(|header| async move {
+			header.data.iter().filter_map(filter_map_events).collect::<Vec<_>>()
+		})
+		.chunk_by_vault_unshared(vaults)
+		.deposit_addresses(scope, state_chain_stream.clone(), state_chain_client.clone())
+		.await
+		.dot_deposits(process_call.clone())
+		.then(proxy_added_witnessing)
+		.egress_items(scope, state_chain_stream, state_chain_client)
+		.await
+		.then(move |epoch, header| {
+			process_egress(epoch, header, process_call.clone(), dot_client.clone())
+		})
+		.logging("rewitnessing")
+		.run_active()
+		.await;
+
+	Ok(())
+}
+
 fn transaction_fee_paids(
 	indices: &BTreeSet<PolkadotExtrinsicIndex>,
 	events: &[(Phase, EventWrapper)],
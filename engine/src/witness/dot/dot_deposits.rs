@@ -11,7 +11,7 @@ use crate::witness::{
 		chunked_chain_source::chunked_by_vault::deposit_addresses::Addresses, RuntimeCallHasChain,
 		RuntimeHasChain,
 	},
-	dot::EventWrapper,
+	dot::{transfers, EventWrapper},
 };
 use cf_chains::{
 	assets::dot::Asset,
@@ -98,23 +98,17 @@ fn deposit_witnesses(
 	monitored_addresses: Vec<PolkadotAccountId>,
 	events: &Vec<(Phase, EventWrapper)>,
 ) -> Vec<DepositWitness<Polkadot>> {
-	let mut deposit_witnesses = vec![];
-	for (phase, wrapped_event) in events {
-		if let Phase::ApplyExtrinsic(_extrinsic_index) = phase {
-			if let EventWrapper::Transfer { to, amount, from: _ } = wrapped_event {
-				let deposit_address = PolkadotAccountId::from_aliased(to.0);
-				if monitored_addresses.contains(&deposit_address) {
-					deposit_witnesses.push(DepositWitness {
-						deposit_address,
-						asset: Asset::Dot,
-						amount: *amount,
-						deposit_details: (),
-					});
-				}
-			}
-		}
-	}
-	deposit_witnesses
+	transfers(events)
+		.filter(|(_extrinsic_index, deposit_address, _amount)| {
+			monitored_addresses.contains(deposit_address)
+		})
+		.map(|(_extrinsic_index, deposit_address, amount)| DepositWitness {
+			deposit_address,
+			asset: Asset::Dot,
+			amount,
+			deposit_details: (),
+		})
+		.collect()
 }
 
 #[cfg(test)]
@@ -10,6 +10,7 @@ pub mod vault;
 use std::{collections::HashMap, sync::Arc};
 
 use cf_primitives::{chains::assets::eth, EpochIndex};
+use ethers::types::BlockNumber;
 use futures_core::Future;
 use sp_core::H160;
 use utilities::task_scope::Scope;
@@ -17,56 +18,46 @@ use utilities::task_scope::Scope;
 use crate::{
 	db::PersistentKeyDB,
 	eth::retry_rpc::EthersRetryRpcClient,
+	settings::EthWitnessingMode,
 	state_chain_observer::client::{
-		chain_api::ChainApi, extrinsic_api::signed::SignedExtrinsicApi, storage_api::StorageApi,
-		StateChainStreamApi,
+		chain_api::ChainApi, storage_api::StorageApi, StateChainStreamApi,
 	},
 	witness::eth::erc20_deposits::{flip::FlipEvents, usdc::UsdcEvents},
 };
 
 use super::common::{
-	chain_source::extension::ChainSourceExt, epoch_source::EpochSourceBuilder,
+	chain_source::{either::EitherSource, extension::ChainSourceExt, range::RangeSource},
+	epoch_source::EpochSourceBuilder,
 	STATE_CHAIN_CONNECTION,
 };
-pub use eth_source::EthSource;
+pub use eth_source::{EthSource, EthTaggedSource, EvmSource, EvmTaggedSource};
 
 use anyhow::{Context, Result};
 
 const SAFETY_MARGIN: usize = 6;
+// Covers the finalized witnessing mode, where witnessing is two to three epochs of 32 blocks
+// behind the tip.
+const MAX_WITNESSING_LAG: u64 = 128;
+/// Ethereum blocks are finalised after two epochs of 32 blocks, so reorgs can't be deeper than
+/// this.
+const REORG_TRACKED_BLOCKS: usize = 64;
+
+struct ContractAddresses {
+	state_chain_gateway_address: H160,
+	key_manager_address: H160,
+	vault_address: H160,
+	address_checker_address: H160,
+	usdc_contract_address: H160,
+	flip_contract_address: H160,
+	/// The assets of the supported ERC20 tokens, by contract address.
+	supported_erc20_tokens: HashMap<H160, cf_primitives::Asset>,
+}
 
-pub async fn start<
-	StateChainClient,
-	StateChainStream,
-	ProcessCall,
-	ProcessingFut,
-	PrewitnessCall,
-	PrewitnessFut,
->(
-	scope: &Scope<'_, anyhow::Error>,
-	eth_client: EthersRetryRpcClient,
-	process_call: ProcessCall,
-	prewitness_call: PrewitnessCall,
-	state_chain_client: Arc<StateChainClient>,
-	state_chain_stream: StateChainStream,
-	unfinalized_state_chain_stream: impl StateChainStreamApi<false>,
-	epoch_source: EpochSourceBuilder<'_, '_, StateChainClient, (), ()>,
-	db: Arc<PersistentKeyDB>,
-) -> Result<()>
+async fn contract_addresses<StateChainClient>(
+	state_chain_client: &StateChainClient,
+) -> Result<ContractAddresses>
 where
-	StateChainClient: StorageApi + ChainApi + SignedExtrinsicApi + 'static + Send + Sync,
-	StateChainStream: StateChainStreamApi + Clone,
-	ProcessCall: Fn(state_chain_runtime::RuntimeCall, EpochIndex) -> ProcessingFut
-		+ Send
-		+ Sync
-		+ Clone
-		+ 'static,
-	ProcessingFut: Future<Output = ()> + Send + 'static,
-	PrewitnessCall: Fn(state_chain_runtime::RuntimeCall, EpochIndex) -> PrewitnessFut
-		+ Send
-		+ Sync
-		+ Clone
-		+ 'static,
-	PrewitnessFut: Future<Output = ()> + Send + 'static,
+	StateChainClient: StorageApi + ChainApi,
 {
 	let state_chain_gateway_address = state_chain_client
         .storage_value::<pallet_cf_environment::EthereumStateChainGatewayAddress<state_chain_runtime::Runtime>>(
@@ -115,20 +106,83 @@ where
 		.map(|(asset, address)| (address, asset.into()))
 		.collect();
 
-	let eth_source = EthSource::new(eth_client.clone()).shared(scope);
+	Ok(ContractAddresses {
+		state_chain_gateway_address,
+		key_manager_address,
+		vault_address,
+		address_checker_address,
+		usdc_contract_address,
+		flip_contract_address,
+		supported_erc20_tokens,
+	})
+}
+
+pub async fn start<
+	StateChainClient,
+	StateChainStream,
+	ProcessCall,
+	ProcessingFut,
+	PrewitnessCall,
+	PrewitnessFut,
+>(
+	scope: &Scope<'_, anyhow::Error>,
+	eth_client: EthersRetryRpcClient,
+	witnessing_mode: EthWitnessingMode,
+	process_call: ProcessCall,
+	prewitness_call: PrewitnessCall,
+	state_chain_client: Arc<StateChainClient>,
+	state_chain_stream: StateChainStream,
+	unfinalized_state_chain_stream: impl StateChainStreamApi<false>,
+	epoch_source: EpochSourceBuilder<'_, '_, StateChainClient, (), ()>,
+	db: Arc<PersistentKeyDB>,
+) -> Result<()>
+where
+	StateChainClient: StorageApi + ChainApi + 'static + Send + Sync,
+	StateChainStream: StateChainStreamApi + Clone,
+	ProcessCall: Fn(state_chain_runtime::RuntimeCall, EpochIndex) -> ProcessingFut
+		+ Send
+		+ Sync
+		+ Clone
+		+ 'static,
+	ProcessingFut: Future<Output = ()> + Send + 'static,
+	PrewitnessCall: Fn(state_chain_runtime::RuntimeCall, EpochIndex) -> PrewitnessFut
+		+ Send
+		+ Sync
+		+ Clone
+		+ 'static,
+	PrewitnessFut: Future<Output = ()> + Send + 'static,
+{
+	let ContractAddresses {
+		state_chain_gateway_address,
+		key_manager_address,
+		vault_address,
+		address_checker_address,
+		usdc_contract_address,
+		flip_contract_address,
+		supported_erc20_tokens,
+	} = contract_addresses(&*state_chain_client).await?;
+
+	let eth_source = EthSource::new(eth_client.clone())
+		.recording(eth_client.recorder().cloned())
+		// Reorgs are only detected and recorded in the metrics. A witness can't be undone once it
+		// has been submitted, so the blocks re-emitted from the new branch are dropped by strictly
+		// monotonic, and the safety margin keeps the witnessing off forked blocks.
+		.reorg_aware("Ethereum", REORG_TRACKED_BLOCKS)
+		.strictly_monotonic()
+		.shared(scope);
 
 	eth_source
 		.clone()
 		.chunk_by_time(epoch_source.clone(), scope)
-		.chain_tracking(state_chain_client.clone(), eth_client.clone())
+		.chain_tracking(process_call.clone(), eth_client.clone())
+		.report_chain_tip(MAX_WITNESSING_LAG)
 		.logging("chain tracking")
 		.spawn(scope);
 
 	let vaults = epoch_source.vaults().await;
 
 	// ===== Prewitnessing stream =====
-	let prewitness_source =
-		eth_source.clone().strictly_monotonic().chunk_by_vault(vaults.clone(), scope);
+	let prewitness_source = eth_source.clone().chunk_by_vault(vaults.clone(), scope);
 
 	let prewitness_source_deposit_addresses = prewitness_source
 		.clone()
@@ -186,10 +240,17 @@ where
 
 	// ===== Full witnessing stream =====
 
-	let eth_safe_vault_source = eth_source
-		.strictly_monotonic()
-		.lag_safety(SAFETY_MARGIN)
+	let eth_safe_source = match witnessing_mode {
+		EthWitnessingMode::FixedLag => EitherSource::Left(eth_source.lag_safety(SAFETY_MARGIN)),
+		EthWitnessingMode::Safe =>
+			EitherSource::Right(EthTaggedSource::new(eth_client.clone(), BlockNumber::Safe)),
+		EthWitnessingMode::Finalized =>
+			EitherSource::Right(EthTaggedSource::new(eth_client.clone(), BlockNumber::Finalized)),
+	};
+
+	let eth_safe_vault_source = eth_safe_source
 		.logging("safe block produced")
+		.report_tip("safe")
 		.chunk_by_vault(vaults, scope);
 
 	let eth_safe_vault_source_deposit_addresses = eth_safe_vault_source
@@ -201,6 +262,7 @@ where
 		.clone()
 		.key_manager_witnessing(process_call.clone(), eth_client.clone(), key_manager_address)
 		.continuous("KeyManager".to_string(), db.clone())
+		.report_witnessed("safe", "KeyManager")
 		.logging("KeyManager")
 		.spawn(scope);
 
@@ -212,6 +274,7 @@ where
 			state_chain_gateway_address,
 		)
 		.continuous("StateChainGateway".to_string(), db.clone())
+		.report_witnessed("safe", "StateChainGateway")
 		.logging("StateChainGateway")
 		.spawn(scope);
 
@@ -225,6 +288,7 @@ where
 		)
 		.await?
 		.continuous("USDCDeposits".to_string(), db.clone())
+		.report_witnessed("safe", "USDCDeposits")
 		.logging("USDCDeposits")
 		.spawn(scope);
 
@@ -238,6 +302,7 @@ where
 		)
 		.await?
 		.continuous("FlipDeposits".to_string(), db.clone())
+		.report_witnessed("safe", "FlipDeposits")
 		.logging("FlipDeposits")
 		.spawn(scope);
 
@@ -252,6 +317,7 @@ where
 		)
 		.await
 		.continuous("EthereumDeposits".to_string(), db.clone())
+		.report_witnessed("safe", "EthereumDeposits")
 		.logging("EthereumDeposits")
 		.spawn(scope);
 
@@ -265,8 +331,115 @@ where
 			supported_erc20_tokens,
 		)
 		.continuous("Vault".to_string(), db)
+		.report_witnessed("safe", "Vault")
 		.logging("Vault")
 		.spawn(scope);
 
 	Ok(())
 }
+
+/// Witnesses the blocks `from..=to` again with the full witnessing pipelines, returning once they
+/// have all been processed.
+pub async fn rewitness<StateChainClient, StateChainStream, ProcessCall, ProcessingFut>(
+	scope: &Scope<'_, anyhow::Error>,
+	eth_client: EthersRetryRpcClient,
+	from: u64,
+	to: u64,
+	process_call: ProcessCall,
+	state_chain_client: Arc<StateChainClient>,
+	state_chain_stream: StateChainStream,
+	epoch_source: EpochSourceBuilder<'_, '_, StateChainClient, (), ()>,
+) -> Result<()>
+where
+	StateChainClient: StorageApi + ChainApi + 'static + Send + Sync,
+	StateChainStream: StateChainStreamApi + Clone,
+	ProcessCall: Fn(state_chain_runtime::RuntimeCall, EpochIndex) -> ProcessingFut
+		+ Send
+		+ Sync
+		+ Clone
+		+ 'static,
+	ProcessingFut: Future<Output = ()> + Send + 'static,
+{
+	let ContractAddresses {
+		state_chain_gateway_address,
+		key_manager_address,
+		vault_address,
+		address_checker_address,
+		usdc_contract_address,
+		flip_contract_address,
+		supported_erc20_tokens,
+	} = contract_addresses(&*state_chain_client).await?;
+
+	let vaults = epoch_source.vaults().await;
+
+	let eth_vault_source = RangeSource::<_, cf_chains::Ethereum>::new(eth_client.clone(), from, to)
+		.chunk_by_vault_unshared(vaults);
+
+	let eth_vault_source_deposit_addresses = eth_vault_source
+		.clone()
+		.deposit_addresses(scope, state_chain_stream.clone(), state_chain_client.clone())
+		.await;
+
+	let usdc_deposits = eth_vault_source_deposit_addresses
+		.clone()
+		.erc20_deposits::<_, _, _, UsdcEvents>(
+			process_call.clone(),
+			eth_client.clone(),
+			cf_primitives::chains::assets::eth::Asset::Usdc,
+			usdc_contract_address,
+		)
+		.await?;
+
+	let flip_deposits = eth_vault_source_deposit_addresses
+		.clone()
+		.erc20_deposits::<_, _, _, FlipEvents>(
+			process_call.clone(),
+			eth_client.clone(),
+			cf_primitives::chains::assets::eth::Asset::Flip,
+			flip_contract_address,
+		)
+		.await?;
+
+	let ethereum_deposits = eth_vault_source_deposit_addresses
+		.ethereum_deposits(
+			process_call.clone(),
+			eth_client.clone(),
+			eth::Asset::Eth,
+			address_checker_address,
+			vault_address,
+		)
+		.await;
+
+	futures::join!(
+		eth_vault_source
+			.clone()
+			.key_manager_witnessing(process_call.clone(), eth_client.clone(), key_manager_address)
+			.logging("rewitnessing KeyManager")
+			.run_active(),
+		eth_vault_source
+			.clone()
+			.state_chain_gateway_witnessing(
+				process_call.clone(),
+				eth_client.clone(),
+				state_chain_gateway_address,
+			)
+			.logging("rewitnessing StateChainGateway")
+			.run_active(),
+		usdc_deposits.logging("rewitnessing USDCDeposits").run_active(),
+		flip_deposits.logging("rewitnessing FlipDeposits").run_active(),
+		ethereum_deposits.logging("rewitnessing EthereumDeposits").run_active(),
+		eth_vault_source
+			.vault_witnessing(
+				process_call,
+				eth_client.clone(),
+				vault_address,
+				cf_primitives::Asset::Eth,
+				cf_primitives::ForeignChain::Ethereum,
+				supported_erc20_tokens,
+			)
+			.logging("rewitnessing Vault")
+			.run_active(),
+	);
+
+	Ok(())
+}
@@ -61,6 +61,9 @@ define_erc20!(
 define_erc20!(usdc, Usdc, UsdcEvents, "$CF_ETH_CONTRACT_ABI_ROOT/IUSDC.json");
 
 impl<Inner: ChunkedByVault> ChunkedByVaultBuilder<Inner> {
+	/// Witnesses the transfers of the token to the deposit addresses. Outputs the deposit address
+	/// and the hash of the transaction of each deposit in the block, in the order they were
+	/// witnessed.
 	pub async fn erc20_deposits<ProcessCall, ProcessingFut, EthRetryRpcClient, Events>(
 		self,
 		process_call: ProcessCall,
@@ -101,33 +104,37 @@ impl<Inner: ChunkedByVault> ChunkedByVaultBuilder<Inner> {
 					.map(|deposit_channel| deposit_channel.deposit_channel.address)
 					.collect::<HashSet<_>>();
 
-				let deposit_witnesses = events_at_block::<Events, _>(
-					Header {
-						index: header.index,
-						hash: header.hash,
-						parent_hash: header.parent_hash,
-						data: header.data.0,
-					},
-					asset_contract_address,
-					&eth_rpc,
-				)
-				.await?
-				.into_iter()
-				.filter_map(|event| {
-					match event.event_parameters.into() {
-						Erc20Events::TransferFilter{to, value, from: _ } if addresses.contains(&to) =>
-							Some(DepositWitness {
-								deposit_address: to,
-								amount: value.try_into().expect(
-									"Any ERC20 tokens we support should have amounts that fit into a u128",
-								),
-								asset,
-								deposit_details: (),
-							}),
-						_ => None,
-				}
-				})
-				.collect::<Vec<_>>();
+				let (deposit_witnesses, deposit_transactions): (Vec<_>, Vec<_>) =
+					events_at_block::<Events, _>(
+						Header {
+							index: header.index,
+							hash: header.hash,
+							parent_hash: header.parent_hash,
+							data: header.data.0,
+						},
+						asset_contract_address,
+						&eth_rpc,
+					)
+					.await?
+					.into_iter()
+					.filter_map(|event| {
+						match event.event_parameters.into() {
+							Erc20Events::TransferFilter { to, value, from: _ } if addresses.contains(&to) =>
+								Some((
+									DepositWitness {
+										deposit_address: to,
+										amount: value.try_into().expect(
+											"Any ERC20 tokens we support should have amounts that fit into a u128",
+										),
+										asset,
+										deposit_details: (),
+									},
+									(to, event.tx_hash),
+								)),
+							_ => None,
+						}
+					})
+					.unzip();
 
 				if !deposit_witnesses.is_empty() {
 					process_call(
@@ -144,7 +151,7 @@ impl<Inner: ChunkedByVault> ChunkedByVaultBuilder<Inner> {
 					.await;
 				}
 
-				Ok::<(), anyhow::Error>(())
+				Ok::<_, anyhow::Error>(deposit_transactions)
 			}
 		}))
 	}
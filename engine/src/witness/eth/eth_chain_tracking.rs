@@ -1,5 +1,5 @@
 use crate::{eth::retry_rpc::EthersRetryRpcApi, witness::common::chain_source::Header};
-use cf_chains::eth::EthereumTrackedData;
+use cf_chains::{arb::ArbitrumTrackedData, eth::EthereumTrackedData};
 use ethers::types::Bloom;
 use sp_core::U256;
 use utilities::context;
@@ -30,3 +30,27 @@ impl<T: EthersRetryRpcApi + Send + Sync + Clone> GetTrackedData<cf_chains::Ether
 		})
 	}
 }
+
+#[async_trait::async_trait]
+impl<T: EthersRetryRpcApi + Send + Sync + Clone> GetTrackedData<cf_chains::Arbitrum, H256, Bloom>
+	for T
+{
+	async fn get_tracked_data(
+		&self,
+		header: &Header<<cf_chains::Arbitrum as cf_chains::Chain>::ChainBlockNumber, H256, Bloom>,
+	) -> Result<<cf_chains::Arbitrum as cf_chains::Chain>::TrackedData, anyhow::Error> {
+		const PRIORITY_FEE_PERCENTILE: f64 = 50.0;
+		let fee_history = self
+			.fee_history(U256::one(), header.index.into(), vec![PRIORITY_FEE_PERCENTILE])
+			.await;
+
+		Ok(ArbitrumTrackedData {
+			base_fee: (*context!(fee_history.base_fee_per_gas.first())?)
+				.try_into()
+				.expect("Base fee should fit u128"),
+			priority_fee: (*context!(context!(fee_history.reward.first())?.first())?)
+				.try_into()
+				.expect("Priority fee should fit u128"),
+		})
+	}
+}
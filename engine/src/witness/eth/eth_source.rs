@@ -1,29 +1,38 @@
-use ethers::types::Bloom;
+use ethers::types::{BlockNumber, Bloom};
 use sp_core::H256;
+use tracing::warn;
 
 use crate::{
 	eth::{
-		core_h256, retry_rpc::EthersRetrySubscribeApi, ConscientiousEthWebsocketBlockHeaderStream,
+		core_h256,
+		retry_rpc::{EthersRetryRpcApi, EthersRetrySubscribeApi},
+		ConscientiousEthWebsocketBlockHeaderStream,
 	},
 	witness::common::{
 		chain_source::{BoxChainStream, ChainClient, ChainSource, Header},
-		ExternalChainSource,
+		ExternalChain, ExternalChainSource,
 	},
 };
 use futures::stream::StreamExt;
 use futures_util::stream;
-use std::time::Duration;
+use std::{marker::PhantomData, time::Duration};
 
+/// A source of block headers for any EVM chain, as all of them share the same RPC interface.
 #[derive(Clone)]
-pub struct EthSource<C> {
+pub struct EvmSource<C, EvmChain> {
 	client: C,
+	_phantom: PhantomData<EvmChain>,
 }
 
-impl<C: EthersRetrySubscribeApi + ChainClient<Index = u64, Hash = H256, Data = Bloom> + Clone>
-	EthSource<C>
+pub type EthSource<C> = EvmSource<C, cf_chains::Ethereum>;
+
+impl<C, EvmChain> EvmSource<C, EvmChain>
+where
+	C: EthersRetrySubscribeApi + ChainClient<Index = u64, Hash = H256, Data = Bloom> + Clone,
+	EvmChain: ExternalChain<ChainBlockNumber = u64>,
 {
 	pub fn new(client: C) -> Self {
-		Self { client }
+		Self { client, _phantom: PhantomData }
 	}
 }
 
@@ -31,9 +40,10 @@ const TIMEOUT: Duration = Duration::from_secs(60);
 const RESTART_STREAM_DELAY: Duration = Duration::from_secs(6);
 
 #[async_trait::async_trait]
-impl<C> ChainSource for EthSource<C>
+impl<C, EvmChain> ChainSource for EvmSource<C, EvmChain>
 where
 	C: EthersRetrySubscribeApi + ChainClient<Index = u64, Hash = H256, Data = Bloom> + Clone,
+	EvmChain: ExternalChain<ChainBlockNumber = u64>,
 {
 	type Index = <C as ChainClient>::Index;
 	type Hash = <C as ChainClient>::Hash;
@@ -85,9 +95,106 @@ where
 	}
 }
 
-impl<C> ExternalChainSource for EthSource<C>
+impl<C, EvmChain> ExternalChainSource for EvmSource<C, EvmChain>
 where
 	C: EthersRetrySubscribeApi + ChainClient<Index = u64, Hash = H256, Data = Bloom> + Clone,
+	EvmChain: ExternalChain<ChainBlockNumber = u64>,
+{
+	type Chain = EvmChain;
+}
+
+/// A source of block headers for any EVM chain that follows the block the node has assigned a given
+/// tag, such as `finalized` or `safe`, instead of the head of the chain. Every block up to the
+/// tagged block is produced in order, so the stream never skips a block.
+#[derive(Clone)]
+pub struct EvmTaggedSource<C, EvmChain> {
+	client: C,
+	tag: BlockNumber,
+	_phantom: PhantomData<EvmChain>,
+}
+
+pub type EthTaggedSource<C> = EvmTaggedSource<C, cf_chains::Ethereum>;
+
+impl<C, EvmChain> EvmTaggedSource<C, EvmChain>
+where
+	C: EthersRetryRpcApi + ChainClient<Index = u64, Hash = H256, Data = Bloom> + Clone,
+	EvmChain: ExternalChain<ChainBlockNumber = u64>,
+{
+	pub fn new(client: C, tag: BlockNumber) -> Self {
+		Self { client, tag, _phantom: PhantomData }
+	}
+}
+
+/// The length of an Ethereum slot. The `safe` tag can move at most once per slot, and the
+/// `finalized` tag once per epoch of 32 slots, so polling more often than this finds nothing new.
+const TAG_POLL_INTERVAL: Duration = Duration::from_secs(12);
+
+#[async_trait::async_trait]
+impl<C, EvmChain> ChainSource for EvmTaggedSource<C, EvmChain>
+where
+	C: EthersRetryRpcApi + ChainClient<Index = u64, Hash = H256, Data = Bloom> + Clone,
+	EvmChain: ExternalChain<ChainBlockNumber = u64>,
+{
+	type Index = <C as ChainClient>::Index;
+	type Hash = <C as ChainClient>::Hash;
+	type Data = <C as ChainClient>::Data;
+	type Client = C;
+
+	async fn stream_and_client(
+		&self,
+	) -> (BoxChainStream<'_, Self::Index, Self::Hash, Self::Data>, Self::Client) {
+		pub struct State<C> {
+			client: C,
+			tag: BlockNumber,
+			next_index: Option<u64>,
+			tagged_index: u64,
+		}
+
+		(
+			Box::pin(stream::unfold(
+				State {
+					client: self.client.clone(),
+					tag: self.tag,
+					next_index: None,
+					tagged_index: 0,
+				},
+				|mut state| async move {
+					loop {
+						if let Some(next_index) =
+							state.next_index.filter(|next_index| *next_index <= state.tagged_index)
+						{
+							let header = state.client.header_at_index(next_index).await;
+							state.next_index = Some(next_index + 1);
+							return Some((header, state))
+						}
+
+						if state.next_index.is_some() {
+							tokio::time::sleep(TAG_POLL_INTERVAL).await;
+						}
+
+						let tag = state.tag;
+						match state.client.block_by_tag(tag).await.number {
+							Some(tagged_index) => {
+								state.tagged_index = tagged_index.as_u64();
+								state.next_index.get_or_insert(state.tagged_index);
+							},
+							None => {
+								warn!("Block for tag {tag:?} has no block number, retrying.");
+								tokio::time::sleep(TAG_POLL_INTERVAL).await;
+							},
+						}
+					}
+				},
+			)),
+			self.client.clone(),
+		)
+	}
+}
+
+impl<C, EvmChain> ExternalChainSource for EvmTaggedSource<C, EvmChain>
+where
+	C: EthersRetryRpcApi + ChainClient<Index = u64, Hash = H256, Data = Bloom> + Clone,
+	EvmChain: ExternalChain<ChainBlockNumber = u64>,
 {
-	type Chain = cf_chains::Ethereum;
+	type Chain = EvmChain;
 }
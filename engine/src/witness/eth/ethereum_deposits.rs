@@ -317,7 +317,7 @@ mod tests {
 				let settings = Settings::new_test().unwrap();
 				let client = EthersRetryRpcClient::new(
 					scope,
-					settings.eth.private_key_file,
+					Some(settings.eth.private_key_file),
 					settings.eth.nodes,
 					U256::from(1337u64),
 				)
@@ -202,15 +202,19 @@ mod tests {
 						primary: WsHttpEndpoints {
 							ws_endpoint: "ws://localhost:8546".into(),
 							http_endpoint: "http://localhost:8545".into(),
+							rate_limit: None,
 						},
 						backup: None,
 					},
 					private_key_file: PathBuf::from_str("/some/key/file").unwrap(),
+					witnessing_mode: settings::EthWitnessingMode::FixedLag,
+					recording_file: None,
+					cross_check_rpcs: false,
 				};
 
 				let retry_client = EthersRetryRpcClient::new(
 					scope,
-					eth_settings.private_key_file,
+					Some(eth_settings.private_key_file),
 					eth_settings.nodes,
 					U256::from(1337u64),
 				)
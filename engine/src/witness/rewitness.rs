@@ -0,0 +1,244 @@
+use std::{
+	collections::BTreeMap,
+	sync::{Arc, Mutex},
+};
+
+use cf_primitives::EpochIndex;
+use codec::Encode;
+use pallet_cf_witnesser::{CallHash, WitnessDataExtraction};
+use tracing::{error, info};
+use utilities::task_scope::Scope;
+
+use crate::{
+	btc::retry_rpc::BtcRetryRpcClient,
+	dot::retry_rpc::DotRetryRpcClient,
+	eth::retry_rpc::EthersRetryRpcClient,
+	state_chain_observer::client::{
+		chain_api::ChainApi,
+		extrinsic_api::signed::{SignedExtrinsicApi, UntilFinalized},
+		storage_api::StorageApi,
+		StateChainStreamApi,
+	},
+};
+
+use super::common::{epoch_source::EpochSource, STATE_CHAIN_CONNECTION};
+
+use anyhow::Result;
+
+/// The client of the chain to rewitness.
+pub enum RewitnessClient {
+	Ethereum(EthersRetryRpcClient),
+	Polkadot(DotRetryRpcClient),
+	Bitcoin(BtcRetryRpcClient),
+	Arbitrum(EthersRetryRpcClient),
+}
+
+/// The number of witnesses of an epoch by their outcome.
+#[derive(Default, Debug)]
+struct EpochReport {
+	submitted: usize,
+	failed: usize,
+	skipped: usize,
+}
+
+/// Witnesses the blocks `from..=to` of a chain again, with the same witnessing as `start`, and
+/// returns once they have all been processed. Only the witnesses that are still pending on the
+/// State Chain are submitted, so this is safe to run over blocks that were already witnessed.
+/// Each submission is waited on until it is finalized, and the number of witnesses submitted,
+/// failed and skipped is printed for each epoch. Returns an error if any submission failed.
+///
+/// Only blocks of vaults that haven't expired yet are witnessed, and deposits are only witnessed
+/// to deposit addresses the State Chain still knows about.
+///
+/// Must not be run while the engine is running with the same account, as the nonces of their
+/// extrinsics would clash.
+pub async fn rewitness<StateChainClient>(
+	scope: &Scope<'_, anyhow::Error>,
+	client: RewitnessClient,
+	from: u64,
+	to: u64,
+	state_chain_client: Arc<StateChainClient>,
+	state_chain_stream: impl StateChainStreamApi + Clone,
+) -> Result<()>
+where
+	StateChainClient: StorageApi + ChainApi + SignedExtrinsicApi + 'static + Send + Sync,
+{
+	anyhow::ensure!(from <= to, "The range to rewitness is empty: {from} > {to}");
+
+	let epoch_source =
+		EpochSource::builder(scope, state_chain_stream.clone(), state_chain_client.clone())
+			.await
+			.participating(state_chain_client.account_id())
+			.await;
+
+	let reports = Arc::new(Mutex::new(BTreeMap::<EpochIndex, EpochReport>::new()));
+
+	let witness_call = {
+		let state_chain_client = state_chain_client.clone();
+		let reports = reports.clone();
+		move |call: state_chain_runtime::RuntimeCall, epoch_index| {
+			let state_chain_client = state_chain_client.clone();
+			let reports = reports.clone();
+			async move {
+				if is_witness_pending(&*state_chain_client, &call, epoch_index).await {
+					let result = state_chain_client
+						.finalize_signed_extrinsic(pallet_cf_witnesser::Call::witness_at_epoch {
+							call: Box::new(call.clone()),
+							epoch_index,
+						})
+						.await
+						.until_finalized()
+						.await;
+					let mut reports = reports.lock().unwrap();
+					let report = reports.entry(epoch_index).or_default();
+					match result {
+						Ok(_) => report.submitted += 1,
+						Err(e) => {
+							error!(
+								"Failed to submit witness at epoch {epoch_index}: {call:?}: {e}"
+							);
+							report.failed += 1;
+						},
+					}
+				} else {
+					info!("Skipping witness that is no longer pending: {call:?}");
+					reports.lock().unwrap().entry(epoch_index).or_default().skipped += 1;
+				}
+			}
+		}
+	};
+
+	let result = match client {
+		RewitnessClient::Ethereum(eth_client) =>
+			super::eth::rewitness(
+				scope,
+				eth_client,
+				from,
+				to,
+				witness_call,
+				state_chain_client,
+				state_chain_stream,
+				epoch_source,
+			)
+			.await,
+		RewitnessClient::Polkadot(dot_client) =>
+			super::dot::rewitness(
+				scope,
+				dot_client,
+				from.try_into()?,
+				to.try_into()?,
+				witness_call,
+				state_chain_client,
+				state_chain_stream,
+				epoch_source,
+			)
+			.await,
+		RewitnessClient::Bitcoin(btc_client) =>
+			super::btc::rewitness(
+				scope,
+				btc_client,
+				from,
+				to,
+				witness_call,
+				state_chain_client,
+				state_chain_stream,
+				epoch_source,
+			)
+			.await,
+		RewitnessClient::Arbitrum(arb_client) =>
+			super::arb::rewitness(
+				scope,
+				arb_client,
+				from,
+				to,
+				witness_call,
+				state_chain_client,
+				state_chain_stream,
+				epoch_source,
+			)
+			.await,
+	};
+
+	let reports = reports.lock().unwrap();
+	for (epoch_index, EpochReport { submitted, failed, skipped }) in reports.iter() {
+		println!(
+			"Epoch {epoch_index}: {submitted} witnesses submitted, {failed} failed, {skipped} skipped as no longer pending"
+		);
+	}
+	result?;
+	let failed = reports.values().map(|report| report.failed).sum::<usize>();
+	anyhow::ensure!(failed == 0, "Failed to submit {failed} witnesses");
+	Ok(())
+}
+
+/// A witness is pending if the call hasn't been executed yet, the epoch hasn't expired and we are
+/// an authority of the epoch that hasn't voted for the call yet.
+async fn is_witness_pending<StateChainClient>(
+	state_chain_client: &StateChainClient,
+	call: &state_chain_runtime::RuntimeCall,
+	epoch_index: EpochIndex,
+) -> bool
+where
+	StateChainClient: StorageApi + ChainApi + SignedExtrinsicApi,
+{
+	let block_hash = state_chain_client.latest_finalized_block().hash;
+
+	// The call hash is computed the same way as the witnesser pallet does it.
+	let call_hash = {
+		let mut call = call.clone();
+		let _extra_data = call.extract();
+		CallHash(sp_core::blake2_256(&call.encode()))
+	};
+
+	let last_expired_epoch = state_chain_client
+		.storage_value::<pallet_cf_validator::LastExpiredEpoch<state_chain_runtime::Runtime>>(
+			block_hash,
+		)
+		.await
+		.expect(STATE_CHAIN_CONNECTION);
+	let current_epoch = state_chain_client
+		.storage_value::<pallet_cf_validator::CurrentEpoch<state_chain_runtime::Runtime>>(
+			block_hash,
+		)
+		.await
+		.expect(STATE_CHAIN_CONNECTION);
+
+	if epoch_index <= last_expired_epoch {
+		return false
+	}
+
+	for epoch in last_expired_epoch..=current_epoch {
+		if state_chain_client
+			.storage_double_map_entry::<pallet_cf_witnesser::CallHashExecuted<state_chain_runtime::Runtime>>(
+				block_hash, &epoch, &call_hash,
+			)
+			.await
+			.expect(STATE_CHAIN_CONNECTION)
+			.is_some()
+		{
+			return false
+		}
+	}
+
+	let Some(authority_index) = state_chain_client
+		.storage_double_map_entry::<pallet_cf_validator::AuthorityIndex<state_chain_runtime::Runtime>>(
+			block_hash,
+			&epoch_index,
+			&state_chain_client.account_id(),
+		)
+		.await
+		.expect(STATE_CHAIN_CONNECTION)
+	else {
+		return false
+	};
+
+	!state_chain_client
+		.storage_double_map_entry::<pallet_cf_witnesser::Votes<state_chain_runtime::Runtime>>(
+			block_hash,
+			&epoch_index,
+			&call_hash,
+		)
+		.await
+		.expect(STATE_CHAIN_CONNECTION)
+		.map_or(false, |votes| pallet_cf_witnesser::has_voted(&votes, authority_index))
+}
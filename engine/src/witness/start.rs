@@ -1,5 +1,7 @@
 use std::sync::Arc;
 
+use cf_primitives::EpochIndex;
+use futures_core::Future;
 use utilities::task_scope::Scope;
 
 use crate::{
@@ -7,6 +9,7 @@ use crate::{
 	db::PersistentKeyDB,
 	dot::retry_rpc::DotRetryRpcClient,
 	eth::retry_rpc::EthersRetryRpcClient,
+	settings::{DotWitnessingMode, EthWitnessingMode},
 	state_chain_observer::client::{
 		extrinsic_api::signed::SignedExtrinsicApi, storage_api::StorageApi, StateChainStreamApi,
 	},
@@ -14,20 +17,20 @@ use crate::{
 
 use crate::state_chain_observer::client::chain_api::ChainApi;
 
-use super::common::epoch_source::EpochSource;
+use super::common::epoch_source::{EpochSource, EpochSourceBuilder};
 
 use anyhow::Result;
 
-/// Starts all the witnessing tasks.
-// It's important that this function is not blocking, at any point, even if there is no connection
-// to any or all chains. This implies that the `start` function for each chain should not be
-// blocking. The chains must be able to witness independently, and if this blocks at any
-// point it means that on start up this will block, and the state chain observer will not start.
+/// Starts all the witnessing tasks, submitting the witnesses of the vaults we participate in to
+/// the State Chain.
 pub async fn start<StateChainClient>(
 	scope: &Scope<'_, anyhow::Error>,
 	eth_client: EthersRetryRpcClient,
 	btc_client: BtcRetryRpcClient,
 	dot_client: DotRetryRpcClient,
+	arb_client: Option<EthersRetryRpcClient>,
+	eth_witnessing_mode: EthWitnessingMode,
+	dot_witnessing_mode: DotWitnessingMode,
 	state_chain_client: Arc<StateChainClient>,
 	state_chain_stream: impl StateChainStreamApi + Clone,
 	unfinalised_state_chain_stream: impl StateChainStreamApi<false> + Clone,
@@ -74,9 +77,73 @@ where
 		}
 	};
 
+	start_witnessing(
+		scope,
+		eth_client,
+		btc_client,
+		dot_client,
+		arb_client,
+		eth_witnessing_mode,
+		dot_witnessing_mode,
+		witness_call,
+		prewitness_call,
+		state_chain_client,
+		state_chain_stream,
+		unfinalised_state_chain_stream,
+		epoch_source,
+		db,
+	)
+	.await
+}
+
+/// Starts the witnessing of every chain for the vaults in `epoch_source`, passing what is
+/// witnessed to `witness_call` and what is prewitnessed to `prewitness_call`. Nothing is signed
+/// here, so this can also be used without a validator account.
+// It's important that this function is not blocking, at any point, even if there is no connection
+// to any or all chains. This implies that the `start` function for each chain should not be
+// blocking. The chains must be able to witness independently, and if this blocks at any
+// point it means that on start up this will block, and the state chain observer will not start.
+pub async fn start_witnessing<
+	StateChainClient,
+	ProcessCall,
+	ProcessingFut,
+	PrewitnessCall,
+	PrewitnessFut,
+>(
+	scope: &Scope<'_, anyhow::Error>,
+	eth_client: EthersRetryRpcClient,
+	btc_client: BtcRetryRpcClient,
+	dot_client: DotRetryRpcClient,
+	arb_client: Option<EthersRetryRpcClient>,
+	eth_witnessing_mode: EthWitnessingMode,
+	dot_witnessing_mode: DotWitnessingMode,
+	witness_call: ProcessCall,
+	prewitness_call: PrewitnessCall,
+	state_chain_client: Arc<StateChainClient>,
+	state_chain_stream: impl StateChainStreamApi + Clone,
+	unfinalised_state_chain_stream: impl StateChainStreamApi<false> + Clone,
+	epoch_source: EpochSourceBuilder<'_, '_, StateChainClient, (), ()>,
+	db: Arc<PersistentKeyDB>,
+) -> Result<()>
+where
+	StateChainClient: StorageApi + ChainApi + 'static + Send + Sync,
+	ProcessCall: Fn(state_chain_runtime::RuntimeCall, EpochIndex) -> ProcessingFut
+		+ Send
+		+ Sync
+		+ Clone
+		+ 'static,
+	ProcessingFut: Future<Output = ()> + Send + 'static,
+	PrewitnessCall: Fn(state_chain_runtime::RuntimeCall, EpochIndex) -> PrewitnessFut
+		+ Send
+		+ Sync
+		+ Clone
+		+ 'static,
+	PrewitnessFut: Future<Output = ()> + Send + 'static,
+{
 	let start_eth = super::eth::start(
 		scope,
 		eth_client,
+		eth_witnessing_mode,
 		witness_call.clone(),
 		prewitness_call.clone(),
 		state_chain_client.clone(),
@@ -101,16 +168,37 @@ where
 	let start_dot = super::dot::start(
 		scope,
 		dot_client,
-		witness_call,
-		prewitness_call,
-		state_chain_client,
-		state_chain_stream,
-		unfinalised_state_chain_stream,
-		epoch_source,
-		db,
+		dot_witnessing_mode,
+		witness_call.clone(),
+		prewitness_call.clone(),
+		state_chain_client.clone(),
+		state_chain_stream.clone(),
+		unfinalised_state_chain_stream.clone(),
+		epoch_source.clone(),
+		db.clone(),
 	);
 
-	futures::future::try_join3(start_eth, start_btc, start_dot).await?;
+	// Arbitrum is only witnessed if it is configured.
+	let start_arb = async move {
+		match arb_client {
+			Some(arb_client) =>
+				super::arb::start(
+					scope,
+					arb_client,
+					witness_call,
+					prewitness_call,
+					state_chain_client,
+					state_chain_stream,
+					unfinalised_state_chain_stream,
+					epoch_source,
+					db,
+				)
+				.await,
+			None => Ok(()),
+		}
+	};
+
+	futures::future::try_join4(start_eth, start_btc, start_dot, start_arb).await?;
 
 	Ok(())
 }
@@ -265,6 +265,43 @@ impl Engine {
 							);
 					}
 
+					RuntimeEvent::EthereumThresholdSigner(
+						// A batch of signature requests
+						pallet_cf_threshold_signature::Event::ThresholdSignatureBatchRequest{
+							ceremony_id,
+							key,
+							payloads,
+							..
+						}) => {
+							queue_dispatch_extrinsic(
+								RuntimeCall::EthereumThresholdSigner(
+									pallet_cf_threshold_signature::Call::batch_signature_success{
+										ceremony_id: *ceremony_id,
+										signatures: payloads.iter().map(|payload| self.eth_threshold_signer.borrow().sign_with_key(*key, payload.as_fixed_bytes())).collect(),
+									}
+								),
+								RuntimeOrigin::none()
+							);
+					}
+
+					RuntimeEvent::PolkadotThresholdSigner(
+						pallet_cf_threshold_signature::Event::ThresholdSignatureBatchRequest {
+							ceremony_id,
+							key,
+							payloads,
+							..
+						}) => {
+							queue_dispatch_extrinsic(
+								RuntimeCall::PolkadotThresholdSigner(
+									pallet_cf_threshold_signature::Call::batch_signature_success{
+										ceremony_id: *ceremony_id,
+										signatures: payloads.iter().map(|payload| self.dot_threshold_signer.borrow().sign_with_key(*key, payload)).collect(),
+									}
+								),
+								RuntimeOrigin::none()
+							);
+					}
+
 					RuntimeEvent::BitcoinThresholdSigner(
 						pallet_cf_threshold_signature::Event::ThresholdSignatureRequest {
 							ceremony_id,
@@ -12,10 +12,10 @@ use cf_chains::{
 };
 use cf_primitives::{AccountId, AccountRole, Asset, AssetAmount, STABLE_ASSET};
 use cf_test_utilities::{assert_events_eq, assert_events_match};
-use cf_traits::{AccountRoleRegistry, EpochInfo, LpBalanceApi};
+use cf_traits::{AccountRoleRegistry, EgressApi, EpochInfo, LpBalanceApi};
 use frame_support::{
 	assert_ok,
-	traits::{OnFinalize, OnIdle, OnNewAccount},
+	traits::{OnFinalize, OnIdle, OnInitialize, OnNewAccount},
 };
 use pallet_cf_ingress_egress::DepositWitness;
 use pallet_cf_pools::{OrderId, RangeOrderSize};
@@ -26,9 +26,9 @@ use state_chain_runtime::{
 		address_derivation::AddressDerivation, ChainAddressConverter, EthEnvironment,
 		EthTransactionBuilder,
 	},
-	AccountRoles, EthereumChainTracking, EthereumInstance, LiquidityPools, LiquidityProvider,
-	Runtime, RuntimeCall, RuntimeEvent, RuntimeOrigin, Swapping, System, Timestamp, Validator,
-	Weight, Witnesser,
+	AccountRoles, EthereumChainTracking, EthereumIngressEgress, EthereumInstance, LiquidityPools,
+	LiquidityProvider, Runtime, RuntimeCall, RuntimeEvent, RuntimeOrigin, Swapping, System,
+	Timestamp, Validator, Weight, Witnesser,
 };
 
 const DORIS: AccountId = AccountId::new([0x11; 32]);
@@ -732,3 +732,39 @@ fn ethereum_ccm_can_calculate_gas_limits() {
 		);
 	});
 }
+
+#[test]
+fn egress_batched_at_the_end_of_the_block_is_signed() {
+	super::genesis::default().build().execute_with(|| {
+		let egress_id = <EthereumIngressEgress as EgressApi<Ethereum>>::schedule_egress(
+			EthAsset::Eth,
+			1_000,
+			[1u8; 20].into(),
+			None,
+		);
+
+		let block = System::block_number();
+		System::reset_events();
+		state_chain_runtime::AllPalletsWithoutSystem::on_finalize(block);
+		assert_events_match!(
+			Runtime,
+			RuntimeEvent::EthereumIngressEgress(
+				pallet_cf_ingress_egress::Event::BatchBroadcastRequested {
+					ref egress_ids,
+					..
+				},
+			) if egress_ids.contains(&egress_id) => ()
+		);
+
+		// The ingress-egress pallets finalize after the threshold signers, so the ceremony for the
+		// batch is started in the next block.
+		System::set_block_number(block + 1);
+		state_chain_runtime::AllPalletsWithoutSystem::on_initialize(block + 1);
+		assert_events_match!(
+			Runtime,
+			RuntimeEvent::EthereumThresholdSigner(
+				pallet_cf_threshold_signature::Event::ThresholdSignatureRequest { .. },
+			) => ()
+		);
+	});
+}
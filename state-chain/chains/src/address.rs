@@ -1,6 +1,9 @@
 extern crate alloc;
 
-use crate::{btc::ScriptPubkey, dot::PolkadotAccountId, eth::Address as EthereumAddress, Chain};
+use crate::{
+	btc::ScriptPubkey, dot::PolkadotAccountId, eth::Address as EthereumAddress, Arbitrum,
+	Bitcoin, Chain, Ethereum, Polkadot,
+};
 use cf_primitives::{ChannelId, ForeignChain, NetworkEnvironment};
 use codec::{Decode, Encode, MaxEncodedLen};
 use scale_info::TypeInfo;
@@ -49,6 +52,7 @@ pub enum ForeignChainAddress {
 	Eth(EthereumAddress),
 	Dot(PolkadotAccountId),
 	Btc(ScriptPubkey),
+	Arb(EthereumAddress),
 }
 
 impl ForeignChainAddress {
@@ -57,6 +61,7 @@ impl ForeignChainAddress {
 			ForeignChainAddress::Eth(_) => ForeignChain::Ethereum,
 			ForeignChainAddress::Dot(_) => ForeignChain::Polkadot,
 			ForeignChainAddress::Btc(_) => ForeignChain::Bitcoin,
+			ForeignChainAddress::Arb(_) => ForeignChain::Arbitrum,
 		}
 	}
 }
@@ -68,6 +73,7 @@ pub enum EncodedAddress {
 	Eth([u8; 20]),
 	Dot([u8; 32]),
 	Btc(Vec<u8>),
+	Arb([u8; 20]),
 }
 
 pub trait AddressConverter: Sized {
@@ -95,6 +101,9 @@ impl core::fmt::Display for EncodedAddress {
 						.unwrap_or("The address cant be decoded from the utf8 encoded bytes")
 				)
 			},
+			EncodedAddress::Arb(addr) => {
+				write!(f, "0x{}", hex::encode(&addr[..]))
+			},
 		}
 	}
 }
@@ -109,7 +118,7 @@ impl TryFrom<ForeignChainAddress> for H160 {
 
 	fn try_from(address: ForeignChainAddress) -> Result<Self, Self::Error> {
 		match address {
-			ForeignChainAddress::Eth(addr) => Ok(addr),
+			ForeignChainAddress::Eth(addr) | ForeignChainAddress::Arb(addr) => Ok(addr),
 			_ => Err(AddressError::InvalidAddress),
 		}
 	}
@@ -137,6 +146,43 @@ impl TryFrom<ForeignChainAddress> for ScriptPubkey {
 	}
 }
 
+/// Converts a chain's account into a [ForeignChainAddress]. This can't be a plain `Into` because
+/// chains can share an account type: an [EthereumAddress] is either an Ethereum or an Arbitrum
+/// address depending on the chain it belongs to.
+pub trait IntoForeignChainAddress<C: Chain> {
+	fn into_foreign_chain_address(self) -> ForeignChainAddress;
+}
+
+impl IntoForeignChainAddress<Ethereum> for EthereumAddress {
+	fn into_foreign_chain_address(self) -> ForeignChainAddress {
+		ForeignChainAddress::Eth(self)
+	}
+}
+
+impl IntoForeignChainAddress<Arbitrum> for EthereumAddress {
+	fn into_foreign_chain_address(self) -> ForeignChainAddress {
+		ForeignChainAddress::Arb(self)
+	}
+}
+
+impl IntoForeignChainAddress<Polkadot> for PolkadotAccountId {
+	fn into_foreign_chain_address(self) -> ForeignChainAddress {
+		ForeignChainAddress::Dot(self)
+	}
+}
+
+impl IntoForeignChainAddress<Bitcoin> for ScriptPubkey {
+	fn into_foreign_chain_address(self) -> ForeignChainAddress {
+		ForeignChainAddress::Btc(self)
+	}
+}
+
+impl<C: Chain> IntoForeignChainAddress<C> for ForeignChainAddress {
+	fn into_foreign_chain_address(self) -> ForeignChainAddress {
+		self
+	}
+}
+
 impl From<EthereumAddress> for ForeignChainAddress {
 	fn from(address: EthereumAddress) -> ForeignChainAddress {
 		ForeignChainAddress::Eth(address)
@@ -175,6 +221,14 @@ impl EncodedAddress {
 				Ok(EncodedAddress::Dot(address))
 			},
 			ForeignChain::Bitcoin => Ok(EncodedAddress::Btc(bytes)),
+			ForeignChain::Arbitrum => {
+				if bytes.len() != 20 {
+					return Err("Invalid Arbitrum address length")
+				}
+				let mut address = [0u8; 20];
+				address.copy_from_slice(&bytes);
+				Ok(EncodedAddress::Arb(address))
+			},
 		}
 	}
 }
@@ -189,6 +243,7 @@ pub fn to_encoded_address<GetNetwork: FnOnce() -> NetworkEnvironment>(
 		ForeignChainAddress::Btc(script_pubkey) => EncodedAddress::Btc(
 			script_pubkey.to_address(&network_environment().into()).as_bytes().to_vec(),
 		),
+		ForeignChainAddress::Arb(address) => EncodedAddress::Arb(address.0),
 	}
 }
 
@@ -208,6 +263,7 @@ pub fn try_from_encoded_address<GetNetwork: FnOnce() -> NetworkEnvironment>(
 			)
 			.map_err(|_| ())?,
 		)),
+		EncodedAddress::Arb(address_bytes) => Ok(ForeignChainAddress::Arb(address_bytes.into())),
 	}
 }
 
@@ -262,6 +318,7 @@ pub enum ForeignChainAddressHumanreadable {
 	Eth(<EthereumAddress as ToHumanreadableAddress>::Humanreadable),
 	Dot(<PolkadotAccountId as ToHumanreadableAddress>::Humanreadable),
 	Btc(<ScriptPubkey as ToHumanreadableAddress>::Humanreadable),
+	Arb(<EthereumAddress as ToHumanreadableAddress>::Humanreadable),
 }
 
 #[cfg(feature = "std")]
@@ -287,6 +344,8 @@ impl ToHumanreadableAddress for ForeignChainAddress {
 				ForeignChainAddressHumanreadable::Dot(address.to_humanreadable(network_environment)),
 			ForeignChainAddress::Btc(address) =>
 				ForeignChainAddressHumanreadable::Btc(address.to_humanreadable(network_environment)),
+			ForeignChainAddress::Arb(address) =>
+				ForeignChainAddressHumanreadable::Arb(address.to_humanreadable(network_environment)),
 		}
 	}
 }
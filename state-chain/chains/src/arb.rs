@@ -0,0 +1,157 @@
+//! Types and functions that are common to Arbitrum.
+pub mod api;
+
+pub mod benchmarking;
+
+use crate::{
+	evm::{DeploymentStatus, EvmFetchId, EvmTransactionMetadata, Transaction},
+	*,
+};
+use cf_primitives::chains::assets;
+pub use cf_primitives::chains::Arbitrum;
+use codec::{Decode, Encode, MaxEncodedLen};
+use evm::api::EvmReplayProtection;
+use frame_support::sp_runtime::{FixedPointNumber, FixedU64, RuntimeDebug};
+use scale_info::TypeInfo;
+use serde::{Deserialize, Serialize};
+use sp_std::{cmp::min, convert::TryInto};
+
+// Reference constants for the chain spec
+pub const CHAIN_ID_MAINNET: u64 = 42161;
+pub const CHAIN_ID_SEPOLIA: u64 = 421614;
+
+impl Chain for Arbitrum {
+	const NAME: &'static str = "Arbitrum";
+	type ChainCrypto = evm::EvmCrypto;
+
+	type ChainBlockNumber = u64;
+	type ChainAmount = EthAmount;
+	type TransactionFee = evm::TransactionFee;
+	type TrackedData = ArbitrumTrackedData;
+	type ChainAccount = evm::Address;
+	type ChainAsset = assets::arb::Asset;
+	type EpochStartData = ();
+	type DepositFetchId = EvmFetchId;
+	type DepositChannelState = DeploymentStatus;
+	type DepositDetails = ();
+	type Transaction = Transaction;
+	type TransactionMetadata = EvmTransactionMetadata;
+	type ReplayProtectionParams = Self::ChainAccount;
+	type ReplayProtection = EvmReplayProtection;
+}
+
+#[derive(
+	Copy,
+	Clone,
+	RuntimeDebug,
+	PartialEq,
+	Eq,
+	Encode,
+	Decode,
+	MaxEncodedLen,
+	TypeInfo,
+	Serialize,
+	Deserialize,
+)]
+#[codec(mel_bound())]
+pub struct ArbitrumTrackedData {
+	pub base_fee: <Arbitrum as Chain>::ChainAmount,
+	pub priority_fee: <Arbitrum as Chain>::ChainAmount,
+}
+
+impl ArbitrumTrackedData {
+	pub fn max_fee_per_gas(
+		&self,
+		base_fee_multiplier: FixedU64,
+	) -> <Arbitrum as Chain>::ChainAmount {
+		base_fee_multiplier
+			.saturating_mul_int(self.base_fee)
+			.saturating_add(self.priority_fee)
+	}
+}
+
+impl Default for ArbitrumTrackedData {
+	#[track_caller]
+	fn default() -> Self {
+		panic!("You should not use the default chain tracking, as it's meaningless.")
+	}
+}
+
+impl FeeRefundCalculator<Arbitrum> for Transaction {
+	fn return_fee_refund(
+		&self,
+		fee_paid: <Arbitrum as Chain>::TransactionFee,
+	) -> <Arbitrum as Chain>::ChainAmount {
+		min(
+			self.max_fee_per_gas
+				.unwrap_or_default()
+				.try_into()
+				.expect("In practice `max_fee_per_gas` is always less than u128::MAX"),
+			fee_paid.effective_gas_price,
+		)
+		.saturating_mul(fee_paid.gas_used)
+	}
+}
+
+impl From<&DepositChannel<Arbitrum>> for EvmFetchId {
+	fn from(channel: &DepositChannel<Arbitrum>) -> Self {
+		match channel.state {
+			DeploymentStatus::Undeployed => EvmFetchId::DeployAndFetch(channel.channel_id),
+			DeploymentStatus::Pending | DeploymentStatus::Deployed =>
+				if channel.asset == assets::arb::Asset::ArbEth {
+					EvmFetchId::NotRequired
+				} else {
+					EvmFetchId::Fetch(channel.address)
+				},
+		}
+	}
+}
+
+#[cfg(test)]
+mod lifecycle_tests {
+	use super::*;
+	const ARBETH: assets::arb::Asset = assets::arb::Asset::ArbEth;
+	const ARBUSDC: assets::arb::Asset = assets::arb::Asset::ArbUsdc;
+
+	macro_rules! expect_deposit_state {
+		( $state:expr, $asset:expr, $pat:pat ) => {
+			assert!(matches!(
+				DepositChannel::<Arbitrum> {
+					channel_id: Default::default(),
+					address: Default::default(),
+					asset: $asset,
+					state: $state,
+				}
+				.fetch_id(),
+				$pat
+			));
+		};
+	}
+
+	#[test]
+	fn native_asset_is_not_fetched_once_deployed() {
+		let mut state = DeploymentStatus::default();
+		expect_deposit_state!(state, ARBETH, EvmFetchId::DeployAndFetch(..));
+		expect_deposit_state!(state, ARBUSDC, EvmFetchId::DeployAndFetch(..));
+
+		assert!(state.on_fetch_scheduled());
+		assert!(state.on_fetch_completed());
+		assert_eq!(state, DeploymentStatus::Deployed);
+		expect_deposit_state!(state, ARBETH, EvmFetchId::NotRequired);
+		expect_deposit_state!(state, ARBUSDC, EvmFetchId::Fetch(..));
+	}
+
+	#[test]
+	fn addresses_are_tagged_with_arbitrum() {
+		let address = evm::Address::repeat_byte(0xab);
+		assert_eq!(
+			IntoForeignChainAddress::<Arbitrum>::into_foreign_chain_address(address),
+			ForeignChainAddress::Arb(address)
+		);
+		assert_eq!(
+			IntoForeignChainAddress::<Ethereum>::into_foreign_chain_address(address),
+			ForeignChainAddress::Eth(address)
+		);
+		assert_eq!(evm::Address::try_from(ForeignChainAddress::Arb(address)), Ok(address));
+	}
+}
@@ -0,0 +1,202 @@
+use super::Arbitrum;
+use crate::{
+	evm::{
+		api::{
+			all_batch, execute_x_swap_and_call, set_agg_key_with_agg_key, EvmEnvironmentProvider,
+			EvmReplayProtection, EvmTransactionBuilder,
+		},
+		EvmCrypto, EvmFetchId,
+	},
+	*,
+};
+use evm::api::common::*;
+use frame_support::{
+	sp_runtime::DispatchError, CloneNoBound, DebugNoBound, EqNoBound, Never, PartialEqNoBound,
+};
+use sp_std::marker::PhantomData;
+
+/// Chainflip api calls available on Arbitrum. Only the KeyManager and the Vault contracts are
+/// deployed on Arbitrum, so the State Chain Gateway calls of [crate::eth::api::EthereumApi] are
+/// not available here.
+#[derive(CloneNoBound, DebugNoBound, PartialEqNoBound, EqNoBound, Encode, Decode, TypeInfo)]
+#[scale_info(skip_type_params(Environment))]
+pub enum ArbitrumApi<Environment: 'static> {
+	SetAggKeyWithAggKey(EvmTransactionBuilder<set_agg_key_with_agg_key::SetAggKeyWithAggKey>),
+	AllBatch(EvmTransactionBuilder<all_batch::AllBatch>),
+	ExecutexSwapAndCall(EvmTransactionBuilder<execute_x_swap_and_call::ExecutexSwapAndCall>),
+	#[doc(hidden)]
+	#[codec(skip)]
+	_Phantom(PhantomData<Environment>, Never),
+}
+
+impl<E> SetAggKeyWithAggKey<EvmCrypto> for ArbitrumApi<E>
+where
+	E: EvmEnvironmentProvider<Arbitrum> + ReplayProtectionProvider<Arbitrum>,
+{
+	fn new_unsigned(
+		_old_key: Option<<EvmCrypto as ChainCrypto>::AggKey>,
+		new_key: <EvmCrypto as ChainCrypto>::AggKey,
+	) -> Result<Self, SetAggKeyWithAggKeyError> {
+		Ok(Self::SetAggKeyWithAggKey(EvmTransactionBuilder::new_unsigned(
+			E::replay_protection(E::key_manager_address()),
+			set_agg_key_with_agg_key::SetAggKeyWithAggKey::new(new_key),
+		)))
+	}
+}
+
+impl<E> AllBatch<Arbitrum> for ArbitrumApi<E>
+where
+	E: EvmEnvironmentProvider<Arbitrum> + ReplayProtectionProvider<Arbitrum>,
+{
+	fn new_unsigned(
+		fetch_params: Vec<FetchAssetParams<Arbitrum>>,
+		transfer_params: Vec<TransferAssetParams<Arbitrum>>,
+	) -> Result<Self, AllBatchError> {
+		let mut fetch_only_params = vec![];
+		let mut fetch_deploy_params = vec![];
+		for FetchAssetParams { deposit_fetch_id, asset } in fetch_params {
+			if let Some(token_address) = E::token_address(asset) {
+				match deposit_fetch_id {
+					EvmFetchId::Fetch(contract_address) => {
+						debug_assert!(
+							asset != assets::arb::Asset::ArbEth,
+							"ArbEth should not be fetched. It is auto-fetched in the smart contract."
+						);
+						fetch_only_params.push(EncodableFetchAssetParams {
+							contract_address,
+							asset: token_address,
+						})
+					},
+					EvmFetchId::DeployAndFetch(channel_id) => fetch_deploy_params
+						.push(EncodableFetchDeployAssetParams { channel_id, asset: token_address }),
+					EvmFetchId::NotRequired => (),
+				};
+			} else {
+				return Err(AllBatchError::Other)
+			}
+		}
+		if fetch_only_params.is_empty() &&
+			fetch_deploy_params.is_empty() &&
+			transfer_params.is_empty()
+		{
+			Err(AllBatchError::NotRequired)
+		} else {
+			Ok(Self::AllBatch(EvmTransactionBuilder::new_unsigned(
+				E::replay_protection(E::vault_address()),
+				all_batch::AllBatch::new(
+					fetch_deploy_params,
+					fetch_only_params,
+					transfer_params
+						.into_iter()
+						.map(|TransferAssetParams { asset, to, amount }| {
+							E::token_address(asset)
+								.map(|address| EncodableTransferAssetParams {
+									to,
+									amount,
+									asset: address,
+								})
+								.ok_or(AllBatchError::Other)
+						})
+						.collect::<Result<Vec<_>, _>>()?,
+				),
+			)))
+		}
+	}
+}
+
+impl<E> ExecutexSwapAndCall<Arbitrum> for ArbitrumApi<E>
+where
+	E: EvmEnvironmentProvider<Arbitrum> + ReplayProtectionProvider<Arbitrum>,
+{
+	fn new_unsigned(
+		egress_id: EgressId,
+		transfer_param: TransferAssetParams<Arbitrum>,
+		source_chain: ForeignChain,
+		source_address: Option<ForeignChainAddress>,
+		gas_budget: <Arbitrum as Chain>::ChainAmount,
+		message: Vec<u8>,
+	) -> Result<Self, DispatchError> {
+		let transfer_param = EncodableTransferAssetParams {
+			asset: E::token_address(transfer_param.asset).ok_or(DispatchError::CannotLookup)?,
+			to: transfer_param.to,
+			amount: transfer_param.amount,
+		};
+
+		Ok(Self::ExecutexSwapAndCall(EvmTransactionBuilder::new_unsigned(
+			E::replay_protection(E::vault_address()),
+			execute_x_swap_and_call::ExecutexSwapAndCall::new(
+				egress_id,
+				transfer_param,
+				source_chain,
+				source_address,
+				gas_budget,
+				message,
+			),
+		)))
+	}
+}
+
+impl<E> From<EvmTransactionBuilder<set_agg_key_with_agg_key::SetAggKeyWithAggKey>>
+	for ArbitrumApi<E>
+{
+	fn from(tx: EvmTransactionBuilder<set_agg_key_with_agg_key::SetAggKeyWithAggKey>) -> Self {
+		Self::SetAggKeyWithAggKey(tx)
+	}
+}
+
+impl<E> From<EvmTransactionBuilder<all_batch::AllBatch>> for ArbitrumApi<E> {
+	fn from(tx: EvmTransactionBuilder<all_batch::AllBatch>) -> Self {
+		Self::AllBatch(tx)
+	}
+}
+
+impl<E> From<EvmTransactionBuilder<execute_x_swap_and_call::ExecutexSwapAndCall>>
+	for ArbitrumApi<E>
+{
+	fn from(tx: EvmTransactionBuilder<execute_x_swap_and_call::ExecutexSwapAndCall>) -> Self {
+		Self::ExecutexSwapAndCall(tx)
+	}
+}
+
+macro_rules! map_over_api_variants {
+	( $self:expr, $var:pat_param, $var_method:expr $(,)* ) => {
+		match $self {
+			ArbitrumApi::SetAggKeyWithAggKey($var) => $var_method,
+			ArbitrumApi::AllBatch($var) => $var_method,
+			ArbitrumApi::ExecutexSwapAndCall($var) => $var_method,
+			ArbitrumApi::_Phantom(..) => unreachable!(),
+		}
+	};
+}
+
+impl<E> ArbitrumApi<E> {
+	pub fn replay_protection(&self) -> EvmReplayProtection {
+		map_over_api_variants!(self, call, call.replay_protection())
+	}
+
+	pub fn gas_budget(&self) -> Option<<Arbitrum as Chain>::ChainAmount> {
+		map_over_api_variants!(self, call, call.gas_budget())
+	}
+}
+
+impl<E> ApiCall<EvmCrypto> for ArbitrumApi<E> {
+	fn threshold_signature_payload(&self) -> <EvmCrypto as ChainCrypto>::Payload {
+		map_over_api_variants!(self, call, call.threshold_signature_payload())
+	}
+
+	fn signed(self, threshold_signature: &<EvmCrypto as ChainCrypto>::ThresholdSignature) -> Self {
+		map_over_api_variants!(self, call, call.signed(threshold_signature).into())
+	}
+
+	fn chain_encoded(&self) -> Vec<u8> {
+		map_over_api_variants!(self, call, call.chain_encoded())
+	}
+
+	fn is_signed(&self) -> bool {
+		map_over_api_variants!(self, call, call.is_signed())
+	}
+
+	fn transaction_out_id(&self) -> <EvmCrypto as ChainCrypto>::TransactionOutId {
+		map_over_api_variants!(self, call, call.transaction_out_id())
+	}
+}
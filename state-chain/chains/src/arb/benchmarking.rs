@@ -0,0 +1,25 @@
+#![cfg(feature = "runtime-benchmarks")]
+
+use crate::{
+	benchmarking_value::BenchmarkValue,
+	evm::api::{all_batch::AllBatch, EvmReplayProtection, EvmTransactionBuilder},
+};
+
+use super::{api::ArbitrumApi, ArbitrumTrackedData};
+use sp_std::vec;
+
+impl<E> BenchmarkValue for ArbitrumApi<E> {
+	fn benchmark_value() -> Self {
+		EvmTransactionBuilder::new_unsigned(
+			EvmReplayProtection::default(),
+			AllBatch::new(vec![], vec![], vec![]),
+		)
+		.into()
+	}
+}
+
+impl BenchmarkValue for ArbitrumTrackedData {
+	fn benchmark_value() -> Self {
+		Self { base_fee: 100_000_000, priority_fee: 0 }
+	}
+}
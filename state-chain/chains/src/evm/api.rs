@@ -194,3 +194,13 @@ pub trait EthEnvironmentProvider {
 		Self::contract_address(EthereumContract::Vault)
 	}
 }
+
+/// Provides the environment data for EVM chains where only the KeyManager and the Vault contracts
+/// are deployed, ie. every EVM chain other than Ethereum.
+pub trait EvmEnvironmentProvider<C: Chain> {
+	fn token_address(asset: C::ChainAsset) -> Option<eth::Address>;
+	fn key_manager_address() -> eth::Address;
+	fn vault_address() -> eth::Address;
+	fn chain_id() -> EvmChainId;
+	fn next_nonce() -> u64;
+}
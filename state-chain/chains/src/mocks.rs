@@ -138,6 +138,12 @@ impl From<u64> for ForeignChainAddress {
 	}
 }
 
+impl IntoForeignChainAddress<MockEthereum> for u64 {
+	fn into_foreign_chain_address(self) -> ForeignChainAddress {
+		self.into()
+	}
+}
+
 impl From<&DepositChannel<MockEthereum>> for MockEthereumChannelId {
 	fn from(channel: &DepositChannel<MockEthereum>) -> Self {
 		channel.channel_id as u128
@@ -7,7 +7,8 @@ use cf_chains::{
 	eth::Address as EthereumAddress,
 };
 use cf_primitives::{
-	AccountRole, Asset, AssetAmount, ForeignChain, NetworkEnvironment, SemVer, SwapOutput,
+	AccountRole, Asset, AssetAmount, CeremonyFailureEvidence, CeremonyId, ForeignChain,
+	NetworkEnvironment, SemVer, SwapOutput,
 };
 use cf_utilities::rpc::NumberOrHex;
 use core::ops::Range;
@@ -21,7 +22,7 @@ use pallet_cf_governance::GovCallHash;
 use pallet_cf_pools::{AssetsMap, PoolInfo, PoolLiquidity, PoolOrders, UnidirectionalPoolDepth};
 use sc_client_api::{BlockchainEvents, HeaderBackend};
 use serde::{Deserialize, Serialize};
-use sp_api::BlockT;
+use sp_api::{ApiExt, BlockT};
 use sp_runtime::DispatchError;
 use state_chain_runtime::{
 	chainflip::Offence,
@@ -447,6 +448,13 @@ pub trait CustomApi {
 	fn cf_current_compatibility_version(&self) -> RpcResult<SemVer>;
 	#[method(name = "min_swap_amount")]
 	fn cf_min_swap_amount(&self, asset: RpcAsset) -> RpcResult<AssetAmount>;
+	#[method(name = "failure_evidence")]
+	fn cf_failure_evidence(
+		&self,
+		chain: ForeignChain,
+		ceremony_id: CeremonyId,
+		at: Option<state_chain_runtime::Hash>,
+	) -> RpcResult<Vec<(state_chain_runtime::AccountId, CeremonyFailureEvidence)>>;
 	#[subscription(name = "subscribe_pool_price", item = Price)]
 	fn cf_subscribe_pool_price(&self, from_asset: RpcAsset, to_asset: RpcAsset);
 
@@ -1001,6 +1009,28 @@ where
 			.map_err(to_rpc_error)
 	}
 
+	fn cf_failure_evidence(
+		&self,
+		chain: ForeignChain,
+		ceremony_id: CeremonyId,
+		at: Option<state_chain_runtime::Hash>,
+	) -> RpcResult<Vec<(state_chain_runtime::AccountId, CeremonyFailureEvidence)>> {
+		let runtime_api = self.client.runtime_api();
+		let hash = self.unwrap_or_best(at);
+
+		// Failure evidence was only added in version 2 of the runtime api.
+		if !runtime_api
+			.has_api_with::<dyn CustomRuntimeApi<B>, _>(hash, |version| version >= 2)
+			.map_err(to_rpc_error)?
+		{
+			return Err(jsonrpsee::core::Error::from(anyhow::anyhow!(
+				"Failure evidence is not supported by the runtime at this block."
+			)))
+		}
+
+		runtime_api.cf_failure_evidence(hash, chain, ceremony_id).map_err(to_rpc_error)
+	}
+
 	fn cf_subscribe_pool_price(
 		&self,
 		sink: SubscriptionSink,
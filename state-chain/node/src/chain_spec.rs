@@ -8,10 +8,11 @@ use cf_primitives::{
 };
 
 use cf_chains::{
+	arb::ArbitrumTrackedData,
 	btc::{BitcoinFeeInfo, BitcoinTrackedData},
 	dot::{PolkadotTrackedData, RuntimeVersion},
 	eth::EthereumTrackedData,
-	Bitcoin, Ethereum, Polkadot,
+	Arbitrum, Bitcoin, Ethereum, Polkadot,
 };
 use common::FLIPPERINOS_PER_FLIP;
 use frame_benchmarking::sp_std::collections::btree_set::BTreeSet;
@@ -24,14 +25,16 @@ use sp_core::{
 	Pair, Public,
 };
 use state_chain_runtime::{
-	chainflip::Offence, opaque::SessionKeys, AccountId, AccountRolesConfig, AuraConfig,
-	BitcoinChainTrackingConfig, BitcoinIngressEgressConfig, BitcoinThresholdSignerConfig,
-	BitcoinVaultConfig, BlockNumber, EmissionsConfig, EnvironmentConfig,
-	EthereumChainTrackingConfig, EthereumIngressEgressConfig, EthereumThresholdSignerConfig,
-	EthereumVaultConfig, FlipBalance, FlipConfig, FundingConfig, GovernanceConfig, GrandpaConfig,
-	PolkadotChainTrackingConfig, PolkadotIngressEgressConfig, PolkadotThresholdSignerConfig,
-	PolkadotVaultConfig, ReputationConfig, RuntimeGenesisConfig, SessionConfig, SetSizeParameters,
-	Signature, SwappingConfig, SystemConfig, ValidatorConfig, WASM_BINARY,
+	chainflip::Offence, opaque::SessionKeys, AccountId, AccountRolesConfig,
+	ArbitrumChainTrackingConfig, ArbitrumIngressEgressConfig, ArbitrumThresholdSignerConfig,
+	ArbitrumVaultConfig, AuraConfig, BitcoinChainTrackingConfig, BitcoinIngressEgressConfig,
+	BitcoinThresholdSignerConfig, BitcoinVaultConfig, BlockNumber, EmissionsConfig,
+	EnvironmentConfig, EthereumChainTrackingConfig, EthereumIngressEgressConfig,
+	EthereumThresholdSignerConfig, EthereumVaultConfig, FlipBalance, FlipConfig, FundingConfig,
+	GovernanceConfig, GrandpaConfig, PolkadotChainTrackingConfig, PolkadotIngressEgressConfig,
+	PolkadotThresholdSignerConfig, PolkadotVaultConfig, ReputationConfig, RuntimeGenesisConfig,
+	SessionConfig, SetSizeParameters, Signature, SwappingConfig, SystemConfig, ValidatorConfig,
+	WASM_BINARY,
 };
 
 use std::{
@@ -93,6 +96,11 @@ pub struct StateChainEnvironment {
 	ethereum_chain_id: u64,
 	eth_init_agg_key: [u8; 33],
 	ethereum_deployment_block: u64,
+	arb_usdc_address: [u8; 20],
+	arb_key_manager_address: [u8; 20],
+	arb_vault_address: [u8; 20],
+	arb_address_checker_address: [u8; 20],
+	arbitrum_chain_id: u64,
 	genesis_funding_amount: u128,
 	/// Note: Minimum funding should be expressed in Flipperinos.
 	min_funding: u128,
@@ -127,6 +135,11 @@ pub fn get_environment_or_defaults(defaults: StateChainEnvironment) -> StateChai
 	from_env_var!(hex_decode, ETH_INIT_AGG_KEY, eth_init_agg_key);
 	from_env_var!(FromStr::from_str, ETHEREUM_CHAIN_ID, ethereum_chain_id);
 	from_env_var!(FromStr::from_str, ETH_DEPLOYMENT_BLOCK, ethereum_deployment_block);
+	from_env_var!(clean_hex_address, ARB_USDC_ADDRESS, arb_usdc_address);
+	from_env_var!(clean_hex_address, ARB_KEY_MANAGER_ADDRESS, arb_key_manager_address);
+	from_env_var!(clean_hex_address, ARB_VAULT_ADDRESS, arb_vault_address);
+	from_env_var!(clean_hex_address, ARB_ADDRESS_CHECKER_ADDRESS, arb_address_checker_address);
+	from_env_var!(FromStr::from_str, ARBITRUM_CHAIN_ID, arbitrum_chain_id);
 	from_env_var!(FromStr::from_str, GENESIS_FUNDING, genesis_funding_amount);
 	from_env_var!(FromStr::from_str, MIN_FUNDING, min_funding);
 
@@ -158,6 +171,11 @@ pub fn get_environment_or_defaults(defaults: StateChainEnvironment) -> StateChai
 		ethereum_chain_id,
 		eth_init_agg_key,
 		ethereum_deployment_block,
+		arb_usdc_address,
+		arb_key_manager_address,
+		arb_vault_address,
+		arb_address_checker_address,
+		arbitrum_chain_id,
 		genesis_funding_amount,
 		min_funding,
 		dot_genesis_hash,
@@ -215,6 +233,11 @@ pub fn inner_cf_development_config(
 		ethereum_chain_id,
 		eth_init_agg_key,
 		ethereum_deployment_block,
+		arb_usdc_address,
+		arb_key_manager_address,
+		arb_vault_address,
+		arb_address_checker_address,
+		arbitrum_chain_id,
 		genesis_funding_amount,
 		min_funding,
 		dot_genesis_hash,
@@ -243,6 +266,12 @@ pub fn inner_cf_development_config(
 					eth_vault_address: eth_vault_address.into(),
 					eth_address_checker_address: eth_address_checker_address.into(),
 					ethereum_chain_id,
+					arb_usdc_address: arb_usdc_address.into(),
+					arb_key_manager_address: arb_key_manager_address.into(),
+					arb_vault_address: arb_vault_address.into(),
+					arb_address_checker_address: arb_address_checker_address.into(),
+					arbitrum_chain_id,
+					arbitrum_integration_enabled: true,
 					polkadot_genesis_hash: dot_genesis_hash,
 					polkadot_vault_account_id: dot_vault_account_id,
 					network_environment: NetworkEnvironment::Development,
@@ -272,6 +301,7 @@ pub fn inner_cf_development_config(
 				devnet::BITCOIN_EXPIRY_BLOCKS,
 				devnet::ETHEREUM_EXPIRY_BLOCKS,
 				devnet::POLKADOT_EXPIRY_BLOCKS,
+				devnet::ARBITRUM_EXPIRY_BLOCKS,
 				devnet::AUCTION_BID_CUTOFF_PERCENTAGE,
 			)
 		},
@@ -315,6 +345,11 @@ macro_rules! network_spec {
 					ethereum_chain_id,
 					eth_init_agg_key,
 					ethereum_deployment_block,
+					arb_usdc_address,
+					arb_key_manager_address,
+					arb_vault_address,
+					arb_address_checker_address,
+					arbitrum_chain_id,
 					genesis_funding_amount,
 					min_funding,
 					dot_genesis_hash,
@@ -372,6 +407,15 @@ macro_rules! network_spec {
 								eth_vault_address: eth_vault_address.into(),
 								eth_address_checker_address: eth_address_checker_address.into(),
 								ethereum_chain_id,
+								arb_usdc_address: arb_usdc_address.into(),
+								arb_key_manager_address: arb_key_manager_address.into(),
+								arb_vault_address: arb_vault_address.into(),
+								arb_address_checker_address: arb_address_checker_address.into(),
+								arbitrum_chain_id,
+								arbitrum_integration_enabled: matches!(
+									NETWORK_ENVIRONMENT,
+									NetworkEnvironment::Development
+								),
 								polkadot_genesis_hash: dot_genesis_hash,
 								polkadot_vault_account_id: dot_vault_account_id.clone(),
 								network_environment: NETWORK_ENVIRONMENT,
@@ -400,6 +444,7 @@ macro_rules! network_spec {
 							BITCOIN_EXPIRY_BLOCKS,
 							ETHEREUM_EXPIRY_BLOCKS,
 							POLKADOT_EXPIRY_BLOCKS,
+							ARBITRUM_EXPIRY_BLOCKS,
 							AUCTION_BID_CUTOFF_PERCENTAGE,
 						)
 					},
@@ -460,6 +505,7 @@ fn testnet_genesis(
 	bitcoin_deposit_channel_lifetime: u32,
 	ethereum_deposit_channel_lifetime: u32,
 	polkadot_deposit_channel_lifetime: u32,
+	arbitrum_deposit_channel_lifetime: u32,
 	auction_bid_cutoff_percentage: Percent,
 ) -> RuntimeGenesisConfig {
 	// Sanity Checks
@@ -620,6 +666,12 @@ fn testnet_genesis(
 			keygen_response_timeout: keygen_ceremony_timeout_blocks,
 			amount_to_slash: FLIPPERINOS_PER_FLIP,
 		},
+		arbitrum_vault: ArbitrumVaultConfig {
+			vault_key: None,
+			deployment_block: 0,
+			keygen_response_timeout: keygen_ceremony_timeout_blocks,
+			amount_to_slash: FLIPPERINOS_PER_FLIP,
+		},
 		ethereum_threshold_signer: EthereumThresholdSignerConfig {
 			threshold_signature_response_timeout: threshold_signature_ceremony_timeout_blocks,
 			_instance: PhantomData,
@@ -633,6 +685,10 @@ fn testnet_genesis(
 			threshold_signature_response_timeout: threshold_signature_ceremony_timeout_blocks,
 			_instance: PhantomData,
 		},
+		arbitrum_threshold_signer: ArbitrumThresholdSignerConfig {
+			threshold_signature_response_timeout: threshold_signature_ceremony_timeout_blocks,
+			_instance: PhantomData,
+		},
 		emissions: EmissionsConfig {
 			current_authority_emission_inflation: current_authority_emission_inflation_perbill,
 			backup_node_emission_inflation: backup_node_emission_inflation_perbill,
@@ -664,6 +720,15 @@ fn testnet_genesis(
 				tracked_data: BitcoinTrackedData { btc_fee_info: BitcoinFeeInfo::new(1000) },
 			},
 		},
+		arbitrum_chain_tracking: ArbitrumChainTrackingConfig {
+			init_chain_state: ChainState::<Arbitrum> {
+				block_height: 0,
+				tracked_data: ArbitrumTrackedData {
+					base_fee: 100000u32.into(),
+					priority_fee: 0u32.into(),
+				},
+			},
+		},
 		transaction_payment: Default::default(),
 		liquidity_pools: Default::default(),
 		swapping: SwappingConfig { minimum_swap_amounts, _phantom: PhantomData },
@@ -677,6 +742,9 @@ fn testnet_genesis(
 		polkadot_ingress_egress: PolkadotIngressEgressConfig {
 			deposit_channel_lifetime: polkadot_deposit_channel_lifetime,
 		},
+		arbitrum_ingress_egress: ArbitrumIngressEgressConfig {
+			deposit_channel_lifetime: arbitrum_deposit_channel_lifetime.into(),
+		},
 	}
 }
 
@@ -1,6 +1,8 @@
 pub use super::common::*;
 use super::StateChainEnvironment;
-use cf_chains::{dot::RuntimeVersion, eth::CHAIN_ID_MAINNET};
+use cf_chains::{
+	arb::CHAIN_ID_MAINNET as ARB_CHAIN_ID_MAINNET, dot::RuntimeVersion, eth::CHAIN_ID_MAINNET,
+};
 use cf_primitives::{
 	AccountId, AccountRole, Asset, AssetAmount, BlockNumber, FlipBalance, NetworkEnvironment,
 };
@@ -19,6 +21,7 @@ pub const PROTOCOL_ID: &str = "flip-berghain";
 pub const BITCOIN_EXPIRY_BLOCKS: u32 = 24 * 60 / 10;
 pub const ETHEREUM_EXPIRY_BLOCKS: u32 = 24 * 3600 / 14;
 pub const POLKADOT_EXPIRY_BLOCKS: u32 = 24 * 3600 / 6;
+pub const ARBITRUM_EXPIRY_BLOCKS: u32 = 24 * 3600 * 4;
 
 pub const ENV: StateChainEnvironment = StateChainEnvironment {
 	flip_token_address: hex_literal::hex!("826180541412D574cf1336d22c0C0a287822678A"),
@@ -32,6 +35,13 @@ pub const ENV: StateChainEnvironment = StateChainEnvironment {
 		"022a1d7efa522ce746bc40a04016178ce38154be1f0537c6957bdeed17057bb955"
 	),
 	ethereum_deployment_block: 18562942,
+	// The Arbitrum contracts are not deployed yet. The vault is activated by governance once they
+	// are.
+	arb_usdc_address: hex_literal::hex!("af88d065e77c8cC2239327C5EDb3A432268e5831"),
+	arb_key_manager_address: hex_literal::hex!("0000000000000000000000000000000000000000"),
+	arb_vault_address: hex_literal::hex!("0000000000000000000000000000000000000000"),
+	arb_address_checker_address: hex_literal::hex!("0000000000000000000000000000000000000000"),
+	arbitrum_chain_id: ARB_CHAIN_ID_MAINNET,
 	genesis_funding_amount: GENESIS_AUTHORITY_FUNDING,
 	min_funding: MIN_FUNDING,
 	dot_genesis_hash: H256(hex_literal::hex!(
@@ -78,6 +88,8 @@ pub const MINIMUM_SWAP_AMOUNTS: &[(Asset, AssetAmount)] = &[
 	(Asset::Usdc, 0u128),
 	(Asset::Dot, 0u128),
 	(Asset::Btc, 0u128),
+	(Asset::ArbEth, 0u128),
+	(Asset::ArbUsdc, 0u128),
 ];
 
 pub const MIN_FUNDING: FlipBalance = 6 * FLIPPERINOS_PER_FLIP;
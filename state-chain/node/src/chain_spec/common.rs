@@ -69,6 +69,8 @@ pub const MINIMUM_SWAP_AMOUNTS: &[(Asset, AssetAmount)] = &[
 	(Asset::Usdc, 1_000_000u128),          // USDC = 6 d.p
 	(Asset::Dot, 2_000_000_000u128),       // 1 USD worth of DOT = 0.2 * 10 d.p
 	(Asset::Btc, 390_000u128),             // 1 USD worth of BTC = 0.000039 * 10 d.p
+	(Asset::ArbEth, 580_000_000_000_000u128), // 1usd worth of Eth = 0.00058 * 18 d.p
+	(Asset::ArbUsdc, 1_000_000u128),       // USDC = 6 d.p
 ];
 
 /// Daily slashing rate 0.1% (of the bond) for offline authority
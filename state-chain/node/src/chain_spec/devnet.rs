@@ -8,6 +8,8 @@ pub use super::common::*;
 pub const BITCOIN_EXPIRY_BLOCKS: u32 = 10 * 60 / 5;
 pub const ETHEREUM_EXPIRY_BLOCKS: u32 = 10 * 60 / 14;
 pub const POLKADOT_EXPIRY_BLOCKS: u32 = 10 * 60 / 6;
+// Arbitrum produces a block roughly every 250ms.
+pub const ARBITRUM_EXPIRY_BLOCKS: u32 = 10 * 60 * 4;
 
 pub const MIN_AUTHORITIES: AuthorityCount = 1;
 pub const AUCTION_PARAMETERS: SetSizeParameters = SetSizeParameters {
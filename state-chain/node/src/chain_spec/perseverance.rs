@@ -1,9 +1,14 @@
 pub use super::{
 	common::*,
-	testnet::{BITCOIN_EXPIRY_BLOCKS, ETHEREUM_EXPIRY_BLOCKS, POLKADOT_EXPIRY_BLOCKS},
+	testnet::{
+		ARBITRUM_EXPIRY_BLOCKS, BITCOIN_EXPIRY_BLOCKS, ETHEREUM_EXPIRY_BLOCKS,
+		POLKADOT_EXPIRY_BLOCKS,
+	},
 };
 use super::{parse_account, StateChainEnvironment};
-use cf_chains::{dot::RuntimeVersion, eth::CHAIN_ID_GOERLI};
+use cf_chains::{
+	arb::CHAIN_ID_SEPOLIA as ARB_CHAIN_ID_SEPOLIA, dot::RuntimeVersion, eth::CHAIN_ID_GOERLI,
+};
 use cf_primitives::{AccountId, AccountRole, BlockNumber, FlipBalance, NetworkEnvironment};
 use sc_service::ChainType;
 use sp_core::H256;
@@ -27,6 +32,13 @@ pub const ENV: StateChainEnvironment = StateChainEnvironment {
 		"02661d4b647d4b49660976ad402f4890cb8f2f4d872dfa5e1c5f33b1da53f4a637"
 	),
 	ethereum_deployment_block: 9595582u64,
+	// The Arbitrum contracts are not deployed yet. The vault is activated by governance once they
+	// are.
+	arb_usdc_address: hex_literal::hex!("75faf114eafb1BDbe2F0316DF893fd58CE46AA4d"),
+	arb_key_manager_address: hex_literal::hex!("0000000000000000000000000000000000000000"),
+	arb_vault_address: hex_literal::hex!("0000000000000000000000000000000000000000"),
+	arb_address_checker_address: hex_literal::hex!("0000000000000000000000000000000000000000"),
+	arbitrum_chain_id: ARB_CHAIN_ID_SEPOLIA,
 	genesis_funding_amount: GENESIS_FUNDING_AMOUNT,
 	min_funding: MIN_FUNDING,
 	dot_genesis_hash: H256(hex_literal::hex!(
@@ -1,6 +1,8 @@
 pub use super::common::*;
 use super::{get_account_id_from_seed, StateChainEnvironment};
-use cf_chains::{dot::RuntimeVersion, eth::CHAIN_ID_GOERLI};
+use cf_chains::{
+	arb::CHAIN_ID_SEPOLIA as ARB_CHAIN_ID_SEPOLIA, dot::RuntimeVersion, eth::CHAIN_ID_GOERLI,
+};
 use cf_primitives::{AccountId, AccountRole, BlockNumber, FlipBalance, NetworkEnvironment};
 use sc_service::ChainType;
 use sp_core::{sr25519, H256};
@@ -16,6 +18,8 @@ pub const PROTOCOL_ID: &str = "flip-test";
 pub const BITCOIN_EXPIRY_BLOCKS: u32 = 2 * 60 * 60 / (10 * 60);
 pub const ETHEREUM_EXPIRY_BLOCKS: u32 = 2 * 60 * 60 / 14;
 pub const POLKADOT_EXPIRY_BLOCKS: u32 = 2 * 60 * 60 / 6;
+// Arbitrum produces a block roughly every 250ms.
+pub const ARBITRUM_EXPIRY_BLOCKS: u32 = 2 * 60 * 60 * 4;
 
 pub const ENV: StateChainEnvironment = StateChainEnvironment {
 	flip_token_address: hex_literal::hex!("Cf7Ed3AccA5a467e9e704C703E8D87F634fB0Fc9"),
@@ -29,6 +33,12 @@ pub const ENV: StateChainEnvironment = StateChainEnvironment {
 		"02e61afd677cdfbec838c6f309deff0b2c6056f8a27f2c783b68bba6b30f667be6"
 	),
 	ethereum_deployment_block: 0u64,
+	// The localnet Arbitrum contracts are deployed after the Ethereum ones, by the same deployer.
+	arb_usdc_address: hex_literal::hex!("5FC8d32690cc91D4c39d9d3abcBD16989F875707"),
+	arb_key_manager_address: hex_literal::hex!("a513E6E4b8f2a923D98304ec87F64353C4D5C853"),
+	arb_vault_address: hex_literal::hex!("0165878A594ca255338adfa4d48449f69242Eb8F"),
+	arb_address_checker_address: hex_literal::hex!("e7f1725E7734CE288F8367e1Bb143E90bb3F0512"),
+	arbitrum_chain_id: ARB_CHAIN_ID_SEPOLIA,
 	genesis_funding_amount: GENESIS_FUNDING_AMOUNT,
 	min_funding: MIN_FUNDING,
 	dot_genesis_hash: H256(hex_literal::hex!(
@@ -11,8 +11,12 @@ use cf_chains::{
 	},
 	dot::{Polkadot, PolkadotAccountId, PolkadotHash, PolkadotIndex},
 	eth::Address as EthereumAddress,
+	Arbitrum,
+};
+use cf_primitives::{
+	chains::assets::{arb::Asset as ArbAsset, eth::Asset as EthAsset},
+	NetworkEnvironment, SemVer,
 };
-use cf_primitives::{chains::assets::eth::Asset as EthAsset, NetworkEnvironment, SemVer};
 use cf_traits::{CompatibleCfeVersions, GetBitcoinFeeInfo, SafeMode};
 use frame_support::{
 	pallet_prelude::*,
@@ -67,6 +71,8 @@ pub mod pallet {
 		type PolkadotVaultKeyWitnessedHandler: VaultKeyWitnessedHandler<Polkadot>;
 		/// On new key witnessed handler for Bitcoin
 		type BitcoinVaultKeyWitnessedHandler: VaultKeyWitnessedHandler<Bitcoin>;
+		/// On new key witnessed handler for Arbitrum
+		type ArbitrumVaultKeyWitnessedHandler: VaultKeyWitnessedHandler<Arbitrum>;
 
 		/// The runtime's safe mode is stored in this pallet.
 		type RuntimeSafeMode: cf_traits::SafeMode + Member + Parameter + Default;
@@ -129,6 +135,43 @@ pub mod pallet {
 	#[pallet::storage]
 	pub type EthereumSignatureNonce<T> = StorageValue<_, SignatureNonce, ValueQuery>;
 
+	// ARBITRUM CHAIN RELATED ENVIRONMENT ITEMS
+
+	#[pallet::storage]
+	#[pallet::getter(fn supported_arb_assets)]
+	/// Map of supported assets for Arbitrum
+	pub type ArbitrumSupportedAssets<T: Config> =
+		StorageMap<_, Blake2_128Concat, ArbAsset, EthereumAddress>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn arb_key_manager_address)]
+	/// The address of the KeyManager contract on Arbitrum
+	pub type ArbitrumKeyManagerAddress<T> = StorageValue<_, EthereumAddress, ValueQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn arb_vault_address)]
+	/// The address of the Vault contract on Arbitrum
+	pub type ArbitrumVaultAddress<T> = StorageValue<_, EthereumAddress, ValueQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn arb_address_checker_address)]
+	/// The address of the Address Checker contract on Arbitrum
+	pub type ArbitrumAddressCheckerAddress<T> = StorageValue<_, EthereumAddress, ValueQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn arbitrum_chain_id)]
+	/// The Arbitrum chain id
+	pub type ArbitrumChainId<T> = StorageValue<_, cf_chains::evm::api::EvmChainId, ValueQuery>;
+
+	#[pallet::storage]
+	pub type ArbitrumSignatureNonce<T> = StorageValue<_, SignatureNonce, ValueQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn arbitrum_integration_enabled)]
+	/// Whether the Arbitrum vault takes part in vault rotations. Networks that were started before
+	/// Arbitrum was integrated only rotate it once governance enables it.
+	pub type ArbitrumIntegrationEnabled<T> = StorageValue<_, bool, ValueQuery>;
+
 	// POLKADOT CHAIN RELATED ENVIRONMENT ITEMS
 
 	#[pallet::storage]
@@ -179,6 +222,15 @@ pub mod pallet {
 		BitcoinBlockNumberSetForVault { block_number: cf_chains::btc::BlockNumber },
 		/// The Safe Mode settings for the chain has been updated
 		RuntimeSafeModeUpdated { safe_mode: SafeModeUpdate<T> },
+		/// The Arbitrum Vault was initialized with the key that was generated for it
+		ArbitrumVaultInitialized {
+			block_number: <Arbitrum as cf_chains::Chain>::ChainBlockNumber,
+			key_manager_address: EthereumAddress,
+			vault_address: EthereumAddress,
+			address_checker_address: EthereumAddress,
+		},
+		/// The Arbitrum vault takes part in vault rotations from now on
+		ArbitrumIntegrationEnabled,
 	}
 
 	#[pallet::call]
@@ -279,6 +331,74 @@ pub mod pallet {
 
 			Ok(())
 		}
+
+		/// Manually witnesses the deployment of the Arbitrum contracts with the first Arbitrum
+		/// aggregate key, which completes the pending Arbitrum vault rotation. There is no existing
+		/// key that could sign the activation on Arbitrum, so it has to be done by governance. The
+		/// addresses of the deployed contracts are stored, engines pick them up when they restart.
+		///
+		/// ## Events
+		///
+		/// - [ArbitrumVaultInitialized](Event::ArbitrumVaultInitialized)
+		///
+		/// ## Errors
+		///
+		/// - [BadOrigin](frame_support::error::BadOrigin)
+		#[pallet::call_index(4)]
+		// This weight is not strictly correct but since it's a governance call, weight is
+		// irrelevant.
+		#[pallet::weight(Weight::zero())]
+		pub fn witness_initialize_arbitrum_vault(
+			origin: OriginFor<T>,
+			block_number: <Arbitrum as cf_chains::Chain>::ChainBlockNumber,
+			key_manager_address: EthereumAddress,
+			vault_address: EthereumAddress,
+			address_checker_address: EthereumAddress,
+		) -> DispatchResultWithPostInfo {
+			T::EnsureGovernance::ensure_origin(origin)?;
+
+			let dispatch_result =
+				T::ArbitrumVaultKeyWitnessedHandler::on_new_key_activated(block_number)?;
+
+			ArbitrumKeyManagerAddress::<T>::put(key_manager_address);
+			ArbitrumVaultAddress::<T>::put(vault_address);
+			ArbitrumAddressCheckerAddress::<T>::put(address_checker_address);
+
+			Self::deposit_event(Event::<T>::ArbitrumVaultInitialized {
+				block_number,
+				key_manager_address,
+				vault_address,
+				address_checker_address,
+			});
+
+			Ok(dispatch_result)
+		}
+
+		/// Makes the Arbitrum vault take part in vault rotations, starting with the next one. The
+		/// first rotation generates the first Arbitrum aggregate key, which has to be initialized
+		/// with [witness_initialize_arbitrum_vault](Call::witness_initialize_arbitrum_vault) once
+		/// the Arbitrum contracts are deployed with it.
+		///
+		/// ## Events
+		///
+		/// - [ArbitrumIntegrationEnabled](Event::ArbitrumIntegrationEnabled)
+		///
+		/// ## Errors
+		///
+		/// - [BadOrigin](frame_support::error::BadOrigin)
+		#[pallet::call_index(5)]
+		// This weight is not strictly correct but since it's a governance call, weight is
+		// irrelevant.
+		#[pallet::weight(Weight::zero())]
+		pub fn enable_arbitrum_integration(origin: OriginFor<T>) -> DispatchResult {
+			T::EnsureGovernance::ensure_origin(origin)?;
+
+			ArbitrumIntegrationEnabled::<T>::put(true);
+
+			Self::deposit_event(Event::<T>::ArbitrumIntegrationEnabled);
+
+			Ok(())
+		}
 	}
 
 	#[pallet::genesis_config]
@@ -291,6 +411,12 @@ pub mod pallet {
 		pub eth_vault_address: EthereumAddress,
 		pub eth_address_checker_address: EthereumAddress,
 		pub ethereum_chain_id: u64,
+		pub arb_usdc_address: EthereumAddress,
+		pub arb_key_manager_address: EthereumAddress,
+		pub arb_vault_address: EthereumAddress,
+		pub arb_address_checker_address: EthereumAddress,
+		pub arbitrum_chain_id: u64,
+		pub arbitrum_integration_enabled: bool,
 		pub polkadot_genesis_hash: PolkadotHash,
 		pub polkadot_vault_account_id: Option<PolkadotAccountId>,
 		pub network_environment: NetworkEnvironment,
@@ -310,6 +436,13 @@ pub mod pallet {
 			EthereumSupportedAssets::<T>::insert(EthAsset::Flip, self.flip_token_address);
 			EthereumSupportedAssets::<T>::insert(EthAsset::Usdc, self.eth_usdc_address);
 
+			ArbitrumKeyManagerAddress::<T>::set(self.arb_key_manager_address);
+			ArbitrumVaultAddress::<T>::set(self.arb_vault_address);
+			ArbitrumAddressCheckerAddress::<T>::set(self.arb_address_checker_address);
+			ArbitrumChainId::<T>::set(self.arbitrum_chain_id);
+			ArbitrumSupportedAssets::<T>::insert(ArbAsset::ArbUsdc, self.arb_usdc_address);
+			ArbitrumIntegrationEnabled::<T>::set(self.arbitrum_integration_enabled);
+
 			PolkadotGenesisHash::<T>::set(self.polkadot_genesis_hash);
 			PolkadotVaultAccountId::<T>::set(self.polkadot_vault_account_id);
 			PolkadotProxyAccountNonce::<T>::set(0);
@@ -335,6 +468,13 @@ impl<T: Config> Pallet<T> {
 		})
 	}
 
+	pub fn next_arbitrum_signature_nonce() -> SignatureNonce {
+		ArbitrumSignatureNonce::<T>::mutate(|nonce| {
+			*nonce += 1;
+			*nonce
+		})
+	}
+
 	pub fn next_polkadot_proxy_account_nonce(reset_nonce: bool) -> PolkadotIndex {
 		PolkadotProxyAccountNonce::<T>::mutate(|nonce| {
 			let current_nonce = *nonce;
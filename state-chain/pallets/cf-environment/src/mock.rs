@@ -4,7 +4,7 @@ use crate::{self as pallet_cf_environment, Decode, Encode, TypeInfo};
 use cf_chains::{
 	btc::BitcoinFeeInfo,
 	dot::{api::CreatePolkadotVault, PolkadotCrypto},
-	eth, ApiCall, Bitcoin, Chain, ChainCrypto, Polkadot,
+	eth, ApiCall, Arbitrum, Bitcoin, Chain, ChainCrypto, Polkadot,
 };
 use cf_primitives::{
 	BroadcastId, SemVer, ThresholdSignatureRequestId, INPUT_UTXO_SIZE_IN_BYTES,
@@ -124,6 +124,14 @@ impl VaultKeyWitnessedHandler<Polkadot> for MockPolkadotVaultKeyWitnessedHandler
 		unimplemented!()
 	}
 }
+pub struct MockArbitrumVaultKeyWitnessedHandler;
+impl VaultKeyWitnessedHandler<Arbitrum> for MockArbitrumVaultKeyWitnessedHandler {
+	fn on_new_key_activated(
+		_block_number: <Arbitrum as Chain>::ChainBlockNumber,
+	) -> frame_support::pallet_prelude::DispatchResultWithPostInfo {
+		Ok(().into())
+	}
+}
 pub struct MockBitcoinVaultKeyWitnessedHandler;
 impl VaultKeyWitnessedHandler<Bitcoin> for MockBitcoinVaultKeyWitnessedHandler {
 	fn on_new_key_activated(
@@ -159,6 +167,7 @@ impl pallet_cf_environment::Config for Test {
 	type RuntimeEvent = RuntimeEvent;
 	type PolkadotVaultKeyWitnessedHandler = MockPolkadotVaultKeyWitnessedHandler;
 	type BitcoinVaultKeyWitnessedHandler = MockBitcoinVaultKeyWitnessedHandler;
+	type ArbitrumVaultKeyWitnessedHandler = MockArbitrumVaultKeyWitnessedHandler;
 	type BitcoinFeeInfo = MockBitcoinFeeInfo;
 	type RuntimeSafeMode = MockRuntimeSafeMode;
 	type CurrentReleaseVersion = CurrentReleaseVersion;
@@ -122,3 +122,34 @@ fn update_safe_mode() {
 		));
 	});
 }
+
+#[test]
+fn arbitrum_integration_is_enabled_and_initialized_by_governance() {
+	new_test_ext().execute_with(|| {
+		assert!(!Environment::arbitrum_integration_enabled());
+		assert_ok!(Environment::enable_arbitrum_integration(OriginTrait::root()));
+		assert!(Environment::arbitrum_integration_enabled());
+		System::assert_last_event(RuntimeEvent::Environment(
+			crate::Event::<Test>::ArbitrumIntegrationEnabled,
+		));
+
+		assert_ok!(Environment::witness_initialize_arbitrum_vault(
+			OriginTrait::root(),
+			100,
+			KEY_MANAGER_ADDRESS,
+			VAULT_ADDRESS,
+			ADDRESS_CHECKER,
+		));
+		assert_eq!(Environment::arb_key_manager_address(), KEY_MANAGER_ADDRESS);
+		assert_eq!(Environment::arb_vault_address(), VAULT_ADDRESS);
+		assert_eq!(Environment::arb_address_checker_address(), ADDRESS_CHECKER);
+		System::assert_last_event(RuntimeEvent::Environment(
+			crate::Event::<Test>::ArbitrumVaultInitialized {
+				block_number: 100,
+				key_manager_address: KEY_MANAGER_ADDRESS,
+				vault_address: VAULT_ADDRESS,
+				address_checker_address: ADDRESS_CHECKER,
+			},
+		));
+	});
+}
@@ -16,7 +16,9 @@ use frame_support::{sp_runtime::SaturatedConversion, transactional};
 pub use weights::WeightInfo;
 
 use cf_chains::{
-	address::{AddressConverter, AddressDerivationApi, AddressDerivationError},
+	address::{
+		AddressConverter, AddressDerivationApi, AddressDerivationError, IntoForeignChainAddress,
+	},
 	AllBatch, AllBatchError, CcmCfParameters, CcmChannelMetadata, CcmDepositMetadata, CcmMessage,
 	Chain, ChannelLifecycleHooks, DepositChannel, ExecutexSwapAndCall, FetchAssetParams,
 	ForeignChainAddress, SwapOrigin, TransferAssetParams,
@@ -835,7 +837,7 @@ impl<T: Config<I>, I: 'static> Pallet<T, I> {
 				broker_commission_bps,
 				..
 			} => T::SwapDepositHandler::schedule_swap_from_channel(
-				deposit_address.clone().into(),
+				deposit_address.clone().into_foreign_chain_address(),
 				block_height.into(),
 				asset.into(),
 				destination_asset,
@@ -862,7 +864,7 @@ impl<T: Config<I>, I: 'static> Pallet<T, I> {
 				},
 				SwapOrigin::DepositChannel {
 					deposit_address: T::AddressConverter::to_encoded_address(
-						deposit_address.clone().into(),
+						deposit_address.clone().into_foreign_chain_address(),
 					),
 					channel_id,
 					deposit_block_height: block_height.into(),
@@ -1014,7 +1016,7 @@ impl<T: Config<I>, I: 'static> DepositApi<T::TargetChain> for Pallet<T, I> {
 		let (channel_id, deposit_address, expiry_block) =
 			Self::open_channel(source_asset, ChannelAction::LiquidityProvision { lp_account })?;
 
-		Ok((channel_id, deposit_address.into(), expiry_block))
+		Ok((channel_id, deposit_address.into_foreign_chain_address(), expiry_block))
 	}
 
 	// This should only be callable by the broker.
@@ -1046,6 +1048,6 @@ impl<T: Config<I>, I: 'static> DepositApi<T::TargetChain> for Pallet<T, I> {
 			},
 		)?;
 
-		Ok((channel_id, deposit_address.into(), expiry_height))
+		Ok((channel_id, deposit_address.into_foreign_chain_address(), expiry_height))
 	}
 }
@@ -4,18 +4,26 @@
 use super::*;
 
 use cf_chains::{benchmarking_value::BenchmarkValue, ChainCrypto};
+use cf_primitives::{
+	broadcast_signing_payload, BlameReason, BroadcastSignature, ConflictingBroadcast,
+	SignedValueHash,
+};
 use cf_traits::{AccountRoleRegistry, Chainflip, ThresholdSigner};
 use frame_benchmarking::{account, benchmarks_instance_pallet, whitelist_account};
 use frame_support::{
 	assert_ok,
 	dispatch::UnfilteredDispatchable,
+	sp_io,
+	sp_runtime::KeyTypeId,
 	traits::{IsType, OnInitialize, OnNewAccount},
 };
 use frame_system::RawOrigin;
-use pallet_cf_validator::CurrentAuthorities;
+use pallet_cf_validator::{AccountPeerMapping, CurrentAuthorities};
 
 const SEED: u32 = 0;
 
+const PEER_ID_KEY: KeyTypeId = KeyTypeId(*b"peer");
+
 type SignatureFor<T, I> = <<T as Config<I>>::TargetChainCrypto as ChainCrypto>::ThresholdSignature;
 
 fn add_authorities<T, I>(authorities: I)
@@ -37,6 +45,13 @@ where
 	}
 }
 
+/// Requests may be held back until the next block so that they can be signed in a batch.
+fn start_requested_ceremonies<T: Config<I>, I: 'static>() {
+	let next_block = frame_system::Pallet::<T>::block_number() + One::one();
+	frame_system::Pallet::<T>::set_block_number(next_block);
+	Pallet::<T, I>::on_initialize(next_block);
+}
+
 benchmarks_instance_pallet! {
 	where_clause {
 		where
@@ -52,6 +67,7 @@ benchmarks_instance_pallet! {
 		add_authorities::<T, _>(all_accounts);
 
 		let request_id = <Pallet::<T, I> as ThresholdSigner<_>>::request_signature(PayloadFor::<T, I>::benchmark_value());
+		start_requested_ceremonies::<T, I>();
 		let ceremony_id = 1;
 		let signature = SignatureFor::<T, I>::benchmark_value();
 	} : _(RawOrigin::None, ceremony_id, signature)
@@ -67,6 +83,7 @@ benchmarks_instance_pallet! {
 		add_authorities::<T, _>(all_accounts);
 
 		let request_id = <Pallet::<T, I> as ThresholdSigner<_>>::request_signature(PayloadFor::<T, I>::benchmark_value());
+		start_requested_ceremonies::<T, I>();
 		let ceremony_id = 1;
 
 		let mut threshold_set = PendingCeremonies::<T, I>::get(ceremony_id).unwrap().remaining_respondents.into_iter();
@@ -77,6 +94,68 @@ benchmarks_instance_pallet! {
 		assert_ok!(<T as Chainflip>::AccountRoleRegistry::register_as_validator(&account));
 		let offenders = BTreeSet::from_iter(threshold_set.take(a as usize));
 	} : _(RawOrigin::Signed(reporter.into()), ceremony_id, offenders)
+	report_failure_evidence {
+		let e in 2 .. 2_000;
+		let all_accounts = (0..150).map(|i| account::<<T as Chainflip>::ValidatorId>("signers", i, SEED));
+
+		add_authorities::<T, _>(all_accounts);
+
+		<Pallet::<T, I> as ThresholdSigner<_>>::request_signature(PayloadFor::<T, I>::benchmark_value());
+		start_requested_ceremonies::<T, I>();
+		let ceremony_id = 1;
+
+		let reporter = PendingCeremonies::<T, I>::get(ceremony_id).unwrap().candidates.into_iter().next().unwrap();
+		// The reporter is also the first party, and the broadcaster of the conflicting values,
+		// so its peer key is registered to check its signatures.
+		let peer_key = sp_io::crypto::ed25519_generate(PEER_ID_KEY, None);
+		AccountPeerMapping::<T>::insert(reporter.clone().into_ref(), (peer_key, 0, 0));
+		// The evidence has `e` distinct signed values, each of which has to be verified.
+		let received = (0..e)
+			.map(|i| {
+				let hash = sp_io::hashing::blake2_256(&i.to_le_bytes());
+				let signature = sp_io::crypto::ed25519_sign(
+					PEER_ID_KEY,
+					&peer_key,
+					&broadcast_signing_payload(T::TargetChain::get(), ceremony_id, 2, &hash),
+				)
+				.unwrap();
+				(Some(SignedValueHash { hash, signature: BroadcastSignature(signature.0) }), vec![])
+			})
+			.collect();
+		let evidence = CeremonyFailureEvidence {
+			reason: BlameReason::InconsistentBroadcast { stage: 2 },
+			conflicting_broadcasts: vec![ConflictingBroadcast { broadcaster: 1, received }],
+		};
+	} : _(RawOrigin::Signed(reporter.clone().into()), ceremony_id, evidence)
+	verify {
+		assert!(FailureEvidence::<T, I>::contains_key(ceremony_id, reporter));
+	}
+	prune_failure_evidence {
+		let c in 1 .. 50;
+		let r in 0 .. 7_500;
+		let expiry_block: BlockNumberFor<T> = 5u32.into();
+		let evidence = CeremonyFailureEvidence {
+			reason: BlameReason::InvalidSignatureShare,
+			conflicting_broadcasts: vec![],
+		};
+		for ceremony_id in 0..c as CeremonyId {
+			FailureEvidenceEntryCount::<T, I>::insert(ceremony_id, 0);
+			FailureEvidenceExpiries::<T, I>::append(expiry_block, ceremony_id);
+		}
+		// The reports are spread across the ceremonies.
+		for i in 0..r {
+			FailureEvidence::<T, I>::insert(
+				(i % c) as CeremonyId,
+				account::<<T as Chainflip>::ValidatorId>("reporter", i, SEED),
+				evidence.clone(),
+			);
+		}
+	}: {
+		Pallet::<T, I>::prune_failure_evidence(expiry_block)
+	}
+	verify {
+		assert_eq!(FailureEvidence::<T, I>::iter().count(), 0);
+	}
 	set_threshold_signature_timeout {
 		let old_timeout: BlockNumberFor<T> = 5u32.into();
 		ThresholdSignatureResponseTimeout::<T, I>::put(old_timeout);
@@ -99,7 +178,7 @@ benchmarks_instance_pallet! {
 
 		// These attempts will fail because there are no authorities to do the signing.
 		for _ in 0..r {
-			Pallet::<T, I>::new_ceremony_attempt(RequestInstruction::new(1, 1, PayloadFor::<T, I>::benchmark_value(), RequestType::CurrentKey));
+			Pallet::<T, I>::new_ceremony_attempt(vec![RequestInstruction::new(1, 1, PayloadFor::<T, I>::benchmark_value(), RequestType::CurrentKey)]);
 		}
 
 		assert_eq!(
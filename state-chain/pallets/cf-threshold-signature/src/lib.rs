@@ -18,11 +18,14 @@ use scale_info::TypeInfo;
 
 use cf_chains::ChainCrypto;
 use cf_primitives::{
-	AuthorityCount, CeremonyId, EpochIndex, ThresholdSignatureRequestId as RequestId,
+	broadcast_signing_payload, AuthorityCount, BlameReason, CeremonyFailureEvidence, CeremonyId,
+	ConflictingBroadcast, EpochIndex, ForeignChain, SignedValueHash,
+	ThresholdSignatureRequestId as RequestId,
 };
 use cf_traits::{
 	offence_reporting::OffenceReporter, AsyncResult, CeremonyIdProvider, Chainflip, EpochInfo,
-	EpochKey, KeyProvider, ThresholdSignerNomination,
+	EpochKey, KeyCeremonyParticipants, KeyProvider, PeerSignatureVerifier,
+	ThresholdSignerNomination,
 };
 
 use cf_runtime_utilities::log_or_panic;
@@ -30,7 +33,7 @@ use frame_support::{
 	dispatch::UnfilteredDispatchable,
 	ensure,
 	sp_runtime::{
-		traits::{BlockNumberProvider, Saturating},
+		traits::{BlockNumberProvider, One, Saturating},
 		RuntimeDebug,
 	},
 	traits::{DefensiveOption, EnsureOrigin, Get, StorageVersion},
@@ -76,7 +79,7 @@ pub enum ThresholdCeremonyType {
 	KeygenVerification,
 }
 
-pub const PALLET_VERSION: StorageVersion = StorageVersion::new(3);
+pub const PALLET_VERSION: StorageVersion = StorageVersion::new(4);
 
 const THRESHOLD_SIGNATURE_RESPONSE_TIMEOUT_DEFAULT: u32 = 10;
 
@@ -97,7 +100,9 @@ pub mod pallet {
 	#[derive(Clone, RuntimeDebug, PartialEq, Eq, Encode, Decode, TypeInfo)]
 	#[scale_info(skip_type_params(T, I))]
 	pub struct CeremonyContext<T: Config<I>, I: 'static> {
-		pub request_context: RequestContext<T, I>,
+		/// The requests signed by this ceremony, in the same order as their payloads. Standard
+		/// requests made in the same block may be signed together in a single ceremony.
+		pub request_contexts: Vec<RequestContext<T, I>>,
 		/// The respondents that have yet to reply.
 		pub remaining_respondents: BTreeSet<T::ValidatorId>,
 		/// The number of blame votes (accusations) each authority has received.
@@ -153,6 +158,10 @@ pub mod pallet {
 		Result<SignatureFor<T, I>, Vec<<T as Chainflip>::ValidatorId>>;
 
 	impl<T: Config<I>, I: 'static> CeremonyContext<T, I> {
+		pub fn request_ids(&self) -> Vec<RequestId> {
+			self.request_contexts.iter().map(|context| context.request_id).collect()
+		}
+
 		/// Based on the reported blame_counts, decide which nodes should be reported for failure.
 		///
 		/// We assume that at least 2/3 of participants need to blame a node for it to be reliable.
@@ -243,6 +252,37 @@ pub mod pallet {
 		#[pallet::constant]
 		type CeremonyRetryDelay: Get<BlockNumberFor<Self>>;
 
+		/// The maximum number of requests that are signed in a single ceremony. If this is greater
+		/// than one, new requests are batched with the other requests made in the same block, and
+		/// their ceremony is started at the beginning of the next block.
+		#[pallet::constant]
+		type MaxSigningBatchSize: Get<u32>;
+
+		/// The number of blocks for which evidence supporting failure reports is kept.
+		#[pallet::constant]
+		type FailureEvidenceRetentionPeriod: Get<BlockNumberFor<Self>>;
+
+		/// The maximum number of entries (see [CeremonyFailureEvidence::entry_count]) in a single
+		/// submission of failure evidence.
+		#[pallet::constant]
+		type MaxFailureEvidenceEntries: Get<u32>;
+
+		/// The maximum number of entries in all the failure evidence submitted for a ceremony.
+		#[pallet::constant]
+		type MaxFailureEvidenceEntriesPerCeremony: Get<u32>;
+
+		/// Provides the participants of the key ceremonies (which aren't tracked by this pallet),
+		/// so that they can submit failure evidence.
+		type KeyCeremonyParticipants: KeyCeremonyParticipants<ValidatorId = Self::ValidatorId>;
+
+		/// The chain whose ceremonies this pallet handles. The participants' signatures over their
+		/// broadcasts are bound to it.
+		#[pallet::constant]
+		type TargetChain: Get<ForeignChain>;
+
+		/// Verifies the participants' signatures over the broadcasts in failure evidence.
+		type PeerSignatureVerifier: PeerSignatureVerifier<ValidatorId = Self::ValidatorId>;
+
 		/// Pallet weights
 		type Weights: WeightInfo;
 	}
@@ -288,6 +328,8 @@ pub mod pallet {
 	pub type CeremonyRetryQueues<T: Config<I>, I: 'static = ()> =
 		StorageMap<_, Twox64Concat, BlockNumberFor<T>, Vec<CeremonyId>, ValueQuery>;
 
+	/// A map containing lists of requests that should be (re)started at the block stored in the
+	/// key. Requests that are started in the same block are batched.
 	#[pallet::storage]
 	#[pallet::getter(fn request_retry_queues)]
 	pub type RequestRetryQueue<T: Config<I>, I: 'static = ()> =
@@ -299,6 +341,28 @@ pub mod pallet {
 	pub type ThresholdSignatureResponseTimeout<T: Config<I>, I: 'static = ()> =
 		StorageValue<_, BlockNumberFor<T>, ValueQuery>;
 
+	/// Evidence supporting the failure reports of ceremony participants, by ceremony id and
+	/// reporter.
+	#[pallet::storage]
+	pub type FailureEvidence<T: Config<I>, I: 'static = ()> = StorageDoubleMap<
+		_,
+		Twox64Concat,
+		CeremonyId,
+		Blake2_128Concat,
+		T::ValidatorId,
+		CeremonyFailureEvidence,
+	>;
+
+	/// The total number of entries in the failure evidence submitted for a ceremony.
+	#[pallet::storage]
+	pub type FailureEvidenceEntryCount<T: Config<I>, I: 'static = ()> =
+		StorageMap<_, Twox64Concat, CeremonyId, u32, ValueQuery>;
+
+	/// The ceremonies whose failure evidence expires at a given block.
+	#[pallet::storage]
+	pub type FailureEvidenceExpiries<T: Config<I>, I: 'static = ()> =
+		StorageMap<_, Twox64Concat, BlockNumberFor<T>, Vec<CeremonyId>, ValueQuery>;
+
 	#[pallet::genesis_config]
 	pub struct GenesisConfig<T: Config<I>, I: 'static = ()> {
 		pub threshold_signature_response_timeout: BlockNumberFor<T>,
@@ -335,6 +399,16 @@ pub mod pallet {
 			signatories: BTreeSet<T::ValidatorId>,
 			payload: PayloadFor<T, I>,
 		},
+		/// A ceremony was started to sign the payloads of multiple requests. The signatures are
+		/// expected in the same order as the payloads.
+		ThresholdSignatureBatchRequest {
+			request_ids: Vec<RequestId>,
+			ceremony_id: CeremonyId,
+			epoch: EpochIndex,
+			key: <T::TargetChainCrypto as ChainCrypto>::AggKey,
+			signatories: BTreeSet<T::ValidatorId>,
+			payloads: Vec<PayloadFor<T, I>>,
+		},
 		ThresholdSignatureFailed {
 			request_id: RequestId,
 			ceremony_id: CeremonyId,
@@ -355,6 +429,7 @@ pub mod pallet {
 			request_id: RequestId,
 			ceremony_id: CeremonyId,
 		},
+		/// For a batched ceremony, this is emitted for each request in the batch.
 		FailureReportProcessed {
 			request_id: RequestId,
 			ceremony_id: CeremonyId,
@@ -374,6 +449,11 @@ pub mod pallet {
 		ThresholdSignatureResponseTimeoutUpdated {
 			new_timeout: BlockNumberFor<T>,
 		},
+		/// A ceremony participant submitted evidence supporting their failure report.
+		FailureEvidenceReported {
+			ceremony_id: CeremonyId,
+			reporter_id: T::ValidatorId,
+		},
 	}
 
 	#[pallet::error]
@@ -387,6 +467,15 @@ pub mod pallet {
 		InvalidRespondent,
 		/// The request Id is stale or not yet valid.
 		InvalidRequestId,
+		/// The failure evidence has more entries than allowed.
+		FailureEvidenceTooLarge,
+		/// The reporter has already submitted failure evidence for this ceremony.
+		DuplicateFailureEvidence,
+		/// The evidence submitted for this ceremony has reached the maximum number of entries.
+		FailureEvidenceLimitReached,
+		/// The failure evidence doesn't prove any inconsistent broadcast, or includes conflicting
+		/// broadcasts that aren't relevant to the reported reason.
+		InvalidFailureEvidence,
 	}
 
 	#[pallet::hooks]
@@ -394,6 +483,8 @@ pub mod pallet {
 		fn on_initialize(current_block: BlockNumberFor<T>) -> frame_support::weights::Weight {
 			let mut num_retries = 0;
 			let mut num_offenders = 0;
+			let (num_expired_ceremonies, num_expired_reports) =
+				Self::prune_failure_evidence(current_block);
 
 			// Process pending retries.
 			for ceremony_id in CeremonyRetryQueues::<T, I>::take(current_block) {
@@ -403,59 +494,73 @@ pub mod pallet {
 					num_offenders += offenders.len();
 					num_retries += 1;
 
+					let request_ids = failed_ceremony_context.request_ids();
 					let CeremonyContext {
-						request_context: RequestContext { request_id, attempt_count, payload },
+						request_contexts,
 						threshold_ceremony_type,
 						key,
 						epoch,
 						..
 					} = failed_ceremony_context;
 
-					Self::deposit_event(match threshold_ceremony_type {
+					match threshold_ceremony_type {
 						ThresholdCeremonyType::Standard => {
 							T::OffenceReporter::report_many(
 								PalletOffence::ParticipateSigningFailed,
 								&offenders[..],
 							);
 
-							Self::new_ceremony_attempt(RequestInstruction::new(
-								request_id,
-								attempt_count.wrapping_add(1),
-								payload,
+							// The requests are retried together.
+							let request_type =
 								if <T::TargetChainCrypto as ChainCrypto>::sign_with_specific_key() {
 									RequestType::SpecificKey(key, epoch)
 								} else {
 									RequestType::CurrentKey
-								},
-							));
-							Event::<T, I>::RetryRequested { request_id, ceremony_id }
-						},
-						ThresholdCeremonyType::KeygenVerification => {
-							Signature::<T, I>::insert(
-								request_id,
-								AsyncResult::Ready(Err(offenders.clone())),
+								};
+							Self::new_ceremony_attempt(
+								request_contexts
+									.into_iter()
+									.map(|RequestContext { request_id, attempt_count, payload }| {
+										RequestInstruction::new(
+											request_id,
+											attempt_count.wrapping_add(1),
+											payload,
+											request_type.clone(),
+										)
+									})
+									.collect(),
 							);
-							Self::maybe_dispatch_callback(request_id, ceremony_id);
-							Event::<T, I>::ThresholdSignatureFailed {
-								request_id,
-								ceremony_id,
-								offenders,
+							for request_id in request_ids {
+								Self::deposit_event(Event::<T, I>::RetryRequested {
+									request_id,
+									ceremony_id,
+								});
 							}
 						},
-					})
+						ThresholdCeremonyType::KeygenVerification =>
+							for request_id in request_ids {
+								Signature::<T, I>::insert(
+									request_id,
+									AsyncResult::Ready(Err(offenders.clone())),
+								);
+								Self::maybe_dispatch_callback(request_id, ceremony_id);
+								Self::deposit_event(Event::<T, I>::ThresholdSignatureFailed {
+									request_id,
+									ceremony_id,
+									offenders: offenders.clone(),
+								});
+							},
+					}
 				}
 			}
 
-			for request_id in RequestRetryQueue::<T, I>::take(current_block) {
-				if let Some(request_instruction) =
-					PendingRequestInstructions::<T, I>::take(request_id)
-				{
-					Self::new_ceremony_attempt(request_instruction);
-				}
-			}
+			// The weight of starting the scheduled requests is accounted for by the callers of
+			// `request_signature`, as when the ceremonies were started straight away.
+			Self::start_scheduled_requests(current_block);
 
 			T::Weights::on_initialize(T::EpochInfo::current_authority_count(), num_retries) +
-				T::Weights::report_offenders(num_offenders as AuthorityCount)
+				T::Weights::report_offenders(num_offenders as AuthorityCount) +
+				T::Weights::prune_failure_evidence(num_expired_ceremonies, num_expired_reports)
 		}
 	}
 
@@ -469,24 +574,32 @@ pub mod pallet {
 		type Call = Call<T, I>;
 
 		fn validate_unsigned(_source: TransactionSource, call: &Self::Call) -> TransactionValidity {
-			if let Call::<T, I>::signature_success { ceremony_id, signature } = call {
-				let CeremonyContext { key, request_context, .. } =
-					PendingCeremonies::<T, I>::get(ceremony_id).ok_or(InvalidTransaction::Stale)?;
-
-				if <T::TargetChainCrypto as ChainCrypto>::verify_threshold_signature(
-					&key,
-					&request_context.payload,
-					signature,
-				) {
-					ValidTransaction::with_tag_prefix(Self::name())
-						// We only expect one success per ceremony.
-						.and_provides(ceremony_id)
-						.build()
-				} else {
-					InvalidTransaction::BadProof.into()
-				}
+			let (ceremony_id, signatures) = match call {
+				Call::<T, I>::signature_success { ceremony_id, signature } =>
+					(ceremony_id, sp_std::slice::from_ref(signature)),
+				Call::<T, I>::batch_signature_success { ceremony_id, signatures } =>
+					(ceremony_id, &signatures[..]),
+				_ => return InvalidTransaction::Call.into(),
+			};
+
+			let CeremonyContext { key, request_contexts, .. } =
+				PendingCeremonies::<T, I>::get(ceremony_id).ok_or(InvalidTransaction::Stale)?;
+
+			// There must be a valid signature for each request in the ceremony.
+			if request_contexts.len() == signatures.len() &&
+				request_contexts.iter().zip(signatures).all(|(request_context, signature)| {
+					<T::TargetChainCrypto as ChainCrypto>::verify_threshold_signature(
+						&key,
+						&request_context.payload,
+						signature,
+					)
+				}) {
+				ValidTransaction::with_tag_prefix(Self::name())
+					// We only expect one success per ceremony.
+					.and_provides(ceremony_id)
+					.build()
 			} else {
-				InvalidTransaction::Call.into()
+				InvalidTransaction::BadProof.into()
 			}
 		}
 	}
@@ -518,35 +631,7 @@ pub mod pallet {
 		) -> DispatchResultWithPostInfo {
 			ensure_none(origin)?;
 
-			let CeremonyContext {
-				request_context: RequestContext { request_id, attempt_count, .. },
-				..
-			} = PendingCeremonies::<T, I>::take(ceremony_id).ok_or_else(|| {
-				// We check the ceremony_id in the ValidateUnsigned transaction, so if this
-				// happens, there is something seriously wrong with our assumptions.
-				log::error!("Invalid ceremony_id received {}.", ceremony_id);
-				Error::<T, I>::InvalidCeremonyId
-			})?;
-
-			PendingRequestInstructions::<T, I>::remove(request_id);
-
-			// Report the success once we know the CeremonyId is valid
-			Self::deposit_event(Event::<T, I>::ThresholdSignatureSuccess {
-				request_id,
-				ceremony_id,
-			});
-
-			log::debug!(
-				"Threshold signature request {} succeeded at ceremony {} after {} attempts.",
-				request_id,
-				ceremony_id,
-				attempt_count
-			);
-
-			Signature::<T, I>::insert(request_id, AsyncResult::Ready(Ok(signature)));
-			Self::maybe_dispatch_callback(request_id, ceremony_id);
-
-			Ok(().into())
+			Self::on_signature_success(ceremony_id, vec![signature])
 		}
 
 		/// Report that a threshold signature ceremony has failed and incriminate the guilty
@@ -601,11 +686,13 @@ pub mod pallet {
 							Self::schedule_ceremony_retry(ceremony_id, 1u32.into());
 						}
 
-						Self::deposit_event(Event::<T, I>::FailureReportProcessed {
-							request_id: context.request_context.request_id,
-							ceremony_id,
-							reporter_id,
-						});
+						for request_id in context.request_ids() {
+							Self::deposit_event(Event::<T, I>::FailureReportProcessed {
+								request_id,
+								ceremony_id,
+								reporter_id: reporter_id.clone(),
+							});
+						}
 
 						Ok(())
 					})
@@ -631,10 +718,197 @@ pub mod pallet {
 
 			Ok(().into())
 		}
+
+		/// A threshold signature ceremony for a batch of requests has succeeded. The signatures
+		/// are in the same order as the payloads of the
+		/// [ThresholdSignatureBatchRequest](Event::ThresholdSignatureBatchRequest).
+		///
+		/// Like [signature_success](Call::signature_success), this is an **Unsigned** Extrinsic
+		/// and the signatures are checked in the [ValidateUnsigned] implementation.
+		///
+		/// ## Events
+		///
+		/// - [ThresholdSignatureSuccess](Event::ThresholdSignatureSuccess)
+		/// - [ThresholdDispatchComplete](Event::ThresholdDispatchComplete)
+		///
+		/// ## Errors
+		///
+		/// - [InvalidCeremonyId](sp_runtime::traits::InvalidCeremonyId)
+		/// - [BadOrigin](sp_runtime::traits::BadOrigin)
+		#[pallet::call_index(3)]
+		#[pallet::weight(T::Weights::signature_success().saturating_mul(signatures.len() as u64))]
+		pub fn batch_signature_success(
+			origin: OriginFor<T>,
+			ceremony_id: CeremonyId,
+			signatures: Vec<SignatureFor<T, I>>,
+		) -> DispatchResultWithPostInfo {
+			ensure_none(origin)?;
+
+			Self::on_signature_success(ceremony_id, signatures)
+		}
+
+		/// Submit evidence supporting a failure report, so that the reasons for blaming the
+		/// reported parties can be reviewed by other nodes and by governance.
+		///
+		/// Evidence can only be submitted by the participants of a pending ceremony, which is
+		/// either a signing ceremony of this pallet, or a key ceremony of the corresponding vault.
+		/// The total amount of evidence per ceremony is bounded by
+		/// [Config::MaxFailureEvidenceEntriesPerCeremony].
+		///
+		/// Evidence of an inconsistent broadcast must include the broadcaster's signatures over
+		/// the conflicting values. Values whose signatures don't verify are dropped, and only the
+		/// broadcasters shown to have signed at least two different values are kept.
+		///
+		/// ## Events
+		///
+		/// - [FailureEvidenceReported](Event::FailureEvidenceReported)
+		///
+		/// ## Errors
+		///
+		/// - [FailureEvidenceTooLarge](Error::FailureEvidenceTooLarge)
+		/// - [DuplicateFailureEvidence](Error::DuplicateFailureEvidence)
+		/// - [FailureEvidenceLimitReached](Error::FailureEvidenceLimitReached)
+		/// - [InvalidCeremonyId](Error::InvalidCeremonyId)
+		/// - [InvalidRespondent](Error::InvalidRespondent)
+		/// - [InvalidFailureEvidence](Error::InvalidFailureEvidence)
+		#[pallet::call_index(4)]
+		#[pallet::weight(T::Weights::report_failure_evidence(evidence.entry_count()))]
+		pub fn report_failure_evidence(
+			origin: OriginFor<T>,
+			ceremony_id: CeremonyId,
+			evidence: CeremonyFailureEvidence,
+		) -> DispatchResultWithPostInfo {
+			let reporter_id: T::ValidatorId =
+				T::AccountRoleRegistry::ensure_validator(origin)?.into();
+
+			ensure!(
+				evidence.entry_count() <= T::MaxFailureEvidenceEntries::get(),
+				Error::<T, I>::FailureEvidenceTooLarge
+			);
+
+			let participants = PendingCeremonies::<T, I>::get(ceremony_id)
+				.map(|context| context.candidates)
+				.or_else(|| T::KeyCeremonyParticipants::pending_ceremony_participants(ceremony_id))
+				.ok_or(Error::<T, I>::InvalidCeremonyId)?;
+			ensure!(participants.contains(&reporter_id), Error::<T, I>::InvalidRespondent);
+
+			ensure!(
+				!FailureEvidence::<T, I>::contains_key(ceremony_id, &reporter_id),
+				Error::<T, I>::DuplicateFailureEvidence
+			);
+
+			let evidence = Self::verify_failure_evidence(ceremony_id, &participants, evidence)?;
+			let entry_count = evidence.entry_count();
+			let previous_entry_count = FailureEvidenceEntryCount::<T, I>::get(ceremony_id);
+			ensure!(
+				previous_entry_count.saturating_add(entry_count) <=
+					T::MaxFailureEvidenceEntriesPerCeremony::get(),
+				Error::<T, I>::FailureEvidenceLimitReached
+			);
+
+			// The evidence for a ceremony expires together, counting from the first submission.
+			if !FailureEvidenceEntryCount::<T, I>::contains_key(ceremony_id) {
+				FailureEvidenceExpiries::<T, I>::append(
+					frame_system::Pallet::<T>::current_block_number()
+						.saturating_add(T::FailureEvidenceRetentionPeriod::get()),
+					ceremony_id,
+				);
+			}
+			FailureEvidenceEntryCount::<T, I>::insert(
+				ceremony_id,
+				previous_entry_count.saturating_add(entry_count),
+			);
+			FailureEvidence::<T, I>::insert(ceremony_id, &reporter_id, evidence);
+
+			Self::deposit_event(Event::<T, I>::FailureEvidenceReported {
+				ceremony_id,
+				reporter_id,
+			});
+
+			Ok(().into())
+		}
 	}
 }
 
 impl<T: Config<I>, I: 'static> Pallet<T, I> {
+	/// The failure evidence submitted for a ceremony, by reporter.
+	pub fn failure_evidence(
+		ceremony_id: CeremonyId,
+	) -> Vec<(T::ValidatorId, CeremonyFailureEvidence)> {
+		FailureEvidence::<T, I>::iter_prefix(ceremony_id).collect()
+	}
+
+	/// Checks the broadcasters' signatures over the conflicting values in the evidence. Values
+	/// with invalid signatures are dropped, as are the broadcasters for whom fewer than two
+	/// different values remain. The remaining evidence is returned if it proves at least one
+	/// inconsistent broadcast.
+	fn verify_failure_evidence(
+		ceremony_id: CeremonyId,
+		participants: &BTreeSet<T::ValidatorId>,
+		evidence: CeremonyFailureEvidence,
+	) -> Result<CeremonyFailureEvidence, Error<T, I>> {
+		let stage = match evidence.reason {
+			BlameReason::InconsistentBroadcast { stage } => stage,
+			_ => {
+				ensure!(
+					evidence.conflicting_broadcasts.is_empty(),
+					Error::<T, I>::InvalidFailureEvidence
+				);
+				return Ok(evidence)
+			},
+		};
+
+		// The parties are indexed from 1, in the order of their account ids.
+		let participants = participants.iter().collect::<Vec<_>>();
+		let conflicting_broadcasts = evidence
+			.conflicting_broadcasts
+			.into_iter()
+			.filter_map(|ConflictingBroadcast { broadcaster, received }| {
+				let broadcaster_id = participants.get((broadcaster as usize).checked_sub(1)?)?;
+				let received = received
+					.into_iter()
+					.filter(|(value, _)| {
+						value.as_ref().map_or(true, |SignedValueHash { hash, signature }| {
+							T::PeerSignatureVerifier::verify_peer_signature(
+								broadcaster_id,
+								&broadcast_signing_payload(
+									T::TargetChain::get(),
+									ceremony_id,
+									stage,
+									hash,
+								),
+								&signature.0,
+							)
+						})
+					})
+					.collect::<Vec<_>>();
+				let distinct_values = received
+					.iter()
+					.filter_map(|(value, _)| value.as_ref().map(|value| value.hash))
+					.collect::<BTreeSet<_>>();
+				(distinct_values.len() >= 2)
+					.then_some(ConflictingBroadcast { broadcaster, received })
+			})
+			.collect::<Vec<_>>();
+		ensure!(!conflicting_broadcasts.is_empty(), Error::<T, I>::InvalidFailureEvidence);
+
+		Ok(CeremonyFailureEvidence { reason: evidence.reason, conflicting_broadcasts })
+	}
+
+	/// Removes the failure evidence that expires at this block, returning the number of
+	/// ceremonies whose evidence was removed, and the number of reports removed.
+	fn prune_failure_evidence(current_block: BlockNumberFor<T>) -> (u32, u32) {
+		let expired = FailureEvidenceExpiries::<T, I>::take(current_block);
+		let mut num_reports = 0u32;
+		for ceremony_id in &expired {
+			FailureEvidenceEntryCount::<T, I>::remove(ceremony_id);
+			num_reports.saturating_accrue(
+				FailureEvidence::<T, I>::clear_prefix(ceremony_id, u32::MAX, None).unique,
+			);
+		}
+		(expired.len() as u32, num_reports)
+	}
+
 	/// Initiate a new signature request, returning the request id.
 	fn inner_request_signature(
 		payload: PayloadFor<T, I>,
@@ -648,117 +922,238 @@ impl<T: Config<I>, I: 'static> Pallet<T, I> {
 			*id
 		});
 
-		Self::new_ceremony_attempt(RequestInstruction {
+		let request_instruction = RequestInstruction {
 			request_context: RequestContext { request_id, payload, attempt_count: 0 },
 			request_type,
-		});
+		};
+
+		if T::MaxSigningBatchSize::get() > 1 &&
+			!matches!(request_instruction.request_type, RequestType::KeygenVerification { .. })
+		{
+			// The ceremony is started at the start of the next block, together with the other
+			// requests made in this block. Requests can be made from the hooks of pallets that run
+			// after this one, so they can't be started at the end of this block.
+			Self::schedule_request(request_instruction, One::one());
+		} else {
+			Self::new_ceremony_attempt(vec![request_instruction]);
+		}
 
 		Signature::<T, I>::insert(request_id, AsyncResult::Pending);
 
 		request_id
 	}
 
-	/// Initiates a new ceremony request. Can return None if no ceremony was started.
-	fn new_ceremony_attempt(request_instruction: RequestInstruction<T, I>) {
-		let request_id = request_instruction.request_context.request_id;
-		let attempt_count = request_instruction.request_context.attempt_count;
-		let payload = request_instruction.request_context.payload.clone();
-
-		let (maybe_epoch_key_and_participants, ceremony_type) =
-			if let RequestType::KeygenVerification { epoch_index, key, ref participants } =
-				request_instruction.request_type
-			{
-				(
-					Ok((epoch_index, key, participants.clone())),
-					ThresholdCeremonyType::KeygenVerification,
-				)
-			} else {
-				(
-					match request_instruction.request_type {
-						RequestType::CurrentKey => T::KeyProvider::active_epoch_key()
-							.and_then(|EpochKey { key_state, key, epoch_index }| {
-								if key_state.is_available_for_request(request_id) {
-									Some((key, epoch_index))
-								} else {
-									None
-								}
-							})
-							.ok_or(Event::<T, I>::CurrentKeyUnavailable {
-								request_id,
-								attempt_count,
-							}),
-						RequestType::SpecificKey(key, epoch_index) => Ok((key, epoch_index)),
-						_ => unreachable!("RequestType::KeygenVerification is handled above"),
-					}
-					.and_then(|(key, epoch_index)| {
-						if let Some(nominees) =
-							T::ThresholdSignerNomination::threshold_nomination_with_seed(
-								(request_id, attempt_count),
-								epoch_index,
-							) {
-							Ok((epoch_index, key, nominees))
-						} else {
-							Err(Event::<T, I>::SignersUnavailable { request_id, attempt_count })
-						}
-					}),
-					ThresholdCeremonyType::Standard,
-				)
-			};
+	/// Initiates a new ceremony for the requests, which must all have the same request type. Any
+	/// requests that a ceremony can't be started for are scheduled for retry.
+	fn new_ceremony_attempt(mut request_instructions: Vec<RequestInstruction<T, I>>) {
+		let Some(request_type) =
+			request_instructions.first().map(|instruction| instruction.request_type.clone())
+		else {
+			return
+		};
 
-		Self::deposit_event(match maybe_epoch_key_and_participants {
-			Ok((epoch, key, participants)) => {
-				let ceremony_id = T::CeremonyIdProvider::increment_ceremony_id();
-				PendingCeremonies::<T, I>::insert(ceremony_id, {
-					CeremonyContext {
-						request_context: RequestContext {
+		let (epoch, key, participants, ceremony_type) = match request_type {
+			RequestType::KeygenVerification { key, epoch_index, participants } =>
+				(epoch_index, key, participants, ThresholdCeremonyType::KeygenVerification),
+			request_type => {
+				let (key, epoch_index) = match request_type {
+					RequestType::CurrentKey => {
+						let Some(EpochKey { key_state, key, epoch_index }) =
+							T::KeyProvider::active_epoch_key()
+						else {
+							Self::schedule_request_retries(
+								request_instructions,
+								|request_id, attempt_count| Event::<T, I>::CurrentKeyUnavailable {
+									request_id,
+									attempt_count,
+								},
+							);
+							return
+						};
+						let unavailable;
+						(request_instructions, unavailable) =
+							request_instructions.into_iter().partition(|instruction| {
+								key_state.is_available_for_request(
+									instruction.request_context.request_id,
+								)
+							});
+						Self::schedule_request_retries(unavailable, |request_id, attempt_count| {
+							Event::<T, I>::CurrentKeyUnavailable { request_id, attempt_count }
+						});
+						(key, epoch_index)
+					},
+					RequestType::SpecificKey(key, epoch_index) => (key, epoch_index),
+					RequestType::KeygenVerification { .. } =>
+						unreachable!("RequestType::KeygenVerification is handled above"),
+				};
+
+				// The signers are nominated based on the first request in the batch.
+				let Some(RequestContext { request_id, attempt_count, .. }) =
+					request_instructions.first().map(|instruction| &instruction.request_context)
+				else {
+					return
+				};
+				let Some(nominees) = T::ThresholdSignerNomination::threshold_nomination_with_seed(
+					(*request_id, *attempt_count),
+					epoch_index,
+				) else {
+					Self::schedule_request_retries(
+						request_instructions,
+						|request_id, attempt_count| Event::<T, I>::SignersUnavailable {
 							request_id,
 							attempt_count,
-							payload: payload.clone(),
 						},
-						threshold_ceremony_type: ceremony_type,
-						epoch,
-						key,
-						blame_counts: BTreeMap::new(),
-						candidates: participants.clone(),
-						remaining_respondents: participants.clone(),
-					}
-				});
-				Self::schedule_ceremony_retry(
-					ceremony_id,
-					ThresholdSignatureResponseTimeout::<T, I>::get(),
-				);
-				log::trace!(
-					target: "threshold-signing",
-					"Threshold set selected for request {}, requesting signature ceremony {}.",
-					request_id,
-					attempt_count
-				);
+					);
+					return
+				};
+
+				(epoch_index, key, nominees, ThresholdCeremonyType::Standard)
+			},
+		};
+
+		let request_contexts = request_instructions
+			.into_iter()
+			.map(|instruction| instruction.request_context)
+			.collect::<Vec<_>>();
+
+		let ceremony_id = T::CeremonyIdProvider::increment_ceremony_id();
+		PendingCeremonies::<T, I>::insert(
+			ceremony_id,
+			CeremonyContext {
+				request_contexts: request_contexts.clone(),
+				threshold_ceremony_type: ceremony_type,
+				epoch,
+				key,
+				blame_counts: BTreeMap::new(),
+				candidates: participants.clone(),
+				remaining_respondents: participants.clone(),
+			},
+		);
+		Self::schedule_ceremony_retry(
+			ceremony_id,
+			ThresholdSignatureResponseTimeout::<T, I>::get(),
+		);
+
+		let request_ids =
+			request_contexts.iter().map(|context| context.request_id).collect::<Vec<_>>();
+		log::trace!(
+			target: "threshold-signing",
+			"Threshold set selected for requests {:?}, requesting signature ceremony {}.",
+			request_ids,
+			ceremony_id
+		);
+
+		Self::deposit_event(match &request_contexts[..] {
+			[RequestContext { request_id, payload, .. }] =>
 				Event::<T, I>::ThresholdSignatureRequest {
-					request_id,
+					request_id: *request_id,
 					ceremony_id,
 					epoch,
 					key,
 					signatories: participants,
-					payload,
-				}
-			},
-			Err(event) => {
-				PendingRequestInstructions::<T, I>::insert(request_id, request_instruction);
-				RequestRetryQueue::<T, I>::append(
-					frame_system::Pallet::<T>::current_block_number()
-						.saturating_add(T::CeremonyRetryDelay::get()),
-					request_id,
-				);
-
-				log::trace!(
-					target: "threshold-signing",
-					"Scheduling retry: {:?}", event
-				);
-				event
+					payload: payload.clone(),
+				},
+			_ => Event::<T, I>::ThresholdSignatureBatchRequest {
+				request_ids,
+				ceremony_id,
+				epoch,
+				key,
+				signatories: participants,
+				payloads: request_contexts.into_iter().map(|context| context.payload).collect(),
 			},
 		});
 	}
 
+	/// Starts ceremonies for the requests scheduled for this block. Requests with the same request
+	/// type are signed together, in batches of up to `MaxSigningBatchSize` requests.
+	fn start_scheduled_requests(current_block: BlockNumberFor<T>) {
+		let max_batch_size = T::MaxSigningBatchSize::get().max(1) as usize;
+		let mut batches: Vec<Vec<RequestInstruction<T, I>>> = Vec::new();
+		for request_id in RequestRetryQueue::<T, I>::take(current_block) {
+			if let Some(request_instruction) = PendingRequestInstructions::<T, I>::take(request_id)
+			{
+				match batches.iter_mut().find(|batch| {
+					batch.len() < max_batch_size &&
+						batch[0].request_type == request_instruction.request_type
+				}) {
+					Some(batch) => batch.push(request_instruction),
+					None => batches.push(vec![request_instruction]),
+				}
+			}
+		}
+		for batch in batches {
+			Self::new_ceremony_attempt(batch);
+		}
+	}
+
+	/// Stores the request, to be (re)started after `delay`.
+	fn schedule_request(request_instruction: RequestInstruction<T, I>, delay: BlockNumberFor<T>) {
+		let request_id = request_instruction.request_context.request_id;
+		PendingRequestInstructions::<T, I>::insert(request_id, request_instruction);
+		RequestRetryQueue::<T, I>::append(
+			frame_system::Pallet::<T>::current_block_number().saturating_add(delay),
+			request_id,
+		);
+	}
+
+	/// Schedules a retry for requests that no ceremony could be started for.
+	fn schedule_request_retries(
+		request_instructions: Vec<RequestInstruction<T, I>>,
+		event: impl Fn(RequestId, AttemptCount) -> Event<T, I>,
+	) {
+		for request_instruction in request_instructions {
+			let RequestContext { request_id, attempt_count, .. } =
+				request_instruction.request_context;
+			Self::schedule_request(request_instruction, T::CeremonyRetryDelay::get());
+
+			let event = event(request_id, attempt_count);
+			log::trace!(
+				target: "threshold-signing",
+				"Scheduling retry: {:?}", event
+			);
+			Self::deposit_event(event);
+		}
+	}
+
+	/// Stores the signatures and dispatches the callbacks for the requests of the ceremony. The
+	/// signatures have already been checked in [ValidateUnsigned].
+	fn on_signature_success(
+		ceremony_id: CeremonyId,
+		signatures: Vec<SignatureFor<T, I>>,
+	) -> DispatchResultWithPostInfo {
+		let CeremonyContext { request_contexts, .. } = PendingCeremonies::<T, I>::take(ceremony_id)
+			.ok_or_else(|| {
+				// We check the ceremony_id in the ValidateUnsigned transaction, so if this
+				// happens, there is something seriously wrong with our assumptions.
+				log::error!("Invalid ceremony_id received {}.", ceremony_id);
+				Error::<T, I>::InvalidCeremonyId
+			})?;
+
+		for (RequestContext { request_id, attempt_count, .. }, signature) in
+			request_contexts.into_iter().zip(signatures)
+		{
+			PendingRequestInstructions::<T, I>::remove(request_id);
+
+			// Report the success once we know the CeremonyId is valid
+			Self::deposit_event(Event::<T, I>::ThresholdSignatureSuccess {
+				request_id,
+				ceremony_id,
+			});
+
+			log::debug!(
+				"Threshold signature request {} succeeded at ceremony {} after {} attempts.",
+				request_id,
+				ceremony_id,
+				attempt_count
+			);
+
+			Signature::<T, I>::insert(request_id, AsyncResult::Ready(Ok(signature)));
+			Self::maybe_dispatch_callback(request_id, ceremony_id);
+		}
+
+		Ok(().into())
+	}
+
 	// We've kicked off a ceremony, now we start a timeout, where it'll retry after that point.
 	fn schedule_ceremony_retry(id: CeremonyId, retry_delay: BlockNumberFor<T>) {
 		CeremonyRetryQueues::<T, I>::append(
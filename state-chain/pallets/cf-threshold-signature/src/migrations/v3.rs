@@ -7,6 +7,17 @@ use sp_std::marker::PhantomData;
 mod old_types {
 	use super::*;
 	use codec::{Decode, Encode};
+	use frame_support::Twox64Concat;
+
+	/// `PendingCeremonies` as of storage version 3, before requests were batched (see the v4
+	/// migration).
+	#[frame_support::storage_alias]
+	pub type PendingCeremonies<T: Config<I>, I: 'static> = StorageMap<
+		Pallet<T, I>,
+		Twox64Concat,
+		CeremonyId,
+		super::super::v4::old_types::CeremonyContext<T, I>,
+	>;
 
 	#[derive(Clone, RuntimeDebug, PartialEq, Eq, Encode, Decode)]
 	pub struct CeremonyContext<T: Config<I>, I: 'static> {
@@ -30,22 +41,24 @@ pub struct Migration<T: Config<I>, I: 'static>(PhantomData<(T, I)>);
 
 impl<T: Config<I>, I: 'static> OnRuntimeUpgrade for Migration<T, I> {
 	fn on_runtime_upgrade() -> frame_support::weights::Weight {
-		PendingCeremonies::<T, I>::translate::<old_types::CeremonyContext<T, I>, _>(|_id, old| {
-			Some(CeremonyContext {
-				request_context: old.request_context,
-				remaining_respondents: old.remaining_respondents,
-				blame_counts: old.blame_counts,
-				// We don't know the actual participants, but it's more important that we get the
-				// set size right, otherwise the threshold will be incorrect.
-				candidates: <<T as Chainflip>::EpochInfo as EpochInfo>::current_authorities()
-					.into_iter()
-					.take(old.participant_count as usize)
-					.collect(),
-				epoch: old.epoch,
-				key: old.key,
-				threshold_ceremony_type: old.threshold_ceremony_type,
-			})
-		});
+		old_types::PendingCeremonies::<T, I>::translate::<old_types::CeremonyContext<T, I>, _>(
+			|_id, old| {
+				Some(super::v4::old_types::CeremonyContext {
+					request_context: old.request_context,
+					remaining_respondents: old.remaining_respondents,
+					blame_counts: old.blame_counts,
+					// We don't know the actual participants, but it's more important that we get
+					// the set size right, otherwise the threshold will be incorrect.
+					candidates: <<T as Chainflip>::EpochInfo as EpochInfo>::current_authorities()
+						.into_iter()
+						.take(old.participant_count as usize)
+						.collect(),
+					epoch: old.epoch,
+					key: old.key,
+					threshold_ceremony_type: old.threshold_ceremony_type,
+				})
+			},
+		);
 		Weight::zero()
 	}
 
@@ -0,0 +1,62 @@
+use crate::*;
+#[cfg(feature = "try-runtime")]
+use frame_support::dispatch::DispatchError;
+use frame_support::{traits::OnRuntimeUpgrade, weights::Weight};
+use sp_std::marker::PhantomData;
+
+pub(super) mod old_types {
+	use super::*;
+	use codec::{Decode, Encode};
+
+	#[derive(Clone, RuntimeDebug, PartialEq, Eq, Encode, Decode)]
+	pub struct CeremonyContext<T: Config<I>, I: 'static> {
+		pub request_context: RequestContext<T, I>,
+		/// The respondents that have yet to reply.
+		pub remaining_respondents: BTreeSet<T::ValidatorId>,
+		/// The number of blame votes (accusations) each authority has received.
+		pub blame_counts: BTreeMap<T::ValidatorId, AuthorityCount>,
+		/// The candidates participating in the signing ceremony (ie. the threshold set).
+		pub candidates: BTreeSet<T::ValidatorId>,
+		/// The epoch in which the ceremony was started.
+		pub epoch: EpochIndex,
+		/// The key we want to sign with.
+		pub key: <T::TargetChainCrypto as ChainCrypto>::AggKey,
+		/// Determines how/if we deal with ceremony failure.
+		pub threshold_ceremony_type: ThresholdCeremonyType,
+	}
+}
+
+pub struct Migration<T: Config<I>, I: 'static>(PhantomData<(T, I)>);
+
+impl<T: Config<I>, I: 'static> OnRuntimeUpgrade for Migration<T, I> {
+	fn on_runtime_upgrade() -> frame_support::weights::Weight {
+		PendingCeremonies::<T, I>::translate::<old_types::CeremonyContext<T, I>, _>(|_id, old| {
+			Some(CeremonyContext {
+				request_contexts: vec![old.request_context],
+				remaining_respondents: old.remaining_respondents,
+				blame_counts: old.blame_counts,
+				candidates: old.candidates,
+				epoch: old.epoch,
+				key: old.key,
+				threshold_ceremony_type: old.threshold_ceremony_type,
+			})
+		});
+		Weight::zero()
+	}
+
+	#[cfg(feature = "try-runtime")]
+	fn pre_upgrade() -> Result<Vec<u8>, DispatchError> {
+		Ok((PendingCeremonies::<T, I>::iter_keys().count() as u32).encode())
+	}
+
+	#[cfg(feature = "try-runtime")]
+	fn post_upgrade(state: Vec<u8>) -> Result<(), DispatchError> {
+		let pending_ceremony_count =
+			<u32>::decode(&mut &state[..]).map_err(|_| "Failed to decode pre-upgrade state.")?;
+		frame_support::ensure!(
+			PendingCeremonies::<T, I>::iter_values().count() as u32 == pending_ceremony_count,
+			"Pending ceremonies were lost in the migration."
+		);
+		Ok(())
+	}
+}
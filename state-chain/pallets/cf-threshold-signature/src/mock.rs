@@ -8,11 +8,13 @@ use cf_chains::{
 	mocks::{MockAggKey, MockEthereumChainCrypto, MockThresholdSignature},
 	ChainCrypto,
 };
+use cf_primitives::ForeignChain;
 use cf_traits::{
 	impl_mock_chainflip,
 	mocks::{
-		ceremony_id_provider::MockCeremonyIdProvider, key_provider::MockKeyProvider,
-		signer_nomination::MockNominator,
+		ceremony_id_provider::MockCeremonyIdProvider,
+		key_ceremony_participants::MockKeyCeremonyParticipants, key_provider::MockKeyProvider,
+		peer_signature_verifier::MockPeerSignatureVerifier, signer_nomination::MockNominator,
 	},
 	AccountRoleRegistry, AsyncResult, KeyProvider, ThresholdSigner,
 };
@@ -130,6 +132,11 @@ pub const INVALID_SIGNATURE: <MockEthereumChainCrypto as ChainCrypto>::Threshold
 
 parameter_types! {
 	pub const CeremonyRetryDelay: BlockNumberFor<Test> = 4;
+	pub static MaxSigningBatchSize: u32 = 1;
+	pub const FailureEvidenceRetentionPeriod: BlockNumberFor<Test> = 10;
+	pub const MaxFailureEvidenceEntries: u32 = 10;
+	pub const MaxFailureEvidenceEntriesPerCeremony: u32 = 15;
+	pub const TargetChain: ForeignChain = ForeignChain::Ethereum;
 }
 
 pub type MockOffenceReporter =
@@ -146,6 +153,13 @@ impl pallet_cf_threshold_signature::Config<Instance1> for Test {
 	type OffenceReporter = MockOffenceReporter;
 	type CeremonyIdProvider = MockCeremonyIdProvider;
 	type CeremonyRetryDelay = CeremonyRetryDelay;
+	type MaxSigningBatchSize = MaxSigningBatchSize;
+	type FailureEvidenceRetentionPeriod = FailureEvidenceRetentionPeriod;
+	type MaxFailureEvidenceEntries = MaxFailureEvidenceEntries;
+	type MaxFailureEvidenceEntriesPerCeremony = MaxFailureEvidenceEntriesPerCeremony;
+	type KeyCeremonyParticipants = MockKeyCeremonyParticipants;
+	type TargetChain = TargetChain;
+	type PeerSignatureVerifier = MockPeerSignatureVerifier;
 	type Weights = ();
 }
 
@@ -18,15 +18,21 @@ use frame_support::{
 use frame_system::pallet_prelude::BlockNumberFor;
 use sp_runtime::traits::BlockNumberProvider;
 
+/// The context of the only request signed by the ceremony.
+fn single_request_context(ceremony_id: CeremonyId) -> RequestContext<Test, Instance1> {
+	let CeremonyContext::<Test, Instance1> { request_contexts, .. } =
+		EthereumThresholdSigner::pending_ceremonies(ceremony_id)
+			.unwrap_or_else(|| panic!("Expected a ceremony with id {ceremony_id:?}"));
+	assert_eq!(request_contexts.len(), 1);
+	request_contexts.into_iter().next().unwrap()
+}
+
 fn get_ceremony_context(
 	ceremony_id: CeremonyId,
 	expected_request_id: RequestId,
 	expected_attempt: AttemptCount,
 ) -> CeremonyContext<Test, Instance1> {
-	let CeremonyContext::<Test, Instance1> {
-		request_context: RequestContext { request_id, attempt_count, .. },
-		..
-	} = EthereumThresholdSigner::pending_ceremonies(ceremony_id).unwrap();
+	let RequestContext { request_id, attempt_count, .. } = single_request_context(ceremony_id);
 	assert_eq!(request_id, expected_request_id);
 	assert_eq!(attempt_count, expected_attempt);
 	EthereumThresholdSigner::pending_ceremonies(ceremony_id)
@@ -149,8 +155,7 @@ fn happy_path_no_callback() {
 		.with_request(b"OHAI")
 		.execute_with_consistency_checks(|| {
 			let ceremony_id = current_ceremony_id();
-			let CeremonyContext::<Test, Instance1> { request_context, .. } =
-				EthereumThresholdSigner::pending_ceremonies(ceremony_id).unwrap();
+			let request_context = single_request_context(ceremony_id);
 			let cfe = MockCfe { id: 1, behaviour: CfeBehaviour::Success };
 
 			run_cfes_on_sc_events(&[cfe]);
@@ -179,8 +184,7 @@ fn happy_path_with_callback() {
 		.with_request_and_callback(b"OHAI", MockCallback::new)
 		.execute_with_consistency_checks(|| {
 			let ceremony_id = current_ceremony_id();
-			let CeremonyContext::<Test, Instance1> { request_context, .. } =
-				EthereumThresholdSigner::pending_ceremonies(ceremony_id).unwrap();
+			let request_context = single_request_context(ceremony_id);
 			let cfe = MockCfe { id: 1, behaviour: CfeBehaviour::Success };
 
 			run_cfes_on_sc_events(&[cfe]);
@@ -214,8 +218,7 @@ fn signature_success_can_only_succeed_once_per_request() {
 		.with_request_and_callback(PAYLOAD, MockCallback::new)
 		.execute_with_consistency_checks(|| {
 			let ceremony_id = current_ceremony_id();
-			let CeremonyContext::<Test, Instance1> { request_context, .. } =
-				EthereumThresholdSigner::pending_ceremonies(ceremony_id).unwrap();
+			let request_context = single_request_context(ceremony_id);
 			assert_eq!(MockCallback::times_called(), 0);
 			// report signature success
 			run_cfes_on_sc_events(&[MockCfe { id: 1, behaviour: CfeBehaviour::Success }]);
@@ -286,10 +289,8 @@ fn fail_path_with_timeout() {
 		.with_request(b"OHAI")
 		.execute_with_consistency_checks(|| {
 			let ceremony_id = current_ceremony_id();
-			let CeremonyContext::<Test, Instance1> {
-				request_context: RequestContext { request_id, attempt_count, .. },
-				..
-			} = EthereumThresholdSigner::pending_ceremonies(ceremony_id).unwrap();
+			let RequestContext { request_id, attempt_count, .. } =
+				single_request_context(ceremony_id);
 			let cfes = [
 				MockCfe { id: 1, behaviour: CfeBehaviour::Timeout },
 				MockCfe { id: 2, behaviour: CfeBehaviour::ReportFailure(vec![1]) },
@@ -352,10 +353,8 @@ fn fail_path_due_to_report_signature_failed() {
 			// progress by one block *after* the initial request is inserted (in the ExtBuilder)
 			System::set_block_number(frame_system::Pallet::<Test>::current_block_number() + 1);
 			let ceremony_id = current_ceremony_id();
-			let CeremonyContext::<Test, Instance1> {
-				request_context: RequestContext { request_id, attempt_count, .. },
-				..
-			} = EthereumThresholdSigner::pending_ceremonies(ceremony_id).unwrap();
+			let RequestContext { request_id, attempt_count, .. } =
+				single_request_context(ceremony_id);
 			let cfes = [(1, vec![]), (2, vec![1]), (3, vec![1]), (4, vec![1]), (5, vec![1])]
 				.into_iter()
 				.map(|(id, report)| MockCfe { id, behaviour: CfeBehaviour::ReportFailure(report) })
@@ -442,10 +441,8 @@ fn test_retries_for_locked_key() {
 		.with_request(b"OHAI")
 		.execute_with_consistency_checks(|| {
 			let ceremony_id = current_ceremony_id();
-			let CeremonyContext::<Test, Instance1> {
-				request_context: RequestContext { request_id, attempt_count: first_attempt, .. },
-				..
-			} = EthereumThresholdSigner::pending_ceremonies(ceremony_id).unwrap();
+			let RequestContext { request_id, attempt_count: first_attempt, .. } =
+				single_request_context(ceremony_id);
 
 			MockKeyProvider::<MockEthereumChainCrypto>::lock_key(request_id);
 
@@ -460,11 +457,8 @@ fn test_retries_for_locked_key() {
 			<EthereumThresholdSigner as Hooks<BlockNumberFor<Test>>>::on_initialize(retry_block);
 
 			let retry_ceremony_id = current_ceremony_id();
-			let CeremonyContext::<Test, Instance1> {
-				request_context:
-					RequestContext { request_id: request_id_2, attempt_count: second_attempt, .. },
-				..
-			} = EthereumThresholdSigner::pending_ceremonies(retry_ceremony_id).unwrap();
+			let RequestContext { request_id: request_id_2, attempt_count: second_attempt, .. } =
+				single_request_context(retry_ceremony_id);
 			assert_eq!(request_id, request_id_2);
 			assert_eq!(second_attempt, first_attempt + 1);
 			assert_eq!(retry_ceremony_id, ceremony_id + 1);
@@ -481,10 +475,8 @@ fn test_retries_for_immutable_key() {
 		.with_request(b"OHAI")
 		.execute_with_consistency_checks(|| {
 			let ceremony_id = current_ceremony_id();
-			let CeremonyContext::<Test, Instance1> {
-				request_context: RequestContext { request_id, attempt_count: first_attempt, .. },
-				..
-			} = EthereumThresholdSigner::pending_ceremonies(ceremony_id).unwrap();
+			let RequestContext { request_id, attempt_count: first_attempt, .. } =
+				single_request_context(ceremony_id);
 
 			MockFixedKeySigningRequests::set(true);
 
@@ -497,12 +489,10 @@ fn test_retries_for_immutable_key() {
 			<EthereumThresholdSigner as Hooks<BlockNumberFor<Test>>>::on_initialize(retry_block);
 
 			let retry_ceremony_id = current_ceremony_id();
-			let CeremonyContext::<Test, Instance1> {
-				request_context:
-					RequestContext { request_id: request_id_2, attempt_count: second_attempt, .. },
-				key,
-				..
-			} = EthereumThresholdSigner::pending_ceremonies(retry_ceremony_id).unwrap();
+			let RequestContext { request_id: request_id_2, attempt_count: second_attempt, .. } =
+				single_request_context(retry_ceremony_id);
+			let CeremonyContext::<Test, Instance1> { key, .. } =
+				EthereumThresholdSigner::pending_ceremonies(retry_ceremony_id).unwrap();
 			assert_eq!(request_id, request_id_2);
 			assert_eq!(second_attempt, first_attempt + 1);
 			assert_eq!(retry_ceremony_id, ceremony_id + 1);
@@ -677,6 +667,228 @@ mod unsigned_validation {
 	}
 }
 
+#[cfg(test)]
+mod batching {
+	use super::*;
+	use crate::{Call as PalletCall, Event as PalletEvent, PendingCeremonies};
+	use frame_support::{pallet_prelude::InvalidTransaction, unsigned::TransactionSource};
+	use sp_runtime::traits::ValidateUnsigned;
+
+	const NOMINEES: [u64; 2] = [1, 2];
+	const AUTHORITIES: [u64; 3] = [1, 2, 3];
+	const PAYLOADS: [[u8; 4]; 4] = [*b"OHAI", *b"SUP?", *b"HEYO", *b"YOYO"];
+	const BATCH_SIZE: u32 = 3;
+
+	/// Requests signatures for all payloads in the same block, then starts the ceremonies for them
+	/// at the start of the next block.
+	fn request_signatures() -> Vec<RequestId> {
+		MaxSigningBatchSize::set(BATCH_SIZE);
+
+		let initial_ceremony_id = current_ceremony_id();
+		let request_ids = PAYLOADS
+			.iter()
+			.map(|payload| {
+				<EthereumThresholdSigner as ThresholdSigner<_>>::request_signature(*payload)
+			})
+			.collect::<Vec<_>>();
+		assert_eq!(current_ceremony_id(), initial_ceremony_id);
+
+		// Nothing is started at the end of the block, as later hooks can still make requests.
+		<EthereumThresholdSigner as Hooks<BlockNumberFor<Test>>>::on_finalize(
+			System::current_block_number(),
+		);
+		assert_eq!(current_ceremony_id(), initial_ceremony_id);
+
+		let next_block = System::current_block_number() + 1;
+		System::set_block_number(next_block);
+		<EthereumThresholdSigner as Hooks<BlockNumberFor<Test>>>::on_initialize(next_block);
+
+		request_ids
+	}
+
+	#[test]
+	fn requests_in_the_same_block_are_signed_together() {
+		new_test_ext()
+			.with_authorities(AUTHORITIES)
+			.with_nominees(NOMINEES)
+			.execute_with_consistency_checks(|| {
+				let request_ids = request_signatures();
+				let batch_ceremony_id = current_ceremony_id() - 1;
+
+				let CeremonyContext::<Test, Instance1> { request_contexts, .. } =
+					PendingCeremonies::<Test, Instance1>::get(batch_ceremony_id).unwrap();
+				assert_eq!(
+					request_contexts.iter().map(|context| context.payload).collect::<Vec<_>>(),
+					PAYLOADS[..BATCH_SIZE as usize].to_vec()
+				);
+				assert!(System::events().iter().any(|record| record.event ==
+					RuntimeEvent::EthereumThresholdSigner(
+						PalletEvent::ThresholdSignatureBatchRequest {
+							request_ids: request_ids[..BATCH_SIZE as usize].to_vec(),
+							ceremony_id: batch_ceremony_id,
+							epoch: 0,
+							key: current_agg_key(),
+							signatories: BTreeSet::from_iter(NOMINEES),
+							payloads: PAYLOADS[..BATCH_SIZE as usize].to_vec(),
+						}
+					)));
+
+				// The remaining request doesn't fit in the batch.
+				assert_eq!(single_request_context(current_ceremony_id()).payload, PAYLOADS[3]);
+
+				assert_ok!(EthereumThresholdSigner::batch_signature_success(
+					RuntimeOrigin::none(),
+					batch_ceremony_id,
+					PAYLOADS[..BATCH_SIZE as usize].iter().copied().map(sign).collect(),
+				));
+				for request_id in &request_ids[..BATCH_SIZE as usize] {
+					assert!(matches!(
+						EthereumThresholdSigner::signature(request_id),
+						AsyncResult::Ready(Ok(..))
+					));
+				}
+				assert!(matches!(
+					EthereumThresholdSigner::signature(request_ids[3]),
+					AsyncResult::Pending
+				));
+			});
+	}
+
+	#[test]
+	fn batch_requires_a_valid_signature_for_each_payload() {
+		new_test_ext()
+			.with_authorities(AUTHORITIES)
+			.with_nominees(NOMINEES)
+			.execute_with_consistency_checks(|| {
+				request_signatures();
+				let ceremony_id = current_ceremony_id() - 1;
+				let signatures =
+					PAYLOADS[..BATCH_SIZE as usize].iter().copied().map(sign).collect::<Vec<_>>();
+
+				let validate = |call: PalletCall<Test, Instance1>| {
+					Test::validate_unsigned(TransactionSource::External, &call.into())
+				};
+
+				assert!(validate(PalletCall::batch_signature_success {
+					ceremony_id,
+					signatures: signatures.clone()
+				})
+				.is_ok());
+				assert_eq!(
+					validate(PalletCall::batch_signature_success {
+						ceremony_id,
+						signatures: signatures[..2].to_vec()
+					})
+					.unwrap_err(),
+					InvalidTransaction::BadProof.into()
+				);
+				assert_eq!(
+					validate(PalletCall::batch_signature_success {
+						ceremony_id,
+						signatures: vec![signatures[0], signatures[1], INVALID_SIGNATURE],
+					})
+					.unwrap_err(),
+					InvalidTransaction::BadProof.into()
+				);
+				assert_eq!(
+					validate(PalletCall::signature_success {
+						ceremony_id,
+						signature: signatures[0]
+					})
+					.unwrap_err(),
+					InvalidTransaction::BadProof.into()
+				);
+			});
+	}
+
+	#[test]
+	fn failed_batch_is_retried_together() {
+		new_test_ext()
+			.with_authorities(AUTHORITIES)
+			.with_nominees(NOMINEES)
+			.execute_with_consistency_checks(|| {
+				let request_ids = request_signatures();
+				let ceremony_id = current_ceremony_id() - 1;
+
+				let retry_block = System::current_block_number() +
+					EthereumThresholdSigner::threshold_signature_response_timeout();
+				System::set_block_number(retry_block);
+				<EthereumThresholdSigner as Hooks<BlockNumberFor<Test>>>::on_initialize(
+					retry_block,
+				);
+
+				assert!(PendingCeremonies::<Test, Instance1>::get(ceremony_id).is_none());
+				let retry_ceremony_id = (ceremony_id + 2..=current_ceremony_id())
+					.find(|id| {
+						PendingCeremonies::<Test, Instance1>::get(id)
+							.is_some_and(|context| context.request_contexts.len() > 1)
+					})
+					.expect("The batch should have been retried");
+				let CeremonyContext::<Test, Instance1> { request_contexts, .. } =
+					PendingCeremonies::<Test, Instance1>::get(retry_ceremony_id).unwrap();
+				assert_eq!(
+					request_contexts
+						.iter()
+						.map(|context| (context.request_id, context.attempt_count))
+						.collect::<Vec<_>>(),
+					request_ids[..BATCH_SIZE as usize]
+						.iter()
+						.map(|request_id| (*request_id, 1))
+						.collect::<Vec<_>>()
+				);
+			});
+	}
+
+	#[test]
+	fn failure_reports_are_processed_for_every_request_in_the_batch() {
+		new_test_ext()
+			.with_authorities(AUTHORITIES)
+			.with_nominees(NOMINEES)
+			.execute_with_consistency_checks(|| {
+				let request_ids = request_signatures();
+				let ceremony_id = current_ceremony_id() - 1;
+
+				assert_ok!(EthereumThresholdSigner::report_signature_failed(
+					RuntimeOrigin::signed(NOMINEES[0]),
+					ceremony_id,
+					BTreeSet::from_iter([NOMINEES[1]]),
+				));
+
+				for request_id in &request_ids[..BATCH_SIZE as usize] {
+					assert!(System::events().iter().any(|record| record.event ==
+						RuntimeEvent::EthereumThresholdSigner(
+							PalletEvent::FailureReportProcessed {
+								request_id: *request_id,
+								ceremony_id,
+								reporter_id: NOMINEES[0],
+							}
+						)));
+				}
+			});
+	}
+
+	#[test]
+	fn keygen_verification_is_not_batched() {
+		new_test_ext()
+			.with_authorities(AUTHORITIES)
+			.with_nominees(NOMINEES)
+			.execute_with_consistency_checks(|| {
+				MaxSigningBatchSize::set(BATCH_SIZE);
+
+				let request_id = EthereumThresholdSigner::request_verification_signature(
+					PAYLOADS[0],
+					BTreeSet::from_iter(NOMINEES),
+					current_agg_key(),
+					0,
+					MockCallback::new,
+				);
+
+				// The ceremony is started immediately.
+				assert_eq!(single_request_context(current_ceremony_id()).request_id, request_id);
+			});
+	}
+}
+
 #[cfg(test)]
 mod failure_reporting {
 	use super::*;
@@ -689,7 +901,11 @@ mod failure_reporting {
 		const PAYLOAD: <MockEthereumChainCrypto as ChainCrypto>::Payload = *b"OHAI";
 		MockEpochInfo::set_authorities(validator_set.into_iter().collect());
 		CeremonyContext {
-			request_context: RequestContext { request_id: 1, attempt_count: 0, payload: PAYLOAD },
+			request_contexts: vec![RequestContext {
+				request_id: 1,
+				attempt_count: 0,
+				payload: PAYLOAD,
+			}],
 			threshold_ceremony_type: ThresholdCeremonyType::Standard,
 			epoch: 0,
 			key: MockAggKey(AGG_KEY),
@@ -735,3 +951,339 @@ mod failure_reporting {
 		assert_eq!(ctx.offenders(), vec![1], "Context was {ctx:?}.");
 	}
 }
+
+mod failure_evidence {
+	use super::*;
+	use crate::{Event as PalletEvent, FailureEvidence, FailureEvidenceEntryCount};
+	use cf_primitives::{
+		broadcast_signing_payload, BlameReason, BroadcastSignature, CeremonyFailureEvidence,
+		ConflictingBroadcast, SignedValueHash,
+	};
+	use cf_traits::mocks::{
+		key_ceremony_participants::MockKeyCeremonyParticipants,
+		peer_signature_verifier::MockPeerSignatureVerifier,
+	};
+
+	const NOMINEES: [u64; 3] = [1, 2, 3];
+	const AUTHORITIES: [u64; 4] = [1, 2, 3, 4];
+
+	/// A value broadcast at stage 2 of the ceremony, signed by the broadcaster.
+	fn signed_value(
+		ceremony_id: CeremonyId,
+		broadcaster_id: u64,
+		hash: [u8; 32],
+	) -> SignedValueHash {
+		SignedValueHash {
+			hash,
+			signature: BroadcastSignature(MockPeerSignatureVerifier::sign(
+				broadcaster_id,
+				&broadcast_signing_payload(TargetChain::get(), ceremony_id, 2, &hash),
+			)),
+		}
+	}
+
+	/// Evidence that the first party (validator 1) broadcast one value to the given parties and
+	/// another to party 3.
+	fn inconsistent_broadcast_evidence(
+		ceremony_id: CeremonyId,
+		parties: Vec<u32>,
+	) -> CeremonyFailureEvidence {
+		CeremonyFailureEvidence {
+			reason: BlameReason::InconsistentBroadcast { stage: 2 },
+			conflicting_broadcasts: vec![ConflictingBroadcast {
+				broadcaster: 1,
+				received: vec![
+					(Some(signed_value(ceremony_id, 1, [1; 32])), parties),
+					(Some(signed_value(ceremony_id, 1, [2; 32])), vec![3]),
+				],
+			}],
+		}
+	}
+
+	#[test]
+	fn participants_can_report_evidence_once() {
+		new_test_ext()
+			.with_authorities(AUTHORITIES)
+			.with_nominees(NOMINEES)
+			.with_request(b"OHAI")
+			.execute_with_consistency_checks(|| {
+				let ceremony_id = current_ceremony_id();
+				let evidence = inconsistent_broadcast_evidence(ceremony_id, vec![1, 2]);
+
+				assert_ok!(EthereumThresholdSigner::report_failure_evidence(
+					RuntimeOrigin::signed(2),
+					ceremony_id,
+					evidence.clone(),
+				));
+				assert!(System::events().iter().any(|record| record.event ==
+					RuntimeEvent::EthereumThresholdSigner(
+						PalletEvent::FailureEvidenceReported { ceremony_id, reporter_id: 2 }
+					)));
+				assert_eq!(
+					EthereumThresholdSigner::failure_evidence(ceremony_id),
+					vec![(2, evidence.clone())]
+				);
+
+				assert_noop!(
+					EthereumThresholdSigner::report_failure_evidence(
+						RuntimeOrigin::signed(2),
+						ceremony_id,
+						evidence.clone(),
+					),
+					Error::<Test, Instance1>::DuplicateFailureEvidence
+				);
+
+				// Only participants of the ceremony can report evidence for it.
+				assert_noop!(
+					EthereumThresholdSigner::report_failure_evidence(
+						RuntimeOrigin::signed(4),
+						ceremony_id,
+						evidence,
+					),
+					Error::<Test, Instance1>::InvalidRespondent
+				);
+			});
+	}
+
+	#[test]
+	fn evidence_size_is_bounded() {
+		new_test_ext().with_authorities(AUTHORITIES).execute_with(|| {
+			let too_large = inconsistent_broadcast_evidence(
+				1,
+				(1..=MaxFailureEvidenceEntries::get()).collect(),
+			);
+			assert!(too_large.entry_count() > MaxFailureEvidenceEntries::get());
+
+			assert_noop!(
+				EthereumThresholdSigner::report_failure_evidence(
+					RuntimeOrigin::signed(1),
+					1,
+					too_large,
+				),
+				Error::<Test, Instance1>::FailureEvidenceTooLarge
+			);
+		});
+	}
+
+	#[test]
+	fn evidence_per_ceremony_is_bounded() {
+		new_test_ext()
+			.with_authorities(AUTHORITIES)
+			.with_nominees(NOMINEES)
+			.with_request(b"OHAI")
+			.execute_with_consistency_checks(|| {
+				let ceremony_id = current_ceremony_id();
+				let evidence = inconsistent_broadcast_evidence(ceremony_id, vec![1, 2, 3, 4, 5, 6]);
+				assert!(evidence.entry_count() <= MaxFailureEvidenceEntries::get());
+				assert!(evidence.entry_count() * 2 > MaxFailureEvidenceEntriesPerCeremony::get());
+
+				assert_ok!(EthereumThresholdSigner::report_failure_evidence(
+					RuntimeOrigin::signed(1),
+					ceremony_id,
+					evidence.clone(),
+				));
+				assert_noop!(
+					EthereumThresholdSigner::report_failure_evidence(
+						RuntimeOrigin::signed(2),
+						ceremony_id,
+						evidence,
+					),
+					Error::<Test, Instance1>::FailureEvidenceLimitReached
+				);
+
+				// Smaller evidence still fits.
+				assert_ok!(EthereumThresholdSigner::report_failure_evidence(
+					RuntimeOrigin::signed(2),
+					ceremony_id,
+					inconsistent_broadcast_evidence(ceremony_id, vec![1]),
+				));
+			});
+	}
+
+	#[test]
+	fn only_signed_conflicts_are_kept_as_evidence() {
+		new_test_ext()
+			.with_authorities(AUTHORITIES)
+			.with_nominees(NOMINEES)
+			.with_request(b"OHAI")
+			.execute_with_consistency_checks(|| {
+				let ceremony_id = current_ceremony_id();
+				let evidence_with = |conflicting_broadcasts| CeremonyFailureEvidence {
+					reason: BlameReason::InconsistentBroadcast { stage: 2 },
+					conflicting_broadcasts,
+				};
+
+				// A value signed by another party doesn't prove a conflict.
+				assert_noop!(
+					EthereumThresholdSigner::report_failure_evidence(
+						RuntimeOrigin::signed(2),
+						ceremony_id,
+						evidence_with(vec![ConflictingBroadcast {
+							broadcaster: 1,
+							received: vec![
+								(Some(signed_value(ceremony_id, 1, [1; 32])), vec![1, 2]),
+								(Some(signed_value(ceremony_id, 2, [2; 32])), vec![3]),
+							],
+						}]),
+					),
+					Error::<Test, Instance1>::InvalidFailureEvidence
+				);
+
+				// Neither does a value signed for another ceremony.
+				assert_noop!(
+					EthereumThresholdSigner::report_failure_evidence(
+						RuntimeOrigin::signed(2),
+						ceremony_id,
+						evidence_with(vec![ConflictingBroadcast {
+							broadcaster: 1,
+							received: vec![
+								(Some(signed_value(ceremony_id, 1, [1; 32])), vec![1, 2]),
+								(Some(signed_value(ceremony_id + 1, 1, [2; 32])), vec![3]),
+							],
+						}]),
+					),
+					Error::<Test, Instance1>::InvalidFailureEvidence
+				);
+
+				// The same value signed twice isn't a conflict.
+				assert_noop!(
+					EthereumThresholdSigner::report_failure_evidence(
+						RuntimeOrigin::signed(2),
+						ceremony_id,
+						evidence_with(vec![ConflictingBroadcast {
+							broadcaster: 1,
+							received: vec![
+								(Some(signed_value(ceremony_id, 1, [1; 32])), vec![1, 2]),
+								(Some(signed_value(ceremony_id, 1, [1; 32])), vec![3]),
+							],
+						}]),
+					),
+					Error::<Test, Instance1>::InvalidFailureEvidence
+				);
+
+				// Only the proven conflicts are stored.
+				let proven = ConflictingBroadcast {
+					broadcaster: 1,
+					received: vec![
+						(Some(signed_value(ceremony_id, 1, [1; 32])), vec![1, 2]),
+						(Some(signed_value(ceremony_id, 1, [2; 32])), vec![3]),
+						(None, vec![2]),
+					],
+				};
+				let mut forged = proven.clone();
+				forged.received.push((Some(signed_value(ceremony_id, 3, [3; 32])), vec![1]));
+				assert_ok!(EthereumThresholdSigner::report_failure_evidence(
+					RuntimeOrigin::signed(2),
+					ceremony_id,
+					evidence_with(vec![
+						forged,
+						ConflictingBroadcast {
+							broadcaster: 4,
+							received: vec![
+								(Some(signed_value(ceremony_id, 4, [1; 32])), vec![1]),
+								(Some(signed_value(ceremony_id, 4, [2; 32])), vec![2]),
+							],
+						},
+					]),
+				));
+				assert_eq!(
+					EthereumThresholdSigner::failure_evidence(ceremony_id),
+					vec![(2, evidence_with(vec![proven.clone()]))]
+				);
+				assert_eq!(
+					FailureEvidenceEntryCount::<Test, Instance1>::get(ceremony_id),
+					evidence_with(vec![proven.clone()]).entry_count()
+				);
+
+				// Other failures can't be supported by conflicting broadcasts.
+				assert_noop!(
+					EthereumThresholdSigner::report_failure_evidence(
+						RuntimeOrigin::signed(3),
+						ceremony_id,
+						CeremonyFailureEvidence {
+							reason: BlameReason::InvalidSignatureShare,
+							conflicting_broadcasts: vec![proven],
+						},
+					),
+					Error::<Test, Instance1>::InvalidFailureEvidence
+				);
+			});
+	}
+
+	#[test]
+	fn evidence_can_only_be_reported_for_pending_ceremonies() {
+		new_test_ext().with_authorities(AUTHORITIES).execute_with(|| {
+			assert_noop!(
+				EthereumThresholdSigner::report_failure_evidence(
+					RuntimeOrigin::signed(1),
+					100,
+					inconsistent_broadcast_evidence(100, vec![1]),
+				),
+				Error::<Test, Instance1>::InvalidCeremonyId
+			);
+		});
+	}
+
+	#[test]
+	fn evidence_expires_after_retention_period() {
+		new_test_ext().with_authorities(AUTHORITIES).execute_with(|| {
+			// Key ceremonies aren't tracked by this pallet, but their participants can also
+			// report evidence.
+			const KEYGEN_CEREMONY_ID: CeremonyId = 100;
+			MockKeyCeremonyParticipants::start_ceremony(KEYGEN_CEREMONY_ID, BTreeSet::from([1, 4]));
+			let evidence = CeremonyFailureEvidence {
+				reason: BlameReason::InvalidComplaint,
+				conflicting_broadcasts: vec![],
+			};
+			assert_noop!(
+				EthereumThresholdSigner::report_failure_evidence(
+					RuntimeOrigin::signed(2),
+					KEYGEN_CEREMONY_ID,
+					evidence.clone(),
+				),
+				Error::<Test, Instance1>::InvalidRespondent
+			);
+
+			let first_report_block = System::current_block_number();
+			assert_ok!(EthereumThresholdSigner::report_failure_evidence(
+				RuntimeOrigin::signed(1),
+				KEYGEN_CEREMONY_ID,
+				evidence.clone(),
+			));
+
+			// Later reports expire together with the first one.
+			System::set_block_number(first_report_block + 1);
+			assert_ok!(EthereumThresholdSigner::report_failure_evidence(
+				RuntimeOrigin::signed(4),
+				KEYGEN_CEREMONY_ID,
+				evidence,
+			));
+			assert_eq!(EthereumThresholdSigner::failure_evidence(KEYGEN_CEREMONY_ID).len(), 2);
+
+			// Evidence can't be reported once the ceremony is over.
+			MockKeyCeremonyParticipants::end_ceremony(KEYGEN_CEREMONY_ID);
+			assert_noop!(
+				EthereumThresholdSigner::report_failure_evidence(
+					RuntimeOrigin::signed(1),
+					KEYGEN_CEREMONY_ID,
+					CeremonyFailureEvidence {
+						reason: BlameReason::InvalidComplaint,
+						conflicting_broadcasts: vec![],
+					},
+				),
+				Error::<Test, Instance1>::InvalidCeremonyId
+			);
+
+			let expiry_block = first_report_block + FailureEvidenceRetentionPeriod::get();
+			<EthereumThresholdSigner as Hooks<BlockNumberFor<Test>>>::on_initialize(
+				expiry_block - 1,
+			);
+			assert_eq!(EthereumThresholdSigner::failure_evidence(KEYGEN_CEREMONY_ID).len(), 2);
+
+			<EthereumThresholdSigner as Hooks<BlockNumberFor<Test>>>::on_initialize(expiry_block);
+			assert!(EthereumThresholdSigner::failure_evidence(KEYGEN_CEREMONY_ID).is_empty());
+			assert_eq!(FailureEvidence::<Test, Instance1>::iter().count(), 0);
+			assert_eq!(FailureEvidenceEntryCount::<Test, Instance1>::iter().count(), 0);
+		});
+	}
+}
@@ -37,6 +37,8 @@ pub trait WeightInfo {
 	fn set_threshold_signature_timeout() -> Weight;
 	fn on_initialize(a: u32, r: u32, ) -> Weight;
 	fn report_offenders(o: u32, ) -> Weight;
+	fn report_failure_evidence(e: u32, ) -> Weight;
+	fn prune_failure_evidence(c: u32, r: u32, ) -> Weight;
 }
 
 /// Weights for pallet_cf_threshold_signature using the Substrate node and recommended hardware.
@@ -152,6 +154,54 @@ impl<T: frame_system::Config> WeightInfo for PalletWeight<T> {
 			.saturating_add(T::DbWeight::get().writes((1_u64).saturating_mul(o.into())))
 			.saturating_add(Weight::from_parts(0, 2475).saturating_mul(o.into()))
 	}
+	/// Storage: `AccountRoles::AccountRoles` (r:1 w:0)
+	/// Proof: `AccountRoles::AccountRoles` (`max_values`: None, `max_size`: Some(33), added: 2508, mode: `MaxEncodedLen`)
+	/// Storage: `EthereumThresholdSigner::PendingCeremonies` (r:1 w:0)
+	/// Proof: `EthereumThresholdSigner::PendingCeremonies` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `Validator::AccountPeerMapping` (r:1 w:0)
+	/// Proof: `Validator::AccountPeerMapping` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `EthereumThresholdSigner::FailureEvidence` (r:1 w:1)
+	/// Proof: `EthereumThresholdSigner::FailureEvidence` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `EthereumThresholdSigner::FailureEvidenceEntryCount` (r:1 w:1)
+	/// Proof: `EthereumThresholdSigner::FailureEvidenceEntryCount` (`max_values`: None, `max_size`: Some(20), added: 2495, mode: `MaxEncodedLen`)
+	/// Storage: `EthereumThresholdSigner::FailureEvidenceExpiries` (r:1 w:1)
+	/// Proof: `EthereumThresholdSigner::FailureEvidenceExpiries` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// The range of component `e` is `[1, 2000]`.
+	fn report_failure_evidence(e: u32, ) -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `5371`
+		//  Estimated: `8836`
+		// Minimum execution time: 58_914_000 picoseconds.
+		Weight::from_parts(61_382_447, 8836)
+			// Standard Error: 1_187
+			.saturating_add(Weight::from_parts(51_243_081, 0).saturating_mul(e.into()))
+			.saturating_add(T::DbWeight::get().reads(6_u64))
+			.saturating_add(T::DbWeight::get().writes(3_u64))
+	}
+	/// Storage: `EthereumThresholdSigner::FailureEvidenceExpiries` (r:1 w:1)
+	/// Proof: `EthereumThresholdSigner::FailureEvidenceExpiries` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `EthereumThresholdSigner::FailureEvidenceEntryCount` (r:0 w:50)
+	/// Proof: `EthereumThresholdSigner::FailureEvidenceEntryCount` (`max_values`: None, `max_size`: Some(20), added: 2495, mode: `MaxEncodedLen`)
+	/// Storage: `EthereumThresholdSigner::FailureEvidence` (r:0 w:7500)
+	/// Proof: `EthereumThresholdSigner::FailureEvidence` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// The range of component `c` is `[1, 50]`.
+	/// The range of component `r` is `[0, 7500]`.
+	fn prune_failure_evidence(c: u32, r: u32, ) -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `103 + c * (8 ±0)`
+		//  Estimated: `1589 + c * (8 ±0)`
+		// Minimum execution time: 9_417_000 picoseconds.
+		Weight::from_parts(5_284_902, 1589)
+			// Standard Error: 41_266
+			.saturating_add(Weight::from_parts(3_912_538, 0).saturating_mul(c.into()))
+			// Standard Error: 274
+			.saturating_add(Weight::from_parts(1_487_215, 0).saturating_mul(r.into()))
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+			.saturating_add(T::DbWeight::get().writes((1_u64).saturating_mul(c.into())))
+			.saturating_add(T::DbWeight::get().writes((1_u64).saturating_mul(r.into())))
+			.saturating_add(Weight::from_parts(0, 8).saturating_mul(c.into()))
+	}
 }
 
 // For backwards compatibility and tests
@@ -266,4 +316,52 @@ impl WeightInfo for () {
 			.saturating_add(RocksDbWeight::get().writes((1_u64).saturating_mul(o.into())))
 			.saturating_add(Weight::from_parts(0, 2475).saturating_mul(o.into()))
 	}
+	/// Storage: `AccountRoles::AccountRoles` (r:1 w:0)
+	/// Proof: `AccountRoles::AccountRoles` (`max_values`: None, `max_size`: Some(33), added: 2508, mode: `MaxEncodedLen`)
+	/// Storage: `EthereumThresholdSigner::PendingCeremonies` (r:1 w:0)
+	/// Proof: `EthereumThresholdSigner::PendingCeremonies` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `Validator::AccountPeerMapping` (r:1 w:0)
+	/// Proof: `Validator::AccountPeerMapping` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `EthereumThresholdSigner::FailureEvidence` (r:1 w:1)
+	/// Proof: `EthereumThresholdSigner::FailureEvidence` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `EthereumThresholdSigner::FailureEvidenceEntryCount` (r:1 w:1)
+	/// Proof: `EthereumThresholdSigner::FailureEvidenceEntryCount` (`max_values`: None, `max_size`: Some(20), added: 2495, mode: `MaxEncodedLen`)
+	/// Storage: `EthereumThresholdSigner::FailureEvidenceExpiries` (r:1 w:1)
+	/// Proof: `EthereumThresholdSigner::FailureEvidenceExpiries` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// The range of component `e` is `[1, 2000]`.
+	fn report_failure_evidence(e: u32, ) -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `5371`
+		//  Estimated: `8836`
+		// Minimum execution time: 58_914_000 picoseconds.
+		Weight::from_parts(61_382_447, 8836)
+			// Standard Error: 1_187
+			.saturating_add(Weight::from_parts(51_243_081, 0).saturating_mul(e.into()))
+			.saturating_add(RocksDbWeight::get().reads(6_u64))
+			.saturating_add(RocksDbWeight::get().writes(3_u64))
+	}
+	/// Storage: `EthereumThresholdSigner::FailureEvidenceExpiries` (r:1 w:1)
+	/// Proof: `EthereumThresholdSigner::FailureEvidenceExpiries` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `EthereumThresholdSigner::FailureEvidenceEntryCount` (r:0 w:50)
+	/// Proof: `EthereumThresholdSigner::FailureEvidenceEntryCount` (`max_values`: None, `max_size`: Some(20), added: 2495, mode: `MaxEncodedLen`)
+	/// Storage: `EthereumThresholdSigner::FailureEvidence` (r:0 w:7500)
+	/// Proof: `EthereumThresholdSigner::FailureEvidence` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// The range of component `c` is `[1, 50]`.
+	/// The range of component `r` is `[0, 7500]`.
+	fn prune_failure_evidence(c: u32, r: u32, ) -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `103 + c * (8 ±0)`
+		//  Estimated: `1589 + c * (8 ±0)`
+		// Minimum execution time: 9_417_000 picoseconds.
+		Weight::from_parts(5_284_902, 1589)
+			// Standard Error: 41_266
+			.saturating_add(Weight::from_parts(3_912_538, 0).saturating_mul(c.into()))
+			// Standard Error: 274
+			.saturating_add(Weight::from_parts(1_487_215, 0).saturating_mul(r.into()))
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+			.saturating_add(RocksDbWeight::get().writes((1_u64).saturating_mul(c.into())))
+			.saturating_add(RocksDbWeight::get().writes((1_u64).saturating_mul(r.into())))
+			.saturating_add(Weight::from_parts(0, 8).saturating_mul(c.into()))
+	}
 }
@@ -25,8 +25,8 @@ use cf_primitives::{
 use cf_traits::{
 	impl_pallet_safe_mode, offence_reporting::OffenceReporter, AsyncResult, AuthoritiesCfeVersions,
 	Bid, BidderProvider, Bonding, Chainflip, EpochInfo, EpochTransitionHandler, ExecutionCondition,
-	FundingInfo, HistoricalEpoch, MissedAuthorshipSlots, OnAccountFunded, QualifyNode,
-	ReputationResetter, SetSafeMode, VaultRotator,
+	FundingInfo, HistoricalEpoch, MissedAuthorshipSlots, OnAccountFunded, PeerSignatureVerifier,
+	QualifyNode, ReputationResetter, SetSafeMode, VaultRotator,
 };
 
 use cf_utilities::Port;
@@ -1358,6 +1358,22 @@ impl<T: Config> QualifyNode<<T as Chainflip>::ValidatorId> for PeerMapping<T> {
 	}
 }
 
+impl<T: Config> PeerSignatureVerifier for Pallet<T> {
+	type ValidatorId = ValidatorIdOf<T>;
+
+	fn verify_peer_signature(
+		validator_id: &Self::ValidatorId,
+		message: &[u8],
+		signature: &[u8; 64],
+	) -> bool {
+		use frame_support::sp_runtime::app_crypto::RuntimePublic;
+
+		AccountPeerMapping::<T>::get(validator_id.into_ref()).map_or(false, |(peer_id, _, _)| {
+			RuntimePublic::verify(&peer_id, &message, &Ed25519Signature::from_raw(*signature))
+		})
+	}
+}
+
 pub struct NotDuringRotation<T: Config>(PhantomData<T>);
 
 impl<T: Config> ExecutionCondition for NotDuringRotation<T> {
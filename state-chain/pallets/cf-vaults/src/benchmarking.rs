@@ -182,5 +182,36 @@ benchmarks_instance_pallet! {
 	verify {
 		assert_eq!(KeygenResponseTimeout::<T, I>::get(), new_timeout);
 	}
+	request_share_refresh {
+		Pallet::<T, I>::set_vault_key_for_epoch(
+			CurrentEpochIndex::<T>::get(),
+			Vault {
+				public_key: AggKeyFor::<T, I>::benchmark_value(),
+				active_from_block: ChainBlockNumberFor::<T, I>::from(0u32),
+			},
+		);
+		let call = Call::<T, I>::request_share_refresh {};
+	} : { call.dispatch_bypass_filter(T::EnsureGovernance::try_successful_origin().unwrap())? }
+	verify {
+		assert!(PendingShareRefresh::<T, I>::exists());
+	}
+	report_share_refresh_outcome {
+		let caller: T::AccountId = whitelisted_caller();
+		<T as frame_system::Config>::OnNewAccount::on_new_account(&caller);
+		T::AccountRoleRegistry::register_as_validator(&caller).unwrap();
+
+		let participants = generate_authority_set::<T, I>(150, caller.clone().into());
+		PendingShareRefresh::<T, I>::put(ShareRefreshStatus {
+			ceremony_id: CEREMONY_ID,
+			key: AggKeyFor::<T, I>::benchmark_value(),
+			response_status: ShareRefreshResponseStatus::<T, I>::new(participants),
+		});
+	} : _(RawOrigin::Signed(caller), CEREMONY_ID, KeygenOutcomeFor::<T, I>::Ok(AggKeyFor::<T, I>::benchmark_value()))
+	verify {
+		assert_eq!(
+			PendingShareRefresh::<T, I>::get().unwrap().response_status.remaining_candidate_count(),
+			149
+		);
+	}
 	impl_benchmark_test_suite!(Pallet, crate::mock::new_test_ext(), crate::mock::Test,);
 }
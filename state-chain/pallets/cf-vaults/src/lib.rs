@@ -9,8 +9,8 @@ use cf_primitives::{
 use cf_runtime_utilities::{EnumVariant, StorageDecodeVariant};
 use cf_traits::{
 	offence_reporting::OffenceReporter, AccountRoleRegistry, AsyncResult, Broadcaster, Chainflip,
-	CurrentEpochIndex, EpochKey, GetBlockHeight, KeyProvider, KeyState, SafeMode, SetSafeMode,
-	Slashing, ThresholdSigner, VaultKeyWitnessedHandler, VaultRotator, VaultStatus,
+	CurrentEpochIndex, EpochInfo, EpochKey, GetBlockHeight, KeyProvider, KeyState, SafeMode,
+	SetSafeMode, Slashing, ThresholdSigner, VaultKeyWitnessedHandler, VaultRotator, VaultStatus,
 };
 use frame_support::{
 	pallet_prelude::*,
@@ -63,6 +63,9 @@ pub type KeygenResponseStatus<T, I> =
 pub type KeyHandoverResponseStatus<T, I> =
 	ResponseStatus<T, KeyHandoverSuccessVoters<T, I>, KeyHandoverFailureVoters<T, I>, I>;
 
+pub type ShareRefreshResponseStatus<T, I> =
+	ResponseStatus<T, ShareRefreshSuccessVoters<T, I>, ShareRefreshFailureVoters<T, I>, I>;
+
 #[derive(Encode, Decode, MaxEncodedLen, TypeInfo, Copy, Clone, PartialEq, Eq, RuntimeDebug)]
 #[scale_info(skip_type_params(I))]
 pub struct PalletSafeMode<I: 'static> {
@@ -129,6 +132,16 @@ pub enum VaultRotationStatus<T: Config<I>, I: 'static = ()> {
 	},
 }
 
+/// A refresh of the current key holders' secret shares, which leaves the key itself unchanged.
+#[derive(PartialEq, Eq, Clone, Encode, Decode, TypeInfo, RuntimeDebugNoBound)]
+#[scale_info(skip_type_params(T, I))]
+pub struct ShareRefreshStatus<T: Config<I>, I: 'static = ()> {
+	pub ceremony_id: CeremonyId,
+	/// The key whose shares are being refreshed.
+	pub key: AggKeyFor<T, I>,
+	pub response_status: ShareRefreshResponseStatus<T, I>,
+}
+
 impl<T: Config<I>, I: 'static> cf_traits::CeremonyIdProvider for Pallet<T, I> {
 	fn increment_ceremony_id() -> CeremonyId {
 		CeremonyIdCounter::<T, I>::mutate(|id| {
@@ -138,6 +151,33 @@ impl<T: Config<I>, I: 'static> cf_traits::CeremonyIdProvider for Pallet<T, I> {
 	}
 }
 
+impl<T: Config<I>, I: 'static> cf_traits::KeyCeremonyParticipants for Pallet<T, I> {
+	type ValidatorId = T::ValidatorId;
+
+	fn pending_ceremony_participants(
+		ceremony_id: CeremonyId,
+	) -> Option<BTreeSet<Self::ValidatorId>> {
+		match PendingVaultRotation::<T, I>::get() {
+			Some(VaultRotationStatus::<T, I>::AwaitingKeygen {
+				ceremony_id: keygen_ceremony_id,
+				keygen_participants,
+				..
+			}) if keygen_ceremony_id == ceremony_id => Some(keygen_participants),
+			Some(VaultRotationStatus::<T, I>::AwaitingKeyHandover {
+				ceremony_id: handover_ceremony_id,
+				response_status,
+				..
+			}) if handover_ceremony_id == ceremony_id => Some(response_status.candidates().clone()),
+			_ => None,
+		}
+		.or_else(|| {
+			PendingShareRefresh::<T, I>::get()
+				.filter(|status| status.ceremony_id == ceremony_id)
+				.map(|status| status.response_status.candidates().clone())
+		})
+	}
+}
+
 /// A single vault.
 #[derive(Default, PartialEq, Eq, Clone, Encode, Decode, TypeInfo, RuntimeDebug)]
 pub struct Vault<T: Chain> {
@@ -151,6 +191,7 @@ pub struct Vault<T: Chain> {
 pub enum PalletOffence {
 	FailedKeygen,
 	FailedKeyHandover,
+	FailedShareRefresh,
 }
 
 #[derive(Encode, Decode, TypeInfo)]
@@ -223,7 +264,45 @@ pub mod pallet {
 	#[pallet::hooks]
 	impl<T: Config<I>, I: 'static> Hooks<BlockNumberFor<T>> for Pallet<T, I> {
 		fn on_initialize(current_block: BlockNumberFor<T>) -> frame_support::weights::Weight {
-			let mut weight = T::DbWeight::get().reads(1);
+			let mut weight = T::DbWeight::get().reads(2);
+
+			if let Some(ShareRefreshStatus { ceremony_id, key, response_status }) =
+				PendingShareRefresh::<T, I>::get()
+			{
+				weight += Self::progress_rotation::<
+					ShareRefreshSuccessVoters<T, I>,
+					ShareRefreshFailureVoters<T, I>,
+					ShareRefreshResolutionPendingSince<T, I>,
+				>(
+					response_status,
+					ceremony_id,
+					current_block,
+					// The refreshed shares must be for the same key
+					|reported_key| {
+						if reported_key == key {
+							Ok(reported_key)
+						} else {
+							log::error!(
+								"Share refresh resulted in an unexpected key: {:?}",
+								&reported_key
+							);
+							Err(Default::default())
+						}
+					},
+					|_| {
+						PendingShareRefresh::<T, I>::kill();
+						Self::deposit_event(Event::ShareRefreshSuccess { ceremony_id });
+					},
+					|offenders| {
+						T::OffenceReporter::report_many(
+							PalletOffence::FailedShareRefresh,
+							offenders.into_iter().collect::<Vec<_>>().as_slice(),
+						);
+						PendingShareRefresh::<T, I>::kill();
+						Self::deposit_event(Event::ShareRefreshFailure { ceremony_id });
+					},
+				);
+			}
 
 			// We don't need self, we can get our own data.
 			if Self::status() != AsyncResult::Pending {
@@ -397,14 +476,38 @@ pub mod pallet {
 	pub(super) type KeyHandoverResolutionPendingSince<T: Config<I>, I: 'static = ()> =
 		StorageValue<_, BlockNumberFor<T>, ValueQuery>;
 
+	/// The share refresh that is currently in progress, if any.
+	#[pallet::storage]
+	#[pallet::getter(fn pending_share_refresh)]
+	pub type PendingShareRefresh<T: Config<I>, I: 'static = ()> =
+		StorageValue<_, ShareRefreshStatus<T, I>>;
+
+	/// The voters who voted for success for a particular share refresh ceremony
+	#[pallet::storage]
+	#[pallet::getter(fn share_refresh_success_voters)]
+	pub type ShareRefreshSuccessVoters<T: Config<I>, I: 'static = ()> =
+		StorageMap<_, Identity, AggKeyFor<T, I>, Vec<T::ValidatorId>, ValueQuery>;
+
+	/// The voters who voted for failure for a particular share refresh ceremony
+	#[pallet::storage]
+	#[pallet::getter(fn share_refresh_failure_voters)]
+	pub type ShareRefreshFailureVoters<T: Config<I>, I: 'static = ()> =
+		StorageValue<_, Vec<T::ValidatorId>, ValueQuery>;
+
+	/// The block since which we have been waiting for the share refresh to be resolved.
+	#[pallet::storage]
+	#[pallet::getter(fn share_refresh_resolution_pending_since)]
+	pub(super) type ShareRefreshResolutionPendingSince<T: Config<I>, I: 'static = ()> =
+		StorageValue<_, BlockNumberFor<T>, ValueQuery>;
+
 	#[pallet::storage]
-	pub(super) type KeygenResponseTimeout<T: Config<I>, I: 'static = ()> =
+	pub type KeygenResponseTimeout<T: Config<I>, I: 'static = ()> =
 		StorageValue<_, BlockNumberFor<T>, ValueQuery>;
 
 	/// The amount of FLIP that is slashed for an agreed reported party expressed in Flipperinos
 	/// (2/3 must agree the node was an offender) on keygen failure.
 	#[pallet::storage]
-	pub(super) type KeygenSlashAmount<T, I = ()> = StorageValue<_, FlipBalance, ValueQuery>;
+	pub type KeygenSlashAmount<T, I = ()> = StorageValue<_, FlipBalance, ValueQuery>;
 
 	/// Counter for generating unique ceremony ids.
 	#[pallet::storage]
@@ -489,6 +592,28 @@ pub mod pallet {
 		},
 		/// The vault rotation has been aborted early.
 		VaultRotationAborted,
+		/// Request a refresh of the secret shares of the current key. The key itself doesn't
+		/// change.
+		ShareRefreshRequest {
+			ceremony_id: CeremonyId,
+			/// The epoch index for which the key was generated.
+			epoch_index: EpochIndex,
+			key: <<T::Chain as Chain>::ChainCrypto as ChainCrypto>::AggKey,
+			participants: BTreeSet<T::ValidatorId>,
+		},
+		/// A share refresh participant has reported that the refresh was successful
+		/// \[validator_id\]
+		ShareRefreshSuccessReported(T::ValidatorId),
+		/// A share refresh participant has reported that the refresh has failed \[validator_id\]
+		ShareRefreshFailureReported(T::ValidatorId),
+		/// All participants have refreshed their shares, which replace the previous shares.
+		ShareRefreshSuccess {
+			ceremony_id: CeremonyId,
+		},
+		/// The share refresh has failed, and the previous shares remain in use.
+		ShareRefreshFailure {
+			ceremony_id: CeremonyId,
+		},
 	}
 
 	#[pallet::error]
@@ -504,6 +629,13 @@ pub mod pallet {
 		InvalidRespondent,
 		/// There is no threshold signature available
 		ThresholdSignatureUnavailable,
+		/// There is already a share refresh in progress for this chain.
+		ShareRefreshInProgress,
+		/// There is currently no share refresh in progress for this chain.
+		NoActiveShareRefresh,
+		/// Shares can only be refreshed for the current authorities' key, outside of a vault
+		/// rotation.
+		ShareRefreshUnavailable,
 	}
 
 	macro_rules! handle_key_ceremony_report {
@@ -531,27 +663,13 @@ pub mod pallet {
 				Error::<T, I>::InvalidRespondent
 			);
 
-			Self::deposit_event(match $reported_outcome {
-				Ok(key) => {
-					response_status.add_success_vote(&reporter, key);
+			Self::deposit_event(
+				if Self::add_outcome_vote(response_status, &reporter, $ceremony_id, $reported_outcome) {
 					$success_event(reporter)
-				},
-				Err(offenders) => {
-					// Remove any offenders that are not part of the ceremony and log them
-					let (valid_blames, invalid_blames): (BTreeSet<_>, BTreeSet<_>) =
-					offenders.into_iter().partition(|id| response_status.candidates().contains(id));
-					if !invalid_blames.is_empty() {
-						log::warn!(
-							"Invalid offenders reported {:?} for ceremony {}.",
-							invalid_blames,
-							$ceremony_id
-						);
-					}
-
-					response_status.add_failure_vote(&reporter, valid_blames);
+				} else {
 					$failure_event(reporter)
-				},
-			});
+				}
+			);
 
 			PendingVaultRotation::<T, I>::put(rotation);
 		};
@@ -755,6 +873,110 @@ pub mod pallet {
 
 			Ok(().into())
 		}
+
+		/// Refresh the current authorities' secret shares of the current key. The key itself
+		/// doesn't change, so no key update is required on the external chain, but any share
+		/// leaked before the refresh can no longer be used to sign.
+		///
+		/// ## Events
+		///
+		/// - [ShareRefreshRequest](Event::ShareRefreshRequest)
+		///
+		/// ## Errors
+		///
+		/// - [BadOrigin](frame_support::error::BadOrigin)
+		/// - [ShareRefreshInProgress](Error::ShareRefreshInProgress)
+		/// - [ShareRefreshUnavailable](Error::ShareRefreshUnavailable)
+		#[pallet::call_index(8)]
+		#[pallet::weight(T::WeightInfo::request_share_refresh())]
+		pub fn request_share_refresh(origin: OriginFor<T>) -> DispatchResultWithPostInfo {
+			T::EnsureGovernance::ensure_origin(origin)?;
+
+			ensure!(!PendingShareRefresh::<T, I>::exists(), Error::<T, I>::ShareRefreshInProgress);
+			ensure!(
+				matches!(
+					PendingVaultRotation::<T, I>::get(),
+					None | Some(VaultRotationStatus::<T, I>::Complete)
+				),
+				Error::<T, I>::ShareRefreshUnavailable
+			);
+			let EpochKey { key, epoch_index, .. } =
+				Self::active_epoch_key().ok_or(Error::<T, I>::ShareRefreshUnavailable)?;
+			// Only the current authorities hold shares of the key.
+			ensure!(
+				epoch_index == CurrentEpochIndex::<T>::get(),
+				Error::<T, I>::ShareRefreshUnavailable
+			);
+
+			let participants = T::EpochInfo::current_authorities();
+			let ceremony_id = <Self as cf_traits::CeremonyIdProvider>::increment_ceremony_id();
+			PendingShareRefresh::<T, I>::put(ShareRefreshStatus {
+				ceremony_id,
+				key,
+				response_status: ShareRefreshResponseStatus::new(participants.clone()),
+			});
+			ShareRefreshResolutionPendingSince::<T, I>::put(
+				frame_system::Pallet::<T>::block_number(),
+			);
+
+			Self::deposit_event(Event::ShareRefreshRequest {
+				ceremony_id,
+				epoch_index,
+				key,
+				participants,
+			});
+
+			Ok(().into())
+		}
+
+		/// Report the outcome of a share refresh ceremony. A successful outcome must report the
+		/// key whose shares were refreshed.
+		///
+		/// ## Events
+		///
+		/// - [ShareRefreshSuccessReported](Event::ShareRefreshSuccessReported)
+		/// - [ShareRefreshFailureReported](Event::ShareRefreshFailureReported)
+		///
+		/// ## Errors
+		///
+		/// - [NoActiveShareRefresh](Error::NoActiveShareRefresh)
+		/// - [InvalidCeremonyId](Error::InvalidCeremonyId)
+		/// - [InvalidRespondent](Error::InvalidRespondent)
+		#[pallet::call_index(9)]
+		#[pallet::weight(T::WeightInfo::report_share_refresh_outcome())]
+		pub fn report_share_refresh_outcome(
+			origin: OriginFor<T>,
+			ceremony_id: CeremonyId,
+			reported_outcome: KeygenOutcomeFor<T, I>,
+		) -> DispatchResultWithPostInfo {
+			let reporter = T::AccountRoleRegistry::ensure_validator(origin)?.into();
+
+			let mut share_refresh =
+				PendingShareRefresh::<T, I>::get().ok_or(Error::<T, I>::NoActiveShareRefresh)?;
+
+			ensure!(share_refresh.ceremony_id == ceremony_id, Error::<T, I>::InvalidCeremonyId);
+			ensure!(
+				share_refresh.response_status.remaining_candidates().contains(&reporter),
+				Error::<T, I>::InvalidRespondent
+			);
+
+			Self::deposit_event(
+				if Self::add_outcome_vote(
+					&mut share_refresh.response_status,
+					&reporter,
+					ceremony_id,
+					reported_outcome,
+				) {
+					Event::ShareRefreshSuccessReported(reporter)
+				} else {
+					Event::ShareRefreshFailureReported(reporter)
+				},
+			);
+
+			PendingShareRefresh::<T, I>::put(share_refresh);
+
+			Ok(().into())
+		}
 	}
 
 	#[pallet::genesis_config]
@@ -795,6 +1017,45 @@ pub mod pallet {
 }
 
 impl<T: Config<I>, I: 'static> Pallet<T, I> {
+	/// Records a participant's reported outcome of a key ceremony. Returns whether the reported
+	/// outcome was a success.
+	fn add_outcome_vote<SuccessVoters, FailureVoters>(
+		response_status: &mut ResponseStatus<T, SuccessVoters, FailureVoters, I>,
+		reporter: &T::ValidatorId,
+		ceremony_id: CeremonyId,
+		reported_outcome: KeygenOutcomeFor<T, I>,
+	) -> bool
+	where
+		SuccessVoters: frame_support::StorageMap<AggKeyFor<T, I>, Vec<T::ValidatorId>>
+			+ frame_support::IterableStorageMap<AggKeyFor<T, I>, Vec<T::ValidatorId>>
+			+ frame_support::StoragePrefixedMap<Vec<T::ValidatorId>>,
+		FailureVoters: frame_support::StorageValue<Vec<T::ValidatorId>>,
+		<FailureVoters as frame_support::StorageValue<Vec<T::ValidatorId>>>::Query:
+			sp_std::iter::IntoIterator<Item = T::ValidatorId>,
+	{
+		match reported_outcome {
+			Ok(key) => {
+				response_status.add_success_vote(reporter, key);
+				true
+			},
+			Err(offenders) => {
+				// Remove any offenders that are not part of the ceremony and log them
+				let (valid_blames, invalid_blames): (BTreeSet<_>, BTreeSet<_>) =
+					offenders.into_iter().partition(|id| response_status.candidates().contains(id));
+				if !invalid_blames.is_empty() {
+					log::warn!(
+						"Invalid offenders reported {:?} for ceremony {}.",
+						invalid_blames,
+						ceremony_id
+					);
+				}
+
+				response_status.add_failure_vote(reporter, valid_blames);
+				false
+			},
+		}
+	}
+
 	fn progress_rotation<SuccessVoters, FailureVoters, PendingSince>(
 		response_status: ResponseStatus<T, SuccessVoters, FailureVoters, I>,
 		ceremony_id: CeremonyId,
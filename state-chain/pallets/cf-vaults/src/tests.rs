@@ -1269,3 +1269,165 @@ fn can_recover_from_abort_vault_rotation_after_key_handover_failed() {
 		do_full_key_rotation();
 	});
 }
+
+mod share_refresh {
+	use super::*;
+	use crate::{PendingShareRefresh, ShareRefreshResolutionPendingSince};
+
+	fn request_share_refresh() -> CeremonyId {
+		assert_ok!(VaultsPallet::request_share_refresh(RuntimeOrigin::root()));
+		let ceremony_id = current_ceremony_id();
+		assert_eq!(
+			last_event::<Test>(),
+			RuntimeEvent::VaultsPallet(PalletEvent::ShareRefreshRequest {
+				ceremony_id,
+				epoch_index: GENESIS_EPOCH,
+				key: GENESIS_AGG_PUB_KEY,
+				participants: BTreeSet::from_iter(ALL_CANDIDATES.iter().cloned()),
+			})
+		);
+		ceremony_id
+	}
+
+	#[test]
+	fn share_refresh_success_keeps_the_key() {
+		new_test_ext().execute_with(|| {
+			let ceremony_id = request_share_refresh();
+
+			for candidate in ALL_CANDIDATES {
+				assert_ok!(VaultsPallet::report_share_refresh_outcome(
+					RuntimeOrigin::signed(*candidate),
+					ceremony_id,
+					Ok(GENESIS_AGG_PUB_KEY),
+				));
+			}
+			VaultsPallet::on_initialize(2);
+
+			assert_last_event!(crate::Event::ShareRefreshSuccess { .. });
+			assert!(!PendingShareRefresh::<Test, _>::exists());
+			assert_eq!(VaultsPallet::active_epoch_key().unwrap().key, GENESIS_AGG_PUB_KEY);
+			assert_eq!(<VaultsPallet as VaultRotator>::status(), AsyncResult::Void);
+		});
+	}
+
+	#[test]
+	fn share_refresh_fails_if_the_key_changes() {
+		new_test_ext().execute_with(|| {
+			let ceremony_id = request_share_refresh();
+
+			for candidate in ALL_CANDIDATES {
+				assert_ok!(VaultsPallet::report_share_refresh_outcome(
+					RuntimeOrigin::signed(*candidate),
+					ceremony_id,
+					Ok(NEW_AGG_PUB_KEY_PRE_HANDOVER),
+				));
+			}
+			VaultsPallet::on_initialize(2);
+
+			assert_last_event!(crate::Event::ShareRefreshFailure { .. });
+			assert!(!PendingShareRefresh::<Test, _>::exists());
+		});
+	}
+
+	#[test]
+	fn share_refresh_failure_reports_offenders() {
+		new_test_ext().execute_with(|| {
+			let ceremony_id = request_share_refresh();
+
+			for candidate in [ALICE, BOB] {
+				assert_ok!(VaultsPallet::report_share_refresh_outcome(
+					RuntimeOrigin::signed(candidate),
+					ceremony_id,
+					Err(BTreeSet::from_iter([CHARLIE])),
+				));
+			}
+			assert_ok!(VaultsPallet::report_share_refresh_outcome(
+				RuntimeOrigin::signed(CHARLIE),
+				ceremony_id,
+				Ok(GENESIS_AGG_PUB_KEY),
+			));
+			VaultsPallet::on_initialize(2);
+
+			assert_last_event!(crate::Event::ShareRefreshFailure { .. });
+			MockOffenceReporter::assert_reported(PalletOffence::FailedShareRefresh, vec![CHARLIE]);
+		});
+	}
+
+	#[test]
+	fn share_refresh_timeout_period() {
+		new_test_ext().execute_with(|| {
+			request_share_refresh();
+			test_key_ceremony_timeout_period::<ShareRefreshResolutionPendingSince<Test, _>, _>(
+				VaultsPallet::report_share_refresh_outcome,
+			);
+			assert!(!PendingShareRefresh::<Test, _>::exists());
+		});
+	}
+
+	#[test]
+	fn only_candidates_can_report_once_for_the_pending_ceremony() {
+		new_test_ext().execute_with(|| {
+			assert_noop!(
+				VaultsPallet::report_share_refresh_outcome(
+					RuntimeOrigin::signed(ALICE),
+					1,
+					Ok(GENESIS_AGG_PUB_KEY),
+				),
+				Error::<Test, _>::NoActiveShareRefresh
+			);
+
+			let ceremony_id = request_share_refresh();
+
+			assert_noop!(
+				VaultsPallet::report_share_refresh_outcome(
+					RuntimeOrigin::signed(ALICE),
+					ceremony_id + 1,
+					Ok(GENESIS_AGG_PUB_KEY),
+				),
+				Error::<Test, _>::InvalidCeremonyId
+			);
+			assert_ok!(VaultsPallet::report_share_refresh_outcome(
+				RuntimeOrigin::signed(ALICE),
+				ceremony_id,
+				Ok(GENESIS_AGG_PUB_KEY),
+			));
+			assert_noop!(
+				VaultsPallet::report_share_refresh_outcome(
+					RuntimeOrigin::signed(ALICE),
+					ceremony_id,
+					Ok(GENESIS_AGG_PUB_KEY),
+				),
+				Error::<Test, _>::InvalidRespondent
+			);
+		});
+	}
+
+	#[test]
+	fn cannot_refresh_shares_during_a_rotation_or_another_refresh() {
+		new_test_ext().execute_with(|| {
+			request_share_refresh();
+			assert_noop!(
+				VaultsPallet::request_share_refresh(RuntimeOrigin::root()),
+				Error::<Test, _>::ShareRefreshInProgress
+			);
+		});
+
+		new_test_ext().execute_with(|| {
+			<VaultsPallet as VaultRotator>::keygen(
+				BTreeSet::from_iter(ALL_CANDIDATES.iter().cloned()),
+				GENESIS_EPOCH + 1,
+			);
+			assert_noop!(
+				VaultsPallet::request_share_refresh(RuntimeOrigin::root()),
+				Error::<Test, _>::ShareRefreshUnavailable
+			);
+		});
+
+		new_test_ext_no_key().execute_with(|| {
+			assert_noop!(
+				VaultsPallet::request_share_refresh(RuntimeOrigin::root()),
+				Error::<Test, _>::ShareRefreshUnavailable
+			);
+		});
+	}
+}
@@ -39,6 +39,8 @@ pub trait WeightInfo {
 	fn vault_key_rotated() -> Weight;
 	fn vault_key_rotated_externally() -> Weight;
 	fn set_keygen_response_timeout() -> Weight;
+	fn request_share_refresh() -> Weight;
+	fn report_share_refresh_outcome() -> Weight;
 }
 
 /// Weights for pallet_cf_vaults using the Substrate node and recommended hardware.
@@ -179,6 +181,46 @@ impl<T: frame_system::Config> WeightInfo for PalletWeight<T> {
 			.saturating_add(T::DbWeight::get().reads(1_u64))
 			.saturating_add(T::DbWeight::get().writes(1_u64))
 	}
+	/// Storage: `EthereumVault::PendingShareRefresh` (r:1 w:1)
+	/// Proof: `EthereumVault::PendingShareRefresh` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `EthereumVault::PendingVaultRotation` (r:1 w:0)
+	/// Proof: `EthereumVault::PendingVaultRotation` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `EthereumVault::CurrentVaultEpochAndState` (r:1 w:0)
+	/// Proof: `EthereumVault::CurrentVaultEpochAndState` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `EthereumVault::Vaults` (r:1 w:0)
+	/// Proof: `EthereumVault::Vaults` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `Validator::CurrentEpoch` (r:1 w:0)
+	/// Proof: `Validator::CurrentEpoch` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `Validator::CurrentAuthorities` (r:1 w:0)
+	/// Proof: `Validator::CurrentAuthorities` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `EthereumVault::CeremonyIdCounter` (r:1 w:1)
+	/// Proof: `EthereumVault::CeremonyIdCounter` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `EthereumVault::ShareRefreshResolutionPendingSince` (r:0 w:1)
+	/// Proof: `EthereumVault::ShareRefreshResolutionPendingSince` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	fn request_share_refresh() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `6041`
+		//  Estimated: `11981`
+		// Minimum execution time: 41_338_000 picoseconds.
+		Weight::from_parts(42_505_000, 11981)
+			.saturating_add(T::DbWeight::get().reads(7_u64))
+			.saturating_add(T::DbWeight::get().writes(3_u64))
+	}
+	/// Storage: `AccountRoles::AccountRoles` (r:1 w:0)
+	/// Proof: `AccountRoles::AccountRoles` (`max_values`: None, `max_size`: Some(33), added: 2508, mode: `MaxEncodedLen`)
+	/// Storage: `EthereumVault::PendingShareRefresh` (r:1 w:1)
+	/// Proof: `EthereumVault::PendingShareRefresh` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `EthereumVault::ShareRefreshSuccessVoters` (r:1 w:1)
+	/// Proof: `EthereumVault::ShareRefreshSuccessVoters` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	fn report_share_refresh_outcome() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `5889`
+		//  Estimated: `11829`
+		// Minimum execution time: 53_712_000 picoseconds.
+		Weight::from_parts(55_104_000, 11829)
+			.saturating_add(T::DbWeight::get().reads(3_u64))
+			.saturating_add(T::DbWeight::get().writes(2_u64))
+	}
 }
 
 // For backwards compatibility and tests
@@ -318,4 +360,44 @@ impl WeightInfo for () {
 			.saturating_add(RocksDbWeight::get().reads(1_u64))
 			.saturating_add(RocksDbWeight::get().writes(1_u64))
 	}
+	/// Storage: `EthereumVault::PendingShareRefresh` (r:1 w:1)
+	/// Proof: `EthereumVault::PendingShareRefresh` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `EthereumVault::PendingVaultRotation` (r:1 w:0)
+	/// Proof: `EthereumVault::PendingVaultRotation` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `EthereumVault::CurrentVaultEpochAndState` (r:1 w:0)
+	/// Proof: `EthereumVault::CurrentVaultEpochAndState` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `EthereumVault::Vaults` (r:1 w:0)
+	/// Proof: `EthereumVault::Vaults` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `Validator::CurrentEpoch` (r:1 w:0)
+	/// Proof: `Validator::CurrentEpoch` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `Validator::CurrentAuthorities` (r:1 w:0)
+	/// Proof: `Validator::CurrentAuthorities` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `EthereumVault::CeremonyIdCounter` (r:1 w:1)
+	/// Proof: `EthereumVault::CeremonyIdCounter` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `EthereumVault::ShareRefreshResolutionPendingSince` (r:0 w:1)
+	/// Proof: `EthereumVault::ShareRefreshResolutionPendingSince` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	fn request_share_refresh() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `6041`
+		//  Estimated: `11981`
+		// Minimum execution time: 41_338_000 picoseconds.
+		Weight::from_parts(42_505_000, 11981)
+			.saturating_add(RocksDbWeight::get().reads(7_u64))
+			.saturating_add(RocksDbWeight::get().writes(3_u64))
+	}
+	/// Storage: `AccountRoles::AccountRoles` (r:1 w:0)
+	/// Proof: `AccountRoles::AccountRoles` (`max_values`: None, `max_size`: Some(33), added: 2508, mode: `MaxEncodedLen`)
+	/// Storage: `EthereumVault::PendingShareRefresh` (r:1 w:1)
+	/// Proof: `EthereumVault::PendingShareRefresh` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `EthereumVault::ShareRefreshSuccessVoters` (r:1 w:1)
+	/// Proof: `EthereumVault::ShareRefreshSuccessVoters` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	fn report_share_refresh_outcome() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `5889`
+		//  Estimated: `11829`
+		// Minimum execution time: 53_712_000 picoseconds.
+		Weight::from_parts(55_104_000, 11829)
+			.saturating_add(RocksDbWeight::get().reads(3_u64))
+			.saturating_add(RocksDbWeight::get().writes(2_u64))
+	}
 }
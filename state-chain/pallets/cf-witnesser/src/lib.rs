@@ -14,7 +14,7 @@ mod mock;
 mod tests;
 
 use bitvec::prelude::*;
-use cf_primitives::EpochIndex;
+use cf_primitives::{AuthorityCount, EpochIndex};
 use cf_traits::{AccountRoleRegistry, CallDispatchFilter, Chainflip, EpochInfo, SafeMode};
 use cf_utilities::success_threshold_from_share_count;
 use codec::{Decode, Encode, MaxEncodedLen};
@@ -481,6 +481,14 @@ impl<T: Config> Pallet<T> {
 	}
 }
 
+/// Whether the authority with the given index voted, according to the bitmask of votes stored in
+/// [`Votes`].
+pub fn has_voted(votes: &[u8], authority_index: AuthorityCount) -> bool {
+	VoteMask::from_slice(votes)
+		.get(authority_index as usize)
+		.map_or(false, |vote| *vote)
+}
+
 impl<T: pallet::Config> cf_traits::EpochTransitionHandler for Pallet<T> {
 	/// Add the expired epoch to the queue to have its data culled. This is prevent the storage from
 	/// growing indefinitely.
@@ -1,6 +1,7 @@
 #![cfg(test)]
 
 use crate::{
+	has_voted,
 	mock::{dummy::pallet as pallet_dummy, *},
 	weights::WeightInfo,
 	CallHash, CallHashExecuted, Config, EpochsToCull, Error, ExtraCallData, PalletSafeMode,
@@ -114,6 +115,30 @@ fn cannot_double_witness() {
 	});
 }
 
+#[test]
+fn votes_can_be_read_with_has_voted() {
+	new_test_ext().execute_with(|| {
+		let call = Box::new(RuntimeCall::Dummy(pallet_dummy::Call::<Test>::increment_value {}));
+		let current_epoch = MockEpochInfo::epoch_index();
+
+		assert_ok!(Witnesser::witness_at_epoch(
+			RuntimeOrigin::signed(ALISSA),
+			call.clone(),
+			current_epoch
+		));
+
+		let call_hash = CallHash(frame_support::Hashable::blake2_256(&*call));
+		let votes = Votes::<Test>::get(current_epoch, call_hash).unwrap();
+		let authority_index =
+			|account| MockEpochInfo::authority_index(current_epoch, &account).unwrap();
+
+		assert!(has_voted(&votes, authority_index(ALISSA)));
+		assert!(!has_voted(&votes, authority_index(BOBSON)));
+		assert!(!has_voted(&votes, authority_index(CHARLEMAGNE)));
+		assert!(!has_voted(&votes, 8));
+	});
+}
+
 #[test]
 fn only_authorities_can_witness() {
 	new_test_ext().execute_with(|| {
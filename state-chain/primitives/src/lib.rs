@@ -90,6 +90,135 @@ pub struct TxId {
 /// The very first epoch number
 pub const GENESIS_EPOCH: u32 = 1;
 
+/// The maximum number of threshold signature requests that are signed together in a single
+/// signing ceremony.
+pub const MAX_SIGNING_BATCH_SIZE: u32 = 4;
+
+/// Why a participant of a failed multisig ceremony was blamed for the failure.
+///
+/// Stages are numbered as in the CFE's multisig client, and parties are identified by their
+/// (1-based) index in the ceremony's participants, sorted by account id.
+#[derive(Clone, RuntimeDebug, PartialEq, Eq, Encode, Decode, TypeInfo, Serialize, Deserialize)]
+pub enum BlameReason {
+	/// Too few parties received the party's broadcast in the given stage.
+	MissingBroadcast {
+		stage: u8,
+	},
+	/// Parties received different values from the party in the given stage.
+	InconsistentBroadcast {
+		stage: u8,
+	},
+	/// The party sent a message that couldn't be deserialized or has the wrong number of elements.
+	InvalidMessage,
+	InvalidCommitment,
+	InvalidSignatureShare,
+	InvalidComplaint,
+	InvalidBlameResponse,
+	/// The party's refreshed key shares don't correspond to the key being refreshed.
+	RefreshedKeyMismatch,
+}
+
+/// Domain separator for the signatures over broadcast values, see [broadcast_signing_payload].
+pub const BROADCAST_SIGNATURE_CONTEXT: &[u8] = b"chainflip-multisig-broadcast";
+
+/// The message that a party signs with its peer key (the ed25519 key it registered as its peer id)
+/// for each value it broadcasts in a stage that is verified, so that broadcasting different values
+/// to different parties can be proven. The `stage` is the stage in which the broadcast is verified,
+/// as in [BlameReason::InconsistentBroadcast], and `value_hash` is the SHA-256 hash of the value.
+pub fn broadcast_signing_payload(
+	chain: ForeignChain,
+	ceremony_id: CeremonyId,
+	stage: u8,
+	value_hash: &[u8; 32],
+) -> Vec<u8> {
+	(BROADCAST_SIGNATURE_CONTEXT, chain, ceremony_id, stage, value_hash).encode()
+}
+
+/// An ed25519 signature over a [broadcast_signing_payload].
+#[derive(
+	Copy,
+	Clone,
+	RuntimeDebug,
+	PartialEq,
+	Eq,
+	PartialOrd,
+	Ord,
+	Encode,
+	Decode,
+	MaxEncodedLen,
+	TypeInfo,
+	Serialize,
+	Deserialize,
+)]
+pub struct BroadcastSignature(#[serde(with = "signature_bytes")] pub [u8; 64]);
+
+/// Serde only supports arrays of up to 32 elements, so signatures are (de)serialized as bytes.
+mod signature_bytes {
+	use serde::{de::Error, Deserialize, Deserializer, Serializer};
+	use sp_std::vec::Vec;
+
+	pub fn serialize<S: Serializer>(signature: &[u8; 64], serializer: S) -> Result<S::Ok, S::Error> {
+		serializer.serialize_bytes(signature)
+	}
+
+	pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<[u8; 64], D::Error> {
+		Vec::<u8>::deserialize(deserializer)?
+			.try_into()
+			.map_err(|_| D::Error::custom("expected a 64 byte signature"))
+	}
+}
+
+/// The hash of a value that was broadcast, with the broadcaster's signature over it.
+#[derive(
+	Copy,
+	Clone,
+	RuntimeDebug,
+	PartialEq,
+	Eq,
+	PartialOrd,
+	Ord,
+	Encode,
+	Decode,
+	TypeInfo,
+	Serialize,
+	Deserialize,
+)]
+pub struct SignedValueHash {
+	pub hash: [u8; 32],
+	pub signature: BroadcastSignature,
+}
+
+/// The values that parties reported receiving from a broadcaster during broadcast verification.
+/// Two of them with valid signatures prove that the broadcaster sent different values to different
+/// parties.
+#[derive(Clone, RuntimeDebug, PartialEq, Eq, Encode, Decode, TypeInfo, Serialize, Deserialize)]
+pub struct ConflictingBroadcast {
+	pub broadcaster: AuthorityCount,
+	/// Each distinct value (or `None` if it wasn't received) along with the parties that received
+	/// it.
+	pub received: Vec<(Option<SignedValueHash>, Vec<AuthorityCount>)>,
+}
+
+/// Supporting evidence for the offenders reported by a participant of a failed ceremony. Each
+/// participant submits their own evidence, so reports can be cross-checked against each other.
+#[derive(Clone, RuntimeDebug, PartialEq, Eq, Encode, Decode, TypeInfo, Serialize, Deserialize)]
+pub struct CeremonyFailureEvidence {
+	pub reason: BlameReason,
+	/// Only populated if the ceremony failed due to inconsistent broadcasts.
+	pub conflicting_broadcasts: Vec<ConflictingBroadcast>,
+}
+
+impl CeremonyFailureEvidence {
+	/// The number of reported values and parties, which bounds the encoded size of the evidence.
+	pub fn entry_count(&self) -> u32 {
+		self.conflicting_broadcasts
+			.iter()
+			.flat_map(|broadcast| &broadcast.received)
+			.map(|(_, parties)| 1 + parties.len() as u32)
+			.sum()
+	}
+}
+
 /// Alias to 512-bit hash when used in the context of a transaction signature on the chain.
 pub type Signature = MultiSignature;
 
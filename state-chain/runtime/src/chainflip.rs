@@ -9,10 +9,11 @@ mod missed_authorship_slots;
 mod offences;
 mod signer_nomination;
 use crate::{
-	AccountId, AccountRoles, Authorship, BitcoinChainTracking, BitcoinIngressEgress, BitcoinVault,
-	BlockNumber, Emissions, Environment, EthereumBroadcaster, EthereumChainTracking,
-	EthereumIngressEgress, Flip, FlipBalance, PolkadotBroadcaster, PolkadotChainTracking,
-	PolkadotIngressEgress, PolkadotVault, Runtime, RuntimeCall, System, Validator, YEAR,
+	AccountId, AccountRoles, ArbitrumChainTracking, ArbitrumIngressEgress, Authorship,
+	BitcoinChainTracking, BitcoinIngressEgress, BitcoinVault, BlockNumber, Emissions, Environment,
+	EthereumBroadcaster, EthereumChainTracking, EthereumIngressEgress, Flip, FlipBalance,
+	PolkadotBroadcaster, PolkadotChainTracking, PolkadotIngressEgress, PolkadotVault, Runtime,
+	RuntimeCall, System, Validator, YEAR,
 };
 use backup_node_rewards::calculate_backup_rewards;
 use cf_chains::{
@@ -20,6 +21,7 @@ use cf_chains::{
 		to_encoded_address, try_from_encoded_address, AddressConverter, EncodedAddress,
 		ForeignChainAddress,
 	},
+	arb::{api::ArbitrumApi, Arbitrum},
 	btc::{
 		api::{BitcoinApi, SelectedUtxosAndChangeAmount, UtxoSelectionType},
 		Bitcoin, BitcoinCrypto, BitcoinFeeInfo, BitcoinTransactionData, UtxoId,
@@ -35,7 +37,7 @@ use cf_chains::{
 		Ethereum,
 	},
 	evm::{
-		api::{EthEnvironmentProvider, EvmReplayProtection},
+		api::{EthEnvironmentProvider, EvmEnvironmentProvider, EvmReplayProtection},
 		EvmCrypto, Transaction,
 	},
 	AnyChain, ApiCall, CcmChannelMetadata, CcmDepositMetadata, Chain, ChainCrypto,
@@ -205,6 +207,75 @@ impl TransactionBuilder<Ethereum, EthereumApi<EthEnvironment>> for EthTransactio
 	}
 }
 
+/// Arbitrum blocks are produced much faster than Ethereum blocks, so the base fee can move further
+/// between the time a transaction is built and the time it is included.
+const ARBITRUM_BASE_FEE_MULTIPLIER: FixedU64 = FixedU64::from_rational(3, 1);
+// Gas on Arbitrum also pays for posting the calldata to L1, so the gas limits are higher than on
+// Ethereum.
+const ARBITRUM_MAX_GAS_LIMIT: u128 = 25_000_000;
+
+pub struct ArbTransactionBuilder;
+
+impl TransactionBuilder<Arbitrum, ArbitrumApi<ArbEnvironment>> for ArbTransactionBuilder {
+	fn build_transaction(
+		signed_call: &ArbitrumApi<ArbEnvironment>,
+	) -> <Arbitrum as Chain>::Transaction {
+		Transaction {
+			chain_id: signed_call.replay_protection().chain_id,
+			contract: signed_call.replay_protection().contract_address,
+			data: signed_call.chain_encoded(),
+			gas_limit: Self::calculate_gas_limit(signed_call),
+			..Default::default()
+		}
+	}
+
+	fn refresh_unsigned_data(unsigned_tx: &mut <Arbitrum as Chain>::Transaction) {
+		if let Some(ChainState { tracked_data, .. }) = ArbitrumChainTracking::chain_state() {
+			let max_fee_per_gas = tracked_data.max_fee_per_gas(ARBITRUM_BASE_FEE_MULTIPLIER);
+			unsigned_tx.max_fee_per_gas = Some(U256::from(max_fee_per_gas));
+			unsigned_tx.max_priority_fee_per_gas = Some(U256::from(tracked_data.priority_fee));
+		} else {
+			log::warn!("No chain data for Arbitrum. This should never happen. Please check Chain Tracking data.");
+		}
+	}
+
+	fn is_valid_for_rebroadcast(
+		call: &ArbitrumApi<ArbEnvironment>,
+		_payload: &<<Arbitrum as Chain>::ChainCrypto as ChainCrypto>::Payload,
+		current_key: &<<Arbitrum as Chain>::ChainCrypto as ChainCrypto>::AggKey,
+		signature: &<<Arbitrum as Chain>::ChainCrypto as ChainCrypto>::ThresholdSignature,
+	) -> bool {
+		<<Arbitrum as Chain>::ChainCrypto as ChainCrypto>::verify_threshold_signature(
+			current_key,
+			&call.threshold_signature_payload(),
+			signature,
+		)
+	}
+
+	/// Calculate the gas limit for an Arbitrum call in the same way as for Ethereum, see
+	/// [EthTransactionBuilder::calculate_gas_limit].
+	fn calculate_gas_limit(call: &ArbitrumApi<ArbEnvironment>) -> Option<U256> {
+		if let Some(gas_budget) = call.gas_budget() {
+			let current_fee_per_gas = ArbitrumChainTracking::chain_state()
+				.or_else(||{
+					log::warn!("No chain data for Arbitrum. This should never happen. Please check Chain Tracking data.");
+					None
+				})?
+				.tracked_data
+				.max_fee_per_gas(One::one());
+			Some(gas_budget
+				.checked_div(current_fee_per_gas)
+				.unwrap_or_else(||{
+					log::warn!("Current gas price for Arbitrum is 0. This should never happen. Please check Chain Tracking data.");
+					Default::default()
+				}).min(ARBITRUM_MAX_GAS_LIMIT)
+				.into())
+		} else {
+			None
+		}
+	}
+}
+
 pub struct DotTransactionBuilder;
 impl TransactionBuilder<Polkadot, PolkadotApi<DotEnvironment>> for DotTransactionBuilder {
 	fn build_transaction(
@@ -321,6 +392,53 @@ impl EthEnvironmentProvider for EthEnvironment {
 	}
 }
 
+/// Whether the Arbitrum vault takes part in vault rotations.
+pub struct ArbitrumIntegrationEnabled;
+
+impl Get<bool> for ArbitrumIntegrationEnabled {
+	fn get() -> bool {
+		Environment::arbitrum_integration_enabled()
+	}
+}
+
+pub struct ArbEnvironment;
+
+impl ReplayProtectionProvider<Arbitrum> for ArbEnvironment {
+	fn replay_protection(contract_address: eth::Address) -> EvmReplayProtection {
+		EvmReplayProtection {
+			nonce: Self::next_nonce(),
+			chain_id: Self::chain_id(),
+			key_manager_address: Self::key_manager_address(),
+			contract_address,
+		}
+	}
+}
+
+impl EvmEnvironmentProvider<Arbitrum> for ArbEnvironment {
+	fn token_address(asset: assets::arb::Asset) -> Option<eth::Address> {
+		match asset {
+			assets::arb::Asset::ArbEth => Some(ETHEREUM_ETH_ADDRESS),
+			erc20 => Environment::supported_arb_assets(erc20),
+		}
+	}
+
+	fn key_manager_address() -> eth::Address {
+		Environment::arb_key_manager_address()
+	}
+
+	fn vault_address() -> eth::Address {
+		Environment::arb_vault_address()
+	}
+
+	fn chain_id() -> cf_chains::evm::api::EvmChainId {
+		Environment::arbitrum_chain_id()
+	}
+
+	fn next_nonce() -> u64 {
+		Environment::next_arbitrum_signature_nonce()
+	}
+}
+
 #[derive(Clone, Debug, PartialEq, Eq, Encode, Decode, TypeInfo)]
 pub struct DotEnvironment;
 
@@ -412,6 +530,8 @@ impl BroadcastAnyChainGovKey for TokenholderGovernanceBroadcaster {
 			ForeignChain::Polkadot =>
 				Self::broadcast_gov_key::<Polkadot, PolkadotBroadcaster>(maybe_old_key, new_key),
 			ForeignChain::Bitcoin => Err(()),
+			// Arbitrum has no governance key: there is no State Chain Gateway on Arbitrum.
+			ForeignChain::Arbitrum => Err(()),
 		}
 	}
 
@@ -422,6 +542,7 @@ impl BroadcastAnyChainGovKey for TokenholderGovernanceBroadcaster {
 			ForeignChain::Polkadot =>
 				Self::is_govkey_compatible::<<Polkadot as Chain>::ChainCrypto>(key),
 			ForeignChain::Bitcoin => false,
+			ForeignChain::Arbitrum => false,
 		}
 	}
 }
@@ -514,19 +635,24 @@ impl_deposit_api_for_anychain!(
 	AnyChainIngressEgressHandler,
 	(Ethereum, EthereumIngressEgress),
 	(Polkadot, PolkadotIngressEgress),
-	(Bitcoin, BitcoinIngressEgress)
+	(Bitcoin, BitcoinIngressEgress),
+	(Arbitrum, ArbitrumIngressEgress)
 );
 
 impl_egress_api_for_anychain!(
 	AnyChainIngressEgressHandler,
 	(Ethereum, EthereumIngressEgress),
 	(Polkadot, PolkadotIngressEgress),
-	(Bitcoin, BitcoinIngressEgress)
+	(Bitcoin, BitcoinIngressEgress),
+	(Arbitrum, ArbitrumIngressEgress)
 );
 
 pub struct EthDepositHandler;
 impl DepositHandler<Ethereum> for EthDepositHandler {}
 
+pub struct ArbDepositHandler;
+impl DepositHandler<Arbitrum> for ArbDepositHandler {}
+
 pub struct DotDepositHandler;
 impl DepositHandler<Polkadot> for DotDepositHandler {}
 
@@ -559,6 +685,9 @@ pub struct BroadcastReadyProvider;
 impl OnBroadcastReady<Ethereum> for BroadcastReadyProvider {
 	type ApiCall = EthereumApi<EthEnvironment>;
 }
+impl OnBroadcastReady<Arbitrum> for BroadcastReadyProvider {
+	type ApiCall = ArbitrumApi<ArbEnvironment>;
+}
 impl OnBroadcastReady<Polkadot> for BroadcastReadyProvider {
 	type ApiCall = PolkadotApi<DotEnvironment>;
 }
@@ -0,0 +1,70 @@
+use super::AddressDerivation;
+use crate::{ArbEnvironment, Environment};
+use cf_chains::{
+	address::{AddressDerivationApi, AddressDerivationError},
+	eth::deposit_address::get_create_2_address,
+	evm::api::EvmEnvironmentProvider,
+	Arbitrum, Chain,
+};
+use cf_primitives::{chains::assets::arb, ChannelId};
+
+impl AddressDerivationApi<Arbitrum> for AddressDerivation {
+	fn generate_address(
+		source_asset: arb::Asset,
+		channel_id: ChannelId,
+	) -> Result<<Arbitrum as Chain>::ChainAccount, AddressDerivationError> {
+		Ok(get_create_2_address(
+			Environment::arb_vault_address(),
+			ArbEnvironment::token_address(source_asset),
+			channel_id,
+		))
+	}
+
+	fn generate_address_and_state(
+		source_asset: <Arbitrum as Chain>::ChainAsset,
+		channel_id: ChannelId,
+	) -> Result<
+		(<Arbitrum as Chain>::ChainAccount, <Arbitrum as Chain>::DepositChannelState),
+		AddressDerivationError,
+	> {
+		Ok((
+			<Self as AddressDerivationApi<Arbitrum>>::generate_address(source_asset, channel_id)?,
+			Default::default(),
+		))
+	}
+}
+
+#[test]
+fn test_address_generation() {
+	use crate::Runtime;
+	use pallet_cf_environment::ArbitrumSupportedAssets;
+
+	frame_support::sp_io::TestExternalities::new_empty().execute_with(|| {
+		// Expect address generation to be successfully for native ArbEth
+		assert!(<AddressDerivation as AddressDerivationApi<Arbitrum>>::generate_address(
+			arb::Asset::ArbEth,
+			1
+		)
+		.is_ok());
+		// The genesis build is not running, so we have to add it manually
+		ArbitrumSupportedAssets::<Runtime>::insert(arb::Asset::ArbUsdc, sp_core::H160([1; 20]));
+		assert!(<AddressDerivation as AddressDerivationApi<Arbitrum>>::generate_address(
+			arb::Asset::ArbUsdc,
+			1
+		)
+		.is_ok());
+		// Addresses on Arbitrum are derived from the Arbitrum vault, so they differ from the
+		// Ethereum addresses for the same channel
+		pallet_cf_environment::ArbitrumVaultAddress::<Runtime>::put(sp_core::H160([2; 20]));
+		assert_ne!(
+			<AddressDerivation as AddressDerivationApi<Arbitrum>>::generate_address(
+				arb::Asset::ArbEth,
+				1
+			),
+			<AddressDerivation as AddressDerivationApi<cf_chains::Ethereum>>::generate_address(
+				cf_primitives::chains::assets::eth::Asset::Eth,
+				1
+			),
+		);
+	});
+}
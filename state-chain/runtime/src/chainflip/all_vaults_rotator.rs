@@ -4,17 +4,34 @@ use core::marker::PhantomData;
 
 use cf_primitives::EpochIndex;
 use cf_traits::{AsyncResult, VaultRotator, VaultStatus};
+use frame_support::traits::Get;
 use sp_std::{collections::btree_set::BTreeSet, vec::Vec};
 
-pub struct AllVaultRotator<A, B, C> {
-	_phantom: PhantomData<(A, B, C)>,
+/// Rotates the vaults `A`, `B` and `C`, and `D` if `DEnabled` returns true when the keygen starts.
+/// `D` is the vault of a chain that is integrated into running networks, so it only takes part in
+/// the rotations that were started after the chain was enabled.
+pub struct AllVaultRotator<A, B, C, D, DEnabled> {
+	_phantom: PhantomData<(A, B, C, D, DEnabled)>,
 }
 
-impl<A, B, C> VaultRotator for AllVaultRotator<A, B, C>
+impl<A, B, C, D, DEnabled> AllVaultRotator<A, B, C, D, DEnabled>
+where
+	D: VaultRotator,
+{
+	/// Whether `D` takes part in the current rotation. `D` has no rotation status unless its keygen
+	/// was started.
+	fn is_rotating_d() -> bool {
+		!matches!(D::status(), AsyncResult::Void)
+	}
+}
+
+impl<A, B, C, D, DEnabled> VaultRotator for AllVaultRotator<A, B, C, D, DEnabled>
 where
 	A: VaultRotator,
 	B: VaultRotator<ValidatorId = A::ValidatorId>,
 	C: VaultRotator<ValidatorId = A::ValidatorId>,
+	D: VaultRotator<ValidatorId = A::ValidatorId>,
+	DEnabled: Get<bool>,
 {
 	type ValidatorId = A::ValidatorId;
 
@@ -22,7 +39,12 @@ where
 	fn keygen(candidates: BTreeSet<Self::ValidatorId>, next_epoch_index: EpochIndex) {
 		A::keygen(candidates.clone(), next_epoch_index);
 		B::keygen(candidates.clone(), next_epoch_index);
-		C::keygen(candidates, next_epoch_index);
+		if DEnabled::get() {
+			C::keygen(candidates.clone(), next_epoch_index);
+			D::keygen(candidates, next_epoch_index);
+		} else {
+			C::keygen(candidates, next_epoch_index);
+		}
 	}
 
 	/// Start all the key handovers for the vaults with the provided `candidates`.
@@ -33,11 +55,19 @@ where
 	) {
 		A::key_handover(sharing_participants.clone(), new_candidates.clone(), epoch_index);
 		B::key_handover(sharing_participants.clone(), new_candidates.clone(), epoch_index);
-		C::key_handover(sharing_participants, new_candidates, epoch_index);
+		if Self::is_rotating_d() {
+			C::key_handover(sharing_participants.clone(), new_candidates.clone(), epoch_index);
+			D::key_handover(sharing_participants, new_candidates, epoch_index);
+		} else {
+			C::key_handover(sharing_participants, new_candidates, epoch_index);
+		}
 	}
 
 	fn status() -> AsyncResult<VaultStatus<Self::ValidatorId>> {
-		let async_results = [A::status(), B::status(), C::status()];
+		let mut async_results = Vec::from([A::status(), B::status(), C::status()]);
+		if Self::is_rotating_d() {
+			async_results.push(D::status());
+		}
 
 		// if any of the inner rotations are void, then the overall vault rotation result is void.
 		if async_results.iter().any(|item| matches!(item, AsyncResult::Void)) {
@@ -81,41 +111,70 @@ where
 		A::activate();
 		B::activate();
 		C::activate();
+		if Self::is_rotating_d() {
+			D::activate();
+		}
 	}
 
 	fn reset_vault_rotation() {
 		A::reset_vault_rotation();
 		B::reset_vault_rotation();
 		C::reset_vault_rotation();
+		D::reset_vault_rotation();
 	}
 
 	#[cfg(feature = "runtime-benchmarks")]
 	fn set_status(outcome: AsyncResult<VaultStatus<Self::ValidatorId>>) {
 		A::set_status(outcome.clone());
 		B::set_status(outcome.clone());
-		C::set_status(outcome);
+		C::set_status(outcome.clone());
+		D::set_status(outcome);
 	}
 }
 
 #[cfg(test)]
 mod tests {
 	use cf_traits::{
-		mocks::vault_rotator::{MockVaultRotatorA, MockVaultRotatorB, MockVaultRotatorC},
+		mocks::vault_rotator::{
+			MockVaultRotatorA, MockVaultRotatorB, MockVaultRotatorC, MockVaultRotatorD,
+		},
 		AsyncResult, VaultRotator,
 	};
 
 	use super::*;
 
+	use frame_support::{parameter_types, traits::ConstBool};
+
+	type AllMockVaultRotators = AllVaultRotator<
+		MockVaultRotatorA,
+		MockVaultRotatorB,
+		MockVaultRotatorC,
+		MockVaultRotatorD,
+		ConstBool<true>,
+	>;
+
+	parameter_types! {
+		pub storage DEnabled: bool = false;
+	}
+
+	type MockVaultRotatorsWithOptionalD = AllVaultRotator<
+		MockVaultRotatorA,
+		MockVaultRotatorB,
+		MockVaultRotatorC,
+		MockVaultRotatorD,
+		DEnabled,
+	>;
+
 	#[test]
 	fn status_keygen_complete_when_all_complete() {
 		frame_support::sp_io::TestExternalities::new_empty().execute_with(|| {
 			MockVaultRotatorA::keygen_success();
 			MockVaultRotatorB::keygen_success();
 			MockVaultRotatorC::keygen_success();
+			MockVaultRotatorD::keygen_success();
 
 			assert_eq!(
-				AllVaultRotator::<MockVaultRotatorA, MockVaultRotatorB, MockVaultRotatorC>::status(
-				),
+				AllMockVaultRotators::status(),
 				AsyncResult::Ready(VaultStatus::KeygenComplete)
 			);
 		});
@@ -127,10 +186,10 @@ mod tests {
 			MockVaultRotatorA::key_handover_success();
 			MockVaultRotatorB::key_handover_success();
 			MockVaultRotatorC::key_handover_success();
+			MockVaultRotatorD::key_handover_success();
 
 			assert_eq!(
-				AllVaultRotator::<MockVaultRotatorA, MockVaultRotatorB, MockVaultRotatorC>::status(
-				),
+				AllMockVaultRotators::status(),
 				AsyncResult::Ready(VaultStatus::KeyHandoverComplete)
 			);
 		});
@@ -142,10 +201,10 @@ mod tests {
 			MockVaultRotatorA::keys_activated();
 			MockVaultRotatorB::keys_activated();
 			MockVaultRotatorC::keys_activated();
+			MockVaultRotatorD::keys_activated();
 
 			assert_eq!(
-				AllVaultRotator::<MockVaultRotatorA, MockVaultRotatorB, MockVaultRotatorC>::status(
-				),
+				AllMockVaultRotators::status(),
 				AsyncResult::Ready(VaultStatus::RotationComplete)
 			);
 		});
@@ -160,10 +219,10 @@ mod tests {
 			MockVaultRotatorA::keys_activated();
 			MockVaultRotatorB::keygen_success();
 			MockVaultRotatorC::keygen_success();
+			MockVaultRotatorD::keygen_success();
 
 			assert_eq!(
-				AllVaultRotator::<MockVaultRotatorA, MockVaultRotatorB, MockVaultRotatorC>::status(
-				),
+				AllMockVaultRotators::status(),
 				AsyncResult::Ready(VaultStatus::Failed(BTreeSet::default()))
 			);
 		});
@@ -177,10 +236,10 @@ mod tests {
 			MockVaultRotatorA::failed(OFFENDERS);
 			MockVaultRotatorB::keygen_success();
 			MockVaultRotatorC::keygen_success();
+			MockVaultRotatorD::keygen_success();
 
 			assert_eq!(
-				AllVaultRotator::<MockVaultRotatorA, MockVaultRotatorB, MockVaultRotatorC>::status(
-				),
+				AllMockVaultRotators::status(),
 				AsyncResult::Ready(VaultStatus::Failed(BTreeSet::from(OFFENDERS)))
 			);
 		});
@@ -190,10 +249,10 @@ mod tests {
 			MockVaultRotatorA::failed(OFFENDERS);
 			MockVaultRotatorB::key_handover_success();
 			MockVaultRotatorC::key_handover_success();
+			MockVaultRotatorD::key_handover_success();
 
 			assert_eq!(
-				AllVaultRotator::<MockVaultRotatorA, MockVaultRotatorB, MockVaultRotatorC>::status(
-				),
+				AllMockVaultRotators::status(),
 				AsyncResult::Ready(VaultStatus::Failed(BTreeSet::from(OFFENDERS)))
 			);
 		});
@@ -205,10 +264,10 @@ mod tests {
 			MockVaultRotatorA::failed([1, 2, 3, 4]);
 			MockVaultRotatorB::failed([2, 4, 5]);
 			MockVaultRotatorC::failed([4, 5, 6]);
+			MockVaultRotatorD::failed([4, 5, 6]);
 
 			assert_eq!(
-				AllVaultRotator::<MockVaultRotatorA, MockVaultRotatorB, MockVaultRotatorC>::status(
-				),
+				AllMockVaultRotators::status(),
 				AsyncResult::Ready(VaultStatus::Failed(BTreeSet::from([1, 2, 3, 4, 5, 6])))
 			);
 		});
@@ -220,12 +279,9 @@ mod tests {
 			MockVaultRotatorA::pending();
 			MockVaultRotatorB::pending();
 			MockVaultRotatorC::pending();
+			MockVaultRotatorD::pending();
 
-			assert_eq!(
-				AllVaultRotator::<MockVaultRotatorA, MockVaultRotatorB, MockVaultRotatorC>::status(
-				),
-				AsyncResult::Pending
-			);
+			assert_eq!(AllMockVaultRotators::status(), AsyncResult::Pending);
 		});
 	}
 
@@ -235,12 +291,9 @@ mod tests {
 			MockVaultRotatorA::keygen_success();
 			MockVaultRotatorB::pending();
 			MockVaultRotatorC::keygen_success();
+			MockVaultRotatorD::keygen_success();
 
-			assert_eq!(
-				AllVaultRotator::<MockVaultRotatorA, MockVaultRotatorB, MockVaultRotatorC>::status(
-				),
-				AsyncResult::Pending
-			);
+			assert_eq!(AllMockVaultRotators::status(), AsyncResult::Pending);
 		});
 	}
 
@@ -253,11 +306,50 @@ mod tests {
 			MockVaultRotatorA::failed([1, 2, 3]);
 			MockVaultRotatorB::pending();
 			MockVaultRotatorC::failed([4, 5, 6]);
+			MockVaultRotatorD::failed([4, 5, 6]);
+
+			assert_eq!(AllMockVaultRotators::status(), AsyncResult::Pending);
+		});
+	}
+
+	#[test]
+	fn disabled_vault_does_not_take_part_in_rotations() {
+		frame_support::sp_io::TestExternalities::new_empty().execute_with(|| {
+			MockVaultRotatorsWithOptionalD::keygen(BTreeSet::from([1, 2, 3]), 2);
+			assert_eq!(MockVaultRotatorD::status(), AsyncResult::Void);
+
+			MockVaultRotatorA::keygen_success();
+			MockVaultRotatorB::keygen_success();
+			MockVaultRotatorC::keygen_success();
+			assert_eq!(
+				MockVaultRotatorsWithOptionalD::status(),
+				AsyncResult::Ready(VaultStatus::KeygenComplete)
+			);
 
+			// Enabling the vault during a rotation doesn't make it take part in that rotation
+			DEnabled::set(&true);
+			MockVaultRotatorsWithOptionalD::activate();
+			assert_eq!(MockVaultRotatorD::status(), AsyncResult::Void);
+			MockVaultRotatorA::keys_activated();
+			MockVaultRotatorB::keys_activated();
+			MockVaultRotatorC::keys_activated();
 			assert_eq!(
-				AllVaultRotator::<MockVaultRotatorA, MockVaultRotatorB, MockVaultRotatorC>::status(
-				),
-				AsyncResult::Pending
+				MockVaultRotatorsWithOptionalD::status(),
+				AsyncResult::Ready(VaultStatus::RotationComplete)
+			);
+
+			// It takes part in the next one
+			MockVaultRotatorsWithOptionalD::reset_vault_rotation();
+			MockVaultRotatorsWithOptionalD::keygen(BTreeSet::from([1, 2, 3]), 3);
+			assert_eq!(MockVaultRotatorD::status(), AsyncResult::Pending);
+			MockVaultRotatorA::keygen_success();
+			MockVaultRotatorB::keygen_success();
+			MockVaultRotatorC::keygen_success();
+			assert_eq!(MockVaultRotatorsWithOptionalD::status(), AsyncResult::Pending);
+			MockVaultRotatorD::keygen_success();
+			assert_eq!(
+				MockVaultRotatorsWithOptionalD::status(),
+				AsyncResult::Ready(VaultStatus::KeygenComplete)
 			);
 		});
 	}
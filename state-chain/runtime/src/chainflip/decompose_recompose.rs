@@ -1,4 +1,6 @@
-use crate::{BitcoinInstance, EthereumInstance, PolkadotInstance, Runtime, RuntimeCall};
+use crate::{
+	ArbitrumInstance, BitcoinInstance, EthereumInstance, PolkadotInstance, Runtime, RuntimeCall,
+};
 use cf_chains::{btc::BitcoinFeeInfo, dot::PolkadotBalance};
 use cf_primitives::EthAmount;
 use codec::{Decode, Encode};
@@ -71,6 +73,15 @@ impl WitnessDataExtraction for RuntimeCall {
 				let fee_info = mem::take(&mut new_chain_state.tracked_data.median_tip);
 				Some(fee_info.encode())
 			},
+			RuntimeCall::ArbitrumChainTracking(pallet_cf_chain_tracking::Call::<
+				Runtime,
+				ArbitrumInstance,
+			>::update_chain_state {
+				ref mut new_chain_state,
+			}) => {
+				let priority_fee = mem::take(&mut new_chain_state.tracked_data.priority_fee);
+				Some(priority_fee.encode())
+			},
 			_ => None,
 		}
 	}
@@ -111,6 +122,17 @@ impl WitnessDataExtraction for RuntimeCall {
 					new_chain_state.tracked_data.median_tip = median;
 				};
 			},
+			RuntimeCall::ArbitrumChainTracking(pallet_cf_chain_tracking::Call::<
+				Runtime,
+				ArbitrumInstance,
+			>::update_chain_state {
+				new_chain_state,
+			}) => {
+				let fee_votes = decode_many::<EthAmount>(data);
+				if let Some(median) = select_median(fee_votes) {
+					new_chain_state.tracked_data.priority_fee = median;
+				}
+			},
 			_ => {
 				log::warn!("No witness data injection for call {:?}", self);
 			},
@@ -123,6 +145,7 @@ mod tests {
 	use super::*;
 	use crate::{RuntimeOrigin, Validator, Witnesser};
 	use cf_chains::{
+		arb::{Arbitrum, ArbitrumTrackedData},
 		btc::{BitcoinFeeInfo, BitcoinTrackedData},
 		dot::PolkadotTrackedData,
 		eth::EthereumTrackedData,
@@ -178,6 +201,19 @@ mod tests {
 						},
 					},
 				}),
+			ForeignChain::Arbitrum =>
+				RuntimeCall::ArbitrumChainTracking(pallet_cf_chain_tracking::Call::<
+					Runtime,
+					ArbitrumInstance,
+				>::update_chain_state {
+					new_chain_state: ChainState {
+						block_height: BLOCK_HEIGHT,
+						tracked_data: ArbitrumTrackedData {
+							base_fee: BASE_FEE,
+							priority_fee: fee.into(),
+						},
+					},
+				}),
 		}
 	}
 
@@ -186,6 +222,7 @@ mod tests {
 		test_medians::<Ethereum>();
 		test_medians::<Bitcoin>();
 		test_medians::<Polkadot>();
+		test_medians::<Arbitrum>();
 	}
 
 	#[track_caller]
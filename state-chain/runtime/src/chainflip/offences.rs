@@ -78,6 +78,9 @@ impl From<pallet_cf_vaults::PalletOffence> for Offence {
 			pallet_cf_vaults::PalletOffence::FailedKeygen => Self::ParticipateKeygenFailed,
 			pallet_cf_vaults::PalletOffence::FailedKeyHandover =>
 				Self::ParticipateKeyHandoverFailed,
+			// A share refresh is a key handover to the same set of authorities.
+			pallet_cf_vaults::PalletOffence::FailedShareRefresh =>
+				Self::ParticipateKeyHandoverFailed,
 		}
 	}
 }
@@ -3,6 +3,7 @@
 #![recursion_limit = "256"]
 pub mod chainflip;
 pub mod constants;
+pub mod migrations;
 pub mod runtime_apis;
 pub mod safe_mode;
 #[cfg(feature = "std")]
@@ -17,13 +18,15 @@ use cf_amm::{
 	range_orders::Liquidity,
 };
 use cf_chains::{
+	arb::{api::ArbitrumApi, Arbitrum},
 	btc::BitcoinCrypto,
 	dot::{self, PolkadotCrypto},
 	eth::{self, api::EthereumApi, Address as EthereumAddress, Ethereum},
 	evm::EvmCrypto,
 	Bitcoin, CcmChannelMetadata, ForeignChain, Polkadot,
 };
-use cf_primitives::NetworkEnvironment;
+use cf_primitives::{CeremonyFailureEvidence, CeremonyId, NetworkEnvironment};
+use cf_runtime_upgrade_utilities::VersionedMigration;
 use core::ops::Range;
 pub use frame_system::Call as SystemCall;
 use pallet_cf_governance::GovCallHash;
@@ -39,7 +42,7 @@ use crate::runtime_apis::RuntimeApiAccountInfoV2;
 
 pub use frame_support::{
 	construct_runtime, debug,
-	instances::{Instance1, Instance2, Instance3},
+	instances::{Instance1, Instance2, Instance3, Instance4},
 	parameter_types,
 	traits::{
 		ConstBool, ConstU128, ConstU16, ConstU32, ConstU64, ConstU8, Get, KeyOwnerProofSystem,
@@ -94,8 +97,8 @@ pub use pallet_cf_validator::SetSizeParameters;
 pub use chainflip::chain_instances::*;
 use chainflip::{
 	all_vaults_rotator::AllVaultRotator, epoch_transition::ChainflipEpochTransitions,
-	BroadcastReadyProvider, BtcEnvironment, ChainAddressConverter, ChainflipHeartbeat,
-	DotEnvironment, EthEnvironment, TokenholderGovernanceBroadcaster,
+	ArbEnvironment, BroadcastReadyProvider, BtcEnvironment, ChainAddressConverter,
+	ChainflipHeartbeat, DotEnvironment, EthEnvironment, TokenholderGovernanceBroadcaster,
 };
 use safe_mode::{RuntimeSafeMode, WitnesserCallPermission};
 
@@ -154,10 +157,10 @@ pub const VERSION: RuntimeVersion = RuntimeVersion {
 	spec_name: create_runtime_str!("chainflip-node"),
 	impl_name: create_runtime_str!("chainflip-node"),
 	authoring_version: 1,
-	spec_version: 100,
+	spec_version: 101,
 	impl_version: 1,
 	apis: RUNTIME_API_VERSIONS,
-	transaction_version: 10,
+	transaction_version: 11,
 	state_version: 1,
 };
 
@@ -172,7 +175,13 @@ impl pallet_cf_validator::Config for Runtime {
 	type Offence = chainflip::Offence;
 	type EpochTransitionHandler = ChainflipEpochTransitions;
 	type ValidatorWeightInfo = pallet_cf_validator::weights::PalletWeight<Runtime>;
-	type VaultRotator = AllVaultRotator<EthereumVault, PolkadotVault, BitcoinVault>;
+	type VaultRotator = AllVaultRotator<
+		EthereumVault,
+		PolkadotVault,
+		BitcoinVault,
+		ArbitrumVault,
+		chainflip::ArbitrumIntegrationEnabled,
+	>;
 	type MissedAuthorshipSlots = chainflip::MissedAuraSlots;
 	type BidderProvider = pallet_cf_funding::Pallet<Self>;
 	type KeygenQualification = (
@@ -209,6 +218,7 @@ impl pallet_cf_environment::Config for Runtime {
 	type RuntimeEvent = RuntimeEvent;
 	type PolkadotVaultKeyWitnessedHandler = PolkadotVault;
 	type BitcoinVaultKeyWitnessedHandler = BitcoinVault;
+	type ArbitrumVaultKeyWitnessedHandler = ArbitrumVault;
 	type BitcoinFeeInfo = chainflip::BitcoinFeeGetter;
 	type RuntimeSafeMode = RuntimeSafeMode;
 	type CurrentReleaseVersion = CurrentReleaseVersion;
@@ -276,6 +286,23 @@ impl pallet_cf_vaults::Config<BitcoinInstance> for Runtime {
 	type Slasher = FlipSlasher<Self>;
 }
 
+impl pallet_cf_vaults::Config<ArbitrumInstance> for Runtime {
+	type RuntimeEvent = RuntimeEvent;
+	type RuntimeCall = RuntimeCall;
+	type EnsureThresholdSigned =
+		pallet_cf_threshold_signature::EnsureThresholdSigned<Self, ArbitrumInstance>;
+	type ThresholdSigner = ArbitrumThresholdSigner;
+	type Offence = chainflip::Offence;
+	type Chain = Arbitrum;
+	type SetAggKeyWithAggKey = ArbitrumApi<ArbEnvironment>;
+	type Broadcaster = ArbitrumBroadcaster;
+	type OffenceReporter = Reputation;
+	type WeightInfo = pallet_cf_vaults::weights::PalletWeight<Runtime>;
+	type ChainTracking = ArbitrumChainTracking;
+	type SafeMode = RuntimeSafeMode;
+	type Slasher = FlipSlasher<Self>;
+}
+
 use chainflip::address_derivation::AddressDerivation;
 
 impl pallet_cf_ingress_egress::Config<EthereumInstance> for Runtime {
@@ -326,6 +353,22 @@ impl pallet_cf_ingress_egress::Config<BitcoinInstance> for Runtime {
 	type CcmHandler = Swapping;
 }
 
+impl pallet_cf_ingress_egress::Config<ArbitrumInstance> for Runtime {
+	type RuntimeEvent = RuntimeEvent;
+	type RuntimeCall = RuntimeCall;
+	type TargetChain = Arbitrum;
+	type AddressDerivation = AddressDerivation;
+	type AddressConverter = ChainAddressConverter;
+	type LpBalance = LiquidityProvider;
+	type SwapDepositHandler = Swapping;
+	type ChainApiCall = ArbitrumApi<ArbEnvironment>;
+	type Broadcaster = ArbitrumBroadcaster;
+	type WeightInfo = pallet_cf_ingress_egress::weights::PalletWeight<Runtime>;
+	type DepositHandler = chainflip::ArbDepositHandler;
+	type ChainTracking = ArbitrumChainTracking;
+	type CcmHandler = Swapping;
+}
+
 parameter_types! {
 	pub const NetworkFee: Permill = Permill::from_perthousand(1);
 }
@@ -621,6 +664,13 @@ impl pallet_cf_threshold_signature::Config<EthereumInstance> for Runtime {
 	type OffenceReporter = Reputation;
 	type CeremonyIdProvider = EthereumVault;
 	type CeremonyRetryDelay = ConstU32<1>;
+	type MaxSigningBatchSize = ConstU32<{ cf_primitives::MAX_SIGNING_BATCH_SIZE }>;
+	type FailureEvidenceRetentionPeriod = ConstU32<{ 14 * DAYS }>;
+	type MaxFailureEvidenceEntries = ConstU32<2_000>;
+	type MaxFailureEvidenceEntriesPerCeremony = ConstU32<20_000>;
+	type KeyCeremonyParticipants = EthereumVault;
+	type TargetChain = Ethereum;
+	type PeerSignatureVerifier = Validator;
 	type Weights = pallet_cf_threshold_signature::weights::PalletWeight<Self>;
 }
 
@@ -635,6 +685,13 @@ impl pallet_cf_threshold_signature::Config<PolkadotInstance> for Runtime {
 	type OffenceReporter = Reputation;
 	type CeremonyIdProvider = PolkadotVault;
 	type CeremonyRetryDelay = ConstU32<1>;
+	type MaxSigningBatchSize = ConstU32<{ cf_primitives::MAX_SIGNING_BATCH_SIZE }>;
+	type FailureEvidenceRetentionPeriod = ConstU32<{ 14 * DAYS }>;
+	type MaxFailureEvidenceEntries = ConstU32<2_000>;
+	type MaxFailureEvidenceEntriesPerCeremony = ConstU32<20_000>;
+	type KeyCeremonyParticipants = PolkadotVault;
+	type TargetChain = Polkadot;
+	type PeerSignatureVerifier = Validator;
 	type Weights = pallet_cf_threshold_signature::weights::PalletWeight<Self>;
 }
 
@@ -649,6 +706,35 @@ impl pallet_cf_threshold_signature::Config<BitcoinInstance> for Runtime {
 	type OffenceReporter = Reputation;
 	type CeremonyIdProvider = BitcoinVault;
 	type CeremonyRetryDelay = ConstU32<1>;
+	// Bitcoin requests already sign all the inputs of a transaction in a single ceremony.
+	type MaxSigningBatchSize = ConstU32<1>;
+	type FailureEvidenceRetentionPeriod = ConstU32<{ 14 * DAYS }>;
+	type MaxFailureEvidenceEntries = ConstU32<2_000>;
+	type MaxFailureEvidenceEntriesPerCeremony = ConstU32<20_000>;
+	type KeyCeremonyParticipants = BitcoinVault;
+	type TargetChain = Bitcoin;
+	type PeerSignatureVerifier = Validator;
+	type Weights = pallet_cf_threshold_signature::weights::PalletWeight<Self>;
+}
+
+impl pallet_cf_threshold_signature::Config<ArbitrumInstance> for Runtime {
+	type RuntimeEvent = RuntimeEvent;
+	type Offence = chainflip::Offence;
+	type RuntimeOrigin = RuntimeOrigin;
+	type ThresholdCallable = RuntimeCall;
+	type ThresholdSignerNomination = chainflip::RandomSignerNomination;
+	type TargetChainCrypto = EvmCrypto;
+	type KeyProvider = ArbitrumVault;
+	type OffenceReporter = Reputation;
+	type CeremonyIdProvider = ArbitrumVault;
+	type CeremonyRetryDelay = ConstU32<1>;
+	type MaxSigningBatchSize = ConstU32<{ cf_primitives::MAX_SIGNING_BATCH_SIZE }>;
+	type FailureEvidenceRetentionPeriod = ConstU32<{ 14 * DAYS }>;
+	type MaxFailureEvidenceEntries = ConstU32<2_000>;
+	type MaxFailureEvidenceEntriesPerCeremony = ConstU32<20_000>;
+	type KeyCeremonyParticipants = ArbitrumVault;
+	type TargetChain = Arbitrum;
+	type PeerSignatureVerifier = Validator;
 	type Weights = pallet_cf_threshold_signature::weights::PalletWeight<Self>;
 }
 
@@ -721,6 +807,29 @@ impl pallet_cf_broadcast::Config<BitcoinInstance> for Runtime {
 	type ChainTracking = BitcoinChainTracking;
 }
 
+impl pallet_cf_broadcast::Config<ArbitrumInstance> for Runtime {
+	type RuntimeEvent = RuntimeEvent;
+	type RuntimeCall = RuntimeCall;
+	type RuntimeOrigin = RuntimeOrigin;
+	type BroadcastCallable = RuntimeCall;
+	type Offence = chainflip::Offence;
+	type TargetChain = Arbitrum;
+	type ApiCall = ArbitrumApi<ArbEnvironment>;
+	type ThresholdSigner = ArbitrumThresholdSigner;
+	type TransactionBuilder = chainflip::ArbTransactionBuilder;
+	type BroadcastSignerNomination = chainflip::RandomSignerNomination;
+	type OffenceReporter = Reputation;
+	type EnsureThresholdSigned =
+		pallet_cf_threshold_signature::EnsureThresholdSigned<Self, ArbitrumInstance>;
+	type BroadcastReadyProvider = BroadcastReadyProvider;
+	type BroadcastTimeout = ConstU32<{ 10 * MINUTES }>;
+	type WeightInfo = pallet_cf_broadcast::weights::PalletWeight<Runtime>;
+	type SafeMode = RuntimeSafeMode;
+	type SafeModeBlockMargin = ConstU32<10>;
+	type KeyProvider = ArbitrumVault;
+	type ChainTracking = ArbitrumChainTracking;
+}
+
 impl pallet_cf_chain_tracking::Config<EthereumInstance> for Runtime {
 	type RuntimeEvent = RuntimeEvent;
 	type TargetChain = Ethereum;
@@ -739,6 +848,12 @@ impl pallet_cf_chain_tracking::Config<BitcoinInstance> for Runtime {
 	type WeightInfo = pallet_cf_chain_tracking::weights::PalletWeight<Runtime>;
 }
 
+impl pallet_cf_chain_tracking::Config<ArbitrumInstance> for Runtime {
+	type RuntimeEvent = RuntimeEvent;
+	type TargetChain = Arbitrum;
+	type WeightInfo = pallet_cf_chain_tracking::weights::PalletWeight<Runtime>;
+}
+
 construct_runtime!(
 	pub struct Runtime
 	{
@@ -786,6 +901,14 @@ construct_runtime!(
 		BitcoinIngressEgress: pallet_cf_ingress_egress::<Instance3>,
 
 		LiquidityPools: pallet_cf_pools,
+
+		// Pallets added after genesis are appended, so the indices of the existing pallets, and
+		// with them the encoding of calls and events, don't change.
+		ArbitrumChainTracking: pallet_cf_chain_tracking::<Instance4>,
+		ArbitrumVault: pallet_cf_vaults::<Instance4>,
+		ArbitrumThresholdSigner: pallet_cf_threshold_signature::<Instance4>,
+		ArbitrumBroadcaster: pallet_cf_broadcast::<Instance4>,
+		ArbitrumIngressEgress: pallet_cf_ingress_egress::<Instance4>,
 	}
 );
 
@@ -851,6 +974,18 @@ type PalletMigrations = (
 	pallet_cf_ingress_egress::migrations::PalletMigration<Runtime, Instance3>,
 	pallet_cf_swapping::migrations::PalletMigration<Runtime>,
 	pallet_cf_lp::migrations::PalletMigration<Runtime>,
+	// Initialises the Arbitrum pallets, so it has to run before their own migrations.
+	VersionedMigration<
+		pallet_cf_vaults::Pallet<Runtime, Instance4>,
+		migrations::arbitrum_integration::Migration,
+		0,
+		2,
+	>,
+	pallet_cf_threshold_signature::migrations::PalletMigration<Runtime, Instance4>,
+	pallet_cf_broadcast::migrations::PalletMigration<Runtime, Instance4>,
+	pallet_cf_chain_tracking::migrations::PalletMigration<Runtime, Instance4>,
+	pallet_cf_vaults::migrations::PalletMigration<Runtime, Instance4>,
+	pallet_cf_ingress_egress::migrations::PalletMigration<Runtime, Instance4>,
 );
 
 #[cfg(feature = "runtime-benchmarks")]
@@ -873,13 +1008,18 @@ mod benches {
 		[pallet_cf_governance, Governance]
 		[pallet_cf_tokenholder_governance, TokenholderGovernance]
 		[pallet_cf_vaults, EthereumVault]
+		[pallet_cf_vaults, ArbitrumVault]
 		[pallet_cf_reputation, Reputation]
 		[pallet_cf_threshold_signature, EthereumThresholdSigner]
+		[pallet_cf_threshold_signature, ArbitrumThresholdSigner]
 		[pallet_cf_broadcast, EthereumBroadcaster]
+		[pallet_cf_broadcast, ArbitrumBroadcaster]
 		[pallet_cf_chain_tracking, EthereumChainTracking]
+		[pallet_cf_chain_tracking, ArbitrumChainTracking]
 		[pallet_cf_swapping, Swapping]
 		[pallet_cf_account_roles, AccountRoles]
 		[pallet_cf_ingress_egress, EthereumIngressEgress]
+		[pallet_cf_ingress_egress, ArbitrumIngressEgress]
 		[pallet_cf_lp, LiquidityProvider]
 		[pallet_cf_pools, LiquidityPools]
 	);
@@ -887,6 +1027,7 @@ mod benches {
 
 impl_runtime_apis! {
 	// START custom runtime APIs
+	#[api_version(2)]
 	impl runtime_apis::CustomRuntimeApi<Block> for Runtime {
 		fn cf_is_auction_phase() -> bool {
 			Validator::is_auction_phase()
@@ -1093,7 +1234,7 @@ impl_runtime_apis! {
 
 		fn cf_min_deposit_amount(asset: Asset) -> AssetAmount {
 			use pallet_cf_ingress_egress::MinimumDeposit;
-			use cf_chains::assets::{eth, dot, btc};
+			use cf_chains::assets::{eth, dot, btc, arb};
 
 			match ForeignChain::from(asset) {
 				ForeignChain::Ethereum => MinimumDeposit::<Runtime, EthereumInstance>::get(
@@ -1108,6 +1249,10 @@ impl_runtime_apis! {
 					btc::Asset::try_from(asset)
 						.expect("Conversion must succeed: ForeignChain checked in match clause.")
 				).into(),
+				ForeignChain::Arbitrum => MinimumDeposit::<Runtime, ArbitrumInstance>::get(
+					arb::Asset::try_from(asset)
+						.expect("Conversion must succeed: ForeignChain checked in match clause.")
+				),
 			}
 		}
 
@@ -1143,6 +1288,18 @@ impl_runtime_apis! {
 			pallet_cf_funding::RedemptionTax::<Runtime>::get()
 		}
 
+		fn cf_failure_evidence(
+			chain: ForeignChain,
+			ceremony_id: CeremonyId,
+		) -> Vec<(AccountId, CeremonyFailureEvidence)> {
+			match chain {
+				ForeignChain::Ethereum => EthereumThresholdSigner::failure_evidence(ceremony_id),
+				ForeignChain::Polkadot => PolkadotThresholdSigner::failure_evidence(ceremony_id),
+				ForeignChain::Bitcoin => BitcoinThresholdSigner::failure_evidence(ceremony_id),
+				ForeignChain::Arbitrum => ArbitrumThresholdSigner::failure_evidence(ceremony_id),
+			}
+		}
+
 		/// This should *not* be fully trusted as if the deposits that are pre-witnessed will definitely go through.
 		/// This returns a list of swaps in the requested direction that are pre-witnessed in the current block.
 		fn cf_prewitness_swaps(from: Asset, to: Asset) -> Option<Vec<AssetAmount>> {
@@ -1231,6 +1388,11 @@ impl_runtime_apis! {
 							}) => {
 								all_prewitnessed_swaps.extend(filter_deposit_swaps::<Polkadot, PolkadotInstance>(from, to, deposit_witnesses));
 							}
+							RuntimeCall::ArbitrumIngressEgress(pallet_cf_ingress_egress::Call::process_deposits {
+								deposit_witnesses, ..
+							}) => {
+								all_prewitnessed_swaps.extend(filter_deposit_swaps::<Arbitrum, ArbitrumInstance>(from, to, deposit_witnesses));
+							}
 							RuntimeCall::Swapping(pallet_cf_swapping::Call::ccm_deposit {
 								source_asset, deposit_amount, destination_asset, deposit_metadata, ..
 							}) => {
@@ -0,0 +1,3 @@
+//! Runtime migrations that span several pallets.
+
+pub mod arbitrum_integration;
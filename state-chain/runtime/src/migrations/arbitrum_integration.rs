@@ -0,0 +1,319 @@
+//! Adds Arbitrum to networks that were started without it. The Arbitrum pallets didn't exist at
+//! genesis, so the storage that their genesis config would have set is initialised here, along
+//! with the Arbitrum settings of the Environment pallet, and the runtime safe mode is extended
+//! with the Arbitrum fields.
+//!
+//! The Arbitrum vault doesn't take part in vault rotations until governance enables it with
+//! `enable_arbitrum_integration`.
+
+use crate::{
+	safe_mode::{RuntimeSafeMode, WitnesserCallPermission},
+	ArbitrumInstance, BitcoinInstance, EthereumInstance, PolkadotInstance, Runtime,
+};
+use cf_chains::{
+	arb::{ArbitrumTrackedData, CHAIN_ID_MAINNET, CHAIN_ID_SEPOLIA},
+	Arbitrum, ChainState,
+};
+use cf_primitives::{chains::assets::arb::Asset as ArbAsset, NetworkEnvironment};
+use cf_traits::SafeMode;
+use codec::Decode;
+use frame_support::{
+	traits::{GetStorageVersion, OnRuntimeUpgrade, StorageVersion},
+	weights::Weight,
+};
+use sp_core::H160;
+#[cfg(feature = "try-runtime")]
+use sp_runtime::DispatchError;
+#[cfg(feature = "try-runtime")]
+use sp_std::vec::Vec;
+
+/// Arbitrum's minimum base fee of 0.1 gwei. The chain tracking witnesses update it as soon as the
+/// engines start witnessing Arbitrum.
+const INITIAL_BASE_FEE: u128 = 100_000_000;
+
+/// The same lifetimes as in the genesis configs of the networks. Arbitrum produces a block every
+/// quarter of a second.
+const MAINNET_DEPOSIT_CHANNEL_LIFETIME: u64 = 24 * 3600 * 4;
+const TESTNET_DEPOSIT_CHANNEL_LIFETIME: u64 = 2 * 3600 * 4;
+const DEVELOPMENT_DEPOSIT_CHANNEL_LIFETIME: u64 = 10 * 60 * 4;
+
+const MAINNET_USDC_ADDRESS: [u8; 20] =
+	hex_literal::hex!("af88d065e77c8cC2239327C5EDb3A432268e5831");
+const TESTNET_USDC_ADDRESS: [u8; 20] =
+	hex_literal::hex!("75faf114eafb1BDbe2F0316DF893fd58CE46AA4d");
+const DEVELOPMENT_USDC_ADDRESS: [u8; 20] =
+	hex_literal::hex!("5FC8d32690cc91D4c39d9d3abcBD16989F875707");
+
+/// The runtime safe mode before Arbitrum was added.
+mod old {
+	use super::*;
+
+	#[derive(Decode)]
+	#[cfg_attr(test, derive(codec::Encode))]
+	pub struct RuntimeSafeMode {
+		pub emissions: pallet_cf_emissions::PalletSafeMode,
+		pub funding: pallet_cf_funding::PalletSafeMode,
+		pub swapping: pallet_cf_swapping::PalletSafeMode,
+		pub liquidity_provider: pallet_cf_lp::PalletSafeMode,
+		pub validator: pallet_cf_validator::PalletSafeMode,
+		pub pools: pallet_cf_pools::PalletSafeMode,
+		pub reputation: pallet_cf_reputation::PalletSafeMode,
+		pub vault_ethereum: pallet_cf_vaults::PalletSafeMode<EthereumInstance>,
+		pub vault_bitcoin: pallet_cf_vaults::PalletSafeMode<BitcoinInstance>,
+		pub vault_polkadot: pallet_cf_vaults::PalletSafeMode<PolkadotInstance>,
+		pub broadcast_ethereum: pallet_cf_broadcast::PalletSafeMode<EthereumInstance>,
+		pub broadcast_bitcoin: pallet_cf_broadcast::PalletSafeMode<BitcoinInstance>,
+		pub broadcast_polkadot: pallet_cf_broadcast::PalletSafeMode<PolkadotInstance>,
+		pub witnesser: pallet_cf_witnesser::PalletSafeMode<WitnesserCallPermission>,
+	}
+
+	#[derive(Decode)]
+	#[cfg_attr(test, derive(codec::Encode))]
+	pub struct WitnesserCallPermission {
+		pub governance: bool,
+		pub funding: bool,
+		pub swapping: bool,
+		pub ethereum_broadcast: bool,
+		pub ethereum_chain_tracking: bool,
+		pub ethereum_ingress_egress: bool,
+		pub ethereum_vault: bool,
+		pub polkadot_broadcast: bool,
+		pub polkadot_chain_tracking: bool,
+		pub polkadot_ingress_egress: bool,
+		pub polkadot_vault: bool,
+		pub bitcoin_broadcast: bool,
+		pub bitcoin_chain_tracking: bool,
+		pub bitcoin_ingress_egress: bool,
+		pub bitcoin_vault: bool,
+	}
+}
+
+/// The Arbitrum settings take the values of the Ethereum ones, so that a network that is in safe
+/// mode stays in safe mode for Arbitrum too.
+fn migrate_safe_mode(old: old::RuntimeSafeMode) -> RuntimeSafeMode {
+	fn evm_safe_mode<S: SafeMode>(enabled: bool) -> S {
+		if enabled {
+			S::CODE_GREEN
+		} else {
+			S::CODE_RED
+		}
+	}
+
+	RuntimeSafeMode {
+		emissions: old.emissions,
+		funding: old.funding,
+		swapping: old.swapping,
+		liquidity_provider: old.liquidity_provider,
+		validator: old.validator,
+		pools: old.pools,
+		reputation: old.reputation,
+		vault_arbitrum: evm_safe_mode(old.vault_ethereum.slashing_enabled),
+		vault_ethereum: old.vault_ethereum,
+		vault_bitcoin: old.vault_bitcoin,
+		vault_polkadot: old.vault_polkadot,
+		broadcast_arbitrum: evm_safe_mode(old.broadcast_ethereum.retry_enabled),
+		broadcast_ethereum: old.broadcast_ethereum,
+		broadcast_bitcoin: old.broadcast_bitcoin,
+		broadcast_polkadot: old.broadcast_polkadot,
+		witnesser: match old.witnesser {
+			pallet_cf_witnesser::PalletSafeMode::CodeGreen =>
+				pallet_cf_witnesser::PalletSafeMode::CodeGreen,
+			pallet_cf_witnesser::PalletSafeMode::CodeRed =>
+				pallet_cf_witnesser::PalletSafeMode::CodeRed,
+			pallet_cf_witnesser::PalletSafeMode::CodeAmber(permission) =>
+				pallet_cf_witnesser::PalletSafeMode::CodeAmber(WitnesserCallPermission {
+					governance: permission.governance,
+					funding: permission.funding,
+					swapping: permission.swapping,
+					ethereum_broadcast: permission.ethereum_broadcast,
+					ethereum_chain_tracking: permission.ethereum_chain_tracking,
+					ethereum_ingress_egress: permission.ethereum_ingress_egress,
+					ethereum_vault: permission.ethereum_vault,
+					polkadot_broadcast: permission.polkadot_broadcast,
+					polkadot_chain_tracking: permission.polkadot_chain_tracking,
+					polkadot_ingress_egress: permission.polkadot_ingress_egress,
+					polkadot_vault: permission.polkadot_vault,
+					bitcoin_broadcast: permission.bitcoin_broadcast,
+					bitcoin_chain_tracking: permission.bitcoin_chain_tracking,
+					bitcoin_ingress_egress: permission.bitcoin_ingress_egress,
+					bitcoin_vault: permission.bitcoin_vault,
+					arbitrum_broadcast: permission.ethereum_broadcast,
+					arbitrum_chain_tracking: permission.ethereum_chain_tracking,
+					arbitrum_ingress_egress: permission.ethereum_ingress_egress,
+					arbitrum_vault: permission.ethereum_vault,
+				}),
+		},
+	}
+}
+
+/// Sets the on-chain storage version of a pallet that was added by this migration to the version
+/// of its code, so that none of its migrations run.
+fn init_storage_version<P: GetStorageVersion<CurrentStorageVersion = StorageVersion>>() {
+	P::current_storage_version().put::<P>();
+}
+
+/// Meant to be run as a `VersionedMigration` of the Arbitrum vault pallet from version 0, which is
+/// the version of pallets that don't exist yet.
+pub struct Migration;
+
+impl OnRuntimeUpgrade for Migration {
+	fn on_runtime_upgrade() -> Weight {
+		let (chain_id, usdc_address, deposit_channel_lifetime) =
+			match pallet_cf_environment::ChainflipNetworkEnvironment::<Runtime>::get() {
+				NetworkEnvironment::Mainnet =>
+					(CHAIN_ID_MAINNET, MAINNET_USDC_ADDRESS, MAINNET_DEPOSIT_CHANNEL_LIFETIME),
+				NetworkEnvironment::Testnet =>
+					(CHAIN_ID_SEPOLIA, TESTNET_USDC_ADDRESS, TESTNET_DEPOSIT_CHANNEL_LIFETIME),
+				NetworkEnvironment::Development => (
+					CHAIN_ID_SEPOLIA,
+					DEVELOPMENT_USDC_ADDRESS,
+					DEVELOPMENT_DEPOSIT_CHANNEL_LIFETIME,
+				),
+			};
+
+		// The contract addresses are set when the Arbitrum vault is initialized.
+		pallet_cf_environment::ArbitrumChainId::<Runtime>::put(chain_id);
+		pallet_cf_environment::ArbitrumSupportedAssets::<Runtime>::insert(
+			ArbAsset::ArbUsdc,
+			H160(usdc_address),
+		);
+		pallet_cf_environment::ArbitrumIntegrationEnabled::<Runtime>::put(false);
+
+		if let Err(e) = pallet_cf_environment::RuntimeSafeMode::<Runtime>::translate(
+			|old: Option<old::RuntimeSafeMode>| old.map(migrate_safe_mode),
+		) {
+			log::error!("Failed to migrate the runtime safe mode, setting it to code red: {e:?}");
+			pallet_cf_environment::RuntimeSafeMode::<Runtime>::put(RuntimeSafeMode::CODE_RED);
+		}
+
+		pallet_cf_chain_tracking::CurrentChainState::<Runtime, ArbitrumInstance>::put(
+			ChainState::<Arbitrum> {
+				block_height: 0,
+				tracked_data: ArbitrumTrackedData { base_fee: INITIAL_BASE_FEE, priority_fee: 0 },
+			},
+		);
+		pallet_cf_vaults::KeygenResponseTimeout::<Runtime, ArbitrumInstance>::put(
+			pallet_cf_vaults::KeygenResponseTimeout::<Runtime, EthereumInstance>::get(),
+		);
+		pallet_cf_vaults::KeygenSlashAmount::<Runtime, ArbitrumInstance>::put(
+			pallet_cf_vaults::KeygenSlashAmount::<Runtime, EthereumInstance>::get(),
+		);
+		pallet_cf_threshold_signature::ThresholdSignatureResponseTimeout::<
+			Runtime,
+			ArbitrumInstance,
+		>::put(pallet_cf_threshold_signature::ThresholdSignatureResponseTimeout::<
+			Runtime,
+			EthereumInstance,
+		>::get());
+		pallet_cf_ingress_egress::DepositChannelLifetime::<Runtime, ArbitrumInstance>::put(
+			deposit_channel_lifetime,
+		);
+
+		// The storage version of the vault pallet is set by the `VersionedMigration`.
+		init_storage_version::<pallet_cf_chain_tracking::Pallet<Runtime, ArbitrumInstance>>();
+		init_storage_version::<pallet_cf_threshold_signature::Pallet<Runtime, ArbitrumInstance>>();
+		init_storage_version::<pallet_cf_broadcast::Pallet<Runtime, ArbitrumInstance>>();
+		init_storage_version::<pallet_cf_ingress_egress::Pallet<Runtime, ArbitrumInstance>>();
+
+		Weight::zero()
+	}
+
+	#[cfg(feature = "try-runtime")]
+	fn pre_upgrade() -> Result<Vec<u8>, DispatchError> {
+		frame_support::ensure!(
+			pallet_cf_chain_tracking::CurrentChainState::<Runtime, ArbitrumInstance>::get()
+				.is_none(),
+			"Arbitrum chain tracking is already initialised"
+		);
+		Ok(Vec::new())
+	}
+
+	#[cfg(feature = "try-runtime")]
+	fn post_upgrade(_state: Vec<u8>) -> Result<(), DispatchError> {
+		frame_support::ensure!(
+			pallet_cf_environment::ArbitrumChainId::<Runtime>::get() != 0,
+			"Arbitrum chain id is not set"
+		);
+		frame_support::ensure!(
+			pallet_cf_environment::ArbitrumSupportedAssets::<Runtime>::contains_key(
+				ArbAsset::ArbUsdc
+			),
+			"Arbitrum USDC address is not set"
+		);
+		frame_support::ensure!(
+			pallet_cf_chain_tracking::CurrentChainState::<Runtime, ArbitrumInstance>::get()
+				.is_some(),
+			"Arbitrum chain tracking is not initialised"
+		);
+		frame_support::ensure!(
+			pallet_cf_environment::RuntimeSafeMode::<Runtime>::exists(),
+			"Runtime safe mode is missing"
+		);
+		Ok(())
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use codec::Encode;
+
+	#[test]
+	fn safe_mode_keeps_its_settings_and_copies_the_ethereum_ones_for_arbitrum() {
+		let old = old::RuntimeSafeMode {
+			emissions: SafeMode::CODE_GREEN,
+			funding: SafeMode::CODE_GREEN,
+			swapping: SafeMode::CODE_GREEN,
+			liquidity_provider: SafeMode::CODE_GREEN,
+			validator: SafeMode::CODE_GREEN,
+			pools: SafeMode::CODE_GREEN,
+			reputation: SafeMode::CODE_GREEN,
+			vault_ethereum: SafeMode::CODE_RED,
+			vault_bitcoin: SafeMode::CODE_GREEN,
+			vault_polkadot: SafeMode::CODE_GREEN,
+			broadcast_ethereum: SafeMode::CODE_GREEN,
+			broadcast_bitcoin: SafeMode::CODE_RED,
+			broadcast_polkadot: SafeMode::CODE_GREEN,
+			witnesser: pallet_cf_witnesser::PalletSafeMode::CodeAmber(
+				old::WitnesserCallPermission {
+					governance: true,
+					funding: true,
+					swapping: true,
+					ethereum_broadcast: true,
+					ethereum_chain_tracking: true,
+					ethereum_ingress_egress: true,
+					ethereum_vault: false,
+					polkadot_broadcast: true,
+					polkadot_chain_tracking: true,
+					polkadot_ingress_egress: true,
+					polkadot_vault: true,
+					bitcoin_broadcast: true,
+					bitcoin_chain_tracking: true,
+					bitcoin_ingress_egress: false,
+					bitcoin_vault: true,
+				},
+			),
+		};
+
+		let migrated =
+			migrate_safe_mode(old::RuntimeSafeMode::decode(&mut &old.encode()[..]).unwrap());
+
+		assert_eq!(
+			migrated,
+			RuntimeSafeMode {
+				vault_ethereum: SafeMode::CODE_RED,
+				vault_arbitrum: SafeMode::CODE_RED,
+				broadcast_bitcoin: SafeMode::CODE_RED,
+				witnesser: pallet_cf_witnesser::PalletSafeMode::CodeAmber(
+					WitnesserCallPermission {
+						ethereum_vault: false,
+						bitcoin_ingress_egress: false,
+						arbitrum_vault: false,
+						..WitnesserCallPermission::allow_all()
+					}
+				),
+				..RuntimeSafeMode::CODE_GREEN
+			}
+		);
+	}
+}
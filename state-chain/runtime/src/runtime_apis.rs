@@ -5,8 +5,8 @@ use cf_amm::{
 };
 use cf_chains::{eth::Address as EthereumAddress, ForeignChainAddress};
 use cf_primitives::{
-	AccountRole, Asset, AssetAmount, EpochIndex, ForeignChain, NetworkEnvironment, SemVer,
-	SwapOutput,
+	AccountRole, Asset, AssetAmount, CeremonyFailureEvidence, CeremonyId, EpochIndex, ForeignChain,
+	NetworkEnvironment, SemVer, SwapOutput,
 };
 use codec::{Decode, Encode};
 use core::ops::Range;
@@ -75,6 +75,7 @@ pub struct LiquidityProviderInfo {
 
 decl_runtime_apis!(
 	/// Definition for all runtime API interfaces.
+	#[api_version(2)]
 	pub trait CustomRuntimeApi {
 		/// Returns true if the current phase is the auction phase.
 		fn cf_is_auction_phase() -> bool;
@@ -131,6 +132,12 @@ decl_runtime_apis!(
 		fn cf_liquidity_provider_info(account_id: AccountId32) -> Option<LiquidityProviderInfo>;
 		fn cf_account_role(account_id: AccountId32) -> Option<AccountRole>;
 		fn cf_redemption_tax() -> AssetAmount;
+		/// The evidence submitted by the participants of a failed ceremony for the given chain.
+		#[api_version(2)]
+		fn cf_failure_evidence(
+			chain: ForeignChain,
+			ceremony_id: CeremonyId,
+		) -> Vec<(AccountId32, CeremonyFailureEvidence)>;
 		fn cf_network_environment() -> NetworkEnvironment;
 	}
 );
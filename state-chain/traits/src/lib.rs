@@ -608,6 +608,31 @@ pub trait CeremonyIdProvider {
 	fn increment_ceremony_id() -> CeremonyId;
 }
 
+/// Something that knows about the key ceremonies (keygen, key handover and share refresh) that are
+/// in progress.
+pub trait KeyCeremonyParticipants {
+	type ValidatorId;
+
+	/// The participants of the key ceremony with this id, if it is in progress.
+	fn pending_ceremony_participants(
+		ceremony_id: CeremonyId,
+	) -> Option<BTreeSet<Self::ValidatorId>>;
+}
+
+/// Checks signatures made with the peer keys (the ed25519 keys that nodes use for p2p) that
+/// validators registered.
+pub trait PeerSignatureVerifier {
+	type ValidatorId;
+
+	/// Whether the signature over the message was made with the validator's peer key. Always
+	/// `false` if the validator hasn't registered a peer key.
+	fn verify_peer_signature(
+		validator_id: &Self::ValidatorId,
+		message: &[u8],
+		signature: &[u8; 64],
+	) -> bool;
+}
+
 /// Something that is able to provide block authorship slots that were missed.
 pub trait MissedAuthorshipSlots {
 	/// Get a list of slots that were missed.
@@ -19,10 +19,12 @@ pub mod epoch_info;
 pub mod eth_environment_provider;
 pub mod fee_payment;
 pub mod funding_info;
+pub mod key_ceremony_participants;
 pub mod key_provider;
 pub mod lp_balance;
 pub mod offence_reporting;
 pub mod on_account_funded;
+pub mod peer_signature_verifier;
 pub mod qualify_node;
 pub mod reputation_resetter;
 pub mod safe_mode;
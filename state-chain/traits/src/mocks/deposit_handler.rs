@@ -62,6 +62,7 @@ impl<C: Chain, T: Chainflip> MockDepositHandler<C, T> {
 					PolkadotAccountId::from_aliased([channel_id as u8; 32]),
 				),
 				ForeignChain::Bitcoin => todo!("Bitcoin address"),
+				ForeignChain::Arbitrum => ForeignChainAddress::Arb([channel_id as u8; 20].into()),
 			},
 		)
 	}
@@ -0,0 +1,31 @@
+use cf_primitives::CeremonyId;
+use sp_std::collections::btree_set::BTreeSet;
+
+use super::{MockPallet, MockPalletStorage};
+use crate::KeyCeremonyParticipants;
+
+pub struct MockKeyCeremonyParticipants;
+
+impl MockPallet for MockKeyCeremonyParticipants {
+	const PREFIX: &'static [u8] = b"MockKeyCeremonyParticipants::";
+}
+
+const PARTICIPANTS: &[u8] = b"PARTICIPANTS";
+
+impl MockKeyCeremonyParticipants {
+	pub fn start_ceremony(ceremony_id: CeremonyId, participants: BTreeSet<u64>) {
+		Self::put_storage(PARTICIPANTS, ceremony_id, participants);
+	}
+
+	pub fn end_ceremony(ceremony_id: CeremonyId) {
+		Self::take_storage::<_, BTreeSet<u64>>(PARTICIPANTS, ceremony_id);
+	}
+}
+
+impl KeyCeremonyParticipants for MockKeyCeremonyParticipants {
+	type ValidatorId = u64;
+
+	fn pending_ceremony_participants(ceremony_id: CeremonyId) -> Option<BTreeSet<u64>> {
+		Self::get_storage(PARTICIPANTS, ceremony_id)
+	}
+}
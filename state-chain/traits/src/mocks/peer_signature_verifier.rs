@@ -0,0 +1,25 @@
+use codec::Encode;
+
+use crate::PeerSignatureVerifier;
+
+/// Accepts the signatures made with [MockPeerSignatureVerifier::sign], as if every validator had
+/// registered a peer key.
+pub struct MockPeerSignatureVerifier;
+
+impl MockPeerSignatureVerifier {
+	/// The mock signature of a validator over a message.
+	pub fn sign(validator_id: u64, message: &[u8]) -> [u8; 64] {
+		let mut signature = [0; 64];
+		signature[..32]
+			.copy_from_slice(&(validator_id, message).using_encoded(sp_io::hashing::blake2_256));
+		signature
+	}
+}
+
+impl PeerSignatureVerifier for MockPeerSignatureVerifier {
+	type ValidatorId = u64;
+
+	fn verify_peer_signature(validator_id: &u64, message: &[u8], signature: &[u8; 64]) -> bool {
+		Self::sign(*validator_id, message) == *signature
+	}
+}
@@ -7,9 +7,10 @@ use crate::ArrayCollect;
 use async_channel::{unbounded, Receiver, Sender};
 use lazy_static;
 use prometheus::{
-	register_int_counter_vec_with_registry, register_int_counter_with_registry,
-	register_int_gauge_vec_with_registry, register_int_gauge_with_registry, IntCounter,
-	IntCounterVec, IntGauge, IntGaugeVec, Opts, Registry,
+	register_histogram_vec_with_registry, register_int_counter_vec_with_registry,
+	register_int_counter_with_registry, register_int_gauge_vec_with_registry,
+	register_int_gauge_with_registry, HistogramOpts, HistogramVec, IntCounter, IntCounterVec,
+	IntGauge, IntGaugeVec, Opts, Registry,
 };
 use serde::Deserialize;
 use std::{collections::HashSet, net::IpAddr};
@@ -148,6 +149,38 @@ impl<const N: usize> IntCounterVecWrapper<N> {
 		}
 	}
 }
+#[derive(Clone)]
+/// wrapper used to enforce the correct number of labels when interacting with a HistogramVec
+pub struct HistogramVecWrapper<const N: usize> {
+	pub prom_metric: HistogramVec,
+}
+
+impl<const N: usize> HistogramVecWrapper<N> {
+	fn new(
+		name: &str,
+		help: &str,
+		labels: &[&str; N],
+		buckets: Vec<f64>,
+		registry: &REGISTRY,
+	) -> HistogramVecWrapper<N> {
+		HistogramVecWrapper {
+			prom_metric: register_histogram_vec_with_registry!(
+				HistogramOpts::new(name, help).buckets(buckets),
+				labels,
+				registry
+			)
+			.expect("A duplicate metric collector has already been registered."),
+		}
+	}
+
+	pub fn observe(&self, labels: &[&str; N], val: f64) {
+		match self.prom_metric.get_metric_with_label_values(labels) {
+			Ok(m) => m.observe(val),
+			Err(e) => tracing::error!("Failed to get the metric: {}", e),
+		}
+	}
+}
+
 macro_rules! build_gauge_vec {
 	($metric_ident:ident, $name:literal, $help:literal, $labels:tt) => {
 		lazy_static::lazy_static!{
@@ -164,6 +197,14 @@ macro_rules! build_counter_vec {
 	}
 }
 
+macro_rules! build_histogram_vec {
+	($metric_ident:ident, $name:literal, $help:literal, $labels:tt, $buckets:expr) => {
+		lazy_static::lazy_static!{
+			pub static ref $metric_ident: HistogramVecWrapper<{ $labels.len() }> = HistogramVecWrapper::new($name, $help, &$labels, $buckets, &REGISTRY);
+		}
+	}
+}
+
 macro_rules! build_gauge_vec_struct {
 	($metric_ident:ident, $struct_ident:ident, $name:literal, $help:literal, $drop:expr, $labels:tt) => {
 		build_gauge_vec!($metric_ident, $name, $help, $labels);
@@ -405,6 +446,42 @@ macro_rules! build_counter_vec_struct {
 	};
 }
 
+/// Histograms aggregate observations across ceremonies, so unlike the other structs
+/// their labels are never deleted once the struct is dropped
+macro_rules! build_histogram_vec_struct {
+	($metric_ident:ident, $struct_ident:ident, $name:literal, $help:literal, $labels:tt, $const_labels:tt, $buckets:expr) => {
+		build_histogram_vec!($metric_ident, $name, $help, $labels, $buckets);
+
+		#[derive(Clone)]
+		pub struct $struct_ident {
+			metric: &'static $metric_ident,
+			const_labels: [String; { $const_labels.len() }],
+		}
+		impl $struct_ident {
+			pub fn new(
+				metric: &'static $metric_ident,
+				const_labels: [String; { $const_labels.len() }],
+			) -> $struct_ident {
+				$struct_ident { metric, const_labels }
+			}
+
+			pub fn observe(
+				&self,
+				non_const_labels: &[&str; { $labels.len() - $const_labels.len() }],
+				val: f64,
+			) {
+				let labels: [&str; { $labels.len() }] = self
+					.const_labels
+					.iter()
+					.map(|s| s.as_str())
+					.chain(*non_const_labels)
+					.collect_array();
+				self.metric.observe(&labels, val);
+			}
+		}
+	};
+}
+
 lazy_static::lazy_static! {
 	static ref REGISTRY: Registry = Registry::new();
 	pub static ref DELETE_METRIC_CHANNEL: (Sender<DeleteMetricCommand>, Receiver<DeleteMetricCommand>) = unbounded::<DeleteMetricCommand>();
@@ -435,10 +512,40 @@ build_counter_vec!(
 	"Count all the rpc calls made by the retrier, it counts every single call even if it is the same made multiple times",
 	["client","rpc_method"]
 );
+build_counter_vec!(
+	RPC_RETRIER_DISAGREEMENTS,
+	"rpc_disagreements",
+	"Count the cross-checked rpc calls for which the primary and backup rpcs returned different responses",
+	["client", "rpc_method"]
+);
+build_counter_vec!(
+	RPC_RETRIER_CROSS_CHECK_FAILURES,
+	"rpc_cross_check_failures",
+	"Count the cross-checked rpc calls for which the primary and backup rpcs kept returning different responses, so the primary's response was used",
+	["client", "rpc_method"]
+);
+build_counter_vec!(
+	RPC_RETRIER_RATE_LIMITED,
+	"rpc_rate_limited",
+	"Count the rpc calls the endpoint rejected because of its rate limit",
+	["client", "rpc_method"]
+);
+build_counter_vec!(
+	RPC_THROTTLED_REQUESTS,
+	"rpc_throttled_requests",
+	"Count the rpc calls delayed to stay within the rate limit and daily budget of the endpoint",
+	["client", "endpoint"]
+);
+build_gauge_vec!(
+	RPC_BUDGET_REMAINING,
+	"rpc_budget_remaining",
+	"The number of rpc calls left in the daily budget of the endpoint",
+	["client", "endpoint"]
+);
 build_counter_vec!(
 	P2P_MONITOR_EVENT,
 	"p2p_monitor_event",
-	"Count the number of events observed by the zmq connection monitor",
+	"Count the number of events observed by the p2p connection monitor, including the relaying of messages",
 	["event_type"]
 );
 build_counter_vec!(
@@ -447,6 +554,13 @@ build_counter_vec!(
 	"Count all the bad p2p msgs received by the engine and labels them by the reason they got discarded",
 	["reason"]
 );
+build_histogram_vec!(
+	CHAIN_SOURCE_REORG_DEPTH,
+	"chain_source_reorg_depth",
+	"Distribution of the depth of the reorgs detected by the witnessing chain sources",
+	["chain_source"],
+	vec![1.0, 2.0, 3.0, 4.0, 6.0, 8.0, 12.0, 16.0, 32.0, 64.0]
+);
 build_counter_vec_struct!(
 	CEREMONY_PROCESSED_MSG,
 	CeremonyProcessedMsgDrop,
@@ -508,6 +622,33 @@ build_counter_vec_struct!(
 	["chain", "stage"],
 	["chain"]
 );
+build_histogram_vec_struct!(
+	STAGE_DURATION_SECONDS,
+	StageDurationSecondsNotDrop,
+	"stage_duration_seconds",
+	"Distribution of the time spent in each phase of a stage in seconds",
+	["chain", "ceremony_type", "stage", "phase"], //phase can be either receiving or processing
+	["chain", "ceremony_type"],
+	vec![0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0, 20.0, 30.0, 60.0]
+);
+build_counter_vec_struct!(
+	STAGE_PEER_TIMEOUT,
+	StagePeerTimeoutNotDrop,
+	"stage_peer_timeout",
+	"Count the number of times a peer failed to deliver its message for a stage before the timeout",
+	false,
+	["chain", "ceremony_type", "stage", "peer"],
+	["chain", "ceremony_type"]
+);
+build_counter_vec_struct!(
+	CEREMONY_OUTCOME,
+	CeremonyOutcomeNotDrop,
+	"ceremony_outcome",
+	"Count the number of authorised ceremonies by their outcome, either success or failure",
+	false,
+	["chain", "ceremony_type", "outcome"],
+	["chain", "ceremony_type"]
+);
 
 /// structure containing the metrics used during a ceremony
 #[derive(Clone)]
@@ -519,6 +660,9 @@ pub struct CeremonyMetrics {
 	pub stage_duration: StageDurationDrop,
 	pub stage_failing: StageFailingNotDrop,
 	pub stage_completing: StageCompletingNotDrop,
+	pub stage_duration_seconds: StageDurationSecondsNotDrop,
+	pub peer_timeout: StagePeerTimeoutNotDrop,
+	pub outcome: CeremonyOutcomeNotDrop,
 }
 impl CeremonyMetrics {
 	pub fn new(ceremony_id: u64, chain_name: &str, ceremony_type: &str) -> Self {
@@ -537,14 +681,23 @@ impl CeremonyMetrics {
 			),
 			missing_messages: CeremonyTimeoutMissingMsgDrop::new(
 				&CEREMONY_TIMEOUT_MISSING_MSG,
-				[chain_name.clone(), ceremony_id.clone(), ceremony_type],
+				[chain_name.clone(), ceremony_id.clone(), ceremony_type.clone()],
 			),
 			stage_duration: StageDurationDrop::new(
 				&STAGE_DURATION,
 				[chain_name.clone(), ceremony_id],
 			),
 			stage_failing: StageFailingNotDrop::new(&STAGE_FAILING, [chain_name.clone()]),
-			stage_completing: StageCompletingNotDrop::new(&STAGE_COMPLETING, [chain_name]),
+			stage_completing: StageCompletingNotDrop::new(&STAGE_COMPLETING, [chain_name.clone()]),
+			stage_duration_seconds: StageDurationSecondsNotDrop::new(
+				&STAGE_DURATION_SECONDS,
+				[chain_name.clone(), ceremony_type.clone()],
+			),
+			peer_timeout: StagePeerTimeoutNotDrop::new(
+				&STAGE_PEER_TIMEOUT,
+				[chain_name.clone(), ceremony_type.clone()],
+			),
+			outcome: CeremonyOutcomeNotDrop::new(&CEREMONY_OUTCOME, [chain_name, ceremony_type]),
 		}
 	}
 }
@@ -746,6 +899,7 @@ stage_failing{chain="Chain1",reason="NotEnoughMessages",stage="stage3"} 1
 "#).await;
 
 				check_deleted_metrics();
+				check_aggregated_ceremony_metrics();
 
 				Ok(())
 			}
@@ -773,6 +927,41 @@ stage_failing{chain="Chain1",reason="NotEnoughMessages",stage="stage3"} 1
 		metric
 	}
 
+	fn check_aggregated_ceremony_metrics() {
+		{
+			let mut metrics = CeremonyMetrics::new(8, "Chain2", "Signing");
+			metrics.stage_duration_seconds.observe(&["stage1", "receiving"], 0.5);
+			metrics.stage_duration_seconds.observe(&["stage1", "receiving"], 12.0);
+			metrics.peer_timeout.inc(&["stage1", "peer1"]);
+			metrics.outcome.inc(&["failure"]);
+		}
+
+		// These metrics aggregate across ceremonies, so they must survive the end of a ceremony
+		delete_labels(&collect_metric_to_delete());
+		let histogram = STAGE_DURATION_SECONDS
+			.prom_metric
+			.get_metric_with_label_values(&["Chain2", "Signing", "stage1", "receiving"])
+			.unwrap();
+		assert_eq!(histogram.get_sample_count(), 2);
+		assert_eq!(histogram.get_sample_sum(), 12.5);
+		assert_eq!(
+			STAGE_PEER_TIMEOUT
+				.prom_metric
+				.get_metric_with_label_values(&["Chain2", "Signing", "stage1", "peer1"])
+				.unwrap()
+				.get(),
+			1
+		);
+		assert_eq!(
+			CEREMONY_OUTCOME
+				.prom_metric
+				.get_metric_with_label_values(&["Chain2", "Signing", "failure"])
+				.unwrap()
+				.get(),
+			1
+		);
+	}
+
 	fn check_deleted_metrics() {
 		assert!(STAGE_DURATION
 			.prom_metric